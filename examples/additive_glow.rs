@@ -0,0 +1,230 @@
+use std::sync::Mutex;
+
+use bevy::{
+    asset::embedded_asset, core_pipeline::fullscreen_vertex_shader::fullscreen_shader_vertex_state,
+    image::BevyDefault, prelude::*,
+};
+use bevy_render::{
+    render_resource::{
+        binding_types::uniform_buffer_sized, encase, BindGroupEntries, BindGroupLayout,
+        BindGroupLayoutEntries, BufferInitDescriptor, BufferUsages, FragmentState,
+        MultisampleState, PrimitiveState, RenderPassDescriptor, RenderPipelineDescriptor,
+        ShaderStages, SpecializedRenderPipeline, TextureFormat,
+    },
+    renderer::RenderDevice,
+    view::ExtractedView,
+};
+
+use gigs::*;
+use input::{
+    JobBlendMode, JobInputItem, JobRenderPipeline, JobViewTarget, JobViewTargetFormatPlugin,
+    ViewTargetBlendKey,
+};
+use meta::JobPriority;
+
+/// The keys the glow pipeline was specialized for, to check that each blend mode got
+/// its own pipeline.
+static SPECIALIZED: Mutex<Vec<ViewTargetBlendKey>> = Mutex::new(Vec::new());
+
+fn main() -> AppExit {
+    let mut app = App::new();
+
+    app.add_plugins((
+        DefaultPlugins,
+        GraphicsJobsPlugin::default(),
+        JobViewTargetFormatPlugin::<GlowPipeline>::default(),
+    ))
+    .init_graphics_job::<GlowJob>();
+
+    embedded_asset!(app, "examples", "additive_glow.wgsl");
+
+    app.add_systems(Startup, setup_scene)
+        .add_systems(Update, spawn_glows);
+
+    app.run()
+}
+
+fn setup_scene(mut commands: Commands) {
+    // as in the `vignette` example, the camera doesn't clear its target, since the
+    // glow jobs draw before the camera renders
+    commands.spawn((
+        Camera2d,
+        Camera {
+            hdr: true,
+            clear_color: ClearColorConfig::None,
+            ..Default::default()
+        },
+        Msaa::Off,
+    ));
+}
+
+fn spawn_glows(camera: Single<Entity, With<Camera>>, time: Res<Time>, mut commands: Commands) {
+    // the backdrop replaces last frame's contents, so it runs first, and the glows
+    // accumulate on top of it. Where they overlap, red, green and blue add up to white.
+    let angle = time.elapsed_secs() * 0.5;
+    let glows = [
+        (Vec3::new(1.0, 0.1, 0.1), angle),
+        (
+            Vec3::new(0.1, 1.0, 0.1),
+            angle + std::f32::consts::TAU / 3.0,
+        ),
+        (
+            Vec3::new(0.1, 0.1, 1.0),
+            angle - std::f32::consts::TAU / 3.0,
+        ),
+    ];
+
+    commands.spawn((
+        GlowJob {
+            center: Vec2::splat(0.5),
+            radius: 2.0,
+            color: Vec3::new(0.05, 0.05, 0.08),
+        },
+        JobBlendMode::Replace,
+        JobPriority::critical(),
+        JobViewTarget::new(*camera),
+        JobRenderPipeline::<GlowPipeline>(ViewTargetBlendKey::new(TextureFormat::bevy_default())),
+    ));
+    for (color, angle) in glows {
+        commands
+            .spawn((
+                GlowJob {
+                    center: Vec2::splat(0.5) + Vec2::from_angle(angle) * 0.12,
+                    radius: 0.25,
+                    color,
+                },
+                JobBlendMode::Additive,
+                JobViewTarget::new(*camera),
+                JobRenderPipeline::<GlowPipeline>(ViewTargetBlendKey::new(
+                    TextureFormat::bevy_default(),
+                )),
+            ))
+            .observe(check_specialized_keys);
+    }
+}
+
+fn check_specialized_keys(trigger: Trigger<JobComplete>, mut checked: Local<bool>) {
+    if *checked || trigger.event().0.is_err() {
+        return;
+    }
+    *checked = true;
+
+    // the key each job was spawned with is only a placeholder, which is replaced with
+    // the camera's HDR format and the job's blend mode before it's specialized
+    let specialized = SPECIALIZED.lock().unwrap();
+    for blend_mode in [JobBlendMode::Replace, JobBlendMode::Additive] {
+        assert!(specialized.contains(&ViewTargetBlendKey {
+            format: TextureFormat::Rgba16Float,
+            blend_mode,
+        }));
+    }
+    println!("Specialized the glow pipeline for {specialized:?}");
+}
+
+/// Draws a soft circle, centered in UV space, into a camera's main texture.
+#[derive(Clone, Component)]
+struct GlowJob {
+    center: Vec2,
+    radius: f32,
+    color: Vec3,
+}
+
+#[derive(Resource)]
+struct GlowPipeline {
+    layout: BindGroupLayout,
+    shader: Handle<Shader>,
+}
+
+impl FromWorld for GlowPipeline {
+    fn from_world(world: &mut World) -> Self {
+        let layout = world.resource::<RenderDevice>().create_bind_group_layout(
+            "additive_glow_layout",
+            &BindGroupLayoutEntries::single(
+                ShaderStages::FRAGMENT,
+                uniform_buffer_sized(false, None),
+            ),
+        );
+        let shader = world
+            .resource::<AssetServer>()
+            .load("embedded://additive_glow/additive_glow.wgsl");
+
+        Self { layout, shader }
+    }
+}
+
+impl SpecializedRenderPipeline for GlowPipeline {
+    type Key = ViewTargetBlendKey;
+
+    fn specialize(&self, key: Self::Key) -> RenderPipelineDescriptor {
+        SPECIALIZED.lock().unwrap().push(key);
+
+        RenderPipelineDescriptor {
+            label: Some("additive_glow_pipeline".into()),
+            layout: vec![self.layout.clone()],
+            push_constant_ranges: Vec::new(),
+            vertex: fullscreen_shader_vertex_state(),
+            primitive: PrimitiveState::default(),
+            depth_stencil: None,
+            multisample: MultisampleState::default(),
+            fragment: Some(FragmentState {
+                shader: self.shader.clone(),
+                shader_defs: Vec::new(),
+                entry_point: "fragment".into(),
+                // the blend state always matches the job's blend mode
+                targets: vec![Some(key.color_target())],
+            }),
+            zero_initialize_workgroup_memory: false,
+        }
+    }
+}
+
+impl GraphicsJob for GlowJob {
+    type In = (JobViewTarget, JobRenderPipeline<GlowPipeline>);
+
+    fn run(
+        &self,
+        world: &World,
+        context: &mut JobRunContext,
+        (target, pipeline): JobInputItem<Self, Self::In>,
+    ) -> Result<(), JobError> {
+        let Some(view) = target.view.get::<ExtractedView>() else {
+            return Ok(());
+        };
+
+        let mut contents = encase::UniformBuffer::new(Vec::new());
+        contents
+            .write(&[
+                self.center.extend(self.radius).extend(0.0),
+                self.color.extend(1.0),
+            ])
+            .map_err(|_| JobError::ExecutionFailed)?;
+        let render_device = context.render_device().clone();
+        let uniforms = render_device.create_buffer_with_data(&BufferInitDescriptor {
+            label: Some("additive_glow_uniforms"),
+            contents: &contents.into_inner(),
+            usage: BufferUsages::UNIFORM,
+        });
+        let bind_group = render_device.create_bind_group(
+            "additive_glow_bind_group",
+            &world.resource::<GlowPipeline>().layout,
+            &BindGroupEntries::single(uniforms.as_entire_binding()),
+        );
+
+        // the attachment's load op matches the job's blend mode
+        let mut render_pass = context.begin_render_pass(&RenderPassDescriptor {
+            label: Some("additive_glow_pass"),
+            color_attachments: &[Some(target.color_attachment())],
+            depth_stencil_attachment: None,
+            timestamp_writes: None,
+            occlusion_query_set: None,
+        });
+
+        let viewport = view.viewport.as_vec4();
+        render_pass.set_viewport(viewport.x, viewport.y, viewport.z, viewport.w, 0.0, 1.0);
+        render_pass.set_pipeline(pipeline);
+        render_pass.set_bind_group(0, &bind_group, &[]);
+        render_pass.draw(0..3, 0..1);
+
+        Ok(())
+    }
+}
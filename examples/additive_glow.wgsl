@@ -0,0 +1,17 @@
+#import bevy_core_pipeline::fullscreen_vertex_shader::FullscreenVertexOutput
+
+struct Glow {
+    // the center in UV space, and the radius
+    center_radius: vec4<f32>,
+    color: vec4<f32>,
+}
+
+@group(0) @binding(0) var<uniform> glow: Glow;
+
+@fragment
+fn fragment(in: FullscreenVertexOutput) -> @location(0) vec4<f32> {
+    // outside the radius this is black, which leaves the target unchanged when blending
+    // additively, and clears it when replacing
+    let falloff = 1.0 - smoothstep(0.0, glow.center_radius.z, distance(in.uv, glow.center_radius.xy));
+    return vec4(glow.color.rgb * falloff * 2.0, 1.0);
+}
@@ -0,0 +1,103 @@
+use bevy::{
+    asset::RenderAssetUsages,
+    prelude::*,
+    render::render_resource::{Extent3d, TextureDimension, TextureFormat},
+};
+
+use gigs::*;
+use input::{JobAsset, JobAssetPublished, JobInputItem};
+
+const SIZE: u32 = 256;
+const ITERATIONS: u32 = 64;
+
+fn main() -> AppExit {
+    let mut app = App::new();
+
+    app.add_plugins((DefaultPlugins, GraphicsJobsPlugin::default()))
+        .init_graphics_job::<BakeFractalJob>()
+        .add_systems(Startup, spawn_bake)
+        .add_systems(Update, log_published_images);
+
+    app.run()
+}
+
+fn spawn_bake(mut images: ResMut<Assets<Image>>, mut commands: Commands) {
+    commands.spawn(Camera2d);
+
+    // the sprite shows a flat placeholder until the baked image replaces it
+    let output = JobAsset::reserve(&mut images, Image::default());
+    commands.spawn(Sprite {
+        image: output.handle.clone(),
+        custom_size: Some(Vec2::splat(SIZE as f32 * 2.0)),
+        ..Default::default()
+    });
+
+    commands.spawn((
+        BakeFractalJob {
+            center: Vec2::new(-0.75, 0.0),
+            extent: 2.5,
+        },
+        output,
+    ));
+}
+
+fn log_published_images(mut published: EventReader<JobAssetPublished<Image>>) {
+    for published in published.read() {
+        info!(
+            "job {} published {:?}",
+            published.job,
+            published.handle.id()
+        );
+    }
+}
+
+/// Bakes a Mandelbrot set into an image on the render thread, and publishes it as the
+/// job's asset.
+#[derive(Clone, Component)]
+struct BakeFractalJob {
+    center: Vec2,
+    extent: f32,
+}
+
+impl GraphicsJob for BakeFractalJob {
+    type In = JobAsset<Image>;
+
+    fn run(
+        &self,
+        _world: &World,
+        context: &mut JobRunContext,
+        (): JobInputItem<Self, Self::In>,
+    ) -> Result<(), JobError> {
+        let mut data = Vec::with_capacity((SIZE * SIZE * 4) as usize);
+        for y in 0..SIZE {
+            for x in 0..SIZE {
+                let uv = Vec2::new(x as f32, y as f32) / SIZE as f32 - 0.5;
+                let c = self.center + uv * self.extent;
+                let mut z = Vec2::ZERO;
+                let mut iterations = 0;
+                while iterations < ITERATIONS && z.length_squared() < 4.0 {
+                    z = Vec2::new(z.x * z.x - z.y * z.y, 2.0 * z.x * z.y) + c;
+                    iterations += 1;
+                }
+
+                let shade = iterations as f32 / ITERATIONS as f32;
+                let color = Color::hsl(240.0 - shade * 200.0, 0.8, shade * 0.6).to_srgba();
+                data.extend_from_slice(&color.to_u8_array());
+            }
+        }
+
+        context.set_result(Image::new(
+            Extent3d {
+                width: SIZE,
+                height: SIZE,
+                depth_or_array_layers: 1,
+            },
+            TextureDimension::D2,
+            data,
+            TextureFormat::Rgba8UnormSrgb,
+            RenderAssetUsages::default(),
+        ));
+
+        Ok(())
+    }
+}
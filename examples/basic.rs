@@ -1,7 +1,7 @@
 use bevy::prelude::*;
-use bevy_render::{render_resource::CommandEncoder, renderer::RenderDevice};
 use gigs::{
-    input::JobInputItem, GraphicsJob, GraphicsJobsPlugin, InitGraphicsJobExt, JobComplete, JobError,
+    input::JobInputItem, GraphicsJob, GraphicsJobsPlugin, InitGraphicsJobExt, JobComplete,
+    JobError, JobRunContext,
 };
 
 fn main() -> AppExit {
@@ -27,8 +27,7 @@ impl GraphicsJob for BasicJob {
     fn run(
         &self,
         _world: &World,
-        _render_device: &RenderDevice,
-        _command_encoder: &mut CommandEncoder,
+        _context: &mut JobRunContext,
         (): JobInputItem<Self, Self::In>,
     ) -> Result<(), JobError> {
         println!("Job running!");
@@ -0,0 +1,214 @@
+use bevy::{
+    asset::{embedded_asset, RenderAssetUsages},
+    core_pipeline::fullscreen_vertex_shader::fullscreen_shader_vertex_state,
+    prelude::*,
+};
+use bevy_render::{
+    render_resource::{
+        binding_types::{sampler, texture_2d},
+        BindGroupEntries, BindGroupLayout, BindGroupLayoutEntries, ColorTargetState, ColorWrites,
+        Extent3d, FragmentState, LoadOp, MultisampleState, Operations, PrimitiveState,
+        RenderPassColorAttachment, RenderPassDescriptor, RenderPipeline, RenderPipelineDescriptor,
+        SamplerBindingType, ShaderStages, SpecializedRenderPipeline, StoreOp, TextureDescriptor,
+        TextureDimension, TextureFormat, TextureSampleType, TextureUsages, TextureView,
+        TextureViewDescriptor,
+    },
+    renderer::RenderDevice,
+};
+
+use gigs::*;
+use input::{JobImageView, JobInputItem, JobLinearSampler, JobRenderPipelines};
+
+const SIZE: u32 = 256;
+const FORMAT: TextureFormat = TextureFormat::Rgba8Unorm;
+
+fn main() -> AppExit {
+    let mut app = App::new();
+
+    app.add_plugins((DefaultPlugins, GraphicsJobsPlugin::default()))
+        .init_graphics_job::<BlurJob>();
+
+    embedded_asset!(app, "examples", "blur.wgsl");
+
+    app.add_systems(Startup, setup_scene);
+
+    app.run()
+}
+
+fn setup_scene(mut images: ResMut<Assets<Image>>, mut commands: Commands) {
+    let mut image = Image::new_fill(
+        Extent3d {
+            width: SIZE,
+            height: SIZE,
+            depth_or_array_layers: 1,
+        },
+        TextureDimension::D2,
+        &[0, 0, 0, 255],
+        FORMAT,
+        RenderAssetUsages::RENDER_WORLD,
+    );
+    image.texture_descriptor.usage |= TextureUsages::RENDER_ATTACHMENT;
+    let image = images.add(image);
+
+    commands.spawn(Camera2d);
+    commands.spawn(Sprite {
+        image: image.clone(),
+        custom_size: Some(Vec2::splat(512.0)),
+        ..Default::default()
+    });
+
+    // each pass uses its own specialization of the same pipeline, in this order
+    commands.spawn((
+        BlurJob,
+        JobImageView::mip_level(image, 0),
+        JobRenderPipelines::<BlurPipeline, 3>([
+            BlurPass::Pattern,
+            BlurPass::Horizontal,
+            BlurPass::Vertical,
+        ]),
+    ));
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+enum BlurPass {
+    /// Draws the pattern to blur.
+    Pattern,
+    Horizontal,
+    Vertical,
+}
+
+#[derive(Resource)]
+struct BlurPipeline {
+    layout: BindGroupLayout,
+    shader: Handle<Shader>,
+}
+
+impl FromWorld for BlurPipeline {
+    fn from_world(world: &mut World) -> Self {
+        let layout = world.resource::<RenderDevice>().create_bind_group_layout(
+            "blur_layout",
+            &BindGroupLayoutEntries::sequential(
+                ShaderStages::FRAGMENT,
+                (
+                    texture_2d(TextureSampleType::Float { filterable: true }),
+                    sampler(SamplerBindingType::Filtering),
+                ),
+            ),
+        );
+        let shader = world
+            .resource::<AssetServer>()
+            .load("embedded://blur/blur.wgsl");
+
+        Self { layout, shader }
+    }
+}
+
+impl SpecializedRenderPipeline for BlurPipeline {
+    type Key = BlurPass;
+
+    fn specialize(&self, pass: Self::Key) -> RenderPipelineDescriptor {
+        let (layout, entry_point, shader_defs) = match pass {
+            BlurPass::Pattern => (Vec::new(), "prepass", Vec::new()),
+            BlurPass::Horizontal => (vec![self.layout.clone()], "blur", vec!["HORIZONTAL".into()]),
+            BlurPass::Vertical => (vec![self.layout.clone()], "blur", Vec::new()),
+        };
+
+        RenderPipelineDescriptor {
+            label: Some("blur_pipeline".into()),
+            layout,
+            push_constant_ranges: Vec::new(),
+            vertex: fullscreen_shader_vertex_state(),
+            primitive: PrimitiveState::default(),
+            depth_stencil: None,
+            multisample: MultisampleState::default(),
+            fragment: Some(FragmentState {
+                shader: self.shader.clone(),
+                shader_defs,
+                entry_point: entry_point.into(),
+                targets: vec![Some(ColorTargetState {
+                    format: FORMAT,
+                    blend: None,
+                    write_mask: ColorWrites::ALL,
+                })],
+            }),
+            zero_initialize_workgroup_memory: false,
+        }
+    }
+}
+
+#[derive(Clone, Component)]
+struct BlurJob;
+
+impl GraphicsJob for BlurJob {
+    type In = (
+        JobImageView,
+        JobRenderPipelines<BlurPipeline, 3>,
+        JobLinearSampler,
+    );
+
+    fn run(
+        &self,
+        world: &World,
+        context: &mut JobRunContext,
+        (target, [pattern, horizontal, vertical], linear_sampler): JobInputItem<Self, Self::In>,
+    ) -> Result<(), JobError> {
+        let render_device = context.render_device();
+        let layout = &world.resource::<BlurPipeline>().layout;
+
+        // the intermediate textures only live as long as the job
+        let intermediate = || {
+            render_device
+                .create_texture(&TextureDescriptor {
+                    label: Some("blur_intermediate"),
+                    size: target.texture.size(),
+                    mip_level_count: 1,
+                    sample_count: 1,
+                    dimension: TextureDimension::D2,
+                    format: FORMAT,
+                    usage: TextureUsages::RENDER_ATTACHMENT | TextureUsages::TEXTURE_BINDING,
+                    view_formats: &[],
+                })
+                .create_view(&TextureViewDescriptor::default())
+        };
+        let pattern_view = intermediate();
+        let horizontal_view = intermediate();
+
+        let mut draw =
+            |pipeline: &RenderPipeline, source: Option<&TextureView>, destination: &TextureView| {
+                let bind_group = source.map(|source| {
+                    render_device.create_bind_group(
+                        "blur_bind_group",
+                        layout,
+                        &BindGroupEntries::sequential((source, linear_sampler)),
+                    )
+                });
+
+                let mut render_pass = context.begin_render_pass(&RenderPassDescriptor {
+                    label: Some("blur_pass"),
+                    color_attachments: &[Some(RenderPassColorAttachment {
+                        view: destination,
+                        resolve_target: None,
+                        ops: Operations {
+                            load: LoadOp::Clear(Default::default()),
+                            store: StoreOp::Store,
+                        },
+                    })],
+                    depth_stencil_attachment: None,
+                    timestamp_writes: None,
+                    occlusion_query_set: None,
+                });
+
+                render_pass.set_pipeline(pipeline);
+                if let Some(bind_group) = &bind_group {
+                    render_pass.set_bind_group(0, bind_group, &[]);
+                }
+                render_pass.draw(0..3, 0..1);
+            };
+
+        draw(pattern, None, &pattern_view);
+        draw(horizontal, Some(&pattern_view), &horizontal_view);
+        draw(vertical, Some(&horizontal_view), &target.view);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,31 @@
+#import bevy_core_pipeline::fullscreen_vertex_shader::FullscreenVertexOutput
+
+@group(0) @binding(0) var source: texture_2d<f32>;
+@group(0) @binding(1) var linear_sampler: sampler;
+
+// each tap samples between two texels, so the filtering averages both
+const TAPS: i32 = 3;
+
+@fragment
+fn prepass(in: FullscreenVertexOutput) -> @location(0) vec4<f32> {
+    // hard-edged diagonal stripes, which show the blur clearly
+    let stripe = step(0.5, fract((in.uv.x + in.uv.y) * 8.0));
+    return vec4(mix(vec3(0.1, 0.2, 0.5), vec3(1.0, 0.8, 0.3), stripe), 1.0);
+}
+
+@fragment
+fn blur(in: FullscreenVertexOutput) -> @location(0) vec4<f32> {
+#ifdef HORIZONTAL
+    let direction = vec2(1, 0);
+#else
+    let direction = vec2(0, 1);
+#endif
+
+    let texel_size = 1.0 / vec2<f32>(textureDimensions(source));
+    var sum = vec4(0.0);
+    for (var i = -TAPS; i <= TAPS; i++) {
+        let offset = vec2<f32>(direction) * (f32(i) * 2.0 + 0.5) * texel_size;
+        sum += textureSampleLevel(source, linear_sampler, in.uv + offset, 0.0);
+    }
+    return sum / f32(TAPS * 2 + 1);
+}
@@ -0,0 +1,80 @@
+use bevy::prelude::*;
+
+use gigs::*;
+use input::{JobInputItem, JobViewParams};
+use meta::CancelOnTargetDespawn;
+
+fn main() -> AppExit {
+    let mut app = App::new();
+
+    app.add_plugins((DefaultPlugins, GraphicsJobsPlugin::default()))
+        .init_graphics_job::<MinimapJob>()
+        .add_systems(Startup, spawn_job)
+        .add_systems(Update, despawn_minimap);
+
+    app.run()
+}
+
+#[derive(Resource)]
+struct Minimap(Entity);
+
+fn spawn_job(mut commands: Commands) {
+    commands.spawn(Camera2d);
+
+    // the minimap camera isn't active yet, so the job waits for its view
+    let minimap = commands
+        .spawn((
+            Camera2d,
+            Camera {
+                order: 1,
+                is_active: false,
+                ..Default::default()
+            },
+        ))
+        .id();
+    commands.insert_resource(Minimap(minimap));
+
+    commands
+        .spawn((
+            MinimapJob,
+            JobViewParams::new(minimap, Vec4::ONE),
+            CancelOnTargetDespawn::new(minimap),
+        ))
+        .observe(
+            |trigger: Trigger<JobComplete>, mut exit: EventWriter<AppExit>| {
+                // "the job completed with Err(Cancelled)", rather than timing out
+                info!("the job completed with {:?}", trigger.event().0);
+                exit.send(AppExit::Success);
+            },
+        );
+}
+
+fn despawn_minimap(minimap: Option<Res<Minimap>>, mut frames: Local<u32>, mut commands: Commands) {
+    let Some(minimap) = minimap else {
+        return;
+    };
+
+    // the minimap is closed while the job is still waiting on it
+    *frames += 1;
+    if *frames == 4 {
+        info!("despawning the minimap camera");
+        commands.entity(minimap.0).despawn();
+        commands.remove_resource::<Minimap>();
+    }
+}
+
+#[derive(Clone, Component)]
+struct MinimapJob;
+
+impl GraphicsJob for MinimapJob {
+    type In = JobViewParams<Vec4>;
+
+    fn run(
+        &self,
+        _world: &World,
+        _context: &mut JobRunContext,
+        _view_params: JobInputItem<Self, Self::In>,
+    ) -> Result<(), JobError> {
+        unreachable!("the minimap camera is despawned before it's ever active")
+    }
+}
@@ -0,0 +1,141 @@
+use bevy::{
+    input::keyboard::KeyboardInput,
+    pbr::{CascadeShadowConfig, CascadeShadowConfigBuilder},
+    prelude::*,
+};
+
+use gigs::*;
+use input::{JobCascadeConfig, JobInputItem};
+
+/// The width of the shadow penumbra to blur across, in world units.
+const PENUMBRA_WIDTH: f32 = 0.25;
+const MAX_BLUR_RADIUS: f32 = 8.0;
+
+fn main() -> AppExit {
+    let mut app = App::new();
+
+    app.add_plugins((DefaultPlugins, GraphicsJobsPlugin::default()))
+        .init_graphics_job::<ChooseBlurRadiiJob>();
+
+    app.add_systems(Startup, setup_scene)
+        .add_systems(Update, handle_input);
+
+    app.run()
+}
+
+#[derive(Resource)]
+struct ShadowCascades {
+    light: Entity,
+    camera: Entity,
+}
+
+fn cascade_config(num_cascades: usize) -> CascadeShadowConfig {
+    CascadeShadowConfigBuilder {
+        num_cascades,
+        first_cascade_far_bound: 4.0,
+        maximum_distance: 40.0,
+        ..Default::default()
+    }
+    .build()
+}
+
+fn setup_scene(
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    mut commands: Commands,
+) {
+    commands.spawn((
+        Mesh3d(meshes.add(Plane3d::default().mesh().size(60.0, 60.0))),
+        MeshMaterial3d(materials.add(Color::srgb(0.3, 0.5, 0.3))),
+    ));
+    let cube = meshes.add(Cuboid::default());
+    let material = materials.add(Color::srgb(0.8, 0.7, 0.6));
+    for z in 0..8 {
+        commands.spawn((
+            Mesh3d(cube.clone()),
+            MeshMaterial3d(material.clone()),
+            Transform::from_xyz(0.0, 0.5, -4.0 * z as f32),
+        ));
+    }
+
+    let light = commands
+        .spawn((
+            DirectionalLight {
+                shadows_enabled: true,
+                ..Default::default()
+            },
+            cascade_config(4),
+            Transform::from_xyz(4.0, 8.0, 2.0).looking_at(Vec3::ZERO, Vec3::Y),
+        ))
+        .id();
+    let camera = commands
+        .spawn((
+            Camera3d::default(),
+            Transform::from_xyz(-3.0, 3.0, 6.0).looking_at(Vec3::new(0.0, 0.0, -8.0), Vec3::Y),
+        ))
+        .id();
+
+    commands.spawn((ChooseBlurRadiiJob, JobCascadeConfig::new(light, camera)));
+    commands.insert_resource(ShadowCascades { light, camera });
+
+    commands.spawn((
+        Text::from("Press [1] to [4] to change the number of cascades."),
+        Node {
+            position_type: PositionType::Absolute,
+            left: Val::Px(12.0),
+            top: Val::Px(12.0),
+            ..Default::default()
+        },
+    ));
+}
+
+fn handle_input(
+    mut keyboard_input: EventReader<KeyboardInput>,
+    cascades: Res<ShadowCascades>,
+    mut commands: Commands,
+) {
+    for key in keyboard_input.read().filter(|key| key.state.is_pressed()) {
+        let num_cascades = match key.key_code {
+            KeyCode::Digit1 => 1,
+            KeyCode::Digit2 => 2,
+            KeyCode::Digit3 => 3,
+            KeyCode::Digit4 => 4,
+            _ => continue,
+        };
+
+        commands
+            .entity(cascades.light)
+            .insert(cascade_config(num_cascades));
+        commands.spawn((
+            ChooseBlurRadiiJob,
+            JobCascadeConfig::new(cascades.light, cascades.camera),
+        ));
+    }
+}
+
+/// Chooses how many texels a shadow blur should sample in each cascade to cover the
+/// same width of penumbra, as a shadow blur pass would before binding them.
+#[derive(Clone, Component)]
+struct ChooseBlurRadiiJob;
+
+impl GraphicsJob for ChooseBlurRadiiJob {
+    type In = JobCascadeConfig;
+
+    fn run(
+        &self,
+        _world: &World,
+        _context: &mut JobRunContext,
+        cascades: JobInputItem<Self, Self::In>,
+    ) -> Result<(), JobError> {
+        // farther cascades have larger texels, so they need fewer of them
+        for (index, cascade) in cascades.cascades.iter().enumerate() {
+            let radius = (PENUMBRA_WIDTH / cascade.texel_size).clamp(1.0, MAX_BLUR_RADIUS);
+            info!(
+                "cascade {index}: {:.1} to {:.1}, {:.3} per texel, blur radius {radius:.1}",
+                cascade.near, cascade.far, cascade.texel_size
+            );
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,47 @@
+use bevy::{asset::RenderAssetUsages, prelude::*};
+use bevy_render::{
+    gpu_readback::{Readback, ReadbackComplete},
+    render_resource::BufferUsages,
+    storage::ShaderStorageBuffer,
+};
+
+use gigs::*;
+use jobs::ClearBufferJob;
+
+const BUFFER_SIZE: usize = 1024;
+
+fn main() -> AppExit {
+    let mut app = App::new();
+
+    // the built-in jobs are initialized by `GraphicsJobsPlugin`
+    app.add_plugins((DefaultPlugins, GraphicsJobsPlugin::default()))
+        .add_systems(Startup, clear_buffer);
+
+    app.run()
+}
+
+fn clear_buffer(mut buffers: ResMut<Assets<ShaderStorageBuffer>>, mut commands: Commands) {
+    let mut buffer = ShaderStorageBuffer::new(&[0xff; BUFFER_SIZE], RenderAssetUsages::default());
+    buffer.buffer_description.usage |= BufferUsages::COPY_DST | BufferUsages::COPY_SRC;
+    let buffer = buffers.add(buffer);
+
+    commands.spawn(ClearBufferJob::new(buffer.clone())).observe(
+        move |trigger: Trigger<JobComplete>, mut commands: Commands| {
+            assert_eq!(trigger.event().0, Ok(()));
+
+            // read the buffer back once to check that it was cleared
+            commands.spawn(Readback::buffer(buffer.clone())).observe(
+                |trigger: Trigger<ReadbackComplete>,
+                 mut commands: Commands,
+                 mut exit: EventWriter<AppExit>| {
+                    assert_eq!(trigger.event().0.len(), BUFFER_SIZE);
+                    assert!(trigger.event().0.iter().all(|byte| *byte == 0));
+                    println!("Buffer cleared to zero!");
+
+                    commands.entity(trigger.entity()).despawn();
+                    exit.send(AppExit::Success);
+                },
+            );
+        },
+    );
+}
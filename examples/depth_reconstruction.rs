@@ -0,0 +1,241 @@
+use bevy::{
+    asset::{embedded_asset, RenderAssetUsages},
+    core_pipeline::prepass::{DepthPrepass, ViewPrepassTextures},
+    input::keyboard::KeyboardInput,
+    prelude::*,
+};
+use bevy_render::{
+    render_resource::{
+        binding_types::{texture_depth_2d, uniform_buffer},
+        BindGroupEntries, BindGroupLayout, BindGroupLayoutEntries, BufferInitDescriptor,
+        BufferUsages, ComputePassDescriptor, ComputePipelineDescriptor, Extent3d, ShaderStages,
+        SpecializedComputePipeline, TextureDimension, TextureFormat, TextureUsages,
+    },
+    renderer::RenderDevice,
+};
+
+use gigs::*;
+use input::{
+    JobComputePipeline, JobInputItem, JobStorageTexture, JobViewMatrices, JobWorkgroupSize,
+};
+
+const OUTPUT_SIZE: UVec2 = UVec2::new(320, 180);
+const OUTPUT_FORMAT: TextureFormat = TextureFormat::Rgba8Unorm;
+
+fn main() -> AppExit {
+    let mut app = App::new();
+
+    app.add_plugins((DefaultPlugins, GraphicsJobsPlugin::default()))
+        .init_graphics_job::<ReconstructJob>();
+
+    embedded_asset!(app, "examples", "depth_reconstruction.wgsl");
+
+    app.add_systems(Startup, setup_scene)
+        .add_systems(Update, handle_input);
+
+    app.run()
+}
+
+#[derive(Resource)]
+struct Reconstruction {
+    camera: Entity,
+    image: Handle<Image>,
+}
+
+fn setup_scene(
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    mut images: ResMut<Assets<Image>>,
+    mut commands: Commands,
+) {
+    commands.spawn((
+        Mesh3d(meshes.add(Plane3d::default().mesh().size(8.0, 8.0))),
+        MeshMaterial3d(materials.add(Color::srgb(0.3, 0.5, 0.3))),
+    ));
+    commands.spawn((
+        Mesh3d(meshes.add(Cuboid::default())),
+        MeshMaterial3d(materials.add(Color::srgb(0.8, 0.7, 0.6))),
+        Transform::from_xyz(0.0, 0.5, 0.0),
+    ));
+    commands.spawn((
+        PointLight {
+            shadows_enabled: true,
+            ..Default::default()
+        },
+        Transform::from_xyz(4.0, 8.0, 4.0),
+    ));
+
+    // the job reads the depth written by the prepass, which can't be multisampled
+    let camera = commands
+        .spawn((
+            Camera3d::default(),
+            DepthPrepass,
+            Msaa::Off,
+            Transform::from_xyz(-2.5, 3.0, 5.0).looking_at(Vec3::ZERO, Vec3::Y),
+        ))
+        .id();
+
+    let mut image = Image::new_fill(
+        Extent3d {
+            width: OUTPUT_SIZE.x,
+            height: OUTPUT_SIZE.y,
+            depth_or_array_layers: 1,
+        },
+        TextureDimension::D2,
+        &[0, 0, 0, 255],
+        OUTPUT_FORMAT,
+        RenderAssetUsages::RENDER_WORLD,
+    );
+    image.texture_descriptor.usage =
+        TextureUsages::COPY_DST | TextureUsages::STORAGE_BINDING | TextureUsages::TEXTURE_BINDING;
+    let image = images.add(image);
+
+    commands.spawn((
+        ImageNode::new(image.clone()),
+        Node {
+            position_type: PositionType::Absolute,
+            right: Val::Px(12.0),
+            bottom: Val::Px(12.0),
+            width: Val::Px(OUTPUT_SIZE.x as f32),
+            height: Val::Px(OUTPUT_SIZE.y as f32),
+            ..Default::default()
+        },
+    ));
+    commands.spawn((
+        Text::from("Press [space] to reconstruct world positions from the camera's depth."),
+        Node {
+            position_type: PositionType::Absolute,
+            left: Val::Px(12.0),
+            top: Val::Px(12.0),
+            ..Default::default()
+        },
+    ));
+
+    commands.insert_resource(Reconstruction { camera, image });
+}
+
+fn handle_input(
+    mut keyboard_input: EventReader<KeyboardInput>,
+    reconstruction: Res<Reconstruction>,
+    mut commands: Commands,
+) {
+    for key in keyboard_input.read().filter(|key| key.state.is_pressed()) {
+        if key.key_code == KeyCode::Space {
+            commands.spawn((
+                ReconstructJob,
+                JobViewMatrices::new(reconstruction.camera),
+                JobStorageTexture::write_only(reconstruction.image.clone(), OUTPUT_FORMAT),
+                JobComputePipeline::<ReconstructPipeline>::new(())
+                    .with_workgroup_size(UVec3::new(8, 8, 1)),
+            ));
+        }
+    }
+}
+
+#[derive(Clone, Component)]
+struct ReconstructJob;
+
+#[derive(Resource)]
+struct ReconstructPipeline {
+    layout: BindGroupLayout,
+    shader: Handle<Shader>,
+}
+
+impl FromWorld for ReconstructPipeline {
+    fn from_world(world: &mut World) -> Self {
+        let storage = JobStorageTexture::write_only(Handle::default(), OUTPUT_FORMAT);
+        let layout = world.resource::<RenderDevice>().create_bind_group_layout(
+            "depth_reconstruction_layout",
+            &BindGroupLayoutEntries::sequential(
+                ShaderStages::COMPUTE,
+                (
+                    texture_depth_2d(),
+                    storage.layout_entry(),
+                    uniform_buffer::<Mat4>(false),
+                ),
+            ),
+        );
+        let shader = world
+            .resource::<AssetServer>()
+            .load("embedded://depth_reconstruction/depth_reconstruction.wgsl");
+
+        Self { layout, shader }
+    }
+}
+
+impl SpecializedComputePipeline for ReconstructPipeline {
+    type Key = ();
+
+    fn specialize(&self, (): Self::Key) -> ComputePipelineDescriptor {
+        ComputePipelineDescriptor {
+            label: Some("depth_reconstruction_compute".into()),
+            layout: vec![self.layout.clone()],
+            push_constant_ranges: Vec::new(),
+            shader: self.shader.clone(),
+            shader_defs: Vec::new(),
+            entry_point: "main".into(),
+            zero_initialize_workgroup_memory: false,
+        }
+    }
+}
+
+impl GraphicsJob for ReconstructJob {
+    type In = (
+        JobViewMatrices,
+        JobStorageTexture,
+        JobComputePipeline<ReconstructPipeline>,
+        JobWorkgroupSize<ReconstructPipeline>,
+    );
+
+    fn run(
+        &self,
+        world: &World,
+        context: &mut JobRunContext,
+        (matrices, storage, pipeline, workgroup_size): JobInputItem<Self, Self::In>,
+    ) -> Result<(), JobError> {
+        // jobs run before the render graph, so this is the depth of the previous frame,
+        // which matches as long as the camera doesn't move
+        let Some(depth) = matrices
+            .view
+            .get::<ViewPrepassTextures>()
+            .and_then(ViewPrepassTextures::depth_view)
+        else {
+            return Err(JobError::InputsFailed);
+        };
+        info!(
+            "reconstructing from a camera at {}",
+            matrices.world_position
+        );
+
+        let render_device = context.render_device();
+        let world_from_clip = render_device.create_buffer_with_data(&BufferInitDescriptor {
+            label: Some("depth_reconstruction_world_from_clip"),
+            contents: &matrices
+                .inverse_view_proj
+                .to_cols_array()
+                .map(f32::to_le_bytes)
+                .concat(),
+            usage: BufferUsages::UNIFORM,
+        });
+        let bind_group = render_device.create_bind_group(
+            "depth_reconstruction_bind_group",
+            &world.resource::<ReconstructPipeline>().layout,
+            &BindGroupEntries::sequential((
+                depth,
+                storage.binding(),
+                world_from_clip.as_entire_binding(),
+            )),
+        );
+        let workgroups = workgroup_size.workgroups(OUTPUT_SIZE.extend(1));
+
+        let mut compute_pass = context.begin_compute_pass(&ComputePassDescriptor {
+            label: Some("depth_reconstruction_compute_pass"),
+            timestamp_writes: None,
+        });
+        compute_pass.set_bind_group(0, &bind_group, &[]);
+        compute_pass.set_pipeline(pipeline);
+        compute_pass.dispatch_workgroups(workgroups.x, workgroups.y, workgroups.z);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,31 @@
+@group(0) @binding(0) var depth: texture_depth_2d;
+@group(0) @binding(1) var output: texture_storage_2d<rgba8unorm, write>;
+@group(0) @binding(2) var<uniform> world_from_clip: mat4x4<f32>;
+
+@compute @workgroup_size(8, 8, 1)
+fn main(@builtin(global_invocation_id) id: vec3<u32>) {
+    let size = textureDimensions(output);
+    if any(id.xy >= size) {
+        return;
+    }
+
+    let uv = (vec2<f32>(id.xy) + 0.5) / vec2<f32>(size);
+    let depth_size = textureDimensions(depth);
+    let pixel = min(vec2<u32>(uv * vec2<f32>(depth_size)), depth_size - 1u);
+    let ndc_depth = textureLoad(depth, pixel, 0);
+
+    // reversed z, so the far plane is at zero
+    if ndc_depth == 0.0 {
+        textureStore(output, id.xy, vec4(0.0, 0.0, 0.0, 1.0));
+        return;
+    }
+
+    let ndc = vec4(uv.x * 2.0 - 1.0, 1.0 - uv.y * 2.0, ndc_depth, 1.0);
+    let world = world_from_clip * ndc;
+    let position = world.xyz / world.w;
+
+    // a world space grid, which stays fixed to the scene
+    let grid = step(vec3(0.9), fract(position * 2.0));
+    let color = mix(fract(position), vec3(1.0), max(grid.x, max(grid.y, grid.z)));
+    textureStore(output, id.xy, vec4(color, 1.0));
+}
@@ -0,0 +1,239 @@
+// the `ShaderType` derive emits size checks that newer compilers flag as unused
+#![allow(dead_code)]
+
+use bevy::{
+    asset::{embedded_asset, RenderAssetUsages},
+    prelude::*,
+};
+use bevy_render::{
+    extract_resource::{ExtractResource, ExtractResourcePlugin},
+    render_asset::RenderAssets,
+    render_resource::{
+        binding_types::{texture_storage_2d, uniform_buffer},
+        encase, BindGroup, BindGroupEntries, BindGroupLayout, BindGroupLayoutEntries,
+        BufferBinding, BufferInitDescriptor, BufferUsages, ComputePassDescriptor,
+        ComputePipelineDescriptor, Extent3d, ShaderStages, ShaderType, SpecializedComputePipeline,
+        StorageTextureAccess, TextureDimension, TextureFormat, TextureUsages,
+    },
+    renderer::RenderDevice,
+    texture::GpuImage,
+    Render, RenderApp, RenderSet,
+};
+
+use gigs::*;
+use input::{
+    JobComputePipeline, JobDynamicBindGroup, JobDynamicBindGroupResource, JobDynamicOffset,
+    JobInputItem, JobWorkgroupSize,
+};
+
+const TILES: u32 = 10;
+const TILE_SIZE: u32 = 32;
+const STORAGE_FORMAT: TextureFormat = TextureFormat::Rgba8Unorm;
+
+/// The distance between tiles in the shared buffer. Dynamic offsets must be aligned
+/// to `min_uniform_buffer_offset_alignment`, which is never more than 256 bytes.
+const STRIDE: u32 = 256;
+
+fn main() -> AppExit {
+    let mut app = App::new();
+
+    app.add_plugins((
+        DefaultPlugins,
+        GraphicsJobsPlugin::default(),
+        ExtractResourcePlugin::<TileImage>::default(),
+    ))
+    .init_graphics_job::<TileJob>()
+    // run every tile in the same frame
+    .insert_resource(JobExecutionSettings {
+        max_jobs_per_frame: TILES * TILES,
+        ..Default::default()
+    });
+
+    embedded_asset!(app, "examples", "dynamic_offset.wgsl");
+
+    app.add_systems(Startup, setup_scene);
+
+    let render_app = app.sub_app_mut(RenderApp);
+    render_app.init_resource::<TileBindGroup>().add_systems(
+        Render,
+        prepare_tile_bind_group.in_set(RenderSet::PrepareBindGroups),
+    );
+
+    app.run()
+}
+
+#[derive(ShaderType)]
+struct Tile {
+    color: Vec4,
+    origin: UVec2,
+}
+
+#[derive(Resource, ExtractResource, Clone)]
+struct TileImage(Handle<Image>);
+
+/// The bind group shared by every tile job, holding one buffer with the parameters of
+/// every tile, and the image they're drawn into.
+#[derive(Resource, Default)]
+struct TileBindGroup(Option<BindGroup>);
+
+impl JobDynamicBindGroupResource for TileBindGroup {
+    fn bind_group(&self) -> Option<&BindGroup> {
+        self.0.as_ref()
+    }
+}
+
+fn prepare_tile_bind_group(
+    mut tile_bind_group: ResMut<TileBindGroup>,
+    pipeline: Option<Res<TilePipeline>>,
+    image: Option<Res<TileImage>>,
+    images: Res<RenderAssets<GpuImage>>,
+    render_device: Res<RenderDevice>,
+) {
+    if tile_bind_group.0.is_some() {
+        return;
+    }
+    let (Some(pipeline), Some(image)) = (pipeline, image) else {
+        return;
+    };
+    let Some(image) = images.get(&image.0) else {
+        return;
+    };
+
+    let mut contents = vec![0; (TILES * TILES * STRIDE) as usize];
+    for index in 0..TILES * TILES {
+        let tile = Tile {
+            color: LinearRgba::from(Color::hsl(index as f32 * 3.6, 0.7, 0.5)).to_vec4(),
+            origin: UVec2::new(index % TILES, index / TILES) * TILE_SIZE,
+        };
+        let start = (index * STRIDE) as usize;
+        encase::UniformBuffer::new(&mut contents[start..start + STRIDE as usize])
+            .write(&tile)
+            .unwrap();
+    }
+    let buffer = render_device.create_buffer_with_data(&BufferInitDescriptor {
+        label: Some("dynamic_offset_tiles"),
+        contents: &contents,
+        usage: BufferUsages::UNIFORM,
+    });
+
+    tile_bind_group.0 = Some(render_device.create_bind_group(
+        "dynamic_offset_bind_group",
+        &pipeline.layout,
+        &BindGroupEntries::sequential((
+            BufferBinding {
+                buffer: &buffer,
+                offset: 0,
+                size: Some(Tile::min_size()),
+            },
+            &image.texture_view,
+        )),
+    ));
+}
+
+fn setup_scene(mut images: ResMut<Assets<Image>>, mut commands: Commands) {
+    commands.spawn(Camera2d);
+
+    let size = TILES * TILE_SIZE;
+    let mut image = Image::new_fill(
+        Extent3d {
+            width: size,
+            height: size,
+            depth_or_array_layers: 1,
+        },
+        TextureDimension::D2,
+        &[0, 0, 0, 255],
+        STORAGE_FORMAT,
+        RenderAssetUsages::RENDER_WORLD,
+    );
+    image.texture_descriptor.usage =
+        TextureUsages::COPY_DST | TextureUsages::STORAGE_BINDING | TextureUsages::TEXTURE_BINDING;
+    let image = images.add(image);
+
+    commands.spawn(Sprite {
+        image: image.clone(),
+        custom_size: Some(Vec2::splat(640.0)),
+        ..Default::default()
+    });
+    commands.insert_resource(TileImage(image));
+
+    // every job shares the same pipeline and bind group, and only differs in its offset
+    for index in 0..TILES * TILES {
+        commands.spawn((
+            TileJob,
+            JobDynamicOffset::from_index(index, STRIDE),
+            JobComputePipeline::<TilePipeline>::default().with_workgroup_size(UVec3::new(8, 8, 1)),
+        ));
+    }
+}
+
+#[derive(Clone, Component)]
+struct TileJob;
+
+#[derive(Resource)]
+struct TilePipeline {
+    layout: BindGroupLayout,
+    shader: Handle<Shader>,
+}
+
+impl FromWorld for TilePipeline {
+    fn from_world(world: &mut World) -> Self {
+        let layout = world.resource::<RenderDevice>().create_bind_group_layout(
+            "dynamic_offset_layout",
+            &BindGroupLayoutEntries::sequential(
+                ShaderStages::COMPUTE,
+                (
+                    uniform_buffer::<Tile>(true),
+                    texture_storage_2d(STORAGE_FORMAT, StorageTextureAccess::WriteOnly),
+                ),
+            ),
+        );
+        let shader = world
+            .resource::<AssetServer>()
+            .load("embedded://dynamic_offset/dynamic_offset.wgsl");
+
+        Self { layout, shader }
+    }
+}
+
+impl SpecializedComputePipeline for TilePipeline {
+    type Key = ();
+
+    fn specialize(&self, (): Self::Key) -> ComputePipelineDescriptor {
+        ComputePipelineDescriptor {
+            label: Some("dynamic_offset_compute".into()),
+            layout: vec![self.layout.clone()],
+            push_constant_ranges: Vec::new(),
+            shader: self.shader.clone(),
+            shader_defs: Vec::new(),
+            entry_point: "main".into(),
+            zero_initialize_workgroup_memory: false,
+        }
+    }
+}
+
+impl GraphicsJob for TileJob {
+    type In = (
+        JobDynamicBindGroup<TileBindGroup>,
+        JobComputePipeline<TilePipeline>,
+        JobWorkgroupSize<TilePipeline>,
+    );
+
+    fn run(
+        &self,
+        _world: &World,
+        context: &mut JobRunContext,
+        (binding, pipeline, workgroup_size): JobInputItem<Self, Self::In>,
+    ) -> Result<(), JobError> {
+        let workgroups = workgroup_size.workgroups(UVec3::new(TILE_SIZE, TILE_SIZE, 1));
+
+        let mut compute_pass = context.begin_compute_pass(&ComputePassDescriptor {
+            label: Some("dynamic_offset_compute_pass"),
+            timestamp_writes: None,
+        });
+        compute_pass.set_pipeline(pipeline);
+        binding.set_compute(&mut compute_pass, 0);
+        compute_pass.dispatch_workgroups(workgroups.x, workgroups.y, workgroups.z);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,16 @@
+struct Tile {
+    color: vec4<f32>,
+    origin: vec2<u32>,
+}
+
+// each job binds the shared buffer at the offset of its own tile
+@group(0) @binding(0) var<uniform> tile: Tile;
+@group(0) @binding(1) var output: texture_storage_2d<rgba8unorm, write>;
+
+@compute @workgroup_size(8, 8, 1)
+fn main(@builtin(global_invocation_id) id: vec3<u32>) {
+    // leave a border around each tile
+    let border = any(id.xy == vec2(0u)) || any(id.xy == vec2(31u));
+    let color = select(tile.color.rgb, vec3(0.0), border);
+    textureStore(output, tile.origin + id.xy, vec4(color, 1.0));
+}
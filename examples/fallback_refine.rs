@@ -0,0 +1,242 @@
+// the `ShaderType` derive emits size checks that newer compilers flag as unused
+#![allow(dead_code)]
+
+use bevy::{
+    asset::{embedded_asset, RenderAssetUsages},
+    prelude::*,
+};
+use bevy_render::{
+    render_resource::{
+        binding_types::{sampler, texture_2d, uniform_buffer},
+        encase, BindGroupEntries, BindGroupLayout, BindGroupLayoutEntries, BufferInitDescriptor,
+        BufferUsages, ComputePassDescriptor, ComputePipelineDescriptor, Extent3d,
+        SamplerBindingType, ShaderStages, ShaderType, SpecializedComputePipeline, TextureDimension,
+        TextureFormat, TextureSampleType, TextureUsages,
+    },
+    renderer::RenderDevice,
+};
+
+use gigs::*;
+use input::{
+    JobComputePipeline, JobFallback, JobImageView, JobInputItem, JobLinearSampler,
+    JobStorageTexture, JobWorkgroupSize,
+};
+
+const OUTPUT_SIZE: UVec2 = UVec2::new(256, 256);
+const OUTPUT_FORMAT: TextureFormat = TextureFormat::Rgba8Unorm;
+
+/// How long the detail texture takes to "load".
+const LOAD_SECONDS: f32 = 2.0;
+
+fn main() -> AppExit {
+    let mut app = App::new();
+
+    app.add_plugins((DefaultPlugins, GraphicsJobsPlugin::default()))
+        .init_graphics_job::<ShadeJob>();
+
+    app.insert_resource(JobExecutionSettings {
+        // the job waits for the detail texture after running with a placeholder
+        time_out_frames: 1000,
+        ..Default::default()
+    });
+
+    embedded_asset!(app, "examples", "fallback_refine.wgsl");
+
+    app.add_systems(Startup, setup)
+        .add_systems(Update, load_detail);
+
+    app.run()
+}
+
+#[derive(Resource)]
+struct Detail(Handle<Image>);
+
+fn setup(mut images: ResMut<Assets<Image>>, mut commands: Commands) {
+    commands.spawn(Camera2d);
+
+    let mut output = Image::new_fill(
+        Extent3d {
+            width: OUTPUT_SIZE.x,
+            height: OUTPUT_SIZE.y,
+            depth_or_array_layers: 1,
+        },
+        TextureDimension::D2,
+        &[0, 0, 0, 255],
+        OUTPUT_FORMAT,
+        RenderAssetUsages::RENDER_WORLD,
+    );
+    output.texture_descriptor.usage =
+        TextureUsages::COPY_DST | TextureUsages::STORAGE_BINDING | TextureUsages::TEXTURE_BINDING;
+    let output = images.add(output);
+
+    // the detail texture doesn't exist yet, so the job starts with bevy's fallback image
+    let detail = images.reserve_handle();
+
+    commands
+        .spawn((
+            ShadeJob,
+            JobImageView::mip_level(detail.clone(), 0),
+            JobStorageTexture::write_only(output.clone(), OUTPUT_FORMAT),
+            JobComputePipeline::<ShadePipeline>::new(()).with_workgroup_size(UVec3::new(8, 8, 1)),
+        ))
+        .on_job_ready(|_: Trigger<OnJobReady>| info!("the job is ready to run"))
+        .on_job_done(|_: Trigger<OnJobDone>| info!("the job refined its output"));
+
+    commands.spawn((
+        ImageNode::new(output),
+        Node {
+            width: Val::Px(OUTPUT_SIZE.x as f32 * 2.0),
+            height: Val::Px(OUTPUT_SIZE.y as f32 * 2.0),
+            margin: UiRect::all(Val::Auto),
+            ..Default::default()
+        },
+    ));
+    commands.insert_resource(Detail(detail));
+}
+
+/// Adds the detail texture a while after the job first ran, as if it finished loading.
+fn load_detail(
+    time: Res<Time>,
+    detail: Res<Detail>,
+    mut images: ResMut<Assets<Image>>,
+    mut loaded: Local<bool>,
+) {
+    if *loaded || time.elapsed_secs() < LOAD_SECONDS {
+        return;
+    }
+    *loaded = true;
+
+    const SIZE: u32 = 64;
+    let data = (0..SIZE * SIZE)
+        .flat_map(|i| {
+            let (x, y) = (i % SIZE, i / SIZE);
+            let checker = if (x / 8 + y / 8) % 2 == 0 { 255 } else { 64 };
+            [checker, (x * 4) as u8, (y * 4) as u8, 255]
+        })
+        .collect::<Vec<_>>();
+    let image = Image::new(
+        Extent3d {
+            width: SIZE,
+            height: SIZE,
+            depth_or_array_layers: 1,
+        },
+        TextureDimension::D2,
+        data,
+        TextureFormat::Rgba8Unorm,
+        RenderAssetUsages::RENDER_WORLD,
+    );
+    images.insert(&detail.0, image);
+}
+
+#[derive(Clone, Component)]
+struct ShadeJob;
+
+#[derive(ShaderType)]
+struct ShadeUniform {
+    is_fallback: u32,
+}
+
+#[derive(Resource)]
+struct ShadePipeline {
+    layout: BindGroupLayout,
+    shader: Handle<Shader>,
+}
+
+impl FromWorld for ShadePipeline {
+    fn from_world(world: &mut World) -> Self {
+        let storage = JobStorageTexture::write_only(Handle::default(), OUTPUT_FORMAT);
+        let layout = world.resource::<RenderDevice>().create_bind_group_layout(
+            "fallback_refine_layout",
+            &BindGroupLayoutEntries::sequential(
+                ShaderStages::COMPUTE,
+                (
+                    texture_2d(TextureSampleType::Float { filterable: true }),
+                    sampler(SamplerBindingType::Filtering),
+                    storage.layout_entry(),
+                    uniform_buffer::<ShadeUniform>(false),
+                ),
+            ),
+        );
+        let shader = world
+            .resource::<AssetServer>()
+            .load("embedded://fallback_refine/fallback_refine.wgsl");
+
+        Self { layout, shader }
+    }
+}
+
+impl SpecializedComputePipeline for ShadePipeline {
+    type Key = ();
+
+    fn specialize(&self, (): Self::Key) -> ComputePipelineDescriptor {
+        ComputePipelineDescriptor {
+            label: Some("fallback_refine_compute".into()),
+            layout: vec![self.layout.clone()],
+            push_constant_ranges: Vec::new(),
+            shader: self.shader.clone(),
+            shader_defs: Vec::new(),
+            entry_point: "main".into(),
+            zero_initialize_workgroup_memory: false,
+        }
+    }
+}
+
+impl GraphicsJob for ShadeJob {
+    type In = (
+        JobFallback<JobImageView>,
+        JobLinearSampler,
+        JobStorageTexture,
+        JobComputePipeline<ShadePipeline>,
+        JobWorkgroupSize<ShadePipeline>,
+    );
+
+    fn run(
+        &self,
+        world: &World,
+        context: &mut JobRunContext,
+        (detail, sampler, output, pipeline, workgroup_size): JobInputItem<Self, Self::In>,
+    ) -> Result<(), JobError> {
+        info!(
+            "shading with {}",
+            match detail.is_fallback {
+                true => "a placeholder",
+                false => "the detail texture",
+            }
+        );
+
+        let mut contents = encase::UniformBuffer::new(Vec::new());
+        contents
+            .write(&ShadeUniform {
+                is_fallback: detail.is_fallback as u32,
+            })
+            .map_err(|_| JobError::ExecutionFailed)?;
+
+        let render_device = context.render_device();
+        let uniforms = render_device.create_buffer_with_data(&BufferInitDescriptor {
+            label: Some("fallback_refine_uniforms"),
+            contents: &contents.into_inner(),
+            usage: BufferUsages::UNIFORM,
+        });
+        let bind_group = render_device.create_bind_group(
+            "fallback_refine_bind_group",
+            &world.resource::<ShadePipeline>().layout,
+            &BindGroupEntries::sequential((
+                &detail.view,
+                sampler,
+                output.binding(),
+                uniforms.as_entire_binding(),
+            )),
+        );
+        let workgroups = workgroup_size.workgroups(OUTPUT_SIZE.extend(1));
+
+        let mut compute_pass = context.begin_compute_pass(&ComputePassDescriptor {
+            label: Some("fallback_refine_compute_pass"),
+            timestamp_writes: None,
+        });
+        compute_pass.set_bind_group(0, &bind_group, &[]);
+        compute_pass.set_pipeline(pipeline);
+        compute_pass.dispatch_workgroups(workgroups.x, workgroups.y, workgroups.z);
+
+        Ok(())
+    }
+}
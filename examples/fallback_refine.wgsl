@@ -0,0 +1,26 @@
+struct Shade {
+    is_fallback: u32,
+}
+
+@group(0) @binding(0) var detail: texture_2d<f32>;
+@group(0) @binding(1) var detail_sampler: sampler;
+@group(0) @binding(2) var output: texture_storage_2d<rgba8unorm, write>;
+@group(0) @binding(3) var<uniform> shade: Shade;
+
+@compute @workgroup_size(8, 8, 1)
+fn main(@builtin(global_invocation_id) id: vec3<u32>) {
+    let size = textureDimensions(output);
+    if any(id.xy >= size) {
+        return;
+    }
+
+    let uv = (vec2<f32>(id.xy) + 0.5) / vec2<f32>(size);
+    // a soft vignette stands in for the lighting, which doesn't depend on the detail
+    let lighting = 1.0 - 0.6 * length(uv - 0.5);
+    var albedo = textureSampleLevel(detail, detail_sampler, uv, 0.0).rgb;
+    if shade.is_fallback != 0u {
+        // bevy's fallback image is white, so tint the approximation instead
+        albedo *= vec3(0.5, 0.5, 0.55);
+    }
+    textureStore(output, id.xy, vec4(albedo * lighting, 1.0));
+}
@@ -0,0 +1,220 @@
+// the `ShaderType` derive emits size checks that newer compilers flag as unused
+#![allow(dead_code)]
+
+use bevy::{
+    asset::{embedded_asset, RenderAssetUsages},
+    pbr::{FogVolume, VolumetricFog, VolumetricLight},
+    prelude::*,
+};
+use bevy_render::{
+    render_resource::{
+        binding_types::uniform_buffer, encase, BindGroupEntries, BindGroupLayout,
+        BindGroupLayoutEntries, BindingType, BufferInitDescriptor, BufferUsages,
+        ComputePassDescriptor, ComputePipelineDescriptor, Extent3d, ShaderStages, ShaderType,
+        SpecializedComputePipeline, StorageTextureAccess, TextureDimension, TextureFormat,
+        TextureUsages, TextureViewDimension,
+    },
+    renderer::RenderDevice,
+};
+
+use gigs::*;
+use input::{JobComputePipeline, JobInputItem, JobVolumetricFog, JobWorkgroupSize};
+
+const DENSITY_SIZE: u32 = 32;
+const DENSITY_FORMAT: TextureFormat = TextureFormat::Rgba16Float;
+
+fn main() -> AppExit {
+    let mut app = App::new();
+
+    app.add_plugins((DefaultPlugins, GraphicsJobsPlugin::default()))
+        .init_graphics_job::<InjectDensityJob>();
+
+    embedded_asset!(app, "examples", "fog_density.wgsl");
+
+    app.add_systems(Startup, setup_scene)
+        .add_systems(Update, spawn_inject_job);
+
+    app.run()
+}
+
+#[derive(Resource)]
+struct FogCamera(Entity);
+
+fn setup_scene(
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    mut images: ResMut<Assets<Image>>,
+    mut commands: Commands,
+) {
+    commands.spawn((
+        Mesh3d(meshes.add(Plane3d::default().mesh().size(12.0, 12.0))),
+        MeshMaterial3d(materials.add(Color::srgb(0.3, 0.5, 0.3))),
+    ));
+    commands.spawn((
+        Mesh3d(meshes.add(Cuboid::default())),
+        MeshMaterial3d(materials.add(Color::srgb(0.8, 0.7, 0.6))),
+        Transform::from_xyz(0.0, 0.5, 0.0),
+    ));
+
+    // bevy only renders volumetric fog when a light has `VolumetricLight`
+    commands.spawn((
+        DirectionalLight {
+            shadows_enabled: true,
+            ..Default::default()
+        },
+        VolumetricLight,
+        Transform::from_xyz(4.0, 8.0, -2.0).looking_at(Vec3::ZERO, Vec3::Y),
+    ));
+
+    // the density is written by a job every frame, so it only needs to exist on the GPU
+    let mut density = Image::new_fill(
+        Extent3d {
+            width: DENSITY_SIZE,
+            height: DENSITY_SIZE,
+            depth_or_array_layers: DENSITY_SIZE,
+        },
+        TextureDimension::D3,
+        &[0; 8],
+        DENSITY_FORMAT,
+        RenderAssetUsages::RENDER_WORLD,
+    );
+    density.texture_descriptor.usage =
+        TextureUsages::COPY_DST | TextureUsages::STORAGE_BINDING | TextureUsages::TEXTURE_BINDING;
+
+    commands.spawn((
+        FogVolume {
+            density_texture: Some(images.add(density)),
+            density_factor: 0.5,
+            ..Default::default()
+        },
+        Transform::from_xyz(0.0, 1.5, 0.0).with_scale(Vec3::new(8.0, 3.0, 8.0)),
+    ));
+
+    let camera = commands
+        .spawn((
+            Camera3d::default(),
+            VolumetricFog::default(),
+            Transform::from_xyz(-5.0, 3.0, 7.0).looking_at(Vec3::new(0.0, 1.0, 0.0), Vec3::Y),
+        ))
+        .id();
+    commands.insert_resource(FogCamera(camera));
+}
+
+fn spawn_inject_job(camera: Res<FogCamera>, time: Res<Time>, mut commands: Commands) {
+    // jobs execute before bevy's fog pass, so the density is sampled the same frame
+    commands.spawn((
+        InjectDensityJob {
+            time: time.elapsed_secs(),
+        },
+        JobVolumetricFog::new(camera.0),
+        JobComputePipeline::<InjectDensityPipeline>::new(()).with_workgroup_size(UVec3::splat(4)),
+    ));
+}
+
+#[derive(Clone, Component)]
+struct InjectDensityJob {
+    time: f32,
+}
+
+#[derive(ShaderType)]
+struct InjectDensityUniform {
+    time: f32,
+}
+
+#[derive(Resource)]
+struct InjectDensityPipeline {
+    layout: BindGroupLayout,
+    shader: Handle<Shader>,
+}
+
+impl FromWorld for InjectDensityPipeline {
+    fn from_world(world: &mut World) -> Self {
+        let layout = world.resource::<RenderDevice>().create_bind_group_layout(
+            "fog_density_layout",
+            &BindGroupLayoutEntries::sequential(
+                ShaderStages::COMPUTE,
+                (
+                    BindingType::StorageTexture {
+                        access: StorageTextureAccess::WriteOnly,
+                        format: DENSITY_FORMAT,
+                        view_dimension: TextureViewDimension::D3,
+                    },
+                    uniform_buffer::<InjectDensityUniform>(false),
+                ),
+            ),
+        );
+        let shader = world
+            .resource::<AssetServer>()
+            .load("embedded://fog_density/fog_density.wgsl");
+
+        Self { layout, shader }
+    }
+}
+
+impl SpecializedComputePipeline for InjectDensityPipeline {
+    type Key = ();
+
+    fn specialize(&self, (): Self::Key) -> ComputePipelineDescriptor {
+        ComputePipelineDescriptor {
+            label: Some("fog_density_compute".into()),
+            layout: vec![self.layout.clone()],
+            push_constant_ranges: Vec::new(),
+            shader: self.shader.clone(),
+            shader_defs: Vec::new(),
+            entry_point: "main".into(),
+            zero_initialize_workgroup_memory: false,
+        }
+    }
+}
+
+impl GraphicsJob for InjectDensityJob {
+    type In = (
+        JobVolumetricFog,
+        JobComputePipeline<InjectDensityPipeline>,
+        JobWorkgroupSize<InjectDensityPipeline>,
+    );
+
+    fn run(
+        &self,
+        world: &World,
+        context: &mut JobRunContext,
+        (fog, pipeline, workgroup_size): JobInputItem<Self, Self::In>,
+    ) -> Result<(), JobError> {
+        let mut contents = encase::UniformBuffer::new(Vec::new());
+        contents
+            .write(&InjectDensityUniform { time: self.time })
+            .map_err(|_| JobError::ExecutionFailed)?;
+
+        let render_device = context.render_device();
+        let uniforms = render_device.create_buffer_with_data(&BufferInitDescriptor {
+            label: Some("fog_density_uniforms"),
+            contents: &contents.into_inner(),
+            usage: BufferUsages::UNIFORM,
+        });
+
+        for volume in &fog.volumes {
+            let Some(density) = volume.density else {
+                continue;
+            };
+            let bind_group = render_device.create_bind_group(
+                "fog_density_bind_group",
+                &world.resource::<InjectDensityPipeline>().layout,
+                &BindGroupEntries::sequential((
+                    &density.texture_view,
+                    uniforms.as_entire_binding(),
+                )),
+            );
+            let workgroups = workgroup_size.workgroups(volume.density_size);
+
+            let mut compute_pass = context.begin_compute_pass(&ComputePassDescriptor {
+                label: Some("fog_density_compute_pass"),
+                timestamp_writes: None,
+            });
+            compute_pass.set_bind_group(0, &bind_group, &[]);
+            compute_pass.set_pipeline(pipeline);
+            compute_pass.dispatch_workgroups(workgroups.x, workgroups.y, workgroups.z);
+        }
+
+        Ok(())
+    }
+}
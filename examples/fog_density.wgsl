@@ -0,0 +1,22 @@
+struct InjectDensity {
+    time: f32,
+}
+
+@group(0) @binding(0) var density: texture_storage_3d<rgba16float, write>;
+@group(0) @binding(1) var<uniform> inject: InjectDensity;
+
+// rolling banks of fog, thinning toward the top of the volume. bevy's fog pass only
+// samples the red channel.
+@compute @workgroup_size(4, 4, 4)
+fn main(@builtin(global_invocation_id) id: vec3<u32>) {
+    let size = textureDimensions(density);
+    if any(id >= size) {
+        return;
+    }
+
+    let uvw = (vec3<f32>(id) + 0.5) / vec3<f32>(size);
+    let p = uvw * 6.0 + vec3(inject.time * 0.4, 0.0, inject.time * 0.25);
+    let banks = sin(p.x) * sin(p.z + sin(p.y + inject.time)) * 0.5 + 0.5;
+    let falloff = 1.0 - uvw.y;
+    textureStore(density, id, vec4(banks * banks * falloff, 0.0, 0.0, 0.0));
+}
@@ -0,0 +1,104 @@
+use bevy::{core::FrameCount, prelude::*};
+use bevy_render::{
+    render_resource::{Buffer, BufferUsages, StorageBuffer},
+    renderer::{RenderDevice, RenderQueue},
+    Render, RenderApp, RenderSet,
+};
+
+use gigs::*;
+use input::{
+    JobBufferResource, JobInputItem, JobReadback, JobReadbackResult, JobResourceBuffer,
+    ReadbackPolling,
+};
+
+fn main() -> AppExit {
+    let mut app = App::new();
+
+    app.add_plugins((DefaultPlugins, GraphicsJobsPlugin::default()))
+        .init_graphics_job::<FrameJob>()
+        .add_systems(Update, (spawn_frame_jobs, print_readback));
+
+    app.insert_resource(JobExecutionSettings {
+        // wait for readbacks on a separate thread, so the render thread never polls
+        readback_polling: ReadbackPolling::Thread,
+        ..Default::default()
+    });
+
+    let render_app = app.sub_app_mut(RenderApp);
+    render_app
+        .init_resource::<RenderFrame>()
+        .add_systems(Render, write_frame.in_set(RenderSet::PrepareResources));
+
+    app.run()
+}
+
+/// The current frame, as seen by the render world.
+#[derive(Resource)]
+struct RenderFrame(StorageBuffer<u32>);
+
+impl Default for RenderFrame {
+    fn default() -> Self {
+        let mut buffer = StorageBuffer::default();
+        buffer.set_label(Some("render_frame"));
+        buffer.add_usages(BufferUsages::COPY_SRC);
+        Self(buffer)
+    }
+}
+
+impl JobBufferResource for RenderFrame {
+    fn buffer(&self) -> Option<&Buffer> {
+        self.0.buffer()
+    }
+}
+
+fn write_frame(
+    mut frame: ResMut<RenderFrame>,
+    frame_count: Res<FrameCount>,
+    render_device: Res<RenderDevice>,
+    render_queue: Res<RenderQueue>,
+) {
+    frame.0.set(frame_count.0);
+    frame.0.write_buffer(&render_device, &render_queue);
+}
+
+fn spawn_frame_jobs(jobs: Query<(), With<FrameJob>>, mut commands: Commands) {
+    // only two readbacks can be in flight at once, so don't queue up more jobs than that
+    if jobs.iter().len() < 2 {
+        commands.spawn((FrameJob, JobReadback::new(size_of::<u32>() as u64)));
+    }
+}
+
+fn print_readback(result: Option<Res<JobReadbackResult<FrameJob>>>, frame_count: Res<FrameCount>) {
+    let Some(result) = result.filter(|result| result.is_changed()) else {
+        return;
+    };
+
+    // the job copied the frame it ran in, so the value should match the result's frame
+    let value = u32::from_le_bytes(result.data[0..4].try_into().unwrap());
+    assert_eq!(value, result.frame);
+
+    println!(
+        "read back frame {} during frame {} ({} frames of latency)",
+        result.frame,
+        frame_count.0,
+        frame_count.0.saturating_sub(result.frame)
+    );
+}
+
+/// Copies the current frame into a readback buffer.
+#[derive(Clone, Component)]
+struct FrameJob;
+
+impl GraphicsJob for FrameJob {
+    type In = (JobResourceBuffer<RenderFrame>, JobReadback);
+
+    fn run(
+        &self,
+        _world: &World,
+        context: &mut JobRunContext,
+        (frame, readback): JobInputItem<Self, Self::In>,
+    ) -> Result<(), JobError> {
+        context.copy_buffer_to_buffer(frame, 0, readback, 0, readback.size());
+        Ok(())
+    }
+}
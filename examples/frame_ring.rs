@@ -0,0 +1,89 @@
+use bevy::prelude::*;
+use bevy_render::{
+    render_resource::{Buffer, BufferDescriptor, BufferUsages},
+    renderer::{RenderDevice, RenderQueue},
+    Render, RenderApp, RenderSet,
+};
+
+use gigs::*;
+use input::{JobFrameInfo, JobInputItem};
+
+/// One more slot than the frames that may be in flight, so a slot is never written
+/// while the GPU may still be reading it.
+const RING_SIZE: u64 = 3;
+
+fn main() -> AppExit {
+    let mut app = App::new();
+
+    app.add_plugins((DefaultPlugins, GraphicsJobsPlugin::default()))
+        .init_graphics_job::<RingJob>()
+        .add_systems(Startup, |mut commands: Commands| {
+            commands.spawn(Camera2d);
+        })
+        .add_systems(Update, spawn_ring_job);
+
+    app.sub_app_mut(RenderApp)
+        .init_resource::<FrameRing>()
+        .add_systems(
+            Render,
+            prepare_frame_ring.in_set(RenderSet::PrepareResources),
+        );
+
+    app.run()
+}
+
+fn spawn_ring_job(mut commands: Commands) {
+    commands.spawn(RingJob);
+}
+
+#[derive(Clone, Component)]
+struct RingJob;
+
+/// A ring of buffers in the render world, each holding the index of the last frame that
+/// wrote to it.
+#[derive(Resource, Default)]
+struct FrameRing(Vec<Buffer>);
+
+fn prepare_frame_ring(render_device: Res<RenderDevice>, mut ring: ResMut<FrameRing>) {
+    if !ring.0.is_empty() {
+        return;
+    }
+    ring.0 = (0..RING_SIZE)
+        .map(|_| {
+            render_device.create_buffer(&BufferDescriptor {
+                label: Some("frame_ring_slot"),
+                size: size_of::<u64>() as u64,
+                usage: BufferUsages::COPY_DST | BufferUsages::STORAGE,
+                mapped_at_creation: false,
+            })
+        })
+        .collect();
+}
+
+impl GraphicsJob for RingJob {
+    type In = JobFrameInfo;
+
+    fn run(
+        &self,
+        world: &World,
+        _context: &mut JobRunContext,
+        frame: JobInputItem<Self, Self::In>,
+    ) -> Result<(), JobError> {
+        let slot = frame.frame_index % RING_SIZE;
+        let ring = world.resource::<FrameRing>();
+        world.resource::<RenderQueue>().write_buffer(
+            &ring.0[slot as usize],
+            0,
+            &frame.frame_index.to_le_bytes(),
+        );
+
+        if frame.frame_index % 120 == 0 {
+            info!(
+                "frame {} wrote slot {slot} with {} view(s) active",
+                frame.frame_index, frame.view_count
+            );
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,168 @@
+// the `ShaderType` derive emits size checks that newer compilers flag as unused
+#![allow(dead_code)]
+
+use bevy::{
+    asset::{embedded_asset, RenderAssetUsages},
+    prelude::*,
+};
+use bevy_render::{
+    extract_resource::{ExtractResource, ExtractResourcePlugin},
+    render_resource::{
+        binding_types::uniform_buffer, BindGroupEntries, BindGroupLayout, BindGroupLayoutEntries,
+        ComputePassDescriptor, ComputePipelineDescriptor, Extent3d, ShaderStages, ShaderType,
+        SpecializedComputePipeline, TextureDimension, TextureFormat, TextureUsages,
+    },
+    renderer::RenderDevice,
+};
+
+use gigs::*;
+use input::{
+    JobComputePipeline, JobFrameUniform, JobInputItem, JobStorageTexture, JobWorkgroupSize,
+};
+
+const SIZE: u32 = 256;
+const STORAGE_FORMAT: TextureFormat = TextureFormat::Rgba8Unorm;
+
+fn main() -> AppExit {
+    let mut app = App::new();
+
+    app.add_plugins((
+        DefaultPlugins,
+        GraphicsJobsPlugin::default(),
+        ExtractResourcePlugin::<Pulse>::default(),
+    ))
+    .init_graphics_job::<PulseJob>()
+    .init_resource::<Pulse>();
+
+    embedded_asset!(app, "examples", "frame_uniform.wgsl");
+
+    app.add_systems(Startup, setup_scene)
+        .add_systems(Update, (update_pulse, spawn_pulse_job).chain());
+
+    app.run()
+}
+
+/// A uniform that changes every frame. Each frame's value is written into its own
+/// buffer, so it never overwrites a value the GPU may still be reading.
+#[derive(Resource, ExtractResource, ShaderType, Clone, Default)]
+struct Pulse {
+    time: f32,
+}
+
+#[derive(Resource)]
+struct PulseImage(Handle<Image>);
+
+fn setup_scene(mut images: ResMut<Assets<Image>>, mut commands: Commands) {
+    let mut image = Image::new_fill(
+        Extent3d {
+            width: SIZE,
+            height: SIZE,
+            depth_or_array_layers: 1,
+        },
+        TextureDimension::D2,
+        &[0, 0, 0, 255],
+        STORAGE_FORMAT,
+        RenderAssetUsages::RENDER_WORLD,
+    );
+    image.texture_descriptor.usage =
+        TextureUsages::COPY_DST | TextureUsages::STORAGE_BINDING | TextureUsages::TEXTURE_BINDING;
+    let image = images.add(image);
+
+    commands.spawn(Camera2d);
+    commands.spawn(Sprite {
+        image: image.clone(),
+        custom_size: Some(Vec2::splat(512.0)),
+        ..Default::default()
+    });
+
+    commands.insert_resource(PulseImage(image));
+}
+
+fn update_pulse(time: Res<Time>, mut pulse: ResMut<Pulse>) {
+    pulse.time = time.elapsed_secs();
+}
+
+/// Spawns a job every frame, which reads that frame's pulse.
+fn spawn_pulse_job(image: Res<PulseImage>, mut commands: Commands) {
+    commands.spawn((
+        PulseJob,
+        JobStorageTexture::write_only(image.0.clone(), STORAGE_FORMAT),
+        JobComputePipeline::<PulsePipeline>::new(()).with_workgroup_size(UVec3::new(8, 8, 1)),
+    ));
+}
+
+#[derive(Clone, Component)]
+struct PulseJob;
+
+#[derive(Resource)]
+struct PulsePipeline {
+    layout: BindGroupLayout,
+    shader: Handle<Shader>,
+}
+
+impl FromWorld for PulsePipeline {
+    fn from_world(world: &mut World) -> Self {
+        let storage = JobStorageTexture::write_only(Handle::default(), STORAGE_FORMAT);
+        let layout = world.resource::<RenderDevice>().create_bind_group_layout(
+            "frame_uniform_layout",
+            &BindGroupLayoutEntries::sequential(
+                ShaderStages::COMPUTE,
+                (storage.layout_entry(), uniform_buffer::<Pulse>(false)),
+            ),
+        );
+        let shader = world
+            .resource::<AssetServer>()
+            .load("embedded://frame_uniform/frame_uniform.wgsl");
+
+        Self { layout, shader }
+    }
+}
+
+impl SpecializedComputePipeline for PulsePipeline {
+    type Key = ();
+
+    fn specialize(&self, (): Self::Key) -> ComputePipelineDescriptor {
+        ComputePipelineDescriptor {
+            label: Some("frame_uniform_compute".into()),
+            layout: vec![self.layout.clone()],
+            push_constant_ranges: Vec::new(),
+            shader: self.shader.clone(),
+            shader_defs: Vec::new(),
+            entry_point: "main".into(),
+            zero_initialize_workgroup_memory: false,
+        }
+    }
+}
+
+impl GraphicsJob for PulseJob {
+    type In = (
+        JobFrameUniform<Pulse>,
+        JobStorageTexture,
+        JobComputePipeline<PulsePipeline>,
+        JobWorkgroupSize<PulsePipeline>,
+    );
+
+    fn run(
+        &self,
+        world: &World,
+        context: &mut JobRunContext,
+        (pulse, storage, pipeline, workgroup_size): JobInputItem<Self, Self::In>,
+    ) -> Result<(), JobError> {
+        let bind_group = context.render_device().create_bind_group(
+            "frame_uniform_bind_group",
+            &world.resource::<PulsePipeline>().layout,
+            &BindGroupEntries::sequential((storage.binding(), pulse.as_entire_binding())),
+        );
+        let workgroups = workgroup_size.workgroups(UVec3::new(SIZE, SIZE, 1));
+
+        let mut compute_pass = context.begin_compute_pass(&ComputePassDescriptor {
+            label: Some("frame_uniform_compute_pass"),
+            timestamp_writes: None,
+        });
+        compute_pass.set_bind_group(0, &bind_group, &[]);
+        compute_pass.set_pipeline(pipeline);
+        compute_pass.dispatch_workgroups(workgroups.x, workgroups.y, workgroups.z);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,20 @@
+struct Pulse {
+    time: f32,
+}
+
+@group(0) @binding(0) var output: texture_storage_2d<rgba8unorm, write>;
+@group(0) @binding(1) var<uniform> pulse: Pulse;
+
+@compute @workgroup_size(8, 8, 1)
+fn main(@builtin(global_invocation_id) id: vec3<u32>) {
+    let size = textureDimensions(output);
+    if any(id.xy >= size) {
+        return;
+    }
+
+    // rings that move outwards as time passes
+    let uv = (vec2<f32>(id.xy) + 0.5) / vec2<f32>(size) - 0.5;
+    let rings = 0.5 + 0.5 * cos(length(uv) * 64.0 - pulse.time * 4.0);
+    let color = mix(vec3(0.1, 0.2, 0.5), vec3(0.9, 0.6, 0.2), rings);
+    textureStore(output, id.xy, vec4(color, 1.0));
+}
@@ -0,0 +1,216 @@
+use bevy::{
+    asset::{embedded_asset, RenderAssetUsages},
+    core_pipeline::prepass::{DeferredPrepass, DepthPrepass},
+    input::keyboard::KeyboardInput,
+    pbr::DefaultOpaqueRendererMethod,
+    prelude::*,
+};
+use bevy_render::{
+    render_resource::{
+        binding_types::texture_2d, BindGroupEntries, BindGroupLayout, BindGroupLayoutEntries,
+        ComputePassDescriptor, ComputePipelineDescriptor, Extent3d, ShaderDefVal, ShaderStages,
+        SpecializedComputePipeline, TextureDimension, TextureFormat, TextureSampleType,
+        TextureUsages,
+    },
+    renderer::RenderDevice,
+};
+
+use gigs::*;
+use input::{
+    GBufferChannel, JobComputePipeline, JobGBuffer, JobInputItem, JobStorageTexture,
+    JobWorkgroupSize,
+};
+
+const OUTPUT_SIZE: UVec2 = UVec2::new(320, 180);
+const OUTPUT_FORMAT: TextureFormat = TextureFormat::Rgba8Unorm;
+
+fn main() -> AppExit {
+    let mut app = App::new();
+
+    app.add_plugins((DefaultPlugins, GraphicsJobsPlugin::default()))
+        .init_graphics_job::<NormalsJob>()
+        // draws the scene's standard materials into the G-buffer
+        .insert_resource(DefaultOpaqueRendererMethod::deferred());
+
+    embedded_asset!(app, "examples", "gbuffer_normals.wgsl");
+
+    app.add_systems(Startup, setup_scene)
+        .add_systems(Update, handle_input);
+
+    app.run()
+}
+
+#[derive(Resource)]
+struct Shading {
+    camera: Entity,
+    image: Handle<Image>,
+}
+
+fn setup_scene(
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    mut images: ResMut<Assets<Image>>,
+    mut commands: Commands,
+) {
+    commands.spawn((
+        Mesh3d(meshes.add(Plane3d::default().mesh().size(8.0, 8.0))),
+        MeshMaterial3d(materials.add(Color::srgb(0.3, 0.5, 0.3))),
+    ));
+    commands.spawn((
+        Mesh3d(meshes.add(Sphere::new(0.75))),
+        MeshMaterial3d(materials.add(Color::srgb(0.8, 0.7, 0.6))),
+        Transform::from_xyz(0.0, 0.75, 0.0),
+    ));
+    commands.spawn((
+        DirectionalLight::default(),
+        Transform::from_xyz(4.0, 8.0, 4.0).looking_at(Vec3::ZERO, Vec3::Y),
+    ));
+
+    // the deferred renderer can't be multisampled
+    let camera = commands
+        .spawn((
+            Camera3d::default(),
+            DepthPrepass,
+            DeferredPrepass,
+            Msaa::Off,
+            Transform::from_xyz(-2.5, 3.0, 5.0).looking_at(Vec3::ZERO, Vec3::Y),
+        ))
+        .id();
+
+    let mut image = Image::new_fill(
+        Extent3d {
+            width: OUTPUT_SIZE.x,
+            height: OUTPUT_SIZE.y,
+            depth_or_array_layers: 1,
+        },
+        TextureDimension::D2,
+        &[0, 0, 0, 255],
+        OUTPUT_FORMAT,
+        RenderAssetUsages::RENDER_WORLD,
+    );
+    image.texture_descriptor.usage =
+        TextureUsages::COPY_DST | TextureUsages::STORAGE_BINDING | TextureUsages::TEXTURE_BINDING;
+    let image = images.add(image);
+
+    commands.spawn((
+        ImageNode::new(image.clone()),
+        Node {
+            position_type: PositionType::Absolute,
+            right: Val::Px(12.0),
+            bottom: Val::Px(12.0),
+            width: Val::Px(OUTPUT_SIZE.x as f32),
+            height: Val::Px(OUTPUT_SIZE.y as f32),
+            ..Default::default()
+        },
+    ));
+    commands.spawn((
+        Text::from("Press [space] to shade the scene from the G-buffer's normals."),
+        Node {
+            position_type: PositionType::Absolute,
+            left: Val::Px(12.0),
+            top: Val::Px(12.0),
+            ..Default::default()
+        },
+    ));
+
+    commands.insert_resource(Shading { camera, image });
+}
+
+fn handle_input(
+    mut keyboard_input: EventReader<KeyboardInput>,
+    shading: Res<Shading>,
+    mut commands: Commands,
+) {
+    for key in keyboard_input.read().filter(|key| key.state.is_pressed()) {
+        if key.key_code == KeyCode::Space {
+            commands.spawn((
+                NormalsJob,
+                JobGBuffer::new(shading.camera),
+                JobStorageTexture::write_only(shading.image.clone(), OUTPUT_FORMAT),
+                JobComputePipeline::<NormalsPipeline>::new(())
+                    .with_workgroup_size(UVec3::new(8, 8, 1)),
+            ));
+        }
+    }
+}
+
+#[derive(Clone, Component)]
+struct NormalsJob;
+
+#[derive(Resource)]
+struct NormalsPipeline {
+    layout: BindGroupLayout,
+    shader: Handle<Shader>,
+}
+
+impl FromWorld for NormalsPipeline {
+    fn from_world(world: &mut World) -> Self {
+        let storage = JobStorageTexture::write_only(Handle::default(), OUTPUT_FORMAT);
+        let layout = world.resource::<RenderDevice>().create_bind_group_layout(
+            "gbuffer_normals_layout",
+            &BindGroupLayoutEntries::sequential(
+                ShaderStages::COMPUTE,
+                (texture_2d(TextureSampleType::Uint), storage.layout_entry()),
+            ),
+        );
+        let shader = world
+            .resource::<AssetServer>()
+            .load("embedded://gbuffer_normals/gbuffer_normals.wgsl");
+
+        Self { layout, shader }
+    }
+}
+
+impl SpecializedComputePipeline for NormalsPipeline {
+    type Key = ();
+
+    fn specialize(&self, (): Self::Key) -> ComputePipelineDescriptor {
+        ComputePipelineDescriptor {
+            label: Some("gbuffer_normals_compute".into()),
+            layout: vec![self.layout.clone()],
+            push_constant_ranges: Vec::new(),
+            shader: self.shader.clone(),
+            shader_defs: vec![ShaderDefVal::UInt(
+                "NORMAL_COMPONENT".into(),
+                GBufferChannel::Normal.component() as u32,
+            )],
+            entry_point: "main".into(),
+            zero_initialize_workgroup_memory: false,
+        }
+    }
+}
+
+impl GraphicsJob for NormalsJob {
+    type In = (
+        JobGBuffer,
+        JobStorageTexture,
+        JobComputePipeline<NormalsPipeline>,
+        JobWorkgroupSize<NormalsPipeline>,
+    );
+
+    fn run(
+        &self,
+        world: &World,
+        context: &mut JobRunContext,
+        (gbuffer, storage, pipeline, workgroup_size): JobInputItem<Self, Self::In>,
+    ) -> Result<(), JobError> {
+        // jobs run before the render graph, so this is the G-buffer of the previous
+        // frame, which matches as long as the camera doesn't move
+        let bind_group = context.render_device().create_bind_group(
+            "gbuffer_normals_bind_group",
+            &world.resource::<NormalsPipeline>().layout,
+            &BindGroupEntries::sequential((gbuffer.deferred, storage.binding())),
+        );
+        let workgroups = workgroup_size.workgroups(OUTPUT_SIZE.extend(1));
+
+        let mut compute_pass = context.begin_compute_pass(&ComputePassDescriptor {
+            label: Some("gbuffer_normals_compute_pass"),
+            timestamp_writes: None,
+        });
+        compute_pass.set_bind_group(0, &bind_group, &[]);
+        compute_pass.set_pipeline(pipeline);
+        compute_pass.dispatch_workgroups(workgroups.x, workgroups.y, workgroups.z);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,39 @@
+@group(0) @binding(0) var gbuffer: texture_2d<u32>;
+@group(0) @binding(1) var output: texture_storage_2d<rgba8unorm, write>;
+
+// the inverse of the octahedral encoding bevy packs normals with
+fn octahedral_decode(v: vec2<f32>) -> vec3<f32> {
+    let f = v * 2.0 - 1.0;
+    let n = vec3(f, 1.0 - abs(f.x) - abs(f.y));
+    let t = saturate(-n.z);
+    let w = select(vec2(t), vec2(-t), n.xy >= vec2(0.0));
+    return normalize(vec3(n.xy + w, n.z));
+}
+
+@compute @workgroup_size(8, 8, 1)
+fn main(@builtin(global_invocation_id) id: vec3<u32>) {
+    let size = textureDimensions(output);
+    if any(id.xy >= size) {
+        return;
+    }
+
+    let uv = (vec2<f32>(id.xy) + 0.5) / vec2<f32>(size);
+    let gbuffer_size = textureDimensions(gbuffer);
+    let pixel = min(vec2<u32>(uv * vec2<f32>(gbuffer_size)), gbuffer_size - 1u);
+    let packed = textureLoad(gbuffer, pixel, 0)[#{NORMAL_COMPONENT}];
+
+    // nothing was drawn to this pixel
+    if packed == 0u {
+        textureStore(output, id.xy, vec4(0.0, 0.0, 0.0, 1.0));
+        return;
+    }
+
+    // the normal is packed as two 12-bit unorms, below the material's flags
+    let octahedral = vec2(f32(packed & 0xFFFu), f32((packed >> 12u) & 0xFFFu)) / 4095.0;
+    let normal = octahedral_decode(octahedral);
+
+    // a toon-like shading from a fixed light, using only the G-buffer's normals
+    let light = normalize(vec3(1.0, 2.0, 1.0));
+    let bands = floor(saturate(dot(normal, light)) * 4.0) / 4.0;
+    textureStore(output, id.xy, vec4(mix(normal * 0.5 + 0.5, vec3(bands), 0.5), 1.0));
+}
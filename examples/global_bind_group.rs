@@ -0,0 +1,223 @@
+// the `ShaderType` derive emits size checks that newer compilers flag as unused
+#![allow(dead_code)]
+
+use bevy::{
+    asset::{embedded_asset, RenderAssetUsages},
+    prelude::*,
+};
+use bevy_render::{
+    render_resource::{
+        binding_types::uniform_buffer, BindGroupEntries, BindGroupLayout, BindGroupLayoutEntries,
+        BindGroupLayoutEntryBuilder, BindingResource, ComputePassDescriptor,
+        ComputePipelineDescriptor, Extent3d, ShaderStages, ShaderType, SpecializedComputePipeline,
+        TextureDimension, TextureFormat, TextureUsages, TextureViewDescriptor, UniformBuffer,
+    },
+    renderer::{RenderDevice, RenderQueue},
+    Render, RenderApp, RenderSet,
+};
+
+use gigs::*;
+use input::{
+    JobComputePipeline, JobGlobalBindGroup, JobGlobalBinding, JobGlobalBindings, JobInputItem,
+    JobStorageTexture, JobWorkgroupSize, RegisterJobGlobalBindingExt,
+};
+
+const SIZE: u32 = 256;
+const STORAGE_FORMAT: TextureFormat = TextureFormat::Rgba8Unorm;
+
+fn main() -> AppExit {
+    let mut app = App::new();
+
+    app.add_plugins((DefaultPlugins, GraphicsJobsPlugin::default()))
+        .init_graphics_job::<PatternJob>()
+        .register_job_global_binding::<GlobalPalette>();
+
+    embedded_asset!(app, "examples", "global_bind_group.wgsl");
+
+    app.add_systems(Startup, setup_scene);
+
+    let render_app = app.sub_app_mut(RenderApp);
+    render_app
+        .init_resource::<GlobalPalette>()
+        .add_systems(Render, write_palette.in_set(RenderSet::PrepareResources));
+
+    app.run()
+}
+
+#[derive(ShaderType, Default)]
+struct Palette {
+    low: Vec4,
+    high: Vec4,
+}
+
+/// A palette shared by every job through the frame-global bind group.
+#[derive(Resource, Default)]
+struct GlobalPalette(UniformBuffer<Palette>);
+
+impl JobGlobalBinding for GlobalPalette {
+    fn layout_entry() -> BindGroupLayoutEntryBuilder {
+        uniform_buffer::<Palette>(false)
+    }
+
+    fn binding(&self) -> Option<BindingResource<'_>> {
+        self.0.binding()
+    }
+}
+
+/// Writes the palette once. Since it's never changed afterwards, the global bind
+/// group is only built once, however many jobs read it.
+fn write_palette(
+    mut palette: ResMut<GlobalPalette>,
+    render_device: Res<RenderDevice>,
+    render_queue: Res<RenderQueue>,
+) {
+    if palette.0.buffer().is_some() {
+        return;
+    }
+
+    palette.0.set(Palette {
+        low: LinearRgba::rgb(0.1, 0.2, 0.5).to_vec4(),
+        high: LinearRgba::rgb(0.9, 0.6, 0.2).to_vec4(),
+    });
+    palette.0.write_buffer(&render_device, &render_queue);
+}
+
+fn setup_scene(mut images: ResMut<Assets<Image>>, mut commands: Commands) {
+    commands.spawn(Camera2d);
+
+    for (stripes, x) in [(false, -272.0), (true, 272.0)] {
+        let mut image = Image::new_fill(
+            Extent3d {
+                width: SIZE,
+                height: SIZE,
+                depth_or_array_layers: 1,
+            },
+            TextureDimension::D2,
+            &[0, 0, 0, 255],
+            STORAGE_FORMAT,
+            RenderAssetUsages::RENDER_WORLD,
+        );
+        image.texture_descriptor.usage = TextureUsages::COPY_DST
+            | TextureUsages::STORAGE_BINDING
+            | TextureUsages::TEXTURE_BINDING;
+        image.texture_descriptor.view_formats = &[TextureFormat::Rgba8UnormSrgb];
+        image.texture_view_descriptor = Some(TextureViewDescriptor {
+            format: Some(TextureFormat::Rgba8UnormSrgb),
+            ..Default::default()
+        });
+        let image = images.add(image);
+
+        commands.spawn((
+            Sprite {
+                image: image.clone(),
+                custom_size: Some(Vec2::splat(512.0)),
+                ..Default::default()
+            },
+            Transform::from_xyz(x, 0.0, 0.0),
+        ));
+
+        commands.spawn((
+            PatternJob,
+            JobStorageTexture::write_only(image, STORAGE_FORMAT),
+            JobComputePipeline::<PatternPipeline>::new(stripes)
+                .with_workgroup_size(UVec3::new(8, 8, 1)),
+        ));
+    }
+}
+
+#[derive(Clone, Component)]
+struct PatternJob;
+
+#[derive(Resource)]
+struct PatternPipeline {
+    global_layout: BindGroupLayout,
+    layout: BindGroupLayout,
+    shader: Handle<Shader>,
+}
+
+impl FromWorld for PatternPipeline {
+    fn from_world(world: &mut World) -> Self {
+        let render_device = world.resource::<RenderDevice>();
+        let global_layout = world
+            .resource::<JobGlobalBindings>()
+            .layout(render_device)
+            .clone();
+        let storage = JobStorageTexture::write_only(Handle::default(), STORAGE_FORMAT);
+        let layout = render_device.create_bind_group_layout(
+            "global_bind_group_pattern_layout",
+            &BindGroupLayoutEntries::single(ShaderStages::COMPUTE, storage.layout_entry()),
+        );
+        let shader = world
+            .resource::<AssetServer>()
+            .load("embedded://global_bind_group/global_bind_group.wgsl");
+
+        Self {
+            global_layout,
+            layout,
+            shader,
+        }
+    }
+}
+
+impl SpecializedComputePipeline for PatternPipeline {
+    type Key = bool;
+
+    fn specialize(&self, stripes: Self::Key) -> ComputePipelineDescriptor {
+        let mut shader_defs = Vec::new();
+        if stripes {
+            shader_defs.push("STRIPES".into());
+        }
+
+        ComputePipelineDescriptor {
+            label: Some("global_bind_group_compute".into()),
+            layout: vec![self.global_layout.clone(), self.layout.clone()],
+            push_constant_ranges: Vec::new(),
+            shader: self.shader.clone(),
+            shader_defs,
+            entry_point: "main".into(),
+            zero_initialize_workgroup_memory: false,
+        }
+    }
+}
+
+impl GraphicsJob for PatternJob {
+    type In = (
+        JobGlobalBindGroup,
+        JobStorageTexture,
+        JobComputePipeline<PatternPipeline>,
+        JobWorkgroupSize<PatternPipeline>,
+    );
+
+    fn run(
+        &self,
+        world: &World,
+        context: &mut JobRunContext,
+        (global_bind_group, storage, pipeline, workgroup_size): JobInputItem<Self, Self::In>,
+    ) -> Result<(), JobError> {
+        // both jobs log the same bind group, which has only been built once
+        info!(
+            "job {:?} bound global bind group {:?}, built {} time(s)",
+            context.job(),
+            global_bind_group.id(),
+            world.resource::<JobGlobalBindings>().generation()
+        );
+
+        let bind_group = context.render_device().create_bind_group(
+            "global_bind_group_pattern_bind_group",
+            &world.resource::<PatternPipeline>().layout,
+            &BindGroupEntries::single(storage.binding()),
+        );
+        let workgroups = workgroup_size.workgroups(UVec3::new(SIZE, SIZE, 1));
+
+        let mut compute_pass = context.begin_compute_pass(&ComputePassDescriptor {
+            label: Some("global_bind_group_compute_pass"),
+            timestamp_writes: None,
+        });
+        compute_pass.set_bind_group(0, global_bind_group, &[]);
+        compute_pass.set_bind_group(1, &bind_group, &[]);
+        compute_pass.set_pipeline(pipeline);
+        compute_pass.dispatch_workgroups(workgroups.x, workgroups.y, workgroups.z);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,25 @@
+struct Palette {
+    low: vec4<f32>,
+    high: vec4<f32>,
+}
+
+// shared by every job through the frame-global bind group
+@group(0) @binding(0) var<uniform> palette: Palette;
+
+@group(1) @binding(0) var output: texture_storage_2d<rgba8unorm, write>;
+
+@compute @workgroup_size(8, 8, 1)
+fn main(@builtin(global_invocation_id) id: vec3<u32>) {
+    let size = textureDimensions(output);
+    if any(id.xy >= size) {
+        return;
+    }
+
+    let uv = (vec2<f32>(id.xy) + 0.5) / vec2<f32>(size) - 0.5;
+#ifdef STRIPES
+    let t = 0.5 + 0.5 * cos((uv.x + uv.y) * 48.0);
+#else
+    let t = 0.5 + 0.5 * cos(length(uv) * 64.0);
+#endif
+    textureStore(output, id.xy, vec4(mix(palette.low.rgb, palette.high.rgb, t), 1.0));
+}
@@ -0,0 +1,196 @@
+use bevy::{
+    asset::{embedded_asset, RenderAssetUsages},
+    prelude::*,
+    render::RenderPlugin,
+    window::ExitCondition,
+    winit::WinitPlugin,
+};
+use bevy_render::{
+    render_resource::{
+        Buffer, BufferDescriptor, BufferUsages, ColorTargetState, ColorWrites, Extent3d,
+        FragmentState, ImageCopyBuffer, ImageCopyTexture, ImageDataLayout, LoadOp, Maintain,
+        MapMode, MultisampleState, Operations, Origin3d, PrimitiveState, RenderPassColorAttachment,
+        RenderPassDescriptor, RenderPipelineDescriptor, SpecializedRenderPipeline, StoreOp,
+        TextureAspect, TextureDimension, TextureFormat, VertexState,
+    },
+    renderer::RenderDevice,
+};
+
+use gigs::*;
+use input::{JobInputItem, JobOffscreenTarget, JobRenderPipeline};
+
+const SIZE: u32 = 256;
+const FORMAT: TextureFormat = TextureFormat::Rgba8UnormSrgb;
+const OUTPUT_PATH: &str = "headless_bake.png";
+
+// a standalone tool, without a window or a main loop
+fn main() {
+    let mut app = App::new();
+
+    app.add_plugins((
+        DefaultPlugins
+            .set(WindowPlugin {
+                primary_window: None,
+                exit_condition: ExitCondition::DontExit,
+                ..Default::default()
+            })
+            .set(RenderPlugin {
+                // keeps the job from timing out while its pipeline compiles
+                synchronous_pipeline_compilation: true,
+                ..Default::default()
+            })
+            .disable::<WinitPlugin>(),
+        GraphicsJobsPlugin::default(),
+    ))
+    .init_graphics_job::<BakeJob>();
+
+    embedded_asset!(app, "examples", "offscreen_bake.wgsl");
+
+    // the render device is only available once the app's plugins are finished
+    app.finish();
+    app.cleanup();
+
+    let render_device = app.world().resource::<RenderDevice>().clone();
+    let readback = render_device.create_buffer(&BufferDescriptor {
+        label: Some("headless_bake_readback"),
+        size: (SIZE * SIZE * 4) as u64,
+        usage: BufferUsages::COPY_DST | BufferUsages::MAP_READ,
+        mapped_at_creation: false,
+    });
+
+    let result = app.run_job_blocking((
+        BakeJob {
+            readback: readback.clone(),
+        },
+        JobOffscreenTarget::new(SIZE, SIZE, FORMAT),
+    ));
+    if let Err(err) = result {
+        eprintln!("Bake failed: {err:?}");
+        return;
+    }
+
+    // the job's commands have been submitted, so wait for them to finish
+    readback.slice(..).map_async(MapMode::Read, |_| {});
+    render_device.poll(Maintain::Wait);
+    let data = readback.slice(..).get_mapped_range().to_vec();
+    readback.unmap();
+
+    let image = Image::new(
+        Extent3d {
+            width: SIZE,
+            height: SIZE,
+            depth_or_array_layers: 1,
+        },
+        TextureDimension::D2,
+        data,
+        FORMAT,
+        RenderAssetUsages::MAIN_WORLD,
+    );
+    image.try_into_dynamic().unwrap().save(OUTPUT_PATH).unwrap();
+
+    println!("Baked scene slice to {OUTPUT_PATH}");
+}
+
+#[derive(Clone, Component)]
+#[require(JobRenderPipeline<BakePipeline>)]
+struct BakeJob {
+    readback: Buffer,
+}
+
+#[derive(Resource)]
+struct BakePipeline {
+    shader: Handle<Shader>,
+}
+
+impl FromWorld for BakePipeline {
+    fn from_world(world: &mut World) -> Self {
+        let shader = world
+            .resource::<AssetServer>()
+            .load("embedded://headless_bake/offscreen_bake.wgsl");
+
+        Self { shader }
+    }
+}
+
+impl SpecializedRenderPipeline for BakePipeline {
+    type Key = ();
+
+    fn specialize(&self, (): Self::Key) -> RenderPipelineDescriptor {
+        RenderPipelineDescriptor {
+            label: Some("headless_bake_pipeline".into()),
+            layout: Vec::new(),
+            push_constant_ranges: Vec::new(),
+            vertex: VertexState {
+                shader: self.shader.clone(),
+                shader_defs: Vec::new(),
+                entry_point: "vertex".into(),
+                buffers: Vec::new(),
+            },
+            primitive: PrimitiveState::default(),
+            depth_stencil: None,
+            multisample: MultisampleState::default(),
+            fragment: Some(FragmentState {
+                shader: self.shader.clone(),
+                shader_defs: Vec::new(),
+                entry_point: "fragment".into(),
+                targets: vec![Some(ColorTargetState {
+                    format: FORMAT,
+                    blend: None,
+                    write_mask: ColorWrites::ALL,
+                })],
+            }),
+            zero_initialize_workgroup_memory: false,
+        }
+    }
+}
+
+impl GraphicsJob for BakeJob {
+    type In = (JobOffscreenTarget, JobRenderPipeline<BakePipeline>);
+
+    fn run(
+        &self,
+        _world: &World,
+        context: &mut JobRunContext,
+        (target, pipeline): JobInputItem<Self, Self::In>,
+    ) -> Result<(), JobError> {
+        {
+            let mut render_pass = context.begin_render_pass(&RenderPassDescriptor {
+                label: Some("headless_bake_pass"),
+                color_attachments: &[Some(RenderPassColorAttachment {
+                    view: &target.view,
+                    resolve_target: None,
+                    ops: Operations {
+                        load: LoadOp::Clear(Default::default()),
+                        store: StoreOp::Store,
+                    },
+                })],
+                depth_stencil_attachment: None,
+                timestamp_writes: None,
+                occlusion_query_set: None,
+            });
+
+            render_pass.set_pipeline(pipeline);
+            render_pass.draw(0..3, 0..1);
+        }
+
+        context.copy_texture_to_buffer(
+            ImageCopyTexture {
+                texture: &target.texture,
+                mip_level: 0,
+                origin: Origin3d::ZERO,
+                aspect: TextureAspect::All,
+            },
+            ImageCopyBuffer {
+                buffer: &self.readback,
+                layout: ImageDataLayout {
+                    offset: 0,
+                    bytes_per_row: Some(SIZE * 4),
+                    rows_per_image: None,
+                },
+            },
+            target.texture.size(),
+        );
+
+        Ok(())
+    }
+}
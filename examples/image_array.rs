@@ -0,0 +1,181 @@
+use bevy::{
+    asset::{embedded_asset, RenderAssetUsages},
+    prelude::*,
+};
+use bevy_render::{
+    render_resource::{
+        binding_types::texture_2d_array, BindGroupEntries, BindGroupLayout, BindGroupLayoutEntries,
+        ComputePassDescriptor, ComputePipelineDescriptor, Extent3d, ShaderDefVal, ShaderStages,
+        SpecializedComputePipeline, TextureDimension, TextureFormat, TextureSampleType,
+        TextureUsages,
+    },
+    renderer::RenderDevice,
+};
+
+use gigs::*;
+use input::{JobComputePipeline, JobImageArray, JobInputItem, JobStorageTexture, JobWorkgroupSize};
+
+const SIZE: u32 = 64;
+const FORMAT: TextureFormat = TextureFormat::Rgba8Unorm;
+/// The layer of the cookie array the job copies to the sprite.
+const LAYER: u32 = 2;
+
+fn main() -> AppExit {
+    let mut app = App::new();
+
+    app.add_plugins((DefaultPlugins, GraphicsJobsPlugin::default()))
+        .init_graphics_job::<CookieJob>();
+
+    embedded_asset!(app, "examples", "image_array.wgsl");
+
+    app.add_systems(Startup, setup_scene);
+
+    app.run()
+}
+
+/// A light cookie of stripes, `stripes` pixels wide.
+fn cookie(stripes: u32) -> Image {
+    let data = (0..SIZE * SIZE)
+        .flat_map(|index| {
+            let lit = (index % SIZE / stripes + index / SIZE / stripes) % 2 == 0;
+            if lit {
+                [255, 220, 150, 255]
+            } else {
+                [20, 20, 30, 255]
+            }
+        })
+        .collect();
+
+    let mut image = Image::new(
+        Extent3d {
+            width: SIZE,
+            height: SIZE,
+            depth_or_array_layers: 1,
+        },
+        TextureDimension::D2,
+        data,
+        FORMAT,
+        RenderAssetUsages::RENDER_WORLD,
+    );
+    // the cookies are copied into the layers of the array
+    image.texture_descriptor.usage |= TextureUsages::COPY_SRC;
+    image
+}
+
+fn setup_scene(mut images: ResMut<Assets<Image>>, mut commands: Commands) {
+    let cookies = [2, 4, 8, 16].map(|stripes| images.add(cookie(stripes)));
+
+    let mut output = Image::new_fill(
+        Extent3d {
+            width: SIZE,
+            height: SIZE,
+            depth_or_array_layers: 1,
+        },
+        TextureDimension::D2,
+        &[0, 0, 0, 255],
+        FORMAT,
+        RenderAssetUsages::RENDER_WORLD,
+    );
+    output.texture_descriptor.usage |= TextureUsages::STORAGE_BINDING;
+    let output = images.add(output);
+
+    commands.spawn(Camera2d);
+    commands.spawn(Sprite {
+        image: output.clone(),
+        custom_size: Some(Vec2::splat(512.0)),
+        ..Default::default()
+    });
+
+    commands
+        .spawn((
+            CookieJob,
+            JobImageArray::Layers(cookies.into()),
+            JobStorageTexture::write_only(output, FORMAT),
+            JobComputePipeline::<CookiePipeline>::new(LAYER)
+                .with_workgroup_size(UVec3::new(8, 8, 1)),
+        ))
+        .observe(|trigger: Trigger<JobComplete>| {
+            println!("Copied cookie {LAYER}: {:?}", trigger.event().0);
+        });
+}
+
+/// Copies a single layer of the cookie array into the sprite's image.
+#[derive(Clone, Component)]
+struct CookieJob;
+
+#[derive(Resource)]
+struct CookiePipeline {
+    layout: BindGroupLayout,
+    shader: Handle<Shader>,
+}
+
+impl FromWorld for CookiePipeline {
+    fn from_world(world: &mut World) -> Self {
+        let storage = JobStorageTexture::write_only(Handle::default(), FORMAT);
+        let layout = world.resource::<RenderDevice>().create_bind_group_layout(
+            "image_array_layout",
+            &BindGroupLayoutEntries::sequential(
+                ShaderStages::COMPUTE,
+                (
+                    texture_2d_array(TextureSampleType::Float { filterable: false }),
+                    storage.layout_entry(),
+                ),
+            ),
+        );
+        let shader = world
+            .resource::<AssetServer>()
+            .load("embedded://image_array/image_array.wgsl");
+
+        Self { layout, shader }
+    }
+}
+
+impl SpecializedComputePipeline for CookiePipeline {
+    /// The layer of the array to copy.
+    type Key = u32;
+
+    fn specialize(&self, layer: Self::Key) -> ComputePipelineDescriptor {
+        ComputePipelineDescriptor {
+            label: Some("image_array_compute".into()),
+            layout: vec![self.layout.clone()],
+            push_constant_ranges: Vec::new(),
+            shader: self.shader.clone(),
+            shader_defs: vec![ShaderDefVal::UInt("LAYER".into(), layer)],
+            entry_point: "main".into(),
+            zero_initialize_workgroup_memory: false,
+        }
+    }
+}
+
+impl GraphicsJob for CookieJob {
+    type In = (
+        JobImageArray,
+        JobStorageTexture,
+        JobComputePipeline<CookiePipeline>,
+        JobWorkgroupSize<CookiePipeline>,
+    );
+
+    fn run(
+        &self,
+        world: &World,
+        context: &mut JobRunContext,
+        (cookies, output, pipeline, workgroup_size): JobInputItem<Self, Self::In>,
+    ) -> Result<(), JobError> {
+        let bind_group = context.render_device().create_bind_group(
+            "image_array_bind_group",
+            &world.resource::<CookiePipeline>().layout,
+            &BindGroupEntries::sequential((&cookies.view, output.binding())),
+        );
+        let workgroups = workgroup_size.workgroups(UVec3::new(SIZE, SIZE, 1));
+
+        let mut compute_pass = context.begin_compute_pass(&ComputePassDescriptor {
+            label: Some("image_array_compute_pass"),
+            timestamp_writes: None,
+        });
+        compute_pass.set_bind_group(0, &bind_group, &[]);
+        compute_pass.set_pipeline(pipeline);
+        compute_pass.dispatch_workgroups(workgroups.x, workgroups.y, workgroups.z);
+
+        Ok(())
+    }
+}
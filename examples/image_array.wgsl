@@ -0,0 +1,14 @@
+@group(0) @binding(0) var cookies: texture_2d_array<f32>;
+@group(0) @binding(1) var output: texture_storage_2d<rgba8unorm, write>;
+
+@compute @workgroup_size(8, 8, 1)
+fn main(@builtin(global_invocation_id) id: vec3<u32>) {
+    let size = textureDimensions(output);
+    if any(id.xy >= size) {
+        return;
+    }
+
+    // the layer is picked when the pipeline is specialized
+    let cookie = textureLoad(cookies, id.xy, #{LAYER}u, 0);
+    textureStore(output, id.xy, vec4(cookie.rgb, 1.0));
+}
@@ -0,0 +1,202 @@
+use bevy::{
+    asset::embedded_asset, core_pipeline::core_3d::Transparent3d, input::keyboard::KeyboardInput,
+    prelude::*,
+};
+use bevy_render::{
+    render_phase::ViewSortedRenderPhases,
+    render_resource::{
+        binding_types::{storage_buffer_read_only_sized, storage_buffer_sized},
+        BindGroupEntries, BindGroupLayout, BindGroupLayoutEntries, BufferInitDescriptor,
+        BufferUsages, ComputePassDescriptor, ComputePipelineDescriptor, ShaderStages,
+        SpecializedComputePipeline,
+    },
+    renderer::RenderDevice,
+    view::GpuCulling,
+};
+
+use gigs::*;
+use input::{
+    JobComputePipeline, JobIndirectParameters, JobInputItem, JobPhaseView, JobWorkgroupSize,
+};
+
+const WORKGROUP_SIZE: UVec3 = UVec3::new(64, 1, 1);
+
+fn main() -> AppExit {
+    let mut app = App::new();
+
+    app.add_plugins((DefaultPlugins, GraphicsJobsPlugin::default()))
+        .init_graphics_job::<CullJob>()
+        .init_resource::<Culling>();
+
+    embedded_asset!(app, "examples", "indirect_culling.wgsl");
+
+    app.add_systems(Startup, setup_scene)
+        .add_systems(Update, (handle_input, spawn_cull_job));
+
+    app.run()
+}
+
+#[derive(Resource)]
+struct MainCamera(Entity);
+
+#[derive(Resource, Default)]
+struct Culling(bool);
+
+fn setup_scene(
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    mut commands: Commands,
+) {
+    let cube = meshes.add(Cuboid::default());
+
+    // each cube has its own material, so it's drawn in a batch of its own
+    for x in -3..=3 {
+        commands.spawn((
+            Mesh3d(cube.clone()),
+            MeshMaterial3d(materials.add(StandardMaterial {
+                base_color: Color::hsla(x as f32 * 40.0 + 120.0, 0.7, 0.6, 0.8),
+                alpha_mode: AlphaMode::Blend,
+                ..Default::default()
+            })),
+            Transform::from_xyz(x as f32 * 1.5, 0.0, 0.0),
+        ));
+    }
+
+    commands.spawn((PointLight::default(), Transform::from_xyz(4.0, 8.0, 4.0)));
+
+    // draws are only made indirectly for cameras culled on the GPU
+    let camera = commands
+        .spawn((
+            Camera3d::default(),
+            GpuCulling,
+            Transform::from_xyz(0.0, 3.0, 10.0).looking_at(Vec3::ZERO, Vec3::Y),
+        ))
+        .id();
+    commands.insert_resource(MainCamera(camera));
+
+    commands.spawn((
+        Text::from("Press [space] to toggle culling every other transparent batch."),
+        Node {
+            position_type: PositionType::Absolute,
+            left: Val::Px(12.0),
+            top: Val::Px(12.0),
+            ..Default::default()
+        },
+    ));
+}
+
+fn handle_input(mut keyboard_input: EventReader<KeyboardInput>, mut culling: ResMut<Culling>) {
+    for key in keyboard_input.read().filter(|key| key.state.is_pressed()) {
+        if key.key_code == KeyCode::Space {
+            culling.0 = !culling.0;
+        }
+    }
+}
+
+/// The indirect parameters are rewritten every frame, so the batches are culled again
+/// each frame by a new job.
+fn spawn_cull_job(culling: Res<Culling>, camera: Res<MainCamera>, mut commands: Commands) {
+    if culling.0 {
+        commands.spawn((
+            CullJob,
+            JobPhaseView::new(camera.0),
+            JobComputePipeline::<CullPipeline>::default().with_workgroup_size(WORKGROUP_SIZE),
+        ));
+    }
+}
+
+#[derive(Clone, Component)]
+struct CullJob;
+
+#[derive(Resource)]
+struct CullPipeline {
+    layout: BindGroupLayout,
+    shader: Handle<Shader>,
+}
+
+impl FromWorld for CullPipeline {
+    fn from_world(world: &mut World) -> Self {
+        let layout = world.resource::<RenderDevice>().create_bind_group_layout(
+            "indirect_culling_layout",
+            &BindGroupLayoutEntries::sequential(
+                ShaderStages::COMPUTE,
+                (
+                    storage_buffer_sized(false, None),
+                    storage_buffer_read_only_sized(false, None),
+                ),
+            ),
+        );
+        let shader = world
+            .resource::<AssetServer>()
+            .load("embedded://indirect_culling/indirect_culling.wgsl");
+
+        Self { layout, shader }
+    }
+}
+
+impl SpecializedComputePipeline for CullPipeline {
+    type Key = ();
+
+    fn specialize(&self, (): Self::Key) -> ComputePipelineDescriptor {
+        ComputePipelineDescriptor {
+            label: Some("indirect_culling_compute".into()),
+            layout: vec![self.layout.clone()],
+            push_constant_ranges: Vec::new(),
+            shader: self.shader.clone(),
+            shader_defs: Vec::new(),
+            entry_point: "main".into(),
+            zero_initialize_workgroup_memory: false,
+        }
+    }
+}
+
+impl GraphicsJob for CullJob {
+    type In = (
+        JobIndirectParameters<ViewSortedRenderPhases<Transparent3d>>,
+        JobComputePipeline<CullPipeline>,
+        JobWorkgroupSize<CullPipeline>,
+    );
+
+    fn run(
+        &self,
+        world: &World,
+        context: &mut JobRunContext,
+        (parameters, pipeline, workgroup_size): JobInputItem<Self, Self::In>,
+    ) -> Result<(), JobError> {
+        let culled = parameters
+            .indices
+            .iter()
+            .step_by(2)
+            .flat_map(|index| index.to_le_bytes())
+            .collect::<Vec<_>>();
+        if culled.is_empty() {
+            return Ok(());
+        }
+
+        let render_device = context.render_device();
+        let culled_buffer = render_device.create_buffer_with_data(&BufferInitDescriptor {
+            label: Some("indirect_culling_culled"),
+            contents: &culled,
+            usage: BufferUsages::STORAGE,
+        });
+        let bind_group = render_device.create_bind_group(
+            "indirect_culling_bind_group",
+            &world.resource::<CullPipeline>().layout,
+            &BindGroupEntries::sequential((
+                parameters.buffer.as_entire_binding(),
+                culled_buffer.as_entire_binding(),
+            )),
+        );
+        let workgroups = workgroup_size.workgroups(UVec3::new(culled.len() as u32 / 4, 1, 1));
+
+        let mut compute_pass = context.begin_compute_pass(&ComputePassDescriptor {
+            label: Some("indirect_culling_compute_pass"),
+            timestamp_writes: None,
+        });
+        compute_pass.set_bind_group(0, &bind_group, &[]);
+        compute_pass.set_pipeline(pipeline);
+        compute_pass.dispatch_workgroups(workgroups.x, workgroups.y, workgroups.z);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,22 @@
+// the layout of bevy's indirect parameters, shared by indexed and non-indexed draws
+struct IndirectParameters {
+    vertex_or_index_count: u32,
+    instance_count: u32,
+    first_vertex_or_first_index: u32,
+    base_vertex_or_first_instance: u32,
+    first_instance: u32,
+}
+
+@group(0) @binding(0) var<storage, read_write> indirect_parameters: array<IndirectParameters>;
+@group(0) @binding(1) var<storage> culled: array<u32>;
+
+@compute @workgroup_size(64, 1, 1)
+fn main(@builtin(global_invocation_id) id: vec3<u32>) {
+    if id.x >= arrayLength(&culled) {
+        return;
+    }
+
+    // bevy's preprocessing pass counts the visible instances after this runs, so the
+    // batch is culled by drawing no vertices rather than no instances
+    indirect_parameters[culled[id.x]].vertex_or_index_count = 0u;
+}
@@ -0,0 +1,204 @@
+// the `ShaderType` derive emits size checks that newer compilers flag as unused
+#![allow(dead_code)]
+
+use bevy::{
+    asset::{embedded_asset, RenderAssetUsages},
+    pbr::{irradiance_volume::IrradianceVolume, LightProbe},
+    prelude::*,
+};
+use bevy_render::{
+    render_resource::{
+        binding_types::uniform_buffer, encase, BindGroupEntries, BindGroupLayout,
+        BindGroupLayoutEntries, BindingType, BufferInitDescriptor, BufferUsages,
+        ComputePassDescriptor, ComputePipelineDescriptor, Extent3d, ShaderStages, ShaderType,
+        SpecializedComputePipeline, StorageTextureAccess, TextureDimension, TextureFormat,
+        TextureUsages, TextureViewDimension,
+    },
+    renderer::RenderDevice,
+};
+
+use gigs::*;
+use input::{JobComputePipeline, JobInputItem, JobIrradianceVolume, JobWorkgroupSize};
+
+const RESOLUTION: UVec3 = UVec3::new(8, 4, 8);
+const VOXEL_FORMAT: TextureFormat = TextureFormat::Rgba16Float;
+const INTENSITY: f32 = 1800.0;
+
+fn main() -> AppExit {
+    let mut app = App::new();
+
+    app.add_plugins((DefaultPlugins, GraphicsJobsPlugin::default()))
+        .init_graphics_job::<BakeIrradianceJob>()
+        // all indirect light comes from the irradiance volume
+        .insert_resource(AmbientLight::NONE);
+
+    embedded_asset!(app, "examples", "irradiance_bake.wgsl");
+
+    app.add_systems(Startup, setup_scene);
+
+    app.run()
+}
+
+fn setup_scene(
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    mut images: ResMut<Assets<Image>>,
+    mut commands: Commands,
+) {
+    let white = materials.add(Color::WHITE);
+    commands.spawn((
+        Mesh3d(meshes.add(Plane3d::default().mesh().size(8.0, 8.0))),
+        MeshMaterial3d(white.clone()),
+    ));
+    for x in [-2.0, 0.0, 2.0] {
+        commands.spawn((
+            Mesh3d(meshes.add(Sphere::new(0.6))),
+            MeshMaterial3d(white.clone()),
+            Transform::from_xyz(x, 0.6, 0.0),
+        ));
+    }
+
+    // ambient cubes are stored in a texture twice as tall and three times as deep as
+    // the grid, and are only written on the GPU
+    let mut voxels = Image::new_fill(
+        Extent3d {
+            width: RESOLUTION.x,
+            height: RESOLUTION.y * 2,
+            depth_or_array_layers: RESOLUTION.z * 3,
+        },
+        TextureDimension::D3,
+        &[0; 8],
+        VOXEL_FORMAT,
+        RenderAssetUsages::RENDER_WORLD,
+    );
+    voxels.texture_descriptor.usage =
+        TextureUsages::COPY_DST | TextureUsages::STORAGE_BINDING | TextureUsages::TEXTURE_BINDING;
+
+    // light probes are unit cubes, scaled to cover the scene
+    let volume = commands
+        .spawn((
+            LightProbe,
+            IrradianceVolume {
+                voxels: images.add(voxels),
+                intensity: INTENSITY,
+            },
+            Transform::from_xyz(0.0, 2.0, 0.0).with_scale(Vec3::new(8.0, 4.0, 8.0)),
+        ))
+        .id();
+
+    commands.spawn((
+        BakeIrradianceJob,
+        JobIrradianceVolume::new(volume),
+        JobComputePipeline::<BakeIrradiancePipeline>::new(()).with_workgroup_size(UVec3::splat(4)),
+    ));
+
+    commands.spawn((
+        Camera3d::default(),
+        Transform::from_xyz(0.0, 3.0, 7.0).looking_at(Vec3::new(0.0, 0.5, 0.0), Vec3::Y),
+    ));
+}
+
+#[derive(Clone, Component)]
+struct BakeIrradianceJob;
+
+#[derive(ShaderType)]
+struct BakeIrradianceUniform {
+    resolution: UVec3,
+    world_from_uvw: Mat4,
+}
+
+#[derive(Resource)]
+struct BakeIrradiancePipeline {
+    layout: BindGroupLayout,
+    shader: Handle<Shader>,
+}
+
+impl FromWorld for BakeIrradiancePipeline {
+    fn from_world(world: &mut World) -> Self {
+        let layout = world.resource::<RenderDevice>().create_bind_group_layout(
+            "irradiance_bake_layout",
+            &BindGroupLayoutEntries::sequential(
+                ShaderStages::COMPUTE,
+                (
+                    BindingType::StorageTexture {
+                        access: StorageTextureAccess::WriteOnly,
+                        format: VOXEL_FORMAT,
+                        view_dimension: TextureViewDimension::D3,
+                    },
+                    uniform_buffer::<BakeIrradianceUniform>(false),
+                ),
+            ),
+        );
+        let shader = world
+            .resource::<AssetServer>()
+            .load("embedded://irradiance_bake/irradiance_bake.wgsl");
+
+        Self { layout, shader }
+    }
+}
+
+impl SpecializedComputePipeline for BakeIrradiancePipeline {
+    type Key = ();
+
+    fn specialize(&self, (): Self::Key) -> ComputePipelineDescriptor {
+        ComputePipelineDescriptor {
+            label: Some("irradiance_bake_compute".into()),
+            layout: vec![self.layout.clone()],
+            push_constant_ranges: Vec::new(),
+            shader: self.shader.clone(),
+            shader_defs: Vec::new(),
+            entry_point: "main".into(),
+            zero_initialize_workgroup_memory: false,
+        }
+    }
+}
+
+impl GraphicsJob for BakeIrradianceJob {
+    type In = (
+        JobIrradianceVolume,
+        JobComputePipeline<BakeIrradiancePipeline>,
+        JobWorkgroupSize<BakeIrradiancePipeline>,
+    );
+
+    fn run(
+        &self,
+        world: &World,
+        context: &mut JobRunContext,
+        (volume, pipeline, workgroup_size): JobInputItem<Self, Self::In>,
+    ) -> Result<(), JobError> {
+        let mut contents = encase::UniformBuffer::new(Vec::new());
+        contents
+            .write(&BakeIrradianceUniform {
+                resolution: volume.resolution,
+                world_from_uvw: volume.uvw_from_world.inverse(),
+            })
+            .map_err(|_| JobError::ExecutionFailed)?;
+
+        let render_device = context.render_device();
+        let uniforms = render_device.create_buffer_with_data(&BufferInitDescriptor {
+            label: Some("irradiance_bake_uniforms"),
+            contents: &contents.into_inner(),
+            usage: BufferUsages::UNIFORM,
+        });
+        let bind_group = render_device.create_bind_group(
+            "irradiance_bake_bind_group",
+            &world.resource::<BakeIrradiancePipeline>().layout,
+            &BindGroupEntries::sequential((
+                &volume.voxels.texture_view,
+                uniforms.as_entire_binding(),
+            )),
+        );
+        // one invocation per voxel, each writing all six sides of its ambient cube
+        let workgroups = workgroup_size.workgroups(volume.resolution);
+
+        let mut compute_pass = context.begin_compute_pass(&ComputePassDescriptor {
+            label: Some("irradiance_bake_compute_pass"),
+            timestamp_writes: None,
+        });
+        compute_pass.set_bind_group(0, &bind_group, &[]);
+        compute_pass.set_pipeline(pipeline);
+        compute_pass.dispatch_workgroups(workgroups.x, workgroups.y, workgroups.z);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,41 @@
+struct BakeIrradiance {
+    resolution: vec3<u32>,
+    world_from_uvw: mat4x4<f32>,
+}
+
+@group(0) @binding(0) var voxels: texture_storage_3d<rgba16float, write>;
+@group(0) @binding(1) var<uniform> bake: BakeIrradiance;
+
+// a blue sky above, a warm bounce from the ground below, and light from a red wall to
+// the -X side of the volume and a green wall to the +X side, fading with distance.
+// each region of the texture stores the light for surfaces facing one direction, with
+// surfaces facing a negative direction in the upper half of the region.
+@compute @workgroup_size(4, 4, 4)
+fn main(@builtin(global_invocation_id) id: vec3<u32>) {
+    let resolution = bake.resolution;
+    if any(id >= resolution) {
+        return;
+    }
+
+    let uvw = (vec3<f32>(id) + 0.5) / vec3<f32>(resolution);
+    let world_position = (bake.world_from_uvw * vec4(uvw, 1.0)).xyz;
+    let height = saturate(world_position.y * 0.25);
+
+    let sky = vec3(0.35, 0.5, 0.8);
+    let ground = vec3(0.4, 0.3, 0.2) * (1.0 - height);
+    let red = vec3(0.8, 0.1, 0.1) * (1.0 - uvw.x);
+    let green = vec3(0.1, 0.8, 0.1) * uvw.x;
+    let fill = vec3(0.1);
+
+    let negative = vec3(0u, resolution.y, 0u);
+    let x = id;
+    let y = id + vec3(0u, 0u, resolution.z);
+    let z = id + vec3(0u, 0u, resolution.z * 2u);
+
+    textureStore(voxels, x, vec4(green, 1.0));
+    textureStore(voxels, x + negative, vec4(red, 1.0));
+    textureStore(voxels, y, vec4(sky, 1.0));
+    textureStore(voxels, y + negative, vec4(ground, 1.0));
+    textureStore(voxels, z, vec4(fill, 1.0));
+    textureStore(voxels, z + negative, vec4(fill, 1.0));
+}
@@ -0,0 +1,101 @@
+use bevy::{asset::RenderAssetUsages, prelude::*};
+use bevy_render::render_resource::{
+    Extent3d, Origin3d, TextureDimension, TextureFormat, TextureUsages,
+};
+
+use gigs::*;
+use jobs::{ClearTextureJob, GenerateMipmapsJob};
+use meta::JobAfter;
+
+const SIZE: u32 = 64;
+const MIP_LEVELS: u32 = 4;
+
+fn main() -> AppExit {
+    let mut app = App::new();
+
+    app.add_plugins((DefaultPlugins, GraphicsJobsPlugin::default()))
+        .add_systems(Startup, setup_scene)
+        .add_observer(log_completed_jobs);
+
+    app.run()
+}
+
+fn setup_scene(mut images: ResMut<Assets<Image>>, mut commands: Commands) {
+    // the image is uploaded with data for every mip level, all of which the jobs fill in
+    let mut image = Image::new(
+        Extent3d {
+            width: SIZE,
+            height: SIZE,
+            depth_or_array_layers: 1,
+        },
+        TextureDimension::D2,
+        vec![0; mip_chain_len(SIZE, MIP_LEVELS)],
+        TextureFormat::Rgba8Unorm,
+        RenderAssetUsages::RENDER_WORLD,
+    );
+    image.texture_descriptor.mip_level_count = MIP_LEVELS;
+    image.texture_descriptor.usage |= TextureUsages::COPY_DST | TextureUsages::RENDER_ATTACHMENT;
+    let image = images.add(image);
+
+    commands.spawn(Camera2d);
+    commands.spawn(Sprite {
+        image: image.clone(),
+        custom_size: Some(Vec2::splat(256.0)),
+        ..Default::default()
+    });
+
+    // the background is cleared first, then both halves are painted, and the mipmaps are
+    // generated once both are done
+    let half = Extent3d {
+        width: SIZE / 2,
+        height: SIZE,
+        depth_or_array_layers: 1,
+    };
+    let jobs = commands
+        .spawn_job(ClearTextureJob::new(image.clone()).with_value([32, 32, 32, 255]))
+        .fan_out([
+            ClearTextureJob::new(image.clone())
+                .with_value([255, 0, 0, 255])
+                .with_region(Origin3d::ZERO, half)
+                .with_mip_levels(0, Some(1)),
+            ClearTextureJob::new(image.clone())
+                .with_value([0, 0, 255, 255])
+                .with_region(
+                    Origin3d {
+                        x: SIZE / 2,
+                        y: 0,
+                        z: 0,
+                    },
+                    half,
+                )
+                .with_mip_levels(0, Some(1)),
+        ])
+        .then(GenerateMipmapsJob::new(image))
+        .jobs();
+
+    commands.queue(move |world: &mut World| {
+        let [background, left, right, mipmaps] = jobs[..] else {
+            unreachable!();
+        };
+        let after = |job| world.get::<JobAfter>(job).map(|after| after.0.clone());
+        assert_eq!(after(background), None);
+        assert_eq!(after(left), Some(vec![background]));
+        assert_eq!(after(right), Some(vec![background]));
+        assert_eq!(after(mipmaps), Some(vec![left, right]));
+        println!("Spawned a chain of {} jobs", jobs.len());
+    });
+}
+
+fn log_completed_jobs(trigger: Trigger<JobComplete>) {
+    println!(
+        "Job {} completed: {:?}",
+        trigger.entity(),
+        trigger.event().0
+    );
+}
+
+fn mip_chain_len(size: u32, mip_levels: u32) -> usize {
+    (0..mip_levels)
+        .map(|level| ((size >> level).max(1).pow(2) * 4) as usize)
+        .sum()
+}
@@ -0,0 +1,79 @@
+use bevy::prelude::*;
+use gigs::{
+    input::JobInputItem,
+    meta::{JobComparator, JobMeta, JobPriority},
+    GraphicsJob, GraphicsJobsPlugin, InitGraphicsJobExt, JobComplete, JobError,
+    JobExecutionSettings, JobRunContext,
+};
+
+fn main() -> AppExit {
+    let mut app = App::new();
+
+    app.add_plugins(DefaultPlugins)
+        .add_plugins(GraphicsJobsPlugin::default())
+        .init_graphics_job::<StreamJob>();
+
+    // run one job per frame, so that the jobs run in the comparator's order
+    app.insert_resource(JobExecutionSettings {
+        max_jobs_per_frame: 1,
+        ..Default::default()
+    });
+
+    // stream in the chunks nearest to the player first, ignoring priority entirely.
+    // the job component is extracted to the render world, so its fields can be read
+    // from the job's entity.
+    app.insert_resource(JobComparator::new(|a, b| {
+        let distance = |meta: &JobMeta| meta.entity.get::<StreamJob>().map(|job| job.distance);
+        distance(a)
+            .cmp(&distance(b))
+            .then(a.sequence.cmp(&b.sequence))
+    }));
+
+    for (name, distance) in [("far", 300), ("near", 10), ("middle", 80)] {
+        app.world_mut()
+            .spawn((
+                StreamJob { name, distance },
+                JobPriority::non_critical::<1>(),
+            ))
+            .observe(print_done);
+    }
+
+    // despite its priority, this job still waits for the nearer chunks
+    app.world_mut()
+        .spawn((
+            StreamJob {
+                name: "urgent but distant",
+                distance: 200,
+            },
+            JobPriority::non_critical::<100>(),
+        ))
+        .observe(print_done);
+
+    app.run()
+}
+
+fn print_done(trigger: Trigger<JobComplete>, jobs: Query<&StreamJob>) {
+    if let Ok(job) = jobs.get(trigger.entity()) {
+        println!("{} chunk streamed!", job.name);
+    }
+}
+
+#[derive(Clone, Component)]
+struct StreamJob {
+    name: &'static str,
+    distance: u32,
+}
+
+impl GraphicsJob for StreamJob {
+    type In = ();
+
+    fn run(
+        &self,
+        _world: &World,
+        _context: &mut JobRunContext,
+        (): JobInputItem<Self, Self::In>,
+    ) -> Result<(), JobError> {
+        println!("streaming {} chunk, {}m away", self.name, self.distance);
+        Ok(())
+    }
+}
@@ -0,0 +1,164 @@
+use bevy::{
+    asset::{embedded_asset, RenderAssetUsages},
+    prelude::*,
+};
+use bevy_render::{
+    render_resource::{
+        BindGroupEntries, BindGroupLayout, BindGroupLayoutEntries, ComputePassDescriptor,
+        ComputePipelineDescriptor, Extent3d, ShaderDefVal, ShaderStages,
+        SpecializedComputePipeline, TextureDimension, TextureFormat, TextureUsages,
+    },
+    renderer::RenderDevice,
+};
+
+use gigs::*;
+use input::{JobComputePipeline, JobInputItem, JobStorageTexture, JobWorkgroupSize};
+use meta::JobPriority;
+
+const SIZE: u32 = 256;
+const FORMAT: TextureFormat = TextureFormat::Rgba8Unorm;
+
+fn main() -> AppExit {
+    let mut app = App::new();
+
+    // every stripes job gets the default pipeline key and priority, so spawning one
+    // only takes the texture it writes to
+    app.add_plugins((
+        DefaultPlugins,
+        GraphicsJobsPlugin::default(),
+        SpecializedGraphicsJobPlugin::<StripesJob>::default()
+            .with_default(JobPriority::non_critical::<4>)
+            .with_default(|| {
+                JobComputePipeline::<StripesPipeline>::new(StripesKey { count: 8 })
+                    .with_workgroup_size(UVec3::new(8, 8, 1))
+            }),
+    ));
+
+    embedded_asset!(app, "examples", "job_defaults.wgsl");
+
+    app.add_systems(Startup, setup_scene);
+
+    app.run()
+}
+
+fn setup_scene(mut images: ResMut<Assets<Image>>, mut commands: Commands) {
+    let mut new_image = || {
+        let mut image = Image::new_fill(
+            Extent3d {
+                width: SIZE,
+                height: SIZE,
+                depth_or_array_layers: 1,
+            },
+            TextureDimension::D2,
+            &[0, 0, 0, 255],
+            FORMAT,
+            RenderAssetUsages::RENDER_WORLD,
+        );
+        image.texture_descriptor.usage = TextureUsages::COPY_DST
+            | TextureUsages::STORAGE_BINDING
+            | TextureUsages::TEXTURE_BINDING;
+        images.add(image)
+    };
+    let bare = new_image();
+    let overridden = new_image();
+
+    commands.spawn(Camera2d);
+    for (image, x) in [(bare.clone(), -140.0), (overridden.clone(), 140.0)] {
+        commands.spawn((
+            Sprite {
+                image,
+                custom_size: Some(Vec2::splat(256.0)),
+                ..Default::default()
+            },
+            Transform::from_xyz(x, 0.0, 0.0),
+        ));
+    }
+
+    // uses the defaults registered with the plugin
+    commands.spawn((StripesJob, JobStorageTexture::write_only(bare, FORMAT)));
+
+    // components given at spawn replace the defaults
+    commands.spawn((
+        StripesJob,
+        JobStorageTexture::write_only(overridden, FORMAT),
+        JobComputePipeline::<StripesPipeline>::new(StripesKey { count: 24 })
+            .with_workgroup_size(UVec3::new(8, 8, 1)),
+    ));
+}
+
+#[derive(Clone, Component)]
+struct StripesJob;
+
+#[derive(Clone, PartialEq, Eq, Hash)]
+struct StripesKey {
+    count: u32,
+}
+
+#[derive(Resource)]
+struct StripesPipeline {
+    layout: BindGroupLayout,
+    shader: Handle<Shader>,
+}
+
+impl FromWorld for StripesPipeline {
+    fn from_world(world: &mut World) -> Self {
+        let storage = JobStorageTexture::write_only(Handle::default(), FORMAT);
+        let layout = world.resource::<RenderDevice>().create_bind_group_layout(
+            "job_defaults_layout",
+            &BindGroupLayoutEntries::single(ShaderStages::COMPUTE, storage.layout_entry()),
+        );
+        let shader = world
+            .resource::<AssetServer>()
+            .load("embedded://job_defaults/job_defaults.wgsl");
+
+        Self { layout, shader }
+    }
+}
+
+impl SpecializedComputePipeline for StripesPipeline {
+    type Key = StripesKey;
+
+    fn specialize(&self, key: Self::Key) -> ComputePipelineDescriptor {
+        ComputePipelineDescriptor {
+            label: Some("job_defaults_compute".into()),
+            layout: vec![self.layout.clone()],
+            push_constant_ranges: Vec::new(),
+            shader: self.shader.clone(),
+            shader_defs: vec![ShaderDefVal::UInt("STRIPE_COUNT".into(), key.count)],
+            entry_point: "main".into(),
+            zero_initialize_workgroup_memory: false,
+        }
+    }
+}
+
+impl GraphicsJob for StripesJob {
+    type In = (
+        JobStorageTexture,
+        JobComputePipeline<StripesPipeline>,
+        JobWorkgroupSize<StripesPipeline>,
+    );
+
+    fn run(
+        &self,
+        world: &World,
+        context: &mut JobRunContext,
+        (storage, pipeline, workgroup_size): JobInputItem<Self, Self::In>,
+    ) -> Result<(), JobError> {
+        let bind_group = context.render_device().create_bind_group(
+            "job_defaults_bind_group",
+            &world.resource::<StripesPipeline>().layout,
+            &BindGroupEntries::single(storage.binding()),
+        );
+        let workgroups = workgroup_size.workgroups(UVec3::new(SIZE, SIZE, 1));
+
+        let mut compute_pass = context.begin_compute_pass(&ComputePassDescriptor {
+            label: Some("job_defaults_compute_pass"),
+            timestamp_writes: None,
+        });
+        compute_pass.set_bind_group(0, &bind_group, &[]);
+        compute_pass.set_pipeline(pipeline);
+        compute_pass.dispatch_workgroups(workgroups.x, workgroups.y, workgroups.z);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,15 @@
+@group(0) @binding(0) var output: texture_storage_2d<rgba8unorm, write>;
+
+@compute @workgroup_size(8, 8, 1)
+fn main(@builtin(global_invocation_id) id: vec3<u32>) {
+    let size = textureDimensions(output);
+    if any(id.xy >= size) {
+        return;
+    }
+
+    // diagonal stripes, as many as the pipeline was specialized with
+    let uv = (vec2<f32>(id.xy) + 0.5) / vec2<f32>(size);
+    let stripe = fract((uv.x + uv.y) * f32(#{STRIPE_COUNT}) * 0.5) < 0.5;
+    let color = select(vec3(0.1, 0.2, 0.5), vec3(0.9, 0.6, 0.2), stripe);
+    textureStore(output, id.xy, vec4(color, 1.0));
+}
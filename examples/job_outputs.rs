@@ -0,0 +1,100 @@
+use bevy::prelude::*;
+use bevy_render::render_resource::{BufferInitDescriptor, BufferUsages};
+
+use gigs::*;
+use input::{
+    JobDependency, JobInputItem, JobOutput, JobOutputLifetime, JobReadback, JobReadbackResult,
+};
+
+const SQUARES: &str = "squares";
+const LEN: u32 = 8;
+
+fn main() -> AppExit {
+    let mut app = App::new();
+
+    app.add_plugins((DefaultPlugins, GraphicsJobsPlugin::default()))
+        .init_graphics_job::<ProduceJob>()
+        .init_graphics_job::<ConsumeJob>()
+        .add_systems(Startup, spawn_jobs)
+        .add_systems(Update, print_result);
+
+    app.run()
+}
+
+fn spawn_jobs(mut commands: Commands) {
+    // the consumer can be spawned first, since it waits for the output to be published.
+    // The output is only needed once, so it's freed after the consumer runs.
+    commands.spawn((
+        ConsumeJob,
+        JobDependency::new(SQUARES),
+        JobReadback::new((LEN * 4) as u64),
+    ));
+    commands.spawn((
+        ProduceJob,
+        JobOutput::new(SQUARES, JobOutputLifetime::UntilDependentsDone),
+    ));
+}
+
+fn print_result(
+    result: Option<Res<JobReadbackResult<ConsumeJob>>>,
+    mut exit: EventWriter<AppExit>,
+) {
+    let Some(result) = result else {
+        return;
+    };
+
+    let squares = result
+        .data
+        .chunks_exact(4)
+        .map(|bytes| u32::from_le_bytes(bytes.try_into().unwrap()))
+        .collect::<Vec<_>>();
+    println!("Consumed squares: {squares:?}");
+    exit.send(AppExit::Success);
+}
+
+/// Publishes a buffer of square numbers.
+#[derive(Clone, Component)]
+struct ProduceJob;
+
+impl GraphicsJob for ProduceJob {
+    type In = JobOutput;
+
+    fn run(
+        &self,
+        _world: &World,
+        context: &mut JobRunContext,
+        output: JobInputItem<Self, Self::In>,
+    ) -> Result<(), JobError> {
+        let render_device = context.render_device();
+        let squares = (0..LEN)
+            .flat_map(|i| (i * i).to_le_bytes())
+            .collect::<Vec<_>>();
+        let buffer = render_device.create_buffer_with_data(&BufferInitDescriptor {
+            label: Some("squares"),
+            contents: &squares,
+            usage: BufferUsages::STORAGE | BufferUsages::COPY_SRC,
+        });
+
+        output.publish(buffer);
+        Ok(())
+    }
+}
+
+/// Copies the published buffer into a readback buffer.
+#[derive(Clone, Component)]
+struct ConsumeJob;
+
+impl GraphicsJob for ConsumeJob {
+    type In = (JobDependency, JobReadback);
+
+    fn run(
+        &self,
+        _world: &World,
+        context: &mut JobRunContext,
+        (squares, readback): JobInputItem<Self, Self::In>,
+    ) -> Result<(), JobError> {
+        let squares = squares.buffer().ok_or(JobError::InputsFailed)?;
+        context.copy_buffer_to_buffer(squares, 0, readback, 0, readback.size());
+        Ok(())
+    }
+}
@@ -0,0 +1,121 @@
+use bevy::{core_pipeline::core_3d::Transparent3d, input::keyboard::KeyboardInput, prelude::*};
+use bevy_render::render_phase::ViewSortedRenderPhases;
+
+use gigs::*;
+use input::{JobInputItem, JobPhaseView, JobRenderPhase};
+
+fn main() -> AppExit {
+    let mut app = App::new();
+
+    app.add_plugins((DefaultPlugins, GraphicsJobsPlugin::default()))
+        .init_graphics_job::<CountVisibleJob>();
+
+    app.insert_resource(JobExecutionSettings {
+        // keep completed counts around for a frame, so their results can be read
+        done_retention_frames: 1,
+        ..Default::default()
+    });
+
+    app.add_systems(Startup, setup_scene)
+        .add_systems(Update, (rotate_camera, spawn_counts, report_counts));
+
+    app.run()
+}
+
+#[derive(Resource)]
+struct MainCamera(Entity);
+
+fn setup_scene(
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    mut commands: Commands,
+) {
+    let cube = meshes.add(Cuboid::default());
+    let material = materials.add(StandardMaterial {
+        base_color: Color::srgba(0.3, 0.6, 0.9, 0.6),
+        alpha_mode: AlphaMode::Blend,
+        ..Default::default()
+    });
+
+    // a ring of cubes, only some of which are in view at a time
+    for i in 0..12 {
+        let angle = i as f32 / 12.0 * core::f32::consts::TAU;
+        commands.spawn((
+            Mesh3d(cube.clone()),
+            MeshMaterial3d(material.clone()),
+            Transform::from_xyz(angle.cos() * 6.0, 0.0, angle.sin() * 6.0),
+        ));
+    }
+
+    commands.spawn((PointLight::default(), Transform::from_xyz(0.0, 8.0, 0.0)));
+
+    let camera = commands
+        .spawn((Camera3d::default(), Transform::from_xyz(0.0, 1.0, 0.0)))
+        .id();
+    commands.insert_resource(MainCamera(camera));
+
+    commands.spawn((
+        Text::from("Press [space] to count the cubes in view."),
+        Node {
+            position_type: PositionType::Absolute,
+            left: Val::Px(12.0),
+            top: Val::Px(12.0),
+            ..Default::default()
+        },
+    ));
+}
+
+fn rotate_camera(time: Res<Time>, camera: Res<MainCamera>, mut transforms: Query<&mut Transform>) {
+    if let Ok(mut transform) = transforms.get_mut(camera.0) {
+        transform.rotate_y(time.delta_secs() * 0.3);
+    }
+}
+
+fn spawn_counts(
+    mut keyboard_input: EventReader<KeyboardInput>,
+    camera: Res<MainCamera>,
+    mut commands: Commands,
+) {
+    for key in keyboard_input.read().filter(|key| key.state.is_pressed()) {
+        if key.key_code == KeyCode::Space {
+            commands.spawn((CountVisibleJob, JobPhaseView::new(camera.0)));
+        }
+    }
+}
+
+/// The result of a [`CountVisibleJob`].
+struct VisibleCount(usize);
+
+/// Reads the results of the counts that completed, once they're done.
+fn report_counts(
+    counts: Jobs<CountVisibleJob>,
+    results: Res<JobResults>,
+    mut text: Single<&mut Text>,
+) {
+    for (job, _, status) in counts.iter() {
+        if status != JobStatus::Done(Ok(())) {
+            continue;
+        }
+        if let Some(VisibleCount(count)) = results.get(job) {
+            text.0 = format!("Press [space] to count the cubes in view.\nCounted {count} cubes.");
+        }
+    }
+}
+
+#[derive(Clone, Component)]
+struct CountVisibleJob;
+
+impl GraphicsJob for CountVisibleJob {
+    type In = JobRenderPhase<ViewSortedRenderPhases<Transparent3d>>;
+
+    fn run(
+        &self,
+        _world: &World,
+        context: &mut JobRunContext,
+        transparent: JobInputItem<Self, Self::In>,
+    ) -> Result<(), JobError> {
+        // only the cubes that weren't culled are queued in the phase
+        context.set_result(VisibleCount(transparent.phase.items.len()));
+        Ok(())
+    }
+}
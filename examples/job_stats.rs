@@ -0,0 +1,83 @@
+use bevy::{asset::RenderAssetUsages, prelude::*, render::diagnostic::RenderDiagnosticsPlugin};
+use bevy_render::render_resource::{Extent3d, TextureDimension, TextureFormat, TextureUsages};
+
+use gigs::*;
+use jobs::GenerateMipmapsJob;
+
+const SIZE: u32 = 1024;
+const RUNS: u64 = 200;
+
+// run with `cargo run --example job_stats --features diagnostics`
+fn main() -> AppExit {
+    let mut app = App::new();
+
+    // GPU times are read from the spans `RenderDiagnosticsPlugin` measures
+    app.add_plugins((
+        DefaultPlugins,
+        RenderDiagnosticsPlugin,
+        GraphicsJobsPlugin::default(),
+    ))
+    .insert_resource(JobStats::new(JobStatsWindow::Session).with_smoothing(0.05))
+    .add_systems(Startup, setup_image)
+    .add_systems(Update, (spawn_job, report_stats).chain());
+
+    app.run()
+}
+
+#[derive(Resource)]
+struct MipmappedImage(Handle<Image>);
+
+fn setup_image(mut images: ResMut<Assets<Image>>, mut commands: Commands) {
+    let mip_levels = SIZE.ilog2() + 1;
+    let data = (0..mip_levels)
+        .flat_map(|level| [64, 128, 255, 255].repeat(((SIZE >> level).pow(2)) as usize))
+        .collect();
+
+    let mut image = Image::new(
+        Extent3d {
+            width: SIZE,
+            height: SIZE,
+            depth_or_array_layers: 1,
+        },
+        TextureDimension::D2,
+        data,
+        TextureFormat::Rgba8Unorm,
+        RenderAssetUsages::RENDER_WORLD,
+    );
+    image.texture_descriptor.mip_level_count = mip_levels;
+    image.texture_descriptor.usage |= TextureUsages::RENDER_ATTACHMENT;
+
+    commands.spawn(Camera2d);
+    commands.insert_resource(MipmappedImage(images.add(image)));
+}
+
+fn spawn_job(image: Res<MipmappedImage>, mut commands: Commands) {
+    commands.spawn(GenerateMipmapsJob::new(image.0.clone()));
+}
+
+/// Prints the statistics of the mipmap jobs once enough of them completed.
+fn report_stats(stats: Res<JobStats>, mut exit: EventWriter<AppExit>) {
+    let Some(mipmaps) = stats.get::<GenerateMipmapsJob>() else {
+        return;
+    };
+    if mipmaps.completed < RUNS {
+        return;
+    }
+
+    println!(
+        "{} jobs of {} completed, {} failed",
+        mipmaps.completed, mipmaps.label, mipmaps.failed
+    );
+    if let Some(frames) = mipmaps.frames_to_ready {
+        println!("Waited {frames:.1} frames for their inputs on average");
+    }
+    match mipmaps.gpu_time {
+        Some(gpu_time) => println!(
+            "Took {:?} of GPU time on average, between {:?} and {:?}",
+            gpu_time.average, gpu_time.min, gpu_time.max
+        ),
+        // timestamp queries aren't supported everywhere
+        None => println!("No GPU times were measured on this adapter"),
+    }
+    exit.send(AppExit::Success);
+}
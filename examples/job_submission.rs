@@ -0,0 +1,102 @@
+use bevy::prelude::*;
+use bevy_render::{
+    render_resource::{
+        Buffer, BufferDescriptor, BufferInitDescriptor, BufferUsages, Maintain, MapMode,
+    },
+    renderer::RenderDevice,
+};
+
+use gigs::*;
+use input::JobInputItem;
+
+const VALUES: [u32; 4] = [1, 4, 9, 16];
+const SIZE: u64 = size_of::<[u32; 4]>() as u64;
+
+fn main() -> AppExit {
+    let mut app = App::new();
+
+    app.add_plugins((DefaultPlugins, GraphicsJobsPlugin::default()))
+        .init_graphics_job::<UploadJob>()
+        .add_systems(Startup, spawn_upload);
+
+    app.run()
+}
+
+fn spawn_upload(render_device: Res<RenderDevice>, mut commands: Commands) {
+    let readback = render_device.create_buffer(&BufferDescriptor {
+        label: Some("job_submission_readback"),
+        size: SIZE,
+        usage: BufferUsages::COPY_DST | BufferUsages::MAP_READ,
+        mapped_at_creation: false,
+    });
+
+    commands
+        .spawn(UploadJob {
+            readback: readback.clone(),
+        })
+        .observe(
+            move |trigger: Trigger<JobComplete>,
+                  submissions: Res<JobSubmissions>,
+                  render_device: Res<RenderDevice>,
+                  mut exit: EventWriter<AppExit>| {
+                assert_eq!(trigger.event().0, Ok(()));
+                let submission = submissions
+                    .get(trigger.entity())
+                    .expect("successful jobs should have a submission")
+                    .clone();
+
+                // rather than waiting for everything submitted so far, only wait for the
+                // submission holding the job's copy, which also maps the buffer
+                let slice = readback.slice(..);
+                slice.map_async(MapMode::Read, |result| result.unwrap());
+                render_device.poll(Maintain::WaitForSubmissionIndex(submission));
+
+                let values = read_u32s(&slice.get_mapped_range());
+                readback.unmap();
+                println!("Read back {values:?}");
+                assert_eq!(values, VALUES);
+                exit.send(AppExit::Success);
+            },
+        );
+}
+
+fn read_u32s(bytes: &[u8]) -> Vec<u32> {
+    bytes
+        .chunks_exact(4)
+        .map(|bytes| u32::from_le_bytes(bytes.try_into().unwrap()))
+        .collect()
+}
+
+/// Copies a few values into a mappable buffer.
+#[derive(Clone, Component)]
+struct UploadJob {
+    readback: Buffer,
+}
+
+impl GraphicsJob for UploadJob {
+    type In = ();
+
+    fn run(
+        &self,
+        _world: &World,
+        context: &mut JobRunContext,
+        (): JobInputItem<Self, Self::In>,
+    ) -> Result<(), JobError> {
+        let contents = VALUES
+            .iter()
+            .flat_map(|value| value.to_le_bytes())
+            .collect::<Vec<_>>();
+        let upload = context
+            .render_device()
+            .create_buffer_with_data(&BufferInitDescriptor {
+                label: Some("job_submission_upload"),
+                contents: &contents,
+                usage: BufferUsages::COPY_SRC,
+            });
+        context
+            .command_encoder()
+            .copy_buffer_to_buffer(&upload, 0, &self.readback, 0, SIZE);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,110 @@
+use bevy::{input::keyboard::KeyboardInput, prelude::*};
+use bevy_render::{Render, RenderApp, RenderSet};
+
+use gigs::*;
+use input::JobInputItem;
+
+fn main() -> AppExit {
+    let mut app = App::new();
+
+    app.add_plugins((DefaultPlugins, GraphicsJobsPlugin::default()))
+        .init_graphics_job::<BakeJob>()
+        .add_systems(Startup, setup_scene)
+        .add_systems(Update, (spawn_bakes, summarize_bakes));
+
+    app.insert_resource(JobExecutionSettings {
+        // spread the bakes over several frames, so their progress can be seen
+        max_submits_per_frame: 2,
+        // keep completed bakes around for a second, to summarize their results
+        done_retention_frames: 60,
+        ..Default::default()
+    });
+
+    app.sub_app_mut(RenderApp)
+        .add_systems(Render, log_running_bakes.in_set(RenderSet::Cleanup));
+
+    app.run()
+}
+
+fn setup_scene(mut commands: Commands) {
+    commands.spawn(Camera2d);
+    commands.spawn((
+        Text::default(),
+        Node {
+            position_type: PositionType::Absolute,
+            left: Val::Px(12.0),
+            top: Val::Px(12.0),
+            ..Default::default()
+        },
+    ));
+}
+
+fn spawn_bakes(mut keyboard_input: EventReader<KeyboardInput>, mut commands: Commands) {
+    for key in keyboard_input.read().filter(|key| key.state.is_pressed()) {
+        if key.key_code == KeyCode::Space {
+            for chunks in 1..=8 {
+                commands.spawn(BakeJob { chunks });
+            }
+        }
+    }
+}
+
+/// Summarizes the bakes in the main world, where pending bakes haven't completed yet.
+fn summarize_bakes(bakes: Jobs<BakeJob>, mut text: Single<&mut Text>) {
+    let (mut pending, mut done) = (0, 0);
+    for (_, _, status) in bakes.iter() {
+        match status {
+            JobStatus::Done(_) => done += 1,
+            _ => pending += 1,
+        }
+    }
+
+    text.0 = format!(
+        "Press [space] to spawn a batch of bakes.\n\
+         Pending: {pending}, recently done: {done}"
+    );
+}
+
+/// Logs the bakes that are partway through their work, from the render world.
+fn log_running_bakes(bakes: Jobs<BakeJob>) {
+    let running = bakes
+        .iter()
+        .filter(|(_, _, status)| *status == JobStatus::Running)
+        .map(|(_, bake, _)| bake.chunks)
+        .collect::<Vec<_>>();
+    if !running.is_empty() {
+        info!("bakes running with chunk counts {running:?}");
+    }
+}
+
+/// A bake that records its work in several chunks.
+#[derive(Clone, Component)]
+struct BakeJob {
+    chunks: u32,
+}
+
+impl GraphicsJob for BakeJob {
+    type In = ();
+
+    fn run(
+        &self,
+        _world: &World,
+        _context: &mut JobRunContext,
+        (): JobInputItem<Self, Self::In>,
+    ) -> Result<(), JobError> {
+        Ok(())
+    }
+
+    fn run_chunk(
+        &self,
+        _world: &World,
+        context: &mut JobRunContext,
+        (): JobInputItem<Self, Self::In>,
+    ) -> Result<JobChunk, JobError> {
+        if context.chunk() + 1 < self.chunks {
+            Ok(JobChunk::Yield)
+        } else {
+            Ok(JobChunk::Done)
+        }
+    }
+}
@@ -0,0 +1,95 @@
+use bevy::{input::keyboard::KeyboardInput, prelude::*};
+
+use gigs::*;
+use input::JobInputItem;
+
+fn main() -> AppExit {
+    let mut app = App::new();
+
+    app.add_plugins((DefaultPlugins, GraphicsJobsPlugin::default()))
+        .init_graphics_job::<CountJob>()
+        .init_resource::<JobCounts>()
+        .add_systems(Startup, setup_scene)
+        .add_systems(Update, (spawn_jobs, update_text));
+
+    app.run()
+}
+
+/// Gameplay state updated by the jobs' observers.
+#[derive(Resource, Default)]
+struct JobCounts {
+    done: u32,
+    failed: u32,
+}
+
+fn setup_scene(mut commands: Commands) {
+    commands.spawn(Camera2d);
+    commands.spawn((
+        Text::default(),
+        Node {
+            position_type: PositionType::Absolute,
+            left: Val::Px(12.0),
+            top: Val::Px(12.0),
+            ..Default::default()
+        },
+    ));
+}
+
+fn spawn_jobs(mut keyboard_input: EventReader<KeyboardInput>, mut commands: Commands) {
+    for key in keyboard_input.read().filter(|key| key.state.is_pressed()) {
+        let fail = match key.key_code {
+            KeyCode::Space => false,
+            KeyCode::KeyF => true,
+            _ => continue,
+        };
+
+        // each job reacts to its own transitions, without polling or reading global events
+        commands
+            .spawn(CountJob { fail })
+            .on_job_ready(|trigger: Trigger<OnJobReady>| {
+                info!("job {} is ready", trigger.entity());
+            })
+            .on_job_done(
+                |_trigger: Trigger<OnJobDone>, mut counts: ResMut<JobCounts>| {
+                    counts.done += 1;
+                },
+            )
+            .on_job_failed(
+                |trigger: Trigger<OnJobFailed>, mut counts: ResMut<JobCounts>| {
+                    warn!("job {} failed: {:?}", trigger.entity(), trigger.event().0);
+                    counts.failed += 1;
+                },
+            );
+    }
+}
+
+fn update_text(counts: Res<JobCounts>, mut text: Single<&mut Text>) {
+    if counts.is_changed() {
+        text.0 = format!(
+            "Press [space] to spawn a job, or [f] to spawn a failing one.\n\
+             Done: {}, failed: {}",
+            counts.done, counts.failed
+        );
+    }
+}
+
+#[derive(Clone, Component)]
+struct CountJob {
+    fail: bool,
+}
+
+impl GraphicsJob for CountJob {
+    type In = ();
+
+    fn run(
+        &self,
+        _world: &World,
+        _context: &mut JobRunContext,
+        (): JobInputItem<Self, Self::In>,
+    ) -> Result<(), JobError> {
+        if self.fail {
+            return Err(JobError::ExecutionFailed);
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,188 @@
+use bevy::{
+    asset::embedded_asset, ecs::event::Events, prelude::*, render::RenderPlugin,
+    window::ExitCondition, winit::WinitPlugin,
+};
+use bevy_render::render_resource::{
+    ColorTargetState, ColorWrites, FragmentState, ImageCopyBuffer, ImageCopyTexture,
+    ImageDataLayout, LoadOp, MultisampleState, Operations, Origin3d, PrimitiveState,
+    RenderPassColorAttachment, RenderPassDescriptor, RenderPipelineDescriptor,
+    SpecializedRenderPipeline, StoreOp, TextureAspect, TextureFormat, VertexState,
+};
+
+use gigs::*;
+use input::{
+    JobFileFormat, JobFileOutput, JobFileTexture, JobFileWritten, JobInputItem, JobOffscreenTarget,
+    JobReadback, JobRenderPipeline,
+};
+
+const TEXTURE: JobFileTexture = JobFileTexture::new(256, 256, TextureFormat::Rgba8UnormSrgb);
+const OUTPUT_PATH: &str = "ktx2_bake.ktx2";
+/// The maximum number of updates to wait for the file after the job completes.
+const MAX_UPDATES: u32 = 64;
+
+// a standalone texture baker, without a window or a main loop
+fn main() -> AppExit {
+    let mut app = App::new();
+
+    app.add_plugins((
+        DefaultPlugins
+            .set(WindowPlugin {
+                primary_window: None,
+                exit_condition: ExitCondition::DontExit,
+                ..Default::default()
+            })
+            .set(RenderPlugin {
+                // keeps the job from timing out while its pipeline compiles
+                synchronous_pipeline_compilation: true,
+                ..Default::default()
+            })
+            .disable::<WinitPlugin>(),
+        GraphicsJobsPlugin::default(),
+    ))
+    .init_graphics_job::<TilesJob>();
+
+    embedded_asset!(app, "examples", "ktx2_bake.wgsl");
+
+    let result = app.run_job_blocking((
+        TilesJob,
+        JobOffscreenTarget::new(TEXTURE.width, TEXTURE.height, TEXTURE.format),
+        JobReadback::new(TEXTURE.readback_size()),
+        JobFileOutput::new(OUTPUT_PATH, JobFileFormat::Ktx2(TEXTURE)),
+    ));
+    if let Err(err) = result {
+        eprintln!("Bake failed: {err:?}");
+        return AppExit::error();
+    }
+
+    // the job completes once its commands are submitted, and the file is written
+    // a few updates later, once the result is read back
+    for _ in 0..MAX_UPDATES {
+        app.update();
+        let written = app
+            .world_mut()
+            .resource_mut::<Events<JobFileWritten>>()
+            .drain()
+            .next();
+        match written.map(|written| written.result) {
+            Some(Ok(())) => {
+                println!("Baked tiles to {OUTPUT_PATH}");
+                return AppExit::Success;
+            }
+            Some(Err(err)) => {
+                eprintln!("Writing {OUTPUT_PATH} failed: {err:?}");
+                return AppExit::error();
+            }
+            None => {}
+        }
+    }
+
+    eprintln!("Timed out waiting for {OUTPUT_PATH} to be written");
+    AppExit::error()
+}
+
+#[derive(Clone, Component)]
+#[require(JobRenderPipeline<TilesPipeline>)]
+struct TilesJob;
+
+#[derive(Resource)]
+struct TilesPipeline {
+    shader: Handle<Shader>,
+}
+
+impl FromWorld for TilesPipeline {
+    fn from_world(world: &mut World) -> Self {
+        let shader = world
+            .resource::<AssetServer>()
+            .load("embedded://ktx2_bake/ktx2_bake.wgsl");
+
+        Self { shader }
+    }
+}
+
+impl SpecializedRenderPipeline for TilesPipeline {
+    type Key = ();
+
+    fn specialize(&self, (): Self::Key) -> RenderPipelineDescriptor {
+        RenderPipelineDescriptor {
+            label: Some("ktx2_bake_pipeline".into()),
+            layout: Vec::new(),
+            push_constant_ranges: Vec::new(),
+            vertex: VertexState {
+                shader: self.shader.clone(),
+                shader_defs: Vec::new(),
+                entry_point: "vertex".into(),
+                buffers: Vec::new(),
+            },
+            primitive: PrimitiveState::default(),
+            depth_stencil: None,
+            multisample: MultisampleState::default(),
+            fragment: Some(FragmentState {
+                shader: self.shader.clone(),
+                shader_defs: Vec::new(),
+                entry_point: "fragment".into(),
+                targets: vec![Some(ColorTargetState {
+                    format: TEXTURE.format,
+                    blend: None,
+                    write_mask: ColorWrites::ALL,
+                })],
+            }),
+            zero_initialize_workgroup_memory: false,
+        }
+    }
+}
+
+impl GraphicsJob for TilesJob {
+    type In = (
+        JobOffscreenTarget,
+        JobReadback,
+        JobRenderPipeline<TilesPipeline>,
+    );
+
+    fn run(
+        &self,
+        _world: &World,
+        context: &mut JobRunContext,
+        (target, readback, pipeline): JobInputItem<Self, Self::In>,
+    ) -> Result<(), JobError> {
+        {
+            let mut render_pass = context.begin_render_pass(&RenderPassDescriptor {
+                label: Some("ktx2_bake_pass"),
+                color_attachments: &[Some(RenderPassColorAttachment {
+                    view: &target.view,
+                    resolve_target: None,
+                    ops: Operations {
+                        load: LoadOp::Clear(Default::default()),
+                        store: StoreOp::Store,
+                    },
+                })],
+                depth_stencil_attachment: None,
+                timestamp_writes: None,
+                occlusion_query_set: None,
+            });
+
+            render_pass.set_pipeline(pipeline);
+            render_pass.draw(0..3, 0..1);
+        }
+
+        // rows are copied with the padding `wgpu` requires, which is stripped from the file
+        context.copy_texture_to_buffer(
+            ImageCopyTexture {
+                texture: &target.texture,
+                mip_level: 0,
+                origin: Origin3d::ZERO,
+                aspect: TextureAspect::All,
+            },
+            ImageCopyBuffer {
+                buffer: readback,
+                layout: ImageDataLayout {
+                    offset: 0,
+                    bytes_per_row: Some(TEXTURE.padded_bytes_per_row()),
+                    rows_per_image: None,
+                },
+            },
+            target.texture.size(),
+        );
+
+        Ok(())
+    }
+}
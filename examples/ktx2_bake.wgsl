@@ -0,0 +1,26 @@
+// a procedural tile pattern, with mortar between slightly varied bricks
+
+@vertex
+fn vertex(@builtin(vertex_index) index: u32) -> @builtin(position) vec4<f32> {
+    let uv = vec2<f32>(f32(index >> 1u), f32(index & 1u)) * 2.0;
+    return vec4<f32>(uv * 2.0 - 1.0, 0.0, 1.0);
+}
+
+fn hash(p: vec2<f32>) -> f32 {
+    return fract(sin(dot(p, vec2(12.9898, 78.233))) * 43758.5453);
+}
+
+@fragment
+fn fragment(@builtin(position) position: vec4<f32>) -> @location(0) vec4<f32> {
+    var p = position.xy / vec2(64.0, 32.0);
+    // every other row is offset by half a brick
+    p.x += 0.5 * f32(i32(floor(p.y)) % 2);
+
+    let brick = floor(p);
+    let edge = min(fract(p), 1.0 - fract(p)) * vec2(64.0, 32.0);
+    let mortar = 1.0 - smoothstep(1.0, 2.5, min(edge.x, edge.y));
+
+    let tint = mix(0.8, 1.1, hash(brick));
+    let color = mix(vec3(0.55, 0.25, 0.18) * tint, vec3(0.75, 0.72, 0.68), mortar);
+    return vec4(color, 1.0);
+}
@@ -0,0 +1,269 @@
+use bevy::{
+    asset::{embedded_asset, RenderAssetUsages},
+    pbr::Lightmap,
+    prelude::*,
+};
+use bevy_render::{
+    mesh::VertexAttributeValues,
+    render_resource::{
+        binding_types::{texture_2d, uniform_buffer_sized},
+        encase, BindGroupEntries, BindGroupLayout, BindGroupLayoutEntries, BindingType,
+        BufferInitDescriptor, BufferUsages, ComputePassDescriptor, ComputePipelineDescriptor,
+        Extent3d, ImageCopyTexture, Origin3d, ShaderStages, SpecializedComputePipeline,
+        StorageTextureAccess, TextureAspect, TextureDescriptor, TextureDimension, TextureFormat,
+        TextureSampleType, TextureUsages, TextureViewDescriptor, TextureViewDimension,
+    },
+    renderer::RenderDevice,
+};
+
+use gigs::*;
+use input::{JobComputePipeline, JobInputItem, JobLightmap, JobWorkgroupSize};
+
+const ATLAS_SIZE: UVec2 = UVec2::new(256, 128);
+const LIGHTMAP_FORMAT: TextureFormat = TextureFormat::Rgba8Unorm;
+
+fn main() -> AppExit {
+    let mut app = App::new();
+
+    app.add_plugins((DefaultPlugins, GraphicsJobsPlugin::default()))
+        .init_graphics_job::<DenoiseLightmapJob>()
+        .insert_resource(AmbientLight::NONE);
+
+    embedded_asset!(app, "examples", "lightmap_denoise.wgsl");
+
+    app.add_systems(Startup, setup_scene);
+
+    app.run()
+}
+
+fn setup_scene(
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    mut images: ResMut<Assets<Image>>,
+    mut commands: Commands,
+) {
+    // lightmapped meshes need a second UV layer, which here matches the first
+    let mut plane = Plane3d::default().mesh().size(3.0, 3.0).build();
+    if let Some(VertexAttributeValues::Float32x2(uvs)) = plane.attribute(Mesh::ATTRIBUTE_UV_0) {
+        let uvs = uvs.clone();
+        plane.insert_attribute(Mesh::ATTRIBUTE_UV_1, uvs);
+    }
+    let plane = meshes.add(plane);
+    let material = materials.add(StandardMaterial {
+        lightmap_exposure: 10000.0,
+        ..Default::default()
+    });
+
+    // both planes share a noisy atlas, as if baked with too few samples
+    let atlas = images.add(noisy_atlas());
+    let mut spawn_plane = |x: f32, uv_rect: Rect| {
+        commands
+            .spawn((
+                Mesh3d(plane.clone()),
+                MeshMaterial3d(material.clone()),
+                Transform::from_xyz(x, 0.0, 0.0),
+                Lightmap {
+                    image: atlas.clone(),
+                    uv_rect,
+                },
+            ))
+            .id()
+    };
+    let denoised = spawn_plane(-1.6, Rect::new(0.0, 0.0, 0.5, 1.0));
+    spawn_plane(1.6, Rect::new(0.5, 0.0, 1.0, 1.0));
+
+    // only the left plane's region of the atlas is denoised
+    commands.spawn((
+        DenoiseLightmapJob,
+        JobLightmap::new(denoised),
+        JobComputePipeline::<DenoisePipeline>::new(()).with_workgroup_size(UVec3::new(8, 8, 1)),
+    ));
+
+    commands.spawn((
+        Camera3d::default(),
+        Transform::from_xyz(0.0, 4.5, 3.0).looking_at(Vec3::ZERO, Vec3::Y),
+    ));
+}
+
+/// A warm gradient with white noise on top, only ever written to on the GPU after it's
+/// uploaded.
+fn noisy_atlas() -> Image {
+    let mut data = Vec::with_capacity((ATLAS_SIZE.x * ATLAS_SIZE.y * 4) as usize);
+    let mut seed = 0x2545_f491_u32;
+    for y in 0..ATLAS_SIZE.y {
+        for x in 0..ATLAS_SIZE.x {
+            // xorshift, to avoid pulling in a random number generator
+            seed ^= seed << 13;
+            seed ^= seed >> 17;
+            seed ^= seed << 5;
+            let noise = (seed % 96) as f32 / 255.0;
+
+            let u = (x % (ATLAS_SIZE.x / 2)) as f32 / (ATLAS_SIZE.x / 2) as f32;
+            let v = y as f32 / ATLAS_SIZE.y as f32;
+            let light = Vec3::new(0.9, 0.7, 0.5) * (1.0 - 0.6 * u) * (1.0 - 0.4 * v) + noise;
+            let [r, g, b] = (light.clamp(Vec3::ZERO, Vec3::ONE) * 255.0).to_array();
+            data.extend_from_slice(&[r as u8, g as u8, b as u8, 255]);
+        }
+    }
+
+    let mut atlas = Image::new(
+        Extent3d {
+            width: ATLAS_SIZE.x,
+            height: ATLAS_SIZE.y,
+            depth_or_array_layers: 1,
+        },
+        TextureDimension::D2,
+        data,
+        LIGHTMAP_FORMAT,
+        RenderAssetUsages::RENDER_WORLD,
+    );
+    atlas.texture_descriptor.usage = TextureUsages::COPY_DST
+        | TextureUsages::COPY_SRC
+        | TextureUsages::STORAGE_BINDING
+        | TextureUsages::TEXTURE_BINDING;
+    atlas
+}
+
+#[derive(Clone, Component)]
+struct DenoiseLightmapJob;
+
+#[derive(Resource)]
+struct DenoisePipeline {
+    layout: BindGroupLayout,
+    shader: Handle<Shader>,
+}
+
+impl FromWorld for DenoisePipeline {
+    fn from_world(world: &mut World) -> Self {
+        let layout = world.resource::<RenderDevice>().create_bind_group_layout(
+            "lightmap_denoise_layout",
+            &BindGroupLayoutEntries::sequential(
+                ShaderStages::COMPUTE,
+                (
+                    texture_2d(TextureSampleType::Float { filterable: false }),
+                    BindingType::StorageTexture {
+                        access: StorageTextureAccess::WriteOnly,
+                        format: LIGHTMAP_FORMAT,
+                        view_dimension: TextureViewDimension::D2,
+                    },
+                    uniform_buffer_sized(false, None),
+                ),
+            ),
+        );
+        let shader = world
+            .resource::<AssetServer>()
+            .load("embedded://lightmap_denoise/lightmap_denoise.wgsl");
+
+        Self { layout, shader }
+    }
+}
+
+impl SpecializedComputePipeline for DenoisePipeline {
+    type Key = ();
+
+    fn specialize(&self, (): Self::Key) -> ComputePipelineDescriptor {
+        ComputePipelineDescriptor {
+            label: Some("lightmap_denoise_compute".into()),
+            layout: vec![self.layout.clone()],
+            push_constant_ranges: Vec::new(),
+            shader: self.shader.clone(),
+            shader_defs: Vec::new(),
+            entry_point: "main".into(),
+            zero_initialize_workgroup_memory: false,
+        }
+    }
+}
+
+impl GraphicsJob for DenoiseLightmapJob {
+    type In = (
+        JobLightmap,
+        JobComputePipeline<DenoisePipeline>,
+        JobWorkgroupSize<DenoisePipeline>,
+    );
+
+    fn run(
+        &self,
+        world: &World,
+        context: &mut JobRunContext,
+        (lightmap, pipeline, workgroup_size): JobInputItem<Self, Self::In>,
+    ) -> Result<(), JobError> {
+        let region = lightmap.texel_rect;
+        let size = region.size();
+        let workgroups = workgroup_size.workgroups(size.extend(1));
+
+        // the filter reads from a copy of the region, since the atlas can't be read
+        // and written in the same pass
+        let extent = Extent3d {
+            width: size.x,
+            height: size.y,
+            depth_or_array_layers: 1,
+        };
+        let render_device = context.render_device().clone();
+        let noisy = render_device.create_texture(&TextureDescriptor {
+            label: Some("lightmap_denoise_noisy"),
+            size: extent,
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: TextureDimension::D2,
+            format: LIGHTMAP_FORMAT,
+            usage: TextureUsages::COPY_DST | TextureUsages::TEXTURE_BINDING,
+            view_formats: &[],
+        });
+        context.copy_texture_to_texture(
+            ImageCopyTexture {
+                texture: &lightmap.image.texture,
+                mip_level: 0,
+                origin: Origin3d {
+                    x: region.min.x,
+                    y: region.min.y,
+                    z: 0,
+                },
+                aspect: TextureAspect::All,
+            },
+            ImageCopyTexture {
+                texture: &noisy,
+                mip_level: 0,
+                origin: Origin3d::ZERO,
+                aspect: TextureAspect::All,
+            },
+            extent,
+        );
+
+        let mut contents = encase::UniformBuffer::new(Vec::new());
+        contents
+            .write(&UVec4::new(
+                region.min.x,
+                region.min.y,
+                region.max.x,
+                region.max.y,
+            ))
+            .map_err(|_| JobError::ExecutionFailed)?;
+        let uniforms = render_device.create_buffer_with_data(&BufferInitDescriptor {
+            label: Some("lightmap_denoise_region"),
+            contents: &contents.into_inner(),
+            usage: BufferUsages::UNIFORM,
+        });
+        let bind_group = render_device.create_bind_group(
+            "lightmap_denoise_bind_group",
+            &world.resource::<DenoisePipeline>().layout,
+            &BindGroupEntries::sequential((
+                &noisy.create_view(&TextureViewDescriptor::default()),
+                &lightmap
+                    .image
+                    .texture
+                    .create_view(&TextureViewDescriptor::default()),
+                uniforms.as_entire_binding(),
+            )),
+        );
+
+        let mut compute_pass = context.begin_compute_pass(&ComputePassDescriptor {
+            label: Some("lightmap_denoise_compute_pass"),
+            timestamp_writes: None,
+        });
+        compute_pass.set_bind_group(0, &bind_group, &[]);
+        compute_pass.set_pipeline(pipeline);
+        compute_pass.dispatch_workgroups(workgroups.x, workgroups.y, workgroups.z);
+
+        Ok(())
+    }
+}
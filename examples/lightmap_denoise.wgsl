@@ -0,0 +1,24 @@
+@group(0) @binding(0) var noisy: texture_2d<f32>;
+@group(0) @binding(1) var lightmap: texture_storage_2d<rgba8unorm, write>;
+// the min and max corners of the entity's region of the lightmap, in texels
+@group(0) @binding(2) var<uniform> region: vec4<u32>;
+
+// a 5x5 box filter over a copy of the region, so texels outside of it never bleed in
+@compute @workgroup_size(8, 8, 1)
+fn main(@builtin(global_invocation_id) id: vec3<u32>) {
+    let size = vec2<i32>(region.zw - region.xy);
+    let texel = vec2<i32>(id.xy);
+    if any(texel >= size) {
+        return;
+    }
+
+    var sum = vec4(0.0);
+    for (var y = -2; y <= 2; y++) {
+        for (var x = -2; x <= 2; x++) {
+            let sample = clamp(texel + vec2(x, y), vec2(0), size - 1);
+            sum += textureLoad(noisy, sample, 0);
+        }
+    }
+
+    textureStore(lightmap, vec2<u32>(texel) + region.xy, sum / 25.0);
+}
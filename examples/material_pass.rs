@@ -0,0 +1,208 @@
+use bevy::{asset::embedded_asset, image::BevyDefault, pbr::MaterialPipeline, prelude::*};
+use bevy_render::{
+    mesh::RenderMeshBufferInfo,
+    render_resource::{
+        BindGroupLayout, ColorTargetState, ColorWrites, FragmentState, LoadOp, MultisampleState,
+        Operations, PrimitiveState, RenderPassColorAttachment, RenderPassDescriptor,
+        RenderPipelineDescriptor, SpecializedRenderPipeline, StoreOp, TextureFormat,
+        VertexAttribute, VertexBufferLayout, VertexFormat, VertexState, VertexStepMode,
+    },
+};
+
+use gigs::*;
+use input::{JobInputItem, JobMaterial, JobMeshSlice, JobRenderPipeline, JobViewTarget};
+
+/// The size of a vertex of a 2D primitive mesh, with a position, normal, and uv.
+const VERTEX_STRIDE: u64 = 32;
+
+fn main() -> AppExit {
+    let mut app = App::new();
+
+    // `StandardMaterial`'s `MaterialPlugin` is added by `PbrPlugin`, part of
+    // `DefaultPlugins`. A custom material would need its own `MaterialPlugin` added.
+    app.add_plugins((DefaultPlugins, GraphicsJobsPlugin::default()))
+        .init_graphics_job::<BadgeJob>();
+
+    embedded_asset!(app, "examples", "material_pass.wgsl");
+
+    app.add_systems(Startup, setup_scene)
+        .add_systems(Update, spawn_badge);
+
+    app.run()
+}
+
+#[derive(Resource)]
+struct Badge {
+    mesh: Handle<Mesh>,
+    material: Handle<StandardMaterial>,
+}
+
+fn setup_scene(
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    mut commands: Commands,
+) {
+    // the job clears the camera's target itself, so the camera doesn't need to
+    commands.spawn((
+        Camera3d::default(),
+        Camera {
+            clear_color: ClearColorConfig::None,
+            ..Default::default()
+        },
+        Msaa::Off,
+        Transform::from_xyz(0.0, 2.0, 5.0).looking_at(Vec3::ZERO, Vec3::Y),
+    ));
+    commands.spawn((
+        DirectionalLight::default(),
+        Transform::from_xyz(2.0, 4.0, 3.0).looking_at(Vec3::ZERO, Vec3::Y),
+    ));
+
+    let material = materials.add(StandardMaterial::from_color(Color::srgb(0.9, 0.3, 0.2)));
+    commands.spawn((
+        Mesh3d(meshes.add(Cuboid::default())),
+        MeshMaterial3d(material.clone()),
+    ));
+
+    // the job draws a badge in the corner with the cube's material. It's positioned
+    // directly in clip space.
+    let badge = Rectangle::new(0.3, 0.3)
+        .mesh()
+        .build()
+        .translated_by(Vec3::new(-0.75, 0.75, 0.0));
+    commands.insert_resource(Badge {
+        mesh: meshes.add(badge),
+        material,
+    });
+}
+
+fn spawn_badge(camera: Single<Entity, With<Camera>>, badge: Res<Badge>, mut commands: Commands) {
+    commands.spawn((
+        BadgeJob,
+        JobMeshSlice::new(badge.mesh.clone()),
+        JobMaterial::new(&badge.material),
+        JobViewTarget::new(*camera),
+        JobRenderPipeline::<BadgePipeline>(TextureFormat::bevy_default()),
+    ));
+}
+
+#[derive(Clone, Component)]
+struct BadgeJob;
+
+#[derive(Resource)]
+struct BadgePipeline {
+    material_layout: BindGroupLayout,
+    shader: Handle<Shader>,
+}
+
+impl FromWorld for BadgePipeline {
+    fn from_world(world: &mut World) -> Self {
+        // the material's bind group is laid out like its `AsBindGroup` impl
+        let material_layout = world
+            .resource::<MaterialPipeline<StandardMaterial>>()
+            .material_layout
+            .clone();
+        let shader = world
+            .resource::<AssetServer>()
+            .load("embedded://material_pass/material_pass.wgsl");
+
+        Self {
+            material_layout,
+            shader,
+        }
+    }
+}
+
+impl SpecializedRenderPipeline for BadgePipeline {
+    type Key = TextureFormat;
+
+    fn specialize(&self, format: Self::Key) -> RenderPipelineDescriptor {
+        RenderPipelineDescriptor {
+            label: Some("badge_pipeline".into()),
+            layout: vec![self.material_layout.clone()],
+            push_constant_ranges: Vec::new(),
+            vertex: VertexState {
+                shader: self.shader.clone(),
+                shader_defs: Vec::new(),
+                entry_point: "vertex".into(),
+                // only the position is read, from the start of each vertex
+                buffers: vec![VertexBufferLayout {
+                    array_stride: VERTEX_STRIDE,
+                    step_mode: VertexStepMode::Vertex,
+                    attributes: vec![VertexAttribute {
+                        format: VertexFormat::Float32x3,
+                        offset: 0,
+                        shader_location: 0,
+                    }],
+                }],
+            },
+            primitive: PrimitiveState::default(),
+            depth_stencil: None,
+            multisample: MultisampleState::default(),
+            fragment: Some(FragmentState {
+                shader: self.shader.clone(),
+                shader_defs: Vec::new(),
+                entry_point: "fragment".into(),
+                targets: vec![Some(ColorTargetState {
+                    format,
+                    blend: None,
+                    write_mask: ColorWrites::ALL,
+                })],
+            }),
+            zero_initialize_workgroup_memory: false,
+        }
+    }
+}
+
+impl GraphicsJob for BadgeJob {
+    type In = (
+        JobMeshSlice,
+        JobMaterial<StandardMaterial>,
+        JobViewTarget,
+        JobRenderPipeline<BadgePipeline>,
+    );
+
+    fn run(
+        &self,
+        _world: &World,
+        context: &mut JobRunContext,
+        (slices, material, target, pipeline): JobInputItem<Self, Self::In>,
+    ) -> Result<(), JobError> {
+        let slice = &slices[0];
+        if slice.mesh.layout.0.layout().array_stride != VERTEX_STRIDE {
+            return Err(JobError::ExecutionFailed);
+        }
+        let Some(indices) = &slice.indices else {
+            return Err(JobError::ExecutionFailed);
+        };
+        let RenderMeshBufferInfo::Indexed { index_format, .. } = slice.mesh.buffer_info else {
+            return Err(JobError::ExecutionFailed);
+        };
+
+        let mut render_pass = context.begin_render_pass(&RenderPassDescriptor {
+            label: Some("badge_pass"),
+            color_attachments: &[Some(RenderPassColorAttachment {
+                view: target.target.main_texture_view(),
+                resolve_target: None,
+                ops: Operations {
+                    load: LoadOp::Clear(LinearRgba::rgb(0.05, 0.05, 0.08).into()),
+                    store: StoreOp::Store,
+                },
+            })],
+            depth_stencil_attachment: None,
+            timestamp_writes: None,
+            occlusion_query_set: None,
+        });
+
+        render_pass.set_pipeline(pipeline);
+        render_pass.set_bind_group(0, &material.bind_group, &[]);
+        render_pass.set_vertex_buffer(0, *slice.vertices.buffer.slice(..));
+        render_pass.set_index_buffer(*indices.buffer.slice(..), index_format);
+        render_pass.draw_indexed(
+            indices.range.clone(),
+            slice.vertices.range.start as i32,
+            0..1,
+        );
+
+        Ok(())
+    }
+}
@@ -0,0 +1,16 @@
+#import bevy_pbr::pbr_types::StandardMaterial
+
+// the material's uniform is the first binding of `StandardMaterial`'s bind group. The
+// pipeline uses its layout as the only group, so only this binding is declared.
+@group(0) @binding(0) var<uniform> material: StandardMaterial;
+
+@vertex
+fn vertex(@location(0) position: vec3<f32>) -> @builtin(position) vec4<f32> {
+    // the badge is built directly in clip space, so no view transform is needed
+    return vec4(position.xy, 0.0, 1.0);
+}
+
+@fragment
+fn fragment() -> @location(0) vec4<f32> {
+    return material.base_color;
+}
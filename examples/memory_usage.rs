@@ -0,0 +1,114 @@
+use bevy::prelude::*;
+use bevy_render::render_resource::{
+    ImageCopyBuffer, ImageCopyTexture, ImageDataLayout, LoadOp, Operations, Origin3d,
+    RenderPassColorAttachment, RenderPassDescriptor, StoreOp, TextureAspect, TextureFormat,
+};
+
+use gigs::*;
+use input::{JobInputItem, JobOffscreenTarget, JobReadback};
+
+const JOBS: u32 = 8;
+const SIZE: u32 = 512;
+const FORMAT: TextureFormat = TextureFormat::Rgba8Unorm;
+
+fn main() -> AppExit {
+    let mut app = App::new();
+
+    app.add_plugins((DefaultPlugins, GraphicsJobsPlugin::default()))
+        .init_graphics_job::<BakeJob>()
+        .add_systems(Startup, spawn_bakes)
+        .add_systems(Update, report_memory);
+
+    app.run()
+}
+
+fn spawn_bakes(mut commands: Commands) {
+    commands.spawn(Camera2d);
+
+    // each job gets its own offscreen texture, while the readback buffers are shared
+    // by every job of the type, and kept between them
+    for index in 0..JOBS {
+        let target = JobOffscreenTarget::new(SIZE, SIZE, FORMAT);
+        commands.spawn((
+            BakeJob {
+                color: LinearRgba::new(index as f32 / JOBS as f32, 0.5, 0.2, 1.0),
+            },
+            target.transient_memory(),
+            target,
+            JobReadback::new((SIZE * SIZE * 4) as u64),
+        ));
+    }
+}
+
+/// Prints the memory held by the job system whenever it changes. Offscreen targets
+/// are dropped as their jobs complete, so the scratch textures fall back to zero,
+/// while the readback staging buffers stay around for the next job.
+fn report_memory(usage: Res<JobMemoryUsage>, mut last: Local<JobMemoryBreakdown>) {
+    let breakdown = usage.breakdown();
+    if breakdown == *last {
+        return;
+    }
+    *last = breakdown;
+
+    let mib = |bytes: u64| bytes as f32 / (1024.0 * 1024.0);
+    info!(
+        "job memory: {:.1} MiB total, {:.1} MiB scratch textures, {:.1} MiB uniform buffers, \
+        {:.1} MiB readback staging, {:.1} MiB persistent",
+        mib(breakdown.total()),
+        mib(breakdown.scratch_textures),
+        mib(breakdown.uniform_buffers),
+        mib(breakdown.readback_staging),
+        mib(breakdown.persistent),
+    );
+}
+
+#[derive(Clone, Component)]
+struct BakeJob {
+    color: LinearRgba,
+}
+
+impl GraphicsJob for BakeJob {
+    type In = (JobOffscreenTarget, JobReadback);
+
+    fn run(
+        &self,
+        _world: &World,
+        context: &mut JobRunContext,
+        (target, readback): JobInputItem<Self, Self::In>,
+    ) -> Result<(), JobError> {
+        context.begin_render_pass(&RenderPassDescriptor {
+            label: Some("memory_usage_clear_pass"),
+            color_attachments: &[Some(RenderPassColorAttachment {
+                view: &target.view,
+                resolve_target: None,
+                ops: Operations {
+                    load: LoadOp::Clear(self.color.into()),
+                    store: StoreOp::Store,
+                },
+            })],
+            depth_stencil_attachment: None,
+            timestamp_writes: None,
+            occlusion_query_set: None,
+        });
+
+        context.copy_texture_to_buffer(
+            ImageCopyTexture {
+                texture: &target.texture,
+                mip_level: 0,
+                origin: Origin3d::ZERO,
+                aspect: TextureAspect::All,
+            },
+            ImageCopyBuffer {
+                buffer: readback,
+                layout: ImageDataLayout {
+                    offset: 0,
+                    bytes_per_row: Some(SIZE * 4),
+                    rows_per_image: None,
+                },
+            },
+            target.texture.size(),
+        );
+
+        Ok(())
+    }
+}
@@ -0,0 +1,221 @@
+use bevy::{asset::embedded_asset, image::BevyDefault, prelude::*};
+use bevy_render::{
+    mesh::RenderMeshBufferInfo,
+    render_resource::{
+        BufferInitDescriptor, BufferUsages, ColorTargetState, ColorWrites, DrawIndexedIndirectArgs,
+        FragmentState, LoadOp, MultisampleState, Operations, PrimitiveState,
+        RenderPassColorAttachment, RenderPassDescriptor, RenderPipelineDescriptor,
+        SpecializedRenderPipeline, StoreOp, TextureFormat, VertexAttribute, VertexBufferLayout,
+        VertexFormat, VertexState, VertexStepMode,
+    },
+};
+
+use gigs::*;
+use input::{
+    JobInputItem, JobMeshSlice, JobRenderPipeline, JobViewTarget, JobViewTargetFormatPlugin,
+};
+
+/// The size of a vertex of a 2D primitive mesh, with a position, normal, and uv.
+const VERTEX_STRIDE: u64 = 32;
+
+fn main() -> AppExit {
+    let mut app = App::new();
+
+    app.add_plugins((
+        DefaultPlugins,
+        GraphicsJobsPlugin::default(),
+        JobViewTargetFormatPlugin::<ShapesPipeline>::default(),
+    ))
+    .init_graphics_job::<ShapesJob>();
+
+    embedded_asset!(app, "examples", "mesh_indirect.wgsl");
+
+    app.add_systems(Startup, setup_scene)
+        .add_systems(Update, spawn_shapes);
+
+    app.run()
+}
+
+#[derive(Resource)]
+struct Shapes(JobMeshSlice);
+
+fn setup_scene(mut meshes: ResMut<Assets<Mesh>>, mut commands: Commands) {
+    // the job clears the camera's target itself, so the camera doesn't need to
+    commands.spawn((
+        Camera2d,
+        Camera {
+            clear_color: ClearColorConfig::None,
+            ..Default::default()
+        },
+        Msaa::Off,
+    ));
+
+    // the meshes share a vertex layout, so the mesh allocator packs them into the
+    // same vertex and index buffers. They're positioned directly in clip space.
+    let shapes = [
+        Circle::new(0.3)
+            .mesh()
+            .build()
+            .translated_by(Vec3::new(-0.5, 0.0, 0.0)),
+        Rectangle::new(0.5, 0.5)
+            .mesh()
+            .build()
+            .translated_by(Vec3::new(0.5, 0.0, 0.0)),
+        RegularPolygon::new(0.25, 6)
+            .mesh()
+            .build()
+            .translated_by(Vec3::new(0.0, 0.5, 0.0)),
+    ];
+    commands.insert_resource(Shapes(
+        shapes.into_iter().map(|mesh| meshes.add(mesh)).collect(),
+    ));
+}
+
+fn spawn_shapes(camera: Single<Entity, With<Camera>>, shapes: Res<Shapes>, mut commands: Commands) {
+    commands.spawn((
+        ShapesJob,
+        shapes.0.clone(),
+        JobViewTarget::new(*camera),
+        JobRenderPipeline::<ShapesPipeline>(TextureFormat::bevy_default()),
+    ));
+}
+
+#[derive(Clone, Component)]
+struct ShapesJob;
+
+#[derive(Resource)]
+struct ShapesPipeline {
+    shader: Handle<Shader>,
+}
+
+impl FromWorld for ShapesPipeline {
+    fn from_world(world: &mut World) -> Self {
+        let shader = world
+            .resource::<AssetServer>()
+            .load("embedded://mesh_indirect/mesh_indirect.wgsl");
+
+        Self { shader }
+    }
+}
+
+impl SpecializedRenderPipeline for ShapesPipeline {
+    type Key = TextureFormat;
+
+    fn specialize(&self, format: Self::Key) -> RenderPipelineDescriptor {
+        RenderPipelineDescriptor {
+            label: Some("shapes_pipeline".into()),
+            layout: Vec::new(),
+            push_constant_ranges: Vec::new(),
+            vertex: VertexState {
+                shader: self.shader.clone(),
+                shader_defs: Vec::new(),
+                entry_point: "vertex".into(),
+                // only the position is read, from the start of each vertex
+                buffers: vec![VertexBufferLayout {
+                    array_stride: VERTEX_STRIDE,
+                    step_mode: VertexStepMode::Vertex,
+                    attributes: vec![VertexAttribute {
+                        format: VertexFormat::Float32x3,
+                        offset: 0,
+                        shader_location: 0,
+                    }],
+                }],
+            },
+            primitive: PrimitiveState::default(),
+            depth_stencil: None,
+            multisample: MultisampleState::default(),
+            fragment: Some(FragmentState {
+                shader: self.shader.clone(),
+                shader_defs: Vec::new(),
+                entry_point: "fragment".into(),
+                targets: vec![Some(ColorTargetState {
+                    format,
+                    blend: None,
+                    write_mask: ColorWrites::ALL,
+                })],
+            }),
+            zero_initialize_workgroup_memory: false,
+        }
+    }
+}
+
+impl GraphicsJob for ShapesJob {
+    type In = (
+        JobMeshSlice,
+        JobViewTarget,
+        JobRenderPipeline<ShapesPipeline>,
+    );
+
+    fn run(
+        &self,
+        _world: &World,
+        context: &mut JobRunContext,
+        (slices, target, pipeline): JobInputItem<Self, Self::In>,
+    ) -> Result<(), JobError> {
+        let render_device = context.render_device();
+        let Some(first) = slices.first() else {
+            return Ok(());
+        };
+        let (Some(indices), RenderMeshBufferInfo::Indexed { index_format, .. }) =
+            (&first.indices, &first.mesh.buffer_info)
+        else {
+            return Err(JobError::ExecutionFailed);
+        };
+
+        // one indirect draw per mesh, each indexing into the shared buffers
+        let mut draws = Vec::new();
+        for slice in &slices {
+            let Some(slice_indices) = &slice.indices else {
+                return Err(JobError::ExecutionFailed);
+            };
+            if slice.mesh.layout.0.layout().array_stride != VERTEX_STRIDE
+                || slice.vertices.buffer.id() != first.vertices.buffer.id()
+                || slice_indices.buffer.id() != indices.buffer.id()
+            {
+                return Err(JobError::ExecutionFailed);
+            }
+
+            draws.extend_from_slice(
+                DrawIndexedIndirectArgs {
+                    index_count: slice_indices.range.len() as u32,
+                    instance_count: 1,
+                    first_index: slice_indices.range.start,
+                    base_vertex: slice.vertices.range.start as i32,
+                    first_instance: 0,
+                }
+                .as_bytes(),
+            );
+        }
+
+        let indirect_buffer = render_device.create_buffer_with_data(&BufferInitDescriptor {
+            label: Some("shapes_indirect_buffer"),
+            contents: &draws,
+            usage: BufferUsages::INDIRECT,
+        });
+
+        let mut render_pass = context.begin_render_pass(&RenderPassDescriptor {
+            label: Some("shapes_pass"),
+            color_attachments: &[Some(RenderPassColorAttachment {
+                view: target.target.main_texture_view(),
+                resolve_target: None,
+                ops: Operations {
+                    load: LoadOp::Clear(LinearRgba::rgb(0.05, 0.05, 0.08).into()),
+                    store: StoreOp::Store,
+                },
+            })],
+            depth_stencil_attachment: None,
+            timestamp_writes: None,
+            occlusion_query_set: None,
+        });
+
+        render_pass.set_pipeline(pipeline);
+        render_pass.set_vertex_buffer(0, *first.vertices.buffer.slice(..));
+        render_pass.set_index_buffer(*indices.buffer.slice(..), *index_format);
+        for i in 0..slices.len() {
+            let offset = (i * size_of::<DrawIndexedIndirectArgs>()) as u64;
+            render_pass.draw_indexed_indirect(&indirect_buffer, offset);
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,18 @@
+struct VertexOutput {
+    @builtin(position) position: vec4<f32>,
+    @location(0) color: vec3<f32>,
+}
+
+@vertex
+fn vertex(@location(0) position: vec3<f32>) -> VertexOutput {
+    // the meshes are built directly in clip space, so no view transform is needed
+    var out: VertexOutput;
+    out.position = vec4(position.xy, 0.0, 1.0);
+    out.color = vec3(0.5 + position.xy, 0.8);
+    return out;
+}
+
+@fragment
+fn fragment(in: VertexOutput) -> @location(0) vec4<f32> {
+    return vec4(in.color, 1.0);
+}
@@ -0,0 +1,107 @@
+use bevy::{
+    asset::RenderAssetUsages,
+    image::{ImageSampler, ImageSamplerDescriptor},
+    prelude::*,
+};
+use bevy_render::render_resource::{
+    Extent3d, LoadOp, Operations, RenderPassColorAttachment, RenderPassDescriptor, StoreOp,
+    TextureDimension, TextureFormat, TextureUsages,
+};
+
+use gigs::*;
+use input::{JobImageView, JobInputItem};
+
+const SIZE: u32 = 16;
+const MIP_LEVELS: u32 = 4;
+const TARGET_MIP_LEVEL: u32 = 2;
+
+fn main() -> AppExit {
+    let mut app = App::new();
+
+    app.add_plugins((DefaultPlugins, GraphicsJobsPlugin::default()))
+        .init_graphics_job::<FillMipJob>()
+        .add_systems(Startup, setup_scene);
+
+    app.run()
+}
+
+fn setup_scene(mut images: ResMut<Assets<Image>>, mut commands: Commands) {
+    // every mip level starts out black
+    let data = vec![0; mip_chain_len(SIZE, MIP_LEVELS)];
+    let mut image = Image::new(
+        Extent3d {
+            width: SIZE,
+            height: SIZE,
+            depth_or_array_layers: 1,
+        },
+        TextureDimension::D2,
+        data,
+        TextureFormat::Rgba8Unorm,
+        RenderAssetUsages::RENDER_WORLD,
+    );
+    image.texture_descriptor.mip_level_count = MIP_LEVELS;
+    image.texture_descriptor.usage |= TextureUsages::RENDER_ATTACHMENT;
+
+    // only sample the target mip level, so the sprite shows what the job wrote
+    image.sampler = ImageSampler::Descriptor(ImageSamplerDescriptor {
+        lod_min_clamp: TARGET_MIP_LEVEL as f32,
+        lod_max_clamp: TARGET_MIP_LEVEL as f32,
+        ..ImageSamplerDescriptor::nearest()
+    });
+    let image = images.add(image);
+
+    commands.spawn(Camera2d);
+    commands.spawn(Sprite {
+        image: image.clone(),
+        custom_size: Some(Vec2::splat(256.0)),
+        ..Default::default()
+    });
+
+    commands
+        .spawn((FillMipJob, JobImageView::mip_level(image, TARGET_MIP_LEVEL)))
+        .observe(|trigger: Trigger<JobComplete>| {
+            println!(
+                "Mip level {TARGET_MIP_LEVEL} filled: {:?}",
+                trigger.event().0
+            );
+        });
+}
+
+fn mip_chain_len(size: u32, mip_levels: u32) -> usize {
+    (0..mip_levels)
+        .map(|level| ((size >> level).max(1).pow(2) * 4) as usize)
+        .sum()
+}
+
+/// Clears a single mip level of an image to a solid color.
+#[derive(Clone, Component)]
+struct FillMipJob;
+
+impl GraphicsJob for FillMipJob {
+    type In = JobImageView;
+
+    fn run(
+        &self,
+        _world: &World,
+        context: &mut JobRunContext,
+        image_view: JobInputItem<Self, Self::In>,
+    ) -> Result<(), JobError> {
+        // the view only covers the target mip level, so the rest of the image is untouched
+        context.begin_render_pass(&RenderPassDescriptor {
+            label: Some("fill_mip_pass"),
+            color_attachments: &[Some(RenderPassColorAttachment {
+                view: &image_view.view,
+                resolve_target: None,
+                ops: Operations {
+                    load: LoadOp::Clear(LinearRgba::rgb(0.3, 0.8, 0.4).into()),
+                    store: StoreOp::Store,
+                },
+            })],
+            depth_stencil_attachment: None,
+            timestamp_writes: None,
+            occlusion_query_set: None,
+        });
+
+        Ok(())
+    }
+}
@@ -0,0 +1,77 @@
+use bevy::{
+    asset::RenderAssetUsages,
+    image::{ImageSampler, ImageSamplerDescriptor},
+    prelude::*,
+};
+use bevy_render::render_resource::{Extent3d, TextureDimension, TextureFormat, TextureUsages};
+
+use gigs::*;
+use jobs::GenerateMipmapsJob;
+
+const SIZE: u32 = 8;
+const MIP_LEVELS: u32 = 4;
+
+fn main() -> AppExit {
+    let mut app = App::new();
+
+    // the built-in jobs are initialized by `GraphicsJobsPlugin`
+    app.add_plugins((DefaultPlugins, GraphicsJobsPlugin::default()))
+        .add_systems(Startup, setup_scene);
+
+    app.run()
+}
+
+fn setup_scene(mut images: ResMut<Assets<Image>>, mut commands: Commands) {
+    // a red and blue checkerboard, which averages out to purple on lower mips
+    let mut data = (0..SIZE * SIZE)
+        .flat_map(|i| match (i % SIZE + i / SIZE) % 2 {
+            0 => [255, 0, 0, 255],
+            _ => [0, 0, 255, 255],
+        })
+        .collect::<Vec<u8>>();
+
+    // the image is uploaded with data for every mip level, so leave the lower
+    // levels blank for the job to fill in
+    data.resize(mip_chain_len(SIZE, MIP_LEVELS), 0);
+
+    let mut image = Image::new(
+        Extent3d {
+            width: SIZE,
+            height: SIZE,
+            depth_or_array_layers: 1,
+        },
+        TextureDimension::D2,
+        data,
+        TextureFormat::Rgba8Unorm,
+        RenderAssetUsages::RENDER_WORLD,
+    );
+    image.texture_descriptor.mip_level_count = MIP_LEVELS;
+    image.texture_descriptor.usage |= TextureUsages::RENDER_ATTACHMENT;
+
+    // clamp sampling to the last mip level, so the sprite shows the 1x1 average
+    // of the whole checkerboard once the job has run
+    image.sampler = ImageSampler::Descriptor(ImageSamplerDescriptor {
+        lod_min_clamp: (MIP_LEVELS - 1) as f32,
+        ..ImageSamplerDescriptor::nearest()
+    });
+    let image = images.add(image);
+
+    commands.spawn(Camera2d);
+    commands.spawn(Sprite {
+        image: image.clone(),
+        custom_size: Some(Vec2::splat(256.0)),
+        ..Default::default()
+    });
+
+    commands
+        .spawn(GenerateMipmapsJob::new(image))
+        .observe(|trigger: Trigger<JobComplete>| {
+            println!("Mipmaps generated: {:?}", trigger.event().0);
+        });
+}
+
+fn mip_chain_len(size: u32, mip_levels: u32) -> usize {
+    (0..mip_levels)
+        .map(|level| ((size >> level).max(1).pow(2) * 4) as usize)
+        .sum()
+}
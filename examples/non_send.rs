@@ -0,0 +1,75 @@
+use std::{cell::Cell, rc::Rc};
+
+use bevy::{input::keyboard::KeyboardInput, prelude::*};
+use bevy_render::{Render, RenderApp, RenderSet};
+
+use gigs::*;
+use input::{JobInputItem, JobLocal, JobLocals};
+
+const CONTEXT_KEY: u64 = 0;
+
+fn main() -> AppExit {
+    let mut app = App::new();
+
+    app.add_plugins((DefaultPlugins, GraphicsJobsPlugin::default()))
+        .init_graphics_job::<InteropJob>()
+        .add_systems(Update, handle_input);
+
+    app.sub_app_mut(RenderApp)
+        .add_systems(Render, create_context.in_set(RenderSet::Prepare));
+
+    app.run()
+}
+
+/// Stands in for an external, non-`Send` context, like a handle to a platform
+/// surface object, which may only be used from the thread that created it.
+struct ExternalContext {
+    submissions: Rc<Cell<u32>>,
+}
+
+/// The context is created on the render thread, and stays in the store until
+/// it's removed. Every job referencing its key shares it.
+fn create_context(locals: Option<NonSendMut<JobLocals<ExternalContext>>>) {
+    // the store is inserted by `gigs` on the render thread the first time the
+    // render schedule runs
+    let Some(mut locals) = locals else {
+        return;
+    };
+    if !locals.contains(CONTEXT_KEY) {
+        locals.insert(
+            CONTEXT_KEY,
+            ExternalContext {
+                submissions: Rc::new(Cell::new(0)),
+            },
+        );
+    }
+}
+
+fn handle_input(mut keyboard_input: EventReader<KeyboardInput>, mut commands: Commands) {
+    for key in keyboard_input.read().filter(|key| key.state.is_pressed()) {
+        if key.key_code == KeyCode::Space {
+            commands.spawn((InteropJob, JobLocal::<ExternalContext>::new(CONTEXT_KEY)));
+        }
+    }
+}
+
+#[derive(Clone, Component)]
+struct InteropJob;
+
+impl GraphicsJob for InteropJob {
+    type In = JobLocal<ExternalContext>;
+
+    fn run(
+        &self,
+        _world: &World,
+        _context: &mut JobRunContext,
+        context: JobInputItem<Self, Self::In>,
+    ) -> Result<(), JobError> {
+        context.submissions.set(context.submissions.get() + 1);
+        println!(
+            "Used the external context on the render thread {} time(s)",
+            context.submissions.get()
+        );
+        Ok(())
+    }
+}
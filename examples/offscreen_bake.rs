@@ -0,0 +1,227 @@
+use bevy::{
+    asset::{embedded_asset, RenderAssetUsages},
+    prelude::*,
+};
+use bevy_render::{
+    render_resource::{
+        Buffer, BufferDescriptor, BufferUsages, ColorTargetState, ColorWrites, Extent3d,
+        FragmentState, ImageCopyBuffer, ImageCopyTexture, ImageDataLayout, LoadOp, Maintain,
+        MapMode, MultisampleState, Operations, Origin3d, PrimitiveState, RenderPassColorAttachment,
+        RenderPassDescriptor, RenderPipelineDescriptor, SpecializedRenderPipeline, StoreOp,
+        TextureAspect, TextureDimension, TextureFormat, VertexState,
+    },
+    renderer::RenderDevice,
+    Render, RenderApp, RenderSet,
+};
+use crossbeam_channel::{Receiver, Sender};
+
+use gigs::*;
+use input::{JobInputItem, JobOffscreenTarget, JobRenderPipeline};
+
+const SIZE: u32 = 256;
+const FORMAT: TextureFormat = TextureFormat::Rgba8UnormSrgb;
+const OUTPUT_PATH: &str = "offscreen_bake.png";
+
+fn main() -> AppExit {
+    let mut app = App::new();
+
+    app.add_plugins((DefaultPlugins, GraphicsJobsPlugin::default()))
+        .init_graphics_job::<BakeJob>();
+
+    embedded_asset!(app, "examples", "offscreen_bake.wgsl");
+
+    let (sender, receiver) = crossbeam_channel::unbounded();
+
+    app.insert_resource(BakeReceiver(receiver))
+        .add_systems(Startup, spawn_bake)
+        .add_systems(Update, save_bake);
+
+    let render_app = app.sub_app_mut(RenderApp);
+    render_app
+        .insert_resource(BakeSender(sender))
+        .add_systems(Render, poll_device.in_set(RenderSet::Cleanup));
+    render_app.world_mut().add_observer(map_readback);
+
+    app.run()
+}
+
+fn spawn_bake(mut commands: Commands) {
+    // the offscreen texture is created for this job, and dropped once it completes.
+    // Declaring its size lets the scheduler keep it within the transient memory budget.
+    let target = JobOffscreenTarget::new(SIZE, SIZE, FORMAT);
+    commands.spawn((BakeJob, target.transient_memory(), target));
+}
+
+#[derive(Resource)]
+struct BakeReceiver(Receiver<Vec<u8>>);
+
+#[derive(Resource)]
+struct BakeSender(Sender<Vec<u8>>);
+
+fn save_bake(receiver: Res<BakeReceiver>, mut exit: EventWriter<AppExit>) {
+    let Ok(data) = receiver.0.try_recv() else {
+        return;
+    };
+
+    let image = Image::new(
+        Extent3d {
+            width: SIZE,
+            height: SIZE,
+            depth_or_array_layers: 1,
+        },
+        TextureDimension::D2,
+        data,
+        FORMAT,
+        RenderAssetUsages::MAIN_WORLD,
+    );
+    image.try_into_dynamic().unwrap().save(OUTPUT_PATH).unwrap();
+
+    println!("Baked scene slice to {OUTPUT_PATH}");
+    exit.send(AppExit::Success);
+}
+
+/// The buffer the baked texture is copied into, so it can be mapped and read on the CPU.
+#[derive(Resource)]
+struct ReadbackBuffer(Buffer);
+
+fn map_readback(
+    trigger: Trigger<JobComplete>,
+    readback: Res<ReadbackBuffer>,
+    sender: Res<BakeSender>,
+) {
+    if trigger.event().0.is_err() {
+        return;
+    }
+
+    // jobs are completed after their commands are submitted, so it's safe to map the buffer
+    let buffer = readback.0.clone();
+    let sender = sender.0.clone();
+    readback
+        .0
+        .slice(..)
+        .map_async(MapMode::Read, move |result| {
+            if result.is_ok() {
+                let data = buffer.slice(..).get_mapped_range().to_vec();
+                buffer.unmap();
+                let _ = sender.send(data);
+            }
+        });
+}
+
+fn poll_device(render_device: Res<RenderDevice>) {
+    render_device.poll(Maintain::Poll);
+}
+
+#[derive(Clone, Component)]
+#[require(JobRenderPipeline<BakePipeline>)]
+struct BakeJob;
+
+#[derive(Resource)]
+struct BakePipeline {
+    shader: Handle<Shader>,
+}
+
+impl FromWorld for BakePipeline {
+    fn from_world(world: &mut World) -> Self {
+        // the render device isn't available until the pipeline is initialized,
+        // so the readback buffer is created alongside it
+        let readback_buffer = world
+            .resource::<RenderDevice>()
+            .create_buffer(&BufferDescriptor {
+                label: Some("offscreen_bake_readback"),
+                size: (SIZE * SIZE * 4) as u64,
+                usage: BufferUsages::COPY_DST | BufferUsages::MAP_READ,
+                mapped_at_creation: false,
+            });
+        world.insert_resource(ReadbackBuffer(readback_buffer));
+
+        let shader = world
+            .resource::<AssetServer>()
+            .load("embedded://offscreen_bake/offscreen_bake.wgsl");
+
+        Self { shader }
+    }
+}
+
+impl SpecializedRenderPipeline for BakePipeline {
+    type Key = ();
+
+    fn specialize(&self, (): Self::Key) -> RenderPipelineDescriptor {
+        RenderPipelineDescriptor {
+            label: Some("offscreen_bake_pipeline".into()),
+            layout: Vec::new(),
+            push_constant_ranges: Vec::new(),
+            vertex: VertexState {
+                shader: self.shader.clone(),
+                shader_defs: Vec::new(),
+                entry_point: "vertex".into(),
+                buffers: Vec::new(),
+            },
+            primitive: PrimitiveState::default(),
+            depth_stencil: None,
+            multisample: MultisampleState::default(),
+            fragment: Some(FragmentState {
+                shader: self.shader.clone(),
+                shader_defs: Vec::new(),
+                entry_point: "fragment".into(),
+                targets: vec![Some(ColorTargetState {
+                    format: FORMAT,
+                    blend: None,
+                    write_mask: ColorWrites::ALL,
+                })],
+            }),
+            zero_initialize_workgroup_memory: false,
+        }
+    }
+}
+
+impl GraphicsJob for BakeJob {
+    type In = (JobOffscreenTarget, JobRenderPipeline<BakePipeline>);
+
+    fn run(
+        &self,
+        world: &World,
+        context: &mut JobRunContext,
+        (target, pipeline): JobInputItem<Self, Self::In>,
+    ) -> Result<(), JobError> {
+        {
+            let mut render_pass = context.begin_render_pass(&RenderPassDescriptor {
+                label: Some("offscreen_bake_pass"),
+                color_attachments: &[Some(RenderPassColorAttachment {
+                    view: &target.view,
+                    resolve_target: None,
+                    ops: Operations {
+                        load: LoadOp::Clear(Default::default()),
+                        store: StoreOp::Store,
+                    },
+                })],
+                depth_stencil_attachment: None,
+                timestamp_writes: None,
+                occlusion_query_set: None,
+            });
+
+            render_pass.set_pipeline(pipeline);
+            render_pass.draw(0..3, 0..1);
+        }
+
+        context.copy_texture_to_buffer(
+            ImageCopyTexture {
+                texture: &target.texture,
+                mip_level: 0,
+                origin: Origin3d::ZERO,
+                aspect: TextureAspect::All,
+            },
+            ImageCopyBuffer {
+                buffer: &world.resource::<ReadbackBuffer>().0,
+                layout: ImageDataLayout {
+                    offset: 0,
+                    bytes_per_row: Some(SIZE * 4),
+                    rows_per_image: None,
+                },
+            },
+            target.texture.size(),
+        );
+
+        Ok(())
+    }
+}
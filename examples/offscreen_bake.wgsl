@@ -0,0 +1,30 @@
+// a horizontal slice through a small scene of spheres, shaded by distance
+// to the nearest surface.
+
+@vertex
+fn vertex(@builtin(vertex_index) index: u32) -> @builtin(position) vec4<f32> {
+    let uv = vec2<f32>(f32(index >> 1u), f32(index & 1u)) * 2.0;
+    return vec4<f32>(uv * 2.0 - 1.0, 0.0, 1.0);
+}
+
+const SLICE_HEIGHT: f32 = 0.25;
+
+fn scene(p: vec3<f32>) -> f32 {
+    let a = length(p - vec3(-0.4, 0.0, 0.0)) - 0.45;
+    let b = length(p - vec3(0.45, 0.1, 0.2)) - 0.3;
+    let c = length(p - vec3(0.1, -0.2, -0.5)) - 0.35;
+    return min(a, min(b, c));
+}
+
+@fragment
+fn fragment(@builtin(position) position: vec4<f32>) -> @location(0) vec4<f32> {
+    let uv = position.xy / 256.0 * 2.0 - 1.0;
+    let distance = scene(vec3(uv.x, SLICE_HEIGHT, uv.y));
+
+    let inside = vec3(0.9, 0.5, 0.2);
+    let outside = vec3(0.2, 0.4, 0.8);
+    let bands = 0.8 + 0.2 * cos(distance * 60.0);
+    let color = select(outside, inside, distance < 0.0) * bands;
+    let edge = 1.0 - smoothstep(0.0, 0.01, abs(distance));
+    return vec4(mix(color, vec3(1.0), edge), 1.0);
+}
@@ -0,0 +1,182 @@
+use bevy::{
+    asset::embedded_asset, core_pipeline::oit::OrderIndependentTransparencySettings, prelude::*,
+};
+use bevy_render::{
+    render_resource::{
+        binding_types::{storage_buffer_sized, uniform_buffer_sized},
+        BindGroupEntries, BindGroupLayout, BindGroupLayoutEntries, BufferInitDescriptor,
+        BufferUsages, ComputePassDescriptor, ComputePipelineDescriptor, ShaderStages,
+        SpecializedComputePipeline,
+    },
+    renderer::RenderDevice,
+};
+
+use gigs::*;
+use input::{JobComputePipeline, JobInputItem, JobOitBuffers};
+
+const WORKGROUP_SIZE: u32 = 8;
+
+fn main() -> AppExit {
+    let mut app = App::new();
+
+    app.add_plugins((DefaultPlugins, GraphicsJobsPlugin::default()))
+        .init_graphics_job::<SplatJob>();
+
+    embedded_asset!(app, "examples", "oit_splats.wgsl");
+
+    app.add_systems(Startup, setup_scene)
+        .add_systems(Update, spawn_splats);
+
+    app.run()
+}
+
+#[derive(Resource)]
+struct SplatCamera(Entity);
+
+fn setup_scene(
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    mut commands: Commands,
+) {
+    // OIT requires MSAA to be disabled on the camera
+    let camera = commands
+        .spawn((
+            Camera3d::default(),
+            Transform::from_xyz(0.0, 0.0, 5.0).looking_at(Vec3::ZERO, Vec3::Y),
+            OrderIndependentTransparencySettings::default(),
+            Msaa::Off,
+        ))
+        .id();
+    commands.insert_resource(SplatCamera(camera));
+
+    commands.spawn((PointLight::default(), Transform::from_xyz(2.0, 4.0, 4.0)));
+
+    // the spheres are drawn into the OIT layers by bevy, and blended with
+    // the splats added by the job in the same resolve pass
+    let sphere = meshes.add(Sphere::new(0.6));
+    for (x, color) in [
+        (-0.5, Color::srgba(1.0, 1.0, 0.2, 0.5)),
+        (0.5, Color::srgba(0.2, 1.0, 1.0, 0.5)),
+    ] {
+        commands.spawn((
+            Mesh3d(sphere.clone()),
+            MeshMaterial3d(materials.add(StandardMaterial {
+                base_color: color,
+                alpha_mode: AlphaMode::Blend,
+                ..Default::default()
+            })),
+            Transform::from_xyz(x, 0.0, 0.0),
+        ));
+    }
+}
+
+fn spawn_splats(time: Res<Time>, camera: Res<SplatCamera>, mut commands: Commands) {
+    // bevy's resolve pass resets the layers every frame, so the splats are added each frame
+    commands.spawn((
+        SplatJob {
+            time: time.elapsed_secs(),
+        },
+        JobOitBuffers::new(camera.0),
+    ));
+}
+
+#[derive(Clone, Component)]
+#[require(JobComputePipeline<SplatPipeline>)]
+struct SplatJob {
+    time: f32,
+}
+
+#[derive(Resource)]
+struct SplatPipeline {
+    layout: BindGroupLayout,
+    shader: Handle<Shader>,
+}
+
+impl FromWorld for SplatPipeline {
+    fn from_world(world: &mut World) -> Self {
+        let layout = world.resource::<RenderDevice>().create_bind_group_layout(
+            "oit_splats_layout",
+            &BindGroupLayoutEntries::sequential(
+                ShaderStages::COMPUTE,
+                (
+                    storage_buffer_sized(false, None),
+                    storage_buffer_sized(false, None),
+                    uniform_buffer_sized(true, None),
+                    uniform_buffer_sized(false, None),
+                ),
+            ),
+        );
+        let shader = world
+            .resource::<AssetServer>()
+            .load("embedded://oit_splats/oit_splats.wgsl");
+
+        Self { layout, shader }
+    }
+}
+
+impl SpecializedComputePipeline for SplatPipeline {
+    type Key = ();
+
+    fn specialize(&self, (): Self::Key) -> ComputePipelineDescriptor {
+        ComputePipelineDescriptor {
+            label: Some("oit_splats_compute".into()),
+            layout: vec![self.layout.clone()],
+            push_constant_ranges: Vec::new(),
+            shader: self.shader.clone(),
+            shader_defs: Vec::new(),
+            entry_point: "main".into(),
+            zero_initialize_workgroup_memory: false,
+        }
+    }
+}
+
+impl GraphicsJob for SplatJob {
+    type In = (JobOitBuffers, JobComputePipeline<SplatPipeline>);
+
+    fn run(
+        &self,
+        world: &World,
+        context: &mut JobRunContext,
+        (oit, pipeline): JobInputItem<Self, Self::In>,
+    ) -> Result<(), JobError> {
+        let render_device = context.render_device();
+        let size = oit.viewport_size;
+        let splats = [
+            size.x.to_le_bytes(),
+            size.y.to_le_bytes(),
+            self.time.to_le_bytes(),
+            [0; 4],
+        ];
+        let splats_buffer = render_device.create_buffer_with_data(&BufferInitDescriptor {
+            label: Some("oit_splats_uniform"),
+            contents: splats.as_flattened(),
+            usage: BufferUsages::UNIFORM,
+        });
+
+        let bind_group = render_device.create_bind_group(
+            "oit_splats_bind_group",
+            &world.resource::<SplatPipeline>().layout,
+            &BindGroupEntries::sequential((
+                oit.layers_binding(),
+                oit.layer_ids_binding(),
+                oit.settings.clone(),
+                splats_buffer.as_entire_binding(),
+            )),
+        );
+
+        let mut compute_pass = context.begin_compute_pass(&ComputePassDescriptor {
+            label: Some("oit_splats_compute_pass"),
+            timestamp_writes: None,
+        });
+
+        compute_pass.set_bind_group(0, &bind_group, &[oit.settings_offset]);
+        compute_pass.set_pipeline(pipeline);
+        compute_pass.dispatch_workgroups(
+            size.x.div_ceil(WORKGROUP_SIZE),
+            size.y.div_ceil(WORKGROUP_SIZE),
+            1,
+        );
+
+        Ok(())
+    }
+}
@@ -0,0 +1,71 @@
+#import bevy_pbr::rgb9e5::vec3_to_rgb9e5_
+
+struct OitSettings {
+    layers_count: i32,
+    alpha_threshold: f32,
+}
+
+struct Splats {
+    viewport_size: vec2<u32>,
+    time: f32,
+}
+
+@group(0) @binding(0) var<storage, read_write> layers: array<vec2<u32>>;
+@group(0) @binding(1) var<storage, read_write> layer_ids: array<atomic<i32>>;
+@group(0) @binding(2) var<uniform> settings: OitSettings;
+@group(0) @binding(3) var<uniform> splats: Splats;
+
+const SPLAT_COUNT: u32 = 3u;
+
+fn splat_color(i: u32) -> vec3<f32> {
+    switch i {
+        case 0u: { return vec3(1.0, 0.2, 0.2); }
+        case 1u: { return vec3(0.2, 1.0, 0.2); }
+        default: { return vec3(0.2, 0.4, 1.0); }
+    }
+}
+
+// the same packing as `bevy_core_pipeline::oit::pack_24bit_depth_8bit_alpha`
+fn pack_depth_alpha(depth: f32, alpha: f32) -> u32 {
+    let depth_bits = u32(saturate(depth) * f32(0xFFFFFFu) + 0.5);
+    let alpha_bits = u32(saturate(alpha) * f32(0xFFu) + 0.5);
+    return (depth_bits & 0xFFFFFFu) | ((alpha_bits & 0xFFu) << 24u);
+}
+
+// adds a fragment to a pixel's layers, like bevy's `oit_draw`
+fn add_fragment(screen_index: u32, color: vec4<f32>, depth: f32) {
+    if color.a < settings.alpha_threshold {
+        return;
+    }
+
+    let layer_id = atomicAdd(&layer_ids[screen_index], 1);
+    if layer_id >= settings.layers_count {
+        atomicStore(&layer_ids[screen_index], settings.layers_count);
+        return;
+    }
+
+    let buffer_size = splats.viewport_size.x * splats.viewport_size.y;
+    let layer_index = screen_index + u32(layer_id) * buffer_size;
+    layers[layer_index] = vec2(vec3_to_rgb9e5_(color.rgb), pack_depth_alpha(depth, color.a));
+}
+
+@compute @workgroup_size(8, 8, 1)
+fn main(@builtin(global_invocation_id) id: vec3<u32>) {
+    if any(id.xy >= splats.viewport_size) {
+        return;
+    }
+
+    let size = vec2<f32>(splats.viewport_size);
+    let uv = (vec2<f32>(id.xy) + 0.5 - size * 0.5) / size.y;
+    let screen_index = id.x + id.y * splats.viewport_size.x;
+
+    for (var i = 0u; i < SPLAT_COUNT; i += 1u) {
+        let angle = splats.time + f32(i) * 2.094;
+        let center = vec2(cos(angle), sin(angle)) * 0.2;
+        let alpha = 0.6 * (1.0 - smoothstep(0.1, 0.25, distance(uv, center)));
+        // the splats orbit through the spheres' depth range, so they're sorted
+        // in front of and behind them as they move
+        let depth = 0.02 + 0.015 * sin(angle);
+        add_fragment(screen_index, vec4(splat_color(i), alpha), depth);
+    }
+}
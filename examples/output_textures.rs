@@ -0,0 +1,147 @@
+use bevy::{asset::RenderAssetUsages, prelude::*};
+use bevy_render::render_resource::{
+    Extent3d, ImageCopyTexture, LoadOp, Operations, Origin3d, RenderPassColorAttachment,
+    RenderPassDescriptor, StoreOp, Texture, TextureAspect, TextureDescriptor, TextureDimension,
+    TextureFormat, TextureUsages, TextureViewDescriptor,
+};
+
+use gigs::*;
+use input::{JobImageView, JobInputItem, JobOutputLifetime, JobOutputTexture, JobOutputTextures};
+
+const SIZE: u32 = 64;
+const FORMAT: TextureFormat = TextureFormat::Rgba8UnormSrgb;
+
+fn main() -> AppExit {
+    let mut app = App::new();
+
+    app.add_plugins((DefaultPlugins, GraphicsJobsPlugin::default()))
+        .init_graphics_job::<ShadeJob>()
+        .init_graphics_job::<PresentJob>()
+        .add_systems(Startup, setup_scene);
+
+    app.run()
+}
+
+fn setup_scene(mut images: ResMut<Assets<Image>>, mut commands: Commands) {
+    let image = images.add(Image::new_fill(
+        Extent3d {
+            width: SIZE,
+            height: SIZE,
+            depth_or_array_layers: 1,
+        },
+        TextureDimension::D2,
+        &[0, 0, 0, 255],
+        FORMAT,
+        RenderAssetUsages::RENDER_WORLD,
+    ));
+
+    commands.spawn(Camera2d);
+    commands.spawn(Sprite {
+        image: image.clone(),
+        custom_size: Some(Vec2::splat(256.0)),
+        ..Default::default()
+    });
+
+    // the shading job produces both a color and a mask texture, and the presenting job
+    // binds only the color, by naming the job that produced it
+    let shade = commands
+        .spawn((
+            ShadeJob,
+            JobOutputTextures::new(&["color", "mask"], JobOutputLifetime::UntilDependentsDone),
+        ))
+        .id();
+    commands.spawn((
+        PresentJob,
+        JobOutputTexture::new(shade, "color"),
+        JobImageView::mip_level(image, 0),
+    ));
+}
+
+/// Renders a color and a mask into textures of its own.
+#[derive(Clone, Component)]
+struct ShadeJob;
+
+fn clear_texture(context: &mut JobRunContext, color: LinearRgba) -> Texture {
+    let texture = context.render_device().create_texture(&TextureDescriptor {
+        label: Some("output_textures_texture"),
+        size: Extent3d {
+            width: SIZE,
+            height: SIZE,
+            depth_or_array_layers: 1,
+        },
+        mip_level_count: 1,
+        sample_count: 1,
+        dimension: TextureDimension::D2,
+        format: FORMAT,
+        usage: TextureUsages::RENDER_ATTACHMENT | TextureUsages::COPY_SRC,
+        view_formats: &[],
+    });
+
+    context.begin_render_pass(&RenderPassDescriptor {
+        label: Some("output_textures_pass"),
+        color_attachments: &[Some(RenderPassColorAttachment {
+            view: &texture.create_view(&TextureViewDescriptor::default()),
+            resolve_target: None,
+            ops: Operations {
+                load: LoadOp::Clear(color.into()),
+                store: StoreOp::Store,
+            },
+        })],
+        depth_stencil_attachment: None,
+        timestamp_writes: None,
+        occlusion_query_set: None,
+    });
+
+    texture
+}
+
+impl GraphicsJob for ShadeJob {
+    type In = JobOutputTextures;
+
+    fn run(
+        &self,
+        _world: &World,
+        context: &mut JobRunContext,
+        outputs: JobInputItem<Self, Self::In>,
+    ) -> Result<(), JobError> {
+        let color = clear_texture(context, LinearRgba::rgb(0.9, 0.4, 0.1));
+        let mask = clear_texture(context, LinearRgba::WHITE);
+
+        outputs.publish("color", color);
+        outputs.publish("mask", mask);
+        Ok(())
+    }
+}
+
+/// Copies the shading job's color output into the sprite's image.
+#[derive(Clone, Component)]
+struct PresentJob;
+
+impl GraphicsJob for PresentJob {
+    type In = (JobOutputTexture, JobImageView);
+
+    fn run(
+        &self,
+        _world: &World,
+        context: &mut JobRunContext,
+        (color, image): JobInputItem<Self, Self::In>,
+    ) -> Result<(), JobError> {
+        context.copy_texture_to_texture(
+            ImageCopyTexture {
+                texture: &color,
+                mip_level: 0,
+                origin: Origin3d::ZERO,
+                aspect: TextureAspect::All,
+            },
+            ImageCopyTexture {
+                texture: &image.texture,
+                mip_level: 0,
+                origin: Origin3d::ZERO,
+                aspect: TextureAspect::All,
+            },
+            color.size(),
+        );
+
+        Ok(())
+    }
+}
@@ -0,0 +1,241 @@
+use bevy::{
+    asset::embedded_asset, core_pipeline::fullscreen_vertex_shader::fullscreen_shader_vertex_state,
+    image::BevyDefault, prelude::*,
+};
+use bevy_render::{
+    render_resource::{
+        binding_types::texture_2d, BindGroup, BindGroupEntries, BindGroupLayout,
+        BindGroupLayoutEntries, ColorTargetState, ColorWrites, FragmentState, LoadOp,
+        MultisampleState, Operations, PrimitiveState, RenderPassColorAttachment,
+        RenderPassDescriptor, RenderPipeline, RenderPipelineDescriptor, ShaderStages,
+        SpecializedRenderPipeline, StoreOp, TextureFormat, TextureSampleType, TextureView,
+    },
+    renderer::RenderDevice,
+};
+
+use gigs::*;
+use input::{
+    JobDependency, JobInputItem, JobOutput, JobOutputLifetime, JobRenderPipeline, JobViewTarget,
+    JobViewTargetFormatPlugin, ViewTargetKey,
+};
+
+const PATTERN: &str = "pattern";
+
+fn main() -> AppExit {
+    let mut app = App::new();
+
+    app.add_plugins((
+        DefaultPlugins,
+        GraphicsJobsPlugin::default(),
+        JobViewTargetFormatPlugin::<ChainPipeline>::default(),
+    ))
+    .init_graphics_job::<PatternJob>()
+    .init_graphics_job::<InvertJob>();
+
+    embedded_asset!(app, "examples", "post_process_chain.wgsl");
+
+    app.add_systems(Startup, setup_scene);
+
+    app.run()
+}
+
+fn setup_scene(mut commands: Commands) {
+    // as in the `view_target_formats` example, the camera doesn't clear its target, so the
+    // output of the chain stays on screen after it runs
+    let camera = commands
+        .spawn((
+            Camera2d,
+            Camera {
+                clear_color: ClearColorConfig::None,
+                ..Default::default()
+            },
+            Msaa::Off,
+        ))
+        .id();
+
+    // the second job waits on the first one's output, which is the main texture it wrote.
+    // It inverts the left half of the pattern, and copies the right half as it is.
+    commands.spawn((
+        PatternJob,
+        JobViewTarget::new(camera),
+        JobOutput::new(PATTERN, JobOutputLifetime::UntilDependentsDone),
+        JobRenderPipeline::<ChainPipeline>(ChainKey::new(ChainPass::Pattern)),
+    ));
+    commands.spawn((
+        InvertJob,
+        JobViewTarget::new(camera),
+        JobDependency::new(PATTERN),
+        JobRenderPipeline::<ChainPipeline>(ChainKey::new(ChainPass::Invert)),
+    ));
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+enum ChainPass {
+    /// Draws the pattern that the chain starts from.
+    Pattern,
+    Invert,
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+struct ChainKey {
+    pass: ChainPass,
+    format: TextureFormat,
+}
+
+impl ChainKey {
+    /// The format is only a placeholder, since `JobViewTargetFormatPlugin` replaces it
+    /// with the format of the camera's main texture.
+    fn new(pass: ChainPass) -> Self {
+        Self {
+            pass,
+            format: TextureFormat::bevy_default(),
+        }
+    }
+}
+
+impl ViewTargetKey for ChainKey {
+    fn view_format(&self) -> TextureFormat {
+        self.format
+    }
+
+    fn set_view_format(&mut self, format: TextureFormat) {
+        self.format = format;
+    }
+}
+
+#[derive(Resource)]
+struct ChainPipeline {
+    layout: BindGroupLayout,
+    shader: Handle<Shader>,
+}
+
+impl FromWorld for ChainPipeline {
+    fn from_world(world: &mut World) -> Self {
+        let layout = world.resource::<RenderDevice>().create_bind_group_layout(
+            "post_process_chain_layout",
+            &BindGroupLayoutEntries::single(
+                ShaderStages::FRAGMENT,
+                texture_2d(TextureSampleType::Float { filterable: false }),
+            ),
+        );
+        let shader = world
+            .resource::<AssetServer>()
+            .load("embedded://post_process_chain/post_process_chain.wgsl");
+
+        Self { layout, shader }
+    }
+}
+
+impl SpecializedRenderPipeline for ChainPipeline {
+    type Key = ChainKey;
+
+    fn specialize(&self, key: Self::Key) -> RenderPipelineDescriptor {
+        let (layout, entry_point) = match key.pass {
+            ChainPass::Pattern => (Vec::new(), "pattern"),
+            ChainPass::Invert => (vec![self.layout.clone()], "invert"),
+        };
+
+        RenderPipelineDescriptor {
+            label: Some("post_process_chain_pipeline".into()),
+            layout,
+            push_constant_ranges: Vec::new(),
+            vertex: fullscreen_shader_vertex_state(),
+            primitive: PrimitiveState::default(),
+            depth_stencil: None,
+            multisample: MultisampleState::default(),
+            fragment: Some(FragmentState {
+                shader: self.shader.clone(),
+                shader_defs: Vec::new(),
+                entry_point: entry_point.into(),
+                targets: vec![Some(ColorTargetState {
+                    format: key.format,
+                    blend: None,
+                    write_mask: ColorWrites::ALL,
+                })],
+            }),
+            zero_initialize_workgroup_memory: false,
+        }
+    }
+}
+
+fn draw_fullscreen(
+    context: &mut JobRunContext,
+    pipeline: &RenderPipeline,
+    bind_group: Option<&BindGroup>,
+    destination: &TextureView,
+) {
+    let mut render_pass = context.begin_render_pass(&RenderPassDescriptor {
+        label: Some("post_process_chain_pass"),
+        color_attachments: &[Some(RenderPassColorAttachment {
+            view: destination,
+            resolve_target: None,
+            ops: Operations {
+                load: LoadOp::Clear(Default::default()),
+                store: StoreOp::Store,
+            },
+        })],
+        depth_stencil_attachment: None,
+        timestamp_writes: None,
+        occlusion_query_set: None,
+    });
+
+    render_pass.set_pipeline(pipeline);
+    if let Some(bind_group) = bind_group {
+        render_pass.set_bind_group(0, bind_group, &[]);
+    }
+    render_pass.draw(0..3, 0..1);
+}
+
+/// Starts the chain by drawing a pattern into the camera's main texture.
+#[derive(Clone, Component)]
+struct PatternJob;
+
+impl GraphicsJob for PatternJob {
+    type In = (JobViewTarget, JobOutput, JobRenderPipeline<ChainPipeline>);
+
+    fn run(
+        &self,
+        _world: &World,
+        context: &mut JobRunContext,
+        (target, output, pipeline): JobInputItem<Self, Self::In>,
+    ) -> Result<(), JobError> {
+        let write = target.post_process_write();
+        draw_fullscreen(context, pipeline, None, write.destination);
+
+        // the next job of the chain reads the texture this one wrote
+        output.publish(write.destination_texture.clone());
+        Ok(())
+    }
+}
+
+/// Reads the pattern from the side of the ping-pong the previous job wrote.
+#[derive(Clone, Component)]
+struct InvertJob;
+
+impl GraphicsJob for InvertJob {
+    type In = (
+        JobViewTarget,
+        JobDependency,
+        JobRenderPipeline<ChainPipeline>,
+    );
+
+    fn run(
+        &self,
+        world: &World,
+        context: &mut JobRunContext,
+        (target, pattern, pipeline): JobInputItem<Self, Self::In>,
+    ) -> Result<(), JobError> {
+        let write = target
+            .post_process_write_after(&pattern)
+            .ok_or(JobError::InputsFailed)?;
+
+        let bind_group = context.render_device().create_bind_group(
+            "post_process_chain_bind_group",
+            &world.resource::<ChainPipeline>().layout,
+            &BindGroupEntries::single(write.source),
+        );
+        draw_fullscreen(context, pipeline, Some(&bind_group), write.destination);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,20 @@
+#import bevy_core_pipeline::fullscreen_vertex_shader::FullscreenVertexOutput
+
+@group(0) @binding(0) var source: texture_2d<f32>;
+
+@fragment
+fn pattern(in: FullscreenVertexOutput) -> @location(0) vec4<f32> {
+    let stripe = step(0.5, fract((in.uv.x + in.uv.y) * 8.0));
+    return vec4(mix(vec3(0.1, 0.2, 0.5), vec3(1.0, 0.8, 0.3), stripe), 1.0);
+}
+
+@fragment
+fn invert(in: FullscreenVertexOutput) -> @location(0) vec4<f32> {
+    let color = textureLoad(source, vec2<i32>(in.position.xy), 0);
+    // if the chain read the wrong side of the ping-pong, this would read garbage
+    // instead of the pattern
+    if in.uv.x < 0.5 {
+        return vec4(1.0 - color.rgb, color.a);
+    }
+    return color;
+}
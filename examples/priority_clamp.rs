@@ -0,0 +1,77 @@
+use bevy::prelude::*;
+use gigs::{
+    input::JobInputItem,
+    meta::{JobPriority, JobPriorityClamp, Priority},
+    GraphicsJob, GraphicsJobsPlugin, InitGraphicsJobExt, JobComplete, JobError,
+    JobExecutionSettings, JobRunContext,
+};
+
+fn main() -> AppExit {
+    let mut app = App::new();
+
+    app.add_plugins(DefaultPlugins)
+        .add_plugins(GraphicsJobsPlugin::default())
+        .init_graphics_job::<PrintJob>();
+
+    // run one job per frame, so that the jobs run in order of priority
+    app.insert_resource(JobExecutionSettings {
+        max_jobs_per_frame: 1,
+        ..Default::default()
+    });
+
+    app.world_mut()
+        .spawn((PrintJob("low"), JobPriority::non_critical::<2>()))
+        .observe(print_done);
+
+    app.world_mut()
+        .spawn((PrintJob("high"), JobPriority::non_critical::<8>()))
+        .observe(print_done);
+
+    // this job asks to be critical, but is capped below the "high" job, so it runs second
+    app.world_mut()
+        .spawn((
+            PrintJob("capped"),
+            JobPriority::critical(),
+            JobPriorityClamp {
+                max: JobPriority::non_critical::<4>().0,
+                ..Default::default()
+            },
+        ))
+        .observe(print_done);
+
+    // this job has the lowest priority, but is raised above the "low" job
+    app.world_mut()
+        .spawn((
+            PrintJob("raised"),
+            JobPriorityClamp {
+                min: Priority::NonCritical(3.try_into().unwrap()),
+                ..Default::default()
+            },
+        ))
+        .observe(print_done);
+
+    app.run()
+}
+
+fn print_done(trigger: Trigger<JobComplete>, jobs: Query<&PrintJob>) {
+    if let Ok(job) = jobs.get(trigger.entity()) {
+        println!("{} job done!", job.0);
+    }
+}
+
+#[derive(Clone, Component)]
+struct PrintJob(&'static str);
+
+impl GraphicsJob for PrintJob {
+    type In = ();
+
+    fn run(
+        &self,
+        _world: &World,
+        _context: &mut JobRunContext,
+        (): JobInputItem<Self, Self::In>,
+    ) -> Result<(), JobError> {
+        println!("{} job running!", self.0);
+        Ok(())
+    }
+}
@@ -0,0 +1,214 @@
+use bevy::{
+    asset::embedded_asset, core_pipeline::fullscreen_vertex_shader::fullscreen_shader_vertex_state,
+    image::BevyDefault, prelude::*,
+};
+use bevy_render::{
+    render_resource::{
+        binding_types::uniform_buffer, BindGroupEntries, BindGroupLayout, BindGroupLayoutEntries,
+        ColorTargetState, ColorWrites, FragmentState, LoadOp, MultisampleState, Operations,
+        PrimitiveState, RenderPassColorAttachment, RenderPassDescriptor, RenderPipelineDescriptor,
+        ShaderDefVal, ShaderStages, SpecializedRenderPipeline, StoreOp, TextureFormat,
+    },
+    renderer::RenderDevice,
+    view::{ViewTarget, ViewUniform, ViewUniforms},
+};
+
+use gigs::*;
+use input::{
+    JobExtractedViews, JobInputItem, JobRenderPipeline, JobShaderDefs, JobShaderDefsPlugin,
+};
+
+const HIGH_QUALITY: &str = "HIGH_QUALITY";
+
+fn main() -> AppExit {
+    let mut app = App::new();
+
+    app.add_plugins((
+        DefaultPlugins,
+        GraphicsJobsPlugin::default(),
+        JobShaderDefsPlugin::<BackgroundPipeline>::default(),
+    ))
+    .init_graphics_job::<BackgroundJob>();
+
+    embedded_asset!(app, "examples", "quality_defines.wgsl");
+
+    app.add_systems(Startup, setup_scene)
+        .add_systems(Update, (toggle_quality, spawn_background));
+
+    app.run()
+}
+
+fn setup_scene(mut commands: Commands) {
+    // as in the `vignette` example, the camera doesn't clear its target, since the
+    // background job draws before the camera renders
+    commands.spawn((
+        Camera2d,
+        Camera {
+            clear_color: ClearColorConfig::None,
+            ..Default::default()
+        },
+        Msaa::Off,
+    ));
+    commands.spawn(Text::new(""));
+}
+
+/// Toggles the quality define every two seconds, or when space is pressed.
+fn toggle_quality(
+    time: Res<Time>,
+    keys: Res<ButtonInput<KeyCode>>,
+    mut timer: Local<Option<Timer>>,
+    mut shader_defs: ResMut<JobShaderDefs>,
+    mut text: Single<&mut Text>,
+) {
+    let timer = timer.get_or_insert_with(|| Timer::from_seconds(2.0, TimerMode::Repeating));
+    if timer.tick(time.delta()).just_finished() || keys.just_pressed(KeyCode::Space) {
+        let high_quality = !shader_defs.contains(HIGH_QUALITY);
+        shader_defs.toggle(HIGH_QUALITY, high_quality);
+        text.0 = format!("Requested {HIGH_QUALITY} = {high_quality}, press [space] to toggle");
+    }
+}
+
+fn spawn_background(mut commands: Commands) {
+    // the defs here are only a placeholder, since `JobShaderDefsPlugin` replaces them
+    // with the current `JobShaderDefs`
+    commands
+        .spawn((
+            BackgroundJob,
+            JobRenderPipeline::<BackgroundPipeline>(Vec::new()),
+        ))
+        .observe(report_quality);
+}
+
+/// The defs the background was drawn with, read back with [`JobResults`].
+struct DrawnWith(Vec<ShaderDefVal>);
+
+/// Logs whenever the background is first drawn with the recompiled pipeline.
+fn report_quality(
+    trigger: Trigger<JobComplete>,
+    results: Res<JobResults>,
+    mut last: Local<Option<bool>>,
+) {
+    let Some(DrawnWith(defs)) = results.get(trigger.entity()) else {
+        return;
+    };
+    let high_quality = defs.contains(&ShaderDefVal::Bool(HIGH_QUALITY.into(), true));
+    if *last != Some(high_quality) {
+        println!("Drew the background with {HIGH_QUALITY} = {high_quality}");
+        *last = Some(high_quality);
+    }
+}
+
+#[derive(Clone, Component)]
+struct BackgroundJob;
+
+#[derive(Resource)]
+struct BackgroundPipeline {
+    layout: BindGroupLayout,
+    shader: Handle<Shader>,
+}
+
+impl FromWorld for BackgroundPipeline {
+    fn from_world(world: &mut World) -> Self {
+        let layout = world.resource::<RenderDevice>().create_bind_group_layout(
+            "quality_defines_layout",
+            &BindGroupLayoutEntries::single(
+                ShaderStages::FRAGMENT,
+                uniform_buffer::<ViewUniform>(true),
+            ),
+        );
+        let shader = world
+            .resource::<AssetServer>()
+            .load("embedded://quality_defines/quality_defines.wgsl");
+
+        Self { layout, shader }
+    }
+}
+
+impl SpecializedRenderPipeline for BackgroundPipeline {
+    type Key = Vec<ShaderDefVal>;
+
+    fn specialize(&self, shader_defs: Self::Key) -> RenderPipelineDescriptor {
+        RenderPipelineDescriptor {
+            label: Some("quality_defines_pipeline".into()),
+            layout: vec![self.layout.clone()],
+            push_constant_ranges: Vec::new(),
+            vertex: fullscreen_shader_vertex_state(),
+            primitive: PrimitiveState::default(),
+            depth_stencil: None,
+            multisample: MultisampleState::default(),
+            fragment: Some(FragmentState {
+                shader: self.shader.clone(),
+                shader_defs,
+                entry_point: "fragment".into(),
+                targets: vec![Some(ColorTargetState {
+                    format: TextureFormat::bevy_default(),
+                    blend: None,
+                    write_mask: ColorWrites::ALL,
+                })],
+            }),
+            zero_initialize_workgroup_memory: false,
+        }
+    }
+}
+
+impl GraphicsJob for BackgroundJob {
+    type In = (JobExtractedViews, JobRenderPipeline<BackgroundPipeline>);
+
+    fn run(
+        &self,
+        world: &World,
+        context: &mut JobRunContext,
+        (views, pipeline): JobInputItem<Self, Self::In>,
+    ) -> Result<(), JobError> {
+        let Some(view_uniforms) = world.resource::<ViewUniforms>().uniforms.binding() else {
+            return Ok(());
+        };
+
+        // the job's key in the render world holds the defs its pipeline was specialized with
+        if let Some(key) = world.get::<JobRenderPipeline<BackgroundPipeline>>(context.job()) {
+            context.set_result(DrawnWith(key.0.clone()));
+        }
+
+        let bind_group = context.render_device().create_bind_group(
+            "quality_defines_bind_group",
+            &world.resource::<BackgroundPipeline>().layout,
+            &BindGroupEntries::single(view_uniforms),
+        );
+
+        for view in views {
+            let Some(view_target) = view.entity.get::<ViewTarget>() else {
+                continue;
+            };
+
+            let mut render_pass = context.begin_render_pass(&RenderPassDescriptor {
+                label: Some("quality_defines_pass"),
+                color_attachments: &[Some(RenderPassColorAttachment {
+                    view: view_target.main_texture_view(),
+                    resolve_target: None,
+                    ops: Operations {
+                        load: LoadOp::Load,
+                        store: StoreOp::Store,
+                    },
+                })],
+                depth_stencil_attachment: None,
+                timestamp_writes: None,
+                occlusion_query_set: None,
+            });
+
+            let viewport = view.viewport().as_rect();
+            render_pass.set_viewport(
+                viewport.min.x,
+                viewport.min.y,
+                viewport.width(),
+                viewport.height(),
+                0.0,
+                1.0,
+            );
+            render_pass.set_pipeline(pipeline);
+            render_pass.set_bind_group(0, &bind_group, &[view.uniform_offset.offset]);
+            render_pass.draw(0..3, 0..1);
+        }
+
+        Ok(())
+    }
+}
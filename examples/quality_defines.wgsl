@@ -0,0 +1,21 @@
+#import bevy_render::view::View
+#import bevy_core_pipeline::fullscreen_vertex_shader::FullscreenVertexOutput
+
+@group(0) @binding(0) var<uniform> view: View;
+
+const INNER: vec3<f32> = vec3(0.3, 0.45, 0.6);
+const OUTER: vec3<f32> = vec3(0.05, 0.05, 0.1);
+
+@fragment
+fn fragment(in: FullscreenVertexOutput) -> @location(0) vec4<f32> {
+    let uv = (in.position.xy - view.viewport.xy) / view.viewport.zw;
+    var t = smoothstep(0.0, 0.75, distance(uv, vec2(0.5)));
+#ifdef HIGH_QUALITY
+    // a smooth gradient
+    return vec4(mix(INNER, OUTER, t), 1.0);
+#else
+    // the cheap version is visibly banded
+    t = floor(t * 5.0) / 5.0;
+    return vec4(mix(INNER, OUTER, t), 1.0);
+#endif
+}
@@ -0,0 +1,148 @@
+use std::sync::{Arc, Mutex};
+
+use bevy::prelude::*;
+use bevy_render::{
+    render_resource::{BufferInitDescriptor, BufferUsages},
+    Render, RenderApp,
+};
+
+use gigs::*;
+use input::{JobDependency, JobInputItem, JobOutput, JobOutputLifetime};
+
+/// Jobs that have waited this long are considered stuck.
+const STUCK_FRAMES: u32 = 60;
+
+fn main() -> AppExit {
+    let mut app = App::new();
+
+    app.add_plugins((DefaultPlugins, GraphicsJobsPlugin::default()))
+        .init_graphics_job::<ProduceJob>()
+        .init_graphics_job::<ConsumeJob>();
+
+    app.insert_resource(JobExecutionSettings {
+        // keep the stuck job around long enough to capture it
+        time_out_frames: STUCK_FRAMES * 10,
+        ..Default::default()
+    });
+
+    // the snapshot is captured in the render world, and read back in the main world
+    let captured = CapturedSnapshot::default();
+    app.insert_resource(captured.clone())
+        .add_systems(Startup, spawn_jobs)
+        .add_systems(Update, replay_snapshot);
+    app.sub_app_mut(RenderApp)
+        .insert_resource(captured)
+        .add_systems(
+            Render,
+            capture_stuck_queue
+                .after(JobSet::Check)
+                .before(JobSet::Execute),
+        );
+
+    app.run()
+}
+
+#[derive(Resource, Clone, Default)]
+struct CapturedSnapshot(Arc<Mutex<Option<JobQueueSnapshot>>>);
+
+fn spawn_jobs(mut commands: Commands) {
+    commands.spawn((
+        ProduceJob,
+        JobOutput::new("shadows", JobOutputLifetime::UntilDependentsDone),
+    ));
+    commands.spawn((ConsumeJob, JobDependency::new("shadows")));
+
+    // the job that produces the lightmap was never spawned, so this one stalls
+    commands.spawn((ConsumeJob, JobDependency::new("lightmap")));
+}
+
+/// Captures the queue once a job has been waiting for a while, just before jobs run.
+fn capture_stuck_queue(world: &mut World) {
+    let captured = world.resource::<CapturedSnapshot>().clone();
+    let mut captured = captured.0.lock().unwrap();
+    if captured.is_some() {
+        return;
+    }
+
+    let snapshot = JobQueueSnapshot::capture(world);
+    if snapshot
+        .jobs
+        .iter()
+        .any(|job| !job.ready && job.waited_frames > STUCK_FRAMES)
+    {
+        *captured = Some(snapshot);
+    }
+}
+
+/// Replays the captured queue to reproduce the stall, as it could be in a test.
+fn replay_snapshot(
+    captured: Res<CapturedSnapshot>,
+    settings: Res<JobExecutionSettings>,
+    mut exit: EventWriter<AppExit>,
+) {
+    let Some(snapshot) = captured.0.lock().unwrap().take() else {
+        return;
+    };
+
+    println!("Captured a stuck queue: {snapshot:#?}");
+
+    let replay = snapshot.replay(&settings);
+    for (frame, jobs) in replay.frames.iter().enumerate() {
+        let labels = jobs
+            .iter()
+            .map(|job| &snapshot.jobs[*job].label)
+            .collect::<Vec<_>>();
+        println!("Frame {frame} runs {labels:?}");
+    }
+    for job in &replay.stalled {
+        let job = &snapshot.jobs[*job];
+        println!(
+            "{} stalls waiting on {:?}",
+            job.label,
+            job.dependency.as_deref().unwrap_or("its inputs")
+        );
+    }
+
+    exit.send(AppExit::Success);
+}
+
+/// Publishes a small buffer for `ConsumeJob` to wait on.
+#[derive(Clone, Component)]
+struct ProduceJob;
+
+impl GraphicsJob for ProduceJob {
+    type In = JobOutput;
+
+    fn run(
+        &self,
+        _world: &World,
+        context: &mut JobRunContext,
+        output: JobInputItem<Self, Self::In>,
+    ) -> Result<(), JobError> {
+        let buffer = context
+            .render_device()
+            .create_buffer_with_data(&BufferInitDescriptor {
+                label: Some("queue_snapshot_output"),
+                contents: &[0; 16],
+                usage: BufferUsages::STORAGE,
+            });
+        output.publish(buffer);
+        Ok(())
+    }
+}
+
+#[derive(Clone, Component)]
+struct ConsumeJob;
+
+impl GraphicsJob for ConsumeJob {
+    type In = JobDependency;
+
+    fn run(
+        &self,
+        _world: &World,
+        _context: &mut JobRunContext,
+        _output: JobInputItem<Self, Self::In>,
+    ) -> Result<(), JobError> {
+        Ok(())
+    }
+}
@@ -0,0 +1,69 @@
+use bevy::{
+    asset::RenderAssetUsages, diagnostic::LogDiagnosticsPlugin, prelude::*,
+    render::diagnostic::RenderDiagnosticsPlugin, time::common_conditions::on_timer,
+};
+use bevy_render::render_resource::{Extent3d, TextureDimension, TextureFormat, TextureUsages};
+use core::time::Duration;
+
+use gigs::*;
+use jobs::GenerateMipmapsJob;
+
+const SIZE: u32 = 2048;
+
+// run with `cargo run --example render_diagnostics --features diagnostics`
+fn main() -> AppExit {
+    let mut app = App::new();
+
+    // each job's GPU time is logged as `render/job/<label>/elapsed_gpu`
+    app.add_plugins((
+        DefaultPlugins,
+        RenderDiagnosticsPlugin,
+        LogDiagnosticsPlugin::default(),
+        GraphicsJobsPlugin::default(),
+    ))
+    .add_systems(Startup, setup_image)
+    .add_systems(
+        Update,
+        spawn_job.run_if(on_timer(Duration::from_millis(500))),
+    );
+
+    // the measured GPU time also adapts how many jobs are executed each frame
+    app.insert_resource(JobExecutionSettings {
+        time_budget: Some(JobTimeBudget::new(Duration::from_millis(2))),
+        ..Default::default()
+    });
+
+    app.run()
+}
+
+#[derive(Resource)]
+struct MipmappedImage(Handle<Image>);
+
+fn setup_image(mut images: ResMut<Assets<Image>>, mut commands: Commands) {
+    // the image is uploaded with data for every mip level, which the jobs overwrite
+    let mip_levels = SIZE.ilog2() + 1;
+    let data = (0..mip_levels)
+        .flat_map(|level| [255, 0, 255, 255].repeat(((SIZE >> level).pow(2)) as usize))
+        .collect();
+
+    let mut image = Image::new(
+        Extent3d {
+            width: SIZE,
+            height: SIZE,
+            depth_or_array_layers: 1,
+        },
+        TextureDimension::D2,
+        data,
+        TextureFormat::Rgba8Unorm,
+        RenderAssetUsages::RENDER_WORLD,
+    );
+    image.texture_descriptor.mip_level_count = mip_levels;
+    image.texture_descriptor.usage |= TextureUsages::RENDER_ATTACHMENT;
+
+    commands.spawn(Camera2d);
+    commands.insert_resource(MipmappedImage(images.add(image)));
+}
+
+fn spawn_job(image: Res<MipmappedImage>, mut commands: Commands) {
+    commands.spawn(GenerateMipmapsJob::new(image.0.clone()));
+}
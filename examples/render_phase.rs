@@ -0,0 +1,99 @@
+use bevy::{core_pipeline::core_3d::Transparent3d, input::keyboard::KeyboardInput, prelude::*};
+use bevy_render::render_phase::ViewSortedRenderPhases;
+
+use gigs::*;
+use input::{JobInputItem, JobPhaseView, JobRenderPhase};
+
+fn main() -> AppExit {
+    let mut app = App::new();
+
+    app.add_plugins((DefaultPlugins, GraphicsJobsPlugin::default()))
+        .init_graphics_job::<CountTransparentJob>();
+
+    app.add_systems(Startup, setup_scene)
+        .add_systems(Update, handle_input);
+
+    app.run()
+}
+
+#[derive(Resource)]
+struct MainCamera(Entity);
+
+fn setup_scene(
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    mut commands: Commands,
+) {
+    let cube = meshes.add(Cuboid::default());
+
+    // only the blended cubes are drawn in the transparent phase
+    for x in -2..=2 {
+        let alpha_mode = if x % 2 == 0 {
+            AlphaMode::Blend
+        } else {
+            AlphaMode::Opaque
+        };
+        commands.spawn((
+            Mesh3d(cube.clone()),
+            MeshMaterial3d(materials.add(StandardMaterial {
+                base_color: Color::srgba(0.8, 0.7, 0.6, 0.5),
+                alpha_mode,
+                ..Default::default()
+            })),
+            Transform::from_xyz(x as f32 * 1.5, 0.0, 0.0),
+        ));
+    }
+
+    commands.spawn((PointLight::default(), Transform::from_xyz(4.0, 8.0, 4.0)));
+
+    let camera = commands
+        .spawn((
+            Camera3d::default(),
+            Transform::from_xyz(0.0, 3.0, 8.0).looking_at(Vec3::ZERO, Vec3::Y),
+        ))
+        .id();
+    commands.insert_resource(MainCamera(camera));
+
+    commands.spawn((
+        Text::from("Press [space] to count the items in the camera's transparent phase."),
+        Node {
+            position_type: PositionType::Absolute,
+            left: Val::Px(12.0),
+            top: Val::Px(12.0),
+            ..Default::default()
+        },
+    ));
+}
+
+fn handle_input(
+    mut keyboard_input: EventReader<KeyboardInput>,
+    camera: Res<MainCamera>,
+    mut commands: Commands,
+) {
+    for key in keyboard_input.read().filter(|key| key.state.is_pressed()) {
+        if key.key_code == KeyCode::Space {
+            commands.spawn((CountTransparentJob, JobPhaseView::new(camera.0)));
+        }
+    }
+}
+
+#[derive(Clone, Component)]
+struct CountTransparentJob;
+
+impl GraphicsJob for CountTransparentJob {
+    type In = JobRenderPhase<ViewSortedRenderPhases<Transparent3d>>;
+
+    fn run(
+        &self,
+        _world: &World,
+        _context: &mut JobRunContext,
+        transparent: JobInputItem<Self, Self::In>,
+    ) -> Result<(), JobError> {
+        info!(
+            "view {} has {} items in its transparent phase",
+            transparent.view.id(),
+            transparent.phase.items.len()
+        );
+        Ok(())
+    }
+}
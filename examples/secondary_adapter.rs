@@ -0,0 +1,163 @@
+use std::{borrow::Cow, sync::Arc};
+
+use bevy::{prelude::*, tasks::block_on};
+use bevy_render::{
+    render_resource::{
+        BindGroupEntries, Buffer, BufferDescriptor, BufferUsages, ComputePassDescriptor, Maintain,
+        MapMode, RawComputePipelineDescriptor, ShaderModuleDescriptor, ShaderSource,
+    },
+    renderer::{RenderAdapterInfo, RenderDevice, RenderInstance, RenderQueue, WgpuWrapper},
+};
+
+use gigs::*;
+use input::JobInputItem;
+use meta::JobAdapter;
+
+const COUNT: u32 = 64;
+const SIZE: u64 = (COUNT * 4) as u64;
+
+fn main() -> AppExit {
+    let mut app = App::new();
+
+    app.add_plugins((DefaultPlugins, GraphicsJobsPlugin::default()))
+        .init_graphics_job::<SquaresJob>()
+        .add_systems(Startup, (request_secondary_device, spawn_squares).chain());
+
+    app.run()
+}
+
+/// Requests a device from the first adapter other than bevy's, if there is one.
+fn request_secondary_device(
+    instance: Res<RenderInstance>,
+    primary: Res<RenderAdapterInfo>,
+    mut commands: Commands,
+) {
+    let secondary = instance
+        .enumerate_adapters(wgpu::Backends::all())
+        .into_iter()
+        .find(|adapter| {
+            let info = adapter.get_info();
+            (info.vendor, info.device, info.backend)
+                != (primary.vendor, primary.device, primary.backend)
+        });
+    let Some(secondary) = secondary else {
+        println!("No secondary adapter found, running on {}", primary.name);
+        return;
+    };
+
+    let info = secondary.get_info();
+    match block_on(secondary.request_device(&wgpu::DeviceDescriptor::default(), None)) {
+        Ok((device, queue)) => {
+            println!("Running on {}, while {} renders", info.name, primary.name);
+            commands.insert_resource(JobSecondaryDevice::new(
+                RenderDevice::from(device),
+                RenderQueue(Arc::new(WgpuWrapper::new(queue))),
+            ));
+        }
+        Err(err) => println!("Failed to request a device from {}: {err}", info.name),
+    }
+}
+
+fn spawn_squares(render_device: Res<RenderDevice>, mut commands: Commands) {
+    // the output lives on bevy's device, whichever device the job runs on
+    let output = render_device.create_buffer(&BufferDescriptor {
+        label: Some("secondary_adapter_output"),
+        size: SIZE,
+        usage: BufferUsages::COPY_DST | BufferUsages::MAP_READ,
+        mapped_at_creation: false,
+    });
+
+    commands
+        .spawn((
+            SquaresJob {
+                output: output.clone(),
+            },
+            JobAdapter::Secondary,
+        ))
+        .observe(
+            move |trigger: Trigger<JobComplete>,
+                  render_device: Res<RenderDevice>,
+                  mut exit: EventWriter<AppExit>| {
+                assert_eq!(trigger.event().0, Ok(()));
+
+                let slice = output.slice(..);
+                slice.map_async(MapMode::Read, |result| result.unwrap());
+                render_device.poll(Maintain::Wait);
+
+                let squares = slice
+                    .get_mapped_range()
+                    .chunks_exact(4)
+                    .map(|bytes| u32::from_le_bytes(bytes.try_into().unwrap()))
+                    .collect::<Vec<_>>();
+                output.unmap();
+                assert!(squares
+                    .iter()
+                    .enumerate()
+                    .all(|(i, square)| *square == (i * i) as u32));
+                println!("Read back {} squares on the primary device", squares.len());
+                exit.send(AppExit::Success);
+            },
+        );
+}
+
+/// Squares the numbers up to [`COUNT`] in a compute shader, and copies them to the
+/// primary device.
+#[derive(Clone, Component)]
+struct SquaresJob {
+    output: Buffer,
+}
+
+impl GraphicsJob for SquaresJob {
+    type In = ();
+
+    fn run(
+        &self,
+        _world: &World,
+        context: &mut JobRunContext,
+        (): JobInputItem<Self, Self::In>,
+    ) -> Result<(), JobError> {
+        info!("Squaring on the {:?} device", context.adapter());
+
+        // bevy's pipeline cache only compiles pipelines for its own device, so the
+        // pipeline is created directly on the device the job runs on
+        let render_device = context.render_device().clone();
+        let shader = render_device.create_shader_module(ShaderModuleDescriptor {
+            label: Some("secondary_adapter_shader"),
+            source: ShaderSource::Wgsl(Cow::Borrowed(include_str!("secondary_adapter.wgsl"))),
+        });
+        let pipeline = render_device.create_compute_pipeline(&RawComputePipelineDescriptor {
+            label: Some("secondary_adapter_pipeline"),
+            layout: None,
+            module: &shader,
+            entry_point: Some("main"),
+            compilation_options: Default::default(),
+            cache: None,
+        });
+
+        let squares = render_device.create_buffer(&BufferDescriptor {
+            label: Some("secondary_adapter_squares"),
+            size: SIZE,
+            usage: BufferUsages::STORAGE | BufferUsages::COPY_SRC,
+            mapped_at_creation: false,
+        });
+        let bind_group = render_device.create_bind_group(
+            "secondary_adapter_bind_group",
+            &pipeline.get_bind_group_layout(0).into(),
+            &BindGroupEntries::single(squares.as_entire_binding()),
+        );
+
+        {
+            let mut compute_pass = context.begin_compute_pass(&ComputePassDescriptor {
+                label: Some("secondary_adapter_pass"),
+                timestamp_writes: None,
+            });
+            compute_pass.set_pipeline(&pipeline);
+            compute_pass.set_bind_group(0, &bind_group, &[]);
+            compute_pass.dispatch_workgroups(COUNT / 64, 1, 1);
+        }
+
+        context.transfer_to_primary(&squares, &self.output, SIZE);
+
+        Ok(())
+    }
+}
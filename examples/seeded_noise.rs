@@ -0,0 +1,201 @@
+use bevy::{
+    asset::{embedded_asset, RenderAssetUsages},
+    input::keyboard::KeyboardInput,
+    prelude::*,
+};
+use bevy_render::{
+    render_asset::RenderAssets,
+    render_resource::{
+        binding_types::{texture_storage_2d, uniform_buffer},
+        BindGroupEntries, BindGroupLayout, BindGroupLayoutEntries, BufferInitDescriptor,
+        BufferUsages, ComputePassDescriptor, ComputePipelineDescriptor, Extent3d, ShaderStages,
+        SpecializedComputePipeline, StorageTextureAccess, TextureDimension, TextureFormat,
+        TextureUsages,
+    },
+    renderer::RenderDevice,
+    texture::GpuImage,
+};
+
+use gigs::*;
+use input::{JobComputePipeline, JobInputItem, JobSeed};
+
+const NOISE_SIZE: u32 = 256;
+const WORKGROUP_SIZE: u32 = 8;
+
+fn main() -> AppExit {
+    let mut app = App::new();
+
+    app.add_plugins((DefaultPlugins, GraphicsJobsPlugin::default()))
+        .init_graphics_job::<NoiseJob>();
+
+    embedded_asset!(app, "examples", "seeded_noise.wgsl");
+
+    app.add_systems(Startup, setup_scene)
+        .add_systems(Update, handle_input);
+
+    app.run()
+}
+
+#[derive(Resource)]
+struct NoiseImage(Handle<Image>);
+
+fn setup_scene(mut images: ResMut<Assets<Image>>, mut commands: Commands) {
+    let mut image = Image::new_fill(
+        Extent3d {
+            width: NOISE_SIZE,
+            height: NOISE_SIZE,
+            depth_or_array_layers: 1,
+        },
+        TextureDimension::D2,
+        &[0, 0, 0, 255],
+        TextureFormat::Rgba8Unorm,
+        RenderAssetUsages::RENDER_WORLD,
+    );
+    image.texture_descriptor.usage =
+        TextureUsages::COPY_DST | TextureUsages::STORAGE_BINDING | TextureUsages::TEXTURE_BINDING;
+    let image = images.add(image);
+
+    commands.spawn(Camera2d);
+    commands.spawn(Sprite {
+        image: image.clone(),
+        custom_size: Some(Vec2::splat(512.0)),
+        ..Default::default()
+    });
+
+    commands.spawn((
+        Text::from(
+            "Press [space] to bake noise with a fixed seed, or [r] to bake with a random seed.\n\
+            Baking with the fixed seed always produces the same image.",
+        ),
+        Node {
+            position_type: PositionType::Absolute,
+            left: Val::Px(12.0),
+            top: Val::Px(12.0),
+            ..Default::default()
+        },
+    ));
+
+    // the seed is derived from a stable key rather than the entity,
+    // so the output is identical between runs of this example
+    commands.spawn((
+        NoiseJob {
+            image: image.clone(),
+        },
+        JobSeed::from_key("seeded_noise"),
+    ));
+
+    commands.insert_resource(NoiseImage(image));
+}
+
+fn handle_input(
+    mut keyboard_input: EventReader<KeyboardInput>,
+    noise_image: Res<NoiseImage>,
+    mut commands: Commands,
+) {
+    for key in keyboard_input.read().filter(|key| key.state.is_pressed()) {
+        let job = NoiseJob {
+            image: noise_image.0.clone(),
+        };
+        match key.key_code {
+            KeyCode::Space => {
+                commands.spawn((job, JobSeed::from_key("seeded_noise")));
+            }
+            // jobs are seeded from their spawn order by default, so randomness is opt-in
+            KeyCode::KeyR => {
+                commands.spawn((job, JobSeed::random()));
+            }
+            _ => {}
+        }
+    }
+}
+
+#[derive(Clone, Component)]
+#[require(JobComputePipeline<NoisePipeline>)]
+struct NoiseJob {
+    image: Handle<Image>,
+}
+
+#[derive(Resource)]
+struct NoisePipeline {
+    layout: BindGroupLayout,
+    shader: Handle<Shader>,
+}
+
+impl FromWorld for NoisePipeline {
+    fn from_world(world: &mut World) -> Self {
+        let layout = world.resource::<RenderDevice>().create_bind_group_layout(
+            "seeded_noise_layout",
+            &BindGroupLayoutEntries::sequential(
+                ShaderStages::COMPUTE,
+                (
+                    texture_storage_2d(TextureFormat::Rgba8Unorm, StorageTextureAccess::WriteOnly),
+                    uniform_buffer::<UVec2>(false),
+                ),
+            ),
+        );
+        let shader = world
+            .resource::<AssetServer>()
+            .load("embedded://seeded_noise/seeded_noise.wgsl");
+
+        Self { layout, shader }
+    }
+}
+
+impl SpecializedComputePipeline for NoisePipeline {
+    type Key = ();
+
+    fn specialize(&self, (): Self::Key) -> ComputePipelineDescriptor {
+        ComputePipelineDescriptor {
+            label: Some("seeded_noise_compute".into()),
+            layout: vec![self.layout.clone()],
+            push_constant_ranges: Vec::new(),
+            shader: self.shader.clone(),
+            shader_defs: Vec::new(),
+            entry_point: "main".into(),
+            zero_initialize_workgroup_memory: false,
+        }
+    }
+}
+
+impl GraphicsJob for NoiseJob {
+    type In = (JobSeed, JobComputePipeline<NoisePipeline>);
+
+    fn run(
+        &self,
+        world: &World,
+        context: &mut JobRunContext,
+        (seed, job_pipeline): JobInputItem<Self, Self::In>,
+    ) -> Result<(), JobError> {
+        let render_device = context.render_device();
+        let Some(image) = world.resource::<RenderAssets<GpuImage>>().get(&self.image) else {
+            return Err(JobError::InputsFailed);
+        };
+
+        let seed_buffer = render_device.create_buffer_with_data(&BufferInitDescriptor {
+            label: Some("seeded_noise_seed"),
+            contents: &seed.to_le_bytes(),
+            usage: BufferUsages::UNIFORM,
+        });
+
+        let bind_group = render_device.create_bind_group(
+            "seeded_noise_bind_group",
+            &world.resource::<NoisePipeline>().layout,
+            &BindGroupEntries::sequential((&image.texture_view, seed_buffer.as_entire_binding())),
+        );
+
+        let mut compute_pass = context.begin_compute_pass(&ComputePassDescriptor {
+            label: Some("seeded_noise_compute_pass"),
+            timestamp_writes: None,
+        });
+
+        compute_pass.set_bind_group(0, &bind_group, &[]);
+        compute_pass.set_pipeline(job_pipeline);
+        compute_pass.dispatch_workgroups(
+            NOISE_SIZE.div_ceil(WORKGROUP_SIZE),
+            NOISE_SIZE.div_ceil(WORKGROUP_SIZE),
+            1,
+        );
+
+        Ok(())
+    }
+}
@@ -0,0 +1,45 @@
+@group(0) @binding(0) var output: texture_storage_2d<rgba8unorm, write>;
+@group(0) @binding(1) var<uniform> seed: vec2<u32>;
+
+const NUM_OCTAVES: u32 = 6u;
+
+// PCG-style integer hash, mixed with the job's seed
+fn hash(p: vec2<u32>) -> f32 {
+    var h = p.x * 747796405u + p.y * 2891336453u + seed.x;
+    h ^= seed.y;
+    h = ((h >> ((h >> 28u) + 4u)) ^ h) * 277803737u;
+    h = (h >> 22u) ^ h;
+    return f32(h) / 4294967295.0;
+}
+
+fn value_noise(pos: vec2<f32>) -> f32 {
+    let i = vec2<u32>(floor(pos));
+    let f = fract(pos);
+    let u = f * f * (3.0 - 2.0 * f);
+
+    let a = hash(i);
+    let b = hash(i + vec2(1u, 0u));
+    let c = hash(i + vec2(0u, 1u));
+    let d = hash(i + vec2(1u, 1u));
+
+    return mix(mix(a, b, u.x), mix(c, d, u.x), u.y);
+}
+
+@compute @workgroup_size(8, 8, 1)
+fn main(@builtin(global_invocation_id) id: vec3<u32>) {
+    let size = textureDimensions(output);
+    if any(id.xy >= size) {
+        return;
+    }
+
+    var pos = vec2<f32>(id.xy) / 32.0;
+    var amplitude = 0.5;
+    var value = 0.0;
+    for (var i: u32 = 0; i < NUM_OCTAVES; i++) {
+        value += amplitude * value_noise(pos);
+        pos *= 2.0;
+        amplitude *= 0.5;
+    }
+
+    textureStore(output, id.xy, vec4(vec3(value), 1.0));
+}
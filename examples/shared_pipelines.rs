@@ -0,0 +1,140 @@
+use std::sync::atomic::{AtomicU32, Ordering};
+
+use bevy::{asset::embedded_asset, core::FrameCount, prelude::*};
+use bevy_render::render_resource::{
+    ComputePassDescriptor, ComputePipeline, ComputePipelineDescriptor, SpecializedComputePipeline,
+};
+
+use gigs::*;
+use input::{JobComputePipeline, JobInputItem};
+
+const JOBS_PER_TYPE: u32 = 512;
+
+/// How many times [`NoopPipeline::specialize`] was called, which should only be once
+/// for every job, of either type, since they all share the same key.
+static SPECIALIZATIONS: AtomicU32 = AtomicU32::new(0);
+
+fn main() -> AppExit {
+    let mut app = App::new();
+
+    app.add_plugins((DefaultPlugins, GraphicsJobsPlugin::default()))
+        .init_graphics_job::<FirstJob>()
+        .init_graphics_job::<SecondJob>()
+        .insert_resource(Remaining(2 * JOBS_PER_TYPE))
+        .add_systems(Startup, spawn_jobs)
+        .add_observer(count_completed_jobs);
+
+    embedded_asset!(app, "examples", "shared_pipelines.wgsl");
+
+    app.run()
+}
+
+#[derive(Resource)]
+struct Remaining(u32);
+
+fn spawn_jobs(mut commands: Commands) {
+    for _ in 0..JOBS_PER_TYPE {
+        commands.spawn(FirstJob);
+        commands.spawn(SecondJob);
+    }
+}
+
+fn count_completed_jobs(
+    trigger: Trigger<JobComplete>,
+    mut remaining: ResMut<Remaining>,
+    frame_count: Res<FrameCount>,
+    mut exit: EventWriter<AppExit>,
+) {
+    assert_eq!(trigger.event().0, Ok(()));
+    remaining.0 -= 1;
+    if remaining.0 > 0 {
+        return;
+    }
+
+    let specializations = SPECIALIZATIONS.load(Ordering::Relaxed);
+    println!(
+        "Ran {} jobs over {} frames with {specializations} specialization(s)",
+        2 * JOBS_PER_TYPE,
+        frame_count.0,
+    );
+    assert_eq!(specializations, 1);
+    exit.send(AppExit::Success);
+}
+
+#[derive(Clone, Component)]
+#[require(JobComputePipeline<NoopPipeline>)]
+struct FirstJob;
+
+#[derive(Clone, Component)]
+#[require(JobComputePipeline<NoopPipeline>)]
+struct SecondJob;
+
+#[derive(Resource)]
+struct NoopPipeline {
+    shader: Handle<Shader>,
+}
+
+impl FromWorld for NoopPipeline {
+    fn from_world(world: &mut World) -> Self {
+        let shader = world
+            .resource::<AssetServer>()
+            .load("embedded://shared_pipelines/shared_pipelines.wgsl");
+
+        Self { shader }
+    }
+}
+
+impl SpecializedComputePipeline for NoopPipeline {
+    type Key = ();
+
+    fn specialize(&self, (): Self::Key) -> ComputePipelineDescriptor {
+        SPECIALIZATIONS.fetch_add(1, Ordering::Relaxed);
+
+        ComputePipelineDescriptor {
+            label: Some("shared_pipelines_compute".into()),
+            layout: Vec::new(),
+            push_constant_ranges: Vec::new(),
+            shader: self.shader.clone(),
+            shader_defs: Vec::new(),
+            entry_point: "main".into(),
+            zero_initialize_workgroup_memory: false,
+        }
+    }
+}
+
+fn dispatch_noop(context: &mut JobRunContext, pipeline: &ComputePipeline) {
+    let mut compute_pass = context.begin_compute_pass(&ComputePassDescriptor {
+        label: Some("shared_pipelines_compute_pass"),
+        timestamp_writes: None,
+    });
+    compute_pass.set_pipeline(pipeline);
+    compute_pass.dispatch_workgroups(1, 1, 1);
+}
+
+impl GraphicsJob for FirstJob {
+    type In = JobComputePipeline<NoopPipeline>;
+
+    fn run(
+        &self,
+        _world: &World,
+        context: &mut JobRunContext,
+        pipeline: JobInputItem<Self, Self::In>,
+    ) -> Result<(), JobError> {
+        dispatch_noop(context, pipeline);
+        Ok(())
+    }
+}
+
+impl GraphicsJob for SecondJob {
+    type In = JobComputePipeline<NoopPipeline>;
+
+    fn run(
+        &self,
+        _world: &World,
+        context: &mut JobRunContext,
+        pipeline: JobInputItem<Self, Self::In>,
+    ) -> Result<(), JobError> {
+        dispatch_noop(context, pipeline);
+        Ok(())
+    }
+}
@@ -0,0 +1,3 @@
+// does nothing, since the example only counts how often its pipeline is specialized
+@compute @workgroup_size(1, 1, 1)
+fn main() {}
@@ -0,0 +1,107 @@
+use bevy::{asset::RenderAssetUsages, prelude::*};
+use bevy_render::{
+    gpu_readback::{Readback, ReadbackComplete},
+    render_asset::RenderAssets,
+    render_resource::{Buffer, BufferUsages, StorageBuffer},
+    renderer::{RenderDevice, RenderQueue},
+    storage::{GpuShaderStorageBuffer, ShaderStorageBuffer},
+    Render, RenderApp, RenderSet,
+};
+
+use gigs::*;
+use input::{JobBufferResource, JobInputItem, JobResourceBuffer};
+
+fn main() -> AppExit {
+    let mut app = App::new();
+
+    app.add_plugins((DefaultPlugins, GraphicsJobsPlugin::default()))
+        .init_graphics_job::<SnapshotJob>()
+        .add_systems(Startup, spawn_snapshot);
+
+    let render_app = app.sub_app_mut(RenderApp);
+    render_app.init_resource::<SimulationState>().add_systems(
+        Render,
+        // the state must be written before jobs check their inputs, which
+        // happens after `RenderSet::Prepare`
+        step_simulation.in_set(RenderSet::PrepareResources),
+    );
+
+    app.run()
+}
+
+/// Global simulation state, shared by every job through [`JobResourceBuffer`].
+/// Here it only holds the current step, but it could be any `ShaderType`.
+#[derive(Resource)]
+struct SimulationState(StorageBuffer<u32>);
+
+impl Default for SimulationState {
+    fn default() -> Self {
+        let mut buffer = StorageBuffer::default();
+        buffer.set_label(Some("simulation_state"));
+        buffer.add_usages(BufferUsages::COPY_SRC);
+        Self(buffer)
+    }
+}
+
+impl JobBufferResource for SimulationState {
+    fn buffer(&self) -> Option<&Buffer> {
+        self.0.buffer()
+    }
+}
+
+fn step_simulation(
+    mut state: ResMut<SimulationState>,
+    render_device: Res<RenderDevice>,
+    render_queue: Res<RenderQueue>,
+) {
+    *state.0.get_mut() += 1;
+    state.0.write_buffer(&render_device, &render_queue);
+}
+
+/// Copies the current simulation state into a buffer that can be read back.
+#[derive(Clone, Component)]
+struct SnapshotJob(Handle<ShaderStorageBuffer>);
+
+impl GraphicsJob for SnapshotJob {
+    type In = JobResourceBuffer<SimulationState>;
+
+    fn run(
+        &self,
+        world: &World,
+        context: &mut JobRunContext,
+        state: JobInputItem<Self, Self::In>,
+    ) -> Result<(), JobError> {
+        let snapshot = world
+            .resource::<RenderAssets<GpuShaderStorageBuffer>>()
+            .get(&self.0)
+            .ok_or(JobError::ExecutionFailed)?;
+
+        context.copy_buffer_to_buffer(state, 0, &snapshot.buffer, 0, state.size());
+        Ok(())
+    }
+}
+
+fn spawn_snapshot(mut buffers: ResMut<Assets<ShaderStorageBuffer>>, mut commands: Commands) {
+    let mut snapshot = ShaderStorageBuffer::from(0u32);
+    snapshot.buffer_description.usage |= BufferUsages::COPY_DST | BufferUsages::COPY_SRC;
+    snapshot.asset_usage = RenderAssetUsages::RENDER_WORLD;
+    let snapshot = buffers.add(snapshot);
+
+    commands.spawn(SnapshotJob(snapshot.clone())).observe(
+        move |trigger: Trigger<JobComplete>, mut commands: Commands| {
+            assert_eq!(trigger.event().0, Ok(()));
+
+            commands.spawn(Readback::buffer(snapshot.clone())).observe(
+                |trigger: Trigger<ReadbackComplete>,
+                 mut commands: Commands,
+                 mut exit: EventWriter<AppExit>| {
+                    let step = u32::from_le_bytes(trigger.event().0[0..4].try_into().unwrap());
+                    println!("Simulation state snapshotted at step {step}");
+
+                    commands.entity(trigger.entity()).despawn();
+                    exit.send(AppExit::Success);
+                },
+            );
+        },
+    );
+}
@@ -0,0 +1,215 @@
+use bevy::{
+    asset::{embedded_asset, RenderAssetUsages},
+    core_pipeline::{fullscreen_vertex_shader::fullscreen_shader_vertex_state, Skybox},
+    image::BevyDefault,
+    prelude::*,
+};
+use bevy_render::{
+    render_resource::{
+        BindGroupLayout, ColorTargetState, ColorWrites, Extent3d, FragmentState, LoadOp,
+        MultisampleState, Operations, PrimitiveState, RenderPassColorAttachment,
+        RenderPassDescriptor, RenderPipelineDescriptor, SpecializedRenderPipeline, StoreOp,
+        TextureDimension, TextureFormat, TextureViewDescriptor, TextureViewDimension,
+    },
+    renderer::RenderDevice,
+    view::ViewTarget,
+};
+
+use gigs::*;
+use input::{JobInputItem, JobRenderPipeline, JobSkybox, JobSkyboxBindGroups};
+
+const FACE_SIZE: u32 = 64;
+
+fn main() -> AppExit {
+    let mut app = App::new();
+
+    app.add_plugins((DefaultPlugins, GraphicsJobsPlugin::default()))
+        .init_graphics_job::<SkyFillJob>();
+
+    embedded_asset!(app, "examples", "skybox_fill.wgsl");
+
+    app.add_systems(Startup, setup_scene)
+        .add_systems(Update, (rotate_skybox, spawn_sky_fill));
+
+    app.run()
+}
+
+#[derive(Resource)]
+struct SkyCamera(Entity);
+
+fn setup_scene(mut images: ResMut<Assets<Image>>, mut commands: Commands) {
+    // bevy only draws the skybox of 3d cameras, but jobs can read the skybox of any
+    // camera with `Skybox`. The camera doesn't clear its target, since the job fills it with the sky
+    // before the sprites are drawn, so the sky shows wherever no sprite is.
+    let camera = commands
+        .spawn((
+            Camera2d,
+            Camera {
+                clear_color: ClearColorConfig::None,
+                ..Default::default()
+            },
+            Msaa::Off,
+            Skybox {
+                image: images.add(sky_cubemap()),
+                // scaled back down by the camera's default exposure
+                brightness: 1000.0,
+                ..Default::default()
+            },
+        ))
+        .id();
+    commands.insert_resource(SkyCamera(camera));
+
+    for (x, color) in [
+        (-320.0, Color::srgb(0.9, 0.6, 0.3)),
+        (0.0, Color::srgb(0.3, 0.7, 0.4)),
+        (320.0, Color::srgb(0.6, 0.4, 0.8)),
+    ] {
+        commands.spawn((
+            Sprite::from_color(color, Vec2::splat(192.0)),
+            Transform::from_xyz(x, 0.0, 0.0),
+        ));
+    }
+}
+
+/// Builds a cubemap fading from a pale horizon to a deep blue zenith, over a dark ground.
+fn sky_cubemap() -> Image {
+    const ZENITH: [u8; 4] = [40, 90, 200, 255];
+    const HORIZON: [u8; 4] = [190, 215, 240, 255];
+    const GROUND: [u8; 4] = [60, 55, 50, 255];
+
+    // faces are ordered +x, -x, +y, -y, +z, -z, and each face's rows go from top to bottom
+    let side = (0..FACE_SIZE).flat_map(|row| {
+        let t = row as f32 / (FACE_SIZE - 1) as f32;
+        let texel: [u8; 4] =
+            core::array::from_fn(|i| (ZENITH[i] as f32 * (1.0 - t) + HORIZON[i] as f32 * t) as u8);
+        texel.repeat(FACE_SIZE as usize)
+    });
+    let face = |texel: [u8; 4]| texel.repeat((FACE_SIZE * FACE_SIZE) as usize);
+    let data = [
+        side.clone().collect(),
+        side.clone().collect(),
+        face(ZENITH),
+        face(GROUND),
+        side.clone().collect(),
+        side.collect::<Vec<_>>(),
+    ]
+    .concat();
+
+    let mut image = Image::new(
+        Extent3d {
+            width: FACE_SIZE,
+            height: FACE_SIZE,
+            depth_or_array_layers: 6,
+        },
+        TextureDimension::D2,
+        data,
+        TextureFormat::Rgba8UnormSrgb,
+        RenderAssetUsages::RENDER_WORLD,
+    );
+    image.texture_view_descriptor = Some(TextureViewDescriptor {
+        dimension: Some(TextureViewDimension::Cube),
+        ..Default::default()
+    });
+    image
+}
+
+fn rotate_skybox(time: Res<Time>, mut skyboxes: Query<&mut Skybox>) {
+    for mut skybox in &mut skyboxes {
+        skybox.rotation = Quat::from_rotation_y(time.elapsed_secs() * 0.2);
+    }
+}
+
+fn spawn_sky_fill(camera: Res<SkyCamera>, mut commands: Commands) {
+    commands.spawn((
+        SkyFillJob,
+        JobSkybox::new(camera.0),
+        JobRenderPipeline::<SkyFillPipeline>(TextureFormat::bevy_default()),
+    ));
+}
+
+#[derive(Clone, Component)]
+struct SkyFillJob;
+
+#[derive(Resource)]
+struct SkyFillPipeline {
+    layout: BindGroupLayout,
+    shader: Handle<Shader>,
+}
+
+impl FromWorld for SkyFillPipeline {
+    fn from_world(world: &mut World) -> Self {
+        let layout = world
+            .resource::<JobSkyboxBindGroups>()
+            .layout(world.resource::<RenderDevice>())
+            .clone();
+        let shader = world
+            .resource::<AssetServer>()
+            .load("embedded://skybox_fill/skybox_fill.wgsl");
+
+        Self { layout, shader }
+    }
+}
+
+impl SpecializedRenderPipeline for SkyFillPipeline {
+    type Key = TextureFormat;
+
+    fn specialize(&self, format: Self::Key) -> RenderPipelineDescriptor {
+        RenderPipelineDescriptor {
+            label: Some("sky_fill_pipeline".into()),
+            layout: vec![self.layout.clone()],
+            push_constant_ranges: Vec::new(),
+            vertex: fullscreen_shader_vertex_state(),
+            primitive: PrimitiveState::default(),
+            depth_stencil: None,
+            multisample: MultisampleState::default(),
+            fragment: Some(FragmentState {
+                shader: self.shader.clone(),
+                shader_defs: Vec::new(),
+                entry_point: "fragment".into(),
+                targets: vec![Some(ColorTargetState {
+                    format,
+                    blend: None,
+                    write_mask: ColorWrites::ALL,
+                })],
+            }),
+            zero_initialize_workgroup_memory: false,
+        }
+    }
+}
+
+impl GraphicsJob for SkyFillJob {
+    type In = (JobSkybox, JobRenderPipeline<SkyFillPipeline>);
+
+    fn run(
+        &self,
+        _world: &World,
+        context: &mut JobRunContext,
+        (skybox, pipeline): JobInputItem<Self, Self::In>,
+    ) -> Result<(), JobError> {
+        let view_target = skybox
+            .view
+            .get::<ViewTarget>()
+            .ok_or(JobError::ExecutionFailed)?;
+
+        let mut render_pass = context.begin_render_pass(&RenderPassDescriptor {
+            label: Some("sky_fill_pass"),
+            color_attachments: &[Some(RenderPassColorAttachment {
+                view: view_target.main_texture_view(),
+                resolve_target: None,
+                ops: Operations {
+                    load: LoadOp::Load,
+                    store: StoreOp::Store,
+                },
+            })],
+            depth_stencil_attachment: None,
+            timestamp_writes: None,
+            occlusion_query_set: None,
+        });
+
+        render_pass.set_pipeline(pipeline);
+        render_pass.set_bind_group(0, skybox.bind_group, &skybox.dynamic_offsets());
+        render_pass.draw(0..3, 0..1);
+
+        Ok(())
+    }
+}
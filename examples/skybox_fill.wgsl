@@ -0,0 +1,27 @@
+#import bevy_render::view::View
+#import bevy_core_pipeline::fullscreen_vertex_shader::FullscreenVertexOutput
+
+struct SkyboxUniforms {
+    brightness: f32,
+    transform: mat4x4<f32>,
+}
+
+@group(0) @binding(0) var skybox: texture_cube<f32>;
+@group(0) @binding(1) var skybox_sampler: sampler;
+@group(0) @binding(2) var<uniform> view: View;
+@group(0) @binding(3) var<uniform> uniforms: SkyboxUniforms;
+
+@fragment
+fn fragment(in: FullscreenVertexOutput) -> @location(0) vec4<f32> {
+    // a 2d camera's projection is orthographic, so rather than unprojecting the
+    // fragment like bevy's skybox shader, look through a fixed perspective
+    let uv = (in.position.xy - view.viewport.xy) / view.viewport.zw;
+    let aspect = view.viewport.z / view.viewport.w;
+    let ray = normalize(vec3((uv.x * 2.0 - 1.0) * aspect, 1.0 - uv.y * 2.0, -1.5));
+
+    // the skybox's rotation is applied like in bevy's skybox shader, and cubemaps
+    // are left-handed, so z is negated
+    let direction = (uniforms.transform * vec4(ray, 0.0)).xyz;
+    let sky = textureSample(skybox, skybox_sampler, direction * vec3(1.0, 1.0, -1.0));
+    return vec4(sky.rgb * uniforms.brightness, 1.0);
+}
@@ -0,0 +1,242 @@
+// the `ShaderType` derive emits size checks that newer compilers flag as unused
+#![allow(dead_code)]
+
+use bevy::{
+    asset::{embedded_asset, RenderAssetUsages},
+    core_pipeline::{
+        fullscreen_vertex_shader::fullscreen_shader_vertex_state, prepass::DepthPrepass,
+    },
+    prelude::*,
+};
+use bevy_render::{
+    render_resource::{
+        binding_types::texture_depth_2d, BindGroupEntries, BindGroupLayout, BindGroupLayoutEntries,
+        ColorTargetState, ColorWrites, Extent3d, FragmentState, LoadOp, MultisampleState,
+        Operations, PrimitiveState, RenderPassColorAttachment, RenderPassDescriptor,
+        RenderPipelineDescriptor, ShaderStages, ShaderType, SpecializedRenderPipeline, StoreOp,
+        TextureDimension, TextureFormat, TextureUsages,
+    },
+    renderer::RenderDevice,
+};
+
+use gigs::*;
+use input::{
+    JobDepthCopy, JobImageView, JobInputItem, JobRenderPipeline, JobViewParams,
+    JobViewParamsBindGroups,
+};
+
+const FOG_SIZE: UVec2 = UVec2::new(640, 360);
+const FOG_FORMAT: TextureFormat = TextureFormat::Rgba8UnormSrgb;
+
+fn main() -> AppExit {
+    let mut app = App::new();
+
+    app.add_plugins((DefaultPlugins, GraphicsJobsPlugin::default()))
+        .init_graphics_job::<FogLayerJob>();
+
+    embedded_asset!(app, "examples", "soft_particles.wgsl");
+
+    app.add_systems(Startup, setup_scene)
+        .add_systems(Update, spawn_fog_layer);
+
+    app.run()
+}
+
+#[derive(Resource)]
+struct FogLayer {
+    camera: Entity,
+    image: Handle<Image>,
+}
+
+fn setup_scene(
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    mut images: ResMut<Assets<Image>>,
+    mut commands: Commands,
+) {
+    commands.spawn((
+        Mesh3d(meshes.add(Plane3d::default().mesh().size(12.0, 12.0))),
+        MeshMaterial3d(materials.add(Color::srgb(0.3, 0.5, 0.3))),
+    ));
+    for x in -2..=2 {
+        commands.spawn((
+            Mesh3d(meshes.add(Cuboid::new(0.8, 2.0, 0.8))),
+            MeshMaterial3d(materials.add(Color::srgb(0.8, 0.7, 0.6))),
+            Transform::from_xyz(x as f32 * 1.8, 0.5, -x as f32 * 0.9),
+        ));
+    }
+    commands.spawn((
+        DirectionalLight::default(),
+        Transform::from_xyz(4.0, 8.0, 4.0).looking_at(Vec3::ZERO, Vec3::Y),
+    ));
+
+    // the depth prepass lets the depth texture be copied directly, while cameras
+    // without one need `TEXTURE_BINDING` in their `depth_texture_usages` to be resolved
+    let camera = commands
+        .spawn((
+            Camera3d::default(),
+            DepthPrepass,
+            Msaa::Off,
+            Transform::from_xyz(0.0, 3.0, 8.0).looking_at(Vec3::ZERO, Vec3::Y),
+        ))
+        .id();
+
+    let mut image = Image::new_fill(
+        Extent3d {
+            width: FOG_SIZE.x,
+            height: FOG_SIZE.y,
+            depth_or_array_layers: 1,
+        },
+        TextureDimension::D2,
+        &[0, 0, 0, 0],
+        FOG_FORMAT,
+        RenderAssetUsages::RENDER_WORLD,
+    );
+    image.texture_descriptor.usage |= TextureUsages::RENDER_ATTACHMENT;
+    let image = images.add(image);
+
+    // the fog is drawn over the scene by the UI, since jobs run before the camera
+    // renders and clears its target
+    commands.spawn((
+        ImageNode::new(image.clone()),
+        Node {
+            position_type: PositionType::Absolute,
+            width: Val::Percent(100.0),
+            height: Val::Percent(100.0),
+            ..Default::default()
+        },
+    ));
+
+    commands.insert_resource(FogLayer { camera, image });
+}
+
+fn spawn_fog_layer(fog: Res<FogLayer>, time: Res<Time>, mut commands: Commands) {
+    // the layer drifts through the scene, fading out where it meets the geometry
+    // rather than cutting through it with a hard edge
+    let distance = 8.0 + time.elapsed_secs().sin() * 2.0;
+    commands.spawn((
+        FogLayerJob,
+        JobDepthCopy::new(fog.camera),
+        JobViewParams::new(
+            fog.camera,
+            FogParams {
+                color: LinearRgba::rgb(0.8, 0.85, 0.9).to_vec4(),
+                distance,
+                softness: 0.75,
+            },
+        ),
+        JobImageView::mip_level(fog.image.clone(), 0),
+        JobRenderPipeline::<FogLayerPipeline>(()),
+    ));
+}
+
+#[derive(Clone, ShaderType)]
+struct FogParams {
+    color: Vec4,
+    distance: f32,
+    softness: f32,
+}
+
+#[derive(Clone, Component)]
+struct FogLayerJob;
+
+#[derive(Resource)]
+struct FogLayerPipeline {
+    view_layout: BindGroupLayout,
+    depth_layout: BindGroupLayout,
+    shader: Handle<Shader>,
+}
+
+impl FromWorld for FogLayerPipeline {
+    fn from_world(world: &mut World) -> Self {
+        let render_device = world.resource::<RenderDevice>();
+        let view_layout = world
+            .resource::<JobViewParamsBindGroups<FogParams>>()
+            .layout(render_device)
+            .clone();
+        let depth_layout = render_device.create_bind_group_layout(
+            "fog_layer_depth_layout",
+            &BindGroupLayoutEntries::single(ShaderStages::FRAGMENT, texture_depth_2d()),
+        );
+        let shader = world
+            .resource::<AssetServer>()
+            .load("embedded://soft_particles/soft_particles.wgsl");
+
+        Self {
+            view_layout,
+            depth_layout,
+            shader,
+        }
+    }
+}
+
+impl SpecializedRenderPipeline for FogLayerPipeline {
+    type Key = ();
+
+    fn specialize(&self, (): Self::Key) -> RenderPipelineDescriptor {
+        RenderPipelineDescriptor {
+            label: Some("fog_layer_pipeline".into()),
+            layout: vec![self.view_layout.clone(), self.depth_layout.clone()],
+            push_constant_ranges: Vec::new(),
+            vertex: fullscreen_shader_vertex_state(),
+            primitive: PrimitiveState::default(),
+            depth_stencil: None,
+            multisample: MultisampleState::default(),
+            fragment: Some(FragmentState {
+                shader: self.shader.clone(),
+                shader_defs: Vec::new(),
+                entry_point: "fragment".into(),
+                targets: vec![Some(ColorTargetState {
+                    format: FOG_FORMAT,
+                    blend: None,
+                    write_mask: ColorWrites::ALL,
+                })],
+            }),
+            zero_initialize_workgroup_memory: false,
+        }
+    }
+}
+
+impl GraphicsJob for FogLayerJob {
+    type In = (
+        JobDepthCopy,
+        JobViewParams<FogParams>,
+        JobImageView,
+        JobRenderPipeline<FogLayerPipeline>,
+    );
+
+    fn run(
+        &self,
+        world: &World,
+        context: &mut JobRunContext,
+        (depth, view_params, target, pipeline): JobInputItem<Self, Self::In>,
+    ) -> Result<(), JobError> {
+        let depth_bind_group = context.render_device().create_bind_group(
+            "fog_layer_depth_bind_group",
+            &world.resource::<FogLayerPipeline>().depth_layout,
+            &BindGroupEntries::single(depth.texture_view),
+        );
+
+        let mut render_pass = context.begin_render_pass(&RenderPassDescriptor {
+            label: Some("fog_layer_pass"),
+            color_attachments: &[Some(RenderPassColorAttachment {
+                view: &target.view,
+                resolve_target: None,
+                ops: Operations {
+                    load: LoadOp::Load,
+                    store: StoreOp::Store,
+                },
+            })],
+            depth_stencil_attachment: None,
+            timestamp_writes: None,
+            occlusion_query_set: None,
+        });
+
+        render_pass.set_pipeline(pipeline);
+        render_pass.set_bind_group(0, view_params.bind_group, &view_params.dynamic_offsets());
+        render_pass.set_bind_group(1, &depth_bind_group, &[]);
+        render_pass.draw(0..3, 0..1);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,26 @@
+#import bevy_render::view::View
+#import bevy_core_pipeline::fullscreen_vertex_shader::FullscreenVertexOutput
+
+struct FogParams {
+    color: vec4<f32>,
+    distance: f32,
+    softness: f32,
+}
+
+@group(0) @binding(0) var<uniform> view: View;
+@group(0) @binding(1) var<uniform> params: FogParams;
+@group(1) @binding(0) var depth: texture_depth_2d;
+
+@fragment
+fn fragment(in: FullscreenVertexOutput) -> @location(0) vec4<f32> {
+    let depth_size = textureDimensions(depth);
+    let pixel = min(vec2<u32>(in.uv * vec2<f32>(depth_size)), depth_size - 1u);
+    let ndc_depth = textureLoad(depth, pixel, 0);
+
+    // bevy's reversed infinite projection, where the far plane is at zero depth
+    let scene_distance = select(view.clip_from_view[3][2] / ndc_depth, 1e6, ndc_depth <= 0.0);
+
+    // the layer is hidden behind geometry, with a soft edge instead of a hard one
+    let fade = saturate((scene_distance - params.distance) / params.softness);
+    return vec4(params.color.rgb, fade * 0.6);
+}
@@ -0,0 +1,89 @@
+use bevy::prelude::*;
+use bevy_render::render_resource::{ComputePipelineDescriptor, SpecializedComputePipeline};
+
+use gigs::*;
+use input::{JobComputePipeline, JobInputItem};
+
+// the shader is missing its closing brace, so the pipeline never compiles
+const BROKEN_SHADER: &str = "
+@compute @workgroup_size(1)
+fn main() {
+";
+
+fn main() -> AppExit {
+    let mut app = App::new();
+
+    app.add_plugins((DefaultPlugins, GraphicsJobsPlugin::default()))
+        .init_graphics_job::<StalledJob>();
+
+    // the job waits for its pipeline until it times out. Halfway there, a warning names
+    // the input it's stuck on:
+    // "input `JobComputePipeline<BrokenPipeline>` of job `StalledJob` never became ready
+    // after waiting 8 frames"
+    app.insert_resource(JobExecutionSettings {
+        stall_warning_frames: Some(8),
+        ..Default::default()
+    })
+    .add_systems(Startup, spawn_job);
+
+    app.run()
+}
+
+fn spawn_job(mut commands: Commands) {
+    commands.spawn(Camera2d);
+    commands
+        .spawn((StalledJob, JobComputePipeline::<BrokenPipeline>::new(())))
+        .observe(
+            |trigger: Trigger<JobComplete>, mut exit: EventWriter<AppExit>| {
+                info!("the job completed with {:?}", trigger.event().0);
+                exit.send(AppExit::Success);
+            },
+        );
+}
+
+#[derive(Clone, Component)]
+struct StalledJob;
+
+#[derive(Resource)]
+struct BrokenPipeline {
+    shader: Handle<Shader>,
+}
+
+impl FromWorld for BrokenPipeline {
+    fn from_world(world: &mut World) -> Self {
+        let shader = world
+            .resource_mut::<Assets<Shader>>()
+            .add(Shader::from_wgsl(BROKEN_SHADER, "stalled_pipeline.wgsl"));
+
+        Self { shader }
+    }
+}
+
+impl SpecializedComputePipeline for BrokenPipeline {
+    type Key = ();
+
+    fn specialize(&self, (): Self::Key) -> ComputePipelineDescriptor {
+        ComputePipelineDescriptor {
+            label: Some("broken_pipeline".into()),
+            layout: Vec::new(),
+            push_constant_ranges: Vec::new(),
+            shader: self.shader.clone(),
+            shader_defs: Vec::new(),
+            entry_point: "main".into(),
+            zero_initialize_workgroup_memory: false,
+        }
+    }
+}
+
+impl GraphicsJob for StalledJob {
+    type In = JobComputePipeline<BrokenPipeline>;
+
+    fn run(
+        &self,
+        _world: &World,
+        _context: &mut JobRunContext,
+        _pipeline: JobInputItem<Self, Self::In>,
+    ) -> Result<(), JobError> {
+        unreachable!("the job's pipeline never compiles")
+    }
+}
@@ -0,0 +1,172 @@
+use bevy::{asset::embedded_asset, image::BevyDefault, input::keyboard::KeyboardInput, prelude::*};
+use bevy_render::render_resource::{
+    ColorTargetState, ColorWrites, FragmentState, LoadOp, MultisampleState, Operations,
+    PrimitiveState, RenderPassColorAttachment, RenderPassDescriptor, RenderPipelineDescriptor,
+    SpecializedRenderPipeline, StoreOp, TextureFormat, VertexState,
+};
+
+use gigs::*;
+use input::{JobInputItem, JobRenderBundle, JobRenderBundles, JobRenderPipeline, JobViewTarget};
+
+const GRID_BUNDLE: &str = "static_grid";
+
+fn main() -> AppExit {
+    let mut app = App::new();
+
+    app.add_plugins((DefaultPlugins, GraphicsJobsPlugin::default()))
+        .init_graphics_job::<GridJob>();
+
+    embedded_asset!(app, "examples", "static_bundle.wgsl");
+
+    app.add_systems(Startup, setup_scene)
+        .add_systems(Update, (handle_input, spawn_grid).chain());
+
+    app.run()
+}
+
+/// The number of cells in the grid, each drawn with its own draw call.
+#[derive(Resource)]
+struct GridCells(u32);
+
+fn setup_scene(mut commands: Commands) {
+    // as in the `vignette` example, the camera doesn't clear its target,
+    // since the grid is drawn before the camera renders
+    commands.spawn((
+        Camera2d,
+        Camera {
+            clear_color: ClearColorConfig::None,
+            ..Default::default()
+        },
+        Msaa::Off,
+    ));
+
+    commands.spawn((
+        Text::new("Press [space] to add cells to the grid, which re-records its bundle."),
+        Node {
+            position_type: PositionType::Absolute,
+            left: Val::Px(12.0),
+            top: Val::Px(12.0),
+            ..Default::default()
+        },
+    ));
+
+    commands.insert_resource(GridCells(256));
+}
+
+fn handle_input(
+    mut keyboard_input: EventReader<KeyboardInput>,
+    mut cells: ResMut<GridCells>,
+    mut bundles: ResMut<JobRenderBundles>,
+) {
+    for key in keyboard_input.read().filter(|key| key.state.is_pressed()) {
+        if key.key_code == KeyCode::Space {
+            cells.0 = (cells.0 + 128).min(1024);
+            // the cached bundle still draws the old cells, so it must be recorded again
+            bundles.invalidate(GRID_BUNDLE);
+        }
+    }
+}
+
+fn spawn_grid(camera: Single<Entity, With<Camera>>, cells: Res<GridCells>, mut commands: Commands) {
+    // the camera isn't HDR, so its main texture has the default format
+    let format = TextureFormat::bevy_default();
+    commands.spawn((
+        GridJob { cells: cells.0 },
+        JobViewTarget::new(*camera),
+        JobRenderPipeline::<GridPipeline>(format),
+        JobRenderBundle::new(GRID_BUNDLE, format),
+    ));
+}
+
+#[derive(Clone, Component)]
+struct GridJob {
+    cells: u32,
+}
+
+#[derive(Resource)]
+struct GridPipeline {
+    shader: Handle<Shader>,
+}
+
+impl FromWorld for GridPipeline {
+    fn from_world(world: &mut World) -> Self {
+        let shader = world
+            .resource::<AssetServer>()
+            .load("embedded://static_bundle/static_bundle.wgsl");
+
+        Self { shader }
+    }
+}
+
+impl SpecializedRenderPipeline for GridPipeline {
+    type Key = TextureFormat;
+
+    fn specialize(&self, format: Self::Key) -> RenderPipelineDescriptor {
+        RenderPipelineDescriptor {
+            label: Some("static_grid_pipeline".into()),
+            layout: Vec::new(),
+            push_constant_ranges: Vec::new(),
+            vertex: VertexState {
+                shader: self.shader.clone(),
+                shader_defs: Vec::new(),
+                entry_point: "vertex".into(),
+                buffers: Vec::new(),
+            },
+            primitive: PrimitiveState::default(),
+            depth_stencil: None,
+            multisample: MultisampleState::default(),
+            fragment: Some(FragmentState {
+                shader: self.shader.clone(),
+                shader_defs: Vec::new(),
+                entry_point: "fragment".into(),
+                targets: vec![Some(ColorTargetState {
+                    format,
+                    blend: None,
+                    write_mask: ColorWrites::ALL,
+                })],
+            }),
+            zero_initialize_workgroup_memory: false,
+        }
+    }
+}
+
+impl GraphicsJob for GridJob {
+    type In = (
+        JobViewTarget,
+        JobRenderPipeline<GridPipeline>,
+        JobRenderBundle,
+    );
+
+    fn run(
+        &self,
+        _world: &World,
+        context: &mut JobRunContext,
+        (target, pipeline, bundle): JobInputItem<Self, Self::In>,
+    ) -> Result<(), JobError> {
+        // the draws are only recorded the first time, and after the bundle is invalidated
+        let bundle = bundle.get_or_record(|encoder| {
+            encoder.set_pipeline(pipeline);
+            for cell in 0..self.cells {
+                encoder.draw(0..6, cell..cell + 1);
+            }
+        });
+
+        let mut render_pass = context.begin_render_pass(&RenderPassDescriptor {
+            label: Some("static_grid_pass"),
+            color_attachments: &[Some(RenderPassColorAttachment {
+                view: target.target.main_texture_view(),
+                resolve_target: None,
+                ops: Operations {
+                    load: LoadOp::Clear(Color::BLACK.to_linear().into()),
+                    store: StoreOp::Store,
+                },
+            })],
+            depth_stencil_attachment: None,
+            timestamp_writes: None,
+            occlusion_query_set: None,
+        });
+        render_pass.execute_bundles([&*bundle]);
+
+        Ok(())
+    }
+}
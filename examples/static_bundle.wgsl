@@ -0,0 +1,24 @@
+const COLUMNS: u32 = 32u;
+
+struct VertexOutput {
+    @builtin(position) position: vec4<f32>,
+    @location(0) color: vec3<f32>,
+};
+
+@vertex
+fn vertex(@builtin(vertex_index) vertex: u32, @builtin(instance_index) instance: u32) -> VertexOutput {
+    // two triangles per cell, each cell drawn by its own draw call
+    let corners = array(vec2(0.0, 0.0), vec2(1.0, 0.0), vec2(0.0, 1.0), vec2(0.0, 1.0), vec2(1.0, 0.0), vec2(1.0, 1.0));
+    let cell = vec2(f32(instance % COLUMNS), f32(instance / COLUMNS));
+    let position = (cell + 0.1 + corners[vertex] * 0.8) / f32(COLUMNS) * 1.8 - 0.9;
+
+    var out: VertexOutput;
+    out.position = vec4(position.x, -position.y, 0.0, 1.0);
+    out.color = 0.5 + 0.5 * cos(f32(instance) * 0.05 + vec3(0.0, 2.0, 4.0));
+    return out;
+}
+
+@fragment
+fn fragment(in: VertexOutput) -> @location(0) vec4<f32> {
+    return vec4(in.color, 1.0);
+}
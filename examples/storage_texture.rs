@@ -0,0 +1,142 @@
+use bevy::{
+    asset::{embedded_asset, RenderAssetUsages},
+    prelude::*,
+};
+use bevy_render::{
+    render_resource::{
+        BindGroupEntries, BindGroupLayout, BindGroupLayoutEntries, ComputePassDescriptor,
+        ComputePipelineDescriptor, Extent3d, ShaderStages, SpecializedComputePipeline,
+        TextureDimension, TextureFormat, TextureUsages, TextureViewDescriptor,
+    },
+    renderer::RenderDevice,
+};
+
+use gigs::*;
+use input::{JobComputePipeline, JobInputItem, JobStorageTexture, JobWorkgroupSize};
+
+const SIZE: u32 = 256;
+// the storage format, which differs from the format the image is sampled with
+const STORAGE_FORMAT: TextureFormat = TextureFormat::Rgba8Unorm;
+
+fn main() -> AppExit {
+    let mut app = App::new();
+
+    app.add_plugins((DefaultPlugins, GraphicsJobsPlugin::default()))
+        .init_graphics_job::<RingsJob>();
+
+    embedded_asset!(app, "examples", "storage_texture.wgsl");
+
+    app.add_systems(Startup, setup_scene);
+
+    app.run()
+}
+
+fn setup_scene(mut images: ResMut<Assets<Image>>, mut commands: Commands) {
+    let mut image = Image::new_fill(
+        Extent3d {
+            width: SIZE,
+            height: SIZE,
+            depth_or_array_layers: 1,
+        },
+        TextureDimension::D2,
+        &[0, 0, 0, 255],
+        STORAGE_FORMAT,
+        RenderAssetUsages::RENDER_WORLD,
+    );
+    image.texture_descriptor.usage =
+        TextureUsages::COPY_DST | TextureUsages::STORAGE_BINDING | TextureUsages::TEXTURE_BINDING;
+    // sRGB formats can't be used as storage, so the sprite samples the image through
+    // an sRGB view instead
+    image.texture_descriptor.view_formats = &[TextureFormat::Rgba8UnormSrgb];
+    image.texture_view_descriptor = Some(TextureViewDescriptor {
+        format: Some(TextureFormat::Rgba8UnormSrgb),
+        ..Default::default()
+    });
+    let image = images.add(image);
+
+    commands.spawn(Camera2d);
+    commands.spawn(Sprite {
+        image: image.clone(),
+        custom_size: Some(Vec2::splat(512.0)),
+        ..Default::default()
+    });
+
+    commands.spawn((
+        RingsJob,
+        JobStorageTexture::write_only(image, STORAGE_FORMAT),
+        JobComputePipeline::<RingsPipeline>::new(()).with_workgroup_size(UVec3::new(8, 8, 1)),
+    ));
+}
+
+#[derive(Clone, Component)]
+struct RingsJob;
+
+#[derive(Resource)]
+struct RingsPipeline {
+    layout: BindGroupLayout,
+    shader: Handle<Shader>,
+}
+
+impl FromWorld for RingsPipeline {
+    fn from_world(world: &mut World) -> Self {
+        // the layout entry matches the view created by `JobStorageTexture`
+        let storage = JobStorageTexture::write_only(Handle::default(), STORAGE_FORMAT);
+        let layout = world.resource::<RenderDevice>().create_bind_group_layout(
+            "storage_texture_layout",
+            &BindGroupLayoutEntries::single(ShaderStages::COMPUTE, storage.layout_entry()),
+        );
+        let shader = world
+            .resource::<AssetServer>()
+            .load("embedded://storage_texture/storage_texture.wgsl");
+
+        Self { layout, shader }
+    }
+}
+
+impl SpecializedComputePipeline for RingsPipeline {
+    type Key = ();
+
+    fn specialize(&self, (): Self::Key) -> ComputePipelineDescriptor {
+        ComputePipelineDescriptor {
+            label: Some("storage_texture_compute".into()),
+            layout: vec![self.layout.clone()],
+            push_constant_ranges: Vec::new(),
+            shader: self.shader.clone(),
+            shader_defs: Vec::new(),
+            entry_point: "main".into(),
+            zero_initialize_workgroup_memory: false,
+        }
+    }
+}
+
+impl GraphicsJob for RingsJob {
+    type In = (
+        JobStorageTexture,
+        JobComputePipeline<RingsPipeline>,
+        JobWorkgroupSize<RingsPipeline>,
+    );
+
+    fn run(
+        &self,
+        world: &World,
+        context: &mut JobRunContext,
+        (storage, pipeline, workgroup_size): JobInputItem<Self, Self::In>,
+    ) -> Result<(), JobError> {
+        let bind_group = context.render_device().create_bind_group(
+            "storage_texture_bind_group",
+            &world.resource::<RingsPipeline>().layout,
+            &BindGroupEntries::single(storage.binding()),
+        );
+        let workgroups = workgroup_size.workgroups(UVec3::new(SIZE, SIZE, 1));
+
+        let mut compute_pass = context.begin_compute_pass(&ComputePassDescriptor {
+            label: Some("storage_texture_compute_pass"),
+            timestamp_writes: None,
+        });
+        compute_pass.set_bind_group(0, &bind_group, &[]);
+        compute_pass.set_pipeline(pipeline);
+        compute_pass.dispatch_workgroups(workgroups.x, workgroups.y, workgroups.z);
+
+        Ok(())
+    }
+}
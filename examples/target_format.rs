@@ -0,0 +1,167 @@
+use bevy::{
+    asset::embedded_asset, core_pipeline::fullscreen_vertex_shader::fullscreen_shader_vertex_state,
+    prelude::*, render::camera::Viewport,
+};
+use bevy_render::{
+    render_resource::{
+        ColorTargetState, ColorWrites, FragmentState, PrimitiveState, RenderPassDescriptor,
+        RenderPipelineDescriptor, SpecializedRenderPipeline,
+    },
+    view::ExtractedView,
+};
+
+use gigs::*;
+use input::{
+    JobInputItem, JobRenderPipeline, JobTargetFormat, JobTargetFormatKeyPlugin, JobViewTarget,
+    ViewTargetFormat,
+};
+
+fn main() -> AppExit {
+    let mut app = App::new();
+
+    app.add_plugins((
+        DefaultPlugins,
+        GraphicsJobsPlugin::default(),
+        JobTargetFormatKeyPlugin::<CheckerPipeline>::default(),
+    ))
+    .init_graphics_job::<CheckerJob>();
+
+    embedded_asset!(app, "examples", "target_format.wgsl");
+
+    app.add_systems(Startup, setup_scene)
+        .add_systems(Update, spawn_checkers);
+
+    app.run()
+}
+
+fn setup_scene(
+    window: Single<&Window>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+    mut commands: Commands,
+) {
+    // the left camera is HDR and multisampled, and the right one is LDR without
+    // multisampling, so a pipeline drawing into both needs two specializations that
+    // differ in both format and sample count
+    let half_size = UVec2::new(window.physical_width() / 2, window.physical_height());
+    for (order, (hdr, msaa)) in [(true, Msaa::Sample4), (false, Msaa::Off)]
+        .into_iter()
+        .enumerate()
+    {
+        commands.spawn((
+            Camera2d,
+            Camera {
+                order: order as isize,
+                hdr,
+                clear_color: ClearColorConfig::None,
+                viewport: Some(Viewport {
+                    physical_position: UVec2::new(half_size.x * order as u32, 0),
+                    physical_size: half_size,
+                    ..Default::default()
+                }),
+                ..Default::default()
+            },
+            msaa,
+        ));
+    }
+
+    commands.spawn((
+        Mesh2d(meshes.add(Circle::new(120.0))),
+        MeshMaterial2d(materials.add(Color::srgb(0.9, 0.6, 0.3))),
+    ));
+}
+
+fn spawn_checkers(cameras: Query<Entity, With<Camera>>, mut commands: Commands) {
+    for camera in &cameras {
+        // the key is only a placeholder, since `JobTargetFormatKeyPlugin` replaces it
+        // with the format and sample count of the camera's target
+        commands.spawn((
+            CheckerJob,
+            JobTargetFormat::new(camera),
+            JobViewTarget::new(camera),
+            JobRenderPipeline::<CheckerPipeline>(ViewTargetFormat::default()),
+        ));
+    }
+}
+
+#[derive(Clone, Component)]
+struct CheckerJob;
+
+#[derive(Resource)]
+struct CheckerPipeline {
+    shader: Handle<Shader>,
+}
+
+impl FromWorld for CheckerPipeline {
+    fn from_world(world: &mut World) -> Self {
+        let shader = world
+            .resource::<AssetServer>()
+            .load("embedded://target_format/target_format.wgsl");
+
+        Self { shader }
+    }
+}
+
+impl SpecializedRenderPipeline for CheckerPipeline {
+    type Key = ViewTargetFormat;
+
+    fn specialize(&self, key: Self::Key) -> RenderPipelineDescriptor {
+        RenderPipelineDescriptor {
+            label: Some("checker_pipeline".into()),
+            layout: Vec::new(),
+            push_constant_ranges: Vec::new(),
+            vertex: fullscreen_shader_vertex_state(),
+            primitive: PrimitiveState::default(),
+            depth_stencil: None,
+            multisample: key.multisample_state(),
+            fragment: Some(FragmentState {
+                shader: self.shader.clone(),
+                shader_defs: Vec::new(),
+                entry_point: "fragment".into(),
+                targets: vec![Some(ColorTargetState {
+                    format: key.format,
+                    blend: None,
+                    write_mask: ColorWrites::ALL,
+                })],
+            }),
+            zero_initialize_workgroup_memory: false,
+        }
+    }
+}
+
+impl GraphicsJob for CheckerJob {
+    type In = (
+        JobTargetFormat,
+        JobViewTarget,
+        JobRenderPipeline<CheckerPipeline>,
+    );
+
+    fn run(
+        &self,
+        _world: &World,
+        context: &mut JobRunContext,
+        (format, target, pipeline): JobInputItem<Self, Self::In>,
+    ) -> Result<(), JobError> {
+        let Some(view) = target.view.get::<ExtractedView>() else {
+            return Ok(());
+        };
+        // the key was synced before the pipeline was specialized this frame, so it
+        // matches the target this job draws into
+        debug_assert_eq!(format.format, target.format());
+
+        let mut render_pass = context.begin_render_pass(&RenderPassDescriptor {
+            label: Some("checker_pass"),
+            color_attachments: &[Some(target.color_attachment())],
+            depth_stencil_attachment: None,
+            timestamp_writes: None,
+            occlusion_query_set: None,
+        });
+
+        let viewport = view.viewport.as_vec4();
+        render_pass.set_viewport(viewport.x, viewport.y, viewport.z, viewport.w, 0.0, 1.0);
+        render_pass.set_pipeline(pipeline);
+        render_pass.draw(0..3, 0..1);
+
+        Ok(())
+    }
+}
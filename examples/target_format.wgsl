@@ -0,0 +1,9 @@
+#import bevy_core_pipeline::fullscreen_vertex_shader::FullscreenVertexOutput
+
+@fragment
+fn fragment(in: FullscreenVertexOutput) -> @location(0) vec4<f32> {
+    // a checkerboard backdrop behind the scene
+    let cell = vec2<u32>(in.uv * vec2(16.0, 9.0));
+    let checker = f32((cell.x + cell.y) % 2u);
+    return vec4(vec3(mix(0.08, 0.16, checker)), 1.0);
+}
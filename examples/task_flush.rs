@@ -0,0 +1,81 @@
+use bevy::{prelude::*, render::RenderPlugin, window::ExitCondition, winit::WinitPlugin};
+
+use gigs::*;
+use input::JobInputItem;
+use meta::{JobMarker, JobPriority, JobTask};
+
+const LIGHTMAPS: JobTask = JobTask(0);
+const NAVMESH: JobTask = JobTask(1);
+
+// a standalone bake tool, like the `headless_bake` example, that bakes two assets
+fn main() {
+    let mut app = App::new();
+
+    app.add_plugins((
+        DefaultPlugins
+            .set(WindowPlugin {
+                primary_window: None,
+                exit_condition: ExitCondition::DontExit,
+                ..Default::default()
+            })
+            .set(RenderPlugin {
+                synchronous_pipeline_compilation: true,
+                ..Default::default()
+            })
+            .disable::<WinitPlugin>(),
+        GraphicsJobsPlugin::default(),
+    ))
+    .init_graphics_job::<BakeJob>();
+
+    // only run a few jobs each frame, so the navmesh isn't done by the time the
+    // lightmaps are
+    app.insert_resource(JobExecutionSettings {
+        max_jobs_per_frame: 2,
+        ..Default::default()
+    });
+
+    for _ in 0..4 {
+        app.world_mut()
+            .spawn((BakeJob, LIGHTMAPS, JobPriority::non_critical::<8>()));
+        app.world_mut().spawn((BakeJob, NAVMESH));
+    }
+
+    // waits for the lightmaps only, and leaves the navmesh to bake in the background
+    for (job, result) in app.flush_task(LIGHTMAPS) {
+        println!("Lightmap job {job}: {result:?}");
+    }
+    println!(
+        "Navmesh jobs still pending: {}",
+        pending_jobs(&mut app, NAVMESH)
+    );
+
+    for (job, result) in app.flush_task(NAVMESH) {
+        println!("Navmesh job {job}: {result:?}");
+    }
+}
+
+fn pending_jobs(app: &mut App, task: JobTask) -> usize {
+    let world = app.world_mut();
+    world
+        .query_filtered::<&JobTask, With<JobMarker>>()
+        .iter(world)
+        .filter(|job_task| **job_task == task)
+        .count()
+}
+
+/// Stands in for baking one chunk of an asset.
+#[derive(Clone, Component)]
+struct BakeJob;
+
+impl GraphicsJob for BakeJob {
+    type In = ();
+
+    fn run(
+        &self,
+        _world: &World,
+        _context: &mut JobRunContext,
+        _input: JobInputItem<Self, Self::In>,
+    ) -> Result<(), JobError> {
+        Ok(())
+    }
+}
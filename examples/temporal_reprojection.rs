@@ -0,0 +1,275 @@
+// the `ShaderType` derive emits size checks that newer compilers flag as unused
+#![allow(dead_code)]
+
+use bevy::{
+    asset::{embedded_asset, RenderAssetUsages},
+    core_pipeline::experimental::taa::{TemporalAntiAliasPlugin, TemporalAntiAliasing},
+    prelude::*,
+};
+use bevy_render::{
+    render_resource::{
+        binding_types::{sampler, texture_2d, uniform_buffer},
+        encase, BindGroupEntries, BindGroupLayout, BindGroupLayoutEntries, BufferInitDescriptor,
+        BufferUsages, ComputePassDescriptor, ComputePipelineDescriptor, Extent3d,
+        SamplerBindingType, ShaderStages, ShaderType, SpecializedComputePipeline, TextureDimension,
+        TextureFormat, TextureSampleType, TextureUsages,
+    },
+    renderer::RenderDevice,
+    view::Msaa,
+};
+
+use gigs::*;
+use input::{
+    JobComputePipeline, JobImageView, JobInputItem, JobLinearSampler, JobStorageTexture,
+    JobTemporal, JobWorkgroupSize,
+};
+
+const OUTPUT_SIZE: UVec2 = UVec2::new(320, 180);
+const OUTPUT_FORMAT: TextureFormat = TextureFormat::Rgba8Unorm;
+
+fn main() -> AppExit {
+    let mut app = App::new();
+
+    app.add_plugins((
+        DefaultPlugins,
+        TemporalAntiAliasPlugin,
+        GraphicsJobsPlugin::default(),
+    ))
+    .init_graphics_job::<ReprojectJob>();
+
+    embedded_asset!(app, "examples", "temporal_reprojection.wgsl");
+
+    app.add_systems(Startup, setup_scene)
+        .add_systems(Update, (rotate_camera, spawn_reproject_job));
+
+    app.run()
+}
+
+/// Two images that take turns being the history and the output of each frame's job.
+#[derive(Resource)]
+struct Reprojection {
+    camera: Entity,
+    images: [Handle<Image>; 2],
+    frame: usize,
+}
+
+fn setup_scene(
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    mut images: ResMut<Assets<Image>>,
+    mut commands: Commands,
+) {
+    commands.spawn((
+        Mesh3d(meshes.add(Plane3d::default().mesh().size(8.0, 8.0))),
+        MeshMaterial3d(materials.add(Color::srgb(0.3, 0.5, 0.3))),
+    ));
+    commands.spawn((
+        Mesh3d(meshes.add(Cuboid::default())),
+        MeshMaterial3d(materials.add(Color::srgb(0.8, 0.7, 0.6))),
+        Transform::from_xyz(0.0, 0.5, 0.0),
+    ));
+    commands.spawn((
+        DirectionalLight::default(),
+        Transform::from_xyz(4.0, 8.0, 4.0).looking_at(Vec3::ZERO, Vec3::Y),
+    ));
+
+    // TAA adds the jitter and prepasses `JobTemporal` reads, and needs MSAA off
+    let camera = commands
+        .spawn((
+            Camera3d::default(),
+            TemporalAntiAliasing::default(),
+            Msaa::Off,
+            Transform::from_xyz(-2.5, 3.0, 5.0).looking_at(Vec3::ZERO, Vec3::Y),
+        ))
+        .id();
+
+    let images = [(); 2].map(|()| {
+        let mut image = Image::new_fill(
+            Extent3d {
+                width: OUTPUT_SIZE.x,
+                height: OUTPUT_SIZE.y,
+                depth_or_array_layers: 1,
+            },
+            TextureDimension::D2,
+            &[0, 0, 0, 255],
+            OUTPUT_FORMAT,
+            RenderAssetUsages::RENDER_WORLD,
+        );
+        image.texture_descriptor.usage = TextureUsages::COPY_DST
+            | TextureUsages::STORAGE_BINDING
+            | TextureUsages::TEXTURE_BINDING;
+        images.add(image)
+    });
+
+    commands.spawn((
+        ImageNode::new(images[0].clone()),
+        Node {
+            position_type: PositionType::Absolute,
+            right: Val::Px(12.0),
+            bottom: Val::Px(12.0),
+            width: Val::Px(OUTPUT_SIZE.x as f32),
+            height: Val::Px(OUTPUT_SIZE.y as f32),
+            ..Default::default()
+        },
+    ));
+    commands.spawn((
+        Text::from("A grid on the sky, accumulated over jittered frames and reprojected as the camera turns."),
+        Node {
+            position_type: PositionType::Absolute,
+            left: Val::Px(12.0),
+            top: Val::Px(12.0),
+            ..Default::default()
+        },
+    ));
+
+    commands.insert_resource(Reprojection {
+        camera,
+        images,
+        frame: 0,
+    });
+}
+
+fn rotate_camera(time: Res<Time>, mut cameras: Query<&mut Transform, With<Camera3d>>) {
+    for mut transform in &mut cameras {
+        transform.rotate_around(Vec3::ZERO, Quat::from_rotation_y(time.delta_secs() * 0.2));
+    }
+}
+
+fn spawn_reproject_job(
+    mut reprojection: ResMut<Reprojection>,
+    mut nodes: Query<&mut ImageNode>,
+    mut commands: Commands,
+) {
+    reprojection.frame += 1;
+    let history = reprojection.images[reprojection.frame % 2].clone();
+    let output = reprojection.images[(reprojection.frame + 1) % 2].clone();
+
+    commands.spawn((
+        ReprojectJob,
+        JobTemporal::new(reprojection.camera),
+        JobImageView::mip_level(history, 0),
+        JobStorageTexture::write_only(output.clone(), OUTPUT_FORMAT),
+        JobComputePipeline::<ReprojectPipeline>::new(()).with_workgroup_size(UVec3::new(8, 8, 1)),
+    ));
+
+    for mut node in &mut nodes {
+        node.image = output.clone();
+    }
+}
+
+#[derive(Clone, Component)]
+struct ReprojectJob;
+
+#[derive(ShaderType)]
+struct ReprojectUniform {
+    world_from_clip: Mat4,
+    previous_clip_from_world: Mat4,
+    jitter: Vec4,
+}
+
+#[derive(Resource)]
+struct ReprojectPipeline {
+    layout: BindGroupLayout,
+    shader: Handle<Shader>,
+}
+
+impl FromWorld for ReprojectPipeline {
+    fn from_world(world: &mut World) -> Self {
+        let storage = JobStorageTexture::write_only(Handle::default(), OUTPUT_FORMAT);
+        let layout = world.resource::<RenderDevice>().create_bind_group_layout(
+            "temporal_reprojection_layout",
+            &BindGroupLayoutEntries::sequential(
+                ShaderStages::COMPUTE,
+                (
+                    texture_2d(TextureSampleType::Float { filterable: true }),
+                    sampler(SamplerBindingType::Filtering),
+                    storage.layout_entry(),
+                    uniform_buffer::<ReprojectUniform>(false),
+                ),
+            ),
+        );
+        let shader = world
+            .resource::<AssetServer>()
+            .load("embedded://temporal_reprojection/temporal_reprojection.wgsl");
+
+        Self { layout, shader }
+    }
+}
+
+impl SpecializedComputePipeline for ReprojectPipeline {
+    type Key = ();
+
+    fn specialize(&self, (): Self::Key) -> ComputePipelineDescriptor {
+        ComputePipelineDescriptor {
+            label: Some("temporal_reprojection_compute".into()),
+            layout: vec![self.layout.clone()],
+            push_constant_ranges: Vec::new(),
+            shader: self.shader.clone(),
+            shader_defs: Vec::new(),
+            entry_point: "main".into(),
+            zero_initialize_workgroup_memory: false,
+        }
+    }
+}
+
+impl GraphicsJob for ReprojectJob {
+    type In = (
+        JobTemporal,
+        JobImageView,
+        JobLinearSampler,
+        JobStorageTexture,
+        JobComputePipeline<ReprojectPipeline>,
+        JobWorkgroupSize<ReprojectPipeline>,
+    );
+
+    fn run(
+        &self,
+        world: &World,
+        context: &mut JobRunContext,
+        (temporal, history, sampler, output, pipeline, workgroup_size): JobInputItem<
+            Self,
+            Self::In,
+        >,
+    ) -> Result<(), JobError> {
+        // the grid is drawn on the sky, so it's reprojected by direction alone. The
+        // jitter moves each frame's sample within its pixel, so the accumulated
+        // history converges to an antialiased grid.
+        let uniform = ReprojectUniform {
+            world_from_clip: temporal.clip_from_world.inverse(),
+            previous_clip_from_world: temporal.previous_clip_from_world,
+            jitter: temporal.jitter.extend(0.0).extend(0.0),
+        };
+        let mut contents = encase::UniformBuffer::new(Vec::new());
+        contents
+            .write(&uniform)
+            .map_err(|_| JobError::ExecutionFailed)?;
+
+        let render_device = context.render_device();
+        let uniforms = render_device.create_buffer_with_data(&BufferInitDescriptor {
+            label: Some("temporal_reprojection_uniforms"),
+            contents: &contents.into_inner(),
+            usage: BufferUsages::UNIFORM,
+        });
+        let bind_group = render_device.create_bind_group(
+            "temporal_reprojection_bind_group",
+            &world.resource::<ReprojectPipeline>().layout,
+            &BindGroupEntries::sequential((
+                &history.view,
+                sampler,
+                output.binding(),
+                uniforms.as_entire_binding(),
+            )),
+        );
+        let workgroups = workgroup_size.workgroups(OUTPUT_SIZE.extend(1));
+
+        let mut compute_pass = context.begin_compute_pass(&ComputePassDescriptor {
+            label: Some("temporal_reprojection_compute_pass"),
+            timestamp_writes: None,
+        });
+        compute_pass.set_bind_group(0, &bind_group, &[]);
+        compute_pass.set_pipeline(pipeline);
+        compute_pass.dispatch_workgroups(workgroups.x, workgroups.y, workgroups.z);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,53 @@
+struct Reprojection {
+    world_from_clip: mat4x4<f32>,
+    previous_clip_from_world: mat4x4<f32>,
+    jitter: vec4<f32>,
+}
+
+@group(0) @binding(0) var history: texture_2d<f32>;
+@group(0) @binding(1) var history_sampler: sampler;
+@group(0) @binding(2) var output: texture_storage_2d<rgba8unorm, write>;
+@group(0) @binding(3) var<uniform> reprojection: Reprojection;
+
+const PI: f32 = 3.14159265;
+
+// the world space direction through a point on the screen, found from two points at
+// different depths, since reversed z puts the far plane at infinity
+fn view_direction(ndc: vec2<f32>) -> vec3<f32> {
+    let near = reprojection.world_from_clip * vec4(ndc, 1.0, 1.0);
+    let far = reprojection.world_from_clip * vec4(ndc, 0.5, 1.0);
+    return normalize(far.xyz / far.w - near.xyz / near.w);
+}
+
+// a grid of thin lines every 10 degrees, which aliases when sampled once per pixel
+fn sky(direction: vec3<f32>) -> vec3<f32> {
+    let longitude = atan2(direction.x, direction.z) / PI * 18.0;
+    let latitude = asin(direction.y) / PI * 18.0;
+    let grid = step(vec2(0.95), fract(vec2(longitude, latitude)));
+    let base = mix(vec3(0.1, 0.15, 0.3), vec3(0.5, 0.7, 1.0), direction.y * 0.5 + 0.5);
+    return mix(base, vec3(1.0), max(grid.x, grid.y));
+}
+
+@compute @workgroup_size(8, 8, 1)
+fn main(@builtin(global_invocation_id) id: vec3<u32>) {
+    let size = textureDimensions(output);
+    if any(id.xy >= size) {
+        return;
+    }
+
+    let uv = (vec2<f32>(id.xy) + 0.5 + reprojection.jitter.xy) / vec2<f32>(size);
+    let direction = view_direction(vec2(uv.x * 2.0 - 1.0, 1.0 - uv.y * 2.0));
+    let current = sky(direction);
+
+    // where this direction was on the screen last frame
+    let previous_clip = reprojection.previous_clip_from_world * vec4(direction, 0.0);
+    let previous_ndc = previous_clip.xy / previous_clip.w;
+    let previous_uv = vec2(previous_ndc.x * 0.5 + 0.5, 0.5 - previous_ndc.y * 0.5);
+
+    var color = current;
+    if previous_clip.w > 0.0 && all(previous_uv >= vec2(0.0)) && all(previous_uv <= vec2(1.0)) {
+        let previous = textureSampleLevel(history, history_sampler, previous_uv, 0.0).rgb;
+        color = mix(previous, current, 0.1);
+    }
+    textureStore(output, id.xy, vec4(color, 1.0));
+}
@@ -1,3 +1,6 @@
+// the `ShaderType` derive emits size checks that newer compilers flag as unused
+#![allow(dead_code)]
+
 use std::mem;
 
 use bevy::{
@@ -9,15 +12,15 @@ use bevy::{
 use bevy_render::{
     mesh::{Indices, PrimitiveTopology},
     render_resource::{
-        AsBindGroup, BindGroupLayout, CommandEncoder, ComputePassDescriptor,
-        ComputePipelineDescriptor, ShaderRef, ShaderType, SpecializedComputePipeline,
+        AsBindGroup, BindGroupLayout, ComputePassDescriptor, ComputePipelineDescriptor, ShaderRef,
+        ShaderType, SpecializedComputePipeline,
     },
     renderer::RenderDevice,
     storage::ShaderStorageBuffer,
 };
 
 use gigs::*;
-use input::{JobAsBindGroup, JobComputePipeline, JobInputItem};
+use input::{JobAsBindGroup, JobComputePipeline, JobInputItem, JobWorkgroupSize};
 
 fn main() -> AppExit {
     let mut app = App::new();
@@ -218,7 +221,7 @@ fn generate_terrain_mesh(terrain_params: TerrainParams) -> Mesh {
 }
 
 #[derive(AsBindGroup, Clone, Component)]
-#[require(JobComputePipeline<TerrainGenPipeline>)]
+#[require(JobComputePipeline<TerrainGenPipeline>(terrain_gen_pipeline))]
 struct TerrainGenJob {
     #[storage(0, visibility(compute))]
     old_heightmap: Handle<ShaderStorageBuffer>,
@@ -232,6 +235,11 @@ struct TerrainGenJob {
     height_scale: f32,
 }
 
+// declared alongside the pipeline, matching `@workgroup_size` in terrain_gen.wgsl
+fn terrain_gen_pipeline() -> JobComputePipeline<TerrainGenPipeline> {
+    JobComputePipeline::new(()).with_workgroup_size(UVec3::new(16, 16, 1))
+}
+
 #[derive(Resource)]
 struct TerrainGenPipeline {
     layout: BindGroupLayout,
@@ -266,29 +274,30 @@ impl SpecializedComputePipeline for TerrainGenPipeline {
 }
 
 impl GraphicsJob for TerrainGenJob {
-    type In = (JobAsBindGroup, JobComputePipeline<TerrainGenPipeline>);
+    type In = (
+        JobAsBindGroup,
+        JobComputePipeline<TerrainGenPipeline>,
+        JobWorkgroupSize<TerrainGenPipeline>,
+    );
 
     fn run(
         &self,
         _world: &World,
-        _render_device: &RenderDevice,
-        command_encoder: &mut CommandEncoder,
-        (job_bind_group, job_pipeline): JobInputItem<Self, Self::In>,
+        context: &mut JobRunContext,
+        (job_bind_group, job_pipeline, workgroup_size): JobInputItem<Self, Self::In>,
     ) -> Result<(), JobError> {
-        let mut compute_pass = command_encoder.begin_compute_pass(&ComputePassDescriptor {
+        // the resolution is only known at runtime, so the number of workgroups is
+        // derived from it and the pipeline's declared workgroup size
+        let workgroups = workgroup_size.workgroups(self.terrain_params.resolution.extend(1));
+
+        let mut compute_pass = context.begin_compute_pass(&ComputePassDescriptor {
             label: Some("terrain_gen_compute_pass"),
             timestamp_writes: None,
         });
 
-        const WORKGROUP_SIZE: u32 = 16;
-
         compute_pass.set_bind_group(0, &job_bind_group.bind_group, &[]);
         compute_pass.set_pipeline(job_pipeline);
-        compute_pass.dispatch_workgroups(
-            self.terrain_params.resolution.x.div_ceil(WORKGROUP_SIZE),
-            self.terrain_params.resolution.y.div_ceil(WORKGROUP_SIZE),
-            1,
-        );
+        compute_pass.dispatch_workgroups(workgroups.x, workgroups.y, workgroups.z);
 
         Ok(())
     }
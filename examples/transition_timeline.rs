@@ -0,0 +1,102 @@
+use bevy::{input::keyboard::KeyboardInput, prelude::*};
+
+use gigs::*;
+use input::JobInputItem;
+
+/// The number of jobs shown in the timeline.
+const TIMELINE_JOBS: usize = 12;
+
+fn main() -> AppExit {
+    let mut app = App::new();
+
+    app.add_plugins((DefaultPlugins, GraphicsJobsPlugin::default()))
+        .init_graphics_job::<StepJob>()
+        .add_systems(Startup, setup_scene)
+        .add_systems(Update, (spawn_jobs, update_timeline));
+
+    app.insert_resource(JobExecutionSettings {
+        // run a single job each frame, so the jobs spread out over the timeline
+        max_jobs_per_frame: 1,
+        transition_history: 64,
+        ..Default::default()
+    });
+
+    app.run()
+}
+
+fn setup_scene(mut commands: Commands) {
+    commands.spawn(Camera2d);
+    commands.spawn((
+        Text::default(),
+        Node {
+            position_type: PositionType::Absolute,
+            left: Val::Px(12.0),
+            top: Val::Px(12.0),
+            ..Default::default()
+        },
+    ));
+}
+
+fn spawn_jobs(mut keyboard_input: EventReader<KeyboardInput>, mut commands: Commands) {
+    for key in keyboard_input.read().filter(|key| key.state.is_pressed()) {
+        if key.key_code == KeyCode::Space {
+            for index in 0..4 {
+                commands.spawn(StepJob { fail: index == 3 });
+            }
+        }
+    }
+}
+
+/// Builds a row for each of the most recently transitioned jobs, listing the frame it
+/// entered each status in.
+fn update_timeline(transitions: Res<JobTransitions>, mut text: Single<&mut Text>) {
+    if !transitions.is_changed() {
+        return;
+    }
+
+    let mut rows = Vec::<(Entity, String)>::new();
+    for transition in transitions.iter().rev() {
+        let index = match rows.iter().position(|(job, _)| *job == transition.job) {
+            Some(index) => index,
+            None if rows.len() < TIMELINE_JOBS => {
+                rows.push((transition.job, String::new()));
+                rows.len() - 1
+            }
+            None => continue,
+        };
+        let row = &mut rows[index].1;
+        let status = match transition.to {
+            JobStatus::Done(Ok(())) => "done".to_string(),
+            JobStatus::Done(Err(err)) => format!("{err:?}"),
+            status => format!("{status:?}").to_lowercase(),
+        };
+        *row = format!("  {status} @ {}{row}", transition.frame);
+    }
+
+    let mut timeline = String::from("Press [space] to spawn a few jobs.\n");
+    for (job, row) in rows {
+        timeline.push_str(&format!("\njob {job}:{row}"));
+    }
+    text.0 = timeline;
+}
+
+#[derive(Clone, Component)]
+struct StepJob {
+    fail: bool,
+}
+
+impl GraphicsJob for StepJob {
+    type In = ();
+
+    fn run(
+        &self,
+        _world: &World,
+        _context: &mut JobRunContext,
+        (): JobInputItem<Self, Self::In>,
+    ) -> Result<(), JobError> {
+        if self.fail {
+            return Err(JobError::ExecutionFailed);
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,114 @@
+use bevy::{
+    input::keyboard::KeyboardInput,
+    prelude::*,
+    render::{camera::Viewport, view::RenderLayers},
+};
+use bevy_render::sync_world::MainEntity;
+
+use gigs::*;
+use input::{JobInputItem, JobRenderLayers, JobViews};
+
+fn main() -> AppExit {
+    let mut app = App::new();
+
+    app.add_plugins((DefaultPlugins, GraphicsJobsPlugin::default()))
+        .init_graphics_job::<ViewEffectJob>();
+
+    app.add_systems(Startup, setup_scene)
+        .add_systems(Update, handle_input);
+
+    app.run()
+}
+
+fn setup_scene(
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    window: Single<&Window>,
+    mut commands: Commands,
+) {
+    // the scene is visible to both cameras
+    let scene_layers = RenderLayers::from_layers(&[0, 1]);
+
+    commands.spawn((
+        Mesh3d(meshes.add(Cuboid::default())),
+        MeshMaterial3d(materials.add(Color::srgb(0.8, 0.7, 0.6))),
+        scene_layers.clone(),
+    ));
+
+    commands.spawn((
+        PointLight::default(),
+        Transform::from_xyz(4.0, 8.0, 4.0),
+        scene_layers,
+    ));
+
+    // the main camera is on the default layer, `0`
+    let main_camera = commands
+        .spawn((
+            Camera3d::default(),
+            Transform::from_xyz(-2.0, 2.5, 5.0).looking_at(Vec3::ZERO, Vec3::Y),
+        ))
+        .id();
+
+    // the minimap camera renders a top-down view into the corner of the
+    // window, and lives only on layer `1`
+    let minimap_size = window.physical_width() / 4;
+    let minimap_camera = commands
+        .spawn((
+            Camera3d::default(),
+            Camera {
+                order: 1,
+                viewport: Some(Viewport {
+                    physical_position: UVec2::ZERO,
+                    physical_size: UVec2::splat(minimap_size),
+                    ..Default::default()
+                }),
+                ..Default::default()
+            },
+            Transform::from_xyz(0.0, 8.0, 0.0).looking_at(Vec3::ZERO, Vec3::Z),
+            RenderLayers::layer(1),
+        ))
+        .id();
+
+    info!("main camera: {main_camera}, minimap camera: {minimap_camera}");
+
+    commands.spawn((
+        Text::from("Press [space] to run an effect on the main camera only."),
+        Node {
+            position_type: PositionType::Absolute,
+            right: Val::Px(12.0),
+            top: Val::Px(12.0),
+            ..Default::default()
+        },
+    ));
+}
+
+fn handle_input(mut keyboard_input: EventReader<KeyboardInput>, mut commands: Commands) {
+    for key in keyboard_input.read().filter(|key| key.state.is_pressed()) {
+        if key.key_code == KeyCode::Space {
+            // only views on layer `0` are provided to the job, so the
+            // minimap camera is skipped
+            commands.spawn((ViewEffectJob, JobRenderLayers(RenderLayers::layer(0))));
+        }
+    }
+}
+
+#[derive(Clone, Component)]
+struct ViewEffectJob;
+
+impl GraphicsJob for ViewEffectJob {
+    type In = JobViews;
+
+    fn run(
+        &self,
+        _world: &World,
+        _context: &mut JobRunContext,
+        views: JobInputItem<Self, Self::In>,
+    ) -> Result<(), JobError> {
+        for view in views {
+            if let Some(main_entity) = view.get::<MainEntity>() {
+                info!("running effect for camera {}", main_entity.id());
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,166 @@
+// the `ShaderType` derive emits size checks that newer compilers flag as unused
+#![allow(dead_code)]
+
+use bevy::{
+    asset::embedded_asset, core_pipeline::fullscreen_vertex_shader::fullscreen_shader_vertex_state,
+    image::BevyDefault, prelude::*,
+};
+use bevy_render::{
+    render_resource::{
+        BindGroupLayout, ColorTargetState, ColorWrites, FragmentState, LoadOp, MultisampleState,
+        Operations, PrimitiveState, RenderPassColorAttachment, RenderPassDescriptor,
+        RenderPipelineDescriptor, ShaderType, SpecializedRenderPipeline, StoreOp, TextureFormat,
+    },
+    renderer::RenderDevice,
+    view::ViewTarget,
+};
+
+use gigs::*;
+use input::{JobInputItem, JobRenderPipeline, JobViewParams, JobViewParamsBindGroups};
+
+fn main() -> AppExit {
+    let mut app = App::new();
+
+    app.add_plugins((DefaultPlugins, GraphicsJobsPlugin::default()))
+        .init_graphics_job::<TintJob>();
+
+    embedded_asset!(app, "examples", "view_params.wgsl");
+
+    app.add_systems(Startup, setup_scene)
+        .add_systems(Update, spawn_tint);
+
+    app.run()
+}
+
+#[derive(Resource)]
+struct MainCamera(Entity);
+
+fn setup_scene(mut commands: Commands) {
+    // the camera doesn't clear its target, since the tint job draws the background
+    // before it renders
+    let camera = commands
+        .spawn((
+            Camera2d,
+            Camera {
+                clear_color: ClearColorConfig::None,
+                ..Default::default()
+            },
+            Msaa::Off,
+        ))
+        .id();
+    commands.insert_resource(MainCamera(camera));
+
+    commands.spawn(Sprite::from_color(
+        Color::srgb(0.9, 0.6, 0.3),
+        Vec2::splat(128.0),
+    ));
+}
+
+fn spawn_tint(camera: Res<MainCamera>, time: Res<Time>, mut commands: Commands) {
+    // the parameters are uploaded when the job is extracted, so a job spawned each frame
+    // can animate them
+    let hue = (time.elapsed_secs() * 30.0) % 360.0;
+    commands.spawn((
+        TintJob,
+        JobViewParams::new(
+            camera.0,
+            TintParams {
+                color: LinearRgba::from(Color::hsl(hue, 0.4, 0.25)).to_vec4(),
+                falloff: 0.6,
+            },
+        ),
+        JobRenderPipeline::<TintPipeline>(TextureFormat::bevy_default()),
+    ));
+}
+
+#[derive(Clone, ShaderType)]
+struct TintParams {
+    color: Vec4,
+    falloff: f32,
+}
+
+#[derive(Clone, Component)]
+struct TintJob;
+
+#[derive(Resource)]
+struct TintPipeline {
+    layout: BindGroupLayout,
+    shader: Handle<Shader>,
+}
+
+impl FromWorld for TintPipeline {
+    fn from_world(world: &mut World) -> Self {
+        let layout = world
+            .resource::<JobViewParamsBindGroups<TintParams>>()
+            .layout(world.resource::<RenderDevice>())
+            .clone();
+        let shader = world
+            .resource::<AssetServer>()
+            .load("embedded://view_params/view_params.wgsl");
+
+        Self { layout, shader }
+    }
+}
+
+impl SpecializedRenderPipeline for TintPipeline {
+    type Key = TextureFormat;
+
+    fn specialize(&self, format: Self::Key) -> RenderPipelineDescriptor {
+        RenderPipelineDescriptor {
+            label: Some("tint_pipeline".into()),
+            layout: vec![self.layout.clone()],
+            push_constant_ranges: Vec::new(),
+            vertex: fullscreen_shader_vertex_state(),
+            primitive: PrimitiveState::default(),
+            depth_stencil: None,
+            multisample: MultisampleState::default(),
+            fragment: Some(FragmentState {
+                shader: self.shader.clone(),
+                shader_defs: Vec::new(),
+                entry_point: "fragment".into(),
+                targets: vec![Some(ColorTargetState {
+                    format,
+                    blend: None,
+                    write_mask: ColorWrites::ALL,
+                })],
+            }),
+            zero_initialize_workgroup_memory: false,
+        }
+    }
+}
+
+impl GraphicsJob for TintJob {
+    type In = (JobViewParams<TintParams>, JobRenderPipeline<TintPipeline>);
+
+    fn run(
+        &self,
+        _world: &World,
+        context: &mut JobRunContext,
+        (view_params, pipeline): JobInputItem<Self, Self::In>,
+    ) -> Result<(), JobError> {
+        let Some(view_target) = view_params.view.get::<ViewTarget>() else {
+            return Ok(());
+        };
+
+        let mut render_pass = context.begin_render_pass(&RenderPassDescriptor {
+            label: Some("tint_pass"),
+            color_attachments: &[Some(RenderPassColorAttachment {
+                view: view_target.main_texture_view(),
+                resolve_target: None,
+                ops: Operations {
+                    load: LoadOp::Load,
+                    store: StoreOp::Store,
+                },
+            })],
+            depth_stencil_attachment: None,
+            timestamp_writes: None,
+            occlusion_query_set: None,
+        });
+
+        render_pass.set_pipeline(pipeline);
+        render_pass.set_bind_group(0, view_params.bind_group, &view_params.dynamic_offsets());
+        render_pass.draw(0..3, 0..1);
+
+        Ok(())
+    }
+}
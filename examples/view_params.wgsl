@@ -0,0 +1,17 @@
+#import bevy_render::view::View
+#import bevy_core_pipeline::fullscreen_vertex_shader::FullscreenVertexOutput
+
+struct TintParams {
+    color: vec4<f32>,
+    falloff: f32,
+}
+
+@group(0) @binding(0) var<uniform> view: View;
+@group(0) @binding(1) var<uniform> params: TintParams;
+
+@fragment
+fn fragment(in: FullscreenVertexOutput) -> @location(0) vec4<f32> {
+    let uv = (in.position.xy - view.viewport.xy) / view.viewport.zw;
+    let vignette = smoothstep(0.2, params.falloff + 0.2, distance(uv, vec2(0.5)));
+    return vec4(mix(params.color.rgb, vec3(0.0), vignette), 1.0);
+}
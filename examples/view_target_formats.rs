@@ -0,0 +1,151 @@
+use bevy::{
+    asset::embedded_asset, core_pipeline::fullscreen_vertex_shader::fullscreen_shader_vertex_state,
+    image::BevyDefault, prelude::*, render::camera::Viewport,
+};
+use bevy_render::{
+    render_resource::{
+        ColorTargetState, ColorWrites, FragmentState, LoadOp, MultisampleState, Operations,
+        PrimitiveState, RenderPassColorAttachment, RenderPassDescriptor, RenderPipelineDescriptor,
+        SpecializedRenderPipeline, StoreOp, TextureFormat,
+    },
+    view::ExtractedView,
+};
+
+use gigs::*;
+use input::{JobInputItem, JobRenderPipeline, JobViewTarget, JobViewTargetFormatPlugin};
+
+fn main() -> AppExit {
+    let mut app = App::new();
+
+    app.add_plugins((
+        DefaultPlugins,
+        GraphicsJobsPlugin::default(),
+        JobViewTargetFormatPlugin::<BackdropPipeline>::default(),
+    ))
+    .init_graphics_job::<BackdropJob>();
+
+    embedded_asset!(app, "examples", "view_target_formats.wgsl");
+
+    app.add_systems(Startup, setup_scene)
+        .add_systems(Update, spawn_backdrops);
+
+    app.run()
+}
+
+fn setup_scene(window: Single<&Window>, mut commands: Commands) {
+    // the left camera is HDR and the right one is LDR, so their main textures have
+    // different formats. As in the `vignette` example, the cameras don't clear their
+    // targets, since the backdrop jobs draw before the cameras render.
+    let half_size = UVec2::new(window.physical_width() / 2, window.physical_height());
+    for (order, hdr) in [true, false].into_iter().enumerate() {
+        commands.spawn((
+            Camera2d,
+            Camera {
+                order: order as isize,
+                hdr,
+                clear_color: ClearColorConfig::None,
+                viewport: Some(Viewport {
+                    physical_position: UVec2::new(half_size.x * order as u32, 0),
+                    physical_size: half_size,
+                    ..Default::default()
+                }),
+                ..Default::default()
+            },
+            Msaa::Off,
+        ));
+    }
+}
+
+fn spawn_backdrops(cameras: Query<Entity, With<Camera>>, mut commands: Commands) {
+    for camera in &cameras {
+        // the format here is only a placeholder, since `JobViewTargetFormatPlugin`
+        // replaces it with the format of the camera's main texture
+        commands.spawn((
+            BackdropJob,
+            JobViewTarget::new(camera),
+            JobRenderPipeline::<BackdropPipeline>(TextureFormat::bevy_default()),
+        ));
+    }
+}
+
+#[derive(Clone, Component)]
+struct BackdropJob;
+
+#[derive(Resource)]
+struct BackdropPipeline {
+    shader: Handle<Shader>,
+}
+
+impl FromWorld for BackdropPipeline {
+    fn from_world(world: &mut World) -> Self {
+        let shader = world
+            .resource::<AssetServer>()
+            .load("embedded://view_target_formats/view_target_formats.wgsl");
+
+        Self { shader }
+    }
+}
+
+impl SpecializedRenderPipeline for BackdropPipeline {
+    type Key = TextureFormat;
+
+    fn specialize(&self, format: Self::Key) -> RenderPipelineDescriptor {
+        RenderPipelineDescriptor {
+            label: Some("backdrop_pipeline".into()),
+            layout: Vec::new(),
+            push_constant_ranges: Vec::new(),
+            vertex: fullscreen_shader_vertex_state(),
+            primitive: PrimitiveState::default(),
+            depth_stencil: None,
+            multisample: MultisampleState::default(),
+            fragment: Some(FragmentState {
+                shader: self.shader.clone(),
+                shader_defs: Vec::new(),
+                entry_point: "fragment".into(),
+                targets: vec![Some(ColorTargetState {
+                    format,
+                    blend: None,
+                    write_mask: ColorWrites::ALL,
+                })],
+            }),
+            zero_initialize_workgroup_memory: false,
+        }
+    }
+}
+
+impl GraphicsJob for BackdropJob {
+    type In = (JobViewTarget, JobRenderPipeline<BackdropPipeline>);
+
+    fn run(
+        &self,
+        _world: &World,
+        context: &mut JobRunContext,
+        (target, pipeline): JobInputItem<Self, Self::In>,
+    ) -> Result<(), JobError> {
+        let Some(view) = target.view.get::<ExtractedView>() else {
+            return Ok(());
+        };
+
+        let mut render_pass = context.begin_render_pass(&RenderPassDescriptor {
+            label: Some("backdrop_pass"),
+            color_attachments: &[Some(RenderPassColorAttachment {
+                view: target.target.main_texture_view(),
+                resolve_target: None,
+                ops: Operations {
+                    load: LoadOp::Load,
+                    store: StoreOp::Store,
+                },
+            })],
+            depth_stencil_attachment: None,
+            timestamp_writes: None,
+            occlusion_query_set: None,
+        });
+
+        let viewport = view.viewport.as_vec4();
+        render_pass.set_viewport(viewport.x, viewport.y, viewport.z, viewport.w, 0.0, 1.0);
+        render_pass.set_pipeline(pipeline);
+        render_pass.draw(0..3, 0..1);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,9 @@
+#import bevy_core_pipeline::fullscreen_vertex_shader::FullscreenVertexOutput
+
+@fragment
+fn fragment(in: FullscreenVertexOutput) -> @location(0) vec4<f32> {
+    // the brightest part of the gradient is out of the LDR range, so it's only
+    // visible as a gradient on the HDR camera, and is clamped on the LDR one
+    let brightness = mix(0.05, 4.0, in.uv.x);
+    return vec4(vec3(0.9, 0.6, 0.3) * brightness, 1.0);
+}
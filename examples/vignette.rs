@@ -0,0 +1,186 @@
+use bevy::{
+    asset::embedded_asset, core_pipeline::fullscreen_vertex_shader::fullscreen_shader_vertex_state,
+    image::BevyDefault, prelude::*, render::camera::Viewport,
+};
+use bevy_render::{
+    render_resource::{
+        binding_types::uniform_buffer, BindGroupEntries, BindGroupLayout, BindGroupLayoutEntries,
+        ColorTargetState, ColorWrites, FragmentState, LoadOp, MultisampleState, Operations,
+        PrimitiveState, RenderPassColorAttachment, RenderPassDescriptor, RenderPipelineDescriptor,
+        ShaderStages, SpecializedRenderPipeline, StoreOp, TextureFormat,
+    },
+    renderer::RenderDevice,
+    view::{ViewTarget, ViewUniform, ViewUniforms},
+};
+
+use gigs::*;
+use input::{JobExtractedViews, JobInputItem, JobRenderPipeline};
+
+fn main() -> AppExit {
+    let mut app = App::new();
+
+    app.add_plugins((DefaultPlugins, GraphicsJobsPlugin::default()))
+        .init_graphics_job::<VignetteJob>();
+
+    embedded_asset!(app, "examples", "vignette.wgsl");
+
+    app.add_systems(Startup, setup_scene)
+        .add_systems(Update, spawn_vignette);
+
+    app.run()
+}
+
+fn setup_scene(window: Single<&Window>, mut commands: Commands) {
+    // the cameras don't clear their targets, since the vignette job draws the background
+    // before they render. Jobs run before the render graph, so anything they draw
+    // into a view target is covered by what the camera renders afterwards.
+    commands.spawn((
+        Camera2d,
+        Camera {
+            clear_color: ClearColorConfig::None,
+            ..Default::default()
+        },
+        Msaa::Off,
+    ));
+
+    let minimap_size = window.physical_width() / 4;
+    commands.spawn((
+        Camera2d,
+        Camera {
+            order: 1,
+            clear_color: ClearColorConfig::None,
+            viewport: Some(Viewport {
+                physical_position: UVec2::ZERO,
+                physical_size: UVec2::splat(minimap_size),
+                ..Default::default()
+            }),
+            ..Default::default()
+        },
+        Msaa::Off,
+    ));
+
+    commands.spawn(Sprite::from_color(
+        Color::srgb(0.9, 0.6, 0.3),
+        Vec2::splat(128.0),
+    ));
+}
+
+fn spawn_vignette(mut commands: Commands) {
+    // a single job processes every view, each frame
+    commands.spawn((
+        VignetteJob,
+        JobRenderPipeline::<VignettePipeline>(TextureFormat::bevy_default()),
+    ));
+}
+
+#[derive(Clone, Component)]
+struct VignetteJob;
+
+#[derive(Resource)]
+struct VignettePipeline {
+    layout: BindGroupLayout,
+    shader: Handle<Shader>,
+}
+
+impl FromWorld for VignettePipeline {
+    fn from_world(world: &mut World) -> Self {
+        let layout = world.resource::<RenderDevice>().create_bind_group_layout(
+            "vignette_layout",
+            &BindGroupLayoutEntries::single(
+                ShaderStages::FRAGMENT,
+                uniform_buffer::<ViewUniform>(true),
+            ),
+        );
+        let shader = world
+            .resource::<AssetServer>()
+            .load("embedded://vignette/vignette.wgsl");
+
+        Self { layout, shader }
+    }
+}
+
+impl SpecializedRenderPipeline for VignettePipeline {
+    type Key = TextureFormat;
+
+    fn specialize(&self, format: Self::Key) -> RenderPipelineDescriptor {
+        RenderPipelineDescriptor {
+            label: Some("vignette_pipeline".into()),
+            layout: vec![self.layout.clone()],
+            push_constant_ranges: Vec::new(),
+            vertex: fullscreen_shader_vertex_state(),
+            primitive: PrimitiveState::default(),
+            depth_stencil: None,
+            multisample: MultisampleState::default(),
+            fragment: Some(FragmentState {
+                shader: self.shader.clone(),
+                shader_defs: Vec::new(),
+                entry_point: "fragment".into(),
+                targets: vec![Some(ColorTargetState {
+                    format,
+                    blend: None,
+                    write_mask: ColorWrites::ALL,
+                })],
+            }),
+            zero_initialize_workgroup_memory: false,
+        }
+    }
+}
+
+impl GraphicsJob for VignetteJob {
+    type In = (JobExtractedViews, JobRenderPipeline<VignettePipeline>);
+
+    fn run(
+        &self,
+        world: &World,
+        context: &mut JobRunContext,
+        (views, pipeline): JobInputItem<Self, Self::In>,
+    ) -> Result<(), JobError> {
+        let render_device = context.render_device();
+        // with no active views, there's simply nothing to do
+        let Some(view_uniforms) = world.resource::<ViewUniforms>().uniforms.binding() else {
+            return Ok(());
+        };
+
+        let bind_group = render_device.create_bind_group(
+            "vignette_bind_group",
+            &world.resource::<VignettePipeline>().layout,
+            &BindGroupEntries::single(view_uniforms),
+        );
+
+        for view in views {
+            let Some(view_target) = view.entity.get::<ViewTarget>() else {
+                continue;
+            };
+
+            let mut render_pass = context.begin_render_pass(&RenderPassDescriptor {
+                label: Some("vignette_pass"),
+                color_attachments: &[Some(RenderPassColorAttachment {
+                    view: view_target.main_texture_view(),
+                    resolve_target: None,
+                    ops: Operations {
+                        load: LoadOp::Load,
+                        store: StoreOp::Store,
+                    },
+                })],
+                depth_stencil_attachment: None,
+                timestamp_writes: None,
+                occlusion_query_set: None,
+            });
+
+            let viewport = view.viewport().as_rect();
+            render_pass.set_viewport(
+                viewport.min.x,
+                viewport.min.y,
+                viewport.width(),
+                viewport.height(),
+                0.0,
+                1.0,
+            );
+            render_pass.set_pipeline(pipeline);
+            render_pass.set_bind_group(0, &bind_group, &[view.uniform_offset.offset]);
+            render_pass.draw(0..3, 0..1);
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,15 @@
+#import bevy_render::view::View
+#import bevy_core_pipeline::fullscreen_vertex_shader::FullscreenVertexOutput
+
+@group(0) @binding(0) var<uniform> view: View;
+
+const BACKGROUND: vec3<f32> = vec3(0.2, 0.25, 0.3);
+
+@fragment
+fn fragment(in: FullscreenVertexOutput) -> @location(0) vec4<f32> {
+    // the fragment position is relative to the render target rather than the
+    // viewport, so use the view's uniforms to find where it lies in the view
+    let uv = (in.position.xy - view.viewport.xy) / view.viewport.zw;
+    let vignette = smoothstep(0.2, 0.8, distance(uv, vec2(0.5)));
+    return vec4(mix(BACKGROUND, vec3(0.0), vignette), 1.0);
+}
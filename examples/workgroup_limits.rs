@@ -0,0 +1,134 @@
+use bevy::{asset::embedded_asset, prelude::*};
+use bevy_render::{
+    gpu_readback::{Readback, ReadbackComplete},
+    render_resource::{
+        AsBindGroup, BindGroupLayout, BufferUsages, ComputePassDescriptor,
+        ComputePipelineDescriptor, SpecializedComputePipeline,
+    },
+    renderer::RenderDevice,
+    storage::ShaderStorageBuffer,
+};
+
+use gigs::*;
+use input::{JobAsBindGroup, JobComputePipeline, JobInputItem, JobLimits};
+
+// more workgroups than the default limit of 65535 per dimension
+const LEN: u32 = 1 << 23;
+const WORKGROUP_SIZE: u32 = 64;
+
+fn main() -> AppExit {
+    let mut app = App::new();
+
+    app.add_plugins((DefaultPlugins, GraphicsJobsPlugin::default()))
+        .init_graphics_job::<FillJob>()
+        .add_systems(Startup, spawn_fill);
+
+    embedded_asset!(app, "examples", "workgroup_limits.wgsl");
+
+    app.run()
+}
+
+fn spawn_fill(mut buffers: ResMut<Assets<ShaderStorageBuffer>>, mut commands: Commands) {
+    let mut buffer =
+        ShaderStorageBuffer::with_size((LEN as usize) * size_of::<u32>(), Default::default());
+    buffer.buffer_description.usage |= BufferUsages::COPY_SRC;
+    let buffer = buffers.add(buffer);
+
+    commands
+        .spawn(FillJob {
+            values: buffer.clone(),
+            len: LEN,
+        })
+        .observe(
+            move |trigger: Trigger<JobComplete>, mut commands: Commands| {
+                assert_eq!(trigger.event().0, Ok(()));
+
+                commands.spawn(Readback::buffer(buffer.clone())).observe(
+                    |trigger: Trigger<ReadbackComplete>,
+                     mut commands: Commands,
+                     mut exit: EventWriter<AppExit>| {
+                        let values: Vec<u32> = trigger.event().to_shader_type();
+                        assert!(values
+                            .iter()
+                            .enumerate()
+                            .all(|(i, value)| i as u32 == *value));
+                        println!("Filled {} values", values.len());
+
+                        commands.entity(trigger.entity()).despawn();
+                        exit.send(AppExit::Success);
+                    },
+                );
+            },
+        );
+}
+
+#[derive(AsBindGroup, Clone, Component)]
+#[require(JobComputePipeline<FillPipeline>)]
+struct FillJob {
+    #[storage(0, visibility(compute))]
+    values: Handle<ShaderStorageBuffer>,
+    #[uniform(1, visibility(compute))]
+    len: u32,
+}
+
+#[derive(Resource)]
+struct FillPipeline {
+    layout: BindGroupLayout,
+    shader: Handle<Shader>,
+}
+
+impl FromWorld for FillPipeline {
+    fn from_world(world: &mut World) -> Self {
+        let layout = FillJob::bind_group_layout(world.resource::<RenderDevice>());
+        let shader = world
+            .resource::<AssetServer>()
+            .load("embedded://workgroup_limits/workgroup_limits.wgsl");
+
+        Self { layout, shader }
+    }
+}
+
+impl SpecializedComputePipeline for FillPipeline {
+    type Key = ();
+
+    fn specialize(&self, (): Self::Key) -> ComputePipelineDescriptor {
+        ComputePipelineDescriptor {
+            label: Some("workgroup_limits_compute".into()),
+            layout: vec![self.layout.clone()],
+            push_constant_ranges: Vec::new(),
+            shader: self.shader.clone(),
+            shader_defs: Vec::new(),
+            entry_point: "main".into(),
+            zero_initialize_workgroup_memory: false,
+        }
+    }
+}
+
+impl GraphicsJob for FillJob {
+    type In = (JobAsBindGroup, JobComputePipeline<FillPipeline>, JobLimits);
+
+    fn run(
+        &self,
+        _world: &World,
+        context: &mut JobRunContext,
+        (bind_group, pipeline, limits): JobInputItem<Self, Self::In>,
+    ) -> Result<(), JobError> {
+        // split the dispatch into rows no wider than the device allows
+        let workgroups = self.len.div_ceil(WORKGROUP_SIZE);
+        let x = workgroups.min(limits.max_compute_workgroups_per_dimension);
+        let y = workgroups.div_ceil(x);
+        if y > limits.max_compute_workgroups_per_dimension {
+            return Err(JobError::ExecutionFailed);
+        }
+
+        let mut compute_pass = context.begin_compute_pass(&ComputePassDescriptor {
+            label: Some("workgroup_limits_compute_pass"),
+            timestamp_writes: None,
+        });
+        compute_pass.set_bind_group(0, &bind_group.bind_group, &[]);
+        compute_pass.set_pipeline(pipeline);
+        compute_pass.dispatch_workgroups(x, y, 1);
+
+        Ok(())
+    }
+}
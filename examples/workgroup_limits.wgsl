@@ -0,0 +1,16 @@
+@group(0) @binding(0) var<storage, read_write> values: array<u32>;
+@group(0) @binding(1) var<uniform> len: u32;
+
+const WORKGROUP_SIZE: u32 = 64u;
+
+@compute @workgroup_size(WORKGROUP_SIZE)
+fn main(
+    @builtin(global_invocation_id) id: vec3<u32>,
+    @builtin(num_workgroups) num_workgroups: vec3<u32>,
+) {
+    // the dispatch may be split across two dimensions to stay within the device limits
+    let index = id.y * num_workgroups.x * WORKGROUP_SIZE + id.x;
+    if index < len {
+        values[index] = index;
+    }
+}
@@ -0,0 +1,111 @@
+use bevy_ecs::system::Resource;
+use bevy_render::{
+    extract_resource::ExtractResource,
+    render_resource::{Buffer, BufferDescriptor, BufferUsages, CommandEncoder, Maintain, MapMode},
+    renderer::{RenderDevice, RenderQueue},
+};
+use bevy_utils::tracing::error;
+
+use crate::JobError;
+
+/// A second GPU for running jobs spawned with [`JobAdapter::Secondary`](crate::meta::JobAdapter),
+/// for example to bake on a laptop's integrated GPU while its discrete GPU renders. Insert
+/// it in the main world, with a device requested from the [`RenderInstance`](bevy_render::renderer::RenderInstance)
+/// for an adapter other than bevy's. Without it, every job runs on bevy's own device.
+///
+/// This only provides the plumbing, and has substantial limitations:
+///
+/// - Resources from one device can't be used on another, and every input other than plain
+///   data, like images, buffers, bind groups and [`JobComputePipeline`](crate::input::JobComputePipeline),
+///   is created on bevy's device. Jobs on the secondary device create what they need
+///   through [`JobRunContext::render_device`](crate::JobRunContext::render_device), and
+///   check [`JobRunContext::adapter`](crate::JobRunContext::adapter) to tell which device
+///   that is.
+/// - Results the primary device needs are copied over with
+///   [`JobRunContext::transfer_to_primary`](crate::JobRunContext::transfer_to_primary),
+///   through the CPU. The render thread blocks until the secondary device finishes the
+///   job, so this is only worth it for jobs that take far longer than the stall.
+/// - Jobs on the secondary device are submitted to its queue as soon as they finish
+///   recording, so they have no [`JobSubmissions`](crate::JobSubmissions) entry, and
+///   aren't timed with the `diagnostics` feature.
+/// - Losing the secondary device isn't detected, see [`DeviceLostPolicy`](crate::DeviceLostPolicy).
+#[derive(Resource, Clone, ExtractResource)]
+pub struct JobSecondaryDevice {
+    pub device: RenderDevice,
+    pub queue: RenderQueue,
+}
+
+impl JobSecondaryDevice {
+    pub fn new(device: RenderDevice, queue: RenderQueue) -> Self {
+        Self { device, queue }
+    }
+
+    /// Submits commands recorded on this device, then waits for them and copies each
+    /// transfer's source into its destination on the primary device.
+    ///
+    /// If any transfer can't be read back from this device, none are copied, and this
+    /// returns [`JobError::ExecutionFailed`], since the job's results would be incomplete.
+    pub(crate) fn submit(
+        &self,
+        command_encoders: impl IntoIterator<Item = CommandEncoder>,
+        transfers: impl IntoIterator<Item = JobTransfer>,
+        primary_queue: &RenderQueue,
+    ) -> Result<(), JobError> {
+        let mut command_buffers = command_encoders
+            .into_iter()
+            .map(|command_encoder| command_encoder.finish())
+            .collect::<Vec<_>>();
+
+        let mut staged = Vec::new();
+        for transfer in transfers {
+            let staging = self.device.create_buffer(&BufferDescriptor {
+                label: Some("job_transfer_staging"),
+                size: transfer.size,
+                usage: BufferUsages::COPY_DST | BufferUsages::MAP_READ,
+                mapped_at_creation: false,
+            });
+            let mut command_encoder = self.device.create_command_encoder(&Default::default());
+            command_encoder.copy_buffer_to_buffer(&transfer.source, 0, &staging, 0, transfer.size);
+            command_buffers.push(command_encoder.finish());
+            staged.push((staging, transfer.destination));
+        }
+
+        self.queue.submit(command_buffers);
+        if staged.is_empty() {
+            return Ok(());
+        }
+
+        let (sender, receiver) = crossbeam_channel::unbounded();
+        for (staging, _) in &staged {
+            let sender = sender.clone();
+            staging.slice(..).map_async(MapMode::Read, move |result| {
+                let _ = sender.send(result);
+            });
+        }
+        self.device.poll(Maintain::Wait);
+
+        // every buffer has been mapped, or failed to be, once the device is idle
+        let results = receiver.try_iter().collect::<Vec<_>>();
+        if let Some(Err(err)) = results.iter().find(|result| result.is_err()) {
+            error!("failed to map a job's transfer from the secondary device: {err}");
+            return Err(JobError::ExecutionFailed);
+        }
+        if results.len() < staged.len() {
+            error!("a job's transfer from the secondary device was never mapped");
+            return Err(JobError::ExecutionFailed);
+        }
+
+        for (staging, destination) in &staged {
+            primary_queue.write_buffer(destination, 0, &staging.slice(..).get_mapped_range());
+        }
+        Ok(())
+    }
+}
+
+/// A copy of a buffer on the secondary device into a buffer on the primary device,
+/// recorded with [`JobRunContext::transfer_to_primary`](crate::JobRunContext::transfer_to_primary).
+pub(crate) struct JobTransfer {
+    pub source: Buffer,
+    pub destination: Buffer,
+    pub size: u64,
+}
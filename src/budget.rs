@@ -0,0 +1,140 @@
+use core::time::Duration;
+
+use bevy_ecs::system::Resource;
+use bevy_render::extract_resource::ExtractResource;
+
+/// Settings for adapting the number of jobs executed each frame to keep their total
+/// GPU time near a target, set with [`JobExecutionSettings::time_budget`](crate::JobExecutionSettings::time_budget).
+///
+/// The GPU time of each job is read from the spans recorded with the `diagnostics`
+/// feature, so this requires both that feature and bevy's `RenderDiagnosticsPlugin`.
+/// Without them no timings are measured, and the budget stays at
+/// [`max_jobs_per_frame`](crate::JobExecutionSettings::max_jobs_per_frame).
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct JobTimeBudget {
+    /// The total GPU time the jobs executed each frame should take.
+    pub gpu_time: Duration,
+    /// The fewest jobs executed each frame, however long they take.
+    pub min_jobs_per_frame: u32,
+    /// The most jobs executed each frame, however short they are.
+    pub max_jobs_per_frame: u32,
+}
+
+impl JobTimeBudget {
+    /// Targets the given total GPU time, executing between 1 and 64 jobs each frame.
+    pub const fn new(gpu_time: Duration) -> Self {
+        Self {
+            gpu_time,
+            min_jobs_per_frame: 1,
+            max_jobs_per_frame: 64,
+        }
+    }
+}
+
+/// The number of jobs executed each frame, as adapted to a [`JobTimeBudget`].
+#[derive(Copy, Clone, Default, Resource, ExtractResource, PartialEq, Eq, Debug)]
+pub struct JobAdaptiveBudget {
+    /// The adapted budget, or `None` until the GPU time of any jobs has been measured.
+    pub jobs_per_frame: Option<u32>,
+}
+
+impl JobAdaptiveBudget {
+    /// The number of jobs to execute this frame, starting from `initial` before any
+    /// jobs are measured.
+    pub fn current(&self, budget: &JobTimeBudget, initial: u32) -> u32 {
+        self.jobs_per_frame
+            .unwrap_or(initial)
+            .clamp(budget.min_jobs_per_frame, budget.max_jobs_per_frame)
+    }
+
+    /// Moves the budget toward the number of jobs that fit in the target GPU time,
+    /// given the total GPU time `jobs` took in a frame.
+    #[cfg_attr(not(feature = "diagnostics"), allow(dead_code))]
+    pub(crate) fn update(
+        &mut self,
+        budget: &JobTimeBudget,
+        initial: u32,
+        gpu_time: Duration,
+        jobs: u32,
+    ) {
+        if jobs == 0 {
+            return;
+        }
+
+        let current = self.current(budget, initial);
+        // the number of jobs that fit in the target, at their average GPU time
+        let fits = (budget.gpu_time.as_nanos() * u128::from(jobs))
+            .checked_div(gpu_time.as_nanos())
+            .unwrap_or(u128::MAX)
+            .clamp(
+                u128::from(budget.min_jobs_per_frame),
+                u128::from(budget.max_jobs_per_frame),
+            ) as u32;
+
+        // only move halfway, so that a single noisy frame doesn't swing the budget.
+        // rounding toward the target makes sure it's reached rather than approached.
+        let next = if fits < current {
+            current - (current - fits).div_ceil(2)
+        } else {
+            current + (fits - current).div_ceil(2)
+        };
+        self.jobs_per_frame = Some(next);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use core::time::Duration;
+
+    use super::{JobAdaptiveBudget, JobTimeBudget};
+
+    fn simulate(budget: &JobTimeBudget, per_job: Duration, frames: u32) -> Vec<u32> {
+        let mut adaptive = JobAdaptiveBudget::default();
+        (0..frames)
+            .map(|_| {
+                let jobs = adaptive.current(budget, 16);
+                adaptive.update(budget, 16, per_job * jobs, jobs);
+                adaptive.current(budget, 16)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn budget_converges_toward_the_target() {
+        let budget = JobTimeBudget::new(Duration::from_millis(2));
+
+        // 0.5ms jobs fit 4 to a frame
+        let history = simulate(&budget, Duration::from_micros(500), 8);
+        assert_eq!(history, [10, 7, 5, 4, 4, 4, 4, 4]);
+
+        // 0.3ms jobs fit 6 to a frame, staying under the target
+        let history = simulate(&budget, Duration::from_micros(300), 8);
+        assert_eq!(history.last(), Some(&6));
+
+        // 0.1ms jobs fit 20 to a frame, so the budget grows
+        let history = simulate(&budget, Duration::from_micros(100), 8);
+        assert_eq!(history.last(), Some(&20));
+    }
+
+    #[test]
+    fn budget_is_clamped() {
+        let budget = JobTimeBudget {
+            gpu_time: Duration::from_millis(2),
+            min_jobs_per_frame: 2,
+            max_jobs_per_frame: 32,
+        };
+
+        let history = simulate(&budget, Duration::from_millis(5), 8);
+        assert_eq!(history.last(), Some(&2));
+
+        let history = simulate(&budget, Duration::ZERO, 8);
+        assert_eq!(history.last(), Some(&32));
+
+        // frames without any measured jobs keep the budget
+        let mut adaptive = JobAdaptiveBudget {
+            jobs_per_frame: Some(8),
+        };
+        adaptive.update(&budget, 16, Duration::ZERO, 0);
+        assert_eq!(adaptive.current(&budget, 16), 8);
+    }
+}
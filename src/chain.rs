@@ -0,0 +1,232 @@
+use bevy_ecs::{
+    bundle::Bundle,
+    entity::Entity,
+    observer::Trigger,
+    query::Has,
+    system::{Commands, Query, Res},
+};
+
+use crate::{
+    completion::{JobCompletionBatch, JobCompletionBatches, JobCompletionSender},
+    meta::{ExtractWhen, JobAfter, JobMarker},
+    runner::complete_main_world_job,
+    JobComplete, JobError, JobExecutionSettings,
+};
+
+/// An extension trait for spawning a chain of jobs, each waiting on the jobs before it
+/// with a [`JobAfter`].
+pub trait SpawnJobChainExt<'w, 's> {
+    /// Spawns the first job of a chain, to be followed by others with
+    /// [`JobChain::then`] and [`JobChain::fan_out`].
+    fn spawn_job(&mut self, job: impl Bundle) -> JobChain<'_, 'w, 's>;
+}
+
+impl<'w, 's> SpawnJobChainExt<'w, 's> for Commands<'w, 's> {
+    fn spawn_job(&mut self, job: impl Bundle) -> JobChain<'_, 'w, 's> {
+        JobChain {
+            commands: self,
+            jobs: Vec::new(),
+            stage: Vec::new(),
+        }
+        .then(job)
+    }
+}
+
+/// A chain of jobs spawned with [`SpawnJobChainExt::spawn_job`], made of stages that
+/// each wait on every job of the stage before them. For example:
+///
+/// ```ignore
+/// let [bake, denoise, preview, upload] = commands
+///     .spawn_job(BakeJob)
+///     .then(DenoiseJob)
+///     .fan_out([PreviewJob, UploadJob])
+///     .jobs()
+///     .try_into()
+///     .unwrap();
+/// ```
+pub struct JobChain<'a, 'w, 's> {
+    commands: &'a mut Commands<'w, 's>,
+    jobs: Vec<Entity>,
+    stage: Vec<Entity>,
+}
+
+impl JobChain<'_, '_, '_> {
+    /// Spawns a job that waits on every job of the last stage.
+    pub fn then(self, job: impl Bundle) -> Self {
+        self.fan_out([job])
+    }
+
+    /// Spawns several jobs that each wait on every job of the last stage, and make up
+    /// the next stage together, so a job added after them waits on all of them.
+    pub fn fan_out<B: Bundle>(mut self, jobs: impl IntoIterator<Item = B>) -> Self {
+        let after = JobAfter(self.stage.clone());
+        let stage = jobs
+            .into_iter()
+            .map(|job| {
+                if after.0.is_empty() {
+                    self.commands.spawn(job).id()
+                } else {
+                    self.commands.spawn((job, after.clone())).id()
+                }
+            })
+            .collect::<Vec<_>>();
+
+        self.jobs.extend(&stage);
+        self.stage = stage;
+        self
+    }
+
+    /// The jobs of the last stage.
+    pub fn stage(&self) -> &[Entity] {
+        &self.stage
+    }
+
+    /// Every job of the chain, in the order they were spawned.
+    pub fn jobs(self) -> Vec<Entity> {
+        self.jobs
+    }
+}
+
+/// Releases the jobs waiting on a completed job with [`JobAfter`] once every job they
+/// wait on has completed, or fails them if it failed.
+pub(crate) fn release_chained_jobs(
+    trigger: Trigger<JobComplete>,
+    mut dependents: Query<(
+        Entity,
+        &mut JobAfter,
+        Has<ExtractWhen>,
+        Option<&JobCompletionSender>,
+        Option<&JobCompletionBatch>,
+    )>,
+    settings: Res<JobExecutionSettings>,
+    mut commands: Commands,
+) {
+    let job = trigger.entity();
+    let mut batches = JobCompletionBatches::default();
+    for (dependent, mut after, deferred, sender, batch) in &mut dependents {
+        if !after.0.contains(&job) {
+            continue;
+        }
+
+        if trigger.event().0.is_err() {
+            // removed first, so the dependent isn't failed again by another job it waits on
+            commands.entity(dependent).remove::<JobAfter>();
+            complete_main_world_job(
+                &mut commands,
+                &mut batches,
+                &settings,
+                (dependent, Err(JobError::InputsFailed)),
+                sender,
+                batch,
+            );
+            continue;
+        }
+
+        after.0.retain(|waited| *waited != job);
+        if after.0.is_empty() {
+            let mut dependent = commands.entity(dependent);
+            dependent.remove::<JobAfter>();
+            // jobs that are also deferred with `ExtractWhen` are released along with them
+            if !deferred {
+                dependent.insert(JobMarker);
+            }
+        }
+    }
+    batches.send();
+}
+
+#[cfg(test)]
+mod test {
+    use bevy_ecs::{component::Component, entity::Entity, world::World};
+
+    use crate::{
+        meta::{defer_chained_extraction, JobAfter, JobMarker},
+        JobComplete, JobDone, JobError, JobExecutionSettings,
+    };
+
+    use super::{release_chained_jobs, SpawnJobChainExt};
+
+    #[derive(Component)]
+    #[require(JobMarker)]
+    struct Stage;
+
+    fn world() -> World {
+        let mut world = World::new();
+        world.insert_resource(JobExecutionSettings {
+            done_retention_frames: 1,
+            ..Default::default()
+        });
+        world.add_observer(defer_chained_extraction);
+        world.add_observer(release_chained_jobs);
+        world
+    }
+
+    fn chain(world: &mut World) -> Vec<Entity> {
+        let jobs = world
+            .commands()
+            .spawn_job(Stage)
+            .then(Stage)
+            .fan_out([Stage, Stage])
+            .then(Stage)
+            .jobs();
+        world.flush();
+        jobs
+    }
+
+    fn after(world: &World, job: Entity) -> Option<&[Entity]> {
+        world.get::<JobAfter>(job).map(|after| &*after.0)
+    }
+
+    #[test]
+    fn chained_jobs_wait_on_the_previous_stage() {
+        let mut world = world();
+        let jobs = chain(&mut world);
+        let [first, second, left, right, last] = jobs[..] else {
+            panic!("expected five jobs, got {}", jobs.len());
+        };
+
+        assert_eq!(after(&world, first), None);
+        assert_eq!(after(&world, second), Some(&[first][..]));
+        assert_eq!(after(&world, left), Some(&[second][..]));
+        assert_eq!(after(&world, right), Some(&[second][..]));
+        assert_eq!(after(&world, last), Some(&[left, right][..]));
+
+        let pending = |world: &World| {
+            jobs.iter()
+                .map(|job| world.get::<JobMarker>(*job).is_some())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(pending(&world), [true, false, false, false, false]);
+
+        world.trigger_targets(JobComplete(Ok(())), first);
+        world.trigger_targets(JobComplete(Ok(())), second);
+        world.flush();
+        assert_eq!(pending(&world), [true, true, true, true, false]);
+
+        // the last job waits on both jobs of the fanned out stage
+        world.trigger_targets(JobComplete(Ok(())), left);
+        assert_eq!(after(&world, last), Some(&[right][..]));
+        world.trigger_targets(JobComplete(Ok(())), right);
+        world.flush();
+        assert_eq!(after(&world, last), None);
+        assert!(world.get::<JobMarker>(last).is_some());
+    }
+
+    #[test]
+    fn failed_jobs_fail_the_rest_of_the_chain() {
+        let mut world = world();
+        let jobs = chain(&mut world);
+
+        world.trigger_targets(JobComplete(Ok(())), jobs[0]);
+        world.trigger_targets(JobComplete(Err(JobError::ExecutionFailed)), jobs[1]);
+        world.flush();
+
+        for job in &jobs[2..] {
+            assert_eq!(
+                world.get::<JobDone>(*job).map(|done| done.result),
+                Some(Err(JobError::InputsFailed))
+            );
+            assert!(world.get::<JobMarker>(*job).is_none());
+        }
+    }
+}
@@ -0,0 +1,48 @@
+use bevy_ecs::{component::Component, entity::Entity};
+use crossbeam_channel::Sender;
+
+use super::JobError;
+
+/// The result of a completed job, along with the main world entity it was spawned on.
+pub type JobCompletion = (Entity, Result<(), JobError>);
+
+/// Sends a job's [`JobCompletion`] over a channel once it completes, for code outside of
+/// the ECS that waits on jobs. Add it to the job's entity when spawning it.
+///
+/// Each job sends its own message. When many jobs complete in the same frame, like in
+/// bulk bakes, [`JobCompletionBatch`] sends far fewer messages.
+#[derive(Clone, Component, Debug)]
+pub struct JobCompletionSender(pub Sender<JobCompletion>);
+
+/// Sends the [`JobCompletion`]s of every job sharing the same channel that completed
+/// in a frame as a single batch, instead of one message per job like
+/// [`JobCompletionSender`]. Add it to each job's entity when spawning it.
+///
+/// Batches are sent at most once per frame and channel, and never empty.
+#[derive(Clone, Component, Debug)]
+pub struct JobCompletionBatch(pub Sender<Vec<JobCompletion>>);
+
+/// Collects the completions of jobs with a [`JobCompletionBatch`] in a frame,
+/// grouped by channel.
+#[derive(Default)]
+pub(crate) struct JobCompletionBatches(Vec<(Sender<Vec<JobCompletion>>, Vec<JobCompletion>)>);
+
+impl JobCompletionBatches {
+    pub fn push(&mut self, batch: &JobCompletionBatch, completion: JobCompletion) {
+        match self
+            .0
+            .iter_mut()
+            .find(|(sender, _)| sender.same_channel(&batch.0))
+        {
+            Some((_, completions)) => completions.push(completion),
+            None => self.0.push((batch.0.clone(), vec![completion])),
+        }
+    }
+
+    /// Sends every batch, ignoring channels whose receivers were dropped.
+    pub fn send(self) {
+        for (sender, completions) in self.0 {
+            let _ = sender.send(completions);
+        }
+    }
+}
@@ -0,0 +1,158 @@
+use core::{
+    any::Any,
+    ops::{Deref, DerefMut},
+};
+
+use bevy_ecs::entity::Entity;
+use bevy_render::{
+    render_resource::{Buffer, CommandEncoder, CommandEncoderDescriptor},
+    renderer::RenderDevice,
+};
+
+use crate::{adapter::JobTransfer, meta::JobAdapter, result::JobResultValue};
+
+/// The context a job records its commands in, passed to [`GraphicsJob::run`](crate::GraphicsJob::run).
+///
+/// It dereferences to the job's current [`CommandEncoder`], so passes can be recorded
+/// on it directly, for example with `context.begin_compute_pass(..)`.
+pub struct JobRunContext<'a> {
+    render_device: &'a RenderDevice,
+    adapter: JobAdapter,
+    label: &'static str,
+    job: Entity,
+    main_job: Option<Entity>,
+    chunk: u32,
+    // the last encoder is the one being recorded into
+    command_encoders: Vec<CommandEncoder>,
+    transfers: Vec<JobTransfer>,
+    result: Option<JobResultValue>,
+}
+
+impl<'a> JobRunContext<'a> {
+    pub(crate) fn new(
+        render_device: &'a RenderDevice,
+        adapter: JobAdapter,
+        label: &'static str,
+        job: Entity,
+        main_job: Option<Entity>,
+        chunk: u32,
+    ) -> Self {
+        let mut context = Self {
+            render_device,
+            adapter,
+            label,
+            job,
+            main_job,
+            chunk,
+            command_encoders: Vec::new(),
+            transfers: Vec::new(),
+            result: None,
+        };
+        context.flush();
+        context
+    }
+
+    /// The render device, for creating resources while recording.
+    pub fn render_device(&self) -> &'a RenderDevice {
+        self.render_device
+    }
+
+    /// The adapter the job is running on, which is only
+    /// [`Secondary`](JobAdapter::Secondary) if the job asked for it with a [`JobAdapter`]
+    /// and a [`JobSecondaryDevice`](crate::JobSecondaryDevice) exists.
+    pub fn adapter(&self) -> JobAdapter {
+        self.adapter
+    }
+
+    /// The job's render world entity.
+    pub fn job(&self) -> Entity {
+        self.job
+    }
+
+    /// The job's main world entity, if it was spawned in the main world.
+    pub fn main_job(&self) -> Option<Entity> {
+        self.main_job
+    }
+
+    /// The index of the chunk being recorded. See [`GraphicsJob::run_chunk`](crate::GraphicsJob::run_chunk).
+    pub fn chunk(&self) -> u32 {
+        self.chunk
+    }
+
+    /// The encoder commands are currently recorded into.
+    pub fn command_encoder(&mut self) -> &mut CommandEncoder {
+        self.command_encoders
+            .last_mut()
+            .expect("a command encoder should always be open")
+    }
+
+    /// Finishes the current command encoder and starts recording into a new one, so that
+    /// the commands recorded so far end up in their own command buffer. The buffers are
+    /// still submitted together with the rest of the job's commands, in order. To submit
+    /// commands early, yield from [`GraphicsJob::run_chunk`](crate::GraphicsJob::run_chunk) instead.
+    pub fn flush(&mut self) {
+        let command_encoder =
+            self.render_device
+                .create_command_encoder(&CommandEncoderDescriptor {
+                    label: Some(self.label),
+                });
+        self.command_encoders.push(command_encoder);
+    }
+
+    /// Sets a value describing the job's result, like the number of samples it baked,
+    /// to be read from [`JobResults`](crate::JobResults) in the main world once the job
+    /// completes. Setting it again replaces the previous value.
+    pub fn set_result<T: Any + Send + Sync>(&mut self, result: T) {
+        self.result = Some(Box::new(result));
+    }
+
+    /// Copies the first `size` bytes of `source`, a buffer on the job's device, into
+    /// `destination`, a buffer on the primary device, for results the primary device needs.
+    ///
+    /// On the primary device this is a plain buffer copy. On a
+    /// [`JobSecondaryDevice`](crate::JobSecondaryDevice) the copy goes through the CPU
+    /// once the job's commands are submitted, blocking until they finish, so
+    /// `destination` needs [`BufferUsages::COPY_DST`](bevy_render::render_resource::BufferUsages::COPY_DST)
+    /// and `source` [`BufferUsages::COPY_SRC`](bevy_render::render_resource::BufferUsages::COPY_SRC)
+    /// in both cases. If the copy can't be read back from the secondary device, the job
+    /// fails with [`JobError::ExecutionFailed`](crate::JobError::ExecutionFailed).
+    pub fn transfer_to_primary(&mut self, source: &Buffer, destination: &Buffer, size: u64) {
+        match self.adapter {
+            JobAdapter::Primary => {
+                self.command_encoder()
+                    .copy_buffer_to_buffer(source, 0, destination, 0, size);
+            }
+            JobAdapter::Secondary => self.transfers.push(JobTransfer {
+                source: source.clone(),
+                destination: destination.clone(),
+                size,
+            }),
+        }
+    }
+
+    pub(crate) fn into_parts(
+        self,
+    ) -> (
+        Vec<CommandEncoder>,
+        Vec<JobTransfer>,
+        Option<JobResultValue>,
+    ) {
+        (self.command_encoders, self.transfers, self.result)
+    }
+}
+
+impl Deref for JobRunContext<'_> {
+    type Target = CommandEncoder;
+
+    fn deref(&self) -> &Self::Target {
+        self.command_encoders
+            .last()
+            .expect("a command encoder should always be open")
+    }
+}
+
+impl DerefMut for JobRunContext<'_> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        self.command_encoder()
+    }
+}
@@ -0,0 +1,145 @@
+use std::sync::{Arc, Mutex};
+
+use bevy_app::App;
+use bevy_ecs::{component::Component, entity::Entity, query::With, system::Resource, world::World};
+use bevy_render::{renderer::RenderDevice, RenderApp};
+use bevy_utils::tracing::error;
+use wgpu_types::DeviceLostReason;
+
+use crate::{runner::reset_jobs, JobExecutionSettings};
+
+/// Describes what happens to in-flight jobs when the GPU device is lost,
+/// for example after a driver reset or a GPU hang.
+///
+/// In both cases, cached pipeline ids, prepared bind groups, and job-owned textures
+/// are dropped so that they're recreated from scratch.
+#[derive(Copy, Clone, Default, PartialEq, Eq, Debug)]
+pub enum DeviceLostPolicy {
+    /// Fails every in-flight job with [`JobError::DeviceLost`](crate::JobError::DeviceLost).
+    #[default]
+    Fail,
+    /// Returns every in-flight job to waiting on its inputs, and restarts its time-out.
+    /// Jobs that aren't [idempotent](crate::GraphicsJob::IS_IDEMPOTENT) and already
+    /// yielded partway through their work are failed instead.
+    Requeue,
+}
+
+/// Set from the device lost callback, which may be called from any thread.
+#[derive(Resource, Clone, Default)]
+pub(crate) struct JobDeviceLost(Arc<Mutex<Option<String>>>);
+
+impl JobDeviceLost {
+    pub fn signal(&self, message: String) {
+        *self.0.lock().unwrap() = Some(message);
+    }
+
+    fn take(&self) -> Option<String> {
+        self.0.lock().unwrap().take()
+    }
+}
+
+/// Callbacks that drop cached resources tied to the lost device, registered by
+/// each input that caches them.
+#[derive(Resource, Default)]
+pub(crate) struct JobDeviceResets(Vec<fn(&mut World)>);
+
+/// Registers a callback to run in the render world when the device is lost.
+/// Callbacks may be registered more than once, so they should be idempotent.
+pub(crate) fn add_device_reset(app: &mut App, reset: fn(&mut World)) {
+    if let Some(render_app) = app.get_sub_app_mut(RenderApp) {
+        render_app
+            .world_mut()
+            .get_resource_or_init::<JobDeviceResets>()
+            .0
+            .push(reset);
+    }
+}
+
+/// Removes a component from every entity that has it, for dropping prepared resources.
+pub(crate) fn remove_all<C: Component>(world: &mut World) {
+    let entities = world
+        .query_filtered::<Entity, With<C>>()
+        .iter(world)
+        .collect::<Vec<_>>();
+    for entity in entities {
+        world.entity_mut(entity).remove::<C>();
+    }
+}
+
+/// Listens for the render device being lost. This replaces any other device lost
+/// callback, since `wgpu` only supports one and has no way to get the current one to
+/// chain to it, see [`GraphicsJobsPlugin`](crate::GraphicsJobsPlugin).
+pub(crate) fn watch_device_lost(world: &World) {
+    let device_lost = world.resource::<JobDeviceLost>().clone();
+    world
+        .resource::<RenderDevice>()
+        .wgpu_device()
+        .set_device_lost_callback(move |reason, message| {
+            // the callback is also called when the device is dropped on exit,
+            // or when it's replaced
+            if matches!(
+                reason,
+                DeviceLostReason::Unknown | DeviceLostReason::Destroyed
+            ) {
+                device_lost.signal(message);
+            }
+        });
+}
+
+pub(crate) fn recover_lost_device(world: &mut World) {
+    let Some(message) = world.resource::<JobDeviceLost>().take() else {
+        return;
+    };
+
+    error!("graphics device lost, resetting graphics jobs: {message}");
+
+    let resets = world
+        .get_resource::<JobDeviceResets>()
+        .map(|resets| resets.0.clone())
+        .unwrap_or_default();
+    for reset in resets {
+        reset(world);
+    }
+
+    let policy = world.resource::<JobExecutionSettings>().on_device_lost;
+    reset_jobs(world, policy);
+}
+
+#[cfg(test)]
+mod test {
+    use bevy_app::{App, SubApp};
+    use bevy_ecs::{component::Component, world::World};
+    use bevy_render::RenderApp;
+
+    use crate::{JobExecutionSettings, JobMarker};
+
+    use super::{
+        add_device_reset, recover_lost_device, remove_all, DeviceLostPolicy, JobDeviceLost,
+    };
+
+    #[derive(Component)]
+    struct Prepared;
+
+    #[test]
+    fn resets_run_once_device_is_lost() {
+        let mut app = App::new();
+        app.insert_sub_app(RenderApp, SubApp::new());
+        add_device_reset(&mut app, remove_all::<Prepared>);
+        add_device_reset(&mut app, remove_all::<Prepared>);
+
+        let world: &mut World = app.sub_app_mut(RenderApp).world_mut();
+        world.init_resource::<JobDeviceLost>();
+        world.insert_resource(JobExecutionSettings {
+            on_device_lost: DeviceLostPolicy::Requeue,
+            ..Default::default()
+        });
+        let job = world.spawn((JobMarker, Prepared)).id();
+
+        recover_lost_device(world);
+        assert!(world.get::<Prepared>(job).is_some());
+
+        world.resource::<JobDeviceLost>().signal("lost".into());
+        recover_lost_device(world);
+        assert!(world.get::<Prepared>(job).is_none());
+    }
+}
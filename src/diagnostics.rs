@@ -0,0 +1,473 @@
+use core::{
+    mem,
+    sync::atomic::{AtomicBool, Ordering},
+    time::Duration,
+};
+use std::sync::{Arc, Mutex};
+
+use bevy_app::{App, Plugin, Update};
+use bevy_diagnostic::{Diagnostic, DiagnosticMeasurement, DiagnosticPath, DiagnosticsStore};
+use bevy_ecs::{
+    schedule::IntoSystemConfigs,
+    system::{Local, Res, ResMut, Resource},
+};
+use bevy_render::{
+    diagnostic::RenderDiagnosticsPlugin,
+    render_resource::{
+        Buffer, BufferDescriptor, BufferUsages, CommandEncoder, CommandEncoderDescriptor, MapMode,
+        WgpuFeatures,
+    },
+    renderer::{RenderDevice, RenderQueue},
+    RenderApp,
+};
+use bevy_utils::Instant;
+use disqualified::ShortName;
+use wgpu::{QuerySet, QuerySetDescriptor, QueryType, QUERY_SIZE};
+
+use crate::{
+    registry::RegisteredJobs,
+    stats::{record_job_gpu_time, JobStats},
+    JobAdaptiveBudget, JobExecutionSettings,
+};
+
+/// Measures the GPU time of each job when bevy's `RenderDiagnosticsPlugin` is added,
+/// and the device supports timestamp queries.
+///
+/// Bevy's diagnostics recorder is only reachable from inside the render graph, so the
+/// runner writes its own timestamps around each finished job's commands, which are
+/// submitted along with them from `JobSet::Execute`. Once read back, the spans are added to the
+/// [`DiagnosticsStore`] next to bevy's, as `render/job/<label>/elapsed_gpu`, where the
+/// label is the job's [`GraphicsJob::label`](crate::GraphicsJob::label). Chunks
+/// submitted early by jobs that yield aren't included in the job's span.
+///
+/// Each frame's spans are also used to adapt the number of jobs executed each frame to
+/// [`JobExecutionSettings::time_budget`], and each span is added to the [`JobStats`]
+/// of its job type.
+pub(crate) struct JobDiagnosticsPlugin;
+
+impl Plugin for JobDiagnosticsPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            Update,
+            (sync_job_gpu_times, (adapt_job_budget, record_job_gpu_times)).chain(),
+        );
+    }
+
+    fn finish(&self, app: &mut App) {
+        if !app.is_plugin_added::<RenderDiagnosticsPlugin>() {
+            return;
+        }
+        let Some(render_app) = app.get_sub_app_mut(RenderApp) else {
+            return;
+        };
+
+        let world = render_app.world();
+        let features =
+            WgpuFeatures::TIMESTAMP_QUERY | WgpuFeatures::TIMESTAMP_QUERY_INSIDE_ENCODERS;
+        if !world
+            .resource::<RenderDevice>()
+            .features()
+            .contains(features)
+        {
+            return;
+        }
+
+        let gpu_times = JobGpuTimes::default();
+        let spans = JobSpans::new(
+            world.resource::<RenderQueue>().get_timestamp_period(),
+            gpu_times.clone(),
+        );
+        render_app.insert_resource(spans);
+        app.insert_resource(gpu_times);
+    }
+}
+
+/// The GPU times of the jobs in each frame that was read back, waiting to be added to
+/// the [`DiagnosticsStore`] by the main world.
+#[derive(Resource, Clone, Default)]
+struct JobGpuTimes(Arc<Mutex<Vec<Vec<(String, f64)>>>>);
+
+/// The most spans measured in a single frame. Jobs finished past this aren't measured.
+const MAX_SPANS: u32 = 128;
+
+/// Timestamps written around the commands of each job finished by the runner, which
+/// are resolved as part of the frame's last submission and read back once it's done.
+#[derive(Resource)]
+pub(crate) struct JobSpans(Mutex<SpanRecorder>);
+
+struct SpanRecorder {
+    timestamp_period: f32,
+    current: Option<SpanFrame>,
+    in_flight: Vec<SpanFrame>,
+    free: Vec<SpanFrame>,
+    gpu_times: JobGpuTimes,
+}
+
+struct SpanFrame {
+    query_set: QuerySet,
+    resolve_buffer: Buffer,
+    read_buffer: Buffer,
+    names: Vec<String>,
+    mapped: Arc<AtomicBool>,
+}
+
+impl SpanFrame {
+    fn new(render_device: &RenderDevice) -> Self {
+        let size = u64::from(MAX_SPANS * 2) * u64::from(QUERY_SIZE);
+        let query_set = render_device
+            .wgpu_device()
+            .create_query_set(&QuerySetDescriptor {
+                label: Some("job_span_timestamps"),
+                ty: QueryType::Timestamp,
+                count: MAX_SPANS * 2,
+            });
+        let resolve_buffer = render_device.create_buffer(&BufferDescriptor {
+            label: Some("job_span_resolve_buffer"),
+            size,
+            usage: BufferUsages::QUERY_RESOLVE | BufferUsages::COPY_SRC,
+            mapped_at_creation: false,
+        });
+        let read_buffer = render_device.create_buffer(&BufferDescriptor {
+            label: Some("job_span_read_buffer"),
+            size,
+            usage: BufferUsages::COPY_DST | BufferUsages::MAP_READ,
+            mapped_at_creation: false,
+        });
+
+        Self {
+            query_set,
+            resolve_buffer,
+            read_buffer,
+            names: Vec::new(),
+            mapped: Arc::default(),
+        }
+    }
+
+    fn timestamp_encoder(&self, render_device: &RenderDevice, index: u32) -> CommandEncoder {
+        let mut encoder =
+            render_device.create_command_encoder(&CommandEncoderDescriptor { label: None });
+        encoder.write_timestamp(&self.query_set, index);
+        encoder
+    }
+
+    fn size(&self) -> u64 {
+        self.names.len() as u64 * 2 * u64::from(QUERY_SIZE)
+    }
+
+    /// The names and elapsed milliseconds of the frame's spans, once it's mapped.
+    fn read(&mut self, timestamp_period: f32) -> Vec<(String, f64)> {
+        let slice = self.read_buffer.slice(..self.size());
+        let view = slice.get_mapped_range();
+        let times = view
+            .chunks_exact(QUERY_SIZE as usize * 2)
+            .zip(mem::take(&mut self.names))
+            .map(|(timestamps, name)| {
+                let timestamp = |bytes: &[u8]| u64::from_le_bytes(bytes.try_into().unwrap());
+                let (begin, end) = timestamps.split_at(QUERY_SIZE as usize);
+                let ticks = timestamp(end).saturating_sub(timestamp(begin));
+                (
+                    name,
+                    ticks as f64 * f64::from(timestamp_period) / 1_000_000.0,
+                )
+            })
+            .collect();
+        drop(view);
+        self.read_buffer.unmap();
+        self.mapped.store(false, Ordering::Relaxed);
+        times
+    }
+}
+
+impl JobSpans {
+    fn new(timestamp_period: f32, gpu_times: JobGpuTimes) -> Self {
+        Self(Mutex::new(SpanRecorder {
+            timestamp_period,
+            current: None,
+            in_flight: Vec::new(),
+            free: Vec::new(),
+            gpu_times,
+        }))
+    }
+
+    /// Wraps the commands of a finished job in timestamps, which are submitted along with them.
+    pub fn record(
+        &self,
+        render_device: &RenderDevice,
+        label: ShortName,
+        encoders: &mut Vec<CommandEncoder>,
+    ) {
+        let recorder = &mut *self.0.lock().unwrap();
+        let frame = recorder.current.get_or_insert_with(|| {
+            recorder
+                .free
+                .pop()
+                .unwrap_or_else(|| SpanFrame::new(render_device))
+        });
+        let index = frame.names.len() as u32 * 2;
+        if index >= MAX_SPANS * 2 {
+            return;
+        }
+
+        encoders.insert(0, frame.timestamp_encoder(render_device, index));
+        encoders.push(frame.timestamp_encoder(render_device, index + 1));
+        frame.names.push(span_name(label));
+    }
+
+    /// Resolves the timestamps written this frame, into an encoder submitted after every job.
+    pub fn resolve(&self, render_device: &RenderDevice) -> Option<CommandEncoder> {
+        let recorder = self.0.lock().unwrap();
+        let frame = recorder.current.as_ref()?;
+        let mut encoder =
+            render_device.create_command_encoder(&CommandEncoderDescriptor { label: None });
+        encoder.resolve_query_set(
+            &frame.query_set,
+            0..frame.names.len() as u32 * 2,
+            &frame.resolve_buffer,
+            0,
+        );
+        encoder.copy_buffer_to_buffer(
+            &frame.resolve_buffer,
+            0,
+            &frame.read_buffer,
+            0,
+            frame.size(),
+        );
+        Some(encoder)
+    }
+
+    /// Reads back the frames whose timestamps are mapped, and maps the frame that was
+    /// just submitted.
+    pub fn finish_frame(&self) {
+        let recorder = &mut *self.0.lock().unwrap();
+
+        let mut index = 0;
+        while index < recorder.in_flight.len() {
+            if !recorder.in_flight[index].mapped.load(Ordering::Relaxed) {
+                index += 1;
+                continue;
+            }
+            let mut frame = recorder.in_flight.swap_remove(index);
+            let times = frame.read(recorder.timestamp_period);
+            recorder.gpu_times.0.lock().unwrap().push(times);
+            recorder.free.push(frame);
+        }
+
+        if let Some(frame) = recorder.current.take() {
+            let mapped = frame.mapped.clone();
+            frame
+                .read_buffer
+                .slice(..frame.size())
+                .map_async(MapMode::Read, move |result| {
+                    mapped.store(result.is_ok(), Ordering::Relaxed);
+                });
+            recorder.in_flight.push(frame);
+        }
+    }
+}
+
+fn span_name(label: ShortName) -> String {
+    format!("job/{label}")
+}
+
+/// Where a span's GPU time is stored, next to those of bevy's render passes.
+fn span_path(name: &str) -> DiagnosticPath {
+    DiagnosticPath::new(format!("render/{name}/elapsed_gpu"))
+}
+
+fn is_job_span(diagnostic: &Diagnostic) -> bool {
+    let path = diagnostic.path().as_str();
+    path.starts_with("render/job/") && path.ends_with("/elapsed_gpu")
+}
+
+/// The total GPU time and number of the jobs measured in the latest frame synced from
+/// the render world, if it's newer than `last_frame`. Each frame's measurements are
+/// added at the same instant, with one measurement per span.
+fn latest_job_gpu_time(
+    store: &DiagnosticsStore,
+    last_frame: &mut Option<Instant>,
+) -> Option<(Duration, u32)> {
+    let measurements = || {
+        store
+            .iter()
+            .filter(|diagnostic| is_job_span(diagnostic))
+            .flat_map(Diagnostic::measurements)
+    };
+
+    let latest = measurements().map(|measurement| measurement.time).max()?;
+    if last_frame.is_some_and(|last| last >= latest) {
+        return None;
+    }
+    *last_frame = Some(latest);
+
+    let (millis, jobs) = measurements()
+        .filter(|measurement| measurement.time == latest)
+        .fold((0.0, 0), |(millis, jobs), measurement| {
+            (millis + measurement.value, jobs + 1)
+        });
+    Some((Duration::from_secs_f64(millis.max(0.0) / 1000.0), jobs))
+}
+
+/// Adds the GPU times read back since the last update to the [`DiagnosticsStore`],
+/// with every span of a frame measured at the same instant.
+fn sync_job_gpu_times(
+    gpu_times: Option<Res<JobGpuTimes>>,
+    mut store: Option<ResMut<DiagnosticsStore>>,
+) {
+    let Some(gpu_times) = gpu_times else {
+        return;
+    };
+
+    for frame in mem::take(&mut *gpu_times.0.lock().unwrap()) {
+        let Some(store) = store.as_deref_mut() else {
+            continue;
+        };
+        let time = Instant::now();
+        for (name, value) in frame {
+            let path = span_path(&name);
+            if store.get(&path).is_none() {
+                store.add(Diagnostic::new(path.clone()).with_suffix("ms"));
+            }
+            let diagnostic = store.get_mut(&path).unwrap();
+            diagnostic.add_measurement(DiagnosticMeasurement { time, value });
+        }
+    }
+}
+
+fn adapt_job_budget(
+    store: Option<Res<DiagnosticsStore>>,
+    exec_settings: Res<JobExecutionSettings>,
+    mut adaptive_budget: ResMut<JobAdaptiveBudget>,
+    mut last_frame: Local<Option<Instant>>,
+) {
+    let (Some(store), Some(budget)) = (store, exec_settings.time_budget) else {
+        return;
+    };
+
+    if let Some((gpu_time, jobs)) = latest_job_gpu_time(&store, &mut last_frame) {
+        adaptive_budget.update(&budget, exec_settings.max_jobs_per_frame, gpu_time, jobs);
+    }
+}
+
+/// Adds the GPU time of every job measured since the last update to its type's [`JobStats`].
+fn record_job_gpu_times(
+    store: Option<Res<DiagnosticsStore>>,
+    registered: Res<RegisteredJobs>,
+    mut stats: ResMut<JobStats>,
+    mut last_frame: Local<Option<Instant>>,
+) {
+    let Some(store) = store else {
+        return;
+    };
+
+    let last = *last_frame;
+    for job_type in registered.job_types() {
+        let path = span_path(&span_name(job_type.label));
+        let Some(diagnostic) = store.get(&path) else {
+            continue;
+        };
+        for measurement in diagnostic.measurements() {
+            if last.is_some_and(|last| measurement.time <= last) {
+                continue;
+            }
+            *last_frame = (*last_frame).max(Some(measurement.time));
+            let gpu_time = Duration::from_secs_f64(measurement.value.max(0.0) / 1000.0);
+            record_job_gpu_time(&mut stats, job_type, gpu_time);
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::sync::{Arc, Mutex};
+
+    use bevy_diagnostic::{
+        Diagnostic, DiagnosticMeasurement, DiagnosticPath, DiagnosticsPlugin, DiagnosticsStore,
+    };
+    use bevy_ecs::{component::Component, observer::Trigger, system::Res, world::World};
+    use bevy_render::diagnostic::RenderDiagnosticsPlugin;
+    use bevy_utils::{Duration, Instant};
+    use disqualified::ShortName;
+
+    use crate::{
+        ext::{headless_test_app, InitGraphicsJobExt},
+        input::JobInputItem,
+        GraphicsJob, JobComplete, JobError, JobRunContext, JobSubmissions,
+    };
+
+    use super::{latest_job_gpu_time, span_name};
+
+    #[derive(Clone, Component)]
+    struct EmptyJob;
+
+    impl GraphicsJob for EmptyJob {
+        type In = ();
+
+        fn run(
+            &self,
+            _world: &World,
+            _context: &mut JobRunContext,
+            (): JobInputItem<Self, Self::In>,
+        ) -> Result<(), JobError> {
+            Ok(())
+        }
+    }
+
+    /// Measuring jobs doesn't move their commands out of the runner's own submission.
+    #[test]
+    fn measured_jobs_keep_their_submission() {
+        let mut app = headless_test_app();
+        app.add_plugins((DiagnosticsPlugin, RenderDiagnosticsPlugin))
+            .init_graphics_job::<EmptyJob>();
+        app.finish();
+        app.cleanup();
+
+        let submitted = Arc::new(Mutex::new(None));
+        let job_submitted = submitted.clone();
+        app.world_mut().spawn(EmptyJob).observe(
+            move |trigger: Trigger<JobComplete>, submissions: Res<JobSubmissions>| {
+                let submission = submissions.get(trigger.entity());
+                *job_submitted.lock().unwrap() = Some(submission.is_some());
+            },
+        );
+
+        for _ in 0..16 {
+            app.update();
+        }
+        assert_eq!(*submitted.lock().unwrap(), Some(true));
+    }
+
+    #[test]
+    fn spans_are_named_after_jobs() {
+        let label = ShortName::of::<crate::jobs::GenerateMipmapsJob>();
+        assert_eq!(span_name(label), "job/GenerateMipmapsJob");
+    }
+
+    #[test]
+    fn job_spans_of_the_latest_frame_are_summed() {
+        let mut store = DiagnosticsStore::default();
+        let earlier = Instant::now();
+        let latest = earlier + Duration::from_millis(16);
+        let mut add = |path: &'static str, time, value| {
+            let path = DiagnosticPath::new(path);
+            if store.get(&path).is_none() {
+                store.add(Diagnostic::new(path.clone()));
+            }
+            let diagnostic = store.get_mut(&path).unwrap();
+            diagnostic.add_measurement(DiagnosticMeasurement { time, value });
+        };
+        add("render/job/BlurJob/elapsed_gpu", earlier, 4.0);
+        add("render/job/BlurJob/elapsed_gpu", latest, 0.5);
+        add("render/job/BlurJob/elapsed_gpu", latest, 0.5);
+        add("render/job/NoiseJob/elapsed_gpu", latest, 1.0);
+        add("render/job/NoiseJob/elapsed_cpu", latest, 8.0);
+        add("render/main_pass/elapsed_gpu", latest, 8.0);
+
+        let mut last_frame = None;
+        assert_eq!(
+            latest_job_gpu_time(&store, &mut last_frame),
+            Some((Duration::from_millis(2), 3))
+        );
+        // the same frame isn't measured twice
+        assert_eq!(latest_job_gpu_time(&store, &mut last_frame), None);
+    }
+}
@@ -0,0 +1,110 @@
+use bevy_ecs::{
+    component::Component,
+    entity::Entity,
+    query::With,
+    system::{Commands, Query, Res},
+};
+
+use crate::{
+    input::{JobDependency, JobOutput},
+    meta::JobMarker,
+    JobError, JobExecutionSettings,
+};
+
+/// Marks a job's main world entity that completed, but is kept around for
+/// [`JobExecutionSettings::done_retention_frames`] so that tooling, like inspection
+/// panels, can read its final status.
+///
+/// Done jobs are no longer pending, so they aren't waited on when the app exits.
+#[derive(Copy, Clone, Component, PartialEq, Eq, Debug)]
+pub struct JobDone {
+    pub result: Result<(), JobError>,
+    /// The number of frames since the job completed.
+    pub frames: u32,
+}
+
+impl JobDone {
+    pub(crate) fn new(result: Result<(), JobError>) -> Self {
+        Self { result, frames: 0 }
+    }
+}
+
+/// Despawns done jobs once they've been done for the retention period, and no pending
+/// jobs depend on their output.
+pub(crate) fn despawn_done_jobs(
+    mut done_jobs: Query<(Entity, &mut JobDone, Option<&JobOutput>)>,
+    dependents: Query<&JobDependency, With<JobMarker>>,
+    settings: Res<JobExecutionSettings>,
+    mut commands: Commands,
+) {
+    for (entity, mut done, output) in &mut done_jobs {
+        let has_dependents = output.is_some_and(|output| {
+            dependents
+                .iter()
+                .any(|dependency| dependency.0 == output.label)
+        });
+        if done.frames >= settings.done_retention_frames && !has_dependents {
+            commands.entity(entity).despawn();
+        } else {
+            done.frames += 1;
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use bevy_app::{App, Update};
+
+    use crate::{
+        input::{JobDependency, JobOutput, JobOutputLifetime},
+        meta::JobMarker,
+        JobExecutionSettings,
+    };
+
+    use super::{despawn_done_jobs, JobDone};
+
+    fn app_with_retention(done_retention_frames: u32) -> App {
+        let mut app = App::new();
+        app.insert_resource(JobExecutionSettings {
+            done_retention_frames,
+            ..Default::default()
+        })
+        .add_systems(Update, despawn_done_jobs);
+        app
+    }
+
+    #[test]
+    fn done_jobs_are_kept_for_retention() {
+        let mut app = app_with_retention(3);
+        let job = app.world_mut().spawn(JobDone::new(Ok(()))).id();
+
+        for frame in 1..=3 {
+            app.update();
+            let done = app.world().get::<JobDone>(job).unwrap();
+            assert_eq!(done.frames, frame);
+        }
+
+        app.update();
+        assert!(app.world().get_entity(job).is_err());
+    }
+
+    #[test]
+    fn done_jobs_are_kept_for_dependents() {
+        let mut app = app_with_retention(1);
+        let output = JobOutput::new("shared", JobOutputLifetime::UntilDependentsDone);
+        let job = app.world_mut().spawn((JobDone::new(Ok(())), output)).id();
+        let dependent = app
+            .world_mut()
+            .spawn((JobMarker, JobDependency::new("shared")))
+            .id();
+
+        for _ in 0..4 {
+            app.update();
+        }
+        assert!(app.world().get_entity(job).is_ok());
+
+        app.world_mut().despawn(dependent);
+        app.update();
+        assert!(app.world().get_entity(job).is_err());
+    }
+}
@@ -1,6 +1,12 @@
-use bevy_app::App;
+use std::sync::{Arc, Mutex};
 
-use super::{GraphicsJob, SpecializedGraphicsJobPlugin};
+use bevy_app::{App, PluginsState};
+use bevy_ecs::{bundle::Bundle, entity::Entity, observer::Trigger, query::With};
+
+use super::{
+    meta::{JobMarker, JobTask},
+    GraphicsJob, JobComplete, JobCompletion, JobError, SpecializedGraphicsJobPlugin,
+};
 
 /// An extension trait for initializing graphics jobs on [`App`]
 pub trait InitGraphicsJobExt {
@@ -12,3 +18,415 @@ impl InitGraphicsJobExt for App {
         self.add_plugins(SpecializedGraphicsJobPlugin::<J>::default())
     }
 }
+
+/// The maximum number of updates [`RunGraphicsJobExt::run_job_blocking`] and
+/// [`RunGraphicsJobExt::flush_task`] wait for jobs.
+const MAX_BLOCKING_UPDATES: u32 = 1024;
+
+/// An extension trait for running graphics jobs to completion outside of
+/// a frame-driven main loop, for example in headless bake tools or tests.
+pub trait RunGraphicsJobExt {
+    /// Spawns a job and updates the app until it completes, returning its result.
+    ///
+    /// If the app's plugins haven't been finished yet, they're finished first.
+    /// If the job hasn't completed after 1024 updates, it's despawned and this
+    /// returns [`JobError::TimedOut`].
+    ///
+    /// This drives the whole app, so it must not be called from within a schedule,
+    /// and it shouldn't be mixed with [`App::run`]. For headless use, consider enabling
+    /// synchronous pipeline compilation on `RenderPlugin`, so that jobs don't time out
+    /// while their pipelines compile.
+    fn run_job_blocking(&mut self, job: impl Bundle) -> Result<(), JobError>;
+
+    /// Updates the app until every pending job of a [`JobTask`] completes, and returns
+    /// their [`JobCompletion`]s, in the order they completed. Jobs of other tasks, or
+    /// without one, keep running as usual, but aren't waited on.
+    ///
+    /// Like [`run_job_blocking`](RunGraphicsJobExt::run_job_blocking), this finishes
+    /// the app's plugins if needed, and waits for at most 1024 updates. Jobs that haven't
+    /// completed by then are despawned, and completed with [`JobError::TimedOut`].
+    fn flush_task(&mut self, task: JobTask) -> Vec<JobCompletion>;
+}
+
+impl RunGraphicsJobExt for App {
+    fn run_job_blocking(&mut self, job: impl Bundle) -> Result<(), JobError> {
+        if self.plugins_state() == PluginsState::Ready {
+            self.finish();
+            self.cleanup();
+        }
+
+        let result = Arc::new(Mutex::new(None));
+        let job_result = result.clone();
+        let job = self
+            .world_mut()
+            .spawn(job)
+            .observe(move |trigger: Trigger<JobComplete>| {
+                *job_result.lock().unwrap() = Some(trigger.event().0);
+            })
+            .id();
+
+        for _ in 0..MAX_BLOCKING_UPDATES {
+            self.update();
+            if let Some(result) = result.lock().unwrap().take() {
+                return result;
+            }
+        }
+
+        self.world_mut().despawn(job);
+        Err(JobError::TimedOut)
+    }
+
+    fn flush_task(&mut self, task: JobTask) -> Vec<JobCompletion> {
+        if self.plugins_state() == PluginsState::Ready {
+            self.finish();
+            self.cleanup();
+        }
+
+        let world = self.world_mut();
+        let jobs = world
+            .query_filtered::<(Entity, &JobTask), With<JobMarker>>()
+            .iter(world)
+            .filter(|(_, job_task)| **job_task == task)
+            .map(|(job, _)| job)
+            .collect::<Vec<_>>();
+
+        let completions = Arc::new(Mutex::new(Vec::new()));
+        for &job in &jobs {
+            let job_completions = completions.clone();
+            world
+                .entity_mut(job)
+                .observe(move |trigger: Trigger<JobComplete>| {
+                    job_completions
+                        .lock()
+                        .unwrap()
+                        .push((trigger.entity(), trigger.event().0));
+                });
+        }
+
+        let mut updates = 0;
+        while completions.lock().unwrap().len() < jobs.len() && updates < MAX_BLOCKING_UPDATES {
+            self.update();
+            updates += 1;
+        }
+
+        let mut completions = core::mem::take(&mut *completions.lock().unwrap());
+        for job in jobs {
+            if completions.iter().all(|(completed, _)| *completed != job) {
+                if self.world().entities().contains(job) {
+                    self.world_mut().despawn(job);
+                }
+                completions.push((job, Err(JobError::TimedOut)));
+            }
+        }
+        completions
+    }
+}
+
+/// An app with the real renderer and no window, for tests that run jobs on the GPU. This
+/// needs a GPU adapter, though a software one will do.
+#[cfg(test)]
+pub(crate) fn headless_test_app() -> App {
+    use bevy::{
+        asset::AssetPlugin,
+        render::{texture::ImagePlugin, RenderPlugin},
+        window::{ExitCondition, WindowPlugin},
+        MinimalPlugins,
+    };
+
+    use crate::GraphicsJobsPlugin;
+
+    let mut app = App::new();
+    app.add_plugins((
+        MinimalPlugins,
+        AssetPlugin::default(),
+        WindowPlugin {
+            primary_window: None,
+            exit_condition: ExitCondition::DontExit,
+            ..Default::default()
+        },
+        RenderPlugin::default(),
+        ImagePlugin::default(),
+        GraphicsJobsPlugin::default(),
+    ));
+    app
+}
+
+/// Copies `size` bytes from the start of a buffer created with
+/// [`BufferUsages::COPY_SRC`](bevy_render::render_resource::BufferUsages::COPY_SRC),
+/// and waits for them on the CPU.
+#[cfg(test)]
+pub(crate) fn read_buffer(
+    app: &App,
+    buffer: &bevy_render::render_resource::Buffer,
+    size: u64,
+) -> Vec<u8> {
+    use bevy_render::{
+        render_resource::{BufferDescriptor, BufferUsages, CommandEncoderDescriptor},
+        renderer::{RenderDevice, RenderQueue},
+    };
+
+    let device = app.world().resource::<RenderDevice>();
+    let queue = app.world().resource::<RenderQueue>();
+    let staging = device.create_buffer(&BufferDescriptor {
+        label: Some("gigs_test_readback"),
+        size,
+        usage: BufferUsages::COPY_DST | BufferUsages::MAP_READ,
+        mapped_at_creation: false,
+    });
+    let mut encoder = device.create_command_encoder(&CommandEncoderDescriptor::default());
+    encoder.copy_buffer_to_buffer(buffer, 0, &staging, 0, size);
+    queue.submit([encoder.finish()]);
+    read_mapped(app, &staging)
+}
+
+/// Reads back one mip level of one array layer of a 2d texture created with
+/// [`TextureUsages::COPY_SRC`](bevy_render::render_resource::TextureUsages::COPY_SRC),
+/// with its rows tightly packed.
+#[cfg(test)]
+pub(crate) fn read_texture(
+    app: &App,
+    texture: &bevy_render::render_resource::Texture,
+    mip_level: u32,
+    layer: u32,
+) -> Vec<u8> {
+    use bevy_render::{
+        render_resource::{
+            BufferDescriptor, BufferUsages, CommandEncoderDescriptor, Extent3d, ImageCopyBuffer,
+            ImageCopyTexture, ImageDataLayout, Origin3d, TextureAspect,
+        },
+        renderer::{RenderDevice, RenderQueue},
+    };
+
+    let device = app.world().resource::<RenderDevice>();
+    let queue = app.world().resource::<RenderQueue>();
+    let size = texture
+        .size()
+        .mip_level_size(mip_level, texture.dimension());
+    let row_bytes = size.width as usize * texture.format().block_copy_size(None).unwrap() as usize;
+    let padded_row_bytes = RenderDevice::align_copy_bytes_per_row(row_bytes);
+
+    let staging = device.create_buffer(&BufferDescriptor {
+        label: Some("gigs_test_readback"),
+        size: (padded_row_bytes * size.height as usize) as u64,
+        usage: BufferUsages::COPY_DST | BufferUsages::MAP_READ,
+        mapped_at_creation: false,
+    });
+    let mut encoder = device.create_command_encoder(&CommandEncoderDescriptor::default());
+    encoder.copy_texture_to_buffer(
+        ImageCopyTexture {
+            texture,
+            mip_level,
+            origin: Origin3d {
+                x: 0,
+                y: 0,
+                z: layer,
+            },
+            aspect: TextureAspect::All,
+        },
+        ImageCopyBuffer {
+            buffer: &staging,
+            layout: ImageDataLayout {
+                offset: 0,
+                bytes_per_row: Some(padded_row_bytes as u32),
+                rows_per_image: None,
+            },
+        },
+        Extent3d {
+            depth_or_array_layers: 1,
+            ..size
+        },
+    );
+    queue.submit([encoder.finish()]);
+
+    let bytes = read_mapped(app, &staging);
+    bytes
+        .chunks(padded_row_bytes)
+        .flat_map(|row| &row[..row_bytes])
+        .copied()
+        .collect()
+}
+
+/// Maps a staging buffer for reading, and waits for its contents.
+#[cfg(test)]
+fn read_mapped(app: &App, staging: &bevy_render::render_resource::Buffer) -> Vec<u8> {
+    use bevy_render::{
+        render_resource::{Maintain, MapMode},
+        renderer::RenderDevice,
+    };
+
+    let slice = staging.slice(..);
+    slice.map_async(MapMode::Read, |result| result.unwrap());
+    app.world().resource::<RenderDevice>().poll(Maintain::Wait);
+    let bytes = slice.get_mapped_range().to_vec();
+    staging.unmap();
+    bytes
+}
+
+#[cfg(test)]
+mod test {
+    use core::sync::atomic::{AtomicBool, Ordering};
+
+    use bevy_app::{App, Update};
+    use bevy_ecs::{
+        component::Component,
+        entity::Entity,
+        query::With,
+        system::{Commands, Query},
+        world::World,
+    };
+
+    use crate::{
+        input::JobInputItem,
+        meta::{JobMarker, JobPriority, JobTask},
+        GraphicsJob, JobComplete, JobError, JobRunContext, SpecializedGraphicsJobPlugin,
+    };
+
+    use super::{headless_test_app, InitGraphicsJobExt, RunGraphicsJobExt};
+
+    #[derive(Component)]
+    struct InstantJob(Result<(), JobError>);
+
+    fn complete_instant_jobs(jobs: Query<(Entity, &InstantJob)>, mut commands: Commands) {
+        for (entity, job) in &jobs {
+            commands.trigger_targets(JobComplete(job.0), entity);
+            commands.entity(entity).despawn();
+        }
+    }
+
+    #[test]
+    fn blocking_returns_job_result() {
+        let mut app = App::new();
+        app.add_systems(Update, complete_instant_jobs);
+
+        assert_eq!(app.run_job_blocking(InstantJob(Ok(()))), Ok(()));
+        assert_eq!(
+            app.run_job_blocking(InstantJob(Err(JobError::ExecutionFailed))),
+            Err(JobError::ExecutionFailed)
+        );
+    }
+
+    #[derive(Component)]
+    struct NeverJob;
+
+    #[test]
+    fn blocking_times_out() {
+        let mut app = App::new();
+        assert_eq!(app.run_job_blocking(NeverJob), Err(JobError::TimedOut));
+        assert_eq!(
+            app.world_mut()
+                .query_filtered::<Entity, With<NeverJob>>()
+                .iter(app.world())
+                .count(),
+            0
+        );
+    }
+
+    #[test]
+    fn flushing_a_task_leaves_other_tasks_pending() {
+        let mut app = App::new();
+        app.add_systems(Update, complete_instant_jobs);
+
+        let baked = app
+            .world_mut()
+            .spawn((InstantJob(Ok(())), JobTask(0), JobMarker))
+            .id();
+        let failed = app
+            .world_mut()
+            .spawn((
+                InstantJob(Err(JobError::ExecutionFailed)),
+                JobTask(0),
+                JobMarker,
+            ))
+            .id();
+        let pending = app
+            .world_mut()
+            .spawn((NeverJob, JobTask(1), JobMarker))
+            .id();
+
+        let mut completions = app.flush_task(JobTask(0));
+        completions.sort_by_key(|(job, _)| *job);
+        assert_eq!(
+            completions,
+            vec![(baked, Ok(())), (failed, Err(JobError::ExecutionFailed))]
+        );
+        assert!(app.world().entities().contains(pending));
+
+        assert_eq!(
+            app.flush_task(JobTask(1)),
+            vec![(pending, Err(JobError::TimedOut))]
+        );
+        assert!(!app.world().entities().contains(pending));
+    }
+
+    #[derive(Clone, Component)]
+    struct DefaultedJob;
+
+    #[derive(Component, PartialEq, Eq, Debug)]
+    struct PipelineKey(u32);
+
+    impl GraphicsJob for DefaultedJob {
+        type In = ();
+
+        fn run(
+            &self,
+            _world: &World,
+            _context: &mut JobRunContext,
+            (): JobInputItem<Self, Self::In>,
+        ) -> Result<(), JobError> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn defaults_are_inserted_unless_overridden() {
+        let mut app = App::new();
+        app.add_plugins(
+            SpecializedGraphicsJobPlugin::<DefaultedJob>::default()
+                .with_default(JobPriority::non_critical::<4>)
+                .with_default(|| PipelineKey(7)),
+        );
+
+        let bare = app.world_mut().spawn(DefaultedJob).id();
+        let bare = app.world().entity(bare);
+        assert!(bare.contains::<JobMarker>());
+        assert_eq!(bare.get(), Some(&JobPriority::non_critical::<4>()));
+        assert_eq!(bare.get(), Some(&PipelineKey(7)));
+
+        let overridden = app
+            .world_mut()
+            .spawn((DefaultedJob, JobPriority::critical(), PipelineKey(1)))
+            .id();
+        let overridden = app.world().entity(overridden);
+        assert_eq!(overridden.get(), Some(&JobPriority::critical()));
+        assert_eq!(overridden.get(), Some(&PipelineKey(1)));
+    }
+
+    static BARE_JOB_RAN: AtomicBool = AtomicBool::new(false);
+
+    #[derive(Clone, Component)]
+    struct BareJob;
+
+    impl GraphicsJob for BareJob {
+        type In = ();
+
+        fn run(
+            &self,
+            _world: &World,
+            _context: &mut JobRunContext,
+            (): JobInputItem<Self, Self::In>,
+        ) -> Result<(), JobError> {
+            BARE_JOB_RAN.store(true, Ordering::Relaxed);
+            Ok(())
+        }
+    }
+
+    /// Runs a job spawned without any metadata on the real renderer.
+    #[test]
+    fn bare_jobs_run_to_completion() {
+        let mut app = headless_test_app();
+        app.init_graphics_job::<BareJob>();
+
+        assert_eq!(app.run_job_blocking(BareJob), Ok(()));
+        assert!(BARE_JOB_RAN.load(Ordering::Relaxed));
+    }
+}
@@ -1,14 +1,16 @@
-use core::marker::PhantomData;
+use core::{any::type_name, marker::PhantomData};
 
 use bevy_app::{App, Plugin};
 use bevy_ecs::{
+    change_detection::DetectChangesMut,
     component::Component,
     entity::Entity,
     query::{Changed, QueryItem, ReadOnlyQueryData, WorldQuery},
-    schedule::IntoSystemConfigs,
+    schedule::{IntoSystemConfigs, IntoSystemSetConfigs, Schedule, SystemSet},
     system::{lifetimeless::Read, Commands, Query, Res, ResMut, Resource, StaticSystemParam},
     world::{FromWorld, World},
 };
+use bevy_math::UVec3;
 use bevy_utils::all_tuples;
 
 use bevy_render::{
@@ -24,10 +26,94 @@ use bevy_render::{
     Render, RenderApp, RenderSet,
 };
 
+use crate::device::{add_device_reset, remove_all};
+use crate::runner::JobReady;
+use crate::JobSet;
+
 use super::GraphicsJob;
 
+mod asset;
+mod camera_view;
+mod cascade_config;
+mod depth_copy;
+mod dynamic_offset;
+mod fallback;
+mod file_output;
+mod frame_info;
+mod frame_uniform;
+mod gbuffer;
+mod global_bind_group;
+mod image_array;
+mod image_view;
+mod indirect_parameters;
+mod irradiance_volume;
+mod lightmap;
+mod limits;
+mod local;
+mod material;
+mod mesh_slice;
+mod offscreen;
+mod oit;
+mod output;
+mod readback;
+mod render_bundle;
+mod render_phase;
+mod resource_buffer;
+mod sampler;
+mod seed;
+mod shader_defs;
+mod skybox;
+mod storage_texture;
+mod target_format;
+mod temporal;
+mod view;
+mod view_matrices;
+mod view_params;
+mod view_target;
+mod volumetric_fog;
+
+pub use asset::*;
+pub use cascade_config::*;
+pub use depth_copy::*;
+pub use dynamic_offset::*;
+pub use fallback::*;
+pub use file_output::*;
+pub use frame_info::*;
+pub use frame_uniform::*;
+pub use gbuffer::*;
+pub use global_bind_group::*;
+pub use image_array::*;
+pub use image_view::*;
+pub use indirect_parameters::*;
+pub use irradiance_volume::*;
+pub use lightmap::*;
+pub use limits::*;
+pub use local::*;
+pub use material::*;
+pub use mesh_slice::*;
+pub use offscreen::*;
+pub use oit::*;
+pub use output::*;
+pub use readback::*;
+pub use render_bundle::*;
+pub use render_phase::*;
+pub use resource_buffer::*;
+pub use sampler::*;
+pub use seed::*;
+pub use shader_defs::*;
+pub use skybox::*;
+pub use storage_texture::*;
+pub use target_format::*;
+pub use temporal::*;
+pub use view::*;
+pub use view_matrices::*;
+pub use view_params::*;
+pub use view_target::*;
+pub use volumetric_fog::*;
+
 /// The status of a job input
 #[derive(Copy, Clone, PartialEq, Eq, Debug)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
 pub enum JobInputStatus {
     /// Signals that input is ready
     Ready,
@@ -49,6 +135,20 @@ impl JobInputStatus {
 
 pub type JobInputItem<'a, J, In> = <In as JobInput<J>>::Item<'a>;
 
+/// The systems preparing the data of a [`JobInput`] in the render world, like the bind
+/// groups of [`JobAsBindGroup`]. Each input's set is ordered after the [`RenderSet`]
+/// it declares with [`JobInput::PREPARE_AFTER`], and before [`JobSet::Check`], so that
+/// its status is never checked against stale data.
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug, SystemSet)]
+pub struct JobPrepareSet(&'static str);
+
+impl JobPrepareSet {
+    /// The set of the systems preparing the input `I`.
+    pub fn of<I: ?Sized>() -> Self {
+        Self(type_name::<I>())
+    }
+}
+
 /// A trait describing input to a graphics job.
 ///
 /// This trait may be thought of as similar to [`QueryData`](bevy_ecs::query::QueryData),
@@ -63,19 +163,56 @@ pub trait JobInput<J: GraphicsJob> {
     type Data: ReadOnlyQueryData;
     type Item<'a>;
 
+    /// the [`RenderSet`] whose data this input is prepared from, like
+    /// [`RenderSet::PrepareResources`] for an input whose bind groups bind buffers
+    /// written there. The input's [`JobPrepareSet`] is ordered after it, and always
+    /// before [`JobSet::Check`] reads the input's [`status`](JobInput::status), so it
+    /// must be a set that runs before then, like any of the [`RenderSet::Prepare`] sets.
+    const PREPARE_AFTER: Option<RenderSet> = None;
+
     /// a plugin to register on the app, to setup behind the scenes processing
     /// for this implementor.
     fn plugin() -> impl Plugin {
         |_: &mut App| {}
     }
 
+    /// orders the [`JobPrepareSet`] of this input after [`PREPARE_AFTER`](JobInput::PREPARE_AFTER).
+    /// Tuples and wrapping inputs, like [`JobFallback`], order the sets of the inputs
+    /// they contain instead.
+    fn configure_prepare_sets(schedule: &mut Schedule) {
+        let set = JobPrepareSet::of::<Self>().before(JobSet::Check);
+        match Self::PREPARE_AFTER {
+            Some(after) => schedule.configure_sets(set.after(after)),
+            None => schedule.configure_sets(set),
+        };
+    }
+
     /// the status of the resources needed by this job input. For example,
     /// an implementor may check the status of a queued pipeline, and wait
     /// until it's done compiling.
     fn status(data: QueryItem<Self::Data>, world: &World) -> JobInputStatus;
 
+    /// appends the status of this input to `statuses`, labeled by its type name.
+    /// Tuples report the status of each of their elements separately, which is
+    /// used by [`job_input_statuses`](crate::job_input_statuses) to diagnose jobs
+    /// that never become ready.
+    fn statuses(
+        data: QueryItem<Self::Data>,
+        world: &World,
+        statuses: &mut Vec<(&'static str, JobInputStatus)>,
+    ) {
+        statuses.push((type_name::<Self>(), Self::status(data, world)));
+    }
+
     /// returns the actual job input item.
     fn get<'a>(data: QueryItem<'a, Self::Data>, world: &'a World) -> Self::Item<'a>;
+
+    /// whether the item returned by [`get`](JobInput::get) is a placeholder standing
+    /// in for a resource that isn't ready yet, like with [`JobFallback`]. A job that
+    /// ran with any placeholders is queued again, to run once its inputs are ready.
+    fn is_fallback(_data: QueryItem<Self::Data>, _world: &World) -> bool {
+        false
+    }
 }
 
 macro_rules! impl_job_input_tuple {
@@ -91,6 +228,11 @@ macro_rules! impl_job_input_tuple {
                 }
             }
 
+            #[allow(unused_variables)]
+            fn configure_prepare_sets(schedule: &mut Schedule) {
+                $(<$T as JobInput<J>>::configure_prepare_sets(schedule);)*
+            }
+
             #[allow(unused_variables)]
             fn status(data: QueryItem<Self::Data>, world: &World) -> JobInputStatus {
                 let ($($t,)*) = data;
@@ -98,11 +240,27 @@ macro_rules! impl_job_input_tuple {
                     $(.combine(<$T as JobInput<J>>::status($t, world)))*
             }
 
+            #[allow(unused_variables)]
+            fn statuses(
+                data: QueryItem<Self::Data>,
+                world: &World,
+                statuses: &mut Vec<(&'static str, JobInputStatus)>,
+            ) {
+                let ($($t,)*) = data;
+                $(<$T as JobInput<J>>::statuses($t, world, statuses);)*
+            }
+
             #[allow(unused_variables, clippy::unused_unit)]
             fn get<'a>(data: QueryItem<'a, Self::Data>, world: &'a World) -> Self::Item<'a> {
                 let ($($t,)*) = data;
                 ($(<$T as JobInput<J>>::get($t, world),)*)
             }
+
+            #[allow(unused_variables)]
+            fn is_fallback(data: QueryItem<Self::Data>, world: &World) -> bool {
+                let ($($t,)*) = data;
+                false $(|| <$T as JobInput<J>>::is_fallback($t, world))*
+            }
         }
     }
 }
@@ -172,6 +330,8 @@ impl<J: GraphicsJob + AsBindGroup> JobInput<J> for JobAsBindGroup {
 
     type Item<'a> = &'a PreparedBindGroup<<J as AsBindGroup>::Data>;
 
+    const PREPARE_AFTER: Option<RenderSet> = Some(RenderSet::PrepareResources);
+
     fn plugin() -> impl Plugin {
         JobAsBindGroupPlugin::<J>(PhantomData)
     }
@@ -195,9 +355,11 @@ impl<J: GraphicsJob + AsBindGroup> Plugin for JobAsBindGroupPlugin<J> {
         if let Some(render_app) = app.get_sub_app_mut(RenderApp) {
             render_app.add_systems(
                 Render,
-                prepare_job_bind_group::<J>.in_set(RenderSet::PrepareBindGroups),
+                prepare_job_bind_group::<J>.in_set(JobPrepareSet::of::<JobAsBindGroup>()),
             );
         }
+
+        add_device_reset(app, reset_job_bind_groups::<J>);
     }
 
     fn finish(&self, app: &mut App) {
@@ -240,6 +402,13 @@ fn prepare_job_bind_group<J: GraphicsJob + AsBindGroup>(
     }
 }
 
+/// Recreates the bind group layout and drops prepared bind groups after the device is lost.
+fn reset_job_bind_groups<J: GraphicsJob + AsBindGroup>(world: &mut World) {
+    let layout = JobBindGroupLayout::<J>::from_world(world);
+    world.insert_resource(layout);
+    remove_all::<PreparedJobBindGroup<J>>(world);
+}
+
 #[doc(hidden)]
 pub trait SpecializedJobRenderPipeline:
     SpecializedRenderPipeline<Key: Send + Sync> + Resource + FromWorld
@@ -331,9 +500,11 @@ impl<P: SpecializedJobRenderPipeline> Plugin for JobRenderPipelinePlugin<P> {
                 .init_resource::<SpecializedRenderPipelines<P>>()
                 .add_systems(
                     Render,
-                    queue_job_render_pipelines::<P>.in_set(RenderSet::Queue),
+                    queue_job_render_pipelines::<P>.in_set(JobSet::QueuePipelines),
                 );
         }
+
+        add_device_reset(app, reset_job_render_pipelines::<P>);
     }
 
     fn finish(&self, app: &mut App) {
@@ -343,18 +514,198 @@ impl<P: SpecializedJobRenderPipeline> Plugin for JobRenderPipelinePlugin<P> {
     }
 }
 
-fn queue_job_render_pipelines<P: SpecializedJobRenderPipeline>(
-    job_pipelines: Query<(Entity, &JobRenderPipeline<P>), Changed<JobRenderPipeline<P>>>,
+/// Specializes the pipeline of each job whose key changed. Jobs specializing the same base
+/// pipeline with the same key share one [`CachedRenderPipelineId`], since
+/// [`SpecializedRenderPipelines`] caches by key no matter the job type. Extraction
+/// reinserts the key every frame, so the id is only reinserted if it actually changed.
+///
+/// A job whose key changed after it became ready, for example from [`JobShaderDefs`],
+/// goes back to waiting, so it doesn't run until the new pipeline is compiled.
+pub(crate) fn queue_job_render_pipelines<P: SpecializedJobRenderPipeline>(
+    job_pipelines: Query<
+        (
+            Entity,
+            &JobRenderPipeline<P>,
+            Option<&JobRenderPipelineId<P>>,
+        ),
+        Changed<JobRenderPipeline<P>>,
+    >,
     pipeline_cache: Res<PipelineCache>,
     base_pipeline: Res<P>,
     mut specializer: ResMut<SpecializedRenderPipelines<P>>,
     mut commands: Commands,
 ) {
-    for (entity, job_pipeline) in &job_pipelines {
+    for (entity, job_pipeline, current) in &job_pipelines {
         let id = specializer.specialize(&pipeline_cache, &base_pipeline, job_pipeline.0.clone());
-        commands
-            .entity(entity)
-            .insert(JobRenderPipelineId::<P>(id, PhantomData));
+        if current.is_some_and(|current| current.0 == id) {
+            continue;
+        }
+        let mut entity = commands.entity(entity);
+        entity.insert(JobRenderPipelineId::<P>(id, PhantomData));
+        if current.is_some() {
+            entity.remove::<JobReady>();
+        }
+    }
+}
+
+/// Recreates the base pipeline and re-specializes every job's pipeline after the
+/// device is lost, since the cached ids refer to pipelines created on the old device.
+fn reset_job_render_pipelines<P: SpecializedJobRenderPipeline>(world: &mut World) {
+    let base_pipeline = P::from_world(world);
+    world.insert_resource(base_pipeline);
+    world.insert_resource(SpecializedRenderPipelines::<P>::default());
+
+    remove_all::<JobRenderPipelineId<P>>(world);
+    for mut job_pipeline in world.query::<&mut JobRenderPipeline<P>>().iter_mut(world) {
+        job_pipeline.set_changed();
+    }
+}
+
+/// A [`JobInput`] type that sets up several [`RenderPipeline`]s from the same base
+/// pipeline, for multi-pass effects like a blur with separate horizontal and vertical
+/// passes. The job waits until every pipeline is compiled, and the pipelines are
+/// provided in the same order as their keys.
+#[derive(Component)]
+pub struct JobRenderPipelines<P: SpecializedJobRenderPipeline, const N: usize>(pub [P::Key; N]);
+
+impl<J: GraphicsJob, P: SpecializedJobRenderPipeline, const N: usize> JobInput<J>
+    for JobRenderPipelines<P, N>
+{
+    type Data = Option<Read<JobRenderPipelineIds<P, N>>>;
+
+    type Item<'a> = [&'a RenderPipeline; N];
+
+    fn plugin() -> impl Plugin {
+        |app: &mut App| {
+            if !app.is_plugin_added::<JobRenderPipelinesPlugin<P, N>>() {
+                app.add_plugins(JobRenderPipelinesPlugin::<P, N>(PhantomData));
+            }
+        }
+    }
+
+    fn status(data: QueryItem<Self::Data>, world: &World) -> JobInputStatus {
+        let Some(JobRenderPipelineIds(ids, _)) = data else {
+            return JobInputStatus::Wait;
+        };
+        let pipeline_cache = world.resource::<PipelineCache>();
+        if ids.iter().all(|id| {
+            matches!(
+                pipeline_cache.get_render_pipeline_state(*id),
+                CachedPipelineState::Ok(_)
+            )
+        }) {
+            JobInputStatus::Ready
+        } else {
+            JobInputStatus::Wait
+        }
+    }
+
+    fn get<'a>(data: QueryItem<'a, Self::Data>, world: &'a World) -> Self::Item<'a> {
+        let pipeline_cache = world.resource::<PipelineCache>();
+        data.unwrap().0.map(|id| {
+            pipeline_cache
+                .get_render_pipeline(id)
+                .expect("pipeline should be ready by this point")
+        })
+    }
+}
+
+impl<P: SpecializedJobRenderPipeline, const N: usize> Clone for JobRenderPipelines<P, N> {
+    fn clone(&self) -> Self {
+        Self(self.0.clone())
+    }
+}
+
+impl<P: SpecializedJobRenderPipeline, const N: usize> ExtractComponent
+    for JobRenderPipelines<P, N>
+{
+    type QueryData = Read<JobRenderPipelines<P, N>>;
+
+    type QueryFilter = ();
+
+    type Out = JobRenderPipelines<P, N>;
+
+    fn extract_component(item: QueryItem<'_, Self::QueryData>) -> Option<Self::Out> {
+        Some(item.clone())
+    }
+}
+
+#[derive(Component)]
+#[doc(hidden)]
+pub struct JobRenderPipelineIds<P: SpecializedJobRenderPipeline, const N: usize>(
+    [CachedRenderPipelineId; N],
+    PhantomData<P>,
+);
+
+struct JobRenderPipelinesPlugin<P: SpecializedJobRenderPipeline, const N: usize>(PhantomData<P>);
+
+impl<P: SpecializedJobRenderPipeline, const N: usize> Plugin for JobRenderPipelinesPlugin<P, N> {
+    fn build(&self, app: &mut App) {
+        app.add_plugins(ExtractComponentPlugin::<JobRenderPipelines<P, N>>::default());
+
+        if let Some(render_app) = app.get_sub_app_mut(RenderApp) {
+            render_app
+                .init_resource::<SpecializedRenderPipelines<P>>()
+                .add_systems(
+                    Render,
+                    queue_job_render_pipeline_sets::<P, N>.in_set(JobSet::QueuePipelines),
+                );
+        }
+
+        add_device_reset(app, reset_job_render_pipeline_sets::<P, N>);
+    }
+
+    fn finish(&self, app: &mut App) {
+        if let Some(render_app) = app.get_sub_app_mut(RenderApp) {
+            render_app.init_resource::<P>();
+        }
+    }
+}
+
+fn queue_job_render_pipeline_sets<P: SpecializedJobRenderPipeline, const N: usize>(
+    job_pipelines: Query<
+        (
+            Entity,
+            &JobRenderPipelines<P, N>,
+            Option<&JobRenderPipelineIds<P, N>>,
+        ),
+        Changed<JobRenderPipelines<P, N>>,
+    >,
+    pipeline_cache: Res<PipelineCache>,
+    base_pipeline: Res<P>,
+    mut specializer: ResMut<SpecializedRenderPipelines<P>>,
+    mut commands: Commands,
+) {
+    for (entity, job_pipelines, current) in &job_pipelines {
+        let ids = job_pipelines
+            .0
+            .clone()
+            .map(|key| specializer.specialize(&pipeline_cache, &base_pipeline, key));
+        if current.is_some_and(|current| current.0 == ids) {
+            continue;
+        }
+        let mut entity = commands.entity(entity);
+        entity.insert(JobRenderPipelineIds::<P, N>(ids, PhantomData));
+        if current.is_some() {
+            entity.remove::<JobReady>();
+        }
+    }
+}
+
+/// Like [`reset_job_render_pipelines`], for jobs with a set of pipelines.
+fn reset_job_render_pipeline_sets<P: SpecializedJobRenderPipeline, const N: usize>(
+    world: &mut World,
+) {
+    let base_pipeline = P::from_world(world);
+    world.insert_resource(base_pipeline);
+    world.insert_resource(SpecializedRenderPipelines::<P>::default());
+
+    remove_all::<JobRenderPipelineIds<P, N>>(world);
+    for mut job_pipelines in world
+        .query::<&mut JobRenderPipelines<P, N>>()
+        .iter_mut(world)
+    {
+        job_pipelines.set_changed();
     }
 }
 
@@ -370,12 +721,36 @@ impl<P: SpecializedComputePipeline<Key: Send + Sync> + Resource + FromWorld>
 
 /// A [`JobInput`] type that sets up a [`ComputePipeline`] for a job. This component must be
 /// added to a job as it is spawned in order to setup the pipeline.
+///
+/// The pipeline's workgroup size can be declared with
+/// [`with_workgroup_size`](Self::with_workgroup_size), so that jobs can size their
+/// dispatches with [`JobWorkgroupSize`] instead of hardcoding the divisor. It isn't
+/// read from the shader, so it must match the `@workgroup_size` of the pipeline's
+/// entry point.
 #[derive(Component)]
-pub struct JobComputePipeline<P: SpecializedJobComputePipeline>(P::Key);
+pub struct JobComputePipeline<P: SpecializedJobComputePipeline> {
+    key: P::Key,
+    workgroup_size: Option<UVec3>,
+}
+
+impl<P: SpecializedJobComputePipeline> JobComputePipeline<P> {
+    pub fn new(key: P::Key) -> Self {
+        Self {
+            key,
+            workgroup_size: None,
+        }
+    }
+
+    /// Declares the workgroup size of the pipeline specialized for this key.
+    pub fn with_workgroup_size(mut self, workgroup_size: UVec3) -> Self {
+        self.workgroup_size = Some(workgroup_size);
+        self
+    }
+}
 
 impl<P: SpecializedJobComputePipeline<Key: Default>> Default for JobComputePipeline<P> {
     fn default() -> Self {
-        Self(Default::default())
+        Self::new(Default::default())
     }
 }
 
@@ -385,11 +760,11 @@ impl<J: GraphicsJob, P: SpecializedJobComputePipeline> JobInput<J> for JobComput
     type Item<'a> = &'a ComputePipeline;
 
     fn plugin() -> impl Plugin {
-        JobComputePipelinePlugin::<P>(PhantomData)
+        compute_pipeline_plugin::<P>
     }
 
     fn status(data: QueryItem<Self::Data>, world: &World) -> JobInputStatus {
-        let Some(JobComputePipelineId(id, _)) = data else {
+        let Some(JobComputePipelineId(id, ..)) = data else {
             return JobInputStatus::Wait;
         };
         if matches!(
@@ -413,9 +788,59 @@ impl<J: GraphicsJob, P: SpecializedJobComputePipeline> JobInput<J> for JobComput
     }
 }
 
+/// A [`JobInput`] providing the workgroup size declared for a job's
+/// [`JobComputePipeline`] with [`with_workgroup_size`](JobComputePipeline::with_workgroup_size),
+/// for sizing its dispatches. The job must also have the [`JobComputePipeline`] as
+/// an input, and fails if no workgroup size was declared.
+pub struct JobWorkgroupSize<P: SpecializedJobComputePipeline>(PhantomData<P>);
+
+/// The workgroup size provided by [`JobWorkgroupSize`].
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub struct JobWorkgroupSizeItem(pub UVec3);
+
+impl JobWorkgroupSizeItem {
+    /// The number of workgroups to dispatch to cover `problem_size` invocations
+    /// in each dimension.
+    pub fn workgroups(&self, problem_size: UVec3) -> UVec3 {
+        UVec3::new(
+            problem_size.x.div_ceil(self.0.x),
+            problem_size.y.div_ceil(self.0.y),
+            problem_size.z.div_ceil(self.0.z),
+        )
+    }
+}
+
+impl<J: GraphicsJob, P: SpecializedJobComputePipeline> JobInput<J> for JobWorkgroupSize<P> {
+    type Data = Read<JobComputePipeline<P>>;
+
+    type Item<'a> = JobWorkgroupSizeItem;
+
+    fn plugin() -> impl Plugin {
+        compute_pipeline_plugin::<P>
+    }
+
+    fn status(job_pipeline: QueryItem<Self::Data>, _world: &World) -> JobInputStatus {
+        match job_pipeline.workgroup_size {
+            Some(_) => JobInputStatus::Ready,
+            None => JobInputStatus::Fail,
+        }
+    }
+
+    fn get<'a>(job_pipeline: QueryItem<'a, Self::Data>, _world: &'a World) -> Self::Item<'a> {
+        JobWorkgroupSizeItem(
+            job_pipeline
+                .workgroup_size
+                .expect("workgroup size should be declared by this point"),
+        )
+    }
+}
+
 impl<P: SpecializedJobComputePipeline> Clone for JobComputePipeline<P> {
     fn clone(&self) -> Self {
-        Self(self.0.clone())
+        Self {
+            key: self.key.clone(),
+            workgroup_size: self.workgroup_size,
+        }
     }
 }
 
@@ -438,6 +863,13 @@ pub struct JobComputePipelineId<P: SpecializedJobComputePipeline>(
     PhantomData<P>,
 );
 
+/// Sets up [`JobComputePipeline`] and [`JobWorkgroupSize`], which share a plugin.
+fn compute_pipeline_plugin<P: SpecializedJobComputePipeline>(app: &mut App) {
+    if !app.is_plugin_added::<JobComputePipelinePlugin<P>>() {
+        app.add_plugins(JobComputePipelinePlugin::<P>(PhantomData));
+    }
+}
+
 struct JobComputePipelinePlugin<P: SpecializedJobComputePipeline>(PhantomData<P>);
 
 impl<P: SpecializedJobComputePipeline> Plugin for JobComputePipelinePlugin<P> {
@@ -449,9 +881,11 @@ impl<P: SpecializedJobComputePipeline> Plugin for JobComputePipelinePlugin<P> {
                 .init_resource::<SpecializedComputePipelines<P>>()
                 .add_systems(
                     Render,
-                    queue_job_compute_pipelines::<P>.in_set(RenderSet::Queue),
+                    queue_job_compute_pipelines::<P>.in_set(JobSet::QueuePipelines),
                 );
         }
+
+        add_device_reset(app, reset_job_compute_pipelines::<P>);
     }
 
     fn finish(&self, app: &mut App) {
@@ -461,17 +895,59 @@ impl<P: SpecializedJobComputePipeline> Plugin for JobComputePipelinePlugin<P> {
     }
 }
 
+/// Like [`queue_job_render_pipelines`], for compute pipelines.
 fn queue_job_compute_pipelines<P: SpecializedJobComputePipeline>(
-    job_pipelines: Query<(Entity, &JobComputePipeline<P>), Changed<JobComputePipeline<P>>>,
+    job_pipelines: Query<
+        (
+            Entity,
+            &JobComputePipeline<P>,
+            Option<&JobComputePipelineId<P>>,
+        ),
+        Changed<JobComputePipeline<P>>,
+    >,
     pipeline_cache: Res<PipelineCache>,
     base_pipeline: Res<P>,
     mut specializer: ResMut<SpecializedComputePipelines<P>>,
     mut commands: Commands,
 ) {
-    for (entity, job_pipeline) in &job_pipelines {
-        let id = specializer.specialize(&pipeline_cache, &base_pipeline, job_pipeline.0.clone());
-        commands
-            .entity(entity)
-            .insert(JobComputePipelineId::<P>(id, PhantomData));
+    for (entity, job_pipeline, current) in &job_pipelines {
+        let id = specializer.specialize(&pipeline_cache, &base_pipeline, job_pipeline.key.clone());
+        if current.is_some_and(|current| current.0 == id) {
+            continue;
+        }
+        let mut entity = commands.entity(entity);
+        entity.insert(JobComputePipelineId::<P>(id, PhantomData));
+        if current.is_some() {
+            entity.remove::<JobReady>();
+        }
+    }
+}
+
+/// Recreates the base pipeline and re-specializes every job's pipeline after the
+/// device is lost, since the cached ids refer to pipelines created on the old device.
+fn reset_job_compute_pipelines<P: SpecializedJobComputePipeline>(world: &mut World) {
+    let base_pipeline = P::from_world(world);
+    world.insert_resource(base_pipeline);
+    world.insert_resource(SpecializedComputePipelines::<P>::default());
+
+    remove_all::<JobComputePipelineId<P>>(world);
+    for mut job_pipeline in world.query::<&mut JobComputePipeline<P>>().iter_mut(world) {
+        job_pipeline.set_changed();
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use bevy_math::UVec3;
+
+    use super::JobWorkgroupSizeItem;
+
+    #[test]
+    fn workgroups_cover_the_problem_size() {
+        let workgroup_size = JobWorkgroupSizeItem(UVec3::new(8, 8, 1));
+        assert_eq!(
+            workgroup_size.workgroups(UVec3::new(17, 8, 3)),
+            UVec3::new(3, 1, 3)
+        );
     }
 }
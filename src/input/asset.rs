@@ -0,0 +1,162 @@
+use bevy_app::{App, Plugin};
+use bevy_asset::{Asset, Assets, Handle};
+use bevy_ecs::{
+    component::Component,
+    entity::Entity,
+    event::{Event, EventWriter, Events},
+    observer::Trigger,
+    query::QueryItem,
+    system::{Query, ResMut},
+    world::World,
+};
+
+use crate::{GraphicsJob, JobComplete, JobResults};
+
+use super::{JobInput, JobInputStatus};
+
+/// A [`JobInput`] for jobs whose output is an [`Asset`], like a baked [`Image`](bevy_image::Image)
+/// or [`Mesh`](bevy_render::mesh::Mesh), which the rest of the app references by its
+/// [`Handle`].
+///
+/// The handle is created up front, usually with a placeholder asset from
+/// [`reserve`](Self::reserve), so gameplay can reference it before the bake finishes.
+/// The job builds the asset in the render world and sets it as its result with
+/// [`JobRunContext::set_result`](crate::JobRunContext::set_result). Once the job
+/// completes successfully, the asset replaces the placeholder in [`Assets<A>`], and a
+/// [`JobAssetPublished<A>`] event is sent with its handle. If the job fails, or doesn't
+/// set an `A` as its result, the placeholder is kept.
+#[derive(Clone, Component, Debug)]
+pub struct JobAsset<A: Asset> {
+    pub handle: Handle<A>,
+}
+
+impl<A: Asset> JobAsset<A> {
+    /// Publishes the job's asset to an existing handle.
+    pub fn new(handle: Handle<A>) -> Self {
+        Self { handle }
+    }
+
+    /// Adds a placeholder asset, to be replaced by the job's asset once it completes.
+    pub fn reserve(assets: &mut Assets<A>, placeholder: A) -> Self {
+        Self::new(assets.add(placeholder))
+    }
+}
+
+/// Sent once a job with a [`JobAsset<A>`] has published its asset.
+#[derive(Event, Clone, Debug)]
+pub struct JobAssetPublished<A: Asset> {
+    /// The job's main world entity.
+    pub job: Entity,
+    pub handle: Handle<A>,
+}
+
+impl<J: GraphicsJob, A: Asset> JobInput<J> for JobAsset<A> {
+    type Data = ();
+
+    type Item<'a> = ();
+
+    fn plugin() -> impl Plugin {
+        |app: &mut App| {
+            if !app
+                .world()
+                .contains_resource::<Events<JobAssetPublished<A>>>()
+            {
+                app.add_event::<JobAssetPublished<A>>()
+                    .add_observer(publish_job_asset::<A>);
+            }
+        }
+    }
+
+    fn status((): QueryItem<Self::Data>, _world: &World) -> JobInputStatus {
+        JobInputStatus::Ready
+    }
+
+    fn get<'a>((): QueryItem<'a, Self::Data>, _world: &'a World) -> Self::Item<'a> {}
+}
+
+/// Moves the asset a job set as its result into [`Assets<A>`] once it completes. Results
+/// are stored before [`JobComplete`] is triggered, and the job's entity is despawned
+/// after, so both are still around.
+fn publish_job_asset<A: Asset>(
+    trigger: Trigger<JobComplete>,
+    jobs: Query<&JobAsset<A>>,
+    mut results: ResMut<JobResults>,
+    mut assets: ResMut<Assets<A>>,
+    mut published: EventWriter<JobAssetPublished<A>>,
+) {
+    let job = trigger.entity();
+    let (Ok(()), Ok(output)) = (trigger.event().0, jobs.get(job)) else {
+        return;
+    };
+    let Some(asset) = results.take::<A>(job) else {
+        return;
+    };
+
+    assets.insert(&output.handle, asset);
+    published.send(JobAssetPublished {
+        job,
+        handle: output.handle.clone(),
+    });
+}
+
+#[cfg(test)]
+mod test {
+    use bevy_asset::{Assets, RenderAssetUsages};
+    use bevy_ecs::{event::Events, world::World};
+    use bevy_image::Image;
+    use bevy_render::render_resource::{Extent3d, TextureDimension, TextureFormat};
+
+    use crate::{JobComplete, JobError, JobResults};
+
+    use super::{publish_job_asset, JobAsset, JobAssetPublished};
+
+    fn image(size: u32) -> Image {
+        Image::new_fill(
+            Extent3d {
+                width: size,
+                height: size,
+                depth_or_array_layers: 1,
+            },
+            TextureDimension::D2,
+            &[0; 4],
+            TextureFormat::Rgba8Unorm,
+            RenderAssetUsages::default(),
+        )
+    }
+
+    #[test]
+    fn completed_jobs_replace_their_placeholder() {
+        let mut world = World::new();
+        world.init_resource::<JobResults>();
+        world.init_resource::<Assets<Image>>();
+        world.init_resource::<Events<JobAssetPublished<Image>>>();
+        world.add_observer(publish_job_asset::<Image>);
+
+        let output = JobAsset::reserve(&mut world.resource_mut::<Assets<Image>>(), image(1));
+        let failed = world.spawn(output.clone()).id();
+        let baked = world.spawn(output.clone()).id();
+        let mut results = world.resource_mut::<JobResults>();
+        results.insert(failed, Box::new(image(2)));
+        results.insert(baked, Box::new(image(4)));
+        let width = |world: &World| {
+            let images = world.resource::<Assets<Image>>();
+            images.get(&output.handle).map(Image::width)
+        };
+
+        world.trigger_targets(JobComplete(Err(JobError::ExecutionFailed)), failed);
+        assert_eq!(width(&world), Some(1));
+        assert!(world
+            .resource::<Events<JobAssetPublished<Image>>>()
+            .is_empty());
+
+        world.trigger_targets(JobComplete(Ok(())), baked);
+        assert_eq!(width(&world), Some(4));
+        assert!(!world.resource::<JobResults>().contains(baked));
+
+        let published = world.resource::<Events<JobAssetPublished<Image>>>();
+        let published = published.iter_current_update_events().collect::<Vec<_>>();
+        assert_eq!(published.len(), 1);
+        assert_eq!(published[0].job, baked);
+        assert_eq!(published[0].handle, output.handle);
+    }
+}
@@ -0,0 +1,161 @@
+use bevy_ecs::{
+    component::Component,
+    entity::Entity,
+    query::{QueryItem, ReadOnlyQueryData, With},
+    system::{Commands, Query},
+    world::{EntityRef, World},
+};
+use bevy_render::{sync_world::RenderEntity, view::ExtractedView, Extract};
+
+use crate::JobMarker;
+
+use super::JobInputStatus;
+
+/// A job input naming a main world camera, whose render world view is found by
+/// [`extract_camera_views`] and stored on the job as [`Self::Extracted`].
+pub(super) trait CameraViewInput: Component {
+    /// The component holding the camera's view on the job's render world entity.
+    type Extracted: Component;
+
+    /// Any other main world data of the job the input is extracted with.
+    type Data: ReadOnlyQueryData + 'static;
+
+    fn camera(&self) -> Entity;
+
+    fn extract(&self, view: Entity, data: QueryItem<Self::Data>) -> Self::Extracted;
+}
+
+/// Extracts the view of each job's camera once the camera has a render world entity.
+///
+/// A camera spawned in the same frame as its job may not be synced to the render world
+/// when the job is first extracted, so jobs are checked every frame until their view is
+/// found, rather than only when they're added.
+pub(super) fn extract_camera_views<I: CameraViewInput>(
+    jobs: Extract<Query<(RenderEntity, &I, I::Data), With<JobMarker>>>,
+    cameras: Extract<Query<&RenderEntity>>,
+    extracted: Query<(), With<I::Extracted>>,
+    mut commands: Commands,
+) {
+    for (render_entity, input, data) in &jobs {
+        if extracted.contains(render_entity) {
+            continue;
+        }
+        if let Ok(view) = cameras.get(input.camera()) {
+            commands
+                .entity(render_entity)
+                .insert(input.extract(view.id(), data));
+        }
+    }
+}
+
+/// Finds the render world view of a job's camera, if the camera is active.
+fn active_view(view: Option<Entity>, world: &World) -> Option<EntityRef<'_>> {
+    view.and_then(|view| world.get_entity(view).ok())
+        .filter(|view| view.contains::<ExtractedView>())
+}
+
+/// The status of an input reading data bevy only prepares for views with `C`. The input
+/// waits until the view is active, fails if the view doesn't have `C`, and is ready once
+/// `prepared` is.
+pub(super) fn view_feature_status<C: Component>(
+    view: Option<Entity>,
+    world: &World,
+    prepared: impl FnOnce(EntityRef) -> bool,
+) -> JobInputStatus {
+    match active_view(view, world) {
+        // the camera isn't active yet
+        None => JobInputStatus::Wait,
+        Some(view) if !view.contains::<C>() => JobInputStatus::Fail,
+        Some(view) if prepared(view) => JobInputStatus::Ready,
+        Some(_) => JobInputStatus::Wait,
+    }
+}
+
+/// An active view, for testing inputs that read a camera's view.
+#[cfg(test)]
+pub(super) fn test_view(hdr: bool) -> ExtractedView {
+    use bevy_math::{Mat4, UVec4};
+    use bevy_transform::components::GlobalTransform;
+
+    ExtractedView {
+        clip_from_view: Mat4::IDENTITY,
+        world_from_view: GlobalTransform::IDENTITY,
+        clip_from_world: None,
+        hdr,
+        viewport: UVec4::new(0, 0, 1280, 720),
+        color_grading: Default::default(),
+    }
+}
+
+/// Checks that an input waits while its job has no view, and while its view isn't
+/// active yet, and returns the view, which is made active by inserting a [`test_view`].
+#[cfg(test)]
+pub(super) fn assert_waits_for_view<E>(
+    world: &mut World,
+    status: impl Fn(Option<&E>, &World) -> JobInputStatus,
+    extracted: impl FnOnce(Entity) -> E,
+) -> (Entity, E) {
+    assert_eq!(status(None, world), JobInputStatus::Wait);
+
+    let view = world.spawn_empty().id();
+    let extracted = extracted(view);
+    assert_eq!(status(Some(&extracted), world), JobInputStatus::Wait);
+    (view, extracted)
+}
+
+#[cfg(test)]
+mod test {
+    use bevy_ecs::{component::Component, entity::Entity, system::RunSystemOnce, world::World};
+    use bevy_render::{sync_world::RenderEntity, MainWorld};
+
+    use crate::JobMarker;
+
+    use super::{extract_camera_views, CameraViewInput};
+
+    #[derive(Component)]
+    struct JobCamera(Entity);
+
+    #[derive(Component, PartialEq, Debug)]
+    struct ExtractedJobCamera(Entity);
+
+    impl CameraViewInput for JobCamera {
+        type Extracted = ExtractedJobCamera;
+
+        type Data = ();
+
+        fn camera(&self) -> Entity {
+            self.0
+        }
+
+        fn extract(&self, view: Entity, (): ()) -> Self::Extracted {
+            ExtractedJobCamera(view)
+        }
+    }
+
+    #[test]
+    fn views_are_extracted_once_their_camera_is_synced() {
+        let mut render_world = World::new();
+        render_world.init_resource::<MainWorld>();
+        let job = render_world.spawn_empty().id();
+        let view = render_world.spawn_empty().id();
+
+        let mut main_world = render_world.resource_mut::<MainWorld>();
+        let camera = main_world.spawn_empty().id();
+        main_world.spawn((JobMarker, RenderEntity::from(job), JobCamera(camera)));
+
+        // the camera hasn't been synced to the render world yet
+        let extract = extract_camera_views::<JobCamera>;
+        render_world.run_system_once(extract).unwrap();
+        assert!(render_world.get::<ExtractedJobCamera>(job).is_none());
+
+        render_world
+            .resource_mut::<MainWorld>()
+            .entity_mut(camera)
+            .insert(RenderEntity::from(view));
+        render_world.run_system_once(extract).unwrap();
+        assert_eq!(
+            render_world.get::<ExtractedJobCamera>(job),
+            Some(&ExtractedJobCamera(view))
+        );
+    }
+}
@@ -0,0 +1,245 @@
+use bevy_app::{App, Plugin};
+use bevy_ecs::{
+    component::Component,
+    entity::Entity,
+    query::{QueryItem, With},
+    system::{lifetimeless::Read, Commands, Query, Res},
+    world::World,
+};
+use bevy_pbr::{CascadeShadowConfig, DirectionalLight, DirectionalLightShadowMap};
+use bevy_render::{
+    camera::{CameraProjection, Projection},
+    sync_world::RenderEntity,
+    Extract, ExtractSchedule, RenderApp,
+};
+
+use crate::{GraphicsJob, JobMarker};
+
+use super::{JobInput, JobInputStatus};
+
+/// A [`JobInput`] providing the cascades of a directional light's shadow map, as seen
+/// by a camera: where each cascade begins and ends, and the size of its shadow map
+/// texels in world units, for shadow jobs that would otherwise re-derive bevy's
+/// cascade math.
+///
+/// The light must have a [`CascadeShadowConfig`], which bevy adds to every
+/// [`DirectionalLight`], with any number of cascades. Cascades cover slices of the
+/// camera's frustum, so their texel sizes depend on its [`Projection`]. Both are
+/// extracted every frame, so the job is ready as soon as the light and camera exist.
+#[derive(Copy, Clone, Component, PartialEq, Eq, Debug)]
+pub struct JobCascadeConfig {
+    /// The main world directional light.
+    pub light: Entity,
+    /// The main world camera the cascades are fit to.
+    pub camera: Entity,
+}
+
+impl JobCascadeConfig {
+    /// Targets the cascades of the given light, fit to the given camera.
+    pub const fn new(light: Entity, camera: Entity) -> Self {
+        Self { light, camera }
+    }
+}
+
+/// The cascades of a job's [`JobCascadeConfig`], resolved during extraction.
+#[derive(Clone, Component)]
+#[doc(hidden)]
+pub struct ExtractedJobCascadeConfig(pub JobCascadeConfigItem);
+
+impl<J: GraphicsJob> JobInput<J> for JobCascadeConfig {
+    type Data = Option<Read<ExtractedJobCascadeConfig>>;
+
+    type Item<'a> = &'a JobCascadeConfigItem;
+
+    fn plugin() -> impl Plugin {
+        |app: &mut App| {
+            if !app.is_plugin_added::<JobCascadeConfigPlugin>() {
+                app.add_plugins(JobCascadeConfigPlugin);
+            }
+        }
+    }
+
+    fn status(data: QueryItem<Self::Data>, _world: &World) -> JobInputStatus {
+        match data {
+            Some(_) => JobInputStatus::Ready,
+            None => JobInputStatus::Wait,
+        }
+    }
+
+    fn get<'a>(data: QueryItem<'a, Self::Data>, _world: &'a World) -> Self::Item<'a> {
+        &data.expect("cascades should be ready by this point").0
+    }
+}
+
+/// The cascades provided by [`JobCascadeConfig`].
+#[derive(Clone, Debug)]
+pub struct JobCascadeConfigItem {
+    /// The light's cascade configuration.
+    pub config: CascadeShadowConfig,
+    /// Each cascade, from nearest to farthest.
+    pub cascades: Vec<JobCascade>,
+    /// The width and height of each cascade's shadow map, in texels.
+    pub shadow_map_size: u32,
+}
+
+/// A single cascade of a directional light's shadow map, provided by [`JobCascadeConfig`].
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct JobCascade {
+    /// The distance in front of the camera the cascade begins at, including its overlap
+    /// with the previous cascade.
+    pub near: f32,
+    /// The distance in front of the camera the cascade ends at.
+    pub far: f32,
+    /// The width of a shadow map texel in world units, the same as bevy's.
+    pub texel_size: f32,
+}
+
+impl JobCascadeConfigItem {
+    fn new(config: &CascadeShadowConfig, projection: &Projection, shadow_map_size: u32) -> Self {
+        let cascades = config
+            .bounds
+            .iter()
+            .enumerate()
+            .map(|(index, &far)| {
+                let near = match index {
+                    0 => config.minimum_distance,
+                    _ => (1.0 - config.overlap_proportion) * config.bounds[index - 1],
+                };
+                // fit the same way as bevy's `calculate_cascade`, which bounds the slice
+                // of the frustum with the larger of its diagonals
+                let corners = projection.get_frustum_corners(-near, -far);
+                let diameter = (corners[0] - corners[6])
+                    .length()
+                    .max((corners[4] - corners[6]).length())
+                    .ceil();
+                JobCascade {
+                    near,
+                    far,
+                    texel_size: diameter / shadow_map_size as f32,
+                }
+            })
+            .collect();
+
+        Self {
+            config: config.clone(),
+            cascades,
+            shadow_map_size,
+        }
+    }
+}
+
+struct JobCascadeConfigPlugin;
+
+impl Plugin for JobCascadeConfigPlugin {
+    fn build(&self, app: &mut App) {
+        if let Some(render_app) = app.get_sub_app_mut(RenderApp) {
+            render_app.add_systems(ExtractSchedule, extract_job_cascade_configs);
+        }
+    }
+}
+
+// cascades follow the camera's projection and the light's config, so they're
+// resolved every frame rather than once
+fn extract_job_cascade_configs(
+    jobs: Extract<Query<(RenderEntity, &JobCascadeConfig), With<JobMarker>>>,
+    lights: Extract<Query<&CascadeShadowConfig, With<DirectionalLight>>>,
+    cameras: Extract<Query<&Projection>>,
+    shadow_map: Extract<Option<Res<DirectionalLightShadowMap>>>,
+    mut commands: Commands,
+) {
+    let shadow_map_size = shadow_map
+        .as_ref()
+        .map_or(DirectionalLightShadowMap::default().size, |shadow_map| {
+            shadow_map.size
+        }) as u32;
+
+    for (render_entity, cascades) in &jobs {
+        let (Ok(config), Ok(projection)) =
+            (lights.get(cascades.light), cameras.get(cascades.camera))
+        else {
+            continue;
+        };
+        commands
+            .entity(render_entity)
+            .insert(ExtractedJobCascadeConfig(JobCascadeConfigItem::new(
+                config,
+                projection,
+                shadow_map_size,
+            )));
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use bevy_ecs::{component::Component, world::World};
+    use bevy_pbr::CascadeShadowConfigBuilder;
+    use bevy_render::camera::{PerspectiveProjection, Projection};
+
+    use crate::{
+        input::{JobInput, JobInputItem, JobInputStatus},
+        GraphicsJob, JobError, JobRunContext,
+    };
+
+    use super::{ExtractedJobCascadeConfig, JobCascadeConfig, JobCascadeConfigItem};
+
+    #[derive(Clone, Component)]
+    struct BlurShadowsJob;
+
+    impl GraphicsJob for BlurShadowsJob {
+        type In = JobCascadeConfig;
+
+        fn run(
+            &self,
+            _world: &World,
+            _context: &mut JobRunContext,
+            _input: JobInputItem<Self, Self::In>,
+        ) -> Result<(), JobError> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn ready_once_extracted() {
+        let world = World::new();
+        let status = <JobCascadeConfig as JobInput<BlurShadowsJob>>::status;
+        assert_eq!(status(None, &world), JobInputStatus::Wait);
+
+        let cascades = ExtractedJobCascadeConfig(JobCascadeConfigItem::new(
+            &CascadeShadowConfigBuilder::default().build(),
+            &Projection::default(),
+            2048,
+        ));
+        assert_eq!(status(Some(&cascades), &world), JobInputStatus::Ready);
+    }
+
+    #[test]
+    fn custom_cascade_counts() {
+        let projection = Projection::Perspective(PerspectiveProjection {
+            aspect_ratio: 16.0 / 9.0,
+            ..Default::default()
+        });
+        for num_cascades in [1, 2, 6] {
+            let config = CascadeShadowConfigBuilder {
+                num_cascades,
+                minimum_distance: 0.1,
+                first_cascade_far_bound: 5.0,
+                maximum_distance: 100.0,
+                overlap_proportion: 0.2,
+            }
+            .build();
+            let item = JobCascadeConfigItem::new(&config, &projection, 1024);
+            assert_eq!(item.cascades.len(), num_cascades);
+
+            let first = item.cascades[0];
+            assert_eq!(first.near, 0.1);
+            let last = item.cascades[num_cascades - 1];
+            assert!((last.far - 100.0).abs() < 1e-3);
+
+            // farther cascades cover more of the frustum with the same number of texels
+            for pair in item.cascades.windows(2) {
+                assert!((pair[1].near - pair[0].far * 0.8).abs() < 1e-5);
+                assert!(pair[1].texel_size > pair[0].texel_size);
+            }
+        }
+    }
+}
@@ -0,0 +1,475 @@
+use bevy_app::{App, Plugin};
+use bevy_asset::{load_internal_asset, Handle};
+use bevy_ecs::{
+    component::Component,
+    entity::Entity,
+    query::{QueryItem, With},
+    schedule::IntoSystemConfigs,
+    system::{lifetimeless::Read, Commands, Query, Res, ResMut, Resource},
+    world::{EntityRef, FromWorld, World},
+};
+use bevy_render::{
+    render_resource::{
+        binding_types::{texture_depth_2d, texture_depth_2d_multisampled},
+        BindGroupEntries, BindGroupLayout, BindGroupLayoutEntries, CommandEncoderDescriptor,
+        CompareFunction, DepthStencilState, Extent3d, FragmentState, ImageCopyTexture, LoadOp,
+        MultisampleState, Operations, Origin3d, PipelineCache, PrimitiveState,
+        RenderPassDepthStencilAttachment, RenderPassDescriptor, RenderPipelineDescriptor, Shader,
+        ShaderStages, SpecializedRenderPipeline, SpecializedRenderPipelines, StoreOp, Texture,
+        TextureAspect, TextureDescriptor, TextureDimension, TextureFormat, TextureUsages,
+        TextureView, TextureViewDescriptor, VertexState,
+    },
+    renderer::{RenderDevice, RenderQueue},
+    texture::{CachedTexture, TextureCache},
+    view::{ExtractedView, ViewDepthTexture},
+    ExtractSchedule, Render, RenderApp, RenderSet,
+};
+use bevy_utils::{tracing::error, HashSet};
+
+use crate::{
+    device::{add_device_reset, remove_all},
+    memory::{texture_bytes, JobMemoryAllocation, JobMemoryCategory, JobMemoryUsage},
+    GraphicsJob,
+};
+
+use super::{
+    camera_view::{extract_camera_views, CameraViewInput},
+    JobInput, JobInputStatus, JobPrepareSet,
+};
+
+const DEPTH_COPY_SHADER_HANDLE: Handle<Shader> =
+    Handle::weak_from_u128(218390275630183519836473051937488120534);
+
+/// A [`JobInput`] providing a sampled copy of a camera's depth buffer, for jobs that
+/// read depth while it's bound as a depth attachment elsewhere, like soft particles
+/// fading out where they meet the scene.
+///
+/// The copy is recorded once per frame for each camera with pending jobs, after the
+/// camera's depth texture is prepared, and is shared by all of them. Single-sampled
+/// `Depth16Unorm` and `Depth32Float` textures with [`TextureUsages::COPY_SRC`] are
+/// copied directly. Other depth textures, like multisampled ones, are resolved into a
+/// `Depth32Float` texture by a fullscreen pass keeping the first sample of each pixel,
+/// which needs [`TextureUsages::TEXTURE_BINDING`]. Either usage can be added with
+/// `Camera3d::depth_texture_usages`, and a `DepthPrepass` adds `COPY_SRC`. The job
+/// fails if the depth texture has neither.
+///
+/// Jobs run before the render graph, so the copy holds the depth of the previous frame.
+#[derive(Copy, Clone, Component, PartialEq, Eq, Debug)]
+pub struct JobDepthCopy(pub Entity);
+
+impl JobDepthCopy {
+    /// Copies the depth of the given main world camera.
+    pub const fn new(camera: Entity) -> Self {
+        Self(camera)
+    }
+}
+
+/// The render world view entity whose depth is copied for a job's [`JobDepthCopy`].
+#[derive(Copy, Clone, Component)]
+#[doc(hidden)]
+pub struct ExtractedJobDepthCopy(pub Entity);
+
+/// The copy of a view's depth texture made this frame.
+#[derive(Component)]
+struct JobDepthCopyTexture {
+    texture: CachedTexture,
+    _memory: JobMemoryAllocation,
+}
+
+impl<J: GraphicsJob> JobInput<J> for JobDepthCopy {
+    type Data = Option<Read<ExtractedJobDepthCopy>>;
+
+    type Item<'a> = JobDepthCopyItem<'a>;
+
+    const PREPARE_AFTER: Option<RenderSet> = Some(RenderSet::PrepareResources);
+
+    fn plugin() -> impl Plugin {
+        |app: &mut App| {
+            if !app.is_plugin_added::<JobDepthCopyPlugin>() {
+                app.add_plugins(JobDepthCopyPlugin);
+            }
+        }
+    }
+
+    fn status(data: QueryItem<Self::Data>, world: &World) -> JobInputStatus {
+        let Some(view) = data.and_then(|view| world.get_entity(view.0).ok()) else {
+            return JobInputStatus::Wait;
+        };
+        let (Some(_), Some(depth)) = (view.get::<ExtractedView>(), view.get::<ViewDepthTexture>())
+        else {
+            // the camera isn't active, or its depth isn't prepared yet
+            return JobInputStatus::Wait;
+        };
+
+        let texture = &depth.texture;
+        if let Err(reason) =
+            depth_copy_method(texture.format(), texture.sample_count(), texture.usage())
+        {
+            error!("unable to copy the depth of a camera: {reason}");
+            return JobInputStatus::Fail;
+        }
+
+        match view.contains::<JobDepthCopyTexture>() {
+            true => JobInputStatus::Ready,
+            false => JobInputStatus::Wait,
+        }
+    }
+
+    fn get<'a>(data: QueryItem<'a, Self::Data>, world: &'a World) -> Self::Item<'a> {
+        let view = world
+            .get_entity(data.expect("depth copy should be ready by this point").0)
+            .expect("depth copy should be ready by this point");
+        let copy = view
+            .get::<JobDepthCopyTexture>()
+            .expect("depth copy should be ready by this point");
+        JobDepthCopyItem {
+            view,
+            texture: &copy.texture.texture,
+            texture_view: &copy.texture.default_view,
+        }
+    }
+}
+
+/// The depth copy provided by [`JobDepthCopy`].
+pub struct JobDepthCopyItem<'a> {
+    /// The render world view entity, for looking up other view components.
+    pub view: EntityRef<'a>,
+    /// The copied depth texture, with the same size as the camera's depth texture.
+    /// Its format is that of the camera's depth texture if it was copied directly, or
+    /// `Depth32Float` if it was resolved.
+    pub texture: &'a Texture,
+    /// A view of the copy, to be bound as a `texture_depth_2d`.
+    pub texture_view: &'a TextureView,
+}
+
+/// How a view's depth texture is copied.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+enum DepthCopyMethod {
+    /// Copied texture to texture.
+    Copy,
+    /// Resolved by a fullscreen pass writing the depth of the first sample of each pixel.
+    Resolve,
+}
+
+/// Picks how a depth texture with the given properties is copied.
+fn depth_copy_method(
+    format: TextureFormat,
+    sample_count: u32,
+    usage: TextureUsages,
+) -> Result<DepthCopyMethod, &'static str> {
+    let copyable = matches!(
+        format,
+        TextureFormat::Depth16Unorm | TextureFormat::Depth32Float
+    );
+    if copyable && sample_count == 1 && usage.contains(TextureUsages::COPY_SRC) {
+        Ok(DepthCopyMethod::Copy)
+    } else if usage.contains(TextureUsages::TEXTURE_BINDING) {
+        Ok(DepthCopyMethod::Resolve)
+    } else {
+        Err("the depth texture must have TEXTURE_BINDING usage, or COPY_SRC usage if it's directly copyable")
+    }
+}
+
+/// The crate-owned pipeline resolving depth for [`JobDepthCopy`], specialized by
+/// whether the camera's depth texture is multisampled.
+#[derive(Resource)]
+struct DepthResolvePipeline {
+    layout: BindGroupLayout,
+    multisampled_layout: BindGroupLayout,
+}
+
+impl DepthResolvePipeline {
+    fn layout(&self, multisampled: bool) -> &BindGroupLayout {
+        match multisampled {
+            true => &self.multisampled_layout,
+            false => &self.layout,
+        }
+    }
+}
+
+impl FromWorld for DepthResolvePipeline {
+    fn from_world(world: &mut World) -> Self {
+        let render_device = world.resource::<RenderDevice>();
+        let layout = render_device.create_bind_group_layout(
+            "job_depth_resolve_layout",
+            &BindGroupLayoutEntries::single(ShaderStages::FRAGMENT, texture_depth_2d()),
+        );
+        let multisampled_layout = render_device.create_bind_group_layout(
+            "job_depth_resolve_multisampled_layout",
+            &BindGroupLayoutEntries::single(
+                ShaderStages::FRAGMENT,
+                texture_depth_2d_multisampled(),
+            ),
+        );
+
+        Self {
+            layout,
+            multisampled_layout,
+        }
+    }
+}
+
+impl SpecializedRenderPipeline for DepthResolvePipeline {
+    type Key = bool;
+
+    fn specialize(&self, multisampled: Self::Key) -> RenderPipelineDescriptor {
+        let shader_defs = match multisampled {
+            true => vec!["MULTISAMPLED".into()],
+            false => Vec::new(),
+        };
+
+        RenderPipelineDescriptor {
+            label: Some("job_depth_resolve_pipeline".into()),
+            layout: vec![self.layout(multisampled).clone()],
+            push_constant_ranges: Vec::new(),
+            vertex: VertexState {
+                shader: DEPTH_COPY_SHADER_HANDLE,
+                shader_defs: shader_defs.clone(),
+                entry_point: "vertex".into(),
+                buffers: Vec::new(),
+            },
+            primitive: PrimitiveState::default(),
+            depth_stencil: Some(DepthStencilState {
+                format: TextureFormat::Depth32Float,
+                depth_write_enabled: true,
+                depth_compare: CompareFunction::Always,
+                stencil: Default::default(),
+                bias: Default::default(),
+            }),
+            multisample: MultisampleState::default(),
+            fragment: Some(FragmentState {
+                shader: DEPTH_COPY_SHADER_HANDLE,
+                shader_defs,
+                entry_point: "fragment".into(),
+                targets: Vec::new(),
+            }),
+            zero_initialize_workgroup_memory: false,
+        }
+    }
+}
+
+struct JobDepthCopyPlugin;
+
+impl Plugin for JobDepthCopyPlugin {
+    fn build(&self, app: &mut App) {
+        load_internal_asset!(
+            app,
+            DEPTH_COPY_SHADER_HANDLE,
+            "depth_copy.wgsl",
+            Shader::from_wgsl
+        );
+
+        if let Some(render_app) = app.get_sub_app_mut(RenderApp) {
+            render_app
+                .init_resource::<SpecializedRenderPipelines<DepthResolvePipeline>>()
+                .add_systems(ExtractSchedule, extract_camera_views::<JobDepthCopy>)
+                .add_systems(
+                    Render,
+                    prepare_job_depth_copies.in_set(JobPrepareSet::of::<JobDepthCopy>()),
+                );
+        }
+
+        add_device_reset(app, reset_job_depth_copies);
+    }
+
+    fn finish(&self, app: &mut App) {
+        if let Some(render_app) = app.get_sub_app_mut(RenderApp) {
+            render_app.init_resource::<DepthResolvePipeline>();
+        }
+    }
+}
+
+impl CameraViewInput for JobDepthCopy {
+    type Extracted = ExtractedJobDepthCopy;
+
+    type Data = ();
+
+    fn camera(&self) -> Entity {
+        self.0
+    }
+
+    fn extract(&self, view: Entity, (): ()) -> Self::Extracted {
+        ExtractedJobDepthCopy(view)
+    }
+}
+
+/// Copies the depth of every view that a pending job reads. Copies from the previous
+/// frame are dropped first, so that their pooled textures aren't held past the frame
+/// they're taken for.
+#[allow(clippy::too_many_arguments)]
+fn prepare_job_depth_copies(
+    jobs: Query<&ExtractedJobDepthCopy>,
+    views: Query<&ViewDepthTexture, With<ExtractedView>>,
+    previous_copies: Query<Entity, With<JobDepthCopyTexture>>,
+    resolve_pipeline: Res<DepthResolvePipeline>,
+    mut resolve_pipelines: ResMut<SpecializedRenderPipelines<DepthResolvePipeline>>,
+    pipeline_cache: Res<PipelineCache>,
+    mut texture_cache: ResMut<TextureCache>,
+    render_device: Res<RenderDevice>,
+    render_queue: Res<RenderQueue>,
+    memory_usage: Res<JobMemoryUsage>,
+    mut commands: Commands,
+) {
+    for view in &previous_copies {
+        commands.entity(view).remove::<JobDepthCopyTexture>();
+    }
+
+    let mut encoder = None;
+    let requested = jobs.iter().map(|job| job.0).collect::<HashSet<_>>();
+    for view in requested {
+        let Ok(depth) = views.get(view) else {
+            continue;
+        };
+        let source = &depth.texture;
+        let Ok(method) = depth_copy_method(source.format(), source.sample_count(), source.usage())
+        else {
+            continue;
+        };
+
+        let resolve = match method {
+            DepthCopyMethod::Copy => None,
+            DepthCopyMethod::Resolve => {
+                let multisampled = source.sample_count() > 1;
+                let id =
+                    resolve_pipelines.specialize(&pipeline_cache, &resolve_pipeline, multisampled);
+                // the copy waits until the pipeline is compiled
+                let Some(pipeline) = pipeline_cache.get_render_pipeline(id) else {
+                    continue;
+                };
+                Some((pipeline, multisampled))
+            }
+        };
+
+        let (format, usage) = match resolve {
+            None => (source.format(), TextureUsages::COPY_DST),
+            Some(_) => (
+                TextureFormat::Depth32Float,
+                TextureUsages::RENDER_ATTACHMENT,
+            ),
+        };
+        let descriptor = TextureDescriptor {
+            label: Some("job_depth_copy_texture"),
+            size: Extent3d {
+                depth_or_array_layers: 1,
+                ..source.size()
+            },
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: TextureDimension::D2,
+            format,
+            usage: usage | TextureUsages::TEXTURE_BINDING,
+            view_formats: &[],
+        };
+        let bytes = texture_bytes(&descriptor);
+        let copy = texture_cache.get(&render_device, descriptor);
+
+        let encoder = encoder.get_or_insert_with(|| {
+            render_device.create_command_encoder(&CommandEncoderDescriptor {
+                label: Some("job_depth_copy_encoder"),
+            })
+        });
+        match resolve {
+            None => encoder.copy_texture_to_texture(
+                ImageCopyTexture {
+                    texture: source,
+                    mip_level: 0,
+                    origin: Origin3d::ZERO,
+                    aspect: TextureAspect::All,
+                },
+                ImageCopyTexture {
+                    texture: &copy.texture,
+                    mip_level: 0,
+                    origin: Origin3d::ZERO,
+                    aspect: TextureAspect::All,
+                },
+                copy.texture.size(),
+            ),
+            Some((pipeline, multisampled)) => {
+                let source_view = source.create_view(&TextureViewDescriptor {
+                    label: Some("job_depth_resolve_source"),
+                    aspect: TextureAspect::DepthOnly,
+                    ..Default::default()
+                });
+                let bind_group = render_device.create_bind_group(
+                    "job_depth_resolve_bind_group",
+                    resolve_pipeline.layout(multisampled),
+                    &BindGroupEntries::single(&source_view),
+                );
+
+                let mut render_pass = encoder.begin_render_pass(&RenderPassDescriptor {
+                    label: Some("job_depth_resolve_pass"),
+                    color_attachments: &[],
+                    depth_stencil_attachment: Some(RenderPassDepthStencilAttachment {
+                        view: &copy.default_view,
+                        depth_ops: Some(Operations {
+                            load: LoadOp::Clear(0.0),
+                            store: StoreOp::Store,
+                        }),
+                        stencil_ops: None,
+                    }),
+                    timestamp_writes: None,
+                    occlusion_query_set: None,
+                });
+                render_pass.set_pipeline(pipeline);
+                render_pass.set_bind_group(0, &bind_group, &[]);
+                render_pass.draw(0..3, 0..1);
+            }
+        }
+
+        commands.entity(view).insert(JobDepthCopyTexture {
+            texture: copy,
+            _memory: memory_usage.allocate(JobMemoryCategory::ScratchTextures, bytes),
+        });
+    }
+
+    // the copies are submitted before any job runs this frame
+    if let Some(encoder) = encoder {
+        render_queue.submit([encoder.finish()]);
+    }
+}
+
+fn reset_job_depth_copies(world: &mut World) {
+    remove_all::<JobDepthCopyTexture>(world);
+    world.insert_resource(SpecializedRenderPipelines::<DepthResolvePipeline>::default());
+}
+
+#[cfg(test)]
+mod test {
+    use bevy_render::render_resource::{TextureFormat, TextureUsages};
+
+    use super::{depth_copy_method, DepthCopyMethod};
+
+    #[test]
+    fn single_sampled_depth_is_copied() {
+        assert_eq!(
+            depth_copy_method(TextureFormat::Depth32Float, 1, TextureUsages::COPY_SRC),
+            Ok(DepthCopyMethod::Copy)
+        );
+    }
+
+    #[test]
+    fn uncopyable_depth_is_resolved() {
+        let usage = TextureUsages::COPY_SRC | TextureUsages::TEXTURE_BINDING;
+        assert_eq!(
+            depth_copy_method(TextureFormat::Depth32Float, 4, usage),
+            Ok(DepthCopyMethod::Resolve)
+        );
+        assert_eq!(
+            depth_copy_method(TextureFormat::Depth24Plus, 1, usage),
+            Ok(DepthCopyMethod::Resolve)
+        );
+    }
+
+    #[test]
+    fn depth_without_usages_fails() {
+        assert!(
+            depth_copy_method(TextureFormat::Depth32Float, 4, TextureUsages::COPY_SRC).is_err()
+        );
+        assert!(depth_copy_method(
+            TextureFormat::Depth32Float,
+            1,
+            TextureUsages::RENDER_ATTACHMENT
+        )
+        .is_err());
+    }
+}
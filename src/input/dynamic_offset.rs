@@ -0,0 +1,180 @@
+use core::marker::PhantomData;
+
+use bevy_app::{App, Plugin};
+use bevy_ecs::{
+    component::Component,
+    query::QueryItem,
+    system::{lifetimeless::Read, Resource},
+    world::World,
+};
+use bevy_render::{
+    extract_component::{ExtractComponent, ExtractComponentPlugin},
+    render_resource::{BindGroup, ComputePass},
+    renderer::RenderDevice,
+    settings::WgpuLimits,
+};
+use wgpu::RenderPass;
+
+use crate::GraphicsJob;
+
+use super::{JobInput, JobInputStatus};
+
+/// A render world [`Resource`] holding a bind group shared by many jobs, whose buffer
+/// is bound with a dynamic offset, for use with [`JobDynamicBindGroup`].
+///
+/// The bind group's layout should contain a single binding with `has_dynamic_offset`
+/// set, like [`uniform_buffer`](bevy_render::render_resource::binding_types::uniform_buffer)`::<T>(true)`.
+pub trait JobDynamicBindGroupResource: Resource {
+    /// Whether the dynamically offset buffer is bound as a storage buffer rather than a
+    /// uniform buffer, which selects the alignment offsets are validated against.
+    const STORAGE: bool = false;
+
+    /// Returns the shared bind group, or `None` if it hasn't been created yet.
+    fn bind_group(&self) -> Option<&BindGroup>;
+}
+
+/// The byte offset a job binds the buffer of its [`JobDynamicBindGroup`] at.
+#[derive(Copy, Clone, Component, PartialEq, Eq, Debug)]
+pub struct JobDynamicOffset(pub u32);
+
+impl JobDynamicOffset {
+    #[inline]
+    pub const fn new(offset: u32) -> Self {
+        Self(offset)
+    }
+
+    /// The offset of the element at `index`, in a buffer of elements `stride` bytes
+    /// apart. A stride of 256 bytes is aligned on every device.
+    #[inline]
+    pub const fn from_index(index: u32, stride: u32) -> Self {
+        Self(index * stride)
+    }
+}
+
+impl ExtractComponent for JobDynamicOffset {
+    type QueryData = Read<JobDynamicOffset>;
+
+    type QueryFilter = ();
+
+    type Out = JobDynamicOffset;
+
+    fn extract_component(item: QueryItem<'_, Self::QueryData>) -> Option<Self::Out> {
+        Some(*item)
+    }
+}
+
+/// A [`JobInput`] providing the bind group of the render world resource `R`, along with
+/// the job's [`JobDynamicOffset`] into its buffer. This lets many jobs sharing a pipeline
+/// read their own parameters out of one large buffer through a single bind group, rather
+/// than each creating a bind group of its own.
+///
+/// Jobs without a [`JobDynamicOffset`], or whose offset isn't a multiple of the device's
+/// `min_uniform_buffer_offset_alignment` (or `min_storage_buffer_offset_alignment` for
+/// [`STORAGE`](JobDynamicBindGroupResource::STORAGE) buffers), fail with
+/// [`JobError::InputsFailed`](crate::JobError::InputsFailed), since binding them would
+/// be a validation error. The job waits until the bind group is created.
+pub struct JobDynamicBindGroup<R>(PhantomData<R>);
+
+impl<J: GraphicsJob, R: JobDynamicBindGroupResource> JobInput<J> for JobDynamicBindGroup<R> {
+    type Data = Option<Read<JobDynamicOffset>>;
+
+    type Item<'a> = JobDynamicBinding<'a>;
+
+    fn plugin() -> impl Plugin {
+        |app: &mut App| {
+            if !app.is_plugin_added::<ExtractComponentPlugin<JobDynamicOffset>>() {
+                app.add_plugins(ExtractComponentPlugin::<JobDynamicOffset>::default());
+            }
+        }
+    }
+
+    fn status(offset: QueryItem<Self::Data>, world: &World) -> JobInputStatus {
+        let alignment = offset_alignment(&world.resource::<RenderDevice>().limits(), R::STORAGE);
+        dynamic_binding_status(
+            offset,
+            alignment,
+            world.get_resource::<R>().and_then(R::bind_group),
+        )
+    }
+
+    fn get<'a>(offset: QueryItem<'a, Self::Data>, world: &'a World) -> Self::Item<'a> {
+        JobDynamicBinding {
+            bind_group: world
+                .resource::<R>()
+                .bind_group()
+                .expect("dynamic bind group should be ready by this point"),
+            offset: offset
+                .expect("dynamic offset should be ready by this point")
+                .0,
+        }
+    }
+}
+
+/// The shared bind group and offset provided by [`JobDynamicBindGroup`].
+#[derive(Copy, Clone)]
+pub struct JobDynamicBinding<'a> {
+    pub bind_group: &'a BindGroup,
+    pub offset: u32,
+}
+
+impl JobDynamicBinding<'_> {
+    /// Binds the shared bind group at `index` with the job's offset.
+    pub fn set_compute(&self, pass: &mut ComputePass, index: u32) {
+        pass.set_bind_group(index, self.bind_group, &[self.offset]);
+    }
+
+    /// Binds the shared bind group at `index` with the job's offset.
+    pub fn set_render(&self, pass: &mut RenderPass, index: u32) {
+        pass.set_bind_group(index, self.bind_group, &[self.offset]);
+    }
+}
+
+fn offset_alignment(limits: &WgpuLimits, storage: bool) -> u32 {
+    match storage {
+        true => limits.min_storage_buffer_offset_alignment,
+        false => limits.min_uniform_buffer_offset_alignment,
+    }
+}
+
+fn dynamic_binding_status(
+    offset: Option<&JobDynamicOffset>,
+    alignment: u32,
+    bind_group: Option<&BindGroup>,
+) -> JobInputStatus {
+    match offset {
+        Some(offset) if offset.0 % alignment == 0 => {}
+        _ => return JobInputStatus::Fail,
+    }
+    match bind_group {
+        Some(_) => JobInputStatus::Ready,
+        None => JobInputStatus::Wait,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use bevy_render::settings::WgpuLimits;
+
+    use crate::input::JobInputStatus;
+
+    use super::{dynamic_binding_status, offset_alignment, JobDynamicOffset};
+
+    #[test]
+    fn validates_offsets_against_alignment() {
+        let alignment = offset_alignment(&WgpuLimits::default(), false);
+        assert_eq!(alignment, 256);
+
+        let status =
+            |offset: Option<&JobDynamicOffset>| dynamic_binding_status(offset, alignment, None);
+        assert_eq!(status(None), JobInputStatus::Fail);
+        assert_eq!(
+            status(Some(&JobDynamicOffset::new(64))),
+            JobInputStatus::Fail
+        );
+        // aligned offsets wait for the bind group to be created
+        assert_eq!(
+            status(Some(&JobDynamicOffset::from_index(3, 256))),
+            JobInputStatus::Wait
+        );
+    }
+}
@@ -0,0 +1,209 @@
+use core::{marker::PhantomData, ops::Deref};
+
+use bevy_app::{App, Plugin};
+use bevy_ecs::{
+    component::Component,
+    query::{Has, QueryItem},
+    schedule::Schedule,
+    world::World,
+};
+use bevy_render::RenderApp;
+
+use crate::GraphicsJob;
+
+use super::{JobInput, JobInputStatus};
+
+/// An input that can stand in a placeholder for its item while it waits, for use
+/// with [`JobFallback`].
+pub trait JobInputFallback<J: GraphicsJob>: JobInput<J> {
+    /// A placeholder for the input's item, like one of bevy's fallback images, or
+    /// `None` if there's no placeholder available yet either.
+    fn fallback(world: &World) -> Option<Self::Item<'_>>;
+}
+
+/// A [`JobInput`] that lets a job run before the wrapped input is ready, with a
+/// placeholder from [`JobInputFallback::fallback`] in its place, rather than stalling.
+/// Jobs opting in render an approximation first, and refine it once their inputs
+/// resolve.
+///
+/// After a job runs with any placeholders, it's queued again instead of completing,
+/// and waits for the real items of all of its inputs to run a second time, which
+/// completes it as usual. A job runs with placeholders at most once, and times out
+/// if its inputs don't resolve within [`time_out_frames`](crate::JobExecutionSettings::time_out_frames)
+/// of its first run. Inputs that fail still fail the job. Jobs that aren't
+/// [idempotent](GraphicsJob::IS_IDEMPOTENT) never run with placeholders, and wait for
+/// their real inputs instead.
+pub struct JobFallback<T>(PhantomData<T>);
+
+/// Marks a job that already ran with placeholders from a [`JobFallback`], so that it
+/// waits for its real inputs from then on.
+#[derive(Copy, Clone, Component)]
+#[doc(hidden)]
+pub struct JobFallbackUsed;
+
+impl<J: GraphicsJob, T: JobInputFallback<J>> JobInput<J> for JobFallback<T> {
+    // the wrapped input's data is queried twice, since checking its status and getting
+    // its item each consume it
+    type Data = (T::Data, T::Data, Has<JobFallbackUsed>);
+
+    type Item<'a> = JobFallbackItem<T::Item<'a>>;
+
+    fn plugin() -> impl Plugin {
+        |app: &mut App| {
+            app.add_plugins(T::plugin());
+            // the marker is queried before any job has used a fallback, so it must
+            // already be registered for jobs' data to match
+            if let Some(render_app) = app.get_sub_app_mut(RenderApp) {
+                render_app
+                    .world_mut()
+                    .register_component::<JobFallbackUsed>();
+            }
+        }
+    }
+
+    fn configure_prepare_sets(schedule: &mut Schedule) {
+        T::configure_prepare_sets(schedule);
+    }
+
+    fn status((data, _, used): QueryItem<Self::Data>, world: &World) -> JobInputStatus {
+        match T::status(data, world) {
+            JobInputStatus::Wait if !used && T::fallback(world).is_some() => JobInputStatus::Ready,
+            status => status,
+        }
+    }
+
+    fn get<'a>((data, item, _): QueryItem<'a, Self::Data>, world: &'a World) -> Self::Item<'a> {
+        match T::status(data, world) {
+            JobInputStatus::Ready => JobFallbackItem {
+                item: T::get(item, world),
+                is_fallback: false,
+            },
+            _ => JobFallbackItem {
+                item: T::fallback(world).expect("fallback should be ready by this point"),
+                is_fallback: true,
+            },
+        }
+    }
+
+    fn is_fallback((data, ..): QueryItem<Self::Data>, world: &World) -> bool {
+        T::status(data, world) != JobInputStatus::Ready
+    }
+}
+
+/// The item provided by [`JobFallback`], which dereferences to the wrapped input's item.
+pub struct JobFallbackItem<I> {
+    /// The wrapped input's item, or its placeholder.
+    pub item: I,
+    /// Whether `item` is a placeholder, because the wrapped input isn't ready yet.
+    pub is_fallback: bool,
+}
+
+impl<I> Deref for JobFallbackItem<I> {
+    type Target = I;
+
+    fn deref(&self) -> &Self::Target {
+        &self.item
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use bevy_ecs::{
+        component::Component,
+        query::QueryItem,
+        system::Resource,
+        world::{EntityRef, World},
+    };
+
+    use crate::{
+        input::{JobInput, JobInputItem, JobInputStatus},
+        runner::DynamicJob,
+        GraphicsJob, JobError, JobRunContext,
+    };
+
+    use super::{JobFallback, JobFallbackUsed, JobInputFallback};
+
+    #[derive(Resource)]
+    struct Baked(bool);
+
+    struct Lightmap;
+
+    impl<J: GraphicsJob> JobInput<J> for Lightmap {
+        type Data = ();
+
+        type Item<'a> = &'static str;
+
+        fn status(_data: QueryItem<Self::Data>, world: &World) -> JobInputStatus {
+            match world.resource::<Baked>().0 {
+                true => JobInputStatus::Ready,
+                false => JobInputStatus::Wait,
+            }
+        }
+
+        fn get<'a>(_data: QueryItem<'a, Self::Data>, _world: &'a World) -> Self::Item<'a> {
+            "baked"
+        }
+    }
+
+    impl<J: GraphicsJob> JobInputFallback<J> for Lightmap {
+        fn fallback(_world: &World) -> Option<Self::Item<'_>> {
+            Some("placeholder")
+        }
+    }
+
+    #[derive(Clone, Component)]
+    struct ShadeJob;
+
+    impl GraphicsJob for ShadeJob {
+        type In = JobFallback<Lightmap>;
+
+        fn run(
+            &self,
+            _world: &World,
+            _context: &mut JobRunContext,
+            _input: JobInputItem<Self, Self::In>,
+        ) -> Result<(), JobError> {
+            Ok(())
+        }
+    }
+
+    fn item(entity: EntityRef, world: &World) -> (&'static str, bool) {
+        let data = entity
+            .get_components::<<JobFallback<Lightmap> as JobInput<ShadeJob>>::Data>()
+            .unwrap();
+        let item = <JobFallback<Lightmap> as JobInput<ShadeJob>>::get(data, world);
+        (item.item, item.is_fallback)
+    }
+
+    #[test]
+    fn jobs_run_with_placeholders_then_refine() {
+        let mut world = World::new();
+        world.insert_resource(Baked(false));
+        world.register_component::<JobFallbackUsed>();
+        let job = world.spawn(ShadeJob).id();
+        let dynamic = DynamicJob::new::<ShadeJob>();
+
+        // the job runs with a placeholder rather than waiting
+        assert_eq!(
+            dynamic.status(world.entity(job), &world),
+            JobInputStatus::Ready
+        );
+        assert_eq!(item(world.entity(job), &world), ("placeholder", true));
+        assert!(dynamic.is_fallback(world.entity(job), &world));
+
+        // once it has, it waits for the real input
+        world.entity_mut(job).insert(JobFallbackUsed);
+        assert_eq!(
+            dynamic.status(world.entity(job), &world),
+            JobInputStatus::Wait
+        );
+
+        world.resource_mut::<Baked>().0 = true;
+        assert_eq!(
+            dynamic.status(world.entity(job), &world),
+            JobInputStatus::Ready
+        );
+        assert_eq!(item(world.entity(job), &world), ("baked", false));
+        assert!(!dynamic.is_fallback(world.entity(job), &world));
+    }
+}
@@ -0,0 +1,618 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    thread,
+};
+
+use bevy_app::{App, PreUpdate};
+use bevy_ecs::{
+    component::Component,
+    event::{Event, EventWriter},
+    query::QueryItem,
+    system::{lifetimeless::Read, Res, Resource},
+};
+use bevy_render::{
+    extract_component::{ExtractComponent, ExtractComponentPlugin},
+    render_resource::TextureFormat,
+    RenderApp,
+};
+use bevy_utils::tracing::{error, warn};
+use crossbeam_channel::{Receiver, Sender};
+use wgpu::COPY_BYTES_PER_ROW_ALIGNMENT;
+
+use crate::JobError;
+
+/// Writes the result a job copies into its [`JobReadback`](super::JobReadback) buffer
+/// to a file once it's read back, without going through a bevy asset, for example to
+/// bake textures in a headless tool. Spawn it on the job along with its `JobReadback`.
+///
+/// Once the file is written, or fails to be, a [`JobFileWritten`] event is sent in the
+/// main world. Jobs complete when their commands are submitted, so the event arrives a
+/// few frames after the job's [`JobComplete`](crate::JobComplete). If the job fails, or
+/// its buffer can't be read back, no file is written, and the event holds the error.
+///
+/// The bytes are encoded and written on a dedicated thread, which is spawned the first
+/// time a file is written and writes files in the order their results are read back,
+/// so the render thread never waits on the disk. The thread lives as long as the app.
+/// On platforms without threads or a file system, like the web, writes fail.
+#[derive(Clone, Component, PartialEq, Eq, Debug)]
+pub struct JobFileOutput {
+    pub path: PathBuf,
+    pub format: JobFileFormat,
+}
+
+impl JobFileOutput {
+    pub fn new(path: impl Into<PathBuf>, format: JobFileFormat) -> Self {
+        Self {
+            path: path.into(),
+            format,
+        }
+    }
+}
+
+impl ExtractComponent for JobFileOutput {
+    type QueryData = Read<JobFileOutput>;
+
+    type QueryFilter = ();
+
+    type Out = JobFileOutput;
+
+    fn extract_component(item: QueryItem<'_, Self::QueryData>) -> Option<Self::Out> {
+        Some(item.clone())
+    }
+}
+
+/// How a [`JobFileOutput`] encodes the bytes read back from its job.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum JobFileFormat {
+    /// The bytes exactly as they were read back, for buffers.
+    Raw,
+    /// A single 2D texture in a KTX2 container, without supercompression.
+    Ktx2(JobFileTexture),
+    /// A single 2D texture in a DDS container, with the DX10 header extension.
+    Dds(JobFileTexture),
+}
+
+/// The texture a job copies into its readback buffer, for writing it to a
+/// [`JobFileFormat::Ktx2`] or [`JobFileFormat::Dds`] file.
+///
+/// The job copies a single mip level into the buffer, with rows of
+/// [`padded_bytes_per_row`](Self::padded_bytes_per_row) bytes, as `wgpu` requires. The
+/// padding is stripped from the file. Only uncompressed formats with 8, 16 or 32-bit
+/// channels are supported: `R8Unorm`, `Rg8Unorm`, `Rgba8Unorm`, `Rgba8UnormSrgb`,
+/// `R16Float`, `Rg16Float`, `Rgba16Float`, `R32Float`, `Rg32Float` and `Rgba32Float`.
+/// Files of other formats, or of empty textures, fail to be written.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub struct JobFileTexture {
+    pub width: u32,
+    pub height: u32,
+    pub format: TextureFormat,
+}
+
+impl JobFileTexture {
+    pub const fn new(width: u32, height: u32, format: TextureFormat) -> Self {
+        Self {
+            width,
+            height,
+            format,
+        }
+    }
+
+    /// The number of bytes of each row of the texture, without padding.
+    pub fn bytes_per_row(&self) -> u32 {
+        self.width * self.format.block_copy_size(None).unwrap_or(0)
+    }
+
+    /// The number of bytes between the rows the job copies into the readback buffer,
+    /// aligned to [`COPY_BYTES_PER_ROW_ALIGNMENT`].
+    pub fn padded_bytes_per_row(&self) -> u32 {
+        self.bytes_per_row()
+            .next_multiple_of(COPY_BYTES_PER_ROW_ALIGNMENT)
+    }
+
+    /// The size of the [`JobReadback`](super::JobReadback) the job copies the texture into.
+    pub fn readback_size(&self) -> u64 {
+        self.padded_bytes_per_row() as u64 * self.height as u64
+    }
+
+    /// Checks that the texture can be written to a file, before any of its sizes are used.
+    fn file_format(&self) -> Result<FileTextureFormat, String> {
+        let Some(format) = FileTextureFormat::of(self.format) else {
+            return Err(format!(
+                "{:?} textures can't be written to files",
+                self.format
+            ));
+        };
+        if self.width == 0 || self.height == 0 {
+            return Err(format!(
+                "a {}x{} texture has no texels to write",
+                self.width, self.height
+            ));
+        }
+        let fits = self
+            .width
+            .checked_mul(format.texel_size())
+            .and_then(|row| row.checked_next_multiple_of(COPY_BYTES_PER_ROW_ALIGNMENT))
+            .is_some();
+        if !fits {
+            return Err(format!("a texture {} texels wide is too large", self.width));
+        }
+        Ok(format)
+    }
+
+    /// The rows of the texture, without their padding.
+    fn unpadded_rows<'a>(&self, data: &'a [u8]) -> impl Iterator<Item = &'a [u8]> {
+        let bytes_per_row = self.bytes_per_row() as usize;
+        data.chunks(self.padded_bytes_per_row() as usize)
+            .map(move |row| &row[..bytes_per_row])
+    }
+}
+
+/// Sent in the main world once the file of a job's [`JobFileOutput`] is written, or
+/// fails to be.
+#[derive(Event, Clone, PartialEq, Eq, Debug)]
+pub struct JobFileWritten {
+    pub path: PathBuf,
+    pub result: Result<(), JobError>,
+}
+
+/// The channel types and sizes of a texture format supported by [`JobFileTexture`].
+struct FileTextureFormat {
+    vk_format: u32,
+    dxgi_format: u32,
+    channels: u8,
+    /// The size of each channel, in bytes.
+    channel_size: u8,
+    float: bool,
+    srgb: bool,
+}
+
+impl FileTextureFormat {
+    fn of(format: TextureFormat) -> Option<Self> {
+        let (vk_format, dxgi_format, channels, channel_size, float) = match format {
+            TextureFormat::R8Unorm => (9, 61, 1, 1, false),
+            TextureFormat::Rg8Unorm => (16, 49, 2, 1, false),
+            TextureFormat::Rgba8Unorm => (37, 28, 4, 1, false),
+            TextureFormat::Rgba8UnormSrgb => (43, 29, 4, 1, false),
+            TextureFormat::R16Float => (76, 54, 1, 2, true),
+            TextureFormat::Rg16Float => (83, 34, 2, 2, true),
+            TextureFormat::Rgba16Float => (97, 10, 4, 2, true),
+            TextureFormat::R32Float => (100, 41, 1, 4, true),
+            TextureFormat::Rg32Float => (103, 16, 2, 4, true),
+            TextureFormat::Rgba32Float => (109, 2, 4, 4, true),
+            _ => return None,
+        };
+        Some(Self {
+            vk_format,
+            dxgi_format,
+            channels,
+            channel_size,
+            float,
+            srgb: format.is_srgb(),
+        })
+    }
+
+    fn texel_size(&self) -> u32 {
+        self.channels as u32 * self.channel_size as u32
+    }
+}
+
+/// Encodes the bytes read back from a job into the contents of its file.
+fn encode(format: &JobFileFormat, data: Vec<u8>) -> Result<Vec<u8>, String> {
+    let (JobFileFormat::Ktx2(texture) | JobFileFormat::Dds(texture)) = format else {
+        return Ok(data);
+    };
+
+    let file_format = texture.file_format()?;
+    if data.len() as u64 != texture.readback_size() {
+        return Err(format!(
+            "expected {} bytes for a {}x{} texture, but {} were read back",
+            texture.readback_size(),
+            texture.width,
+            texture.height,
+            data.len()
+        ));
+    }
+
+    Ok(match format {
+        JobFileFormat::Ktx2(_) => encode_ktx2(texture, &file_format, &data),
+        _ => encode_dds(texture, &file_format, &data),
+    })
+}
+
+fn push_u32s(file: &mut Vec<u8>, values: &[u32]) {
+    for value in values {
+        file.extend_from_slice(&value.to_le_bytes());
+    }
+}
+
+fn push_u64s(file: &mut Vec<u8>, values: &[u64]) {
+    for value in values {
+        file.extend_from_slice(&value.to_le_bytes());
+    }
+}
+
+const KTX2_IDENTIFIER: [u8; 12] = [
+    0xAB, 0x4B, 0x54, 0x58, 0x20, 0x32, 0x30, 0xBB, 0x0D, 0x0A, 0x1A, 0x0A,
+];
+
+/// The size of a KTX2 file's header, index and the level index of a single level.
+const KTX2_DFD_OFFSET: u32 = 12 + 9 * 4 + 4 * 4 + 2 * 8 + 3 * 8;
+
+fn encode_ktx2(texture: &JobFileTexture, format: &FileTextureFormat, data: &[u8]) -> Vec<u8> {
+    let dfd = ktx2_data_format_descriptor(format);
+    let dfd_end = KTX2_DFD_OFFSET + dfd.len() as u32;
+    // levels are aligned to the least common multiple of the texel size and 4
+    let level_offset = dfd_end.next_multiple_of(format.texel_size().max(4));
+    let level_size = (texture.bytes_per_row() * texture.height) as u64;
+
+    let mut file = Vec::with_capacity(level_offset as usize + level_size as usize);
+    file.extend_from_slice(&KTX2_IDENTIFIER);
+    push_u32s(
+        &mut file,
+        &[
+            format.vk_format,
+            format.channel_size as u32,
+            texture.width,
+            texture.height,
+            // a 2D texture, without array layers or faces, with a single mip level
+            0,
+            0,
+            1,
+            1,
+            // no supercompression
+            0,
+        ],
+    );
+    // the data format descriptor, without key/value data
+    push_u32s(&mut file, &[KTX2_DFD_OFFSET, dfd.len() as u32, 0, 0]);
+    push_u64s(&mut file, &[0, 0]);
+    push_u64s(&mut file, &[level_offset as u64, level_size, level_size]);
+    file.extend_from_slice(&dfd);
+    file.resize(level_offset as usize, 0);
+    for row in texture.unpadded_rows(data) {
+        file.extend_from_slice(row);
+    }
+    file
+}
+
+/// The data format descriptor of a KTX2 file, with a single basic descriptor block.
+fn ktx2_data_format_descriptor(format: &FileTextureFormat) -> Vec<u8> {
+    const RGBSDA_MODEL: u32 = 1;
+    const BT709_PRIMARIES: u32 = 1;
+    const ALPHA_CHANNEL: u32 = 15;
+    const LINEAR_QUALIFIER: u32 = 0x10;
+    const SIGNED_QUALIFIER: u32 = 0x40;
+    const FLOAT_QUALIFIER: u32 = 0x80;
+
+    let block_size = 24 + 16 * format.channels as u32;
+    let transfer = if format.srgb { 2 } else { 1 };
+
+    let mut dfd = Vec::with_capacity(4 + block_size as usize);
+    push_u32s(
+        &mut dfd,
+        &[
+            4 + block_size,
+            // a basic descriptor block, from Khronos
+            0,
+            2 | (block_size << 16),
+            RGBSDA_MODEL | (BT709_PRIMARIES << 8) | (transfer << 16),
+            // a single texel per block, in a single plane
+            0,
+            format.texel_size(),
+            0,
+        ],
+    );
+    let bits = format.channel_size as u32 * 8;
+    for channel in 0..format.channels as u32 {
+        // alpha is never encoded with the sRGB transfer function
+        let (id, mut qualifiers) = match channel {
+            3 if format.srgb => (ALPHA_CHANNEL, LINEAR_QUALIFIER),
+            3 => (ALPHA_CHANNEL, 0),
+            _ => (channel, 0),
+        };
+        let (lower, upper) = if format.float {
+            qualifiers |= FLOAT_QUALIFIER | SIGNED_QUALIFIER;
+            ((-1.0f32).to_bits(), 1.0f32.to_bits())
+        } else {
+            (0, u32::MAX >> (32 - bits))
+        };
+        push_u32s(
+            &mut dfd,
+            &[
+                (channel * bits) | ((bits - 1) << 16) | ((id | qualifiers) << 24),
+                0,
+                lower,
+                upper,
+            ],
+        );
+    }
+    dfd
+}
+
+fn encode_dds(texture: &JobFileTexture, format: &FileTextureFormat, data: &[u8]) -> Vec<u8> {
+    const CAPS: u32 = 0x1;
+    const HEIGHT: u32 = 0x2;
+    const WIDTH: u32 = 0x4;
+    const PITCH: u32 = 0x8;
+    const PIXEL_FORMAT: u32 = 0x1000;
+    const FOUR_CC: u32 = 0x4;
+    const TEXTURE_CAPS: u32 = 0x1000;
+    const TEXTURE_2D: u32 = 3;
+
+    let mut file = Vec::with_capacity(148 + data.len());
+    file.extend_from_slice(b"DDS ");
+    push_u32s(
+        &mut file,
+        &[
+            124,
+            CAPS | HEIGHT | WIDTH | PITCH | PIXEL_FORMAT,
+            texture.height,
+            texture.width,
+            texture.bytes_per_row(),
+            0,
+            1,
+        ],
+    );
+    push_u32s(&mut file, &[0; 11]);
+    // the pixel format defers to the DX10 header
+    push_u32s(&mut file, &[32, FOUR_CC]);
+    file.extend_from_slice(b"DX10");
+    push_u32s(&mut file, &[0; 5]);
+    push_u32s(&mut file, &[TEXTURE_CAPS, 0, 0, 0, 0]);
+    push_u32s(&mut file, &[format.dxgi_format, TEXTURE_2D, 0, 1, 0]);
+    for row in texture.unpadded_rows(data) {
+        file.extend_from_slice(row);
+    }
+    file
+}
+
+/// Encodes and writes a job's file, logging why it failed if it does.
+fn write_file(file: JobFileOutput, data: Vec<u8>) -> JobFileWritten {
+    let result = encode(&file.format, data)
+        .and_then(|contents| write_contents(&file.path, &contents).map_err(|err| err.to_string()))
+        .map_err(|reason| {
+            error!(
+                "unable to write the output of a job to {}: {reason}",
+                file.path.display()
+            );
+            JobError::ExecutionFailed
+        });
+    JobFileWritten {
+        path: file.path,
+        result,
+    }
+}
+
+fn write_contents(path: &Path, contents: &[u8]) -> std::io::Result<()> {
+    if let Some(parent) = path
+        .parent()
+        .filter(|parent| !parent.as_os_str().is_empty())
+    {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(path, contents)
+}
+
+/// Hands the results of jobs with a [`JobFileOutput`] to the thread writing them.
+#[derive(Resource)]
+pub(super) struct JobFileWriter {
+    written: Sender<JobFileWritten>,
+    /// Sends files to the writing thread, once it's spawned.
+    files: Option<Sender<(JobFileOutput, Vec<u8>)>>,
+}
+
+impl JobFileWriter {
+    /// Writes a job's file on the writing thread, spawning it if needed.
+    pub(super) fn write(&mut self, file: JobFileOutput, data: Vec<u8>) {
+        if self.files.is_none() {
+            self.files = spawn_file_writer(self.written.clone());
+        }
+
+        let unsent = match &self.files {
+            Some(files) => files.send((file, data)).err().map(|unsent| unsent.0),
+            None => Some((file, data)),
+        };
+        // without a writing thread, the file is written on the render thread instead
+        if let Some((file, data)) = unsent {
+            self.files = None;
+            let _ = self.written.send(write_file(file, data));
+        }
+    }
+
+    /// Reports a job's file as failed, without writing it.
+    pub(super) fn fail(&self, file: JobFileOutput, error: JobError) {
+        let _ = self.written.send(JobFileWritten {
+            path: file.path,
+            result: Err(error),
+        });
+    }
+}
+
+/// Spawns a thread that writes each file it's sent, in order, and reports them as
+/// written. The thread exits once the returned sender is dropped.
+fn spawn_file_writer(written: Sender<JobFileWritten>) -> Option<Sender<(JobFileOutput, Vec<u8>)>> {
+    let (files, received) = crossbeam_channel::unbounded();
+    thread::Builder::new()
+        .name("gigs file writer".into())
+        .spawn(move || {
+            for (file, data) in received {
+                let _ = written.send(write_file(file, data));
+            }
+        })
+        .inspect_err(|error| {
+            warn!(
+                "failed to spawn the file writing thread, writing on the render thread instead: {error}"
+            );
+        })
+        .ok()
+        .map(|_| files)
+}
+
+#[derive(Resource)]
+struct JobFileWrittenReceiver(Receiver<JobFileWritten>);
+
+/// Sets up writing files for [`JobFileOutput`], once for all job types.
+pub(super) fn build_job_file_output(app: &mut App) {
+    let (written, receiver) = crossbeam_channel::unbounded();
+    app.add_plugins(ExtractComponentPlugin::<JobFileOutput>::default())
+        .add_event::<JobFileWritten>()
+        .insert_resource(JobFileWrittenReceiver(receiver))
+        .add_systems(PreUpdate, receive_written_job_files);
+
+    if let Some(render_app) = app.get_sub_app_mut(RenderApp) {
+        render_app.insert_resource(JobFileWriter {
+            written,
+            files: None,
+        });
+    }
+}
+
+fn receive_written_job_files(
+    receiver: Res<JobFileWrittenReceiver>,
+    mut events: EventWriter<JobFileWritten>,
+) {
+    events.send_batch(receiver.0.try_iter());
+}
+
+#[cfg(test)]
+mod test {
+    use core::time::Duration;
+    use std::fs;
+
+    use bevy_render::render_resource::TextureFormat;
+
+    use crate::JobError;
+
+    use super::{
+        encode, spawn_file_writer, JobFileFormat, JobFileOutput, JobFileTexture, KTX2_IDENTIFIER,
+    };
+
+    fn u32_at(file: &[u8], offset: usize) -> u32 {
+        u32::from_le_bytes(file[offset..offset + 4].try_into().unwrap())
+    }
+
+    fn u64_at(file: &[u8], offset: usize) -> u64 {
+        u64::from_le_bytes(file[offset..offset + 8].try_into().unwrap())
+    }
+
+    /// A readback of a texture whose texels are numbered in order, with padding
+    /// after each row.
+    fn readback(texture: &JobFileTexture) -> Vec<u8> {
+        let mut data = vec![0xff; texture.readback_size() as usize];
+        let bytes_per_row = texture.bytes_per_row() as usize;
+        for (y, row) in data
+            .chunks_mut(texture.padded_bytes_per_row() as usize)
+            .enumerate()
+        {
+            for (x, byte) in row[..bytes_per_row].iter_mut().enumerate() {
+                *byte = (y * bytes_per_row + x) as u8;
+            }
+        }
+        data
+    }
+
+    #[test]
+    fn ktx2_files_hold_unpadded_texels() {
+        let texture = JobFileTexture::new(3, 2, TextureFormat::Rgba8UnormSrgb);
+        assert_eq!(texture.padded_bytes_per_row(), 256);
+        let file = encode(&JobFileFormat::Ktx2(texture), readback(&texture)).unwrap();
+
+        assert_eq!(file[..12], KTX2_IDENTIFIER);
+        // the format, channel size, and size
+        assert_eq!(u32_at(&file, 12), 43);
+        assert_eq!(u32_at(&file, 16), 1);
+        assert_eq!((u32_at(&file, 20), u32_at(&file, 24)), (3, 2));
+        // a single level
+        assert_eq!(u32_at(&file, 40), 1);
+
+        let dfd_offset = u32_at(&file, 48) as usize;
+        let dfd_size = u32_at(&file, 52) as usize;
+        assert_eq!(u32_at(&file, dfd_offset), dfd_size as u32);
+        // four samples, one for each channel
+        assert_eq!(dfd_size, 4 + 24 + 4 * 16);
+
+        let level_offset = u64_at(&file, 80) as usize;
+        let level_size = u64_at(&file, 88) as usize;
+        assert_eq!(level_offset % 4, 0);
+        assert!(level_offset >= dfd_offset + dfd_size);
+        assert_eq!(level_size, 3 * 2 * 4);
+        assert_eq!(file.len(), level_offset + level_size);
+        let texels = &file[level_offset..];
+        assert!(texels.iter().enumerate().all(|(i, byte)| *byte == i as u8));
+    }
+
+    #[test]
+    fn dds_files_hold_unpadded_texels() {
+        let texture = JobFileTexture::new(5, 3, TextureFormat::R32Float);
+        let file = encode(&JobFileFormat::Dds(texture), readback(&texture)).unwrap();
+
+        assert_eq!(&file[..4], b"DDS ");
+        assert_eq!(u32_at(&file, 4), 124);
+        assert_eq!((u32_at(&file, 12), u32_at(&file, 16)), (3, 5));
+        assert_eq!(&file[84..88], b"DX10");
+        // the DX10 header's format
+        assert_eq!(u32_at(&file, 128), 41);
+        assert_eq!(file.len(), 148 + 5 * 3 * 4);
+        assert!(file[148..]
+            .iter()
+            .enumerate()
+            .all(|(i, byte)| *byte == i as u8));
+    }
+
+    #[test]
+    fn mismatched_readbacks_fail_to_encode() {
+        let texture = JobFileTexture::new(4, 4, TextureFormat::Rgba8Unorm);
+        assert!(encode(&JobFileFormat::Ktx2(texture), vec![0; 64]).is_err());
+
+        let compressed = JobFileTexture::new(4, 4, TextureFormat::Bc1RgbaUnorm);
+        assert!(encode(&JobFileFormat::Dds(compressed), vec![0; 8]).is_err());
+
+        // empty textures would otherwise be read back as no bytes at all
+        let empty = JobFileTexture::new(0, 4, TextureFormat::Rgba8Unorm);
+        assert_eq!(empty.readback_size(), 0);
+        assert!(encode(&JobFileFormat::Ktx2(empty), Vec::new()).is_err());
+        let empty = JobFileTexture::new(4, 0, TextureFormat::R32Float);
+        assert!(encode(&JobFileFormat::Dds(empty), Vec::new()).is_err());
+        let wide = JobFileTexture::new(u32::MAX / 2, 1, TextureFormat::Rgba32Float);
+        assert!(encode(&JobFileFormat::Ktx2(wide), Vec::new()).is_err());
+
+        // raw files are written as they are
+        assert_eq!(
+            encode(&JobFileFormat::Raw, vec![1, 2, 3]),
+            Ok(vec![1, 2, 3])
+        );
+    }
+
+    #[test]
+    fn files_are_written_on_the_writing_thread() {
+        let dir = std::env::temp_dir().join(format!("gigs_file_output_{}", std::process::id()));
+        let path = dir.join("nested").join("result.bin");
+        let (written, received) = crossbeam_channel::unbounded();
+        let files = spawn_file_writer(written).unwrap();
+
+        files
+            .send((JobFileOutput::new(&path, JobFileFormat::Raw), vec![4, 5, 6]))
+            .unwrap();
+        let invalid = JobFileTexture::new(2, 2, TextureFormat::Rgba8Unorm);
+        files
+            .send((
+                JobFileOutput::new(dir.join("invalid.ktx2"), JobFileFormat::Ktx2(invalid)),
+                Vec::new(),
+            ))
+            .unwrap();
+
+        let first = received.recv_timeout(Duration::from_secs(5)).unwrap();
+        assert_eq!(
+            (first.path.as_path(), first.result),
+            (path.as_path(), Ok(()))
+        );
+        assert_eq!(fs::read(&path).unwrap(), vec![4, 5, 6]);
+
+        let second = received.recv_timeout(Duration::from_secs(5)).unwrap();
+        assert_eq!(second.result, Err(JobError::ExecutionFailed));
+        assert!(!dir.join("invalid.ktx2").exists());
+
+        let _ = fs::remove_dir_all(dir);
+    }
+}
@@ -0,0 +1,129 @@
+use bevy_app::{App, Plugin};
+use bevy_ecs::{
+    query::{QueryItem, With},
+    schedule::IntoSystemConfigs,
+    system::{Query, ResMut, Resource},
+    world::World,
+};
+use bevy_render::{view::ExtractedView, Render, RenderApp, RenderSet};
+
+use crate::GraphicsJob;
+
+use super::{JobInput, JobInputStatus};
+
+/// A [`JobInput`] providing the index of the current render frame and the number of
+/// active views, for jobs that pick a slot in a ring of per-frame resources with
+/// `frame_index % N`, or allocate resources for each view.
+///
+/// Unlike [`FrameCount`](bevy_core::FrameCount), the frame index doesn't wrap, and
+/// counts render frames rather than main world updates. It starts at zero and is
+/// incremented once at the end of every render frame, so every job that runs in the
+/// same frame sees the same index.
+pub struct JobFrameInfo;
+
+/// The frame provided by [`JobFrameInfo`].
+#[derive(Copy, Clone, Default, PartialEq, Eq, Debug)]
+pub struct JobFrameInfoItem {
+    pub frame_index: u64,
+    /// The number of extracted views, including shadow views.
+    pub view_count: u32,
+}
+
+impl<J: GraphicsJob> JobInput<J> for JobFrameInfo {
+    type Data = ();
+
+    type Item<'a> = JobFrameInfoItem;
+
+    fn plugin() -> impl Plugin {
+        |app: &mut App| {
+            if !app.is_plugin_added::<JobFrameInfoPlugin>() {
+                app.add_plugins(JobFrameInfoPlugin);
+            }
+        }
+    }
+
+    fn status((): QueryItem<Self::Data>, _world: &World) -> JobInputStatus {
+        JobInputStatus::Ready
+    }
+
+    fn get<'a>((): QueryItem<'a, Self::Data>, world: &'a World) -> Self::Item<'a> {
+        world.resource::<JobFrame>().0
+    }
+}
+
+/// The current render frame, kept in the render world for [`JobFrameInfo`].
+#[derive(Resource, Default)]
+struct JobFrame(JobFrameInfoItem);
+
+struct JobFrameInfoPlugin;
+
+impl Plugin for JobFrameInfoPlugin {
+    fn build(&self, app: &mut App) {
+        if let Some(render_app) = app.get_sub_app_mut(RenderApp) {
+            render_app.init_resource::<JobFrame>().add_systems(
+                Render,
+                (
+                    count_job_frame_views.in_set(RenderSet::Prepare),
+                    advance_job_frame.in_set(RenderSet::Cleanup),
+                ),
+            );
+        }
+    }
+}
+
+fn count_job_frame_views(views: Query<(), With<ExtractedView>>, mut frame: ResMut<JobFrame>) {
+    frame.0.view_count = views.iter().len() as u32;
+}
+
+fn advance_job_frame(mut frame: ResMut<JobFrame>) {
+    frame.0.frame_index += 1;
+}
+
+#[cfg(test)]
+mod test {
+    use bevy_ecs::{
+        component::Component,
+        schedule::{IntoSystemConfigs, Schedule},
+        world::World,
+    };
+
+    use crate::{
+        input::{JobInput, JobInputItem},
+        GraphicsJob, JobError, JobRunContext,
+    };
+
+    use super::{advance_job_frame, count_job_frame_views, JobFrame, JobFrameInfo};
+
+    #[derive(Clone, Component)]
+    struct RingJob;
+
+    impl GraphicsJob for RingJob {
+        type In = JobFrameInfo;
+
+        fn run(
+            &self,
+            _world: &World,
+            _context: &mut JobRunContext,
+            _input: JobInputItem<Self, Self::In>,
+        ) -> Result<(), JobError> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn frame_index_advances_once_per_frame() {
+        let mut world = World::new();
+        world.init_resource::<JobFrame>();
+
+        let mut render = Schedule::default();
+        let mut indices = Vec::new();
+        render.add_systems((count_job_frame_views, advance_job_frame).chain());
+
+        for _ in 0..3 {
+            indices.push(<JobFrameInfo as JobInput<RingJob>>::get((), &world).frame_index);
+            render.run(&mut world);
+        }
+        assert_eq!(indices, [0, 1, 2]);
+        assert_eq!(world.resource::<JobFrame>().0.view_count, 0);
+    }
+}
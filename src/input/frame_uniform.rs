@@ -0,0 +1,182 @@
+use core::marker::PhantomData;
+
+use bevy_app::{App, Plugin};
+use bevy_ecs::{
+    query::QueryItem,
+    schedule::IntoSystemConfigs,
+    system::{Res, ResMut, Resource},
+    world::World,
+};
+use bevy_render::{
+    render_resource::{encase::internal::WriteInto, Buffer, ShaderType, UniformBuffer},
+    renderer::{RenderDevice, RenderQueue},
+    view::ExtractedWindows,
+    Render, RenderApp, RenderSet,
+};
+
+use crate::{
+    device::add_device_reset,
+    memory::{JobMemoryAllocation, JobMemoryCategory, JobMemoryUsage},
+    GraphicsJob,
+};
+
+use super::{JobInput, JobInputStatus};
+
+/// The frame latency windows use when they don't set one, matching `bevy_render`.
+const DEFAULT_FRAME_LATENCY: u32 = 2;
+
+/// A [`JobInput`] providing a uniform buffer holding the current value of the render
+/// world resource `T`, for uniforms that change every frame, like a time or a camera
+/// position.
+///
+/// The value is written into a ring of buffers, one for each frame that may be in
+/// flight, so that writing this frame's value never touches a buffer the GPU may
+/// still be reading for a previous frame. The ring holds one more buffer than the
+/// largest `desired_maximum_frame_latency` of the app's windows.
+///
+/// `T` must be inserted into the render world, for example with
+/// [`ExtractResourcePlugin`](bevy_render::extract_resource::ExtractResourcePlugin),
+/// and the job waits until it has been written for the first time.
+pub struct JobFrameUniform<T>(PhantomData<T>);
+
+impl<J: GraphicsJob, T: Resource + ShaderType + WriteInto + Clone> JobInput<J>
+    for JobFrameUniform<T>
+{
+    type Data = ();
+
+    type Item<'a> = &'a Buffer;
+
+    fn plugin() -> impl Plugin {
+        |app: &mut App| {
+            if !app.is_plugin_added::<JobFrameUniformPlugin<T>>() {
+                app.add_plugins(JobFrameUniformPlugin::<T>(PhantomData));
+            }
+        }
+    }
+
+    fn status((): QueryItem<Self::Data>, world: &World) -> JobInputStatus {
+        match world
+            .get_resource::<JobFrameUniforms<T>>()
+            .and_then(JobFrameUniforms::current)
+        {
+            Some(_) => JobInputStatus::Ready,
+            None => JobInputStatus::Wait,
+        }
+    }
+
+    fn get<'a>((): QueryItem<'a, Self::Data>, world: &'a World) -> Self::Item<'a> {
+        world
+            .resource::<JobFrameUniforms<T>>()
+            .current()
+            .expect("frame uniform should be ready by this point")
+    }
+}
+
+struct JobFrameUniformPlugin<T>(PhantomData<T>);
+
+impl<T: Resource + ShaderType + WriteInto + Clone> Plugin for JobFrameUniformPlugin<T> {
+    fn build(&self, app: &mut App) {
+        if let Some(render_app) = app.get_sub_app_mut(RenderApp) {
+            render_app
+                .init_resource::<JobFrameUniforms<T>>()
+                .add_systems(
+                    Render,
+                    prepare_job_frame_uniforms::<T>.in_set(RenderSet::PrepareResources),
+                );
+        }
+
+        add_device_reset(app, reset_job_frame_uniforms::<T>);
+    }
+}
+
+/// The ring of buffers written by [`JobFrameUniform<T>`].
+#[derive(Resource)]
+struct JobFrameUniforms<T: ShaderType> {
+    buffers: Vec<UniformBuffer<T>>,
+    /// The slot written this frame, or `None` if the value hasn't been written yet.
+    current: Option<usize>,
+    memory: Option<JobMemoryAllocation>,
+}
+
+impl<T: ShaderType> Default for JobFrameUniforms<T> {
+    fn default() -> Self {
+        Self {
+            buffers: Vec::new(),
+            current: None,
+            memory: None,
+        }
+    }
+}
+
+impl<T: ShaderType + WriteInto> JobFrameUniforms<T> {
+    fn current(&self) -> Option<&Buffer> {
+        self.buffers[self.current?].buffer()
+    }
+}
+
+/// Returns the index of the slot to write next, in a ring of `len` slots.
+fn next_slot(current: Option<usize>, len: usize) -> usize {
+    current.map_or(0, |current| (current + 1) % len)
+}
+
+fn prepare_job_frame_uniforms<T: Resource + ShaderType + WriteInto + Clone>(
+    value: Option<Res<T>>,
+    windows: Option<Res<ExtractedWindows>>,
+    mut uniforms: ResMut<JobFrameUniforms<T>>,
+    render_device: Res<RenderDevice>,
+    render_queue: Res<RenderQueue>,
+    memory_usage: Res<JobMemoryUsage>,
+) {
+    let Some(value) = value else {
+        return;
+    };
+
+    let frame_latency = windows
+        .iter()
+        .flat_map(|windows| windows.windows.values())
+        .map(|window| {
+            window
+                .desired_maximum_frame_latency
+                .map_or(DEFAULT_FRAME_LATENCY, |latency| latency.get())
+        })
+        .max()
+        .unwrap_or(DEFAULT_FRAME_LATENCY);
+    let frames_in_flight = frame_latency as usize + 1;
+    if uniforms.buffers.len() < frames_in_flight {
+        uniforms.buffers.resize_with(frames_in_flight, || {
+            let mut buffer = UniformBuffer::from(value.clone());
+            buffer.set_label(Some("job_frame_uniform"));
+            buffer
+        });
+        uniforms.memory = Some(memory_usage.allocate(
+            JobMemoryCategory::UniformBuffers,
+            T::min_size().get() * frames_in_flight as u64,
+        ));
+    }
+
+    let slot = next_slot(uniforms.current, uniforms.buffers.len());
+    let buffer = &mut uniforms.buffers[slot];
+    buffer.set(value.clone());
+    buffer.write_buffer(&render_device, &render_queue);
+    uniforms.current = Some(slot);
+}
+
+fn reset_job_frame_uniforms<T: Resource + ShaderType + WriteInto + Clone>(world: &mut World) {
+    if let Some(mut uniforms) = world.get_resource_mut::<JobFrameUniforms<T>>() {
+        *uniforms = JobFrameUniforms::default();
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::next_slot;
+
+    #[test]
+    fn slots_wrap_around_the_ring() {
+        assert_eq!(next_slot(None, 3), 0);
+        assert_eq!(next_slot(Some(0), 3), 1);
+        assert_eq!(next_slot(Some(2), 3), 0);
+        // a ring that grew keeps going from the current slot
+        assert_eq!(next_slot(Some(2), 4), 3);
+    }
+}
@@ -0,0 +1,210 @@
+use bevy_app::{App, Plugin};
+use bevy_core_pipeline::prepass::{DeferredPrepass, ViewPrepassTextures};
+use bevy_ecs::{
+    component::Component,
+    entity::Entity,
+    query::QueryItem,
+    system::lifetimeless::Read,
+    world::{EntityRef, World},
+};
+use bevy_render::{
+    render_resource::{Extent3d, TextureView},
+    ExtractSchedule, RenderApp,
+};
+
+use crate::GraphicsJob;
+
+use super::{
+    camera_view::{extract_camera_views, view_feature_status, CameraViewInput},
+    JobInput, JobInputStatus,
+};
+
+/// A [`JobInput`] providing the G-buffer of a camera rendered with bevy's deferred
+/// renderer, for example for custom deferred lighting or screen-space reflections.
+/// The job waits until the camera's prepass textures are prepared, and fails if the
+/// camera renders in forward mode, without a [`DeferredPrepass`].
+///
+/// Jobs run before the render graph, so the G-buffer holds the previous frame.
+#[derive(Copy, Clone, Component, PartialEq, Eq, Debug)]
+pub struct JobGBuffer(pub Entity);
+
+impl JobGBuffer {
+    /// Targets the given main world camera.
+    pub const fn new(camera: Entity) -> Self {
+        Self(camera)
+    }
+}
+
+/// The render world view entity targeted by a job's [`JobGBuffer`].
+#[derive(Copy, Clone, Component)]
+#[doc(hidden)]
+pub struct ExtractedJobGBuffer(pub Entity);
+
+impl<J: GraphicsJob> JobInput<J> for JobGBuffer {
+    type Data = Option<Read<ExtractedJobGBuffer>>;
+
+    type Item<'a> = JobGBufferItem<'a>;
+
+    fn plugin() -> impl Plugin {
+        |app: &mut App| {
+            if !app.is_plugin_added::<JobGBufferPlugin>() {
+                app.add_plugins(JobGBufferPlugin);
+            }
+        }
+    }
+
+    fn status(data: QueryItem<Self::Data>, world: &World) -> JobInputStatus {
+        view_feature_status::<DeferredPrepass>(data.map(|view| view.0), world, |view| {
+            JobGBufferItem::new(view).is_some()
+        })
+    }
+
+    fn get<'a>(data: QueryItem<'a, Self::Data>, world: &'a World) -> Self::Item<'a> {
+        let view = world
+            .get_entity(data.expect("G-buffer should be ready by this point").0)
+            .expect("G-buffer should be ready by this point");
+        JobGBufferItem::new(view).expect("G-buffer should be ready by this point")
+    }
+}
+
+/// The G-buffer provided by [`JobGBuffer`].
+pub struct JobGBufferItem<'a> {
+    /// The render world view entity, for looking up other view components.
+    pub view: EntityRef<'a>,
+    /// Bevy's packed G-buffer, an `Rgba32Uint` texture with each of its channels packed
+    /// into one component. See [`GBufferChannel`]. All channels are unpacked at once
+    /// with `bevy_pbr::pbr_deferred_functions::pbr_input_from_deferred_gbuffer`.
+    pub deferred: &'a TextureView,
+    /// The deferred lighting pass id of the material drawn to each pixel.
+    pub lighting_pass_id: &'a TextureView,
+    /// The prepass depth, if the camera also has a
+    /// [`DepthPrepass`](bevy_core_pipeline::prepass::DepthPrepass).
+    pub depth: Option<&'a TextureView>,
+    /// The prepass world space normals, if the camera also has a
+    /// [`NormalPrepass`](bevy_core_pipeline::prepass::NormalPrepass).
+    pub normal: Option<&'a TextureView>,
+    /// The prepass motion vectors, if the camera also has a
+    /// [`MotionVectorPrepass`](bevy_core_pipeline::prepass::MotionVectorPrepass).
+    pub motion_vectors: Option<&'a TextureView>,
+    /// The size of every G-buffer texture.
+    pub size: Extent3d,
+}
+
+/// A channel of bevy's deferred G-buffer, each packed into one component of the
+/// [`deferred`](JobGBufferItem::deferred) texture.
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+pub enum GBufferChannel {
+    /// The sRGB base color and perceptual roughness, packed as `unorm4x8`.
+    BaseColor,
+    /// The emissive color, packed as `rgb9e5`. Unlit materials store their
+    /// base color here instead.
+    Emissive,
+    /// The reflectance, metallic, and diffuse occlusion, packed as `unorm4x8`.
+    /// On WebGL, depth is packed into the last 20 bits instead.
+    Material,
+    /// The octahedral-encoded world space normal in the low 24 bits, as two 12-bit
+    /// unorms, and the material's deferred flags in the high 8 bits.
+    Normal,
+}
+
+impl GBufferChannel {
+    /// The index of the component of the packed texture holding this channel.
+    pub const fn component(self) -> usize {
+        match self {
+            GBufferChannel::BaseColor => 0,
+            GBufferChannel::Emissive => 1,
+            GBufferChannel::Material => 2,
+            GBufferChannel::Normal => 3,
+        }
+    }
+}
+
+impl<'a> JobGBufferItem<'a> {
+    fn new(view: EntityRef<'a>) -> Option<Self> {
+        let textures = view.get::<ViewPrepassTextures>()?;
+        Some(Self {
+            view,
+            deferred: textures.deferred_view()?,
+            lighting_pass_id: &textures
+                .deferred_lighting_pass_id
+                .as_ref()?
+                .texture
+                .default_view,
+            depth: textures.depth_view(),
+            normal: textures.normal_view(),
+            motion_vectors: textures.motion_vectors_view(),
+            size: textures.size,
+        })
+    }
+}
+
+struct JobGBufferPlugin;
+
+impl Plugin for JobGBufferPlugin {
+    fn build(&self, app: &mut App) {
+        if let Some(render_app) = app.get_sub_app_mut(RenderApp) {
+            render_app.add_systems(ExtractSchedule, extract_camera_views::<JobGBuffer>);
+        }
+    }
+}
+
+impl CameraViewInput for JobGBuffer {
+    type Extracted = ExtractedJobGBuffer;
+
+    type Data = ();
+
+    fn camera(&self) -> Entity {
+        self.0
+    }
+
+    fn extract(&self, view: Entity, (): ()) -> Self::Extracted {
+        ExtractedJobGBuffer(view)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use bevy_core_pipeline::prepass::DeferredPrepass;
+    use bevy_ecs::{component::Component, world::World};
+
+    use crate::{
+        input::{
+            camera_view::{assert_waits_for_view, test_view},
+            JobInput, JobInputItem, JobInputStatus,
+        },
+        GraphicsJob, JobError, JobRunContext,
+    };
+
+    use super::{ExtractedJobGBuffer, JobGBuffer};
+
+    #[derive(Clone, Component)]
+    struct LightingJob;
+
+    impl GraphicsJob for LightingJob {
+        type In = JobGBuffer;
+
+        fn run(
+            &self,
+            _world: &World,
+            _context: &mut JobRunContext,
+            _input: JobInputItem<Self, Self::In>,
+        ) -> Result<(), JobError> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn forward_views_fail() {
+        let mut world = World::new();
+        let status = <JobGBuffer as JobInput<LightingJob>>::status;
+        let (forward, gbuffer) = assert_waits_for_view(&mut world, status, ExtractedJobGBuffer);
+
+        world.entity_mut(forward).insert(test_view(false));
+        assert_eq!(status(Some(&gbuffer), &world), JobInputStatus::Fail);
+
+        // the prepass textures aren't prepared yet
+        let deferred = world.spawn((test_view(false), DeferredPrepass)).id();
+        let gbuffer = ExtractedJobGBuffer(deferred);
+        assert_eq!(status(Some(&gbuffer), &world), JobInputStatus::Wait);
+    }
+}
@@ -0,0 +1,304 @@
+use core::any::TypeId;
+use std::sync::OnceLock;
+
+use bevy_app::{App, Plugin};
+use bevy_ecs::{
+    component::Tick,
+    query::QueryItem,
+    schedule::IntoSystemConfigs,
+    system::Resource,
+    world::{Mut, World},
+};
+use bevy_render::{
+    render_resource::{
+        BindGroup, BindGroupEntry, BindGroupLayout, BindGroupLayoutEntry,
+        BindGroupLayoutEntryBuilder, BindingResource, ShaderStages,
+    },
+    renderer::RenderDevice,
+    Render, RenderApp, RenderSet,
+};
+
+use crate::{device::add_device_reset, GraphicsJob};
+
+use super::{JobInput, JobInputStatus};
+
+/// A render world [`Resource`] that provides one entry of the frame-global bind group,
+/// for bindings shared by many jobs, like global uniforms or a shared noise texture.
+///
+/// Register it with [`register_job_global_binding`](RegisterJobGlobalBindingExt::register_job_global_binding)
+/// and read the shared group with [`JobGlobalBindGroup`].
+pub trait JobGlobalBinding: Resource {
+    /// The layout of this entry. Unless a visibility is set on the builder, the entry
+    /// is visible to every shader stage.
+    fn layout_entry() -> BindGroupLayoutEntryBuilder;
+
+    /// Returns the bound resource, or `None` if it isn't ready yet.
+    fn binding(&self) -> Option<BindingResource<'_>>;
+}
+
+/// An extension trait for registering entries of the frame-global bind group on [`App`].
+pub trait RegisterJobGlobalBindingExt {
+    /// Adds `R` as the next entry of the frame-global bind group, so its binding index is
+    /// the number of entries registered before it. Registering the same resource again
+    /// does nothing.
+    ///
+    /// Entries must be registered while building the app, before the layout is created.
+    fn register_job_global_binding<R: JobGlobalBinding>(&mut self) -> &mut Self;
+}
+
+impl RegisterJobGlobalBindingExt for App {
+    fn register_job_global_binding<R: JobGlobalBinding>(&mut self) -> &mut Self {
+        init_job_global_bindings(self);
+        if let Some(render_app) = self.get_sub_app_mut(RenderApp) {
+            render_app
+                .world_mut()
+                .resource_mut::<JobGlobalBindings>()
+                .register::<R>();
+        }
+        self
+    }
+}
+
+struct JobGlobalEntry {
+    type_id: TypeId,
+    layout_entry: BindGroupLayoutEntry,
+    binding: for<'w> fn(&'w World) -> Option<BindingResource<'w>>,
+    changed: fn(&World, Tick, Tick) -> bool,
+}
+
+/// The registered entries of the frame-global bind group, and the group itself.
+///
+/// The group is prepared once in [`RenderSet::PrepareBindGroups`], and only rebuilt
+/// when one of its entries' resources changed since it was last built. It isn't
+/// available until every entry's [`binding`](JobGlobalBinding::binding) is ready.
+#[derive(Resource, Default)]
+pub struct JobGlobalBindings {
+    entries: Vec<JobGlobalEntry>,
+    layout: OnceLock<BindGroupLayout>,
+    bind_group: Option<BindGroup>,
+    last_build: Tick,
+    generation: u32,
+}
+
+impl JobGlobalBindings {
+    fn register<R: JobGlobalBinding>(&mut self) {
+        let type_id = TypeId::of::<R>();
+        if self.entries.iter().any(|entry| entry.type_id == type_id) {
+            return;
+        }
+        assert!(
+            self.layout.get().is_none(),
+            "job global bindings must be registered before the layout is created"
+        );
+
+        self.entries.push(JobGlobalEntry {
+            type_id,
+            layout_entry: R::layout_entry().build(self.entries.len() as u32, ShaderStages::all()),
+            binding: |world| world.get_resource::<R>().and_then(R::binding),
+            changed: |world, last_run, this_run| match world.get_resource_change_ticks::<R>() {
+                Some(ticks) => ticks.is_changed(last_run, this_run),
+                None => true,
+            },
+        });
+    }
+
+    /// The binding index of `R` in the group, if it's registered.
+    pub fn index_of<R: JobGlobalBinding>(&self) -> Option<u32> {
+        let type_id = TypeId::of::<R>();
+        self.entries
+            .iter()
+            .position(|entry| entry.type_id == type_id)
+            .map(|index| index as u32)
+    }
+
+    /// The layout of the group, for creating the pipelines of jobs that bind it.
+    pub fn layout(&self, render_device: &RenderDevice) -> &BindGroupLayout {
+        self.layout.get_or_init(|| {
+            let entries = self
+                .entries
+                .iter()
+                .map(|entry| entry.layout_entry)
+                .collect::<Vec<_>>();
+            render_device.create_bind_group_layout("job_global_bind_group_layout", &entries)
+        })
+    }
+
+    /// The group prepared this frame, if every entry is ready.
+    pub fn bind_group(&self) -> Option<&BindGroup> {
+        self.bind_group.as_ref()
+    }
+
+    /// The number of times the group has been built.
+    pub fn generation(&self) -> u32 {
+        self.generation
+    }
+
+    fn needs_rebuild(&self, world: &World, this_run: Tick) -> bool {
+        self.bind_group.is_none()
+            || self
+                .entries
+                .iter()
+                .any(|entry| (entry.changed)(world, self.last_build, this_run))
+    }
+}
+
+/// A [`JobInput`] providing the frame-global bind group, shared by every job that
+/// reads it, instead of each job creating its own group for common bindings.
+///
+/// Create the job's pipeline with [`JobGlobalBindings::layout`] at the group index of
+/// your choice. The job waits until every registered entry is ready.
+pub struct JobGlobalBindGroup;
+
+impl<J: GraphicsJob> JobInput<J> for JobGlobalBindGroup {
+    type Data = ();
+
+    type Item<'a> = &'a BindGroup;
+
+    fn plugin() -> impl Plugin {
+        |app: &mut App| init_job_global_bindings(app)
+    }
+
+    fn status((): QueryItem<Self::Data>, world: &World) -> JobInputStatus {
+        match world
+            .get_resource::<JobGlobalBindings>()
+            .and_then(JobGlobalBindings::bind_group)
+        {
+            Some(_) => JobInputStatus::Ready,
+            None => JobInputStatus::Wait,
+        }
+    }
+
+    fn get<'a>((): QueryItem<'a, Self::Data>, world: &'a World) -> Self::Item<'a> {
+        world
+            .resource::<JobGlobalBindings>()
+            .bind_group()
+            .expect("global bind group should be ready by this point")
+    }
+}
+
+fn init_job_global_bindings(app: &mut App) {
+    let Some(render_app) = app.get_sub_app_mut(RenderApp) else {
+        return;
+    };
+    if render_app.world().contains_resource::<JobGlobalBindings>() {
+        return;
+    }
+
+    render_app.init_resource::<JobGlobalBindings>().add_systems(
+        Render,
+        prepare_job_global_bind_group.in_set(RenderSet::PrepareBindGroups),
+    );
+    add_device_reset(app, reset_job_global_bind_group);
+}
+
+fn prepare_job_global_bind_group(world: &mut World) {
+    let this_run = world.change_tick();
+    world.resource_scope(|world, mut globals: Mut<JobGlobalBindings>| {
+        if !globals.needs_rebuild(world, this_run) {
+            return;
+        }
+
+        let bindings = globals
+            .entries
+            .iter()
+            .map(|entry| (entry.binding)(world))
+            .collect::<Option<Vec<_>>>();
+        let Some(bindings) = bindings else {
+            globals.bind_group = None;
+            return;
+        };
+
+        let entries = bindings
+            .into_iter()
+            .enumerate()
+            .map(|(index, resource)| BindGroupEntry {
+                binding: index as u32,
+                resource,
+            })
+            .collect::<Vec<_>>();
+        let render_device = world.resource::<RenderDevice>();
+        let bind_group = render_device.create_bind_group(
+            "job_global_bind_group",
+            globals.layout(render_device),
+            &entries,
+        );
+
+        globals.bind_group = Some(bind_group);
+        globals.last_build = this_run;
+        globals.generation += 1;
+    });
+}
+
+fn reset_job_global_bind_group(world: &mut World) {
+    if let Some(mut globals) = world.get_resource_mut::<JobGlobalBindings>() {
+        globals.bind_group = None;
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use bevy_ecs::{change_detection::DetectChangesMut, system::Resource, world::World};
+    use bevy_render::render_resource::{
+        binding_types::uniform_buffer, BindGroupLayoutEntryBuilder, BindingResource,
+    };
+
+    use super::{JobGlobalBinding, JobGlobalBindings};
+
+    #[derive(Resource)]
+    struct GlobalParams;
+
+    impl JobGlobalBinding for GlobalParams {
+        fn layout_entry() -> BindGroupLayoutEntryBuilder {
+            uniform_buffer::<u32>(false)
+        }
+
+        fn binding(&self) -> Option<BindingResource<'_>> {
+            None
+        }
+    }
+
+    #[derive(Resource)]
+    struct GlobalNoise;
+
+    impl JobGlobalBinding for GlobalNoise {
+        fn layout_entry() -> BindGroupLayoutEntryBuilder {
+            uniform_buffer::<u32>(false)
+        }
+
+        fn binding(&self) -> Option<BindingResource<'_>> {
+            None
+        }
+    }
+
+    #[test]
+    fn entries_are_indexed_in_registration_order() {
+        let mut globals = JobGlobalBindings::default();
+        globals.register::<GlobalNoise>();
+        globals.register::<GlobalParams>();
+        globals.register::<GlobalNoise>();
+
+        assert_eq!(globals.index_of::<GlobalNoise>(), Some(0));
+        assert_eq!(globals.index_of::<GlobalParams>(), Some(1));
+        assert_eq!(globals.entries.len(), 2);
+    }
+
+    #[test]
+    fn rebuilds_only_on_change() {
+        let mut world = World::new();
+        world.insert_resource(GlobalParams);
+        let mut globals = JobGlobalBindings::default();
+        globals.register::<GlobalParams>();
+        let changed = globals.entries[0].changed;
+
+        // nothing has been built yet
+        assert!(globals.needs_rebuild(&world, world.read_change_tick()));
+
+        // the group was built after the resource was inserted
+        let last_build = world.increment_change_tick();
+        world.increment_change_tick();
+        assert!(!changed(&world, last_build, world.read_change_tick()));
+
+        world.resource_mut::<GlobalParams>().set_changed();
+        assert!(changed(&world, last_build, world.read_change_tick()));
+    }
+}
@@ -0,0 +1,413 @@
+use bevy_app::{App, Plugin};
+use bevy_asset::Handle;
+use bevy_ecs::{
+    component::Component,
+    entity::Entity,
+    query::{QueryItem, Without},
+    schedule::IntoSystemConfigs,
+    system::{lifetimeless::Read, Commands, Query, Res, ResMut, Resource},
+    world::World,
+};
+use bevy_image::Image;
+use bevy_render::{
+    extract_component::{ExtractComponent, ExtractComponentPlugin},
+    render_asset::RenderAssets,
+    render_resource::{
+        CommandEncoder, CommandEncoderDescriptor, Extent3d, ImageCopyTexture, Origin3d, Texture,
+        TextureAspect, TextureDescriptor, TextureDimension, TextureFormat, TextureId,
+        TextureUsages, TextureView, TextureViewDescriptor, TextureViewDimension,
+    },
+    renderer::{RenderDevice, RenderQueue},
+    texture::GpuImage,
+    Render, RenderApp, RenderSet,
+};
+use bevy_utils::{tracing::error, HashMap};
+
+use crate::{
+    device::{add_device_reset, remove_all},
+    memory::{texture_bytes, JobMemoryAllocation, JobMemoryCategory, JobMemoryUsage},
+    GraphicsJob,
+};
+
+use super::{JobInput, JobInputStatus};
+
+/// A [`JobInput`] providing a 2D array view of several images, for example a set of
+/// light cookies to be sampled by layer.
+///
+/// The array is either an image that's already an array texture, or is assembled by
+/// copying every mip level of each image into its own layer, once they're all
+/// prepared in the render world. Images copied into an array must be 2D, have a single
+/// layer, the same size, format and mip level count, and [`TextureUsages::COPY_SRC`].
+/// Otherwise, the job fails. Assembled arrays are cached between jobs, and assembled
+/// again if any of their images are re-uploaded.
+#[derive(Clone, Component, PartialEq, Eq, Hash, Debug)]
+pub enum JobImageArray {
+    /// An image that's already a 2D array texture.
+    Image(Handle<Image>),
+    /// Images to copy into the layers of a new array texture, in order.
+    Layers(Vec<Handle<Image>>),
+}
+
+impl JobImageArray {
+    /// The images the array is prepared from.
+    fn images(&self) -> &[Handle<Image>] {
+        match self {
+            JobImageArray::Image(image) => core::slice::from_ref(image),
+            JobImageArray::Layers(layers) => layers,
+        }
+    }
+
+    /// The layout of the array, or `None` if any of its images aren't prepared yet.
+    fn layout(&self, images: &RenderAssets<GpuImage>) -> Option<Result<ArrayLayout, &'static str>> {
+        match self {
+            JobImageArray::Image(image) => {
+                let texture = &images.get(image)?.texture;
+                Some(match texture.dimension() {
+                    TextureDimension::D2 => Ok(ArrayLayout::of(texture)),
+                    _ => Err("the image isn't a 2D texture"),
+                })
+            }
+            JobImageArray::Layers(layers) => {
+                let layers = layers
+                    .iter()
+                    .map(|layer| images.get(layer).map(|image| &image.texture))
+                    .collect::<Option<Vec<_>>>()?;
+                Some(layers_layout(layers.iter().map(|texture| {
+                    (
+                        ArrayLayout::of(texture),
+                        texture.dimension(),
+                        texture.usage(),
+                    )
+                })))
+            }
+        }
+    }
+}
+
+impl<J: GraphicsJob> JobInput<J> for JobImageArray {
+    type Data = (Read<JobImageArray>, Option<Read<PreparedJobImageArray>>);
+
+    type Item<'a> = &'a PreparedJobImageArray;
+
+    fn plugin() -> impl Plugin {
+        |app: &mut App| {
+            if !app.is_plugin_added::<ExtractComponentPlugin<JobImageArray>>() {
+                app.add_plugins(ExtractComponentPlugin::<JobImageArray>::default());
+
+                if let Some(render_app) = app.get_sub_app_mut(RenderApp) {
+                    render_app
+                        .init_resource::<JobImageArrayCache>()
+                        .add_systems(
+                            Render,
+                            prepare_job_image_arrays.in_set(RenderSet::PrepareResources),
+                        );
+                }
+
+                add_device_reset(app, reset_job_image_arrays);
+            }
+        }
+    }
+
+    fn status((array, prepared): QueryItem<Self::Data>, world: &World) -> JobInputStatus {
+        if prepared.is_some() {
+            return JobInputStatus::Ready;
+        }
+
+        match array.layout(world.resource::<RenderAssets<GpuImage>>()) {
+            Some(Err(reason)) => {
+                error!("unable to assemble an image array: {reason}");
+                JobInputStatus::Fail
+            }
+            // the array is assembled while preparing resources
+            _ => JobInputStatus::Wait,
+        }
+    }
+
+    fn get<'a>((_, prepared): QueryItem<'a, Self::Data>, _world: &'a World) -> Self::Item<'a> {
+        prepared.expect("image array should be ready by this point")
+    }
+}
+
+impl ExtractComponent for JobImageArray {
+    type QueryData = Read<JobImageArray>;
+
+    type QueryFilter = ();
+
+    type Out = JobImageArray;
+
+    fn extract_component(item: QueryItem<'_, Self::QueryData>) -> Option<Self::Out> {
+        Some(item.clone())
+    }
+}
+
+/// The array texture provided to a job by [`JobImageArray`], along with a
+/// [`D2Array`](TextureViewDimension::D2Array) view of every layer.
+#[derive(Component)]
+pub struct PreparedJobImageArray {
+    pub texture: Texture,
+    pub view: TextureView,
+}
+
+/// The size, format and mip level count shared by every layer of an array.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+struct ArrayLayout {
+    width: u32,
+    height: u32,
+    layers: u32,
+    format: TextureFormat,
+    mip_level_count: u32,
+}
+
+impl ArrayLayout {
+    fn of(texture: &Texture) -> Self {
+        Self {
+            width: texture.width(),
+            height: texture.height(),
+            layers: texture.depth_or_array_layers(),
+            format: texture.format(),
+            mip_level_count: texture.mip_level_count(),
+        }
+    }
+}
+
+/// Checks that the textures copied into an array's layers can share it, and returns
+/// the layout of the array.
+fn layers_layout(
+    layers: impl IntoIterator<Item = (ArrayLayout, TextureDimension, TextureUsages)>,
+) -> Result<ArrayLayout, &'static str> {
+    let mut array: Option<ArrayLayout> = None;
+    for (layout, dimension, usage) in layers {
+        if dimension != TextureDimension::D2 || layout.layers != 1 {
+            return Err("every image must be a 2D texture with a single layer");
+        }
+        if !usage.contains(TextureUsages::COPY_SRC) {
+            return Err("every image must have `TextureUsages::COPY_SRC`");
+        }
+
+        match &mut array {
+            None => array = Some(layout),
+            Some(array) => {
+                if (layout.width, layout.height) != (array.width, array.height) {
+                    return Err("the images don't all have the same size");
+                }
+                if layout.format != array.format {
+                    return Err("the images don't all have the same format");
+                }
+                if layout.mip_level_count != array.mip_level_count {
+                    return Err("the images don't all have the same mip level count");
+                }
+                array.layers += 1;
+            }
+        }
+    }
+    array.ok_or("there are no images to assemble the array from")
+}
+
+/// Arrays prepared by [`JobImageArray`], along with the textures of the images they
+/// were prepared from, so they can be dropped once any of the images is removed or
+/// re-uploaded.
+#[derive(Resource, Default)]
+struct JobImageArrayCache(
+    HashMap<
+        JobImageArray,
+        (
+            Vec<TextureId>,
+            Texture,
+            TextureView,
+            Option<JobMemoryAllocation>,
+        ),
+    >,
+);
+
+fn prepare_job_image_arrays(
+    jobs: Query<(Entity, &JobImageArray), Without<PreparedJobImageArray>>,
+    images: Res<RenderAssets<GpuImage>>,
+    mut cache: ResMut<JobImageArrayCache>,
+    render_device: Res<RenderDevice>,
+    render_queue: Res<RenderQueue>,
+    memory_usage: Res<JobMemoryUsage>,
+    mut commands: Commands,
+) {
+    cache.0.retain(|array, (textures, ..)| {
+        array.images().iter().zip(textures).all(|(image, texture)| {
+            images
+                .get(image)
+                .is_some_and(|image| image.texture.id() == *texture)
+        })
+    });
+
+    let mut encoder = None;
+    for (entity, array) in &jobs {
+        let Some(Ok(layout)) = array.layout(&images) else {
+            continue;
+        };
+
+        let (_, texture, view, _) = cache.0.entry(array.clone()).or_insert_with(|| {
+            // arrays viewing a single image use its own texture, so only assembled
+            // arrays hold memory of their own
+            let (texture, sources, memory) = match array {
+                JobImageArray::Image(image) => {
+                    let texture = images.get(image).unwrap().texture.clone();
+                    (texture.clone(), vec![texture.id()], None)
+                }
+                JobImageArray::Layers(layers) => {
+                    let sources = layers
+                        .iter()
+                        .map(|layer| &images.get(layer).unwrap().texture)
+                        .collect::<Vec<_>>();
+                    let encoder = encoder.get_or_insert_with(|| {
+                        render_device.create_command_encoder(&CommandEncoderDescriptor {
+                            label: Some("job_image_array_encoder"),
+                        })
+                    });
+                    let texture = assemble_image_array(&render_device, encoder, &sources, layout);
+                    let memory = memory_usage.allocate(
+                        JobMemoryCategory::Persistent,
+                        texture_bytes(&TextureDescriptor {
+                            label: None,
+                            size: texture.size(),
+                            mip_level_count: texture.mip_level_count(),
+                            sample_count: texture.sample_count(),
+                            dimension: texture.dimension(),
+                            format: texture.format(),
+                            usage: texture.usage(),
+                            view_formats: &[],
+                        }),
+                    );
+                    let sources = sources.iter().map(|source| source.id()).collect();
+                    (texture, sources, Some(memory))
+                }
+            };
+
+            let view = texture.create_view(&TextureViewDescriptor {
+                label: Some("job_image_array_view"),
+                dimension: Some(TextureViewDimension::D2Array),
+                ..Default::default()
+            });
+            (sources, texture, view, memory)
+        });
+
+        commands.entity(entity).insert(PreparedJobImageArray {
+            texture: texture.clone(),
+            view: view.clone(),
+        });
+    }
+
+    // the copies are submitted before any job runs this frame
+    if let Some(encoder) = encoder {
+        render_queue.submit([encoder.finish()]);
+    }
+}
+
+/// Creates an array texture, and records copying every mip level of each source
+/// texture into its own layer.
+fn assemble_image_array(
+    render_device: &RenderDevice,
+    encoder: &mut CommandEncoder,
+    sources: &[&Texture],
+    layout: ArrayLayout,
+) -> Texture {
+    let size = Extent3d {
+        width: layout.width,
+        height: layout.height,
+        depth_or_array_layers: layout.layers,
+    };
+    let texture = render_device.create_texture(&TextureDescriptor {
+        label: Some("job_image_array"),
+        size,
+        mip_level_count: layout.mip_level_count,
+        sample_count: 1,
+        dimension: TextureDimension::D2,
+        format: layout.format,
+        usage: TextureUsages::TEXTURE_BINDING | TextureUsages::COPY_DST | TextureUsages::COPY_SRC,
+        view_formats: &[],
+    });
+
+    for (layer, source) in sources.iter().enumerate() {
+        for mip_level in 0..layout.mip_level_count {
+            // block compressed mip levels are copied whole, including their padding
+            let copy_size = Extent3d {
+                depth_or_array_layers: 1,
+                ..size
+            }
+            .mip_level_size(mip_level, TextureDimension::D2)
+            .physical_size(layout.format);
+
+            encoder.copy_texture_to_texture(
+                ImageCopyTexture {
+                    texture: source,
+                    mip_level,
+                    origin: Origin3d::ZERO,
+                    aspect: TextureAspect::All,
+                },
+                ImageCopyTexture {
+                    texture: &texture,
+                    mip_level,
+                    origin: Origin3d {
+                        x: 0,
+                        y: 0,
+                        z: layer as u32,
+                    },
+                    aspect: TextureAspect::All,
+                },
+                copy_size,
+            );
+        }
+    }
+
+    texture
+}
+
+fn reset_job_image_arrays(world: &mut World) {
+    world.insert_resource(JobImageArrayCache::default());
+    remove_all::<PreparedJobImageArray>(world);
+}
+
+#[cfg(test)]
+mod test {
+    use bevy_render::render_resource::{TextureDimension, TextureFormat, TextureUsages};
+
+    use super::{layers_layout, ArrayLayout};
+
+    fn layer(width: u32, format: TextureFormat) -> (ArrayLayout, TextureDimension, TextureUsages) {
+        let layout = ArrayLayout {
+            width,
+            height: 16,
+            layers: 1,
+            format,
+            mip_level_count: 1,
+        };
+        (layout, TextureDimension::D2, TextureUsages::COPY_SRC)
+    }
+
+    #[test]
+    fn layers_share_a_layout() {
+        let layout = layers_layout([
+            layer(16, TextureFormat::Rgba8Unorm),
+            layer(16, TextureFormat::Rgba8Unorm),
+            layer(16, TextureFormat::Rgba8Unorm),
+        ]);
+        assert_eq!(layout.map(|layout| layout.layers), Ok(3));
+    }
+
+    #[test]
+    fn mismatched_layers_fail() {
+        let mismatched_size = layers_layout([
+            layer(16, TextureFormat::Rgba8Unorm),
+            layer(32, TextureFormat::Rgba8Unorm),
+        ]);
+        assert!(mismatched_size.is_err());
+
+        let mismatched_format = layers_layout([
+            layer(16, TextureFormat::Rgba8Unorm),
+            layer(16, TextureFormat::R8Unorm),
+        ]);
+        assert!(mismatched_format.is_err());
+
+        let (layout, dimension, _) = layer(16, TextureFormat::Rgba8Unorm);
+        let not_copyable = layers_layout([(layout, dimension, TextureUsages::TEXTURE_BINDING)]);
+        assert!(not_copyable.is_err());
+
+        assert!(layers_layout([]).is_err());
+    }
+}
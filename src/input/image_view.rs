@@ -0,0 +1,259 @@
+use core::ops::Range;
+
+use bevy_app::{App, Plugin};
+use bevy_asset::{AssetId, Handle};
+use bevy_ecs::{
+    component::Component,
+    entity::Entity,
+    query::{QueryItem, Without},
+    schedule::IntoSystemConfigs,
+    system::{lifetimeless::Read, Commands, Query, Res, ResMut, Resource},
+    world::World,
+};
+use bevy_image::Image;
+use bevy_render::{
+    extract_component::{ExtractComponent, ExtractComponentPlugin},
+    render_asset::RenderAssets,
+    render_resource::{
+        Texture, TextureId, TextureView, TextureViewDescriptor, TextureViewDimension,
+    },
+    texture::{FallbackImage, GpuImage},
+    Render, RenderApp, RenderSet,
+};
+use bevy_utils::HashMap;
+
+use crate::{
+    device::{add_device_reset, remove_all},
+    GraphicsJob,
+};
+
+use super::{JobInput, JobInputFallback, JobInputStatus};
+
+/// A [`JobInput`] providing a view of a range of mip levels and array layers of an
+/// [`Image`], for example to render into a single mip level of a texture.
+///
+/// The job waits until the image is prepared in the render world, and fails if the
+/// ranges are out of bounds for the image. Views are cached between jobs, so jobs
+/// that view the same part of an image each frame don't recreate the view.
+///
+/// With [`JobFallback`](super::JobFallback), jobs view bevy's white 2D [`FallbackImage`]
+/// until the image is prepared.
+#[derive(Clone, Component, PartialEq, Debug)]
+pub struct JobImageView {
+    pub image: Handle<Image>,
+    pub mip_levels: Range<u32>,
+    pub array_layers: Range<u32>,
+    /// The dimension of the view, or `None` to use the dimension of the image.
+    /// Viewing a single layer of an array texture as `D2` requires setting this.
+    pub dimension: Option<TextureViewDimension>,
+}
+
+impl JobImageView {
+    /// Views a single mip level of every array layer of the image.
+    pub fn mip_level(image: Handle<Image>, mip_level: u32) -> Self {
+        Self {
+            image,
+            mip_levels: mip_level..mip_level + 1,
+            array_layers: 0..u32::MAX,
+            dimension: None,
+        }
+    }
+
+    /// Views a single array layer of the image as a 2D texture, with every mip level.
+    pub fn array_layer(image: Handle<Image>, array_layer: u32) -> Self {
+        Self {
+            image,
+            mip_levels: 0..u32::MAX,
+            array_layers: array_layer..array_layer + 1,
+            dimension: Some(TextureViewDimension::D2),
+        }
+    }
+
+    /// Limits the view to the given mip levels. The end of the range is
+    /// clamped to the image's mip level count.
+    pub fn with_mip_levels(mut self, mip_levels: Range<u32>) -> Self {
+        self.mip_levels = mip_levels;
+        self
+    }
+
+    /// Limits the view to the given array layers. The end of the range is
+    /// clamped to the image's array layer count.
+    pub fn with_array_layers(mut self, array_layers: Range<u32>) -> Self {
+        self.array_layers = array_layers;
+        self
+    }
+
+    fn key(&self, texture: &Texture) -> Option<ImageViewKey> {
+        let mip_levels = clamp_range(&self.mip_levels, texture.mip_level_count())?;
+        let array_layers = clamp_range(&self.array_layers, texture.depth_or_array_layers())?;
+        Some(ImageViewKey {
+            image: self.image.id(),
+            mip_levels,
+            array_layers,
+            dimension: self.dimension,
+        })
+    }
+}
+
+/// Clamps the end of a range to `count`, or returns `None` if the range is empty
+/// afterwards.
+fn clamp_range(range: &Range<u32>, count: u32) -> Option<Range<u32>> {
+    let range = range.start..range.end.min(count);
+    (!range.is_empty()).then_some(range)
+}
+
+impl<J: GraphicsJob> JobInput<J> for JobImageView {
+    type Data = (Read<JobImageView>, Option<Read<PreparedJobImageView>>);
+
+    type Item<'a> = &'a PreparedJobImageView;
+
+    fn plugin() -> impl Plugin {
+        |app: &mut App| {
+            if !app.is_plugin_added::<ExtractComponentPlugin<JobImageView>>() {
+                app.add_plugins(ExtractComponentPlugin::<JobImageView>::default());
+
+                if let Some(render_app) = app.get_sub_app_mut(RenderApp) {
+                    render_app.init_resource::<JobImageViewCache>().add_systems(
+                        Render,
+                        (prepare_job_image_views, prepare_job_fallback_image_view)
+                            .in_set(RenderSet::PrepareResources),
+                    );
+                }
+
+                add_device_reset(app, reset_job_image_views);
+            }
+        }
+    }
+
+    fn status((view, prepared): QueryItem<Self::Data>, world: &World) -> JobInputStatus {
+        if prepared.is_some() {
+            return JobInputStatus::Ready;
+        }
+
+        match world.resource::<RenderAssets<GpuImage>>().get(&view.image) {
+            Some(image) if view.key(&image.texture).is_none() => JobInputStatus::Fail,
+            _ => JobInputStatus::Wait,
+        }
+    }
+
+    fn get<'a>((_, prepared): QueryItem<'a, Self::Data>, _world: &'a World) -> Self::Item<'a> {
+        prepared.expect("image view should be ready by this point")
+    }
+}
+
+impl<J: GraphicsJob> JobInputFallback<J> for JobImageView {
+    fn fallback(world: &World) -> Option<Self::Item<'_>> {
+        world
+            .get_resource::<JobFallbackImageView>()
+            .map(|fallback| &fallback.0)
+    }
+}
+
+impl ExtractComponent for JobImageView {
+    type QueryData = Read<JobImageView>;
+
+    type QueryFilter = ();
+
+    type Out = JobImageView;
+
+    fn extract_component(item: QueryItem<'_, Self::QueryData>) -> Option<Self::Out> {
+        Some(item.clone())
+    }
+}
+
+/// The view of an image created for a job by [`JobImageView`].
+#[derive(Component)]
+pub struct PreparedJobImageView {
+    pub texture: Texture,
+    pub view: TextureView,
+}
+
+#[derive(Clone, PartialEq, Eq, Hash)]
+struct ImageViewKey {
+    image: AssetId<Image>,
+    mip_levels: Range<u32>,
+    array_layers: Range<u32>,
+    dimension: Option<TextureViewDimension>,
+}
+
+/// Views created by [`JobImageView`], along with the texture they view, so
+/// they can be dropped once the image is removed or re-uploaded.
+#[derive(Resource, Default)]
+struct JobImageViewCache(HashMap<ImageViewKey, (TextureId, TextureView)>);
+
+fn prepare_job_image_views(
+    jobs: Query<(Entity, &JobImageView), Without<PreparedJobImageView>>,
+    images: Res<RenderAssets<GpuImage>>,
+    mut cache: ResMut<JobImageViewCache>,
+    mut commands: Commands,
+) {
+    cache.0.retain(|key, (texture, _)| {
+        images
+            .get(key.image)
+            .is_some_and(|image| image.texture.id() == *texture)
+    });
+
+    for (entity, job_view) in &jobs {
+        let Some(image) = images.get(&job_view.image) else {
+            continue;
+        };
+        let Some(key) = job_view.key(&image.texture) else {
+            continue;
+        };
+
+        let (_, view) = cache.0.entry(key.clone()).or_insert_with(|| {
+            let view = image.texture.create_view(&TextureViewDescriptor {
+                label: Some("job_image_view"),
+                dimension: key.dimension,
+                base_mip_level: key.mip_levels.start,
+                mip_level_count: Some(key.mip_levels.len() as u32),
+                base_array_layer: key.array_layers.start,
+                array_layer_count: Some(key.array_layers.len() as u32),
+                ..Default::default()
+            });
+            (image.texture.id(), view)
+        });
+
+        commands.entity(entity).insert(PreparedJobImageView {
+            texture: image.texture.clone(),
+            view: view.clone(),
+        });
+    }
+}
+
+/// The view of bevy's 2D [`FallbackImage`] provided in place of images that aren't
+/// prepared yet, by [`JobFallback`](super::JobFallback).
+#[derive(Resource)]
+struct JobFallbackImageView(PreparedJobImageView);
+
+fn prepare_job_fallback_image_view(
+    fallback_image: Option<Res<FallbackImage>>,
+    fallback_view: Option<Res<JobFallbackImageView>>,
+    mut commands: Commands,
+) {
+    if let (Some(fallback_image), None) = (fallback_image, fallback_view) {
+        commands.insert_resource(JobFallbackImageView(PreparedJobImageView {
+            texture: fallback_image.d2.texture.clone(),
+            view: fallback_image.d2.texture_view.clone(),
+        }));
+    }
+}
+
+fn reset_job_image_views(world: &mut World) {
+    world.remove_resource::<JobFallbackImageView>();
+    world.insert_resource(JobImageViewCache::default());
+    remove_all::<PreparedJobImageView>(world);
+}
+
+#[cfg(test)]
+mod test {
+    use super::clamp_range;
+
+    #[test]
+    fn ranges_are_clamped_to_texture() {
+        assert_eq!(clamp_range(&(2..3), 4), Some(2..3));
+        assert_eq!(clamp_range(&(1..u32::MAX), 4), Some(1..4));
+        assert_eq!(clamp_range(&(4..5), 4), None);
+        assert_eq!(clamp_range(&(2..2), 4), None);
+    }
+}
@@ -0,0 +1,212 @@
+use core::marker::PhantomData;
+
+use bevy_app::{App, Plugin};
+use bevy_ecs::{
+    query::QueryItem,
+    system::lifetimeless::Read,
+    world::{EntityRef, World},
+};
+use bevy_render::{
+    batching::gpu_preprocessing::IndirectParametersBuffer,
+    render_phase::{SortedPhaseItem, SortedRenderPhase},
+    render_resource::Buffer,
+    view::{ExtractedView, GpuCulling},
+};
+
+use crate::GraphicsJob;
+
+use super::{
+    render_phase::JobPhaseViewPlugin, ExtractedJobPhaseView, JobInput, JobInputStatus,
+    ViewRenderPhases,
+};
+
+/// A render phase whose draws can be made with indirect parameters from bevy's
+/// [`IndirectParametersBuffer`], for use with [`JobIndirectParameters`].
+///
+/// This is only implemented for [`SortedRenderPhase`], since the batches of a
+/// [`BinnedRenderPhase`](bevy_render::render_phase::BinnedRenderPhase) aren't public.
+pub trait IndirectParametersPhase {
+    /// Returns the index into the indirect parameters buffer of each indirect draw
+    /// in the phase, in draw order.
+    fn indirect_parameters_indices(&self) -> Vec<u32>;
+}
+
+impl<I: SortedPhaseItem> IndirectParametersPhase for SortedRenderPhase<I> {
+    fn indirect_parameters_indices(&self) -> Vec<u32> {
+        // batched items are drawn once, by the first item of the batch, the same way
+        // the phase is rendered
+        let mut indices = Vec::new();
+        let mut index = 0;
+        while let Some(item) = self.items.get(index) {
+            let batch_range = item.batch_range();
+            if batch_range.is_empty() {
+                index += 1;
+                continue;
+            }
+            if let Some(indirect) = item.extra_index().as_indirect_parameters_index() {
+                indices.push(indirect);
+            }
+            index += batch_range.len();
+        }
+        indices
+    }
+}
+
+/// A [`JobInput`] providing bevy's indirect parameters buffer, along with the indices of
+/// the draws made for the render phase `P` of the view selected by the job's
+/// [`JobPhaseView`](super::JobPhaseView), so that GPU-driven culling or preprocessing
+/// jobs can modify the draw arguments of a camera's batches.
+///
+/// The buffer is shared by every view, and is an array of
+/// [`IndirectParameters`](bevy_render::batching::gpu_preprocessing::IndirectParameters),
+/// each 20 bytes long:
+///
+/// ```wgsl
+/// struct IndirectParameters {
+///     // `vertex_count` for non-indexed meshes, `index_count` for indexed ones
+///     vertex_or_index_count: u32,
+///     instance_count: u32,
+///     // `first_vertex` for non-indexed meshes, `first_index` for indexed ones
+///     first_vertex_or_first_index: u32,
+///     // `first_instance` for non-indexed meshes, `base_vertex` for indexed ones
+///     base_vertex_or_first_instance: u32,
+///     first_instance: u32,
+/// }
+/// ```
+///
+/// Jobs run before bevy's mesh preprocessing pass, which counts each batch's visible
+/// instances into `instance_count` from zero, so writes to `instance_count` are
+/// overwritten. To cull a batch, zero its `vertex_or_index_count` instead.
+///
+/// Draws are only made indirectly for cameras with [`GpuCulling`], so the job fails
+/// for views without it, as well as for views without the phase. It waits until the
+/// buffer is allocated, which happens once any view makes an indirect draw.
+pub struct JobIndirectParameters<P>(PhantomData<P>);
+
+impl<J: GraphicsJob, P> JobInput<J> for JobIndirectParameters<P>
+where
+    P: ViewRenderPhases<Phase: IndirectParametersPhase>,
+{
+    type Data = Option<Read<ExtractedJobPhaseView>>;
+
+    type Item<'a> = JobIndirectParametersItem<'a>;
+
+    fn plugin() -> impl Plugin {
+        |app: &mut App| {
+            if !app.is_plugin_added::<JobPhaseViewPlugin>() {
+                app.add_plugins(JobPhaseViewPlugin);
+            }
+        }
+    }
+
+    fn status(data: QueryItem<Self::Data>, world: &World) -> JobInputStatus {
+        let Some(view) = data.and_then(|view| world.get_entity(view.0).ok()) else {
+            return JobInputStatus::Wait;
+        };
+        if !view.contains::<ExtractedView>() {
+            return JobInputStatus::Wait;
+        }
+
+        let has_phase = world
+            .get_resource::<P>()
+            .is_some_and(|phases| phases.view_phase(view.id()).is_some());
+        if !has_phase || !view.contains::<GpuCulling>() {
+            return JobInputStatus::Fail;
+        }
+
+        match world
+            .get_resource::<IndirectParametersBuffer>()
+            .and_then(|buffer| buffer.buffer())
+        {
+            Some(_) => JobInputStatus::Ready,
+            None => JobInputStatus::Wait,
+        }
+    }
+
+    fn get<'a>(data: QueryItem<'a, Self::Data>, world: &'a World) -> Self::Item<'a> {
+        let view = world
+            .get_entity(
+                data.expect("indirect parameters should be ready by this point")
+                    .0,
+            )
+            .expect("indirect parameters should be ready by this point");
+        let phase = world
+            .resource::<P>()
+            .view_phase(view.id())
+            .expect("indirect parameters should be ready by this point");
+        JobIndirectParametersItem {
+            view,
+            buffer: world
+                .resource::<IndirectParametersBuffer>()
+                .buffer()
+                .expect("indirect parameters should be ready by this point"),
+            indices: phase.indirect_parameters_indices(),
+        }
+    }
+}
+
+/// The indirect parameters provided by [`JobIndirectParameters`].
+pub struct JobIndirectParametersItem<'a> {
+    /// The render world view entity, for looking up other view components.
+    pub view: EntityRef<'a>,
+    /// The indirect parameters buffer, shared by every view.
+    pub buffer: &'a Buffer,
+    /// The indices into `buffer` of the phase's indirect draws, in draw order.
+    pub indices: Vec<u32>,
+}
+
+#[cfg(test)]
+mod test {
+    use bevy_core_pipeline::core_3d::Transparent3d;
+    use bevy_ecs::{component::Component, world::World};
+    use bevy_render::{
+        batching::gpu_preprocessing::IndirectParametersBuffer,
+        render_phase::ViewSortedRenderPhases, view::GpuCulling,
+    };
+
+    use crate::{
+        input::{
+            camera_view::test_view, ExtractedJobPhaseView, JobInput, JobInputItem, JobInputStatus,
+        },
+        GraphicsJob, JobError, JobRunContext,
+    };
+
+    use super::JobIndirectParameters;
+
+    type TransparentParameters = JobIndirectParameters<ViewSortedRenderPhases<Transparent3d>>;
+
+    #[derive(Clone, Component)]
+    struct CullJob;
+
+    impl GraphicsJob for CullJob {
+        type In = TransparentParameters;
+
+        fn run(
+            &self,
+            _world: &World,
+            _context: &mut JobRunContext,
+            _input: JobInputItem<Self, Self::In>,
+        ) -> Result<(), JobError> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn fails_for_views_without_gpu_culling() {
+        let status = <TransparentParameters as JobInput<CullJob>>::status;
+        let mut world = World::new();
+        world.init_resource::<ViewSortedRenderPhases<Transparent3d>>();
+        world.init_resource::<IndirectParametersBuffer>();
+
+        let view = world.spawn(test_view(false)).id();
+        world
+            .resource_mut::<ViewSortedRenderPhases<Transparent3d>>()
+            .insert_or_clear(view);
+        let phase_view = ExtractedJobPhaseView(view);
+        assert_eq!(status(Some(&phase_view), &world), JobInputStatus::Fail);
+
+        // the buffer isn't allocated until something is drawn indirectly
+        world.entity_mut(view).insert(GpuCulling);
+        assert_eq!(status(Some(&phase_view), &world), JobInputStatus::Wait);
+    }
+}
@@ -0,0 +1,298 @@
+use bevy_app::{App, Plugin};
+use bevy_asset::AssetId;
+use bevy_ecs::{
+    component::Component,
+    entity::Entity,
+    query::{QueryItem, With},
+    system::{lifetimeless::Read, Commands, Query},
+    world::World,
+};
+use bevy_image::Image;
+use bevy_math::{Mat4, UVec3, Vec4};
+use bevy_pbr::irradiance_volume::IrradianceVolume;
+use bevy_render::{
+    render_asset::RenderAssets, render_resource::TextureDimension, sync_world::RenderEntity,
+    texture::GpuImage, Extract, ExtractSchedule, RenderApp,
+};
+use bevy_transform::components::GlobalTransform;
+
+use crate::{GraphicsJob, JobMarker};
+
+use super::{JobInput, JobInputStatus};
+
+/// A [`JobInput`] providing the voxels of one of bevy's irradiance volumes, for jobs
+/// that bake or sample indirect lighting.
+///
+/// The entity must have an [`IrradianceVolume`] and a `Transform`, along with the
+/// [`LightProbe`](bevy_pbr::LightProbe) marker for bevy to render with it. Like every
+/// light probe, the volume is a unit cube centered on the origin of its transform. The
+/// job waits until the voxel texture is prepared, and fails if the entity doesn't have
+/// an [`IrradianceVolume`], or its voxels aren't a 3D texture.
+///
+/// Voxels are stored as ambient cubes, with one color for each side of the cube, each
+/// side in its own region of the texture. See [`AmbientCubeSide`].
+/// Voxel textures written by jobs need `TextureUsages::STORAGE_BINDING`.
+#[derive(Copy, Clone, Component, PartialEq, Eq, Debug)]
+pub struct JobIrradianceVolume(pub Entity);
+
+impl JobIrradianceVolume {
+    /// Targets the given main world irradiance volume.
+    pub const fn new(volume: Entity) -> Self {
+        Self(volume)
+    }
+}
+
+/// The irradiance volume targeted by a job's [`JobIrradianceVolume`], extracted every
+/// frame, or `None` if the entity doesn't have one.
+#[derive(Clone, Component)]
+#[doc(hidden)]
+pub struct ExtractedJobIrradianceVolume(pub Option<ExtractedIrradianceVolume>);
+
+#[derive(Clone)]
+#[doc(hidden)]
+pub struct ExtractedIrradianceVolume {
+    pub voxels: AssetId<Image>,
+    pub intensity: f32,
+    pub world_from_local: Mat4,
+}
+
+impl<J: GraphicsJob> JobInput<J> for JobIrradianceVolume {
+    type Data = Option<Read<ExtractedJobIrradianceVolume>>;
+
+    type Item<'a> = JobIrradianceVolumeItem<'a>;
+
+    fn plugin() -> impl Plugin {
+        |app: &mut App| {
+            if !app.is_plugin_added::<JobIrradianceVolumePlugin>() {
+                app.add_plugins(JobIrradianceVolumePlugin);
+            }
+        }
+    }
+
+    fn status(data: QueryItem<Self::Data>, world: &World) -> JobInputStatus {
+        let Some(ExtractedJobIrradianceVolume(volume)) = data else {
+            return JobInputStatus::Wait;
+        };
+        let Some(volume) = volume else {
+            return JobInputStatus::Fail;
+        };
+
+        match world
+            .get_resource::<RenderAssets<GpuImage>>()
+            .and_then(|images| images.get(volume.voxels))
+        {
+            None => JobInputStatus::Wait,
+            Some(voxels) if voxels.texture.dimension() != TextureDimension::D3 => {
+                JobInputStatus::Fail
+            }
+            Some(_) => JobInputStatus::Ready,
+        }
+    }
+
+    fn get<'a>(data: QueryItem<'a, Self::Data>, world: &'a World) -> Self::Item<'a> {
+        let volume = data
+            .and_then(|volume| volume.0.as_ref())
+            .expect("irradiance volume should be ready by this point");
+        let voxels = world
+            .resource::<RenderAssets<GpuImage>>()
+            .get(volume.voxels)
+            .expect("irradiance volume should be ready by this point");
+        let size = voxels.texture.size();
+
+        JobIrradianceVolumeItem {
+            voxels,
+            intensity: volume.intensity,
+            world_from_local: volume.world_from_local,
+            uvw_from_world: uvw_from_local() * volume.world_from_local.inverse(),
+            resolution: grid_resolution(UVec3::new(
+                size.width,
+                size.height,
+                size.depth_or_array_layers,
+            )),
+        }
+    }
+}
+
+/// The irradiance volume provided by [`JobIrradianceVolume`].
+pub struct JobIrradianceVolumeItem<'a> {
+    /// The 3D texture of ambient cubes.
+    pub voxels: &'a GpuImage,
+    /// The volume's [`IrradianceVolume::intensity`].
+    pub intensity: f32,
+    /// Transforms from the volume's local space, a unit cube centered on the origin,
+    /// to world space.
+    pub world_from_local: Mat4,
+    /// Transforms from world space to the volume's grid coordinates, which span
+    /// `[0, 1]` on each axis of the volume.
+    pub uvw_from_world: Mat4,
+    /// The number of voxels along each axis of the volume, a third of the texture's
+    /// depth and half its height.
+    pub resolution: UVec3,
+}
+
+/// A side of an ambient cube, each stored in its own region of an irradiance volume's
+/// texture, named for the normals of the surfaces it lights.
+///
+/// Bevy's shader lights surfaces facing a negative direction from the upper half of
+/// each region, so this follows the shader rather than the table in
+/// [`bevy_pbr::irradiance_volume`], which has the halves the other way around.
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+pub enum AmbientCubeSide {
+    /// Lights surfaces facing +X.
+    PositiveX,
+    /// Lights surfaces facing -X.
+    NegativeX,
+    /// Lights surfaces facing +Y.
+    PositiveY,
+    /// Lights surfaces facing -Y.
+    NegativeY,
+    /// Lights surfaces facing +Z.
+    PositiveZ,
+    /// Lights surfaces facing -Z.
+    NegativeZ,
+}
+
+impl AmbientCubeSide {
+    /// Every side, in the order of their regions of the texture.
+    pub const ALL: [Self; 6] = [
+        Self::PositiveX,
+        Self::NegativeX,
+        Self::PositiveY,
+        Self::NegativeY,
+        Self::PositiveZ,
+        Self::NegativeZ,
+    ];
+
+    /// The texel storing this side of the voxel at `voxel`, for a volume with the
+    /// given grid resolution.
+    pub const fn texel(self, voxel: UVec3, resolution: UVec3) -> UVec3 {
+        let (negative, axis) = match self {
+            Self::PositiveX => (false, 0),
+            Self::NegativeX => (true, 0),
+            Self::PositiveY => (false, 1),
+            Self::NegativeY => (true, 1),
+            Self::PositiveZ => (false, 2),
+            Self::NegativeZ => (true, 2),
+        };
+        UVec3::new(
+            voxel.x,
+            voxel.y + if negative { resolution.y } else { 0 },
+            voxel.z + axis * resolution.z,
+        )
+    }
+}
+
+/// The grid resolution of an irradiance volume with a texture of the given size.
+fn grid_resolution(size: UVec3) -> UVec3 {
+    UVec3::new(size.x, size.y / 2, size.z / 3)
+}
+
+/// Maps a light probe's local space, a unit cube centered on the origin, to its grid
+/// coordinates.
+fn uvw_from_local() -> Mat4 {
+    Mat4::from_cols(Vec4::X, Vec4::Y, Vec4::Z, Vec4::new(0.5, 0.5, 0.5, 1.0))
+}
+
+struct JobIrradianceVolumePlugin;
+
+impl Plugin for JobIrradianceVolumePlugin {
+    fn build(&self, app: &mut App) {
+        if let Some(render_app) = app.get_sub_app_mut(RenderApp) {
+            render_app.add_systems(ExtractSchedule, extract_job_irradiance_volumes);
+        }
+    }
+}
+
+// light probes aren't synced to the render world, so their data is copied onto each job
+// every frame, following any changes to the volume
+fn extract_job_irradiance_volumes(
+    jobs: Extract<Query<(RenderEntity, &JobIrradianceVolume), With<JobMarker>>>,
+    volumes: Extract<Query<(&IrradianceVolume, &GlobalTransform)>>,
+    mut commands: Commands,
+) {
+    for (render_entity, volume) in &jobs {
+        let volume =
+            volumes
+                .get(volume.0)
+                .ok()
+                .map(|(volume, transform)| ExtractedIrradianceVolume {
+                    voxels: volume.voxels.id(),
+                    intensity: volume.intensity,
+                    world_from_local: transform.compute_matrix(),
+                });
+        commands
+            .entity(render_entity)
+            .insert(ExtractedJobIrradianceVolume(volume));
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use bevy_asset::AssetId;
+    use bevy_ecs::{component::Component, world::World};
+    use bevy_math::{Mat4, UVec3};
+
+    use crate::{
+        input::{JobInput, JobInputItem, JobInputStatus},
+        GraphicsJob, JobError, JobRunContext,
+    };
+
+    use super::{
+        grid_resolution, AmbientCubeSide, ExtractedIrradianceVolume, ExtractedJobIrradianceVolume,
+        JobIrradianceVolume,
+    };
+
+    #[derive(Clone, Component)]
+    struct BakeJob;
+
+    impl GraphicsJob for BakeJob {
+        type In = JobIrradianceVolume;
+
+        fn run(
+            &self,
+            _world: &World,
+            _context: &mut JobRunContext,
+            _input: JobInputItem<Self, Self::In>,
+        ) -> Result<(), JobError> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn entities_without_volumes_fail() {
+        let world = World::new();
+        let status = <JobIrradianceVolume as JobInput<BakeJob>>::status;
+        assert_eq!(status(None, &world), JobInputStatus::Wait);
+
+        let missing = ExtractedJobIrradianceVolume(None);
+        assert_eq!(status(Some(&missing), &world), JobInputStatus::Fail);
+
+        // the voxels aren't prepared yet
+        let volume = ExtractedJobIrradianceVolume(Some(ExtractedIrradianceVolume {
+            voxels: AssetId::default(),
+            intensity: 1.0,
+            world_from_local: Mat4::IDENTITY,
+        }));
+        assert_eq!(status(Some(&volume), &world), JobInputStatus::Wait);
+    }
+
+    #[test]
+    fn sides_map_to_their_regions() {
+        let resolution = grid_resolution(UVec3::new(4, 6, 6));
+        assert_eq!(resolution, UVec3::new(4, 3, 2));
+
+        let voxel = UVec3::new(1, 2, 1);
+        let texels = AmbientCubeSide::ALL.map(|side| side.texel(voxel, resolution));
+        assert_eq!(
+            texels,
+            [
+                UVec3::new(1, 2, 1),
+                UVec3::new(1, 5, 1),
+                UVec3::new(1, 2, 3),
+                UVec3::new(1, 5, 3),
+                UVec3::new(1, 2, 5),
+                UVec3::new(1, 5, 5),
+            ]
+        );
+    }
+}
@@ -0,0 +1,218 @@
+use bevy_app::{App, Plugin};
+use bevy_asset::AssetId;
+use bevy_ecs::{
+    component::Component,
+    entity::Entity,
+    query::{QueryItem, With},
+    system::{lifetimeless::Read, Commands, Query},
+    world::World,
+};
+use bevy_image::Image;
+use bevy_math::{Rect, URect, UVec2, Vec2};
+use bevy_pbr::Lightmap;
+use bevy_render::{
+    render_asset::RenderAssets, sync_world::RenderEntity, texture::GpuImage, Extract,
+    ExtractSchedule, RenderApp,
+};
+
+use crate::{GraphicsJob, JobMarker};
+
+use super::{JobInput, JobInputStatus};
+
+/// A [`JobInput`] providing an entity's baked lightmap, for jobs that bake, denoise or
+/// otherwise post-process lightmaps.
+///
+/// The entity must have a [`Lightmap`], whose `uv_rect` is the region of the lightmap
+/// texture it covers, since several meshes may share one atlas. For bevy to render
+/// with it, the entity also needs a `Mesh3d` with a second UV layer
+/// (`Mesh::ATTRIBUTE_UV_1`) and a `MeshMaterial3d<StandardMaterial>`, but the job only
+/// reads the [`Lightmap`]. The job waits until the lightmap texture is prepared, and
+/// fails if the entity doesn't have a [`Lightmap`].
+///
+/// Lightmap textures written by jobs need `TextureUsages::STORAGE_BINDING`, or
+/// `TextureUsages::COPY_DST` to be copied into.
+#[derive(Copy, Clone, Component, PartialEq, Eq, Debug)]
+pub struct JobLightmap(pub Entity);
+
+impl JobLightmap {
+    /// Targets the lightmap of the given main world entity.
+    pub const fn new(entity: Entity) -> Self {
+        Self(entity)
+    }
+}
+
+/// The lightmap of the entity targeted by a job's [`JobLightmap`], extracted every
+/// frame, or `None` if the entity doesn't have one.
+#[derive(Clone, Component)]
+#[doc(hidden)]
+pub struct ExtractedJobLightmap(pub Option<ExtractedLightmap>);
+
+#[derive(Clone)]
+#[doc(hidden)]
+pub struct ExtractedLightmap {
+    pub image: AssetId<Image>,
+    pub uv_rect: Rect,
+}
+
+impl<J: GraphicsJob> JobInput<J> for JobLightmap {
+    type Data = Option<Read<ExtractedJobLightmap>>;
+
+    type Item<'a> = JobLightmapItem<'a>;
+
+    fn plugin() -> impl Plugin {
+        |app: &mut App| {
+            if !app.is_plugin_added::<JobLightmapPlugin>() {
+                app.add_plugins(JobLightmapPlugin);
+            }
+        }
+    }
+
+    fn status(data: QueryItem<Self::Data>, world: &World) -> JobInputStatus {
+        let Some(ExtractedJobLightmap(lightmap)) = data else {
+            return JobInputStatus::Wait;
+        };
+        let Some(lightmap) = lightmap else {
+            return JobInputStatus::Fail;
+        };
+
+        match world
+            .get_resource::<RenderAssets<GpuImage>>()
+            .and_then(|images| images.get(lightmap.image))
+        {
+            Some(_) => JobInputStatus::Ready,
+            None => JobInputStatus::Wait,
+        }
+    }
+
+    fn get<'a>(data: QueryItem<'a, Self::Data>, world: &'a World) -> Self::Item<'a> {
+        let lightmap = data
+            .and_then(|lightmap| lightmap.0.as_ref())
+            .expect("lightmap should be ready by this point");
+        let image = world
+            .resource::<RenderAssets<GpuImage>>()
+            .get(lightmap.image)
+            .expect("lightmap should be ready by this point");
+
+        JobLightmapItem {
+            image,
+            uv_rect: lightmap.uv_rect,
+            texel_rect: texel_rect(lightmap.uv_rect, image.size),
+        }
+    }
+}
+
+/// The lightmap provided by [`JobLightmap`].
+pub struct JobLightmapItem<'a> {
+    /// The lightmap texture, which may be an atlas shared with other entities.
+    pub image: &'a GpuImage,
+    /// The entity's [`Lightmap::uv_rect`], the region of the texture it covers, from
+    /// (0, 0) to (1, 1).
+    pub uv_rect: Rect,
+    /// The texels covered by the entity's region, rounded outwards to whole texels
+    /// and clamped to the texture.
+    pub texel_rect: URect,
+}
+
+/// The texels of a texture of the given size covered by a lightmap's UV rect.
+fn texel_rect(uv_rect: Rect, size: UVec2) -> URect {
+    let size = size.as_vec2();
+    let min = (uv_rect.min * size).floor().clamp(Vec2::ZERO, size);
+    let max = (uv_rect.max * size).ceil().clamp(Vec2::ZERO, size);
+    URect::from_corners(min.as_uvec2(), max.as_uvec2())
+}
+
+struct JobLightmapPlugin;
+
+impl Plugin for JobLightmapPlugin {
+    fn build(&self, app: &mut App) {
+        if let Some(render_app) = app.get_sub_app_mut(RenderApp) {
+            render_app.add_systems(ExtractSchedule, extract_job_lightmaps);
+        }
+    }
+}
+
+// lightmaps are only kept in the render world for visible meshes, so they're copied onto
+// each job every frame instead, following any changes to the entity's lightmap
+fn extract_job_lightmaps(
+    jobs: Extract<Query<(RenderEntity, &JobLightmap), With<JobMarker>>>,
+    lightmaps: Extract<Query<&Lightmap>>,
+    mut commands: Commands,
+) {
+    for (render_entity, lightmap) in &jobs {
+        let lightmap = lightmaps
+            .get(lightmap.0)
+            .ok()
+            .map(|lightmap| ExtractedLightmap {
+                image: lightmap.image.id(),
+                uv_rect: lightmap.uv_rect,
+            });
+        commands
+            .entity(render_entity)
+            .insert(ExtractedJobLightmap(lightmap));
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use bevy_asset::AssetId;
+    use bevy_ecs::{component::Component, world::World};
+    use bevy_math::{Rect, URect, UVec2};
+
+    use crate::{
+        input::{JobInput, JobInputItem, JobInputStatus},
+        GraphicsJob, JobError, JobRunContext,
+    };
+
+    use super::{texel_rect, ExtractedJobLightmap, ExtractedLightmap, JobLightmap};
+
+    #[derive(Clone, Component)]
+    struct DenoiseJob;
+
+    impl GraphicsJob for DenoiseJob {
+        type In = JobLightmap;
+
+        fn run(
+            &self,
+            _world: &World,
+            _context: &mut JobRunContext,
+            _input: JobInputItem<Self, Self::In>,
+        ) -> Result<(), JobError> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn entities_without_lightmaps_fail() {
+        let world = World::new();
+        let status = <JobLightmap as JobInput<DenoiseJob>>::status;
+        assert_eq!(status(None, &world), JobInputStatus::Wait);
+
+        let missing = ExtractedJobLightmap(None);
+        assert_eq!(status(Some(&missing), &world), JobInputStatus::Fail);
+
+        // the lightmap isn't prepared yet
+        let lightmap = ExtractedJobLightmap(Some(ExtractedLightmap {
+            image: AssetId::default(),
+            uv_rect: Rect::new(0.0, 0.0, 1.0, 1.0),
+        }));
+        assert_eq!(status(Some(&lightmap), &world), JobInputStatus::Wait);
+    }
+
+    #[test]
+    fn uv_rects_cover_whole_texels() {
+        let size = UVec2::new(256, 128);
+        assert_eq!(
+            texel_rect(Rect::new(0.0, 0.0, 1.0, 1.0), size),
+            URect::new(0, 0, 256, 128)
+        );
+        // an atlas region that doesn't line up with texels is rounded outwards
+        assert_eq!(
+            texel_rect(Rect::new(0.5, 0.25, 0.751, 0.5), size),
+            URect::new(128, 32, 193, 64)
+        );
+        assert_eq!(
+            texel_rect(Rect::new(0.9, 0.9, 1.2, 1.2), size),
+            URect::new(230, 115, 256, 128)
+        );
+    }
+}
@@ -0,0 +1,25 @@
+use bevy_ecs::{query::QueryItem, world::World};
+use bevy_render::{renderer::RenderDevice, settings::WgpuLimits};
+
+use crate::GraphicsJob;
+
+use super::{JobInput, JobInputStatus};
+
+/// A [`JobInput`] providing a snapshot of the render device's limits, for jobs that
+/// adapt their tile sizes or dispatch dimensions to the hardware, for example
+/// by clamping workgroup counts to `max_compute_workgroups_per_dimension`.
+pub struct JobLimits;
+
+impl<J: GraphicsJob> JobInput<J> for JobLimits {
+    type Data = ();
+
+    type Item<'a> = WgpuLimits;
+
+    fn status((): QueryItem<Self::Data>, _world: &World) -> JobInputStatus {
+        JobInputStatus::Ready
+    }
+
+    fn get<'a>((): QueryItem<'a, Self::Data>, world: &'a World) -> Self::Item<'a> {
+        world.resource::<RenderDevice>().limits()
+    }
+}
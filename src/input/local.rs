@@ -0,0 +1,173 @@
+use core::marker::PhantomData;
+
+use bevy_app::{App, Plugin};
+use bevy_ecs::{
+    component::Component, query::QueryItem, schedule::IntoSystemConfigs,
+    system::lifetimeless::Read, world::World,
+};
+use bevy_render::{
+    extract_component::{ExtractComponent, ExtractComponentPlugin},
+    Render, RenderApp,
+};
+use bevy_utils::HashMap;
+
+use crate::{runner::JobSet, GraphicsJob};
+
+use super::{JobInput, JobInputStatus};
+
+/// A [`JobInput`] referencing render-thread-local data that isn't [`Send`], like
+/// platform interop handles, which can't be stored on the job component itself.
+///
+/// The data lives in a [`JobLocals<T>`] non-send resource in the render world, and
+/// is keyed by the `u64` given to [`JobLocal::new`], so that several jobs may share
+/// the same value. Values must be inserted from the render thread, for example by a
+/// render world system taking [`NonSendMut<JobLocals<T>>`](bevy_ecs::system::NonSendMut),
+/// and stay in the store until they're removed. A job waits until a value exists
+/// for its key.
+///
+/// Jobs receive a shared reference to the value, so use interior mutability (like
+/// [`RefCell`](core::cell::RefCell)) for data that must be mutated while recording.
+#[derive(Component)]
+pub struct JobLocal<T: 'static> {
+    key: u64,
+    marker: PhantomData<fn() -> T>,
+}
+
+impl<T: 'static> JobLocal<T> {
+    /// References the value stored with the given key.
+    pub const fn new(key: u64) -> Self {
+        Self {
+            key,
+            marker: PhantomData,
+        }
+    }
+
+    /// Returns the key of the referenced value.
+    pub const fn key(&self) -> u64 {
+        self.key
+    }
+}
+
+impl<T: 'static> Clone for JobLocal<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T: 'static> Copy for JobLocal<T> {}
+
+impl<T: 'static> ExtractComponent for JobLocal<T> {
+    type QueryData = Read<JobLocal<T>>;
+
+    type QueryFilter = ();
+
+    type Out = JobLocal<T>;
+
+    fn extract_component(item: QueryItem<'_, Self::QueryData>) -> Option<Self::Out> {
+        Some(*item)
+    }
+}
+
+impl<J: GraphicsJob, T: 'static> JobInput<J> for JobLocal<T> {
+    type Data = Read<JobLocal<T>>;
+
+    type Item<'a> = &'a T;
+
+    fn plugin() -> impl Plugin {
+        |app: &mut App| {
+            if !app.is_plugin_added::<ExtractComponentPlugin<JobLocal<T>>>() {
+                app.add_plugins(ExtractComponentPlugin::<JobLocal<T>>::default());
+
+                if let Some(render_app) = app.get_sub_app_mut(RenderApp) {
+                    render_app.add_systems(Render, init_job_locals::<T>.in_set(JobSet::Setup));
+                }
+            }
+        }
+    }
+
+    fn status(data: QueryItem<Self::Data>, world: &World) -> JobInputStatus {
+        match world
+            .get_non_send_resource::<JobLocals<T>>()
+            .is_some_and(|locals| locals.contains(data.key))
+        {
+            true => JobInputStatus::Ready,
+            false => JobInputStatus::Wait,
+        }
+    }
+
+    fn get<'a>(data: QueryItem<'a, Self::Data>, world: &'a World) -> Self::Item<'a> {
+        world
+            .non_send_resource::<JobLocals<T>>()
+            .get(data.key)
+            .expect("job local should be ready by this point")
+    }
+}
+
+/// A render-thread-local store of values referenced by [`JobLocal<T>`].
+///
+/// This is inserted into the render world as a non-send resource the first
+/// time the render schedule runs, on the render thread.
+pub struct JobLocals<T> {
+    values: HashMap<u64, T>,
+}
+
+impl<T> Default for JobLocals<T> {
+    fn default() -> Self {
+        Self {
+            values: HashMap::default(),
+        }
+    }
+}
+
+impl<T> JobLocals<T> {
+    /// Stores a value with the given key, returning the previous value if there was one.
+    pub fn insert(&mut self, key: u64, value: T) -> Option<T> {
+        self.values.insert(key, value)
+    }
+
+    /// Removes the value with the given key.
+    pub fn remove(&mut self, key: u64) -> Option<T> {
+        self.values.remove(&key)
+    }
+
+    pub fn get(&self, key: u64) -> Option<&T> {
+        self.values.get(&key)
+    }
+
+    pub fn get_mut(&mut self, key: u64) -> Option<&mut T> {
+        self.values.get_mut(&key)
+    }
+
+    pub fn contains(&self, key: u64) -> bool {
+        self.values.contains_key(&key)
+    }
+}
+
+/// Inserts the store from the render thread, since non-send resources may
+/// only be accessed from the thread they were inserted on.
+fn init_job_locals<T: 'static>(world: &mut World) {
+    if !world.contains_non_send::<JobLocals<T>>() {
+        world.insert_non_send_resource(JobLocals::<T>::default());
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::rc::Rc;
+
+    use super::JobLocals;
+
+    #[test]
+    fn locals_are_keyed() {
+        let mut locals = JobLocals::<Rc<u32>>::default();
+        assert!(locals.insert(0, Rc::new(1)).is_none());
+        assert!(locals.insert(1, Rc::new(2)).is_none());
+
+        assert_eq!(locals.get(0).map(|value| **value), Some(1));
+        assert_eq!(locals.get(1).map(|value| **value), Some(2));
+        assert!(!locals.contains(2));
+
+        assert_eq!(locals.remove(0).map(|value| *value), Some(1));
+        assert!(!locals.contains(0));
+    }
+}
@@ -0,0 +1,197 @@
+use bevy_app::{App, Plugin};
+use bevy_asset::{AssetId, AssetServer, Assets, LoadState};
+use bevy_ecs::{
+    component::Component,
+    query::{QueryItem, With},
+    system::{lifetimeless::Read, Commands, Query, Res},
+    world::World,
+};
+use bevy_pbr::{Material, PreparedMaterial};
+use bevy_render::{
+    render_asset::RenderAssets, sync_world::RenderEntity, Extract, ExtractSchedule, RenderApp,
+};
+use bevy_utils::tracing::error;
+
+use crate::{GraphicsJob, JobMarker};
+
+use super::{JobInput, JobInputStatus};
+
+/// A [`JobInput`] providing the prepared bind group of an existing [`Material`], for
+/// custom passes that draw with the same material as the rest of the scene.
+///
+/// The material must be rendered by bevy with its [`MaterialPlugin<M>`](bevy_pbr::MaterialPlugin),
+/// which prepares its bind group. [`StandardMaterial`](bevy_pbr::StandardMaterial)'s
+/// plugin is added by `PbrPlugin`, while custom materials need theirs added to the app.
+/// The bind group is laid out like the material's [`AsBindGroup`](bevy_render::render_resource::AsBindGroup)
+/// impl, so create the job's pipeline with the `material_layout` of the render world's
+/// [`MaterialPipeline<M>`](bevy_pbr::MaterialPipeline).
+///
+/// The job waits until the material is prepared, which may take several frames while
+/// its textures load, and fails if the material doesn't exist and isn't being loaded.
+#[derive(Component)]
+pub struct JobMaterial<M: Material>(pub AssetId<M>);
+
+impl<M: Material> JobMaterial<M> {
+    /// Provides the bind group of the material with the given id, or handle.
+    pub fn new(material: impl Into<AssetId<M>>) -> Self {
+        Self(material.into())
+    }
+}
+
+impl<M: Material> Clone for JobMaterial<M> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<M: Material> Copy for JobMaterial<M> {}
+
+/// The material of a job's [`JobMaterial`], and whether it exists in the main world.
+#[derive(Component)]
+#[doc(hidden)]
+pub struct ExtractedJobMaterial<M: Material> {
+    id: AssetId<M>,
+    unknown: bool,
+}
+
+impl<J: GraphicsJob, M: Material> JobInput<J> for JobMaterial<M> {
+    type Data = Option<Read<ExtractedJobMaterial<M>>>;
+
+    type Item<'a> = &'a PreparedMaterial<M>;
+
+    fn plugin() -> impl Plugin {
+        |app: &mut App| {
+            if !app.is_plugin_added::<JobMaterialPlugin<M>>() {
+                app.add_plugins(JobMaterialPlugin::<M>::default());
+            }
+        }
+    }
+
+    fn status(data: QueryItem<Self::Data>, world: &World) -> JobInputStatus {
+        let Some(material) = data else {
+            return JobInputStatus::Wait;
+        };
+        let Some(materials) = world.get_resource::<RenderAssets<PreparedMaterial<M>>>() else {
+            error!(
+                "unable to find prepared `{}` materials, add its `MaterialPlugin`",
+                core::any::type_name::<M>()
+            );
+            return JobInputStatus::Fail;
+        };
+
+        if materials.get(material.id).is_some() {
+            JobInputStatus::Ready
+        } else if material.unknown {
+            JobInputStatus::Fail
+        } else {
+            JobInputStatus::Wait
+        }
+    }
+
+    fn get<'a>(data: QueryItem<'a, Self::Data>, world: &'a World) -> Self::Item<'a> {
+        let material = data.expect("material should be ready by this point");
+        world
+            .resource::<RenderAssets<PreparedMaterial<M>>>()
+            .get(material.id)
+            .expect("material should be ready by this point")
+    }
+}
+
+struct JobMaterialPlugin<M>(core::marker::PhantomData<M>);
+
+impl<M> Default for JobMaterialPlugin<M> {
+    fn default() -> Self {
+        Self(core::marker::PhantomData)
+    }
+}
+
+impl<M: Material> Plugin for JobMaterialPlugin<M> {
+    fn build(&self, app: &mut App) {
+        if let Some(render_app) = app.get_sub_app_mut(RenderApp) {
+            render_app.add_systems(ExtractSchedule, extract_job_materials::<M>);
+        }
+    }
+}
+
+/// Extracts the material of every pending job each frame, since a material that's
+/// still loading may fail to load, or be removed, while the job waits.
+fn extract_job_materials<M: Material>(
+    jobs: Extract<Query<(RenderEntity, &JobMaterial<M>), With<JobMarker>>>,
+    materials: Extract<Option<Res<Assets<M>>>>,
+    asset_server: Extract<Option<Res<AssetServer>>>,
+    mut commands: Commands,
+) {
+    for (render_entity, material) in &jobs {
+        let loading = asset_server.as_ref().is_some_and(|asset_server| {
+            matches!(
+                asset_server.get_load_state(material.0),
+                Some(LoadState::Loading)
+            )
+        });
+        let exists = materials
+            .as_ref()
+            .is_some_and(|materials| materials.contains(material.0));
+        let unknown = !exists && !loading;
+        if let Some(mut entity) = commands.get_entity(render_entity) {
+            entity.insert(ExtractedJobMaterial {
+                id: material.0,
+                unknown,
+            });
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use bevy_asset::AssetId;
+    use bevy_ecs::{component::Component, query::QueryState, world::World};
+    use bevy_pbr::{PreparedMaterial, StandardMaterial};
+    use bevy_render::render_asset::RenderAssets;
+
+    use crate::{
+        input::{JobInput, JobInputItem, JobInputStatus},
+        GraphicsJob, JobError, JobRunContext,
+    };
+
+    use super::{ExtractedJobMaterial, JobMaterial};
+
+    #[derive(Clone, Component)]
+    struct OutlineJob;
+
+    impl GraphicsJob for OutlineJob {
+        type In = JobMaterial<StandardMaterial>;
+
+        fn run(
+            &self,
+            _world: &World,
+            _context: &mut JobRunContext,
+            _input: JobInputItem<Self, Self::In>,
+        ) -> Result<(), JobError> {
+            Ok(())
+        }
+    }
+
+    fn status(world: &mut World, unknown: bool) -> JobInputStatus {
+        let job = world
+            .spawn(ExtractedJobMaterial::<StandardMaterial> {
+                id: AssetId::default(),
+                unknown,
+            })
+            .id();
+        let mut query =
+            QueryState::<<JobMaterial<StandardMaterial> as JobInput<OutlineJob>>::Data>::new(world);
+        let data = query.get(world, job).unwrap();
+        <JobMaterial<StandardMaterial> as JobInput<OutlineJob>>::status(data, world)
+    }
+
+    #[test]
+    fn waits_for_known_materials_and_fails_unknown_ones() {
+        let mut world = World::new();
+        // without the material's plugin, the material is never prepared
+        assert_eq!(status(&mut world, false), JobInputStatus::Fail);
+
+        world.init_resource::<RenderAssets<PreparedMaterial<StandardMaterial>>>();
+        assert_eq!(status(&mut world, false), JobInputStatus::Wait);
+        assert_eq!(status(&mut world, true), JobInputStatus::Fail);
+    }
+}
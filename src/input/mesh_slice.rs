@@ -0,0 +1,160 @@
+use bevy_app::{App, Plugin};
+use bevy_asset::Handle;
+use bevy_ecs::{component::Component, query::QueryItem, system::lifetimeless::Read, world::World};
+use bevy_render::{
+    extract_component::{ExtractComponent, ExtractComponentPlugin},
+    mesh::{
+        allocator::{MeshAllocator, MeshBufferSlice},
+        Mesh, RenderMesh, RenderMeshBufferInfo,
+    },
+    render_asset::RenderAssets,
+};
+
+use crate::GraphicsJob;
+
+use super::{JobInput, JobInputStatus};
+
+/// A [`JobInput`] providing where the vertex and index data of some meshes live
+/// in the shared buffers of bevy's [`MeshAllocator`], for example to issue indirect
+/// draws for several meshes from a GPU-driven job.
+///
+/// Meshes with the same vertex layout usually share a buffer, so a single pass can
+/// draw all of them by offsetting into it. The job waits until every mesh has been
+/// prepared and allocated.
+#[derive(Clone, Component, PartialEq, Debug)]
+pub struct JobMeshSlice(pub Vec<Handle<Mesh>>);
+
+impl JobMeshSlice {
+    /// Provides the slices of a single mesh.
+    pub fn new(mesh: Handle<Mesh>) -> Self {
+        Self(vec![mesh])
+    }
+}
+
+impl FromIterator<Handle<Mesh>> for JobMeshSlice {
+    fn from_iter<T: IntoIterator<Item = Handle<Mesh>>>(iter: T) -> Self {
+        Self(iter.into_iter().collect())
+    }
+}
+
+/// The allocation of a single mesh, provided by [`JobMeshSlice`] in the
+/// same order as the meshes were given.
+pub struct JobMeshSliceItem<'a> {
+    pub mesh: &'a RenderMesh,
+    /// The vertex data of the mesh. The range is measured in vertices.
+    pub vertices: MeshBufferSlice<'a>,
+    /// The index data of the mesh, if it's indexed. The range is measured in indices.
+    pub indices: Option<MeshBufferSlice<'a>>,
+}
+
+impl<J: GraphicsJob> JobInput<J> for JobMeshSlice {
+    type Data = Read<JobMeshSlice>;
+
+    type Item<'a> = Vec<JobMeshSliceItem<'a>>;
+
+    fn plugin() -> impl Plugin {
+        |app: &mut App| {
+            if !app.is_plugin_added::<ExtractComponentPlugin<JobMeshSlice>>() {
+                app.add_plugins(ExtractComponentPlugin::<JobMeshSlice>::default());
+            }
+        }
+    }
+
+    fn status(data: QueryItem<Self::Data>, world: &World) -> JobInputStatus {
+        let (Some(meshes), Some(allocator)) = (
+            world.get_resource::<RenderAssets<RenderMesh>>(),
+            world.get_resource::<MeshAllocator>(),
+        ) else {
+            return JobInputStatus::Wait;
+        };
+
+        if data
+            .0
+            .iter()
+            .all(|mesh| mesh_slice(mesh, meshes, allocator).is_some())
+        {
+            JobInputStatus::Ready
+        } else {
+            JobInputStatus::Wait
+        }
+    }
+
+    fn get<'a>(data: QueryItem<'a, Self::Data>, world: &'a World) -> Self::Item<'a> {
+        let meshes = world.resource::<RenderAssets<RenderMesh>>();
+        let allocator = world.resource::<MeshAllocator>();
+        data.0
+            .iter()
+            .map(|mesh| {
+                mesh_slice(mesh, meshes, allocator).expect("mesh should be ready by this point")
+            })
+            .collect()
+    }
+}
+
+fn mesh_slice<'a>(
+    mesh: &Handle<Mesh>,
+    meshes: &'a RenderAssets<RenderMesh>,
+    allocator: &'a MeshAllocator,
+) -> Option<JobMeshSliceItem<'a>> {
+    let render_mesh = meshes.get(mesh)?;
+    let vertices = allocator.mesh_vertex_slice(&mesh.id())?;
+    let indices = match render_mesh.buffer_info {
+        RenderMeshBufferInfo::Indexed { .. } => Some(allocator.mesh_index_slice(&mesh.id())?),
+        RenderMeshBufferInfo::NonIndexed => None,
+    };
+
+    Some(JobMeshSliceItem {
+        mesh: render_mesh,
+        vertices,
+        indices,
+    })
+}
+
+impl ExtractComponent for JobMeshSlice {
+    type QueryData = Read<JobMeshSlice>;
+
+    type QueryFilter = ();
+
+    type Out = JobMeshSlice;
+
+    fn extract_component(item: QueryItem<'_, Self::QueryData>) -> Option<Self::Out> {
+        Some(item.clone())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use bevy_asset::Handle;
+    use bevy_ecs::{component::Component, world::World};
+
+    use crate::{
+        input::{JobInput, JobInputItem, JobInputStatus},
+        GraphicsJob, JobError, JobRunContext,
+    };
+
+    use super::JobMeshSlice;
+
+    #[derive(Clone, Component)]
+    struct MeshJob;
+
+    impl GraphicsJob for MeshJob {
+        type In = JobMeshSlice;
+
+        fn run(
+            &self,
+            _world: &World,
+            _context: &mut JobRunContext,
+            _input: JobInputItem<Self, Self::In>,
+        ) -> Result<(), JobError> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn waits_for_mesh_allocator() {
+        let world = World::new();
+        let slices = JobMeshSlice::new(Handle::default());
+        let status = <JobMeshSlice as JobInput<MeshJob>>::status(&slices, &world);
+        assert_eq!(status, JobInputStatus::Wait);
+    }
+}
@@ -0,0 +1,315 @@
+use bevy_app::{App, Plugin};
+use bevy_ecs::{
+    component::Component,
+    entity::Entity,
+    query::QueryItem,
+    schedule::IntoSystemConfigs,
+    system::{lifetimeless::Read, Commands, Query, Res},
+    world::World,
+};
+use bevy_math::UVec2;
+use bevy_render::{
+    camera::ExtractedCamera,
+    extract_component::{ExtractComponent, ExtractComponentPlugin},
+    render_resource::{
+        Extent3d, Texture, TextureDescriptor, TextureDimension, TextureFormat, TextureUsages,
+        TextureView, TextureViewDescriptor,
+    },
+    renderer::RenderDevice,
+    ExtractSchedule, Render, RenderApp, RenderSet,
+};
+
+use crate::{
+    device::{add_device_reset, remove_all},
+    label::job_resource_label,
+    memory::{texture_bytes, JobMemoryAllocation, JobMemoryCategory, JobMemoryUsage},
+    meta::JobTransientMemory,
+    runner::DynamicJob,
+    GraphicsJob, JobSet,
+};
+
+use super::{
+    camera_view::{extract_camera_views, CameraViewInput},
+    JobInput, JobInputStatus,
+};
+
+/// A [`JobInput`] that creates a fresh offscreen texture for a job to render into,
+/// for example to bake a texture that is then read back and saved to a file.
+///
+/// The texture is created once from the given descriptor and is owned by the job,
+/// so it's dropped when the job completes. If the descriptor changes before the job
+/// runs, the texture is recreated. With the `labels` feature, the texture's label is
+/// prefixed with the job's name. Add [`TextureUsages::COPY_SRC`] to the descriptor's
+/// usages to copy the result elsewhere, for example into a buffer for readback.
+///
+/// For screen-space jobs, add a [`JobOffscreenViewSize`] to size the texture to a
+/// camera's target instead, following it as the window resizes.
+///
+/// To count the texture towards the transient memory budget, spawn the job with
+/// the [`JobTransientMemory`] given by [`JobOffscreenTarget::transient_memory`].
+#[derive(Clone, Component, PartialEq, Debug)]
+pub struct JobOffscreenTarget(pub TextureDescriptor<'static>);
+
+/// Sizes a job's [`JobOffscreenTarget`] to the physical size of the given main world
+/// camera's render target, overriding the width and height of its descriptor.
+///
+/// The size is read each frame, so if the camera's target is resized while the job is
+/// waiting to run, the texture is recreated at the new size and the old one is dropped.
+/// The texture is only recreated when the size actually changes, so rapid resizes don't
+/// create a texture every frame. The job waits until the camera has been extracted.
+#[derive(Copy, Clone, Component, PartialEq, Eq, Debug)]
+pub struct JobOffscreenViewSize(pub Entity);
+
+/// The render world view entity a job's [`JobOffscreenViewSize`] refers to.
+#[derive(Copy, Clone, Component)]
+#[doc(hidden)]
+pub struct ExtractedJobOffscreenView(pub Entity);
+
+impl JobOffscreenTarget {
+    /// Describes a single-sampled 2D texture without mipmaps, usable as a render
+    /// attachment, a texture binding, and a copy source.
+    pub fn new(width: u32, height: u32, format: TextureFormat) -> Self {
+        Self(TextureDescriptor {
+            label: Some("job_offscreen_target"),
+            size: Extent3d {
+                width,
+                height,
+                depth_or_array_layers: 1,
+            },
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: TextureDimension::D2,
+            format,
+            usage: TextureUsages::RENDER_ATTACHMENT
+                | TextureUsages::TEXTURE_BINDING
+                | TextureUsages::COPY_SRC,
+            view_formats: &[],
+        })
+    }
+
+    /// Estimates the memory used by the texture, to declare as the job's
+    /// [`JobTransientMemory`] so that it counts towards
+    /// [`JobExecutionSettings::max_transient_bytes`](crate::JobExecutionSettings::max_transient_bytes).
+    pub fn transient_memory(&self) -> JobTransientMemory {
+        JobTransientMemory(texture_bytes(&self.0))
+    }
+}
+
+impl<J: GraphicsJob> JobInput<J> for JobOffscreenTarget {
+    type Data = (
+        Read<JobOffscreenTarget>,
+        Option<Read<ExtractedJobOffscreenView>>,
+        Option<Read<PreparedOffscreenTarget>>,
+    );
+
+    type Item<'a> = &'a PreparedOffscreenTarget;
+
+    fn plugin() -> impl Plugin {
+        |app: &mut App| {
+            if !app.is_plugin_added::<ExtractComponentPlugin<JobOffscreenTarget>>() {
+                app.add_plugins(ExtractComponentPlugin::<JobOffscreenTarget>::default());
+
+                if let Some(render_app) = app.get_sub_app_mut(RenderApp) {
+                    render_app.add_systems(
+                        ExtractSchedule,
+                        extract_camera_views::<JobOffscreenViewSize>,
+                    );
+                    render_app.add_systems(
+                        Render,
+                        prepare_offscreen_targets
+                            .in_set(RenderSet::PrepareResources)
+                            .after(JobSet::Setup),
+                    );
+                }
+
+                add_device_reset(app, remove_all::<PreparedOffscreenTarget>);
+            }
+        }
+    }
+
+    fn status((target, view, prepared): QueryItem<Self::Data>, world: &World) -> JobInputStatus {
+        let descriptor = target_descriptor(target, view, |view| world.get(view));
+        match (descriptor, prepared) {
+            (Some(descriptor), Some(prepared)) if prepared.descriptor == descriptor => {
+                JobInputStatus::Ready
+            }
+            _ => JobInputStatus::Wait,
+        }
+    }
+
+    fn get<'a>((.., prepared): QueryItem<'a, Self::Data>, _world: &'a World) -> Self::Item<'a> {
+        prepared.expect("offscreen target should be ready by this point")
+    }
+}
+
+impl ExtractComponent for JobOffscreenTarget {
+    type QueryData = Read<JobOffscreenTarget>;
+
+    type QueryFilter = ();
+
+    type Out = JobOffscreenTarget;
+
+    fn extract_component(item: QueryItem<'_, Self::QueryData>) -> Option<Self::Out> {
+        Some(item.clone())
+    }
+}
+
+/// The offscreen texture created for a job by [`JobOffscreenTarget`].
+#[derive(Component)]
+pub struct PreparedOffscreenTarget {
+    pub texture: Texture,
+    pub view: TextureView,
+    descriptor: TextureDescriptor<'static>,
+    _memory: JobMemoryAllocation,
+}
+
+/// The descriptor of a job's texture, sized to its view if it has one. Returns
+/// `None` while the size of the view isn't known.
+fn target_descriptor<'a>(
+    target: &JobOffscreenTarget,
+    view: Option<&ExtractedJobOffscreenView>,
+    camera: impl FnOnce(Entity) -> Option<&'a ExtractedCamera>,
+) -> Option<TextureDescriptor<'static>> {
+    let mut descriptor = target.0.clone();
+    if let Some(view) = view {
+        let UVec2 { x, y } = camera(view.0)?.physical_target_size?;
+        descriptor.size.width = x;
+        descriptor.size.height = y;
+    }
+    Some(descriptor)
+}
+
+impl CameraViewInput for JobOffscreenViewSize {
+    type Extracted = ExtractedJobOffscreenView;
+
+    type Data = ();
+
+    fn camera(&self) -> Entity {
+        self.0
+    }
+
+    fn extract(&self, view: Entity, (): ()) -> Self::Extracted {
+        ExtractedJobOffscreenView(view)
+    }
+}
+
+fn prepare_offscreen_targets(
+    targets: Query<(
+        Entity,
+        &JobOffscreenTarget,
+        Option<&ExtractedJobOffscreenView>,
+        Option<&PreparedOffscreenTarget>,
+        Option<&DynamicJob>,
+    )>,
+    cameras: Query<&ExtractedCamera>,
+    render_device: Res<RenderDevice>,
+    memory_usage: Res<JobMemoryUsage>,
+    mut commands: Commands,
+) {
+    for (entity, target, view, prepared, job) in &targets {
+        let Some(descriptor) = target_descriptor(target, view, |view| cameras.get(view).ok())
+        else {
+            continue;
+        };
+        if prepared.is_some_and(|prepared| prepared.descriptor == descriptor) {
+            continue;
+        }
+
+        let job = job.map(DynamicJob::label);
+        let label = job_resource_label(job, descriptor.label.unwrap_or("job_offscreen_target"));
+        let texture = render_device.create_texture(&TextureDescriptor {
+            label: Some(&label),
+            ..descriptor.clone()
+        });
+        let view_label = job_resource_label(job, "job_offscreen_target_view");
+        let view = texture.create_view(&TextureViewDescriptor {
+            label: Some(&view_label),
+            ..Default::default()
+        });
+        // replacing the prepared target drops the stale texture
+        let memory = memory_usage.allocate(
+            JobMemoryCategory::ScratchTextures,
+            texture_bytes(&descriptor),
+        );
+        commands.entity(entity).insert(PreparedOffscreenTarget {
+            texture,
+            view,
+            descriptor,
+            _memory: memory,
+        });
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use bevy_core_pipeline::core_2d::graph::Core2d;
+    use bevy_ecs::world::World;
+    use bevy_math::UVec2;
+    use bevy_render::{
+        camera::ExtractedCamera, render_graph::RenderSubGraph, render_resource::TextureFormat,
+    };
+
+    use crate::meta::JobTransientMemory;
+
+    use super::{target_descriptor, ExtractedJobOffscreenView, JobOffscreenTarget};
+
+    fn extracted_camera(size: UVec2) -> ExtractedCamera {
+        ExtractedCamera {
+            target: None,
+            physical_viewport_size: Some(size),
+            physical_target_size: Some(size),
+            viewport: None,
+            render_graph: Core2d.intern(),
+            order: 0,
+            output_mode: Default::default(),
+            msaa_writeback: false,
+            clear_color: Default::default(),
+            sorted_camera_index_for_target: 0,
+            exposure: 1.0,
+            hdr: false,
+        }
+    }
+
+    #[test]
+    fn view_sized_targets_follow_resizes() {
+        let mut world = World::new();
+        let camera = world.spawn(extracted_camera(UVec2::new(1280, 720))).id();
+        let target = JobOffscreenTarget::new(1, 1, TextureFormat::Rgba8Unorm);
+        let view = ExtractedJobOffscreenView(camera);
+
+        let descriptor = |world: &World| {
+            target_descriptor(&target, Some(&view), |view| world.get(view)).unwrap()
+        };
+        let before = descriptor(&world);
+        assert_eq!((before.size.width, before.size.height), (1280, 720));
+
+        // a prepared texture with the old descriptor no longer matches, so it's recreated
+        *world.get_mut::<ExtractedCamera>(camera).unwrap() =
+            extracted_camera(UVec2::new(1920, 1080));
+        let after = descriptor(&world);
+        assert_eq!((after.size.width, after.size.height), (1920, 1080));
+        assert_ne!(before, after);
+        assert_eq!(after, descriptor(&world));
+
+        // unsized targets keep their own size, and views without a size wait
+        let fixed = target_descriptor(&target, None, |_| None).unwrap();
+        assert_eq!((fixed.size.width, fixed.size.height), (1, 1));
+        world
+            .get_mut::<ExtractedCamera>(camera)
+            .unwrap()
+            .physical_target_size = None;
+        assert!(target_descriptor(&target, Some(&view), |view| world.get(view)).is_none());
+    }
+
+    #[test]
+    fn transient_memory_covers_all_mips() {
+        let mut target = JobOffscreenTarget::new(256, 256, TextureFormat::Rgba8Unorm);
+        assert_eq!(target.transient_memory(), JobTransientMemory(256 * 256 * 4));
+
+        target.0.mip_level_count = 2;
+        assert_eq!(
+            target.transient_memory(),
+            JobTransientMemory((256 * 256 + 128 * 128) * 4)
+        );
+    }
+}
@@ -0,0 +1,218 @@
+use bevy_app::{App, Plugin};
+use bevy_core_pipeline::oit::{
+    OitBuffers, OrderIndependentTransparencySettings, OrderIndependentTransparencySettingsOffset,
+};
+use bevy_ecs::{
+    component::Component,
+    entity::Entity,
+    query::QueryItem,
+    system::lifetimeless::Read,
+    world::{EntityRef, World},
+};
+use bevy_math::{UVec2, Vec4Swizzles};
+use bevy_render::{
+    camera::ExtractedCamera,
+    render_resource::{BindingResource, Buffer},
+    view::ExtractedView,
+    ExtractSchedule, RenderApp,
+};
+
+use crate::GraphicsJob;
+
+use super::{
+    camera_view::{extract_camera_views, CameraViewInput},
+    JobInput, JobInputStatus,
+};
+
+/// A [`JobInput`] providing the order-independent transparency buffers of a camera,
+/// for jobs that add their own fragments to the camera's OIT layers.
+///
+/// The camera must have bevy's [`OrderIndependentTransparencySettings`], which requires
+/// the `OrderIndependentTransparencyPlugin` added by bevy's `CorePipelinePlugin`, a GPU
+/// supporting `DownlevelFlags::FRAGMENT_WRITABLE_STORAGE`, and `Msaa::Off` on the camera.
+/// The job waits until the buffers are prepared, and fails if the camera doesn't use OIT.
+///
+/// Jobs execute before the render graph, so fragments written by a job are blended
+/// with the camera's transparent meshes by bevy's resolve pass later that frame. The
+/// resolve pass also resets the layers, so a job never sees fragments from the render graph.
+#[derive(Copy, Clone, Component, PartialEq, Eq, Debug)]
+pub struct JobOitBuffers(pub Entity);
+
+impl JobOitBuffers {
+    /// Uses the OIT buffers of the given main world camera.
+    pub const fn new(camera: Entity) -> Self {
+        Self(camera)
+    }
+}
+
+/// The render world view entity whose OIT buffers are used by a job's [`JobOitBuffers`].
+#[derive(Copy, Clone, Component)]
+#[doc(hidden)]
+pub struct ExtractedJobOitView(pub Entity);
+
+impl<J: GraphicsJob> JobInput<J> for JobOitBuffers {
+    type Data = Option<Read<ExtractedJobOitView>>;
+
+    type Item<'a> = JobOitBuffersItem<'a>;
+
+    fn plugin() -> impl Plugin {
+        |app: &mut App| {
+            if !app.is_plugin_added::<JobOitBuffersPlugin>() {
+                app.add_plugins(JobOitBuffersPlugin);
+            }
+        }
+    }
+
+    fn status(data: QueryItem<Self::Data>, world: &World) -> JobInputStatus {
+        let Some(view) = data.and_then(|view| world.get_entity(view.0).ok()) else {
+            return JobInputStatus::Wait;
+        };
+        oit_status(view, world)
+    }
+
+    fn get<'a>(data: QueryItem<'a, Self::Data>, world: &'a World) -> Self::Item<'a> {
+        let view = world
+            .get_entity(data.expect("OIT buffers should be ready by this point").0)
+            .expect("OIT buffers should be ready by this point");
+        oit_buffers(view, world).expect("OIT buffers should be ready by this point")
+    }
+}
+
+fn oit_status(view: EntityRef, world: &World) -> JobInputStatus {
+    if !view.contains::<ExtractedCamera>() {
+        return JobInputStatus::Wait;
+    }
+    if !view.contains::<OrderIndependentTransparencySettings>() {
+        return JobInputStatus::Fail;
+    }
+    match oit_buffers(view, world) {
+        Some(_) => JobInputStatus::Ready,
+        None => JobInputStatus::Wait,
+    }
+}
+
+fn oit_buffers<'a>(view: EntityRef<'a>, world: &'a World) -> Option<JobOitBuffersItem<'a>> {
+    let buffers = world.get_resource::<OitBuffers>()?;
+    Some(JobOitBuffersItem {
+        layers: buffers.layers.buffer()?,
+        layer_ids: buffers.layer_ids.buffer()?,
+        settings: buffers.settings.binding()?,
+        settings_offset: view
+            .get::<OrderIndependentTransparencySettingsOffset>()?
+            .offset,
+        layer_count: view
+            .get::<OrderIndependentTransparencySettings>()?
+            .layer_count as u32,
+        viewport_size: view.get::<ExtractedView>()?.viewport.zw(),
+        view,
+    })
+}
+
+/// The OIT buffers provided by [`JobOitBuffers`]. They're shared by every camera
+/// using OIT, and sized for the largest one.
+///
+/// The layers are laid out like in bevy's `oit_draw` shader function: the fragment
+/// count of the pixel at `index = x + y * viewport_size.x` is `layer_ids[index]`, and
+/// its `n`th fragment is `layers[index + n * viewport_size.x * viewport_size.y]`,
+/// packing its color as `rgb9e5`, and its depth and alpha as 24 and 8 bits.
+pub struct JobOitBuffersItem<'a> {
+    /// The render world view entity, for looking up other view components.
+    pub view: EntityRef<'a>,
+    /// The fragments of every pixel, as an `array<vec2<u32>>`.
+    pub layers: &'a Buffer,
+    /// The number of fragments written to each pixel, as an `array<atomic<i32>>`.
+    pub layer_ids: &'a Buffer,
+    /// The uniform buffer of every camera's [`OrderIndependentTransparencySettings`],
+    /// bound with a dynamic offset.
+    pub settings: BindingResource<'a>,
+    /// The dynamic offset of this camera's settings in [`settings`](Self::settings).
+    pub settings_offset: u32,
+    /// The maximum number of fragments stored for each pixel.
+    pub layer_count: u32,
+    /// The size of the view's viewport, which the layers are indexed by.
+    pub viewport_size: UVec2,
+}
+
+impl<'a> JobOitBuffersItem<'a> {
+    /// Returns the binding of the buffer holding the fragments of every pixel.
+    pub fn layers_binding(&self) -> BindingResource<'a> {
+        self.layers.as_entire_binding()
+    }
+
+    /// Returns the binding of the buffer counting the fragments of every pixel.
+    pub fn layer_ids_binding(&self) -> BindingResource<'a> {
+        self.layer_ids.as_entire_binding()
+    }
+}
+
+struct JobOitBuffersPlugin;
+
+impl Plugin for JobOitBuffersPlugin {
+    fn build(&self, app: &mut App) {
+        if let Some(render_app) = app.get_sub_app_mut(RenderApp) {
+            render_app.add_systems(ExtractSchedule, extract_camera_views::<JobOitBuffers>);
+        }
+    }
+}
+
+impl CameraViewInput for JobOitBuffers {
+    type Extracted = ExtractedJobOitView;
+
+    type Data = ();
+
+    fn camera(&self) -> Entity {
+        self.0
+    }
+
+    fn extract(&self, view: Entity, (): ()) -> Self::Extracted {
+        ExtractedJobOitView(view)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use bevy_core_pipeline::{core_3d::graph::Core3d, oit::OrderIndependentTransparencySettings};
+    use bevy_ecs::world::World;
+    use bevy_math::UVec2;
+    use bevy_render::{camera::ExtractedCamera, render_graph::RenderSubGraph};
+
+    use crate::input::JobInputStatus;
+
+    use super::oit_status;
+
+    fn extracted_camera() -> ExtractedCamera {
+        ExtractedCamera {
+            target: None,
+            physical_viewport_size: Some(UVec2::splat(64)),
+            physical_target_size: Some(UVec2::splat(64)),
+            viewport: None,
+            render_graph: Core3d.intern(),
+            order: 0,
+            output_mode: Default::default(),
+            msaa_writeback: false,
+            clear_color: Default::default(),
+            sorted_camera_index_for_target: 0,
+            exposure: 1.0,
+            hdr: false,
+        }
+    }
+
+    #[test]
+    fn fails_without_oit() {
+        let mut world = World::new();
+        let pending = world.spawn_empty().id();
+        let opaque = world.spawn(extracted_camera()).id();
+        let transparent = world
+            .spawn((
+                extracted_camera(),
+                OrderIndependentTransparencySettings::default(),
+            ))
+            .id();
+
+        let status = |view| oit_status(world.entity(view), &world);
+        assert_eq!(status(pending), JobInputStatus::Wait);
+        assert_eq!(status(opaque), JobInputStatus::Fail);
+        // the buffers haven't been prepared
+        assert_eq!(status(transparent), JobInputStatus::Wait);
+    }
+}
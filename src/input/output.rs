@@ -0,0 +1,612 @@
+use std::sync::Mutex;
+
+use bevy_app::{App, Plugin};
+use bevy_ecs::{
+    component::Component,
+    entity::Entity,
+    query::{Added, QueryItem, With},
+    schedule::IntoSystemConfigs,
+    system::{lifetimeless::Read, Commands, Query, Res, Resource},
+    world::World,
+};
+use bevy_render::{
+    extract_component::{ExtractComponent, ExtractComponentPlugin},
+    render_resource::{Buffer, Texture},
+    sync_world::{MainEntity, RenderEntity},
+    Extract, ExtractSchedule, Render, RenderApp,
+};
+use bevy_utils::HashMap;
+
+use crate::{
+    device::add_device_reset,
+    runner::{sync_completed_jobs, JobReady},
+    GraphicsJob, JobMarker, JobSet,
+};
+
+use super::{JobInput, JobInputStatus};
+
+/// Names a resource published by one job with [`JobOutput`], and consumed
+/// by others with [`JobDependency`].
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+pub struct JobOutputLabel(pub &'static str);
+
+/// A GPU resource published by a job for other jobs to consume.
+#[derive(Clone, Debug)]
+pub enum JobOutputResource {
+    Buffer(Buffer),
+    Texture(Texture),
+}
+
+impl From<Buffer> for JobOutputResource {
+    fn from(buffer: Buffer) -> Self {
+        Self::Buffer(buffer)
+    }
+}
+
+impl From<Texture> for JobOutputResource {
+    fn from(texture: Texture) -> Self {
+        Self::Texture(texture)
+    }
+}
+
+impl JobOutputResource {
+    pub fn buffer(&self) -> Option<&Buffer> {
+        match self {
+            Self::Buffer(buffer) => Some(buffer),
+            Self::Texture(_) => None,
+        }
+    }
+
+    pub fn texture(&self) -> Option<&Texture> {
+        match self {
+            Self::Texture(texture) => Some(texture),
+            Self::Buffer(_) => None,
+        }
+    }
+}
+
+/// Describes how long a published output is kept alive for its dependents.
+/// An output is always replaced when it's published again.
+#[derive(Copy, Clone, Default, PartialEq, Eq, Debug)]
+pub enum JobOutputLifetime {
+    /// Frees the output once it has been consumed and no job depending on it is
+    /// left, for one-off outputs.
+    #[default]
+    UntilDependentsDone,
+    /// Keeps the output until a job publishing it is spawned again, so that
+    /// dependents never see the output of the previous run. For jobs that are
+    /// spawned each frame.
+    UntilJobReset,
+    /// Keeps the output until it's replaced, so that it's always available
+    /// to dependents, even while a new one is being produced.
+    Forever,
+}
+
+impl JobOutputLifetime {
+    /// Returns whether an output should be freed during cleanup.
+    fn expired(self, consumed: bool, has_dependents: bool) -> bool {
+        match self {
+            Self::UntilDependentsDone => consumed && !has_dependents,
+            Self::UntilJobReset | Self::Forever => false,
+        }
+    }
+
+    /// Returns whether an output should be freed when its producer is spawned again.
+    fn expires_on_reset(self) -> bool {
+        self == Self::UntilJobReset
+    }
+}
+
+/// A [`JobInput`] that lets a job publish a resource under a label, for jobs
+/// with a matching [`JobDependency`] to consume.
+#[derive(Copy, Clone, Component, PartialEq, Eq, Debug)]
+pub struct JobOutput {
+    pub label: JobOutputLabel,
+    pub lifetime: JobOutputLifetime,
+}
+
+impl JobOutput {
+    pub const fn new(label: &'static str, lifetime: JobOutputLifetime) -> Self {
+        Self {
+            label: JobOutputLabel(label),
+            lifetime,
+        }
+    }
+}
+
+/// Publishes the output of a job with [`JobOutput`].
+pub struct JobOutputWriter<'a> {
+    output: &'a JobOutput,
+    outputs: &'a JobOutputs,
+}
+
+impl JobOutputWriter<'_> {
+    /// Publishes the job's output. Dependents can use it once the job's commands
+    /// are submitted, since they're submitted in execution order.
+    pub fn publish(&self, resource: impl Into<JobOutputResource>) {
+        self.outputs.0.lock().unwrap().insert(
+            OutputKey::Label(self.output.label),
+            PublishedOutput {
+                resource: resource.into(),
+                lifetime: self.output.lifetime,
+                consumed: false,
+            },
+        );
+    }
+}
+
+impl<J: GraphicsJob> JobInput<J> for JobOutput {
+    type Data = Read<JobOutput>;
+
+    type Item<'a> = JobOutputWriter<'a>;
+
+    fn plugin() -> impl Plugin {
+        |app: &mut App| {
+            if !app.is_plugin_added::<JobOutputsPlugin>() {
+                app.add_plugins(JobOutputsPlugin);
+            }
+        }
+    }
+
+    fn status(_data: QueryItem<Self::Data>, _world: &World) -> JobInputStatus {
+        JobInputStatus::Ready
+    }
+
+    fn get<'a>(output: QueryItem<'a, Self::Data>, world: &'a World) -> Self::Item<'a> {
+        JobOutputWriter {
+            output,
+            outputs: world.resource::<JobOutputs>(),
+        }
+    }
+}
+
+/// A [`JobInput`] providing the resource published by another job under the given
+/// label. The job waits until the resource is published.
+#[derive(Copy, Clone, Component, PartialEq, Eq, Debug)]
+pub struct JobDependency(pub JobOutputLabel);
+
+impl JobDependency {
+    pub const fn new(label: &'static str) -> Self {
+        Self(JobOutputLabel(label))
+    }
+}
+
+impl<J: GraphicsJob> JobInput<J> for JobDependency {
+    type Data = Read<JobDependency>;
+
+    type Item<'a> = JobOutputResource;
+
+    fn plugin() -> impl Plugin {
+        <JobOutput as JobInput<J>>::plugin()
+    }
+
+    fn status(dependency: QueryItem<Self::Data>, world: &World) -> JobInputStatus {
+        let outputs = world.resource::<JobOutputs>().0.lock().unwrap();
+        match outputs.contains_key(&OutputKey::Label(dependency.0)) {
+            true => JobInputStatus::Ready,
+            false => JobInputStatus::Wait,
+        }
+    }
+
+    fn get<'a>(dependency: QueryItem<'a, Self::Data>, world: &'a World) -> Self::Item<'a> {
+        let mut outputs = world.resource::<JobOutputs>().0.lock().unwrap();
+        let output = outputs
+            .get_mut(&OutputKey::Label(dependency.0))
+            .expect("job output should be ready by this point");
+        output.consumed = true;
+        output.resource.clone()
+    }
+}
+
+/// A [`JobInput`] that lets a job publish several textures under named roles, like
+/// its color and depth targets, for jobs with a [`JobOutputTexture`] naming this job
+/// and one of its roles to bind. Unlike [`JobOutput`], outputs are tied to the job
+/// that publishes them, so several jobs can publish the same roles.
+///
+/// A [`JobOutputLifetime::UntilJobReset`] output is freed when the job is spawned
+/// again on the same entity. A [`JobOutputLifetime::Forever`] output is only freed
+/// when it's replaced, so it should only be used for producers that are reused.
+#[derive(Copy, Clone, Component, PartialEq, Eq, Debug)]
+pub struct JobOutputTextures {
+    /// The roles the job publishes textures under.
+    pub roles: &'static [&'static str],
+    pub lifetime: JobOutputLifetime,
+}
+
+impl JobOutputTextures {
+    pub const fn new(roles: &'static [&'static str], lifetime: JobOutputLifetime) -> Self {
+        Self { roles, lifetime }
+    }
+}
+
+/// Publishes the textures of a job with [`JobOutputTextures`].
+pub struct JobOutputTexturesWriter<'a> {
+    producer: MainEntity,
+    output: &'a JobOutputTextures,
+    outputs: &'a JobOutputs,
+}
+
+impl JobOutputTexturesWriter<'_> {
+    /// Publishes the job's texture for a role. Dependents can use it once the job's
+    /// commands are submitted, since they're submitted in execution order.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the role isn't one of the job's [`JobOutputTextures::roles`].
+    pub fn publish(&self, role: &'static str, texture: Texture) {
+        assert!(
+            self.output.roles.contains(&role),
+            "published a texture for the undeclared output role `{role}`"
+        );
+        self.outputs.0.lock().unwrap().insert(
+            OutputKey::Role(self.producer.id(), role),
+            PublishedOutput {
+                resource: texture.into(),
+                lifetime: self.output.lifetime,
+                consumed: false,
+            },
+        );
+    }
+}
+
+impl<J: GraphicsJob> JobInput<J> for JobOutputTextures {
+    type Data = (Read<JobOutputTextures>, Read<MainEntity>);
+
+    type Item<'a> = JobOutputTexturesWriter<'a>;
+
+    fn plugin() -> impl Plugin {
+        <JobOutput as JobInput<J>>::plugin()
+    }
+
+    fn status(_data: QueryItem<Self::Data>, _world: &World) -> JobInputStatus {
+        JobInputStatus::Ready
+    }
+
+    fn get<'a>((output, producer): QueryItem<'a, Self::Data>, world: &'a World) -> Self::Item<'a> {
+        JobOutputTexturesWriter {
+            producer: *producer,
+            output,
+            outputs: world.resource::<JobOutputs>(),
+        }
+    }
+}
+
+/// A [`JobInput`] providing the texture published by another job for a role, with
+/// [`JobOutputTextures`]. The job waits until the texture is published, and fails if
+/// the producer doesn't declare the role, or is gone without having published it.
+#[derive(Copy, Clone, Component, PartialEq, Eq, Debug)]
+pub struct JobOutputTexture {
+    /// The main world entity of the job publishing the texture.
+    pub producer: Entity,
+    pub role: &'static str,
+}
+
+impl JobOutputTexture {
+    pub const fn new(producer: Entity, role: &'static str) -> Self {
+        Self { producer, role }
+    }
+
+    fn key(&self) -> OutputKey {
+        OutputKey::Role(self.producer, self.role)
+    }
+}
+
+/// The render world entity of the producer named by a job's [`JobOutputTexture`].
+#[derive(Copy, Clone, Component)]
+#[doc(hidden)]
+pub struct ExtractedJobOutputProducer(pub Entity);
+
+impl<J: GraphicsJob> JobInput<J> for JobOutputTexture {
+    type Data = (
+        Read<JobOutputTexture>,
+        Option<Read<ExtractedJobOutputProducer>>,
+    );
+
+    type Item<'a> = Texture;
+
+    fn plugin() -> impl Plugin {
+        <JobOutput as JobInput<J>>::plugin()
+    }
+
+    fn status((dependency, producer): QueryItem<Self::Data>, world: &World) -> JobInputStatus {
+        let outputs = world.resource::<JobOutputs>().0.lock().unwrap();
+        if outputs.contains_key(&dependency.key()) {
+            return JobInputStatus::Ready;
+        }
+
+        match producer.and_then(|producer| world.get::<JobOutputTextures>(producer.0)) {
+            Some(output) if output.roles.contains(&dependency.role) => JobInputStatus::Wait,
+            _ => JobInputStatus::Fail,
+        }
+    }
+
+    fn get<'a>((dependency, _): QueryItem<'a, Self::Data>, world: &'a World) -> Self::Item<'a> {
+        let mut outputs = world.resource::<JobOutputs>().0.lock().unwrap();
+        let output = outputs
+            .get_mut(&dependency.key())
+            .expect("job output texture should be ready by this point");
+        output.consumed = true;
+        output
+            .resource
+            .texture()
+            .expect("job output texture should be a texture")
+            .clone()
+    }
+}
+
+impl ExtractComponent for JobOutput {
+    type QueryData = Read<JobOutput>;
+
+    type QueryFilter = ();
+
+    type Out = JobOutput;
+
+    fn extract_component(item: QueryItem<'_, Self::QueryData>) -> Option<Self::Out> {
+        Some(*item)
+    }
+}
+
+impl ExtractComponent for JobDependency {
+    type QueryData = Read<JobDependency>;
+
+    type QueryFilter = ();
+
+    type Out = JobDependency;
+
+    fn extract_component(item: QueryItem<'_, Self::QueryData>) -> Option<Self::Out> {
+        Some(*item)
+    }
+}
+
+impl ExtractComponent for JobOutputTextures {
+    type QueryData = Read<JobOutputTextures>;
+
+    type QueryFilter = ();
+
+    type Out = JobOutputTextures;
+
+    fn extract_component(item: QueryItem<'_, Self::QueryData>) -> Option<Self::Out> {
+        Some(*item)
+    }
+}
+
+impl ExtractComponent for JobOutputTexture {
+    type QueryData = Read<JobOutputTexture>;
+
+    type QueryFilter = ();
+
+    type Out = JobOutputTexture;
+
+    fn extract_component(item: QueryItem<'_, Self::QueryData>) -> Option<Self::Out> {
+        Some(*item)
+    }
+}
+
+/// Identifies a published output, either by its label or by its producer and role.
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+enum OutputKey {
+    Label(JobOutputLabel),
+    /// The main world entity of the producer, and the role.
+    Role(Entity, &'static str),
+}
+
+struct PublishedOutput {
+    resource: JobOutputResource,
+    lifetime: JobOutputLifetime,
+    consumed: bool,
+}
+
+/// The outputs published by jobs, written to while jobs execute.
+#[derive(Resource, Default)]
+pub(crate) struct JobOutputs(Mutex<HashMap<OutputKey, PublishedOutput>>);
+
+struct JobOutputsPlugin;
+
+impl Plugin for JobOutputsPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_plugins((
+            ExtractComponentPlugin::<JobOutput>::default(),
+            ExtractComponentPlugin::<JobDependency>::default(),
+            ExtractComponentPlugin::<JobOutputTextures>::default(),
+            ExtractComponentPlugin::<JobOutputTexture>::default(),
+        ));
+
+        if let Some(render_app) = app.get_sub_app_mut(RenderApp) {
+            render_app
+                .init_resource::<JobOutputs>()
+                .add_systems(ExtractSchedule, extract_job_output_producers)
+                .add_systems(
+                    Render,
+                    (
+                        reset_job_outputs.in_set(JobSet::Setup),
+                        free_job_outputs
+                            .in_set(JobSet::Cleanup)
+                            .after(sync_completed_jobs),
+                    ),
+                );
+        }
+
+        add_device_reset(app, |world| {
+            world.insert_resource(JobOutputs::default());
+        });
+    }
+}
+
+fn extract_job_output_producers(
+    jobs: Extract<Query<(RenderEntity, &JobOutputTexture), Added<JobMarker>>>,
+    producers: Extract<Query<&RenderEntity>>,
+    mut commands: Commands,
+) {
+    for (render_entity, dependency) in &jobs {
+        if let Ok(producer) = producers.get(dependency.producer) {
+            commands
+                .entity(render_entity)
+                .insert(ExtractedJobOutputProducer(producer.id()));
+        }
+    }
+}
+
+/// Frees [`JobOutputLifetime::UntilJobReset`] outputs when a job producing them
+/// is spawned again. Dependents that were ready to consume the old output wait for
+/// the new one instead.
+fn reset_job_outputs(
+    producers: Query<&JobOutput, Added<JobOutput>>,
+    texture_producers: Query<(&MainEntity, &JobOutputTextures), Added<JobOutputTextures>>,
+    dependents: Query<(Entity, &JobDependency), With<JobReady>>,
+    texture_dependents: Query<(Entity, &JobOutputTexture), With<JobReady>>,
+    outputs: Res<JobOutputs>,
+    mut commands: Commands,
+) {
+    let mut outputs = outputs.0.lock().unwrap();
+    for producer in &producers {
+        if !producer.lifetime.expires_on_reset()
+            || outputs.remove(&OutputKey::Label(producer.label)).is_none()
+        {
+            continue;
+        }
+
+        for (entity, dependency) in &dependents {
+            if dependency.0 == producer.label {
+                commands.entity(entity).remove::<JobReady>();
+            }
+        }
+    }
+
+    for (producer, output) in &texture_producers {
+        if !output.lifetime.expires_on_reset() {
+            continue;
+        }
+
+        for &role in output.roles {
+            if outputs
+                .remove(&OutputKey::Role(producer.id(), role))
+                .is_none()
+            {
+                continue;
+            }
+
+            for (entity, dependency) in &texture_dependents {
+                if dependency.key() == OutputKey::Role(producer.id(), role) {
+                    commands.entity(entity).remove::<JobReady>();
+                }
+            }
+        }
+    }
+}
+
+fn free_job_outputs(
+    dependents: Query<&JobDependency>,
+    texture_dependents: Query<&JobOutputTexture>,
+    outputs: Res<JobOutputs>,
+) {
+    outputs.0.lock().unwrap().retain(|key, output| {
+        let has_dependents = match key {
+            OutputKey::Label(label) => dependents.iter().any(|dependency| dependency.0 == *label),
+            OutputKey::Role(..) => texture_dependents
+                .iter()
+                .any(|dependency| dependency.key() == *key),
+        };
+        !output.lifetime.expired(output.consumed, has_dependents)
+    });
+}
+
+#[cfg(test)]
+mod test {
+    use bevy_ecs::{component::Component, world::World};
+
+    use crate::{
+        input::{JobInput, JobInputItem, JobInputStatus},
+        GraphicsJob, JobError, JobRunContext,
+    };
+
+    use super::{
+        ExtractedJobOutputProducer, JobOutputLifetime, JobOutputTexture, JobOutputTextures,
+        JobOutputs,
+    };
+
+    /// Returns whether an output is still alive after a producer publishes it,
+    /// a consumer runs and completes, and the producer is spawned again.
+    fn lifecycle(lifetime: JobOutputLifetime) -> [bool; 3] {
+        // published, with a consumer still waiting
+        let published = !lifetime.expired(false, true);
+        // consumed, and the consumer is done
+        let consumed = published && !lifetime.expired(true, false);
+        // the producer is spawned again
+        let reset = consumed && !lifetime.expires_on_reset();
+        [published, consumed, reset]
+    }
+
+    #[test]
+    fn until_dependents_done() {
+        let lifetime = JobOutputLifetime::UntilDependentsDone;
+        assert_eq!(lifecycle(lifetime), [true, false, false]);
+        // kept while a consumer that already ran is joined by another that hasn't
+        assert!(!lifetime.expired(true, true));
+        // kept until the first consumer runs
+        assert!(!lifetime.expired(false, false));
+    }
+
+    #[test]
+    fn until_job_reset() {
+        assert_eq!(
+            lifecycle(JobOutputLifetime::UntilJobReset),
+            [true, true, false]
+        );
+    }
+
+    #[test]
+    fn forever() {
+        assert_eq!(lifecycle(JobOutputLifetime::Forever), [true, true, true]);
+    }
+
+    #[derive(Clone, Component)]
+    struct CompositeJob;
+
+    impl GraphicsJob for CompositeJob {
+        type In = JobOutputTexture;
+
+        fn run(
+            &self,
+            _world: &World,
+            _context: &mut JobRunContext,
+            _input: JobInputItem<Self, Self::In>,
+        ) -> Result<(), JobError> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn output_textures_are_bound_by_declared_role() {
+        let mut world = World::new();
+        world.init_resource::<JobOutputs>();
+        let status = <JobOutputTexture as JobInput<CompositeJob>>::status;
+
+        let producer = world
+            .spawn(JobOutputTextures::new(
+                &["color", "depth"],
+                JobOutputLifetime::UntilDependentsDone,
+            ))
+            .id();
+        let extracted = ExtractedJobOutputProducer(producer);
+
+        let color = JobOutputTexture::new(producer, "color");
+        assert_eq!(
+            status((&color, Some(&extracted)), &world),
+            JobInputStatus::Wait
+        );
+
+        let motion = JobOutputTexture::new(producer, "motion");
+        assert_eq!(
+            status((&motion, Some(&extracted)), &world),
+            JobInputStatus::Fail
+        );
+
+        // the producer is gone without having published its color
+        world.despawn(producer);
+        assert_eq!(
+            status((&color, Some(&extracted)), &world),
+            JobInputStatus::Fail
+        );
+        assert_eq!(status((&color, None), &world), JobInputStatus::Fail);
+    }
+}
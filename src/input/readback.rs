@@ -0,0 +1,564 @@
+use core::{marker::PhantomData, mem};
+use std::{
+    sync::{Arc, Mutex},
+    thread,
+};
+
+use bevy_app::{App, Plugin, PreUpdate};
+use bevy_core::FrameCount;
+use bevy_ecs::{
+    component::Component,
+    entity::Entity,
+    observer::Trigger,
+    query::{QueryItem, With, Without},
+    schedule::IntoSystemConfigs,
+    system::{lifetimeless::Read, Commands, Query, Res, ResMut, Resource},
+    world::World,
+};
+use bevy_render::{
+    extract_component::{ExtractComponent, ExtractComponentPlugin},
+    render_resource::{Buffer, BufferDescriptor, BufferUsages, Maintain, MapMode},
+    renderer::RenderDevice,
+    Render, RenderApp, RenderSet,
+};
+use bevy_utils::tracing::warn;
+use crossbeam_channel::{Receiver, Sender};
+
+use crate::{
+    device::{add_device_reset, remove_all},
+    memory::{JobMemoryAllocation, JobMemoryCategory, JobMemoryUsage},
+    runner::sync_completed_jobs,
+    GraphicsJob, JobComplete, JobError, JobExecutionSettings, JobSet,
+};
+
+use super::{build_job_file_output, JobFileOutput, JobFileWriter, JobInput, JobInputStatus};
+
+/// A [`JobInput`] providing a buffer for a job to copy its results into, which is
+/// then read back on the CPU without ever blocking the render thread. The most recent
+/// result of any job of type `J` is available in the main world as [`JobReadbackResult<J>`].
+///
+/// Each job type has two readback buffers, which are used in turn: while the results
+/// of one frame are being mapped, the next job can copy into the other buffer. A
+/// result is usually available one or two frames after its job runs, depending on
+/// how far the GPU is behind. If both buffers are still being mapped, new jobs wait
+/// for one to free up. If a newer result finishes mapping at the same time as an
+/// older one, only the newer result is kept.
+///
+/// Where the device is polled for finished mappings is configured with
+/// [`JobExecutionSettings::readback_polling`].
+///
+/// To write a job's result to a file instead, for example in a headless bake tool,
+/// spawn it with a [`JobFileOutput`] too. Results written to files are still
+/// available as [`JobReadbackResult<J>`].
+#[derive(Copy, Clone, Component, PartialEq, Eq, Debug)]
+pub struct JobReadback {
+    /// The number of bytes the job copies into the readback buffer.
+    pub size: u64,
+}
+
+impl JobReadback {
+    pub const fn new(size: u64) -> Self {
+        Self { size }
+    }
+}
+
+/// Describes where the device is polled for readback buffers that finished mapping.
+/// `wgpu` only calls the callbacks of mapped buffers while the device is polled.
+///
+/// On the web, buffers are mapped by the browser without being polled, and threads
+/// can't block on the GPU, so [`ReadbackPolling::Thread`] behaves like
+/// [`ReadbackPolling::RenderThread`] there.
+#[derive(Copy, Clone, Default, PartialEq, Eq, Debug)]
+pub enum ReadbackPolling {
+    /// Polls the device on the render thread once per frame, without waiting on the GPU.
+    /// Results are only picked up at the end of a frame, and polling can stall the
+    /// render thread on drivers where it's slow.
+    #[default]
+    RenderThread,
+    /// Polls the device from a dedicated thread, which waits on the GPU whenever a
+    /// readback buffer starts mapping, so the render thread never polls. The thread is
+    /// spawned the first time a buffer is mapped. Results are still handed to the main
+    /// world at the end of the frame in which they finish mapping.
+    Thread,
+}
+
+/// The most recent result read back from a job of type `J` with [`JobReadback`].
+/// This resource is inserted once the first result is available.
+#[derive(Resource, Clone, Debug)]
+pub struct JobReadbackResult<J: GraphicsJob> {
+    /// The [`FrameCount`] of the frame in which the job ran.
+    pub frame: u32,
+    pub data: Vec<u8>,
+    marker: PhantomData<J>,
+}
+
+impl<J: GraphicsJob> JobInput<J> for JobReadback {
+    type Data = Option<Read<PreparedJobReadback>>;
+
+    type Item<'a> = &'a Buffer;
+
+    fn plugin() -> impl Plugin {
+        |app: &mut App| {
+            if !app.is_plugin_added::<ExtractComponentPlugin<JobReadback>>() {
+                app.add_plugins(ExtractComponentPlugin::<JobReadback>::default());
+
+                if let Some(render_app) = app.get_sub_app_mut(RenderApp) {
+                    render_app.init_resource::<JobReadbackPoller>().add_systems(
+                        Render,
+                        poll_job_readbacks
+                            .in_set(JobSet::Cleanup)
+                            .after(sync_completed_jobs),
+                    );
+                }
+
+                add_device_reset(app, remove_all::<PreparedJobReadback>);
+                add_device_reset(app, reset_job_readback_poller);
+                build_job_file_output(app);
+            }
+
+            if !app.world().contains_resource::<JobReadbackReceiver<J>>() {
+                let (sender, receiver) = crossbeam_channel::unbounded();
+                app.insert_resource(JobReadbackReceiver::<J>(receiver, PhantomData))
+                    .add_systems(PreUpdate, receive_job_readbacks::<J>);
+
+                if let Some(render_app) = app.get_sub_app_mut(RenderApp) {
+                    render_app
+                        .insert_resource(JobReadbackSender::<J>(sender, PhantomData))
+                        .init_resource::<JobReadbackRing<J>>()
+                        .add_systems(
+                            Render,
+                            (
+                                prepare_job_readbacks::<J>.in_set(RenderSet::PrepareResources),
+                                collect_job_readbacks::<J>
+                                    .in_set(JobSet::Cleanup)
+                                    .after(poll_job_readbacks),
+                            ),
+                        );
+                    render_app.world_mut().add_observer(map_job_readback::<J>);
+                }
+
+                add_device_reset(app, reset_job_readback_ring::<J>);
+            }
+        }
+    }
+
+    fn status(prepared: QueryItem<Self::Data>, _world: &World) -> JobInputStatus {
+        match prepared {
+            Some(_) => JobInputStatus::Ready,
+            None => JobInputStatus::Wait,
+        }
+    }
+
+    fn get<'a>(prepared: QueryItem<'a, Self::Data>, _world: &'a World) -> Self::Item<'a> {
+        &prepared
+            .expect("readback buffer should be ready by this point")
+            .buffer
+    }
+}
+
+impl ExtractComponent for JobReadback {
+    type QueryData = Read<JobReadback>;
+
+    type QueryFilter = ();
+
+    type Out = JobReadback;
+
+    fn extract_component(item: QueryItem<'_, Self::QueryData>) -> Option<Self::Out> {
+        Some(*item)
+    }
+}
+
+/// The readback buffer assigned to a job by [`JobReadback`].
+#[derive(Component)]
+#[doc(hidden)]
+pub struct PreparedJobReadback {
+    slot: usize,
+    buffer: Buffer,
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+enum ReadbackState {
+    Free,
+    /// Copied into by the given job, which hasn't completed yet.
+    Assigned(Entity),
+    /// Waiting for the buffer to be mapped, after the job ran in the given frame.
+    Mapping(u32),
+    Mapped(u32),
+    /// The buffer couldn't be mapped, so its contents are discarded.
+    Failed,
+}
+
+struct ReadbackSlot {
+    buffer: Option<(Buffer, JobMemoryAllocation)>,
+    // written from the map callback, which may run on any thread
+    state: Arc<Mutex<ReadbackState>>,
+    /// The file to write the result being mapped to, if its job has one.
+    file: Option<JobFileOutput>,
+}
+
+impl Default for ReadbackSlot {
+    fn default() -> Self {
+        Self {
+            buffer: None,
+            state: Arc::new(Mutex::new(ReadbackState::Free)),
+            file: None,
+        }
+    }
+}
+
+impl ReadbackSlot {
+    fn state(&self) -> ReadbackState {
+        *self.state.lock().unwrap()
+    }
+
+    fn set_state(&self, state: ReadbackState) {
+        *self.state.lock().unwrap() = state;
+    }
+}
+
+/// The double-buffered readback buffers for jobs of type `J`.
+#[derive(Resource)]
+struct JobReadbackRing<J> {
+    slots: [ReadbackSlot; 2],
+    marker: PhantomData<J>,
+}
+
+impl<J> Default for JobReadbackRing<J> {
+    fn default() -> Self {
+        Self {
+            slots: Default::default(),
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<J> JobReadbackRing<J> {
+    /// Frees slots assigned to jobs that no longer exist, for example
+    /// because they were despawned before running.
+    fn release_orphans(&self, exists: impl Fn(Entity) -> bool) {
+        for slot in &self.slots {
+            if let ReadbackState::Assigned(job) = slot.state() {
+                if !exists(job) {
+                    slot.set_state(ReadbackState::Free);
+                }
+            }
+        }
+    }
+
+    /// Assigns a free slot to a job, if there is one.
+    fn acquire(&self, job: Entity) -> Option<usize> {
+        let index = self
+            .slots
+            .iter()
+            .position(|slot| slot.state() == ReadbackState::Free)?;
+        self.slots[index].set_state(ReadbackState::Assigned(job));
+        Some(index)
+    }
+
+    /// Returns the slot with the most recent mapped result, and frees every other
+    /// slot that finished mapping. The returned slot must be freed after it's read.
+    fn take_latest(&self) -> Option<(usize, u32)> {
+        let mut latest: Option<(usize, u32)> = None;
+        for (index, slot) in self.slots.iter().enumerate() {
+            match slot.state() {
+                ReadbackState::Mapped(frame) => {
+                    if let Some((older, _)) = latest.filter(|(_, latest)| *latest < frame) {
+                        self.free(older);
+                        latest = Some((index, frame));
+                    } else if latest.is_some() {
+                        self.free(index);
+                    } else {
+                        latest = Some((index, frame));
+                    }
+                }
+                ReadbackState::Failed => self.free(index),
+                _ => {}
+            }
+        }
+        latest
+    }
+
+    fn free(&self, index: usize) {
+        let slot = &self.slots[index];
+        if let Some((buffer, _)) = &slot.buffer {
+            if matches!(slot.state(), ReadbackState::Mapped(_)) {
+                buffer.unmap();
+            }
+        }
+        slot.set_state(ReadbackState::Free);
+    }
+}
+
+#[derive(Resource)]
+struct JobReadbackSender<J>(Sender<(u32, Vec<u8>)>, PhantomData<J>);
+
+#[derive(Resource)]
+struct JobReadbackReceiver<J>(Receiver<(u32, Vec<u8>)>, PhantomData<J>);
+
+fn prepare_job_readbacks<J: GraphicsJob>(
+    jobs: Query<(Entity, &JobReadback), (With<J>, Without<PreparedJobReadback>)>,
+    all_jobs: Query<(), With<J>>,
+    mut ring: ResMut<JobReadbackRing<J>>,
+    render_device: Res<RenderDevice>,
+    memory_usage: Res<JobMemoryUsage>,
+    mut commands: Commands,
+) {
+    ring.release_orphans(|job| all_jobs.contains(job));
+
+    for (entity, readback) in &jobs {
+        let Some(index) = ring.acquire(entity) else {
+            break;
+        };
+
+        let slot = &mut ring.slots[index];
+        let buffer = match &slot.buffer {
+            Some((buffer, _)) if buffer.size() == readback.size => buffer.clone(),
+            _ => {
+                let buffer = render_device.create_buffer(&BufferDescriptor {
+                    label: Some(&J::resource_label("job_readback_buffer")),
+                    size: readback.size,
+                    usage: BufferUsages::COPY_DST | BufferUsages::MAP_READ,
+                    mapped_at_creation: false,
+                });
+                // replacing the slot's buffer also releases the old one's memory
+                let memory =
+                    memory_usage.allocate(JobMemoryCategory::ReadbackStaging, readback.size);
+                slot.buffer.insert((buffer, memory)).0.clone()
+            }
+        };
+
+        commands.entity(entity).insert(PreparedJobReadback {
+            slot: index,
+            buffer,
+        });
+    }
+}
+
+/// Starts mapping a job's readback buffer once it completes. Jobs are completed
+/// after their commands are submitted, so the buffer is no longer in use.
+fn map_job_readback<J: GraphicsJob>(
+    trigger: Trigger<JobComplete>,
+    jobs: Query<(&PreparedJobReadback, Option<&JobFileOutput>), With<J>>,
+    mut ring: ResMut<JobReadbackRing<J>>,
+    mut poller: ResMut<JobReadbackPoller>,
+    file_writer: Res<JobFileWriter>,
+    frame_count: Res<FrameCount>,
+) {
+    let Ok((prepared, file)) = jobs.get(trigger.entity()) else {
+        return;
+    };
+
+    let slot = &mut ring.slots[prepared.slot];
+    if let Err(error) = trigger.event().0 {
+        if let Some(file) = file {
+            file_writer.fail(file.clone(), error);
+        }
+        slot.set_state(ReadbackState::Free);
+        return;
+    }
+    slot.file = file.cloned();
+
+    let frame = frame_count.0;
+    slot.set_state(ReadbackState::Mapping(frame));
+    let state = slot.state.clone();
+    prepared
+        .buffer
+        .slice(..)
+        .map_async(MapMode::Read, move |result| {
+            *state.lock().unwrap() = match result {
+                Ok(()) => ReadbackState::Mapped(frame),
+                Err(_) => ReadbackState::Failed,
+            };
+        });
+    poller.pending = true;
+}
+
+/// Polls the device for the readbacks of every job type, according to
+/// [`JobExecutionSettings::readback_polling`].
+#[derive(Resource, Default)]
+struct JobReadbackPoller {
+    /// Wakes the polling thread, once it's spawned.
+    wake: Option<Sender<()>>,
+    /// Whether a buffer started mapping since the polling thread was last woken.
+    pending: bool,
+}
+
+/// Spawns a thread that calls `poll` each time it's woken through the returned sender.
+/// The thread exits once the sender is dropped.
+fn spawn_readback_poller(poll: impl Fn() + Send + 'static) -> Option<Sender<()>> {
+    // wakes sent while the thread is polling are coalesced into a single one
+    let (wake, woken) = crossbeam_channel::bounded(1);
+    thread::Builder::new()
+        .name("gigs readback poller".into())
+        .spawn(move || {
+            while woken.recv().is_ok() {
+                poll();
+            }
+        })
+        .inspect_err(|error| {
+            warn!(
+                "failed to spawn the readback polling thread, polling on the render thread instead: {error}"
+            );
+        })
+        .ok()
+        .map(|_| wake)
+}
+
+/// Calls the callbacks of buffers that finished mapping, either right away without
+/// waiting on the GPU, or by waking the polling thread.
+fn poll_job_readbacks(
+    settings: Res<JobExecutionSettings>,
+    mut poller: ResMut<JobReadbackPoller>,
+    render_device: Res<RenderDevice>,
+) {
+    let pending = mem::take(&mut poller.pending);
+    if cfg!(target_arch = "wasm32") || settings.readback_polling == ReadbackPolling::RenderThread {
+        poller.wake = None;
+        render_device.poll(Maintain::Poll);
+        return;
+    }
+
+    if poller.wake.is_none() {
+        let device = render_device.clone();
+        // waiting for the latest submission also waits for every buffer mapped so far
+        poller.wake = spawn_readback_poller(move || {
+            device.poll(Maintain::Wait);
+        });
+    }
+
+    match &poller.wake {
+        Some(wake) if pending => {
+            let _ = wake.try_send(());
+        }
+        Some(_) => {}
+        None => {
+            render_device.poll(Maintain::Poll);
+        }
+    }
+}
+
+fn collect_job_readbacks<J: GraphicsJob>(
+    mut ring: ResMut<JobReadbackRing<J>>,
+    sender: Res<JobReadbackSender<J>>,
+    mut file_writer: ResMut<JobFileWriter>,
+) {
+    // every result with a file is written, even those that aren't the latest
+    for slot in &mut ring.slots {
+        let state = slot.state();
+        if !matches!(state, ReadbackState::Mapped(_) | ReadbackState::Failed) {
+            continue;
+        }
+        let Some(file) = slot.file.take() else {
+            continue;
+        };
+        match (state, &slot.buffer) {
+            (ReadbackState::Mapped(_), Some((buffer, _))) => {
+                file_writer.write(file, buffer.slice(..).get_mapped_range().to_vec());
+            }
+            _ => file_writer.fail(file, JobError::ExecutionFailed),
+        }
+    }
+
+    let Some((index, frame)) = ring.take_latest() else {
+        return;
+    };
+
+    if let Some((buffer, _)) = &ring.slots[index].buffer {
+        let data = buffer.slice(..).get_mapped_range().to_vec();
+        let _ = sender.0.send((frame, data));
+    }
+    ring.free(index);
+}
+
+fn receive_job_readbacks<J: GraphicsJob>(
+    receiver: Res<JobReadbackReceiver<J>>,
+    mut commands: Commands,
+) {
+    if let Some((frame, data)) = receiver.0.try_iter().last() {
+        commands.insert_resource(JobReadbackResult::<J> {
+            frame,
+            data,
+            marker: PhantomData,
+        });
+    }
+}
+
+/// Drops the readback buffers after the device is lost. Results still being
+/// mapped are discarded.
+fn reset_job_readback_ring<J: GraphicsJob>(world: &mut World) {
+    world.insert_resource(JobReadbackRing::<J>::default());
+}
+
+/// Stops the polling thread, which holds on to the lost device. It's spawned again
+/// for the new device the next time a buffer is mapped.
+fn reset_job_readback_poller(world: &mut World) {
+    world.insert_resource(JobReadbackPoller::default());
+}
+
+#[cfg(test)]
+mod test {
+    use core::time::Duration;
+    use std::thread;
+
+    use bevy_ecs::entity::Entity;
+
+    use super::{spawn_readback_poller, JobReadbackRing, ReadbackState};
+
+    fn ring_with(states: [ReadbackState; 2]) -> JobReadbackRing<()> {
+        let ring = JobReadbackRing::default();
+        for (slot, state) in ring.slots.iter().zip(states) {
+            slot.set_state(state);
+        }
+        ring
+    }
+
+    #[test]
+    fn at_most_two_readbacks_in_flight() {
+        let ring = ring_with([ReadbackState::Free; 2]);
+        assert_eq!(ring.acquire(Entity::from_raw(0)), Some(0));
+        assert_eq!(ring.acquire(Entity::from_raw(1)), Some(1));
+        assert_eq!(ring.acquire(Entity::from_raw(2)), None);
+
+        ring.release_orphans(|job| job != Entity::from_raw(0));
+        assert_eq!(ring.acquire(Entity::from_raw(2)), Some(0));
+    }
+
+    #[test]
+    fn latest_result_is_kept() {
+        let ring = ring_with([ReadbackState::Mapped(4), ReadbackState::Mapped(3)]);
+        assert_eq!(ring.take_latest(), Some((0, 4)));
+        assert_eq!(ring.slots[1].state(), ReadbackState::Free);
+
+        let ring = ring_with([ReadbackState::Mapped(4), ReadbackState::Mapped(5)]);
+        assert_eq!(ring.take_latest(), Some((1, 5)));
+        assert_eq!(ring.slots[0].state(), ReadbackState::Free);
+    }
+
+    #[test]
+    fn pending_results_are_not_taken() {
+        let ring = ring_with([ReadbackState::Mapping(6), ReadbackState::Failed]);
+        assert_eq!(ring.take_latest(), None);
+        assert_eq!(ring.slots[0].state(), ReadbackState::Mapping(6));
+        assert_eq!(ring.slots[1].state(), ReadbackState::Free);
+    }
+
+    #[test]
+    fn readbacks_complete_on_the_polling_thread() {
+        let ring = ring_with([ReadbackState::Mapping(7), ReadbackState::Free]);
+        let state = ring.slots[0].state.clone();
+        let (polled, polled_on) = crossbeam_channel::unbounded();
+        let wake = spawn_readback_poller(move || {
+            // stands in for the map callback, which `wgpu` calls while polling
+            *state.lock().unwrap() = ReadbackState::Mapped(7);
+            let _ = polled.send(thread::current().id());
+        })
+        .unwrap();
+
+        wake.send(()).unwrap();
+        let polled_on = polled_on.recv_timeout(Duration::from_secs(5)).unwrap();
+        assert_ne!(polled_on, thread::current().id());
+
+        // the render thread picks up the result without polling
+        assert_eq!(ring.take_latest(), Some((0, 7)));
+    }
+}
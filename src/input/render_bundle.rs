@@ -0,0 +1,227 @@
+use std::sync::{Arc, Mutex};
+
+use bevy_app::{App, Plugin};
+use bevy_ecs::{
+    component::Component,
+    query::QueryItem,
+    system::{lifetimeless::Read, Resource},
+    world::World,
+};
+use bevy_render::{
+    extract_component::{ExtractComponent, ExtractComponentPlugin},
+    extract_resource::{ExtractResource, ExtractResourcePlugin},
+    render_resource::TextureFormat,
+    renderer::RenderDevice,
+    RenderApp,
+};
+use bevy_utils::HashMap;
+use disqualified::ShortName;
+use wgpu::{
+    RenderBundle, RenderBundleDepthStencil, RenderBundleDescriptor, RenderBundleEncoder,
+    RenderBundleEncoderDescriptor,
+};
+
+use crate::{device::add_device_reset, label::job_resource_label, GraphicsJob};
+
+use super::{JobInput, JobInputStatus};
+
+/// A [`JobInput`] for jobs that issue the same draws every time they run, for example
+/// a static backdrop drawn each frame. The job records its draws into a [`RenderBundle`]
+/// once, and later jobs with the same key replay it instead of recording them again,
+/// saving CPU time.
+///
+/// Bundles are cached by key in the render world until the key is invalidated with
+/// [`JobRenderBundles::invalidate`], or the bundle's formats change. A bundle holds on
+/// to the pipelines, bind groups and buffers it was recorded with, so invalidate it
+/// when any of them change.
+///
+/// Render bundles are more limited than command encoders: they can only record draws
+/// and the state for them (pipelines, bind groups, vertex and index buffers), not
+/// compute passes, copies, or render pass state like viewports, scissors, blend
+/// constants and stencil references. Those are set on the render pass executing
+/// the bundle instead. Bundles are supported by every backend, but are emulated on
+/// some, like WebGL, where they don't save any CPU time.
+#[derive(Clone, Component, PartialEq, Debug)]
+pub struct JobRenderBundle {
+    pub key: &'static str,
+    pub color_formats: Vec<Option<TextureFormat>>,
+    pub depth_stencil: Option<RenderBundleDepthStencil>,
+    pub sample_count: u32,
+}
+
+impl JobRenderBundle {
+    /// Describes a bundle drawing into a single, single-sampled color target.
+    pub fn new(key: &'static str, format: TextureFormat) -> Self {
+        Self {
+            key,
+            color_formats: vec![Some(format)],
+            depth_stencil: None,
+            sample_count: 1,
+        }
+    }
+
+    pub fn with_depth_stencil(mut self, depth_stencil: RenderBundleDepthStencil) -> Self {
+        self.depth_stencil = Some(depth_stencil);
+        self
+    }
+
+    pub fn with_sample_count(mut self, sample_count: u32) -> Self {
+        self.sample_count = sample_count;
+        self
+    }
+}
+
+/// The render bundle cache provided by [`JobRenderBundle`].
+pub struct JobRenderBundleItem<'a> {
+    job: ShortName<'static>,
+    bundle: &'a JobRenderBundle,
+    render_device: &'a RenderDevice,
+    cache: &'a JobRenderBundleCache,
+    generation: u32,
+}
+
+impl<'a> JobRenderBundleItem<'a> {
+    /// Returns the cached bundle for the job's key, or records and caches a new one with
+    /// `record` if there is none, or if it was invalidated. Execute the bundle with
+    /// `RenderPass::execute_bundles` in a render pass matching the bundle's formats.
+    pub fn get_or_record(
+        &self,
+        record: impl FnOnce(&mut RenderBundleEncoder<'a>),
+    ) -> Arc<RenderBundle> {
+        let mut cache = self.cache.0.lock().unwrap();
+        if let Some(cached) = cache.get(self.bundle.key) {
+            if cached.generation == self.generation && cached.descriptor == *self.bundle {
+                return cached.bundle.clone();
+            }
+        }
+
+        let label = job_resource_label(Some(self.job), self.bundle.key);
+        let mut encoder = self
+            .render_device
+            .wgpu_device()
+            .create_render_bundle_encoder(&RenderBundleEncoderDescriptor {
+                label: Some(&label),
+                color_formats: &self.bundle.color_formats,
+                depth_stencil: self.bundle.depth_stencil,
+                sample_count: self.bundle.sample_count,
+                multiview: None,
+            });
+        record(&mut encoder);
+        let bundle = Arc::new(encoder.finish(&RenderBundleDescriptor {
+            label: Some(&label),
+        }));
+
+        cache.insert(
+            self.bundle.key,
+            CachedRenderBundle {
+                generation: self.generation,
+                descriptor: self.bundle.clone(),
+                bundle: bundle.clone(),
+            },
+        );
+        bundle
+    }
+}
+
+impl<J: GraphicsJob> JobInput<J> for JobRenderBundle {
+    type Data = Read<JobRenderBundle>;
+
+    type Item<'a> = JobRenderBundleItem<'a>;
+
+    fn plugin() -> impl Plugin {
+        |app: &mut App| {
+            if !app.is_plugin_added::<ExtractComponentPlugin<JobRenderBundle>>() {
+                app.init_resource::<JobRenderBundles>().add_plugins((
+                    ExtractComponentPlugin::<JobRenderBundle>::default(),
+                    ExtractResourcePlugin::<JobRenderBundles>::default(),
+                ));
+
+                if let Some(render_app) = app.get_sub_app_mut(RenderApp) {
+                    render_app.init_resource::<JobRenderBundleCache>();
+                }
+
+                add_device_reset(app, |world| {
+                    world.insert_resource(JobRenderBundleCache::default());
+                });
+            }
+        }
+    }
+
+    fn status(_data: QueryItem<Self::Data>, _world: &World) -> JobInputStatus {
+        JobInputStatus::Ready
+    }
+
+    fn get<'a>(bundle: QueryItem<'a, Self::Data>, world: &'a World) -> Self::Item<'a> {
+        JobRenderBundleItem {
+            job: J::label(),
+            bundle,
+            render_device: world.resource::<RenderDevice>(),
+            cache: world.resource::<JobRenderBundleCache>(),
+            generation: world
+                .get_resource::<JobRenderBundles>()
+                .map_or(0, |bundles| bundles.generation(bundle.key)),
+        }
+    }
+}
+
+impl ExtractComponent for JobRenderBundle {
+    type QueryData = Read<JobRenderBundle>;
+
+    type QueryFilter = ();
+
+    type Out = JobRenderBundle;
+
+    fn extract_component(item: QueryItem<'_, Self::QueryData>) -> Option<Self::Out> {
+        Some(item.clone())
+    }
+}
+
+/// Tracks invalidated [`JobRenderBundle`]s in the main world.
+#[derive(Resource, Clone, Default, Debug)]
+pub struct JobRenderBundles(HashMap<&'static str, u32>);
+
+impl JobRenderBundles {
+    /// Forces the bundle with the given key to be recorded again the next time
+    /// a job uses it.
+    pub fn invalidate(&mut self, key: &'static str) {
+        *self.0.entry(key).or_default() += 1;
+    }
+
+    fn generation(&self, key: &'static str) -> u32 {
+        self.0.get(key).copied().unwrap_or_default()
+    }
+}
+
+impl ExtractResource for JobRenderBundles {
+    type Source = JobRenderBundles;
+
+    fn extract_resource(source: &Self::Source) -> Self {
+        source.clone()
+    }
+}
+
+struct CachedRenderBundle {
+    generation: u32,
+    descriptor: JobRenderBundle,
+    bundle: Arc<RenderBundle>,
+}
+
+/// The bundles recorded by jobs, written to while jobs execute.
+#[derive(Resource, Default)]
+struct JobRenderBundleCache(Mutex<HashMap<&'static str, CachedRenderBundle>>);
+
+#[cfg(test)]
+mod test {
+    use super::JobRenderBundles;
+
+    #[test]
+    fn invalidating_bumps_generation() {
+        let mut bundles = JobRenderBundles::default();
+        assert_eq!(bundles.generation("backdrop"), 0);
+
+        bundles.invalidate("backdrop");
+        bundles.invalidate("backdrop");
+        assert_eq!(bundles.generation("backdrop"), 2);
+        assert_eq!(bundles.generation("other"), 0);
+    }
+}
@@ -0,0 +1,204 @@
+use core::marker::PhantomData;
+
+use bevy_app::{App, Plugin};
+use bevy_ecs::{
+    component::Component,
+    entity::Entity,
+    query::QueryItem,
+    system::{lifetimeless::Read, Resource},
+    world::{EntityRef, World},
+};
+use bevy_render::{
+    render_phase::{
+        BinnedPhaseItem, BinnedRenderPhase, SortedPhaseItem, SortedRenderPhase,
+        ViewBinnedRenderPhases, ViewSortedRenderPhases,
+    },
+    view::ExtractedView,
+    ExtractSchedule, RenderApp,
+};
+
+use crate::GraphicsJob;
+
+use super::{
+    camera_view::{extract_camera_views, CameraViewInput},
+    JobInput, JobInputStatus,
+};
+
+/// A render world [`Resource`] holding a render phase for each view, like
+/// [`ViewSortedRenderPhases`] or [`ViewBinnedRenderPhases`], for use with
+/// [`JobRenderPhase`].
+pub trait ViewRenderPhases: Resource {
+    type Phase: Send + Sync + 'static;
+
+    /// Returns the phase of the given render world view, if it has one.
+    fn view_phase(&self, view: Entity) -> Option<&Self::Phase>;
+}
+
+impl<I: SortedPhaseItem> ViewRenderPhases for ViewSortedRenderPhases<I> {
+    type Phase = SortedRenderPhase<I>;
+
+    fn view_phase(&self, view: Entity) -> Option<&Self::Phase> {
+        self.get(&view)
+    }
+}
+
+impl<I: BinnedPhaseItem> ViewRenderPhases for ViewBinnedRenderPhases<I> {
+    type Phase = BinnedRenderPhase<I>;
+
+    fn view_phase(&self, view: Entity) -> Option<&Self::Phase> {
+        self.get(&view)
+    }
+}
+
+/// Selects the camera whose render phases are provided to a job by [`JobRenderPhase`].
+#[derive(Copy, Clone, Component, PartialEq, Eq, Debug)]
+pub struct JobPhaseView(pub Entity);
+
+impl JobPhaseView {
+    /// Targets the given main world camera.
+    pub const fn new(camera: Entity) -> Self {
+        Self(camera)
+    }
+}
+
+/// The render world view entity targeted by a job's [`JobPhaseView`].
+#[derive(Copy, Clone, Component)]
+#[doc(hidden)]
+pub struct ExtractedJobPhaseView(pub Entity);
+
+/// A [`JobInput`] providing the render phase `P` of the view selected by the job's
+/// [`JobPhaseView`], for example to analyze what's drawn in a camera's transparent pass
+/// with `JobRenderPhase<ViewSortedRenderPhases<Transparent3d>>`.
+///
+/// Jobs check their inputs after phases are queued and sorted, so the phase holds the
+/// items drawn this frame. The job waits until the view is extracted, and fails if the
+/// view doesn't have the phase, for example a 2D camera asked for a 3D phase.
+pub struct JobRenderPhase<P>(PhantomData<P>);
+
+impl<J: GraphicsJob, P: ViewRenderPhases> JobInput<J> for JobRenderPhase<P> {
+    type Data = Option<Read<ExtractedJobPhaseView>>;
+
+    type Item<'a> = JobRenderPhaseItem<'a, P::Phase>;
+
+    fn plugin() -> impl Plugin {
+        |app: &mut App| {
+            if !app.is_plugin_added::<JobPhaseViewPlugin>() {
+                app.add_plugins(JobPhaseViewPlugin);
+            }
+        }
+    }
+
+    fn status(data: QueryItem<Self::Data>, world: &World) -> JobInputStatus {
+        let Some(view) = data.and_then(|view| world.get_entity(view.0).ok()) else {
+            return JobInputStatus::Wait;
+        };
+        if !view.contains::<ExtractedView>() {
+            return JobInputStatus::Wait;
+        }
+
+        match world
+            .get_resource::<P>()
+            .and_then(|phases| phases.view_phase(view.id()))
+        {
+            Some(_) => JobInputStatus::Ready,
+            None => JobInputStatus::Fail,
+        }
+    }
+
+    fn get<'a>(data: QueryItem<'a, Self::Data>, world: &'a World) -> Self::Item<'a> {
+        let view = world
+            .get_entity(data.expect("render phase should be ready by this point").0)
+            .expect("render phase should be ready by this point");
+        JobRenderPhaseItem {
+            view,
+            phase: world
+                .resource::<P>()
+                .view_phase(view.id())
+                .expect("render phase should be ready by this point"),
+        }
+    }
+}
+
+/// The render phase provided by [`JobRenderPhase`].
+pub struct JobRenderPhaseItem<'a, Phase> {
+    /// The render world view entity, for looking up other view components.
+    pub view: EntityRef<'a>,
+    pub phase: &'a Phase,
+}
+
+pub(super) struct JobPhaseViewPlugin;
+
+impl Plugin for JobPhaseViewPlugin {
+    fn build(&self, app: &mut App) {
+        if let Some(render_app) = app.get_sub_app_mut(RenderApp) {
+            render_app.add_systems(ExtractSchedule, extract_camera_views::<JobPhaseView>);
+        }
+    }
+}
+
+impl CameraViewInput for JobPhaseView {
+    type Extracted = ExtractedJobPhaseView;
+
+    type Data = ();
+
+    fn camera(&self) -> Entity {
+        self.0
+    }
+
+    fn extract(&self, view: Entity, (): ()) -> Self::Extracted {
+        ExtractedJobPhaseView(view)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use bevy_core_pipeline::core_3d::Transparent3d;
+    use bevy_ecs::{component::Component, world::World};
+    use bevy_render::render_phase::ViewSortedRenderPhases;
+
+    use crate::{
+        input::{camera_view::test_view, JobInput, JobInputItem, JobInputStatus},
+        GraphicsJob, JobError, JobRunContext,
+    };
+
+    use super::{ExtractedJobPhaseView, JobRenderPhase};
+
+    type TransparentPhase = JobRenderPhase<ViewSortedRenderPhases<Transparent3d>>;
+
+    #[derive(Clone, Component)]
+    struct PhaseJob;
+
+    impl GraphicsJob for PhaseJob {
+        type In = TransparentPhase;
+
+        fn run(
+            &self,
+            _world: &World,
+            _context: &mut JobRunContext,
+            _input: JobInputItem<Self, Self::In>,
+        ) -> Result<(), JobError> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn fails_for_views_without_the_phase() {
+        let status = <TransparentPhase as JobInput<PhaseJob>>::status;
+        let mut world = World::new();
+        world.init_resource::<ViewSortedRenderPhases<Transparent3d>>();
+        assert_eq!(status(None, &world), JobInputStatus::Wait);
+
+        // the view hasn't been extracted yet
+        let view = world.spawn_empty().id();
+        let phase_view = ExtractedJobPhaseView(view);
+        assert_eq!(status(Some(&phase_view), &world), JobInputStatus::Wait);
+
+        world.entity_mut(view).insert(test_view(false));
+        assert_eq!(status(Some(&phase_view), &world), JobInputStatus::Fail);
+
+        world
+            .resource_mut::<ViewSortedRenderPhases<Transparent3d>>()
+            .insert_or_clear(view);
+        assert_eq!(status(Some(&phase_view), &world), JobInputStatus::Ready);
+    }
+}
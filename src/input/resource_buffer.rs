@@ -0,0 +1,96 @@
+use core::marker::PhantomData;
+
+use bevy_ecs::{query::QueryItem, system::Resource, world::World};
+use bevy_render::render_resource::Buffer;
+
+use crate::GraphicsJob;
+
+use super::{JobInput, JobInputStatus};
+
+/// A render world [`Resource`] that wraps a GPU buffer, like a
+/// [`StorageBuffer`](bevy_render::render_resource::StorageBuffer) or
+/// [`UniformBuffer`](bevy_render::render_resource::UniformBuffer), for use with
+/// [`JobResourceBuffer`].
+pub trait JobBufferResource: Resource {
+    /// Returns the wrapped buffer, or `None` if it hasn't been written yet.
+    fn buffer(&self) -> Option<&Buffer>;
+}
+
+/// A [`JobInput`] that reads the GPU buffer of a render world resource, for example
+/// a global simulation state kept in a
+/// [`StorageBuffer`](bevy_render::render_resource::StorageBuffer) and shared between jobs.
+///
+/// The job waits until the resource exists and its buffer has been created, which
+/// happens the first time `write_buffer` is called on it. Jobs check their inputs
+/// after [`RenderSet::Prepare`](bevy_render::RenderSet::Prepare), so write the buffer
+/// in [`RenderSet::PrepareResources`](bevy_render::RenderSet::PrepareResources) or
+/// earlier for jobs to see the values written that frame.
+pub struct JobResourceBuffer<R: JobBufferResource>(PhantomData<R>);
+
+impl<J: GraphicsJob, R: JobBufferResource> JobInput<J> for JobResourceBuffer<R> {
+    type Data = ();
+
+    type Item<'a> = &'a Buffer;
+
+    fn status((): QueryItem<Self::Data>, world: &World) -> JobInputStatus {
+        match world.get_resource::<R>().and_then(R::buffer) {
+            Some(_) => JobInputStatus::Ready,
+            None => JobInputStatus::Wait,
+        }
+    }
+
+    fn get<'a>((): QueryItem<'a, Self::Data>, world: &'a World) -> Self::Item<'a> {
+        world
+            .resource::<R>()
+            .buffer()
+            .expect("resource buffer should be ready by this point")
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use bevy_ecs::{component::Component, system::Resource, world::World};
+    use bevy_render::render_resource::Buffer;
+
+    use crate::{
+        input::{JobInput, JobInputItem, JobInputStatus},
+        GraphicsJob, JobError, JobRunContext,
+    };
+
+    use super::{JobBufferResource, JobResourceBuffer};
+
+    #[derive(Resource, Default)]
+    struct UnwrittenBuffer;
+
+    impl JobBufferResource for UnwrittenBuffer {
+        fn buffer(&self) -> Option<&Buffer> {
+            None
+        }
+    }
+
+    #[derive(Clone, Component)]
+    struct BufferJob;
+
+    impl GraphicsJob for BufferJob {
+        type In = JobResourceBuffer<UnwrittenBuffer>;
+
+        fn run(
+            &self,
+            _world: &World,
+            _context: &mut JobRunContext,
+            _input: JobInputItem<Self, Self::In>,
+        ) -> Result<(), JobError> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn waits_for_buffer() {
+        let mut world = World::new();
+        let status = <JobResourceBuffer<UnwrittenBuffer> as JobInput<BufferJob>>::status;
+        assert_eq!(status((), &world), JobInputStatus::Wait);
+
+        world.init_resource::<UnwrittenBuffer>();
+        assert_eq!(status((), &world), JobInputStatus::Wait);
+    }
+}
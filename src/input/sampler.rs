@@ -0,0 +1,139 @@
+use std::sync::OnceLock;
+
+use bevy_app::{App, Plugin};
+use bevy_ecs::{query::QueryItem, system::Resource, world::World};
+use bevy_render::{
+    render_resource::{AddressMode, FilterMode, Sampler, SamplerDescriptor},
+    renderer::RenderDevice,
+    RenderApp,
+};
+
+use crate::{device::add_device_reset, GraphicsJob};
+
+use super::{JobInput, JobInputStatus};
+
+/// The samplers shared by [`JobLinearSampler`] and [`JobNearestSampler`], each created
+/// once, the first time it's read.
+///
+/// Both clamp to the edge of the texture. Read them from the render world to create
+/// bind groups outside of jobs.
+#[derive(Resource, Default)]
+pub struct JobSamplers {
+    linear: OnceLock<Sampler>,
+    nearest: OnceLock<Sampler>,
+}
+
+impl JobSamplers {
+    /// The shared sampler with linear filtering, including between mip levels.
+    pub fn linear(&self, render_device: &RenderDevice) -> &Sampler {
+        self.linear.get_or_init(|| {
+            render_device.create_sampler(&SamplerDescriptor {
+                label: Some("job_linear_sampler"),
+                ..sampler_descriptor(FilterMode::Linear)
+            })
+        })
+    }
+
+    /// The shared sampler with nearest filtering, including between mip levels.
+    pub fn nearest(&self, render_device: &RenderDevice) -> &Sampler {
+        self.nearest.get_or_init(|| {
+            render_device.create_sampler(&SamplerDescriptor {
+                label: Some("job_nearest_sampler"),
+                ..sampler_descriptor(FilterMode::Nearest)
+            })
+        })
+    }
+}
+
+fn sampler_descriptor(filter: FilterMode) -> SamplerDescriptor<'static> {
+    SamplerDescriptor {
+        address_mode_u: AddressMode::ClampToEdge,
+        address_mode_v: AddressMode::ClampToEdge,
+        address_mode_w: AddressMode::ClampToEdge,
+        mag_filter: filter,
+        min_filter: filter,
+        mipmap_filter: filter,
+        ..Default::default()
+    }
+}
+
+/// A [`JobInput`] providing a shared sampler with linear filtering, so jobs don't
+/// each create their own. See [`JobSamplers`].
+pub struct JobLinearSampler;
+
+impl<J: GraphicsJob> JobInput<J> for JobLinearSampler {
+    type Data = ();
+
+    type Item<'a> = &'a Sampler;
+
+    fn plugin() -> impl Plugin {
+        |app: &mut App| init_job_samplers(app)
+    }
+
+    fn status((): QueryItem<Self::Data>, _world: &World) -> JobInputStatus {
+        JobInputStatus::Ready
+    }
+
+    fn get<'a>((): QueryItem<'a, Self::Data>, world: &'a World) -> Self::Item<'a> {
+        world
+            .resource::<JobSamplers>()
+            .linear(world.resource::<RenderDevice>())
+    }
+}
+
+/// A [`JobInput`] providing a shared sampler with nearest filtering, so jobs don't
+/// each create their own. See [`JobSamplers`].
+pub struct JobNearestSampler;
+
+impl<J: GraphicsJob> JobInput<J> for JobNearestSampler {
+    type Data = ();
+
+    type Item<'a> = &'a Sampler;
+
+    fn plugin() -> impl Plugin {
+        |app: &mut App| init_job_samplers(app)
+    }
+
+    fn status((): QueryItem<Self::Data>, _world: &World) -> JobInputStatus {
+        JobInputStatus::Ready
+    }
+
+    fn get<'a>((): QueryItem<'a, Self::Data>, world: &'a World) -> Self::Item<'a> {
+        world
+            .resource::<JobSamplers>()
+            .nearest(world.resource::<RenderDevice>())
+    }
+}
+
+fn init_job_samplers(app: &mut App) {
+    let Some(render_app) = app.get_sub_app_mut(RenderApp) else {
+        return;
+    };
+    if render_app.world().contains_resource::<JobSamplers>() {
+        return;
+    }
+
+    render_app.init_resource::<JobSamplers>();
+    add_device_reset(app, |world| {
+        world.insert_resource(JobSamplers::default());
+    });
+}
+
+#[cfg(test)]
+mod test {
+    use bevy_render::render_resource::FilterMode;
+
+    use super::sampler_descriptor;
+
+    #[test]
+    fn descriptors_filter_every_level() {
+        for filter in [FilterMode::Linear, FilterMode::Nearest] {
+            let descriptor = sampler_descriptor(filter);
+            assert_eq!(descriptor.mag_filter, filter);
+            assert_eq!(descriptor.min_filter, filter);
+            assert_eq!(descriptor.mipmap_filter, filter);
+            // comparison samplers can't be used with filtering texture bindings
+            assert_eq!(descriptor.compare, None);
+        }
+    }
+}
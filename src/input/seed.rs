@@ -0,0 +1,224 @@
+use core::hash::{BuildHasher, Hash, Hasher};
+use std::hash::RandomState;
+
+use bevy_app::{App, Plugin};
+use bevy_ecs::{
+    component::Component,
+    observer::Trigger,
+    query::QueryItem,
+    system::{lifetimeless::Read, Commands, Local},
+    world::{OnAdd, World},
+};
+use bevy_render::extract_component::{ExtractComponent, ExtractComponentPlugin};
+
+use crate::GraphicsJob;
+
+use super::{JobInput, JobInputStatus};
+
+/// A [`JobInput`] providing a per-job seed for jobs that use randomness, for
+/// example to seed GPU noise from the CPU.
+///
+/// By default, each job receives a seed derived from its [label](GraphicsJob::label)
+/// and the order it was spawned in among jobs of its type, so output is reproducible
+/// (for example, for golden-image testing of procedural bakes) as long as jobs are
+/// spawned in the same order. To keep seeds stable when the spawn order changes, add
+/// a seed derived from a stable key with [`JobSeed::from_key`], or set one explicitly
+/// with [`JobSeed::new`], when spawning the job. Avoid keying on
+/// [`Entity`](bevy_ecs::entity::Entity) ids, since those vary between runs. Jobs that
+/// should differ between runs opt in with [`JobSeed::random`].
+///
+/// Note: seeds derived from a key are stable across runs and compiler versions,
+/// but not necessarily across platforms, since [`Hash`] implementations may
+/// depend on pointer width or endianness.
+#[derive(Copy, Clone, Component, PartialEq, Eq, Hash, Debug)]
+pub struct JobSeed(pub u64);
+
+impl JobSeed {
+    /// Creates a seed with an explicit value.
+    #[inline]
+    pub const fn new(seed: u64) -> Self {
+        Self(seed)
+    }
+
+    /// Derives a seed deterministically from a stable key, such as an asset
+    /// path, a chunk coordinate, or the job's spawn index.
+    pub fn from_key(key: impl Hash) -> Self {
+        let mut hasher = StableHasher::default();
+        key.hash(&mut hasher);
+        Self(hasher.finish())
+    }
+
+    /// Creates a random seed, which will differ between runs.
+    pub fn random() -> Self {
+        Self(RandomState::new().build_hasher().finish())
+    }
+
+    /// Derives a new seed from this one and a key, for jobs that need several
+    /// independent random streams.
+    pub fn derive(&self, key: impl Hash) -> Self {
+        Self::from_key((self.0, key))
+    }
+
+    /// Returns the raw seed value.
+    #[inline]
+    pub const fn get(&self) -> u64 {
+        self.0
+    }
+}
+
+impl ExtractComponent for JobSeed {
+    type QueryData = Read<JobSeed>;
+
+    type QueryFilter = ();
+
+    type Out = JobSeed;
+
+    fn extract_component(item: QueryItem<'_, Self::QueryData>) -> Option<Self::Out> {
+        Some(*item)
+    }
+}
+
+impl<J: GraphicsJob> JobInput<J> for JobSeed {
+    type Data = Read<JobSeed>;
+
+    type Item<'a> = u64;
+
+    fn plugin() -> impl Plugin {
+        |app: &mut App| {
+            app.add_observer(seed_jobs::<J>);
+            if !app.is_plugin_added::<ExtractComponentPlugin<JobSeed>>() {
+                app.add_plugins(ExtractComponentPlugin::<JobSeed>::default());
+            }
+        }
+    }
+
+    fn status(_data: QueryItem<Self::Data>, _world: &World) -> JobInputStatus {
+        JobInputStatus::Ready
+    }
+
+    fn get<'a>(data: QueryItem<'a, Self::Data>, _world: &'a World) -> Self::Item<'a> {
+        data.0
+    }
+}
+
+/// Seeds each job of type `J` spawned without a [`JobSeed`] from the order it was
+/// spawned in among them.
+fn seed_jobs<J: GraphicsJob>(
+    trigger: Trigger<OnAdd, J>,
+    mut next: Local<u64>,
+    mut commands: Commands,
+) {
+    commands
+        .entity(trigger.entity())
+        .insert_if_new(JobSeed::from_key((J::label().original(), *next)));
+    *next += 1;
+}
+
+/// A 64-bit FNV-1a hasher with a final avalanche step. Unlike
+/// [`DefaultHasher`](std::hash::DefaultHasher), its output is
+/// guaranteed not to change between Rust versions.
+struct StableHasher(u64);
+
+impl Default for StableHasher {
+    fn default() -> Self {
+        Self(0xcbf2_9ce4_8422_2325)
+    }
+}
+
+impl Hasher for StableHasher {
+    fn write(&mut self, bytes: &[u8]) {
+        for byte in bytes {
+            self.0 ^= *byte as u64;
+            self.0 = self.0.wrapping_mul(0x0000_0100_0000_01b3);
+        }
+    }
+
+    fn finish(&self) -> u64 {
+        // splitmix64 finalizer, so that similar keys give very different seeds
+        let mut z = self.0.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use bevy_app::App;
+    use bevy_ecs::{component::Component, world::World};
+
+    use crate::{input::JobInputItem, GraphicsJob, InitGraphicsJobExt, JobError, JobRunContext};
+
+    use super::JobSeed;
+
+    #[derive(Clone, Component)]
+    struct NoiseJob;
+
+    impl GraphicsJob for NoiseJob {
+        type In = JobSeed;
+
+        fn run(
+            &self,
+            _world: &World,
+            _context: &mut JobRunContext,
+            _seed: JobInputItem<Self, Self::In>,
+        ) -> Result<(), JobError> {
+            Ok(())
+        }
+    }
+
+    /// Spawns three jobs in a new app, the second with an explicit seed, and returns
+    /// their seeds.
+    fn spawn_seeded_jobs() -> Vec<JobSeed> {
+        let mut app = App::new();
+        app.init_graphics_job::<NoiseJob>();
+        let jobs = [
+            app.world_mut().spawn(NoiseJob).id(),
+            app.world_mut().spawn((NoiseJob, JobSeed::new(7))).id(),
+            app.world_mut().spawn(NoiseJob).id(),
+        ];
+        app.world_mut().flush();
+        jobs.map(|job| *app.world().get::<JobSeed>(job).unwrap())
+            .to_vec()
+    }
+
+    #[test]
+    fn default_seeds_are_reproducible() {
+        let seeds = spawn_seeded_jobs();
+        assert_eq!(seeds, spawn_seeded_jobs());
+        assert_eq!(seeds[1], JobSeed::new(7));
+        assert_ne!(seeds[0], seeds[2]);
+    }
+
+    #[test]
+    fn seed_from_key_is_stable() {
+        assert_eq!(JobSeed::from_key("chunk"), JobSeed::from_key("chunk"));
+        assert_eq!(
+            JobSeed::from_key((3u32, 7u32)),
+            JobSeed::from_key((3u32, 7u32))
+        );
+        assert_ne!(
+            JobSeed::from_key((3u32, 7u32)),
+            JobSeed::from_key((7u32, 3u32))
+        );
+    }
+
+    #[test]
+    fn seed_from_key_known_value() {
+        // guards against accidental changes to the hashing scheme, which
+        // would silently break reproducible bakes
+        assert_eq!(JobSeed::from_key(0u64), JobSeed::new(0x5ba3_14b8_cfda_3b6b));
+    }
+
+    #[test]
+    fn derived_seeds_differ() {
+        let base = JobSeed::new(42);
+        assert_ne!(base.derive(0u32), base.derive(1u32));
+        assert_eq!(base.derive(0u32), JobSeed::new(42).derive(0u32));
+    }
+
+    #[test]
+    fn random_seeds_differ() {
+        assert_ne!(JobSeed::random(), JobSeed::random());
+    }
+}
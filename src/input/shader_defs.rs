@@ -0,0 +1,208 @@
+use core::marker::PhantomData;
+
+use bevy_app::{App, Plugin};
+use bevy_ecs::{
+    schedule::IntoSystemConfigs,
+    system::{Query, Res, Resource},
+};
+use bevy_render::{
+    extract_resource::{ExtractResource, ExtractResourcePlugin},
+    render_resource::ShaderDefVal,
+    Render, RenderApp, RenderSet,
+};
+
+use crate::JobSet;
+
+use super::{JobRenderPipeline, SpecializedJobRenderPipeline};
+
+/// The shader defs jobs' pipelines are currently specialized with, like a quality
+/// setting toggled at runtime. With [`JobShaderDefsPlugin`], changing them updates
+/// the [`JobRenderPipeline`] key of every job using the plugin's pipeline, so the
+/// pipeline is re-specialized, and jobs wait for the recompiled pipeline before running.
+#[derive(Resource, Clone, Default, PartialEq, Eq, Debug, ExtractResource)]
+pub struct JobShaderDefs(pub Vec<ShaderDefVal>);
+
+impl JobShaderDefs {
+    /// Adds a def, or replaces the def with the same name.
+    pub fn set(&mut self, def: ShaderDefVal) {
+        let name = def_name(&def);
+        match self.0.iter_mut().find(|current| def_name(current) == name) {
+            Some(current) => *current = def,
+            None => self.0.push(def),
+        }
+    }
+
+    /// Removes the def with the given name, if there is one.
+    pub fn remove(&mut self, name: &str) {
+        self.0.retain(|def| def_name(def) != name);
+    }
+
+    /// Whether a def with the given name is set.
+    pub fn contains(&self, name: &str) -> bool {
+        self.0.iter().any(|def| def_name(def) == name)
+    }
+
+    /// Sets a boolean def if `enabled`, and removes it otherwise.
+    pub fn toggle(&mut self, name: &str, enabled: bool) {
+        if enabled {
+            self.set(ShaderDefVal::Bool(name.into(), true));
+        } else {
+            self.remove(name);
+        }
+    }
+}
+
+fn def_name(def: &ShaderDefVal) -> &str {
+    match def {
+        ShaderDefVal::Bool(name, _) | ShaderDefVal::Int(name, _) | ShaderDefVal::UInt(name, _) => {
+            name
+        }
+    }
+}
+
+/// A specialization key for a pipeline that depends on [`JobShaderDefs`], which is kept
+/// in sync with them by [`JobShaderDefsPlugin`].
+pub trait ShaderDefsKey {
+    fn shader_defs(&self) -> &[ShaderDefVal];
+
+    fn set_shader_defs(&mut self, shader_defs: Vec<ShaderDefVal>);
+}
+
+impl ShaderDefsKey for Vec<ShaderDefVal> {
+    fn shader_defs(&self) -> &[ShaderDefVal] {
+        self
+    }
+
+    fn set_shader_defs(&mut self, shader_defs: Vec<ShaderDefVal>) {
+        *self = shader_defs;
+    }
+}
+
+/// Updates the [`JobRenderPipeline<P>`] key of every job to the current
+/// [`JobShaderDefs`], before the pipeline is specialized. The defs a job is spawned
+/// with are only a placeholder.
+pub struct JobShaderDefsPlugin<P>(PhantomData<P>);
+
+impl<P> Default for JobShaderDefsPlugin<P> {
+    fn default() -> Self {
+        Self(PhantomData)
+    }
+}
+
+impl<P: SpecializedJobRenderPipeline<Key: ShaderDefsKey>> Plugin for JobShaderDefsPlugin<P> {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<JobShaderDefs>();
+        if !app.is_plugin_added::<ExtractResourcePlugin<JobShaderDefs>>() {
+            app.add_plugins(ExtractResourcePlugin::<JobShaderDefs>::default());
+        }
+
+        if let Some(render_app) = app.get_sub_app_mut(RenderApp) {
+            render_app.add_systems(
+                Render,
+                apply_job_shader_defs::<P>
+                    .in_set(RenderSet::Queue)
+                    .before(JobSet::QueuePipelines),
+            );
+        }
+    }
+}
+
+fn apply_job_shader_defs<P: SpecializedJobRenderPipeline<Key: ShaderDefsKey>>(
+    mut jobs: Query<&mut JobRenderPipeline<P>>,
+    shader_defs: Option<Res<JobShaderDefs>>,
+) {
+    let Some(shader_defs) = shader_defs else {
+        return;
+    };
+
+    // extraction reinserts the key a job was spawned with every frame, so the defs are
+    // applied every frame, and only the jobs whose key differs are changed
+    for mut pipeline in &mut jobs {
+        if pipeline.0.shader_defs() != shader_defs.0 {
+            pipeline.0.set_shader_defs(shader_defs.0.clone());
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use bevy_ecs::{
+        change_detection::DetectChanges,
+        entity::Entity,
+        system::{Resource, RunSystemOnce},
+        world::{FromWorld, World},
+    };
+    use bevy_render::render_resource::{
+        RenderPipelineDescriptor, ShaderDefVal, SpecializedRenderPipeline,
+    };
+
+    use crate::input::JobRenderPipeline;
+
+    use super::{apply_job_shader_defs, JobShaderDefs};
+
+    #[derive(Resource)]
+    struct DefsPipeline;
+
+    impl FromWorld for DefsPipeline {
+        fn from_world(_world: &mut World) -> Self {
+            Self
+        }
+    }
+
+    impl SpecializedRenderPipeline for DefsPipeline {
+        type Key = Vec<ShaderDefVal>;
+
+        fn specialize(&self, _key: Self::Key) -> RenderPipelineDescriptor {
+            unreachable!()
+        }
+    }
+
+    #[test]
+    fn defs_replace_by_name() {
+        let mut defs = JobShaderDefs::default();
+        defs.set(ShaderDefVal::UInt("SAMPLES".into(), 4));
+        defs.set(ShaderDefVal::UInt("SAMPLES".into(), 16));
+        defs.toggle("HIGH_QUALITY", true);
+        assert_eq!(
+            defs.0,
+            [
+                ShaderDefVal::UInt("SAMPLES".into(), 16),
+                ShaderDefVal::Bool("HIGH_QUALITY".into(), true)
+            ]
+        );
+
+        defs.toggle("HIGH_QUALITY", false);
+        assert!(!defs.contains("HIGH_QUALITY"));
+        assert!(defs.contains("SAMPLES"));
+    }
+
+    #[test]
+    fn keys_follow_the_current_defs() {
+        let mut world = World::new();
+        let mut defs = JobShaderDefs::default();
+        defs.toggle("HIGH_QUALITY", true);
+        world.insert_resource(defs.clone());
+
+        let stale = world
+            .spawn(JobRenderPipeline::<DefsPipeline>(Vec::new()))
+            .id();
+        let current = world
+            .spawn(JobRenderPipeline::<DefsPipeline>(defs.0.clone()))
+            .id();
+        world.clear_trackers();
+
+        world
+            .run_system_once(apply_job_shader_defs::<DefsPipeline>)
+            .unwrap();
+        let key = |world: &World, job: Entity| {
+            let pipeline = world
+                .entity(job)
+                .get_ref::<JobRenderPipeline<DefsPipeline>>()
+                .unwrap();
+            (pipeline.0.clone(), pipeline.is_changed())
+        };
+        assert_eq!(key(&world, stale), (defs.0.clone(), true));
+        // keys that already match aren't marked changed, so they aren't re-specialized
+        assert_eq!(key(&world, current), (defs.0, false));
+    }
+}
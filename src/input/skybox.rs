@@ -0,0 +1,346 @@
+use std::sync::OnceLock;
+
+use bevy_app::{App, Plugin};
+use bevy_core_pipeline::Skybox;
+use bevy_ecs::{
+    component::Component,
+    entity::Entity,
+    query::QueryItem,
+    schedule::IntoSystemConfigs,
+    system::{lifetimeless::Read, Commands, Query, Res, ResMut, Resource},
+    world::{EntityRef, World},
+};
+use bevy_math::{Mat4, Vec4};
+use bevy_render::{
+    camera::ExtractedCamera,
+    render_asset::RenderAssets,
+    render_resource::{
+        binding_types::{sampler, texture_cube, uniform_buffer},
+        BindGroup, BindGroupEntries, BindGroupLayout, BindGroupLayoutEntries, DynamicUniformBuffer,
+        SamplerBindingType, ShaderStages, TextureSampleType,
+    },
+    renderer::{RenderDevice, RenderQueue},
+    texture::GpuImage,
+    view::{ViewUniform, ViewUniformOffset, ViewUniforms},
+    ExtractSchedule, Render, RenderApp, RenderSet,
+};
+
+use crate::{
+    device::{add_device_reset, remove_all},
+    GraphicsJob,
+};
+
+use super::{
+    camera_view::{extract_camera_views, view_feature_status, CameraViewInput},
+    JobInput, JobInputStatus, JobPrepareSet,
+};
+
+/// A [`JobInput`] providing the skybox of a camera, for jobs that render or sample
+/// the sky, like compositing a custom background or atmosphere behind a scene.
+///
+/// The camera must have bevy's [`Skybox`] component, which is extracted by the
+/// `SkyboxPlugin` added by bevy's `CorePipelinePlugin`. The job waits until the skybox's
+/// cubemap is loaded and its bind group is prepared, and fails if the camera doesn't
+/// have a skybox.
+///
+/// bevy keeps the bind group it draws the skybox with private, so an equivalent one is
+/// prepared for each camera with a skybox, laid out like bevy's skybox shader:
+///
+/// ```wgsl
+/// struct SkyboxUniforms {
+///     brightness: f32,
+///     transform: mat4x4<f32>,
+/// }
+///
+/// @group(0) @binding(0) var skybox: texture_cube<f32>;
+/// @group(0) @binding(1) var skybox_sampler: sampler;
+/// @group(0) @binding(2) var<uniform> view: View;
+/// @group(0) @binding(3) var<uniform> uniforms: SkyboxUniforms;
+/// ```
+///
+/// The last two are bound with the [`dynamic_offsets`](JobSkyboxItem::dynamic_offsets)
+/// of the view. Create the job's pipeline with [`JobSkyboxBindGroups::layout`].
+#[derive(Copy, Clone, Component, PartialEq, Eq, Debug)]
+pub struct JobSkybox(pub Entity);
+
+impl JobSkybox {
+    /// Uses the skybox of the given main world camera.
+    pub const fn new(camera: Entity) -> Self {
+        Self(camera)
+    }
+}
+
+/// The render world view entity whose skybox is used by a job's [`JobSkybox`].
+#[derive(Copy, Clone, Component)]
+#[doc(hidden)]
+pub struct ExtractedJobSkybox(pub Entity);
+
+impl<J: GraphicsJob> JobInput<J> for JobSkybox {
+    type Data = Option<Read<ExtractedJobSkybox>>;
+
+    type Item<'a> = JobSkyboxItem<'a>;
+
+    const PREPARE_AFTER: Option<RenderSet> = Some(RenderSet::PrepareResources);
+
+    fn plugin() -> impl Plugin {
+        |app: &mut App| {
+            if !app.is_plugin_added::<JobSkyboxPlugin>() {
+                app.add_plugins(JobSkyboxPlugin);
+            }
+        }
+    }
+
+    fn status(data: QueryItem<Self::Data>, world: &World) -> JobInputStatus {
+        view_feature_status::<Skybox>(data.map(|view| view.0), world, |view| {
+            JobSkyboxItem::new(view, world).is_some()
+        })
+    }
+
+    fn get<'a>(data: QueryItem<'a, Self::Data>, world: &'a World) -> Self::Item<'a> {
+        let view = world
+            .get_entity(data.expect("skybox should be ready by this point").0)
+            .expect("skybox should be ready by this point");
+        JobSkyboxItem::new(view, world).expect("skybox should be ready by this point")
+    }
+}
+
+/// The skybox provided by [`JobSkybox`].
+pub struct JobSkyboxItem<'a> {
+    /// The render world view entity, for looking up other view components.
+    pub view: EntityRef<'a>,
+    /// The camera's skybox settings.
+    pub skybox: &'a Skybox,
+    /// The skybox's cubemap, for jobs that build their own bind groups with it.
+    pub image: &'a GpuImage,
+    /// The bind group of the skybox, laid out as described in [`JobSkybox`].
+    pub bind_group: &'a BindGroup,
+    /// The dynamic offset of the view's [`ViewUniform`] in the bind group.
+    pub view_uniform_offset: u32,
+    /// The dynamic offset of the skybox's uniforms in the bind group.
+    pub skybox_uniform_offset: u32,
+}
+
+impl<'a> JobSkyboxItem<'a> {
+    fn new(view: EntityRef<'a>, world: &'a World) -> Option<Self> {
+        let skybox = view.get::<Skybox>()?;
+        let prepared = view.get::<JobSkyboxBindGroup>()?;
+        Some(Self {
+            skybox,
+            image: world
+                .get_resource::<RenderAssets<GpuImage>>()?
+                .get(&skybox.image)?,
+            bind_group: &prepared.bind_group,
+            view_uniform_offset: view.get::<ViewUniformOffset>()?.offset,
+            skybox_uniform_offset: prepared.uniform_offset,
+            view,
+        })
+    }
+
+    /// The dynamic offsets to set the [`bind_group`](Self::bind_group) with, in binding order.
+    pub fn dynamic_offsets(&self) -> [u32; 2] {
+        [self.view_uniform_offset, self.skybox_uniform_offset]
+    }
+}
+
+/// The uniforms of a camera's skybox, laid out like the `SkyboxUniforms` struct in
+/// [`JobSkybox`]: the brightness multiplied by the camera's exposure, padded to 16 bytes,
+/// followed by the columns of the transform that undoes the skybox's rotation.
+fn skybox_uniforms(skybox: &Skybox, exposure: f32) -> [Vec4; 5] {
+    let transform = Mat4::from_quat(skybox.rotation.inverse());
+    [
+        Vec4::new(skybox.brightness * exposure, 0.0, 0.0, 0.0),
+        transform.x_axis,
+        transform.y_axis,
+        transform.z_axis,
+        transform.w_axis,
+    ]
+}
+
+/// The layout and uniforms of the bind groups prepared for [`JobSkybox`].
+///
+/// A bind group is prepared in the [`JobPrepareSet`] of [`JobSkybox`] for every camera with a
+/// [`Skybox`] whose cubemap is loaded.
+#[derive(Resource, Default)]
+pub struct JobSkyboxBindGroups {
+    layout: OnceLock<BindGroupLayout>,
+    uniforms: DynamicUniformBuffer<[Vec4; 5]>,
+}
+
+impl JobSkyboxBindGroups {
+    /// The layout of the skybox's bind group, for creating the pipelines of jobs that bind it.
+    pub fn layout(&self, render_device: &RenderDevice) -> &BindGroupLayout {
+        self.layout.get_or_init(|| {
+            render_device.create_bind_group_layout(
+                "job_skybox_bind_group_layout",
+                &BindGroupLayoutEntries::sequential(
+                    ShaderStages::FRAGMENT,
+                    (
+                        texture_cube(TextureSampleType::Float { filterable: true }),
+                        sampler(SamplerBindingType::Filtering),
+                        uniform_buffer::<ViewUniform>(true)
+                            .visibility(ShaderStages::VERTEX_FRAGMENT),
+                        uniform_buffer::<[Vec4; 5]>(true),
+                    ),
+                ),
+            )
+        })
+    }
+}
+
+/// The bind group prepared for a view's skybox.
+#[derive(Component)]
+struct JobSkyboxBindGroup {
+    bind_group: BindGroup,
+    uniform_offset: u32,
+}
+
+struct JobSkyboxPlugin;
+
+impl Plugin for JobSkyboxPlugin {
+    fn build(&self, app: &mut App) {
+        if let Some(render_app) = app.get_sub_app_mut(RenderApp) {
+            render_app
+                .init_resource::<JobSkyboxBindGroups>()
+                .add_systems(ExtractSchedule, extract_camera_views::<JobSkybox>)
+                .add_systems(
+                    Render,
+                    prepare_job_skybox_bind_groups.in_set(JobPrepareSet::of::<JobSkybox>()),
+                );
+        }
+
+        add_device_reset(app, reset_job_skybox_bind_groups);
+    }
+}
+
+impl CameraViewInput for JobSkybox {
+    type Extracted = ExtractedJobSkybox;
+
+    type Data = ();
+
+    fn camera(&self) -> Entity {
+        self.0
+    }
+
+    fn extract(&self, view: Entity, (): ()) -> Self::Extracted {
+        ExtractedJobSkybox(view)
+    }
+}
+
+fn prepare_job_skybox_bind_groups(
+    views: Query<(Entity, &Skybox, &ExtractedCamera)>,
+    images: Res<RenderAssets<GpuImage>>,
+    view_uniforms: Res<ViewUniforms>,
+    mut skyboxes: ResMut<JobSkyboxBindGroups>,
+    render_device: Res<RenderDevice>,
+    render_queue: Res<RenderQueue>,
+    mut commands: Commands,
+) {
+    let skyboxes = skyboxes.as_mut();
+    skyboxes.uniforms.clear();
+    let offsets = views
+        .iter()
+        .map(|(_, skybox, camera)| {
+            skyboxes
+                .uniforms
+                .push(&skybox_uniforms(skybox, camera.exposure))
+        })
+        .collect::<Vec<_>>();
+    skyboxes
+        .uniforms
+        .write_buffer(&render_device, &render_queue);
+
+    for ((view, skybox, _), uniform_offset) in views.iter().zip(offsets) {
+        let (Some(image), Some(view_uniforms), Some(uniforms)) = (
+            images.get(&skybox.image),
+            view_uniforms.uniforms.binding(),
+            skyboxes.uniforms.binding(),
+        ) else {
+            commands.entity(view).remove::<JobSkyboxBindGroup>();
+            continue;
+        };
+
+        let bind_group = render_device.create_bind_group(
+            "job_skybox_bind_group",
+            skyboxes.layout(&render_device),
+            &BindGroupEntries::sequential((
+                &image.texture_view,
+                &image.sampler,
+                view_uniforms,
+                uniforms,
+            )),
+        );
+        commands.entity(view).insert(JobSkyboxBindGroup {
+            bind_group,
+            uniform_offset,
+        });
+    }
+}
+
+fn reset_job_skybox_bind_groups(world: &mut World) {
+    remove_all::<JobSkyboxBindGroup>(world);
+    if let Some(mut skyboxes) = world.get_resource_mut::<JobSkyboxBindGroups>() {
+        skyboxes.uniforms = DynamicUniformBuffer::default();
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use bevy_core_pipeline::Skybox;
+    use bevy_ecs::{component::Component, world::World};
+    use bevy_math::{Mat4, Quat, Vec3};
+
+    use crate::{
+        input::{
+            camera_view::{assert_waits_for_view, test_view},
+            JobInput, JobInputItem, JobInputStatus,
+        },
+        GraphicsJob, JobError, JobRunContext,
+    };
+
+    use super::{skybox_uniforms, ExtractedJobSkybox, JobSkybox};
+
+    #[derive(Clone, Component)]
+    struct SkyFillJob;
+
+    impl GraphicsJob for SkyFillJob {
+        type In = JobSkybox;
+
+        fn run(
+            &self,
+            _world: &World,
+            _context: &mut JobRunContext,
+            _input: JobInputItem<Self, Self::In>,
+        ) -> Result<(), JobError> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn views_without_skybox_fail() {
+        let mut world = World::new();
+        let status = <JobSkybox as JobInput<SkyFillJob>>::status;
+        let (camera, skybox) = assert_waits_for_view(&mut world, status, ExtractedJobSkybox);
+
+        world.entity_mut(camera).insert(test_view(false));
+        assert_eq!(status(Some(&skybox), &world), JobInputStatus::Fail);
+
+        // the bind group hasn't been prepared
+        world.entity_mut(camera).insert(Skybox::default());
+        assert_eq!(status(Some(&skybox), &world), JobInputStatus::Wait);
+    }
+
+    #[test]
+    fn uniforms_undo_skybox_rotation() {
+        let skybox = Skybox {
+            brightness: 1000.0,
+            rotation: Quat::from_rotation_y(core::f32::consts::FRAC_PI_2),
+            ..Default::default()
+        };
+        let [brightness, x_axis, y_axis, z_axis, w_axis] = skybox_uniforms(&skybox, 0.5);
+        assert_eq!(brightness.x, 500.0);
+
+        let transform = Mat4::from_cols(x_axis, y_axis, z_axis, w_axis);
+        let direction = transform.transform_vector3(Vec3::X);
+        assert!(direction.abs_diff_eq(Vec3::Z, 1e-6));
+    }
+}
@@ -0,0 +1,225 @@
+use bevy_app::{App, Plugin};
+use bevy_asset::Handle;
+use bevy_ecs::{
+    component::Component,
+    entity::Entity,
+    query::{QueryItem, Without},
+    schedule::IntoSystemConfigs,
+    system::{lifetimeless::Read, Commands, Query, Res},
+    world::World,
+};
+use bevy_image::Image;
+use bevy_render::{
+    extract_component::{ExtractComponent, ExtractComponentPlugin},
+    render_asset::RenderAssets,
+    render_resource::{
+        binding_types::texture_storage_2d, BindGroupLayoutEntryBuilder, BindingResource,
+        StorageTextureAccess, Texture, TextureFormat, TextureUsages, TextureView,
+        TextureViewDescriptor, TextureViewDimension,
+    },
+    texture::GpuImage,
+    Render, RenderApp, RenderSet,
+};
+
+use crate::{
+    device::{add_device_reset, remove_all},
+    GraphicsJob,
+};
+
+use super::{JobInput, JobInputStatus};
+
+/// A [`JobInput`] providing a 2D [`Image`] as a storage texture, with the given access
+/// and storage format, for example for a compute job writing into an image that's then
+/// displayed by a sprite.
+///
+/// The storage format must be the format of the image's texture, which must support
+/// storage, but it may differ from the format the image is sampled with. For example,
+/// an `Rgba8Unorm` image can be written to as a storage texture and sampled as
+/// `Rgba8UnormSrgb` by setting the format of its `texture_view_descriptor`, and listing
+/// that format in its `view_formats`. The image must be created with
+/// [`TextureUsages::STORAGE_BINDING`].
+///
+/// The job waits until the image is prepared in the render world, and fails if it
+/// lacks the storage usage, if the formats are incompatible, or if the mip level is
+/// out of bounds. Use [`layout_entry`](Self::layout_entry) when creating the bind group
+/// layout, so that it matches the view bound with [`PreparedJobStorageTexture::binding`].
+#[derive(Clone, Component, PartialEq, Debug)]
+pub struct JobStorageTexture {
+    pub image: Handle<Image>,
+    pub access: StorageTextureAccess,
+    pub format: TextureFormat,
+    /// The mip level to bind, since storage textures can only view a single one.
+    pub mip_level: u32,
+}
+
+impl JobStorageTexture {
+    /// Binds the first mip level of the image for writing.
+    pub fn write_only(image: Handle<Image>, format: TextureFormat) -> Self {
+        Self {
+            image,
+            access: StorageTextureAccess::WriteOnly,
+            format,
+            mip_level: 0,
+        }
+    }
+
+    /// Binds the first mip level of the image for reading and writing. Only some
+    /// formats support this, like `R32Float` and `R32Uint`.
+    pub fn read_write(image: Handle<Image>, format: TextureFormat) -> Self {
+        Self {
+            access: StorageTextureAccess::ReadWrite,
+            ..Self::write_only(image, format)
+        }
+    }
+
+    pub fn with_mip_level(mut self, mip_level: u32) -> Self {
+        self.mip_level = mip_level;
+        self
+    }
+
+    /// The bind group layout entry for a texture bound with this access and format.
+    pub fn layout_entry(&self) -> BindGroupLayoutEntryBuilder {
+        texture_storage_2d(self.format, self.access)
+    }
+
+    /// Returns whether a texture with the given usage, format and mip level count
+    /// can be bound as this storage texture.
+    fn supports(&self, usage: TextureUsages, format: TextureFormat, mip_level_count: u32) -> bool {
+        usage.contains(TextureUsages::STORAGE_BINDING)
+            && self.format == format
+            && self.mip_level < mip_level_count
+    }
+}
+
+impl<J: GraphicsJob> JobInput<J> for JobStorageTexture {
+    type Data = (
+        Read<JobStorageTexture>,
+        Option<Read<PreparedJobStorageTexture>>,
+    );
+
+    type Item<'a> = &'a PreparedJobStorageTexture;
+
+    fn plugin() -> impl Plugin {
+        |app: &mut App| {
+            if !app.is_plugin_added::<ExtractComponentPlugin<JobStorageTexture>>() {
+                app.add_plugins(ExtractComponentPlugin::<JobStorageTexture>::default());
+
+                if let Some(render_app) = app.get_sub_app_mut(RenderApp) {
+                    render_app.add_systems(
+                        Render,
+                        prepare_job_storage_textures.in_set(RenderSet::PrepareResources),
+                    );
+                }
+
+                add_device_reset(app, remove_all::<PreparedJobStorageTexture>);
+            }
+        }
+    }
+
+    fn status((storage, prepared): QueryItem<Self::Data>, world: &World) -> JobInputStatus {
+        if prepared.is_some() {
+            return JobInputStatus::Ready;
+        }
+
+        match world
+            .resource::<RenderAssets<GpuImage>>()
+            .get(&storage.image)
+        {
+            Some(image)
+                if !storage.supports(
+                    image.texture.usage(),
+                    image.texture.format(),
+                    image.mip_level_count,
+                ) =>
+            {
+                JobInputStatus::Fail
+            }
+            _ => JobInputStatus::Wait,
+        }
+    }
+
+    fn get<'a>((_, prepared): QueryItem<'a, Self::Data>, _world: &'a World) -> Self::Item<'a> {
+        prepared.expect("storage texture should be ready by this point")
+    }
+}
+
+impl ExtractComponent for JobStorageTexture {
+    type QueryData = Read<JobStorageTexture>;
+
+    type QueryFilter = ();
+
+    type Out = JobStorageTexture;
+
+    fn extract_component(item: QueryItem<'_, Self::QueryData>) -> Option<Self::Out> {
+        Some(item.clone())
+    }
+}
+
+/// The storage view of an image created for a job by [`JobStorageTexture`].
+#[derive(Component)]
+pub struct PreparedJobStorageTexture {
+    pub texture: Texture,
+    pub view: TextureView,
+}
+
+impl PreparedJobStorageTexture {
+    pub fn binding(&self) -> BindingResource<'_> {
+        BindingResource::TextureView(&self.view)
+    }
+}
+
+fn prepare_job_storage_textures(
+    jobs: Query<(Entity, &JobStorageTexture), Without<PreparedJobStorageTexture>>,
+    images: Res<RenderAssets<GpuImage>>,
+    mut commands: Commands,
+) {
+    for (entity, storage) in &jobs {
+        let Some(image) = images.get(&storage.image) else {
+            continue;
+        };
+        if !storage.supports(
+            image.texture.usage(),
+            image.texture.format(),
+            image.mip_level_count,
+        ) {
+            continue;
+        }
+
+        let view = image.texture.create_view(&TextureViewDescriptor {
+            label: Some("job_storage_texture_view"),
+            format: Some(storage.format),
+            dimension: Some(TextureViewDimension::D2),
+            base_mip_level: storage.mip_level,
+            mip_level_count: Some(1),
+            ..Default::default()
+        });
+
+        commands.entity(entity).insert(PreparedJobStorageTexture {
+            texture: image.texture.clone(),
+            view,
+        });
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use bevy_asset::Handle;
+    use bevy_render::render_resource::{TextureFormat, TextureUsages};
+
+    use super::JobStorageTexture;
+
+    #[test]
+    fn validates_usage_and_format() {
+        let storage = JobStorageTexture::write_only(Handle::default(), TextureFormat::Rgba8Unorm);
+        let usage = TextureUsages::STORAGE_BINDING | TextureUsages::TEXTURE_BINDING;
+
+        assert!(storage.supports(usage, TextureFormat::Rgba8Unorm, 1));
+        // the texture itself must have the storage format, not only its views
+        assert!(!storage.supports(usage, TextureFormat::Rgba8UnormSrgb, 1));
+        assert!(!storage.supports(TextureUsages::TEXTURE_BINDING, TextureFormat::Rgba8Unorm, 1));
+        assert!(!storage.supports(usage, TextureFormat::Bgra8Unorm, 1));
+        assert!(!storage
+            .with_mip_level(1)
+            .supports(usage, TextureFormat::Rgba8Unorm, 1));
+    }
+}
@@ -0,0 +1,306 @@
+use core::marker::PhantomData;
+
+use bevy_app::{App, Plugin};
+use bevy_core_pipeline::{core_2d::Camera2d, core_3d::Camera3d};
+use bevy_ecs::{
+    component::Component,
+    entity::Entity,
+    query::{Has, QueryItem},
+    schedule::IntoSystemConfigs,
+    system::{lifetimeless::Read, Query},
+    world::{EntityRef, World},
+};
+use bevy_image::BevyDefault;
+use bevy_render::{
+    render_resource::{MultisampleState, TextureFormat},
+    view::{ExtractedView, Msaa, ViewTarget},
+    ExtractSchedule, Render, RenderApp, RenderSet,
+};
+
+use crate::{GraphicsJob, JobSet};
+
+use super::{
+    camera_view::{extract_camera_views, CameraViewInput},
+    JobInput, JobInputStatus, JobRenderPipeline, SpecializedJobRenderPipeline,
+};
+
+/// A [`JobInput`] providing everything a render pipeline drawing into a camera's
+/// [`ViewTarget`] must be specialized for at once, as a [`ViewTargetFormat`], so that
+/// a pipeline can't be built for the right format but the wrong sample count.
+///
+/// The job waits until the camera is active. To keep a job's [`JobRenderPipeline`] key
+/// in sync with the target, implement [`TargetFormatKey`] for the pipeline's key, or use
+/// [`ViewTargetFormat`] itself as the key, and add [`JobTargetFormatKeyPlugin`].
+#[derive(Copy, Clone, Component, PartialEq, Eq, Debug)]
+pub struct JobTargetFormat(pub Entity);
+
+impl JobTargetFormat {
+    /// Reads the target format of the given main world camera.
+    pub const fn new(camera: Entity) -> Self {
+        Self(camera)
+    }
+}
+
+/// The render world view entity whose target format is read by a job's [`JobTargetFormat`].
+#[derive(Copy, Clone, Component)]
+#[doc(hidden)]
+pub struct ExtractedJobTargetFormat(pub Entity);
+
+/// The format and sample count of a view's main texture, and whether the view has a
+/// depth texture, provided by [`JobTargetFormat`].
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+pub struct ViewTargetFormat {
+    /// The format of the view's main texture, which differs between HDR and LDR views.
+    pub format: TextureFormat,
+    /// The number of samples of the view's main texture, which is more than one if the
+    /// view is multisampled.
+    pub sample_count: u32,
+    /// Whether bevy's 2D or 3D pipeline prepares a depth texture for the view, with the
+    /// same sample count as its main texture, in `Depth32Float`.
+    pub has_depth: bool,
+}
+
+impl ViewTargetFormat {
+    /// Reads the target format of a render world view, or returns `None` if the
+    /// entity isn't an active view.
+    ///
+    /// This only depends on the view's extracted components, so it's known before the
+    /// view's textures are prepared, when pipelines are specialized.
+    pub fn of(view: EntityRef) -> Option<Self> {
+        Some(Self::from_components(
+            view.get::<ExtractedView>()?,
+            view.get::<Msaa>(),
+            view.contains::<Camera3d>() || view.contains::<Camera2d>(),
+        ))
+    }
+
+    fn from_components(view: &ExtractedView, msaa: Option<&Msaa>, has_depth: bool) -> Self {
+        Self {
+            format: match view.hdr {
+                true => ViewTarget::TEXTURE_FORMAT_HDR,
+                false => TextureFormat::bevy_default(),
+            },
+            sample_count: msaa.map_or(1, Msaa::samples),
+            has_depth,
+        }
+    }
+
+    /// The multisample state for a pipeline drawing into the view's main texture.
+    pub fn multisample_state(&self) -> MultisampleState {
+        MultisampleState {
+            count: self.sample_count,
+            ..Default::default()
+        }
+    }
+}
+
+impl Default for ViewTargetFormat {
+    /// A placeholder for an LDR view without multisampling or depth.
+    fn default() -> Self {
+        Self {
+            format: TextureFormat::bevy_default(),
+            sample_count: 1,
+            has_depth: false,
+        }
+    }
+}
+
+impl<J: GraphicsJob> JobInput<J> for JobTargetFormat {
+    type Data = Option<Read<ExtractedJobTargetFormat>>;
+
+    type Item<'a> = ViewTargetFormat;
+
+    fn plugin() -> impl Plugin {
+        |app: &mut App| {
+            if !app.is_plugin_added::<JobTargetFormatPlugin>() {
+                app.add_plugins(JobTargetFormatPlugin);
+            }
+        }
+    }
+
+    fn status(data: QueryItem<Self::Data>, world: &World) -> JobInputStatus {
+        match data
+            .and_then(|view| world.get_entity(view.0).ok())
+            .and_then(ViewTargetFormat::of)
+        {
+            Some(_) => JobInputStatus::Ready,
+            // the camera isn't active yet
+            None => JobInputStatus::Wait,
+        }
+    }
+
+    fn get<'a>(data: QueryItem<'a, Self::Data>, world: &'a World) -> Self::Item<'a> {
+        data.and_then(|view| world.get_entity(view.0).ok())
+            .and_then(ViewTargetFormat::of)
+            .expect("target format should be ready by this point")
+    }
+}
+
+struct JobTargetFormatPlugin;
+
+impl Plugin for JobTargetFormatPlugin {
+    fn build(&self, app: &mut App) {
+        if let Some(render_app) = app.get_sub_app_mut(RenderApp) {
+            render_app.add_systems(ExtractSchedule, extract_camera_views::<JobTargetFormat>);
+        }
+    }
+}
+
+impl CameraViewInput for JobTargetFormat {
+    type Extracted = ExtractedJobTargetFormat;
+
+    type Data = ();
+
+    fn camera(&self) -> Entity {
+        self.0
+    }
+
+    fn extract(&self, view: Entity, (): ()) -> Self::Extracted {
+        ExtractedJobTargetFormat(view)
+    }
+}
+
+/// A specialization key for a pipeline that draws into a view's target, which is kept
+/// in sync with the view's [`ViewTargetFormat`] by [`JobTargetFormatKeyPlugin`].
+pub trait TargetFormatKey {
+    fn target_format(&self) -> ViewTargetFormat;
+
+    fn set_target_format(&mut self, format: ViewTargetFormat);
+}
+
+impl TargetFormatKey for ViewTargetFormat {
+    fn target_format(&self) -> ViewTargetFormat {
+        *self
+    }
+
+    fn set_target_format(&mut self, format: ViewTargetFormat) {
+        *self = format;
+    }
+}
+
+/// Updates the [`JobRenderPipeline<P>`] key of every job with a [`JobTargetFormat`] to
+/// match the [`ViewTargetFormat`] of the view it reads, before the pipeline is
+/// specialized. The key a job is spawned with is only a placeholder.
+pub struct JobTargetFormatKeyPlugin<P>(PhantomData<P>);
+
+impl<P> Default for JobTargetFormatKeyPlugin<P> {
+    fn default() -> Self {
+        Self(PhantomData)
+    }
+}
+
+impl<P: SpecializedJobRenderPipeline<Key: TargetFormatKey>> Plugin for JobTargetFormatKeyPlugin<P> {
+    fn build(&self, app: &mut App) {
+        if let Some(render_app) = app.get_sub_app_mut(RenderApp) {
+            render_app.add_systems(
+                Render,
+                specialize_target_format_jobs::<P>
+                    .in_set(RenderSet::Queue)
+                    .before(JobSet::QueuePipelines),
+            );
+        }
+    }
+}
+
+fn specialize_target_format_jobs<P: SpecializedJobRenderPipeline<Key: TargetFormatKey>>(
+    mut jobs: Query<(&ExtractedJobTargetFormat, &mut JobRenderPipeline<P>)>,
+    views: Query<(&ExtractedView, Option<&Msaa>, Has<Camera3d>, Has<Camera2d>)>,
+) {
+    for (target, mut pipeline) in &mut jobs {
+        let Ok((view, msaa, is_3d, is_2d)) = views.get(target.0) else {
+            continue;
+        };
+
+        let format = ViewTargetFormat::from_components(view, msaa, is_3d || is_2d);
+        if pipeline.0.target_format() != format {
+            pipeline.0.set_target_format(format);
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use bevy_core_pipeline::{core_2d::Camera2d, core_3d::Camera3d};
+    use bevy_ecs::{component::Component, world::World};
+    use bevy_image::BevyDefault;
+    use bevy_render::{
+        render_resource::TextureFormat,
+        view::{Msaa, ViewTarget},
+    };
+
+    use crate::{
+        input::{
+            camera_view::{assert_waits_for_view, test_view},
+            JobInput, JobInputItem, JobInputStatus,
+        },
+        GraphicsJob, JobError, JobRunContext,
+    };
+
+    use super::{ExtractedJobTargetFormat, JobTargetFormat, ViewTargetFormat};
+
+    #[derive(Clone, Component)]
+    struct OverlayJob;
+
+    impl GraphicsJob for OverlayJob {
+        type In = JobTargetFormat;
+
+        fn run(
+            &self,
+            _world: &World,
+            _context: &mut JobRunContext,
+            _input: JobInputItem<Self, Self::In>,
+        ) -> Result<(), JobError> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn waits_for_active_view() {
+        let mut world = World::new();
+        let status = <JobTargetFormat as JobInput<OverlayJob>>::status;
+        let (camera, target) = assert_waits_for_view(&mut world, status, ExtractedJobTargetFormat);
+
+        world.entity_mut(camera).insert(test_view(false));
+        assert_eq!(status(Some(&target), &world), JobInputStatus::Ready);
+    }
+
+    #[test]
+    fn formats_follow_hdr_and_msaa() {
+        let mut world = World::new();
+        let mut format = |hdr, msaa: Msaa| {
+            let view = world
+                .spawn((test_view(hdr), msaa, Camera3d::default()))
+                .id();
+            ViewTargetFormat::of(world.entity(view)).unwrap()
+        };
+
+        let ldr = format(false, Msaa::Off);
+        assert_eq!(ldr.format, TextureFormat::bevy_default());
+        assert_eq!(ldr.sample_count, 1);
+        assert!(ldr.has_depth);
+
+        let hdr = format(true, Msaa::Sample4);
+        assert_eq!(hdr.format, ViewTarget::TEXTURE_FORMAT_HDR);
+        assert_eq!(hdr.sample_count, 4);
+        assert_eq!(hdr.multisample_state().count, 4);
+
+        assert_eq!(format(true, Msaa::Off).sample_count, 1);
+        assert_eq!(
+            format(false, Msaa::Sample4).format,
+            TextureFormat::bevy_default()
+        );
+
+        // views rendered by neither of bevy's 2D or 3D pipelines have no depth texture
+        let custom = world.spawn(test_view(false)).id();
+        let custom = ViewTargetFormat::of(world.entity(custom)).unwrap();
+        assert!(!custom.has_depth);
+        assert_eq!(custom.sample_count, 1);
+
+        let sprites = world.spawn((test_view(false), Camera2d)).id();
+        assert!(
+            ViewTargetFormat::of(world.entity(sprites))
+                .unwrap()
+                .has_depth
+        );
+    }
+}
@@ -0,0 +1,250 @@
+use bevy_app::{App, Plugin};
+use bevy_core_pipeline::{
+    experimental::taa::TemporalAntiAliasing,
+    prepass::{PreviousViewData, ViewPrepassTextures},
+};
+use bevy_ecs::{
+    component::Component,
+    entity::Entity,
+    query::QueryItem,
+    system::lifetimeless::Read,
+    world::{EntityRef, World},
+};
+use bevy_math::{Mat4, Vec2};
+use bevy_render::{
+    camera::TemporalJitter,
+    render_resource::{Extent3d, TextureView},
+    view::ExtractedView,
+    ExtractSchedule, RenderApp,
+};
+
+use crate::GraphicsJob;
+
+use super::{
+    camera_view::{extract_camera_views, view_feature_status, CameraViewInput},
+    JobInput, JobInputStatus,
+};
+
+/// A [`JobInput`] providing what custom temporal effects need to reproject a history
+/// texture: a camera's [`TemporalJitter`] this frame, and its view projection in the
+/// previous frame.
+///
+/// The camera must have [`TemporalAntiAliasing`], which requires a perspective
+/// [`Camera3d`](bevy_core_pipeline::core_3d::Camera3d) with [`Msaa::Off`](bevy_render::view::Msaa::Off),
+/// and adds the [`TemporalJitter`], [`DepthPrepass`](bevy_core_pipeline::prepass::DepthPrepass)
+/// and [`MotionVectorPrepass`](bevy_core_pipeline::prepass::MotionVectorPrepass) it relies on.
+/// The job waits until the camera's prepass textures are prepared, and fails if the
+/// camera doesn't use TAA.
+#[derive(Copy, Clone, Component, PartialEq, Eq, Debug)]
+pub struct JobTemporal(pub Entity);
+
+impl JobTemporal {
+    /// Targets the given main world camera.
+    pub const fn new(camera: Entity) -> Self {
+        Self(camera)
+    }
+}
+
+/// The render world view entity targeted by a job's [`JobTemporal`].
+#[derive(Copy, Clone, Component)]
+#[doc(hidden)]
+pub struct ExtractedJobTemporal(pub Entity);
+
+impl<J: GraphicsJob> JobInput<J> for JobTemporal {
+    type Data = Option<Read<ExtractedJobTemporal>>;
+
+    type Item<'a> = JobTemporalItem<'a>;
+
+    fn plugin() -> impl Plugin {
+        |app: &mut App| {
+            if !app.is_plugin_added::<JobTemporalPlugin>() {
+                app.add_plugins(JobTemporalPlugin);
+            }
+        }
+    }
+
+    fn status(data: QueryItem<Self::Data>, world: &World) -> JobInputStatus {
+        view_feature_status::<TemporalAntiAliasing>(data.map(|view| view.0), world, |view| {
+            JobTemporalItem::new(view).is_some()
+        })
+    }
+
+    fn get<'a>(data: QueryItem<'a, Self::Data>, world: &'a World) -> Self::Item<'a> {
+        let view = world
+            .get_entity(data.expect("temporal data should be ready by this point").0)
+            .expect("temporal data should be ready by this point");
+        JobTemporalItem::new(view).expect("temporal data should be ready by this point")
+    }
+}
+
+/// The temporal data provided by [`JobTemporal`].
+///
+/// Jobs run before the render graph, so the prepass textures hold the previous frame,
+/// which was rendered with [`previous_clip_from_world`](Self::previous_clip_from_world).
+pub struct JobTemporalItem<'a> {
+    /// The render world view entity, for looking up other view components.
+    pub view: EntityRef<'a>,
+    /// The camera's sub-pixel jitter this frame, in pixels, in the range `[-0.5, 0.5]`.
+    pub jitter: Vec2,
+    /// Transforms from world space to clip space this frame, without the jitter.
+    pub clip_from_world: Mat4,
+    /// Transforms from world space to view space in the previous frame.
+    pub previous_view_from_world: Mat4,
+    /// Transforms from world space to clip space in the previous frame, without its
+    /// jitter. In the camera's first frame, this is the same as
+    /// [`clip_from_world`](Self::clip_from_world).
+    pub previous_clip_from_world: Mat4,
+    /// The prepass depth.
+    pub depth: &'a TextureView,
+    /// The prepass motion vectors.
+    pub motion_vectors: &'a TextureView,
+    /// The size of the prepass textures.
+    pub size: Extent3d,
+}
+
+impl<'a> JobTemporalItem<'a> {
+    fn new(view: EntityRef<'a>) -> Option<Self> {
+        let extracted_view = view.get::<ExtractedView>()?;
+        let jitter = view.get::<TemporalJitter>()?;
+        let textures = view.get::<ViewPrepassTextures>()?;
+
+        let view_from_world = extracted_view.world_from_view.compute_matrix().inverse();
+        let clip_from_world = extracted_view
+            .clip_from_world
+            .unwrap_or(extracted_view.clip_from_view * view_from_world);
+        let (previous_view_from_world, previous_clip_from_world) =
+            previous_view(view, view_from_world, clip_from_world);
+
+        Some(Self {
+            view,
+            jitter: jitter.offset,
+            clip_from_world,
+            previous_view_from_world,
+            previous_clip_from_world,
+            depth: textures.depth_view()?,
+            motion_vectors: textures.motion_vectors_view()?,
+            size: textures.size,
+        })
+    }
+}
+
+/// The previous `view_from_world` and `clip_from_world` of a view. bevy_pbr extracts
+/// these for cameras with motion vectors, and falls back to the current ones in their
+/// first frame the same way.
+fn previous_view(view: EntityRef, view_from_world: Mat4, clip_from_world: Mat4) -> (Mat4, Mat4) {
+    view.get::<PreviousViewData>()
+        .map_or((view_from_world, clip_from_world), |previous| {
+            (previous.view_from_world, previous.clip_from_world)
+        })
+}
+
+struct JobTemporalPlugin;
+
+impl Plugin for JobTemporalPlugin {
+    fn build(&self, app: &mut App) {
+        if let Some(render_app) = app.get_sub_app_mut(RenderApp) {
+            render_app.add_systems(ExtractSchedule, extract_camera_views::<JobTemporal>);
+        }
+    }
+}
+
+impl CameraViewInput for JobTemporal {
+    type Extracted = ExtractedJobTemporal;
+
+    type Data = ();
+
+    fn camera(&self) -> Entity {
+        self.0
+    }
+
+    fn extract(&self, view: Entity, (): ()) -> Self::Extracted {
+        ExtractedJobTemporal(view)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use bevy::transform::components::{GlobalTransform, Transform};
+    use bevy_core_pipeline::{experimental::taa::TemporalAntiAliasing, prepass::PreviousViewData};
+    use bevy_ecs::{component::Component, world::World};
+    use bevy_math::{Mat4, Vec3};
+    use bevy_render::{camera::TemporalJitter, view::ExtractedView};
+
+    use crate::{
+        input::{
+            camera_view::{assert_waits_for_view, test_view},
+            JobInput, JobInputItem, JobInputStatus,
+        },
+        GraphicsJob, JobError, JobRunContext,
+    };
+
+    use super::{previous_view, ExtractedJobTemporal, JobTemporal};
+
+    #[derive(Clone, Component)]
+    struct ReprojectJob;
+
+    impl GraphicsJob for ReprojectJob {
+        type In = JobTemporal;
+
+        fn run(
+            &self,
+            _world: &World,
+            _context: &mut JobRunContext,
+            _input: JobInputItem<Self, Self::In>,
+        ) -> Result<(), JobError> {
+            Ok(())
+        }
+    }
+
+    /// A perspective view looking at the origin, so reprojection isn't the identity.
+    fn view() -> ExtractedView {
+        ExtractedView {
+            clip_from_view: Mat4::perspective_infinite_reverse_rh(1.0, 16.0 / 9.0, 0.1),
+            world_from_view: GlobalTransform::from(
+                Transform::from_xyz(0.0, 1.0, 4.0).looking_at(Vec3::ZERO, Vec3::Y),
+            ),
+            ..test_view(false)
+        }
+    }
+
+    #[test]
+    fn views_without_taa_fail() {
+        let mut world = World::new();
+        let status = <JobTemporal as JobInput<ReprojectJob>>::status;
+        let (camera, temporal) = assert_waits_for_view(&mut world, status, ExtractedJobTemporal);
+
+        world.entity_mut(camera).insert(view());
+        assert_eq!(status(Some(&temporal), &world), JobInputStatus::Fail);
+
+        // the prepass textures aren't prepared yet
+        world
+            .entity_mut(camera)
+            .insert((TemporalAntiAliasing::default(), TemporalJitter::default()));
+        assert_eq!(status(Some(&temporal), &world), JobInputStatus::Wait);
+    }
+
+    #[test]
+    fn previous_view_falls_back_to_the_current_view() {
+        let mut world = World::new();
+        let view = view();
+        let view_from_world = view.world_from_view.compute_matrix().inverse();
+        let clip_from_view = view.clip_from_view;
+        let clip_from_world = clip_from_view * view_from_world;
+        let camera = world.spawn(view).id();
+
+        assert_eq!(
+            previous_view(world.entity(camera), view_from_world, clip_from_world),
+            (view_from_world, clip_from_world)
+        );
+
+        let previous = Mat4::from_translation(Vec3::X);
+        world.entity_mut(camera).insert(PreviousViewData {
+            view_from_world: previous,
+            clip_from_world: clip_from_view * previous,
+        });
+        assert_eq!(
+            previous_view(world.entity(camera), view_from_world, clip_from_world),
+            (previous, clip_from_view * previous)
+        );
+    }
+}
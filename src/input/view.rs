@@ -0,0 +1,202 @@
+use bevy_app::{App, Plugin};
+use bevy_ecs::{
+    component::Component,
+    entity::Entity,
+    query::{QueryItem, With},
+    schedule::IntoSystemConfigs,
+    system::{lifetimeless::Read, Query, ResMut, Resource},
+    world::{EntityRef, World},
+};
+use bevy_math::{URect, UVec4, Vec4Swizzles};
+use bevy_render::{
+    extract_component::{ExtractComponent, ExtractComponentPlugin},
+    view::{ExtractedView, RenderLayers, ViewUniformOffset},
+    Render, RenderApp, RenderSet,
+};
+
+use crate::GraphicsJob;
+
+use super::{JobInput, JobInputStatus};
+
+/// A [`JobInput`] providing the render-world view entities a job should process,
+/// for example to apply an effect to each active camera.
+///
+/// By default, all views are provided. To only process views on certain layers,
+/// add [`JobRenderLayers`] to the job when it is spawned.
+pub struct JobViews;
+
+impl<J: GraphicsJob> JobInput<J> for JobViews {
+    type Data = Option<Read<JobRenderLayers>>;
+
+    type Item<'a> = Vec<EntityRef<'a>>;
+
+    fn plugin() -> impl Plugin {
+        |app: &mut App| {
+            if !app.is_plugin_added::<JobViewsPlugin>() {
+                app.add_plugins(JobViewsPlugin);
+            }
+        }
+    }
+
+    fn status(_data: QueryItem<Self::Data>, _world: &World) -> JobInputStatus {
+        JobInputStatus::Ready
+    }
+
+    fn get<'a>(data: QueryItem<'a, Self::Data>, world: &'a World) -> Self::Item<'a> {
+        job_views(data, world).collect()
+    }
+}
+
+/// A [`JobInput`] providing every extracted view a job should process, along with
+/// its [`ViewUniformOffset`] and viewport, so that a single job can loop over all
+/// views, for example to apply a screen-space effect to each camera.
+///
+/// Unlike [`JobViews`], views whose uniforms haven't been prepared are skipped.
+/// If there are no active views, the job still runs with an empty list. As with
+/// [`JobViews`], add [`JobRenderLayers`] to the job to filter the views by layer.
+pub struct JobExtractedViews;
+
+impl<J: GraphicsJob> JobInput<J> for JobExtractedViews {
+    type Data = Option<Read<JobRenderLayers>>;
+
+    type Item<'a> = Vec<JobExtractedView<'a>>;
+
+    fn plugin() -> impl Plugin {
+        <JobViews as JobInput<J>>::plugin()
+    }
+
+    fn status(_data: QueryItem<Self::Data>, _world: &World) -> JobInputStatus {
+        JobInputStatus::Ready
+    }
+
+    fn get<'a>(data: QueryItem<'a, Self::Data>, world: &'a World) -> Self::Item<'a> {
+        job_views(data, world)
+            .filter_map(|entity| {
+                Some(JobExtractedView {
+                    view: entity.get::<ExtractedView>()?,
+                    uniform_offset: entity.get::<ViewUniformOffset>()?,
+                    entity,
+                })
+            })
+            .collect()
+    }
+}
+
+/// A view provided by [`JobExtractedViews`].
+pub struct JobExtractedView<'a> {
+    /// The render-world view entity, for looking up other view components
+    /// like [`ViewTarget`](bevy_render::view::ViewTarget).
+    pub entity: EntityRef<'a>,
+    pub view: &'a ExtractedView,
+    /// The offset of this view's data in [`ViewUniforms`](bevy_render::view::ViewUniforms),
+    /// for binding it with a dynamic offset.
+    pub uniform_offset: &'a ViewUniformOffset,
+}
+
+impl JobExtractedView<'_> {
+    /// Returns the view's viewport in physical pixels, relative to its render target.
+    pub fn viewport(&self) -> URect {
+        viewport_rect(self.view.viewport)
+    }
+}
+
+/// Converts a viewport stored as `(x, y, width, height)` to a rect.
+fn viewport_rect(viewport: UVec4) -> URect {
+    URect::from_corners(viewport.xy(), viewport.xy() + viewport.zw())
+}
+
+fn job_views<'a>(
+    layers: Option<&'a JobRenderLayers>,
+    world: &'a World,
+) -> impl Iterator<Item = EntityRef<'a>> {
+    world
+        .resource::<JobViewEntities>()
+        .0
+        .iter()
+        .filter_map(|entity| world.get_entity(*entity).ok())
+        .filter(move |view| layers.is_none_or(|layers| layers.matches(view.get::<RenderLayers>())))
+}
+
+/// Restricts the views provided to a job by [`JobViews`] to those whose [`RenderLayers`]
+/// intersect the given layers. This is useful to keep an effect from running on cameras
+/// it shouldn't touch, like UI or minimap cameras.
+///
+/// Views without a [`RenderLayers`] component are treated as being on layer `0`.
+#[derive(Clone, Component, Default, Debug)]
+pub struct JobRenderLayers(pub RenderLayers);
+
+impl JobRenderLayers {
+    /// Returns whether a view with the given layers should be processed
+    pub fn matches(&self, view_layers: Option<&RenderLayers>) -> bool {
+        match view_layers {
+            Some(view_layers) => self.0.intersects(view_layers),
+            None => self.0.intersects(&RenderLayers::default()),
+        }
+    }
+}
+
+impl ExtractComponent for JobRenderLayers {
+    type QueryData = Read<JobRenderLayers>;
+
+    type QueryFilter = ();
+
+    type Out = JobRenderLayers;
+
+    fn extract_component(item: QueryItem<'_, Self::QueryData>) -> Option<Self::Out> {
+        Some(item.clone())
+    }
+}
+
+/// All views in the render world, collected each frame for [`JobViews`].
+#[derive(Resource, Default)]
+struct JobViewEntities(Vec<Entity>);
+
+struct JobViewsPlugin;
+
+impl Plugin for JobViewsPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_plugins(ExtractComponentPlugin::<JobRenderLayers>::default());
+
+        if let Some(render_app) = app.get_sub_app_mut(RenderApp) {
+            render_app
+                .init_resource::<JobViewEntities>()
+                .add_systems(Render, collect_job_views.in_set(RenderSet::Prepare));
+        }
+    }
+}
+
+fn collect_job_views(
+    views: Query<Entity, With<ExtractedView>>,
+    mut view_entities: ResMut<JobViewEntities>,
+) {
+    view_entities.0.clear();
+    view_entities.0.extend(views.iter());
+}
+
+#[cfg(test)]
+mod test {
+    use bevy_math::{URect, UVec4};
+    use bevy_render::view::RenderLayers;
+
+    use super::{viewport_rect, JobRenderLayers};
+
+    #[test]
+    fn default_layer_matches_views_without_layers() {
+        let filter = JobRenderLayers(RenderLayers::layer(0));
+        assert!(filter.matches(None));
+        assert!(!JobRenderLayers(RenderLayers::layer(1)).matches(None));
+    }
+
+    #[test]
+    fn layers_must_intersect() {
+        let filter = JobRenderLayers(RenderLayers::layer(0));
+        assert!(filter.matches(Some(&RenderLayers::from_layers(&[0, 2]))));
+        assert!(!filter.matches(Some(&RenderLayers::layer(1))));
+    }
+
+    #[test]
+    fn viewport_is_offset_by_origin() {
+        let rect = viewport_rect(UVec4::new(10, 20, 100, 50));
+        assert_eq!(rect, URect::new(10, 20, 110, 70));
+    }
+}
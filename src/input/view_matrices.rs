@@ -0,0 +1,178 @@
+use bevy_app::{App, Plugin};
+use bevy_ecs::{
+    component::Component,
+    entity::Entity,
+    query::QueryItem,
+    system::lifetimeless::Read,
+    world::{EntityRef, World},
+};
+use bevy_math::{Mat4, Vec3, Vec4Swizzles};
+use bevy_render::{
+    camera::TemporalJitter,
+    view::{ExtractedView, ViewUniformOffset},
+    ExtractSchedule, RenderApp,
+};
+
+use crate::GraphicsJob;
+
+use super::{
+    camera_view::{extract_camera_views, CameraViewInput},
+    JobInput, JobInputStatus,
+};
+
+/// A [`JobInput`] providing the inverse matrices of a camera's view, for example to
+/// reconstruct world space positions from a depth texture. The job waits until the
+/// camera's view uniforms are prepared.
+///
+/// The matrices are computed from the camera's [`ExtractedView`] the same way as
+/// in [`ViewUniform`](bevy_render::view::ViewUniform), including the camera's
+/// [`TemporalJitter`], so they match what shaders reading the view uniforms see.
+#[derive(Copy, Clone, Component, PartialEq, Eq, Debug)]
+pub struct JobViewMatrices(pub Entity);
+
+impl JobViewMatrices {
+    /// Targets the given main world camera.
+    pub const fn new(camera: Entity) -> Self {
+        Self(camera)
+    }
+}
+
+/// The render world view entity targeted by a job's [`JobViewMatrices`].
+#[derive(Copy, Clone, Component)]
+#[doc(hidden)]
+pub struct ExtractedJobViewMatrices(pub Entity);
+
+impl<J: GraphicsJob> JobInput<J> for JobViewMatrices {
+    type Data = Option<Read<ExtractedJobViewMatrices>>;
+
+    type Item<'a> = JobViewMatricesItem<'a>;
+
+    fn plugin() -> impl Plugin {
+        |app: &mut App| {
+            if !app.is_plugin_added::<JobViewMatricesPlugin>() {
+                app.add_plugins(JobViewMatricesPlugin);
+            }
+        }
+    }
+
+    fn status(data: QueryItem<Self::Data>, world: &World) -> JobInputStatus {
+        match data
+            .and_then(|view| world.get_entity(view.0).ok())
+            .and_then(JobViewMatricesItem::new)
+        {
+            Some(_) => JobInputStatus::Ready,
+            None => JobInputStatus::Wait,
+        }
+    }
+
+    fn get<'a>(data: QueryItem<'a, Self::Data>, world: &'a World) -> Self::Item<'a> {
+        let view = world
+            .get_entity(data.expect("view matrices should be ready by this point").0)
+            .expect("view matrices should be ready by this point");
+        JobViewMatricesItem::new(view).expect("view matrices should be ready by this point")
+    }
+}
+
+/// The view matrices provided by [`JobViewMatrices`].
+pub struct JobViewMatricesItem<'a> {
+    /// The render world view entity, for looking up other view components, like
+    /// [`ViewPrepassTextures`](bevy_core_pipeline::prepass::ViewPrepassTextures).
+    pub view: EntityRef<'a>,
+    /// The offset of this view's data in [`ViewUniforms`](bevy_render::view::ViewUniforms),
+    /// for binding it with a dynamic offset.
+    pub uniform_offset: &'a ViewUniformOffset,
+    /// Transforms from view space to world space.
+    pub inverse_view: Mat4,
+    /// Transforms from clip space to view space.
+    pub inverse_projection: Mat4,
+    /// Transforms from clip space to world space.
+    pub inverse_view_proj: Mat4,
+    /// The camera's position in world space.
+    pub world_position: Vec3,
+}
+
+impl<'a> JobViewMatricesItem<'a> {
+    fn new(view: EntityRef<'a>) -> Option<Self> {
+        let extracted_view = view.get::<ExtractedView>()?;
+        let mut clip_from_view = extracted_view.clip_from_view;
+        if let Some(jitter) = view.get::<TemporalJitter>() {
+            let view_size = extracted_view.viewport.zw().as_vec2();
+            jitter.jitter_projection(&mut clip_from_view, view_size);
+        }
+
+        let inverse_view = extracted_view.world_from_view.compute_matrix();
+        let inverse_projection = clip_from_view.inverse();
+        Some(Self {
+            view,
+            uniform_offset: view.get::<ViewUniformOffset>()?,
+            inverse_view,
+            inverse_projection,
+            inverse_view_proj: inverse_view * inverse_projection,
+            world_position: extracted_view.world_from_view.translation(),
+        })
+    }
+}
+
+struct JobViewMatricesPlugin;
+
+impl Plugin for JobViewMatricesPlugin {
+    fn build(&self, app: &mut App) {
+        if let Some(render_app) = app.get_sub_app_mut(RenderApp) {
+            render_app.add_systems(ExtractSchedule, extract_camera_views::<JobViewMatrices>);
+        }
+    }
+}
+
+impl CameraViewInput for JobViewMatrices {
+    type Extracted = ExtractedJobViewMatrices;
+
+    type Data = ();
+
+    fn camera(&self) -> Entity {
+        self.0
+    }
+
+    fn extract(&self, view: Entity, (): ()) -> Self::Extracted {
+        ExtractedJobViewMatrices(view)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use bevy::transform::components::{GlobalTransform, Transform};
+    use bevy_ecs::world::World;
+    use bevy_math::{Mat4, Vec3, Vec4};
+    use bevy_render::view::{ExtractedView, ViewUniformOffset};
+
+    use crate::input::camera_view::test_view;
+
+    use super::JobViewMatricesItem;
+
+    #[test]
+    fn reconstructs_world_positions() {
+        let transform = Transform::from_xyz(1.0, 2.0, 3.0).looking_at(Vec3::ZERO, Vec3::Y);
+        let view = ExtractedView {
+            clip_from_view: Mat4::perspective_infinite_reverse_rh(1.0, 16.0 / 9.0, 0.1),
+            world_from_view: GlobalTransform::from(transform),
+            ..test_view(false)
+        };
+        let clip_from_world = view.clip_from_view * transform.compute_matrix().inverse();
+
+        let mut world = World::new();
+        let entity = world.spawn(view).id();
+        assert!(JobViewMatricesItem::new(world.entity(entity)).is_none());
+
+        world
+            .entity_mut(entity)
+            .insert(ViewUniformOffset { offset: 0 });
+        let matrices = JobViewMatricesItem::new(world.entity(entity)).unwrap();
+        assert_eq!(matrices.world_position, Vec3::new(1.0, 2.0, 3.0));
+
+        // project a point to clip space and back, as from a depth texture
+        let point = Vec3::new(0.5, -0.25, 0.0);
+        let clip = clip_from_world * point.extend(1.0);
+        let ndc = clip / clip.w;
+        let world_position = matrices.inverse_view_proj * Vec4::new(ndc.x, ndc.y, ndc.z, 1.0);
+        assert!((world_position.truncate() / world_position.w).abs_diff_eq(point, 1e-4));
+    }
+}
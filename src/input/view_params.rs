@@ -0,0 +1,333 @@
+use core::marker::PhantomData;
+use std::sync::OnceLock;
+
+use bevy_app::{App, Plugin};
+use bevy_ecs::{
+    component::Component,
+    entity::Entity,
+    query::{QueryItem, Without},
+    schedule::IntoSystemConfigs,
+    system::{lifetimeless::Read, Commands, Query, Res, Resource},
+    world::{EntityRef, World},
+};
+use bevy_render::{
+    render_resource::{
+        binding_types::uniform_buffer, encase::internal::WriteInto, BindGroup, BindGroupEntries,
+        BindGroupLayout, BindGroupLayoutEntries, ShaderStages, ShaderType, UniformBuffer,
+    },
+    renderer::{RenderDevice, RenderQueue},
+    view::{ViewUniform, ViewUniformOffset, ViewUniforms},
+    ExtractSchedule, Render, RenderApp, RenderSet,
+};
+
+use crate::{
+    device::{add_device_reset, remove_all},
+    memory::{JobMemoryAllocation, JobMemoryCategory, JobMemoryUsage},
+    GraphicsJob,
+};
+
+use super::{
+    camera_view::{extract_camera_views, CameraViewInput},
+    JobInput, JobInputStatus, JobPrepareSet,
+};
+
+/// A [`JobInput`] providing a single bind group holding both a camera's [`ViewUniform`]
+/// and the job's own uniform parameters, matching the most common layout of a
+/// post-process shader without assembling the bind group by hand:
+///
+/// ```wgsl
+/// @group(0) @binding(0) var<uniform> view: View;
+/// @group(0) @binding(1) var<uniform> params: Params;
+/// ```
+///
+/// The view is bound with the [`view_uniform_offset`](JobViewParamsItem::view_uniform_offset)
+/// of the camera. Create the job's pipeline with [`JobViewParamsBindGroups::layout`]. The
+/// parameters are uploaded once, when the job is extracted, and the job waits until both
+/// they and the camera's view uniforms are prepared.
+#[derive(Clone, Component)]
+pub struct JobViewParams<T: ShaderType + WriteInto + Clone + Send + Sync + 'static> {
+    /// The main world camera whose view uniform is bound.
+    pub camera: Entity,
+    /// The job's parameters, bound after the view.
+    pub params: T,
+}
+
+impl<T: ShaderType + WriteInto + Clone + Send + Sync + 'static> JobViewParams<T> {
+    /// Binds the view of the given main world camera along with `params`.
+    pub const fn new(camera: Entity, params: T) -> Self {
+        Self { camera, params }
+    }
+}
+
+/// The render world view entity and parameters of a job's [`JobViewParams`].
+#[derive(Component)]
+#[doc(hidden)]
+pub struct ExtractedJobViewParams<T: ShaderType + WriteInto + Clone + Send + Sync + 'static> {
+    view: Entity,
+    params: T,
+}
+
+/// The uniform buffer holding a job's parameters.
+#[derive(Component)]
+struct JobViewParamsBuffer<T: ShaderType + WriteInto + Clone + Send + Sync + 'static> {
+    buffer: UniformBuffer<T>,
+    _memory: JobMemoryAllocation,
+}
+
+/// The bind group of a job's [`JobViewParams`], rebuilt each frame since the view
+/// uniforms are.
+#[derive(Component)]
+#[doc(hidden)]
+pub struct PreparedJobViewParams<T: ShaderType + WriteInto + Clone + Send + Sync + 'static> {
+    bind_group: BindGroup,
+    view: Entity,
+    view_uniform_offset: u32,
+    marker: PhantomData<T>,
+}
+
+impl<J: GraphicsJob, T: ShaderType + WriteInto + Clone + Send + Sync + 'static> JobInput<J>
+    for JobViewParams<T>
+{
+    type Data = (
+        Option<Read<ExtractedJobViewParams<T>>>,
+        Option<Read<PreparedJobViewParams<T>>>,
+    );
+
+    type Item<'a> = JobViewParamsItem<'a, T>;
+
+    const PREPARE_AFTER: Option<RenderSet> = Some(RenderSet::PrepareResources);
+
+    fn plugin() -> impl Plugin {
+        |app: &mut App| {
+            if !app.is_plugin_added::<JobViewParamsPlugin<T>>() {
+                app.add_plugins(JobViewParamsPlugin::<T>(PhantomData));
+            }
+        }
+    }
+
+    fn status((_, prepared): QueryItem<Self::Data>, _world: &World) -> JobInputStatus {
+        match prepared {
+            Some(_) => JobInputStatus::Ready,
+            None => JobInputStatus::Wait,
+        }
+    }
+
+    fn get<'a>(
+        (extracted, prepared): QueryItem<'a, Self::Data>,
+        world: &'a World,
+    ) -> Self::Item<'a> {
+        let extracted = extracted.expect("view params should be ready by this point");
+        let prepared = prepared.expect("view params should be ready by this point");
+        JobViewParamsItem {
+            view: world
+                .get_entity(prepared.view)
+                .expect("view params should be ready by this point"),
+            bind_group: &prepared.bind_group,
+            view_uniform_offset: prepared.view_uniform_offset,
+            params: &extracted.params,
+        }
+    }
+}
+
+/// The bind group provided by [`JobViewParams`].
+pub struct JobViewParamsItem<'a, T> {
+    /// The render world view entity, for looking up other view components, like
+    /// [`ViewTarget`](bevy_render::view::ViewTarget).
+    pub view: EntityRef<'a>,
+    /// The bind group holding the view uniform and the job's parameters.
+    pub bind_group: &'a BindGroup,
+    /// The dynamic offset of the view's [`ViewUniform`] in the bind group.
+    pub view_uniform_offset: u32,
+    /// The job's parameters, as they were uploaded.
+    pub params: &'a T,
+}
+
+impl<T> JobViewParamsItem<'_, T> {
+    /// The dynamic offsets to set the [`bind_group`](Self::bind_group) with.
+    pub fn dynamic_offsets(&self) -> [u32; 1] {
+        [self.view_uniform_offset]
+    }
+}
+
+/// The layout of the bind groups prepared for [`JobViewParams<T>`].
+#[derive(Resource)]
+pub struct JobViewParamsBindGroups<T> {
+    layout: OnceLock<BindGroupLayout>,
+    marker: PhantomData<T>,
+}
+
+impl<T> Default for JobViewParamsBindGroups<T> {
+    fn default() -> Self {
+        Self {
+            layout: OnceLock::new(),
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<T: ShaderType> JobViewParamsBindGroups<T> {
+    /// The layout of the bind group, for creating the pipelines of jobs that bind it. Both
+    /// bindings are visible to every shader stage.
+    pub fn layout(&self, render_device: &RenderDevice) -> &BindGroupLayout {
+        self.layout.get_or_init(|| {
+            render_device.create_bind_group_layout(
+                "job_view_params_bind_group_layout",
+                &BindGroupLayoutEntries::sequential(
+                    ShaderStages::all(),
+                    (
+                        uniform_buffer::<ViewUniform>(true),
+                        uniform_buffer::<T>(false),
+                    ),
+                ),
+            )
+        })
+    }
+}
+
+struct JobViewParamsPlugin<T>(PhantomData<T>);
+
+impl<T: ShaderType + WriteInto + Clone + Send + Sync + 'static> Plugin for JobViewParamsPlugin<T> {
+    fn build(&self, app: &mut App) {
+        if let Some(render_app) = app.get_sub_app_mut(RenderApp) {
+            render_app
+                .init_resource::<JobViewParamsBindGroups<T>>()
+                .add_systems(ExtractSchedule, extract_camera_views::<JobViewParams<T>>)
+                .add_systems(
+                    Render,
+                    (
+                        prepare_job_view_params_buffers::<T>.in_set(RenderSet::PrepareResources),
+                        prepare_job_view_params::<T>
+                            .in_set(JobPrepareSet::of::<JobViewParams<T>>()),
+                    ),
+                );
+        }
+
+        add_device_reset(app, reset_job_view_params::<T>);
+    }
+}
+
+impl<T: ShaderType + WriteInto + Clone + Send + Sync + 'static> CameraViewInput
+    for JobViewParams<T>
+{
+    type Extracted = ExtractedJobViewParams<T>;
+
+    type Data = ();
+
+    fn camera(&self) -> Entity {
+        self.camera
+    }
+
+    fn extract(&self, view: Entity, (): ()) -> Self::Extracted {
+        ExtractedJobViewParams {
+            view,
+            params: self.params.clone(),
+        }
+    }
+}
+
+fn prepare_job_view_params_buffers<T: ShaderType + WriteInto + Clone + Send + Sync + 'static>(
+    jobs: Query<(Entity, &ExtractedJobViewParams<T>), Without<JobViewParamsBuffer<T>>>,
+    render_device: Res<RenderDevice>,
+    render_queue: Res<RenderQueue>,
+    memory_usage: Res<JobMemoryUsage>,
+    mut commands: Commands,
+) {
+    for (entity, extracted) in &jobs {
+        let mut buffer = UniformBuffer::from(extracted.params.clone());
+        buffer.set_label(Some("job_view_params_buffer"));
+        buffer.write_buffer(&render_device, &render_queue);
+        commands.entity(entity).insert(JobViewParamsBuffer {
+            buffer,
+            _memory: memory_usage.allocate(JobMemoryCategory::UniformBuffers, T::min_size().get()),
+        });
+    }
+}
+
+fn prepare_job_view_params<T: ShaderType + WriteInto + Clone + Send + Sync + 'static>(
+    jobs: Query<(Entity, &ExtractedJobViewParams<T>, &JobViewParamsBuffer<T>)>,
+    views: Query<&ViewUniformOffset>,
+    view_uniforms: Res<ViewUniforms>,
+    bind_groups: Res<JobViewParamsBindGroups<T>>,
+    render_device: Res<RenderDevice>,
+    mut commands: Commands,
+) {
+    for (entity, extracted, params) in &jobs {
+        let (Some(view_binding), Ok(view_uniform_offset), Some(params_binding)) = (
+            view_uniforms.uniforms.binding(),
+            views.get(extracted.view),
+            params.buffer.binding(),
+        ) else {
+            commands.entity(entity).remove::<PreparedJobViewParams<T>>();
+            continue;
+        };
+
+        let bind_group = render_device.create_bind_group(
+            "job_view_params_bind_group",
+            bind_groups.layout(&render_device),
+            &BindGroupEntries::sequential((view_binding, params_binding)),
+        );
+        commands.entity(entity).insert(PreparedJobViewParams::<T> {
+            bind_group,
+            view: extracted.view,
+            view_uniform_offset: view_uniform_offset.offset,
+            marker: PhantomData,
+        });
+    }
+}
+
+fn reset_job_view_params<T: ShaderType + WriteInto + Clone + Send + Sync + 'static>(
+    world: &mut World,
+) {
+    remove_all::<PreparedJobViewParams<T>>(world);
+    remove_all::<JobViewParamsBuffer<T>>(world);
+    world.insert_resource(JobViewParamsBindGroups::<T>::default());
+}
+
+#[cfg(test)]
+mod test {
+    use bevy_ecs::{component::Component, query::QueryState, world::World};
+    use bevy_math::Vec4;
+
+    use crate::{
+        input::{JobInput, JobInputItem, JobInputStatus},
+        GraphicsJob, JobError, JobRunContext,
+    };
+
+    use super::{ExtractedJobViewParams, JobViewParams};
+
+    #[derive(Clone, Component)]
+    struct TintJob;
+
+    impl GraphicsJob for TintJob {
+        type In = JobViewParams<Vec4>;
+
+        fn run(
+            &self,
+            _world: &World,
+            _context: &mut JobRunContext,
+            _input: JobInputItem<Self, Self::In>,
+        ) -> Result<(), JobError> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn waits_until_bind_group_is_prepared() {
+        let mut world = World::new();
+        let view = world.spawn_empty().id();
+        let job = world
+            .spawn(ExtractedJobViewParams {
+                view,
+                params: Vec4::ONE,
+            })
+            .id();
+
+        let mut query =
+            QueryState::<<JobViewParams<Vec4> as JobInput<TintJob>>::Data>::new(&mut world);
+        let data = query.get(&world, job).unwrap();
+        assert_eq!(
+            <JobViewParams<Vec4> as JobInput<TintJob>>::status(data, &world),
+            JobInputStatus::Wait
+        );
+    }
+}
@@ -0,0 +1,467 @@
+use core::marker::PhantomData;
+
+use bevy_app::{App, Plugin};
+use bevy_ecs::{
+    component::Component,
+    entity::Entity,
+    query::QueryItem,
+    schedule::IntoSystemConfigs,
+    system::{lifetimeless::Read, Query},
+    world::{EntityRef, World},
+};
+use bevy_render::{
+    render_resource::{
+        BlendComponent, BlendFactor, BlendOperation, BlendState, ColorTargetState, ColorWrites,
+        LoadOp, Operations, RenderPassColorAttachment, StoreOp, Texture, TextureFormat, TextureId,
+        TextureView,
+    },
+    view::ViewTarget,
+    ExtractSchedule, Render, RenderApp, RenderSet,
+};
+
+use crate::{GraphicsJob, JobSet};
+
+use super::{
+    camera_view::{extract_camera_views, CameraViewInput},
+    JobInput, JobInputStatus, JobOutputResource, JobRenderPipeline, SpecializedJobRenderPipeline,
+};
+
+/// A [`JobInput`] providing the [`ViewTarget`] of a camera, for example to draw
+/// into its main texture. The job waits until the camera's view target is prepared.
+///
+/// A view target's format depends on whether its camera is HDR, so a pipeline drawing
+/// into it must be specialized for the right format. To keep a job's
+/// [`JobRenderPipeline`] key in sync with the target's format, implement
+/// [`ViewTargetKey`] for the pipeline's key, and add [`JobViewTargetFormatPlugin`].
+///
+/// Post-process jobs can be chained through [`JobOutput`](super::JobOutput) and
+/// [`JobDependency`](super::JobDependency): the first job of the chain writes with
+/// [`post_process_write`](JobViewTargetItem::post_process_write) and publishes the texture
+/// it wrote, and each following job writes with
+/// [`post_process_write_after`](JobViewTargetItem::post_process_write_after) the output
+/// of the job before it. Bevy resets which of the view's main textures is current every
+/// frame, so the chain reads the side each job actually wrote, rather than the side
+/// bevy considers current.
+///
+/// Jobs that draw on top of the target rather than overwriting it, for example to
+/// accumulate glow additively, can declare how with a [`JobBlendMode`]. Its render pass
+/// is set up by [`JobViewTargetItem::color_attachment`], and a [`ViewTargetKey`] that
+/// stores the blend mode, like [`ViewTargetBlendKey`], has it kept in sync by
+/// [`JobViewTargetFormatPlugin`], so the pipeline's blend state can't drift from it.
+#[derive(Copy, Clone, Component, PartialEq, Eq, Debug)]
+pub struct JobViewTarget(pub Entity);
+
+impl JobViewTarget {
+    /// Targets the given main world camera.
+    pub const fn new(camera: Entity) -> Self {
+        Self(camera)
+    }
+}
+
+/// How a job with a [`JobViewTarget`] combines what it draws with the view's main
+/// texture. Jobs without one replace the texture's contents.
+#[derive(Copy, Clone, Component, Default, PartialEq, Eq, Hash, Debug)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub enum JobBlendMode {
+    /// Overwrites the target with what's drawn.
+    #[default]
+    Replace,
+    /// Adds what's drawn to the target, for accumulating light, like bloom or glow.
+    Additive,
+    /// Blends what's drawn over the target by its alpha.
+    AlphaBlend,
+}
+
+impl JobBlendMode {
+    /// The blend state for a pipeline drawing with this mode.
+    pub fn blend_state(self) -> Option<BlendState> {
+        match self {
+            JobBlendMode::Replace => None,
+            JobBlendMode::Additive => Some(BlendState {
+                color: BlendComponent {
+                    src_factor: BlendFactor::One,
+                    dst_factor: BlendFactor::One,
+                    operation: BlendOperation::Add,
+                },
+                alpha: BlendComponent {
+                    src_factor: BlendFactor::One,
+                    dst_factor: BlendFactor::One,
+                    operation: BlendOperation::Add,
+                },
+            }),
+            JobBlendMode::AlphaBlend => Some(BlendState::ALPHA_BLENDING),
+        }
+    }
+
+    /// The color target for a pipeline drawing into a texture of the given format
+    /// with this mode.
+    pub fn color_target(self, format: TextureFormat) -> ColorTargetState {
+        ColorTargetState {
+            format,
+            blend: self.blend_state(),
+            write_mask: ColorWrites::ALL,
+        }
+    }
+
+    /// The load op for a render pass drawing with this mode. The target is always
+    /// loaded, even when replacing it, since a job's draws may only cover part of it,
+    /// like a single viewport of a window shared by several cameras.
+    pub fn load_op<V>(self) -> LoadOp<V> {
+        LoadOp::Load
+    }
+}
+
+/// The render world view entity targeted by a job's [`JobViewTarget`], and the job's
+/// [`JobBlendMode`].
+#[derive(Copy, Clone, Component)]
+#[doc(hidden)]
+pub struct ExtractedJobViewTarget(pub Entity, pub JobBlendMode);
+
+impl<J: GraphicsJob> JobInput<J> for JobViewTarget {
+    type Data = Option<Read<ExtractedJobViewTarget>>;
+
+    type Item<'a> = JobViewTargetItem<'a>;
+
+    fn plugin() -> impl Plugin {
+        |app: &mut App| {
+            if !app.is_plugin_added::<JobViewTargetPlugin>() {
+                app.add_plugins(JobViewTargetPlugin);
+            }
+        }
+    }
+
+    fn status(data: QueryItem<Self::Data>, world: &World) -> JobInputStatus {
+        match data.and_then(|target| world.get::<ViewTarget>(target.0)) {
+            Some(_) => JobInputStatus::Ready,
+            None => JobInputStatus::Wait,
+        }
+    }
+
+    fn get<'a>(data: QueryItem<'a, Self::Data>, world: &'a World) -> Self::Item<'a> {
+        let view = world
+            .get_entity(data.expect("view target should be ready by this point").0)
+            .expect("view target should be ready by this point");
+        JobViewTargetItem {
+            target: view
+                .get::<ViewTarget>()
+                .expect("view target should be ready by this point"),
+            view,
+            blend_mode: data.expect("view target should be ready by this point").1,
+        }
+    }
+}
+
+/// The view target provided by [`JobViewTarget`].
+pub struct JobViewTargetItem<'a> {
+    /// The render world view entity, for looking up other view components.
+    pub view: EntityRef<'a>,
+    /// The view's target, including its main texture.
+    pub target: &'a ViewTarget,
+    /// How the job combines what it draws with the view's main texture.
+    pub blend_mode: JobBlendMode,
+}
+
+impl JobViewTargetItem<'_> {
+    /// Returns the format of the view's main texture, which differs between HDR and LDR views.
+    pub fn format(&self) -> TextureFormat {
+        self.target.main_texture_format()
+    }
+
+    /// A color attachment for drawing into the view's current main texture with the
+    /// job's [`JobBlendMode`]. The pipeline drawing into it should blend with the same
+    /// mode, see [`JobBlendMode::color_target`].
+    pub fn color_attachment(&self) -> RenderPassColorAttachment<'_> {
+        RenderPassColorAttachment {
+            view: self.target.main_texture_view(),
+            resolve_target: None,
+            ops: Operations {
+                load: self.blend_mode.load_op(),
+                store: StoreOp::Store,
+            },
+        }
+    }
+
+    /// Starts a post-process chain, reading the view's current main texture and writing
+    /// the other one. Like bevy's own post-process passes, this makes the written texture
+    /// current, so the camera renders on top of it this frame.
+    pub fn post_process_write(&self) -> JobPostProcessWrite<'_> {
+        let write = self.target.post_process_write();
+        JobPostProcessWrite {
+            source: write.source,
+            destination: write.destination,
+            destination_texture: self.target.main_texture(),
+        }
+    }
+
+    /// Continues a post-process chain from the texture published by the previous job,
+    /// reading the main texture it wrote and writing the other one, which is made current.
+    ///
+    /// Returns `None` if `previous` isn't one of the view's main textures, for example if
+    /// they were reallocated after the window was resized.
+    pub fn post_process_write_after(
+        &self,
+        previous: &JobOutputResource,
+    ) -> Option<JobPostProcessWrite<'_>> {
+        let previous = previous.texture()?;
+        match chain_source(
+            previous.id(),
+            self.target.main_texture().id(),
+            self.target.main_texture_other().id(),
+        )? {
+            ChainSource::Main => Some(self.post_process_write()),
+            // the current main texture is already the one to write, so it isn't flipped
+            ChainSource::Other => Some(JobPostProcessWrite {
+                source: self.target.main_texture_other_view(),
+                destination: self.target.main_texture_view(),
+                destination_texture: self.target.main_texture(),
+            }),
+        }
+    }
+}
+
+/// A write into one of a view's main textures, reading from the other one. See
+/// [`JobViewTargetItem::post_process_write`].
+pub struct JobPostProcessWrite<'a> {
+    pub source: &'a TextureView,
+    pub destination: &'a TextureView,
+    /// The texture of `destination`, to publish for the next job of the chain.
+    pub destination_texture: &'a Texture,
+}
+
+/// Which of a view's main textures the previous job of a post-process chain wrote.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+enum ChainSource {
+    Main,
+    Other,
+}
+
+fn chain_source(previous: TextureId, main: TextureId, other: TextureId) -> Option<ChainSource> {
+    if previous == main {
+        Some(ChainSource::Main)
+    } else if previous == other {
+        Some(ChainSource::Other)
+    } else {
+        None
+    }
+}
+
+struct JobViewTargetPlugin;
+
+impl Plugin for JobViewTargetPlugin {
+    fn build(&self, app: &mut App) {
+        if let Some(render_app) = app.get_sub_app_mut(RenderApp) {
+            render_app.add_systems(ExtractSchedule, extract_camera_views::<JobViewTarget>);
+        }
+    }
+}
+
+impl CameraViewInput for JobViewTarget {
+    type Extracted = ExtractedJobViewTarget;
+
+    type Data = Option<Read<JobBlendMode>>;
+
+    fn camera(&self) -> Entity {
+        self.0
+    }
+
+    fn extract(&self, view: Entity, blend_mode: Option<&JobBlendMode>) -> Self::Extracted {
+        ExtractedJobViewTarget(view, blend_mode.copied().unwrap_or_default())
+    }
+}
+
+/// A specialization key for a pipeline that draws into a view's main texture,
+/// which is kept in sync with the view's format by [`JobViewTargetFormatPlugin`].
+///
+/// Keys that also store the job's [`JobBlendMode`] override
+/// [`blend_mode`](ViewTargetKey::blend_mode) and
+/// [`set_blend_mode`](ViewTargetKey::set_blend_mode) to have it kept in sync too.
+pub trait ViewTargetKey {
+    fn view_format(&self) -> TextureFormat;
+
+    fn set_view_format(&mut self, format: TextureFormat);
+
+    /// The blend mode the key was specialized for, or `None` if it doesn't store one.
+    fn blend_mode(&self) -> Option<JobBlendMode> {
+        None
+    }
+
+    fn set_blend_mode(&mut self, _blend_mode: JobBlendMode) {}
+}
+
+impl ViewTargetKey for TextureFormat {
+    fn view_format(&self) -> TextureFormat {
+        *self
+    }
+
+    fn set_view_format(&mut self, format: TextureFormat) {
+        *self = format;
+    }
+}
+
+/// A [`ViewTargetKey`] storing both the view's format and the job's [`JobBlendMode`],
+/// for pipelines that only need to be specialized for those.
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+pub struct ViewTargetBlendKey {
+    pub format: TextureFormat,
+    pub blend_mode: JobBlendMode,
+}
+
+impl ViewTargetBlendKey {
+    /// A key for the given format, with the blend mode left as a placeholder.
+    pub fn new(format: TextureFormat) -> Self {
+        Self {
+            format,
+            blend_mode: JobBlendMode::default(),
+        }
+    }
+
+    /// The color target for this key's format and blend mode.
+    pub fn color_target(&self) -> ColorTargetState {
+        self.blend_mode.color_target(self.format)
+    }
+}
+
+impl ViewTargetKey for ViewTargetBlendKey {
+    fn view_format(&self) -> TextureFormat {
+        self.format
+    }
+
+    fn set_view_format(&mut self, format: TextureFormat) {
+        self.format = format;
+    }
+
+    fn blend_mode(&self) -> Option<JobBlendMode> {
+        Some(self.blend_mode)
+    }
+
+    fn set_blend_mode(&mut self, blend_mode: JobBlendMode) {
+        self.blend_mode = blend_mode;
+    }
+}
+
+/// Updates the [`JobRenderPipeline<P>`] key of every job with a [`JobViewTarget`]
+/// to match the format of the targeted view, and the job's [`JobBlendMode`] if the key
+/// stores one, before the pipeline is specialized. The key a job is spawned with is
+/// only a placeholder for both.
+pub struct JobViewTargetFormatPlugin<P>(PhantomData<P>);
+
+impl<P> Default for JobViewTargetFormatPlugin<P> {
+    fn default() -> Self {
+        Self(PhantomData)
+    }
+}
+
+impl<P: SpecializedJobRenderPipeline<Key: ViewTargetKey>> Plugin for JobViewTargetFormatPlugin<P> {
+    fn build(&self, app: &mut App) {
+        if let Some(render_app) = app.get_sub_app_mut(RenderApp) {
+            render_app.add_systems(
+                Render,
+                specialize_view_target_jobs::<P>
+                    .in_set(RenderSet::Queue)
+                    .before(JobSet::QueuePipelines),
+            );
+        }
+    }
+}
+
+fn specialize_view_target_jobs<P: SpecializedJobRenderPipeline<Key: ViewTargetKey>>(
+    mut jobs: Query<(&ExtractedJobViewTarget, &mut JobRenderPipeline<P>)>,
+    views: Query<&ViewTarget>,
+) {
+    for (target, mut pipeline) in &mut jobs {
+        let Ok(view_target) = views.get(target.0) else {
+            continue;
+        };
+
+        let format = view_target.main_texture_format();
+        if pipeline.0.view_format() != format {
+            pipeline.0.set_view_format(format);
+        }
+        if pipeline.0.blend_mode().is_some_and(|mode| mode != target.1) {
+            pipeline.0.set_blend_mode(target.1);
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use bevy_ecs::{component::Component, world::World};
+    use bevy_render::render_resource::{BlendState, TextureFormat, TextureId};
+
+    use crate::{
+        input::{JobInput, JobInputItem, JobInputStatus},
+        GraphicsJob, JobError, JobRunContext,
+    };
+
+    use super::{
+        chain_source, ChainSource, ExtractedJobViewTarget, JobBlendMode, JobViewTarget,
+        ViewTargetBlendKey, ViewTargetKey,
+    };
+
+    #[derive(Clone, Component)]
+    struct TargetJob;
+
+    impl GraphicsJob for TargetJob {
+        type In = JobViewTarget;
+
+        fn run(
+            &self,
+            _world: &World,
+            _context: &mut JobRunContext,
+            _input: JobInputItem<Self, Self::In>,
+        ) -> Result<(), JobError> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn waits_for_prepared_view_target() {
+        let mut world = World::new();
+        let status = <JobViewTarget as JobInput<TargetJob>>::status;
+        assert_eq!(status(None, &world), JobInputStatus::Wait);
+
+        let view = world.spawn_empty().id();
+        let target = ExtractedJobViewTarget(view, JobBlendMode::Replace);
+        assert_eq!(status(Some(&target), &world), JobInputStatus::Wait);
+    }
+
+    #[test]
+    fn chains_read_the_side_written_last() {
+        let (main, other) = (TextureId::new(), TextureId::new());
+        assert_eq!(chain_source(main, main, other), Some(ChainSource::Main));
+        assert_eq!(chain_source(other, main, other), Some(ChainSource::Other));
+        // the view's textures were reallocated since the previous job wrote
+        assert_eq!(chain_source(TextureId::new(), main, other), None);
+    }
+
+    #[test]
+    fn blend_modes_map_to_blend_states() {
+        assert_eq!(JobBlendMode::Replace.blend_state(), None);
+        assert_eq!(
+            JobBlendMode::AlphaBlend.blend_state(),
+            Some(BlendState::ALPHA_BLENDING)
+        );
+
+        // additive blending keeps what's already in the target, whatever the source's alpha
+        let additive = JobBlendMode::Additive.blend_state().unwrap();
+        assert_eq!(additive.color.src_factor, additive.color.dst_factor);
+        assert_ne!(Some(additive), JobBlendMode::AlphaBlend.blend_state());
+    }
+
+    #[test]
+    fn only_blend_keys_store_the_blend_mode() {
+        let mut format = TextureFormat::Rgba8Unorm;
+        format.set_blend_mode(JobBlendMode::Additive);
+        assert_eq!(format.blend_mode(), None);
+
+        let mut key = ViewTargetBlendKey::new(TextureFormat::Rgba8Unorm);
+        assert_eq!(key.blend_mode(), Some(JobBlendMode::Replace));
+        key.set_blend_mode(JobBlendMode::Additive);
+        key.set_view_format(TextureFormat::Rgba16Float);
+        assert_eq!(
+            key.color_target().blend,
+            JobBlendMode::Additive.blend_state()
+        );
+        assert_eq!(key.color_target().format, TextureFormat::Rgba16Float);
+    }
+}
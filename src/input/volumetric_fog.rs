@@ -0,0 +1,258 @@
+use bevy_app::{App, Plugin};
+use bevy_ecs::{
+    component::Component,
+    entity::Entity,
+    query::{QueryItem, With},
+    schedule::IntoSystemConfigs,
+    system::{lifetimeless::Read, Query, ResMut, Resource},
+    world::{EntityRef, World},
+};
+use bevy_math::{Mat4, UVec3, Vec4};
+use bevy_pbr::{FogVolume, VolumetricFog};
+use bevy_render::{
+    render_asset::RenderAssets, texture::GpuImage, ExtractSchedule, Render, RenderApp, RenderSet,
+};
+use bevy_transform::components::GlobalTransform;
+
+use crate::GraphicsJob;
+
+use super::{
+    camera_view::{extract_camera_views, view_feature_status, CameraViewInput},
+    JobInput, JobInputStatus,
+};
+
+/// A [`JobInput`] providing the fog volumes raymarched by a camera with bevy's
+/// [`VolumetricFog`], for jobs that compute or sample participating media, like
+/// injecting density into a volume's 3D density texture.
+///
+/// The camera must be a `Camera3d` with [`VolumetricFog`], and bevy only renders
+/// volumetric fog while at least one light has a `VolumetricLight`. The job waits until
+/// the density texture of every volume is prepared, and fails if the camera doesn't
+/// render volumetric fog.
+///
+/// Bevy raymarches each volume directly, sampling its density texture, rather than
+/// through a froxel grid, so the density textures are the only volumes to write into.
+/// Jobs execute before the render graph, so density written by a job is sampled by
+/// bevy's fog pass later that frame. Density textures written by jobs need
+/// `TextureUsages::STORAGE_BINDING`.
+#[derive(Copy, Clone, Component, PartialEq, Eq, Debug)]
+pub struct JobVolumetricFog(pub Entity);
+
+impl JobVolumetricFog {
+    /// Targets the given main world camera.
+    pub const fn new(camera: Entity) -> Self {
+        Self(camera)
+    }
+}
+
+/// The render world view entity targeted by a job's [`JobVolumetricFog`].
+#[derive(Copy, Clone, Component)]
+#[doc(hidden)]
+pub struct ExtractedJobVolumetricFog(pub Entity);
+
+impl<J: GraphicsJob> JobInput<J> for JobVolumetricFog {
+    type Data = Option<Read<ExtractedJobVolumetricFog>>;
+
+    type Item<'a> = JobVolumetricFogItem<'a>;
+
+    fn plugin() -> impl Plugin {
+        |app: &mut App| {
+            if !app.is_plugin_added::<JobVolumetricFogPlugin>() {
+                app.add_plugins(JobVolumetricFogPlugin);
+            }
+        }
+    }
+
+    fn status(data: QueryItem<Self::Data>, world: &World) -> JobInputStatus {
+        view_feature_status::<VolumetricFog>(data.map(|view| view.0), world, |view| {
+            JobVolumetricFogItem::new(view, world).is_some()
+        })
+    }
+
+    fn get<'a>(data: QueryItem<'a, Self::Data>, world: &'a World) -> Self::Item<'a> {
+        let view = world
+            .get_entity(
+                data.expect("volumetric fog should be ready by this point")
+                    .0,
+            )
+            .expect("volumetric fog should be ready by this point");
+        JobVolumetricFogItem::new(view, world)
+            .expect("volumetric fog should be ready by this point")
+    }
+}
+
+/// The volumetric fog provided by [`JobVolumetricFog`].
+pub struct JobVolumetricFogItem<'a> {
+    /// The render world view entity, for looking up other view components.
+    pub view: EntityRef<'a>,
+    /// The camera's fog settings.
+    pub settings: &'a VolumetricFog,
+    /// Every fog volume in the scene, which bevy raymarches for each view with fog.
+    pub volumes: Vec<JobFogVolume<'a>>,
+}
+
+/// A fog volume raymarched by a view, provided by [`JobVolumetricFog`].
+pub struct JobFogVolume<'a> {
+    /// The render world entity of the volume.
+    pub entity: EntityRef<'a>,
+    /// The volume's fog parameters.
+    pub fog_volume: &'a FogVolume,
+    /// Transforms from world space to the volume's density texture coordinates, which
+    /// span `[0, 1]` on each axis of the volume, before its `density_texture_offset`.
+    pub uvw_from_world: Mat4,
+    /// The volume's density texture, if it has one.
+    pub density: Option<&'a GpuImage>,
+    /// The dimensions of the density texture's grid of voxels, or zero without one.
+    pub density_size: UVec3,
+}
+
+impl<'a> JobVolumetricFogItem<'a> {
+    fn new(view: EntityRef<'a>, world: &'a World) -> Option<Self> {
+        let settings = view.get::<VolumetricFog>()?;
+        let images = world.get_resource::<RenderAssets<GpuImage>>();
+        let volumes = world
+            .get_resource::<JobFogVolumes>()
+            .map_or(&[][..], |volumes| &volumes.0)
+            .iter()
+            .filter_map(|entity| world.get_entity(*entity).ok())
+            .filter_map(|entity| Some((entity, entity.get::<FogVolume>()?)))
+            .map(|(entity, fog_volume)| {
+                let density = match &fog_volume.density_texture {
+                    Some(image) => Some(images?.get(image)?),
+                    None => None,
+                };
+                let world_from_local = entity
+                    .get::<GlobalTransform>()
+                    .map_or(Mat4::IDENTITY, GlobalTransform::compute_matrix);
+                Some(JobFogVolume {
+                    entity,
+                    fog_volume,
+                    uvw_from_world: uvw_from_local() * world_from_local.inverse(),
+                    density,
+                    density_size: density.map_or(UVec3::ZERO, |image| {
+                        let size = image.texture.size();
+                        UVec3::new(size.width, size.height, size.depth_or_array_layers)
+                    }),
+                })
+            })
+            .collect::<Option<Vec<_>>>()?;
+
+        Some(Self {
+            view,
+            settings,
+            volumes,
+        })
+    }
+}
+
+/// Maps a fog volume's local space, a unit cube centered on the origin, to its density
+/// texture coordinates, the same way as bevy's fog pass.
+fn uvw_from_local() -> Mat4 {
+    Mat4::from_cols(Vec4::X, Vec4::Y, Vec4::Z, Vec4::new(0.5, 0.5, 0.5, 1.0))
+}
+
+/// The render world entities of every [`FogVolume`], collected each frame.
+#[derive(Resource, Default)]
+struct JobFogVolumes(Vec<Entity>);
+
+struct JobVolumetricFogPlugin;
+
+impl Plugin for JobVolumetricFogPlugin {
+    fn build(&self, app: &mut App) {
+        if let Some(render_app) = app.get_sub_app_mut(RenderApp) {
+            render_app
+                .init_resource::<JobFogVolumes>()
+                .add_systems(ExtractSchedule, extract_camera_views::<JobVolumetricFog>)
+                .add_systems(
+                    Render,
+                    collect_job_fog_volumes.in_set(RenderSet::PrepareResources),
+                );
+        }
+    }
+}
+
+impl CameraViewInput for JobVolumetricFog {
+    type Extracted = ExtractedJobVolumetricFog;
+
+    type Data = ();
+
+    fn camera(&self) -> Entity {
+        self.0
+    }
+
+    fn extract(&self, view: Entity, (): ()) -> Self::Extracted {
+        ExtractedJobVolumetricFog(view)
+    }
+}
+
+fn collect_job_fog_volumes(
+    fog_volumes: Query<Entity, With<FogVolume>>,
+    mut volumes: ResMut<JobFogVolumes>,
+) {
+    volumes.0.clear();
+    volumes.0.extend(&fog_volumes);
+}
+
+#[cfg(test)]
+mod test {
+    use bevy::transform::components::Transform;
+    use bevy_ecs::{component::Component, world::World};
+    use bevy_math::Vec3;
+    use bevy_pbr::VolumetricFog;
+
+    use crate::{
+        input::{
+            camera_view::{assert_waits_for_view, test_view},
+            JobInput, JobInputItem, JobInputStatus,
+        },
+        GraphicsJob, JobError, JobRunContext,
+    };
+
+    use super::{uvw_from_local, ExtractedJobVolumetricFog, JobVolumetricFog};
+
+    #[derive(Clone, Component)]
+    struct InjectDensityJob;
+
+    impl GraphicsJob for InjectDensityJob {
+        type In = JobVolumetricFog;
+
+        fn run(
+            &self,
+            _world: &World,
+            _context: &mut JobRunContext,
+            _input: JobInputItem<Self, Self::In>,
+        ) -> Result<(), JobError> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn views_without_fog_fail() {
+        let mut world = World::new();
+        let status = <JobVolumetricFog as JobInput<InjectDensityJob>>::status;
+        let (camera, fog) = assert_waits_for_view(&mut world, status, ExtractedJobVolumetricFog);
+
+        world.entity_mut(camera).insert(test_view(false));
+        assert_eq!(status(Some(&fog), &world), JobInputStatus::Fail);
+
+        // without any fog volumes, there's nothing to wait for
+        world.entity_mut(camera).insert(VolumetricFog::default());
+        assert_eq!(status(Some(&fog), &world), JobInputStatus::Ready);
+    }
+
+    #[test]
+    fn volume_corners_map_to_texture_corners() {
+        let world_from_local = Transform::from_xyz(2.0, 0.0, 0.0)
+            .with_scale(Vec3::splat(4.0))
+            .compute_matrix();
+        let uvw_from_world = uvw_from_local() * world_from_local.inverse();
+        assert_eq!(
+            uvw_from_world.transform_point3(Vec3::new(0.0, -2.0, -2.0)),
+            Vec3::ZERO
+        );
+        assert_eq!(
+            uvw_from_world.transform_point3(Vec3::new(4.0, 2.0, 2.0)),
+            Vec3::ONE
+        );
+    }
+}
@@ -0,0 +1,182 @@
+use core::fmt::Display;
+
+use bevy_utils::tracing::error;
+use crossbeam_channel::Sender;
+
+use crate::JobError;
+
+/// Describes what happens when one of the crate's internal invariants doesn't hold while
+/// jobs run, for example if a pipeline that an input reported as ready is gone by the
+/// time the job reads it.
+///
+/// This only covers the crate's own checks. Panics in a job's own code, like in
+/// [`GraphicsJob::run`](crate::GraphicsJob::run), aren't caught either way.
+#[derive(Copy, Clone, Default, PartialEq, Eq, Debug)]
+pub enum InvariantViolation {
+    /// Panics, so that the bug surfaces right away.
+    #[default]
+    Panic,
+    /// Logs an error and fails the offending job with [`JobError::InvariantViolated`],
+    /// and keeps running other jobs.
+    LogAndRecover,
+}
+
+impl InvariantViolation {
+    /// Handles a violated invariant, by panicking with `message`, or by logging it so
+    /// that the caller can recover.
+    pub(crate) fn report(self, message: impl Display) {
+        match self {
+            Self::Panic => panic!("{message}"),
+            Self::LogAndRecover => error!("{message}"),
+        }
+    }
+
+    /// Handles a violated invariant of a job, returning the error to fail it with if
+    /// it's recovered from.
+    pub(crate) fn fail_job(self, job: &str, message: &str) -> JobError {
+        self.report(format_args!(
+            "job {job} failed, since an invariant was violated: {message}"
+        ));
+        JobError::InvariantViolated
+    }
+
+    /// Sends over one of the crate's channels, whose receivers should outlive every job.
+    pub(crate) fn send<T>(self, sender: &Sender<T>, message: T) {
+        if sender.send(message).is_err() {
+            self.report("a graphics jobs channel was closed while jobs were running");
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use core::sync::atomic::{AtomicBool, Ordering};
+
+    use bevy_app::App;
+    use bevy_ecs::{
+        component::Component,
+        query::QueryItem,
+        schedule::IntoSystemConfigs,
+        system::{ResMut, Resource},
+        world::World,
+    };
+    use bevy_render::{Render, RenderApp};
+
+    use crate::{
+        ext::{headless_test_app, InitGraphicsJobExt, RunGraphicsJobExt},
+        input::{JobInput, JobInputItem, JobInputStatus},
+        GraphicsJob, JobError, JobExecutionSettings, JobRunContext, JobSet,
+    };
+
+    use super::InvariantViolation;
+
+    /// Whether the pipeline of [`RacingPipeline`] is still around, and whether a job
+    /// was checked while it was.
+    #[derive(Resource, Default)]
+    struct PipelineState {
+        lost: bool,
+        checked: AtomicBool,
+    }
+
+    /// An input whose pipeline is ready when its status is checked, but is gone by
+    /// the time the job runs.
+    struct RacingPipeline;
+
+    impl<J: GraphicsJob> JobInput<J> for RacingPipeline {
+        type Data = ();
+
+        type Item<'a> = ();
+
+        fn status((): QueryItem<Self::Data>, world: &World) -> JobInputStatus {
+            let pipeline = world.resource::<PipelineState>();
+            if pipeline.lost {
+                return JobInputStatus::Wait;
+            }
+            pipeline.checked.store(true, Ordering::Relaxed);
+            JobInputStatus::Ready
+        }
+
+        fn get<'a>((): QueryItem<'a, Self::Data>, _world: &'a World) -> Self::Item<'a> {
+            panic!("pipeline should be ready by this point");
+        }
+    }
+
+    #[derive(Clone, Component)]
+    struct RacingJob;
+
+    impl GraphicsJob for RacingJob {
+        type In = RacingPipeline;
+
+        fn run(
+            &self,
+            _world: &World,
+            _context: &mut JobRunContext,
+            (): JobInputItem<Self, Self::In>,
+        ) -> Result<(), JobError> {
+            Ok(())
+        }
+    }
+
+    fn lose_pipeline(mut pipeline: ResMut<PipelineState>) {
+        pipeline.lost = *pipeline.checked.get_mut();
+    }
+
+    #[derive(Clone, Component)]
+    struct PanickingJob;
+
+    impl GraphicsJob for PanickingJob {
+        type In = ();
+
+        fn run(
+            &self,
+            _world: &World,
+            _context: &mut JobRunContext,
+            (): JobInputItem<Self, Self::In>,
+        ) -> Result<(), JobError> {
+            panic!("the job's own code panicked");
+        }
+    }
+
+    /// An app running jobs with the given policy, in which the pipeline of every
+    /// [`RacingJob`] is lost between checking and running it. Like other tests
+    /// running jobs on the real renderer, this needs a GPU adapter.
+    fn racing_app(policy: InvariantViolation) -> App {
+        let mut app = headless_test_app();
+        app.insert_resource(JobExecutionSettings {
+            on_invariant_violation: policy,
+            ..Default::default()
+        })
+        .init_graphics_job::<RacingJob>()
+        .init_graphics_job::<PanickingJob>();
+
+        let render_app = app.sub_app_mut(RenderApp);
+        render_app.init_resource::<PipelineState>().add_systems(
+            Render,
+            lose_pipeline.after(JobSet::Check).before(JobSet::Execute),
+        );
+        app
+    }
+
+    #[test]
+    fn recovering_fails_the_job() {
+        let mut app = racing_app(InvariantViolation::LogAndRecover);
+        assert_eq!(
+            app.run_job_blocking(RacingJob),
+            Err(JobError::InvariantViolated)
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "an invariant was violated")]
+    fn panicking_propagates() {
+        let mut app = racing_app(InvariantViolation::Panic);
+        let _ = app.run_job_blocking(RacingJob);
+    }
+
+    #[test]
+    #[should_panic(expected = "the job's own code panicked")]
+    fn job_panics_propagate_while_recovering() {
+        let mut app = racing_app(InvariantViolation::LogAndRecover);
+        let _ = app.run_job_blocking(PanickingJob);
+    }
+}
@@ -0,0 +1,83 @@
+use bevy_asset::Handle;
+use bevy_ecs::world::World;
+use bevy_image::Image;
+use bevy_render::{
+    render_asset::RenderAssets,
+    render_resource::{Buffer, Texture},
+    storage::{GpuShaderStorageBuffer, ShaderStorageBuffer},
+    texture::GpuImage,
+};
+
+mod clear;
+mod mipmaps;
+
+pub use clear::*;
+pub use mipmaps::*;
+
+/// A texture targeted by one of the built-in graphics jobs, either
+/// an [`Image`] asset or a texture created manually in the render world.
+#[derive(Clone)]
+pub enum JobTexture {
+    Image(Handle<Image>),
+    Texture(Texture),
+}
+
+impl JobTexture {
+    /// Returns the target texture, or `None` if the image asset
+    /// hasn't been prepared yet.
+    pub fn get<'a>(&'a self, world: &'a World) -> Option<&'a Texture> {
+        match self {
+            JobTexture::Image(handle) => world
+                .resource::<RenderAssets<GpuImage>>()
+                .get(handle)
+                .map(|image| &image.texture),
+            JobTexture::Texture(texture) => Some(texture),
+        }
+    }
+}
+
+impl From<Handle<Image>> for JobTexture {
+    fn from(handle: Handle<Image>) -> Self {
+        Self::Image(handle)
+    }
+}
+
+impl From<Texture> for JobTexture {
+    fn from(texture: Texture) -> Self {
+        Self::Texture(texture)
+    }
+}
+
+/// A buffer targeted by one of the built-in graphics jobs, either a
+/// [`ShaderStorageBuffer`] asset or a buffer created manually in the render world.
+#[derive(Clone)]
+pub enum JobBuffer {
+    Storage(Handle<ShaderStorageBuffer>),
+    Buffer(Buffer),
+}
+
+impl JobBuffer {
+    /// Returns the target buffer, or `None` if the storage buffer
+    /// asset hasn't been prepared yet.
+    pub fn get<'a>(&'a self, world: &'a World) -> Option<&'a Buffer> {
+        match self {
+            JobBuffer::Storage(handle) => world
+                .resource::<RenderAssets<GpuShaderStorageBuffer>>()
+                .get(handle)
+                .map(|buffer| &buffer.buffer),
+            JobBuffer::Buffer(buffer) => Some(buffer),
+        }
+    }
+}
+
+impl From<Handle<ShaderStorageBuffer>> for JobBuffer {
+    fn from(handle: Handle<ShaderStorageBuffer>) -> Self {
+        Self::Storage(handle)
+    }
+}
+
+impl From<Buffer> for JobBuffer {
+    fn from(buffer: Buffer) -> Self {
+        Self::Buffer(buffer)
+    }
+}
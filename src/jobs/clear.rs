@@ -0,0 +1,591 @@
+use core::ops::Range;
+
+use bevy_ecs::{component::Component, query::QueryItem, system::lifetimeless::Read, world::World};
+use bevy_render::{
+    render_resource::{
+        Buffer, BufferInitDescriptor, BufferUsages, Extent3d, ImageCopyBuffer, ImageCopyTexture,
+        ImageDataLayout, Origin3d, Texture, TextureAspect, TextureDimension, TextureFormat,
+        COPY_BUFFER_ALIGNMENT,
+    },
+    renderer::RenderDevice,
+};
+
+use crate::{
+    input::{JobInput, JobInputItem, JobInputStatus},
+    GraphicsJob, JobError, JobRunContext,
+};
+
+use super::{JobBuffer, JobTexture};
+
+/// A built-in graphics job that fills a range of a buffer with a repeated value.
+///
+/// The buffer must have been created with [`BufferUsages::COPY_DST`]. Clearing to
+/// zero uses [`CommandEncoder::clear_buffer`](bevy_render::render_resource::CommandEncoder::clear_buffer), while other values are copied in
+/// from a temporary buffer.
+///
+/// This job is initialized by [`GraphicsJobsPlugin`](crate::GraphicsJobsPlugin),
+/// so it can be spawned right away.
+#[derive(Clone, Component)]
+pub struct ClearBufferJob {
+    pub target: JobBuffer,
+    /// The value written to each 4-byte word of the cleared range.
+    pub value: u32,
+    /// The byte offset of the cleared range. Must be a multiple of 4.
+    pub offset: u64,
+    /// The size in bytes of the cleared range, or `None` to clear to the end
+    /// of the buffer. Must be a multiple of 4.
+    pub size: Option<u64>,
+}
+
+impl ClearBufferJob {
+    /// Creates a job that clears the whole target buffer to zero.
+    pub fn new(target: impl Into<JobBuffer>) -> Self {
+        Self {
+            target: target.into(),
+            value: 0,
+            offset: 0,
+            size: None,
+        }
+    }
+
+    /// Sets the value written to each 4-byte word of the cleared range.
+    pub fn with_value(mut self, value: u32) -> Self {
+        self.value = value;
+        self
+    }
+
+    /// Restricts the job to clearing `size` bytes starting at `offset`.
+    pub fn with_range(mut self, offset: u64, size: Option<u64>) -> Self {
+        self.offset = offset;
+        self.size = size;
+        self
+    }
+}
+
+impl GraphicsJob for ClearBufferJob {
+    type In = ClearBufferTarget;
+
+    fn run(
+        &self,
+        _world: &World,
+        context: &mut JobRunContext,
+        buffer: JobInputItem<Self, Self::In>,
+    ) -> Result<(), JobError> {
+        let render_device = context.render_device();
+        let Some(size) = clear_buffer_size(buffer.size(), self.offset, self.size) else {
+            return Err(JobError::ExecutionFailed);
+        };
+
+        if size == 0 {
+            return Ok(());
+        }
+
+        if self.value == 0 {
+            context.clear_buffer(buffer, self.offset, Some(size));
+        } else {
+            let source = render_device.create_buffer_with_data(&BufferInitDescriptor {
+                label: Some("clear_buffer_source"),
+                contents: &fill_bytes(&self.value.to_le_bytes(), size as usize),
+                usage: BufferUsages::COPY_SRC,
+            });
+            context.copy_buffer_to_buffer(&source, 0, buffer, self.offset, size);
+        }
+
+        Ok(())
+    }
+}
+
+/// The [`JobInput`] for [`ClearBufferJob`], which waits for the target buffer to be prepared.
+pub struct ClearBufferTarget;
+
+impl JobInput<ClearBufferJob> for ClearBufferTarget {
+    type Data = Read<ClearBufferJob>;
+
+    type Item<'a> = &'a Buffer;
+
+    fn status(data: QueryItem<Self::Data>, world: &World) -> JobInputStatus {
+        match data.target.get(world) {
+            Some(_) => JobInputStatus::Ready,
+            None => JobInputStatus::Wait,
+        }
+    }
+
+    fn get<'a>(data: QueryItem<'a, Self::Data>, world: &'a World) -> Self::Item<'a> {
+        data.target
+            .get(world)
+            .expect("buffer should be ready by this point")
+    }
+}
+
+/// A built-in graphics job that fills a region of a texture with a single texel value.
+///
+/// The texture must have been created with [`TextureUsages::COPY_DST`](bevy_render::render_resource::TextureUsages::COPY_DST),
+/// and must have a color format. The cleared region is given in texels of mip
+/// level `0`, and is scaled down for each cleared mip level. For block-compressed
+/// formats, the value is a single block, and the region should be aligned to the
+/// format's block size on every cleared mip level.
+///
+/// This job is initialized by [`GraphicsJobsPlugin`](crate::GraphicsJobsPlugin),
+/// so it can be spawned right away.
+#[derive(Clone, Component)]
+pub struct ClearTextureJob {
+    pub target: JobTexture,
+    /// The bytes of a single texel in the texture's format, for example
+    /// `[255, 0, 0, 255]` for opaque red in [`TextureFormat::Rgba8Unorm`].
+    /// If empty, the texture is cleared to zero.
+    pub value: Vec<u8>,
+    /// The origin of the cleared region. The `z` coordinate only applies
+    /// to 3D textures.
+    pub origin: Origin3d,
+    /// The size of the cleared region, or `None` to clear to the edges of
+    /// the texture. The depth only applies to 3D textures.
+    pub size: Option<Extent3d>,
+    pub base_mip_level: u32,
+    /// The number of mip levels to clear, or `None` to clear all remaining levels.
+    pub mip_level_count: Option<u32>,
+    pub base_array_layer: u32,
+    /// The number of array layers to clear, or `None` to clear all remaining layers.
+    pub array_layer_count: Option<u32>,
+}
+
+impl ClearTextureJob {
+    /// Creates a job that clears every mip level and array layer of the target
+    /// texture to zero.
+    pub fn new(target: impl Into<JobTexture>) -> Self {
+        Self {
+            target: target.into(),
+            value: Vec::new(),
+            origin: Origin3d::ZERO,
+            size: None,
+            base_mip_level: 0,
+            mip_level_count: None,
+            base_array_layer: 0,
+            array_layer_count: None,
+        }
+    }
+
+    /// Sets the bytes of the texel written to the cleared region.
+    pub fn with_value(mut self, value: impl Into<Vec<u8>>) -> Self {
+        self.value = value.into();
+        self
+    }
+
+    /// Restricts the job to clearing a region of the texture.
+    pub fn with_region(mut self, origin: Origin3d, size: Extent3d) -> Self {
+        self.origin = origin;
+        self.size = Some(size);
+        self
+    }
+
+    /// Restricts the job to clearing a range of mip levels.
+    pub fn with_mip_levels(mut self, base: u32, count: Option<u32>) -> Self {
+        self.base_mip_level = base;
+        self.mip_level_count = count;
+        self
+    }
+
+    /// Restricts the job to clearing a range of array layers.
+    pub fn with_array_layers(mut self, base: u32, count: Option<u32>) -> Self {
+        self.base_array_layer = base;
+        self.array_layer_count = count;
+        self
+    }
+}
+
+impl GraphicsJob for ClearTextureJob {
+    type In = ClearTextureTarget;
+
+    fn run(
+        &self,
+        _world: &World,
+        context: &mut JobRunContext,
+        texture: JobInputItem<Self, Self::In>,
+    ) -> Result<(), JobError> {
+        let render_device = context.render_device();
+        let format = texture.format();
+        let dimension = texture.dimension();
+
+        // depth-stencil formats don't have a single copyable aspect
+        let Some(block_size) = format.block_copy_size(None) else {
+            return Err(JobError::ExecutionFailed);
+        };
+
+        let texel = match self.value.is_empty() {
+            true => vec![0; block_size as usize],
+            false => self.value.clone(),
+        };
+        if texel.len() != block_size as usize {
+            return Err(JobError::ExecutionFailed);
+        }
+
+        let layer_count = match dimension {
+            TextureDimension::D3 => 1,
+            _ => texture.depth_or_array_layers(),
+        };
+        let (Some(mip_levels), Some(array_layers)) = (
+            subresource_range(
+                self.base_mip_level,
+                self.mip_level_count,
+                texture.mip_level_count(),
+            ),
+            subresource_range(self.base_array_layer, self.array_layer_count, layer_count),
+        ) else {
+            return Err(JobError::ExecutionFailed);
+        };
+
+        let regions = mip_levels
+            .filter_map(|mip| {
+                mip_region(
+                    texture.size(),
+                    self.origin,
+                    self.size,
+                    mip,
+                    dimension,
+                    format,
+                )
+            })
+            .collect::<Vec<_>>();
+
+        // the region on the first mip level is the largest, so a source buffer
+        // laid out for it can be reused for every other level.
+        let Some((_, _, largest)) = regions.first() else {
+            return Ok(());
+        };
+        let (block_width, block_height) = format.block_dimensions();
+        let bytes_per_row = RenderDevice::align_copy_bytes_per_row(
+            (largest.width / block_width * block_size) as usize,
+        ) as u32;
+        let rows_per_image = largest.height / block_height;
+
+        let source = render_device.create_buffer_with_data(&BufferInitDescriptor {
+            label: Some("clear_texture_source"),
+            contents: &fill_bytes(
+                &texel,
+                (bytes_per_row * rows_per_image * largest.depth_or_array_layers) as usize,
+            ),
+            usage: BufferUsages::COPY_SRC,
+        });
+
+        for (mip_level, origin, size) in regions {
+            for layer in array_layers.clone() {
+                let origin = match dimension {
+                    TextureDimension::D3 => origin,
+                    _ => Origin3d { z: layer, ..origin },
+                };
+                context.copy_buffer_to_texture(
+                    ImageCopyBuffer {
+                        buffer: &source,
+                        layout: ImageDataLayout {
+                            offset: 0,
+                            bytes_per_row: Some(bytes_per_row),
+                            rows_per_image: Some(rows_per_image),
+                        },
+                    },
+                    ImageCopyTexture {
+                        texture,
+                        mip_level,
+                        origin,
+                        aspect: TextureAspect::All,
+                    },
+                    size,
+                );
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// The [`JobInput`] for [`ClearTextureJob`], which waits for the target texture to be prepared.
+pub struct ClearTextureTarget;
+
+impl JobInput<ClearTextureJob> for ClearTextureTarget {
+    type Data = Read<ClearTextureJob>;
+
+    type Item<'a> = &'a Texture;
+
+    fn status(data: QueryItem<Self::Data>, world: &World) -> JobInputStatus {
+        match data.target.get(world) {
+            Some(_) => JobInputStatus::Ready,
+            None => JobInputStatus::Wait,
+        }
+    }
+
+    fn get<'a>(data: QueryItem<'a, Self::Data>, world: &'a World) -> Self::Item<'a> {
+        data.target
+            .get(world)
+            .expect("texture should be ready by this point")
+    }
+}
+
+/// Repeats `pattern` to fill `len` bytes.
+fn fill_bytes(pattern: &[u8], len: usize) -> Vec<u8> {
+    pattern.iter().copied().cycle().take(len).collect()
+}
+
+/// Returns the size of the range to clear, or `None` if the range is out of
+/// bounds or misaligned.
+fn clear_buffer_size(buffer_size: u64, offset: u64, size: Option<u64>) -> Option<u64> {
+    let size = match size {
+        Some(size) => size,
+        None => buffer_size.checked_sub(offset)?,
+    };
+    let in_bounds = offset
+        .checked_add(size)
+        .is_some_and(|end| end <= buffer_size);
+    let aligned = offset % COPY_BUFFER_ALIGNMENT == 0 && size % COPY_BUFFER_ALIGNMENT == 0;
+    (in_bounds && aligned).then_some(size)
+}
+
+/// Returns the range of mip levels or array layers to clear, or `None`
+/// if it's out of bounds.
+fn subresource_range(base: u32, count: Option<u32>, total: u32) -> Option<Range<u32>> {
+    let count = match count {
+        Some(count) => count,
+        None => total.checked_sub(base)?,
+    };
+    let end = base.checked_add(count)?;
+    (end <= total).then_some(base..end)
+}
+
+/// Scales the region to clear down to the given mip level, clamped to the
+/// bounds of that level. Returns `None` if the region is empty on that level.
+fn mip_region(
+    texture_size: Extent3d,
+    origin: Origin3d,
+    size: Option<Extent3d>,
+    mip_level: u32,
+    dimension: TextureDimension,
+    format: TextureFormat,
+) -> Option<(u32, Origin3d, Extent3d)> {
+    let is_3d = dimension == TextureDimension::D3;
+    let level_size = texture_size
+        .mip_level_size(mip_level, dimension)
+        .physical_size(format);
+    let region_size = size
+        .unwrap_or(texture_size)
+        .mip_level_size(mip_level, dimension)
+        .physical_size(format);
+
+    let origin = Origin3d {
+        x: origin.x >> mip_level,
+        y: origin.y >> mip_level,
+        z: if is_3d { origin.z >> mip_level } else { 0 },
+    };
+    let size = Extent3d {
+        width: region_size
+            .width
+            .min(level_size.width.checked_sub(origin.x)?),
+        height: region_size
+            .height
+            .min(level_size.height.checked_sub(origin.y)?),
+        depth_or_array_layers: match is_3d {
+            true => region_size
+                .depth_or_array_layers
+                .min(level_size.depth_or_array_layers.checked_sub(origin.z)?),
+            false => 1,
+        },
+    };
+
+    (size.width > 0 && size.height > 0 && size.depth_or_array_layers > 0)
+        .then_some((mip_level, origin, size))
+}
+
+#[cfg(test)]
+mod test {
+    use bevy_render::{
+        render_resource::{
+            BufferInitDescriptor, BufferUsages, Extent3d, Origin3d, TextureDescriptor,
+            TextureDimension, TextureFormat, TextureUsages,
+        },
+        renderer::{RenderDevice, RenderQueue},
+    };
+    use wgpu::util::TextureDataOrder;
+
+    use crate::ext::{headless_test_app, read_buffer, read_texture, RunGraphicsJobExt};
+
+    use super::{
+        clear_buffer_size, fill_bytes, mip_region, subresource_range, ClearBufferJob,
+        ClearTextureJob,
+    };
+
+    fn extent(width: u32, height: u32, depth_or_array_layers: u32) -> Extent3d {
+        Extent3d {
+            width,
+            height,
+            depth_or_array_layers,
+        }
+    }
+
+    #[test]
+    fn zero_clear_fills_zeros() {
+        let contents = fill_bytes(&0u32.to_le_bytes(), 64);
+        assert_eq!(contents.len(), 64);
+        assert!(contents.iter().all(|byte| *byte == 0));
+    }
+
+    #[test]
+    fn value_clear_repeats_words() {
+        let contents = fill_bytes(&0xaabb_ccddu32.to_le_bytes(), 8);
+        assert_eq!(contents, [0xdd, 0xcc, 0xbb, 0xaa, 0xdd, 0xcc, 0xbb, 0xaa]);
+    }
+
+    #[test]
+    fn buffer_ranges_are_validated() {
+        assert_eq!(clear_buffer_size(64, 0, None), Some(64));
+        assert_eq!(clear_buffer_size(64, 16, None), Some(48));
+        assert_eq!(clear_buffer_size(64, 16, Some(16)), Some(16));
+        assert_eq!(clear_buffer_size(64, 60, Some(8)), None);
+        assert_eq!(clear_buffer_size(64, 2, None), None);
+        assert_eq!(clear_buffer_size(64, 80, None), None);
+    }
+
+    #[test]
+    fn subresource_ranges_are_validated() {
+        assert_eq!(subresource_range(0, None, 4), Some(0..4));
+        assert_eq!(subresource_range(1, Some(2), 4), Some(1..3));
+        assert_eq!(subresource_range(3, Some(2), 4), None);
+        assert_eq!(subresource_range(5, None, 4), None);
+    }
+
+    #[test]
+    fn regions_scale_with_mip_level() {
+        let texture_size = extent(8, 8, 1);
+        let origin = Origin3d { x: 4, y: 0, z: 0 };
+        let size = extent(4, 8, 1);
+        let region = |mip| {
+            mip_region(
+                texture_size,
+                origin,
+                Some(size),
+                mip,
+                TextureDimension::D2,
+                TextureFormat::Rgba8Unorm,
+            )
+        };
+
+        assert_eq!(region(0), Some((0, origin, size)));
+        assert_eq!(
+            region(1),
+            Some((1, Origin3d { x: 2, y: 0, z: 0 }, extent(2, 4, 1)))
+        );
+        assert_eq!(
+            region(3),
+            Some((3, Origin3d { x: 0, y: 0, z: 0 }, extent(1, 1, 1)))
+        );
+    }
+
+    #[test]
+    fn regions_are_clamped_to_texture() {
+        let region = mip_region(
+            extent(8, 8, 6),
+            Origin3d { x: 6, y: 6, z: 0 },
+            None,
+            0,
+            TextureDimension::D2,
+            TextureFormat::Rgba8Unorm,
+        );
+        assert_eq!(
+            region,
+            Some((0, Origin3d { x: 6, y: 6, z: 0 }, extent(2, 2, 1)))
+        );
+    }
+
+    #[test]
+    fn compressed_regions_cover_whole_blocks() {
+        let region = mip_region(
+            extent(8, 8, 1),
+            Origin3d::ZERO,
+            None,
+            2,
+            TextureDimension::D2,
+            TextureFormat::Bc1RgbaUnorm,
+        );
+        assert_eq!(region, Some((2, Origin3d::ZERO, extent(4, 4, 1))));
+    }
+
+    // Like other tests running jobs on the real renderer, these need a GPU adapter.
+
+    #[test]
+    fn clears_buffer_ranges() {
+        let mut app = headless_test_app();
+        app.finish();
+        app.cleanup();
+
+        let buffer = app
+            .world()
+            .resource::<RenderDevice>()
+            .create_buffer_with_data(&BufferInitDescriptor {
+                label: None,
+                contents: &[0xff; 64],
+                usage: BufferUsages::COPY_DST | BufferUsages::COPY_SRC,
+            });
+
+        let job = ClearBufferJob::new(buffer.clone()).with_range(16, Some(16));
+        assert_eq!(app.run_job_blocking(job), Ok(()));
+        let job = ClearBufferJob::new(buffer.clone())
+            .with_value(0xaabb_ccdd)
+            .with_range(40, None);
+        assert_eq!(app.run_job_blocking(job), Ok(()));
+
+        let contents = read_buffer(&app, &buffer, 64);
+        assert!(contents[..16].iter().all(|byte| *byte == 0xff));
+        assert!(contents[16..32].iter().all(|byte| *byte == 0));
+        assert!(contents[32..40].iter().all(|byte| *byte == 0xff));
+        assert_eq!(
+            contents[40..],
+            fill_bytes(&0xaabb_ccddu32.to_le_bytes(), 24)
+        );
+    }
+
+    #[test]
+    fn clears_texture_regions() {
+        const WHITE: [u8; 4] = [255; 4];
+        const RED: [u8; 4] = [255, 0, 0, 255];
+
+        let mut app = headless_test_app();
+        app.finish();
+        app.cleanup();
+
+        // two 8x8 layers with two mip levels each, all white
+        let texture = app
+            .world()
+            .resource::<RenderDevice>()
+            .create_texture_with_data(
+                app.world().resource::<RenderQueue>(),
+                &TextureDescriptor {
+                    label: None,
+                    size: extent(8, 8, 2),
+                    mip_level_count: 2,
+                    sample_count: 1,
+                    dimension: TextureDimension::D2,
+                    format: TextureFormat::Rgba8Unorm,
+                    usage: TextureUsages::COPY_DST | TextureUsages::COPY_SRC,
+                    view_formats: &[],
+                },
+                TextureDataOrder::LayerMajor,
+                &[255; 2 * (8 * 8 + 4 * 4) * 4],
+            );
+
+        // the right half of the second layer
+        let job = ClearTextureJob::new(texture.clone())
+            .with_value(RED)
+            .with_region(Origin3d { x: 4, y: 0, z: 0 }, extent(4, 8, 1))
+            .with_array_layers(1, Some(1));
+        assert_eq!(app.run_job_blocking(job), Ok(()));
+
+        for (mip_level, width) in [(0, 8), (1, 4)] {
+            let untouched = read_texture(&app, &texture, mip_level, 0);
+            assert!(untouched.chunks(4).all(|texel| texel == WHITE));
+
+            let cleared = read_texture(&app, &texture, mip_level, 1);
+            for (index, texel) in cleared.chunks(4).enumerate() {
+                let expected = match index % width >= width / 2 {
+                    true => RED,
+                    false => WHITE,
+                };
+                assert_eq!(texel, expected, "texel {index} of mip level {mip_level}");
+            }
+        }
+    }
+}
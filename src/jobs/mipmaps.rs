@@ -0,0 +1,438 @@
+use bevy_app::{App, Plugin};
+use bevy_asset::{load_internal_asset, Handle};
+use bevy_ecs::{
+    component::Component,
+    entity::Entity,
+    query::{QueryItem, Without},
+    schedule::IntoSystemConfigs,
+    system::{lifetimeless::Read, Commands, Query, Resource},
+    world::{FromWorld, World},
+};
+use bevy_render::{
+    render_resource::{
+        binding_types::{texture_2d, texture_2d_array},
+        BindGroupEntries, BindGroupLayout, BindGroupLayoutEntries, ColorTargetState, ColorWrites,
+        FragmentState, LoadOp, MultisampleState, Operations, PrimitiveState,
+        RenderPassColorAttachment, RenderPassDescriptor, RenderPipelineDescriptor, Shader,
+        ShaderStages, SpecializedRenderPipeline, StoreOp, Texture, TextureDimension, TextureFormat,
+        TextureSampleType, TextureUsages, TextureViewDescriptor, TextureViewDimension, VertexState,
+    },
+    renderer::{RenderAdapter, RenderDevice},
+    Render, RenderApp, RenderSet,
+};
+use bevy_utils::tracing::error;
+
+use crate::{
+    input::{JobInput, JobInputItem, JobInputStatus, JobRenderPipeline},
+    GraphicsJob, JobError, JobRunContext, JobSet,
+};
+
+use super::JobTexture;
+
+const MIPMAP_SHADER_HANDLE: Handle<Shader> =
+    Handle::weak_from_u128(104507608578707437454611254442697200766);
+
+/// A built-in graphics job that generates the mip chain of a 2D texture, by
+/// repeatedly downsampling each mip level into the next with a box filter.
+///
+/// The texture must have been created with [`TextureUsages::TEXTURE_BINDING`] and
+/// [`TextureUsages::RENDER_ATTACHMENT`], and with the desired number of mip levels.
+/// Textures of any size are supported, including non-power-of-two sizes, as well
+/// as any renderable float color format. The job fails with [`JobError::InputsFailed`]
+/// for other formats, including block-compressed formats, which can't be rendered to.
+///
+/// This job is initialized by [`GraphicsJobsPlugin`](crate::GraphicsJobsPlugin),
+/// so it can be spawned right away.
+#[derive(Clone, Component)]
+pub struct GenerateMipmapsJob {
+    pub target: JobTexture,
+}
+
+impl GenerateMipmapsJob {
+    /// Creates a job that generates every mip level of the target texture from mip level `0`.
+    pub fn new(target: impl Into<JobTexture>) -> Self {
+        Self {
+            target: target.into(),
+        }
+    }
+}
+
+impl GraphicsJob for GenerateMipmapsJob {
+    type In = (MipmapTarget, JobRenderPipeline<MipmapPipeline>);
+
+    fn run(
+        &self,
+        world: &World,
+        context: &mut JobRunContext,
+        (texture, pipeline): JobInputItem<Self, Self::In>,
+    ) -> Result<(), JobError> {
+        let render_device = context.render_device();
+        let mipmap_pipeline = world.resource::<MipmapPipeline>();
+        let view_label = Self::resource_label("generate_mipmaps_view");
+        let bind_group_label = Self::resource_label("generate_mipmaps_bind_group");
+
+        // array textures are sampled through a view of every layer, since some
+        // backends can't sample a single layer of an array texture as a 2D texture
+        let layers = texture.depth_or_array_layers();
+        let (layout, source_dimension) = match layers > 1 {
+            true => (&mipmap_pipeline.array_layout, TextureViewDimension::D2Array),
+            false => (&mipmap_pipeline.layout, TextureViewDimension::D2),
+        };
+
+        for layer in 0..layers {
+            for mip_level in 1..texture.mip_level_count() {
+                let source = texture.create_view(&TextureViewDescriptor {
+                    label: Some(&view_label),
+                    dimension: Some(source_dimension),
+                    base_mip_level: mip_level - 1,
+                    mip_level_count: Some(1),
+                    ..Default::default()
+                });
+                let target = texture.create_view(&TextureViewDescriptor {
+                    label: Some(&view_label),
+                    dimension: Some(TextureViewDimension::D2),
+                    base_mip_level: mip_level,
+                    mip_level_count: Some(1),
+                    base_array_layer: layer,
+                    array_layer_count: Some(1),
+                    ..Default::default()
+                });
+
+                let bind_group = render_device.create_bind_group(
+                    Some(&*bind_group_label),
+                    layout,
+                    &BindGroupEntries::single(&source),
+                );
+
+                let mut render_pass = context.begin_render_pass(&RenderPassDescriptor {
+                    label: Some("generate_mipmaps_pass"),
+                    color_attachments: &[Some(RenderPassColorAttachment {
+                        view: &target,
+                        resolve_target: None,
+                        ops: Operations {
+                            load: LoadOp::Clear(Default::default()),
+                            store: StoreOp::Store,
+                        },
+                    })],
+                    depth_stencil_attachment: None,
+                    timestamp_writes: None,
+                    occlusion_query_set: None,
+                });
+
+                render_pass.set_pipeline(pipeline);
+                render_pass.set_bind_group(0, &bind_group, &[]);
+                // the layer to sample is passed through the instance index
+                render_pass.draw(0..3, layer..layer + 1);
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// The [`JobInput`] for [`GenerateMipmapsJob`], which waits for the target texture to be
+/// prepared, and fails if it can't be rendered to.
+pub struct MipmapTarget;
+
+impl JobInput<GenerateMipmapsJob> for MipmapTarget {
+    type Data = Read<GenerateMipmapsJob>;
+
+    type Item<'a> = &'a Texture;
+
+    fn plugin() -> impl Plugin {
+        |app: &mut App| {
+            load_internal_asset!(app, MIPMAP_SHADER_HANDLE, "mipmaps.wgsl", Shader::from_wgsl);
+
+            if let Some(render_app) = app.get_sub_app_mut(RenderApp) {
+                render_app.add_systems(
+                    Render,
+                    specialize_mipmap_jobs
+                        .in_set(RenderSet::Queue)
+                        .before(JobSet::QueuePipelines),
+                );
+            }
+        }
+    }
+
+    fn status(data: QueryItem<Self::Data>, world: &World) -> JobInputStatus {
+        let Some(texture) = data.target.get(world) else {
+            return JobInputStatus::Wait;
+        };
+
+        let format_features = world
+            .resource::<RenderAdapter>()
+            .get_texture_format_features(texture.format());
+        let required_usages = TextureUsages::TEXTURE_BINDING | TextureUsages::RENDER_ATTACHMENT;
+
+        let result = check_mipmap_format(texture.format()).and_then(|()| {
+            if texture.dimension() != TextureDimension::D2 {
+                Err("only 2D textures are supported")
+            } else if !texture.usage().contains(required_usages) {
+                Err("the texture must have TEXTURE_BINDING and RENDER_ATTACHMENT usages")
+            } else if !format_features
+                .allowed_usages
+                .contains(TextureUsages::RENDER_ATTACHMENT)
+            {
+                Err("the texture format isn't renderable on this device")
+            } else {
+                Ok(())
+            }
+        });
+
+        match result {
+            Ok(()) => JobInputStatus::Ready,
+            Err(reason) => {
+                error!(
+                    "unable to generate mipmaps for a texture with format {:?}: {reason}",
+                    texture.format()
+                );
+                JobInputStatus::Fail
+            }
+        }
+    }
+
+    fn get<'a>(data: QueryItem<'a, Self::Data>, world: &'a World) -> Self::Item<'a> {
+        data.target
+            .get(world)
+            .expect("texture should be ready by this point")
+    }
+}
+
+/// The crate-owned pipeline used by [`GenerateMipmapsJob`], specialized by the
+/// format of the target texture, and whether it has multiple array layers.
+#[derive(Resource)]
+pub struct MipmapPipeline {
+    layout: BindGroupLayout,
+    array_layout: BindGroupLayout,
+}
+
+impl FromWorld for MipmapPipeline {
+    fn from_world(world: &mut World) -> Self {
+        let render_device = world.resource::<RenderDevice>();
+        let sample_type = TextureSampleType::Float { filterable: false };
+        let layout = render_device.create_bind_group_layout(
+            "generate_mipmaps_layout",
+            &BindGroupLayoutEntries::single(ShaderStages::FRAGMENT, texture_2d(sample_type)),
+        );
+        let array_layout = render_device.create_bind_group_layout(
+            "generate_mipmaps_array_layout",
+            &BindGroupLayoutEntries::single(ShaderStages::FRAGMENT, texture_2d_array(sample_type)),
+        );
+
+        Self {
+            layout,
+            array_layout,
+        }
+    }
+}
+
+/// The key [`MipmapPipeline`] is specialized by.
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+pub struct MipmapPipelineKey {
+    pub format: TextureFormat,
+    /// Whether the texture has multiple array layers.
+    pub array: bool,
+}
+
+impl MipmapPipelineKey {
+    fn for_texture(texture: &Texture) -> Self {
+        Self {
+            format: texture.format(),
+            array: texture.depth_or_array_layers() > 1,
+        }
+    }
+}
+
+impl SpecializedRenderPipeline for MipmapPipeline {
+    type Key = MipmapPipelineKey;
+
+    fn specialize(&self, key: Self::Key) -> RenderPipelineDescriptor {
+        let (layout, shader_defs) = match key.array {
+            true => (&self.array_layout, vec!["ARRAY".into()]),
+            false => (&self.layout, Vec::new()),
+        };
+
+        RenderPipelineDescriptor {
+            label: Some("generate_mipmaps_pipeline".into()),
+            layout: vec![layout.clone()],
+            push_constant_ranges: Vec::new(),
+            vertex: VertexState {
+                shader: MIPMAP_SHADER_HANDLE,
+                shader_defs: shader_defs.clone(),
+                entry_point: "vertex".into(),
+                buffers: Vec::new(),
+            },
+            primitive: PrimitiveState::default(),
+            depth_stencil: None,
+            multisample: MultisampleState::default(),
+            fragment: Some(FragmentState {
+                shader: MIPMAP_SHADER_HANDLE,
+                shader_defs,
+                entry_point: "fragment".into(),
+                targets: vec![Some(ColorTargetState {
+                    format: key.format,
+                    blend: None,
+                    write_mask: ColorWrites::ALL,
+                })],
+            }),
+            zero_initialize_workgroup_memory: false,
+        }
+    }
+}
+
+/// The pipeline for each job depends on the format and layers of its texture, which isn't
+/// known until the texture is prepared in the render world, so the pipeline key
+/// is added here rather than when the job is spawned.
+fn specialize_mipmap_jobs(
+    jobs: Query<(Entity, &GenerateMipmapsJob), Without<JobRenderPipeline<MipmapPipeline>>>,
+    world: &World,
+    mut commands: Commands,
+) {
+    for (entity, job) in &jobs {
+        let Some(texture) = job.target.get(world) else {
+            continue;
+        };
+        if check_mipmap_format(texture.format()).is_ok() {
+            commands
+                .entity(entity)
+                .insert(JobRenderPipeline::<MipmapPipeline>(
+                    MipmapPipelineKey::for_texture(texture),
+                ));
+        }
+    }
+}
+
+/// Checks that mipmaps can be generated for textures with the given format.
+fn check_mipmap_format(format: TextureFormat) -> Result<(), &'static str> {
+    if format.is_compressed() {
+        return Err("block-compressed formats can't be rendered to");
+    }
+    match format.sample_type(None, None) {
+        Some(TextureSampleType::Float { .. }) => Ok(()),
+        _ => Err("only float color formats are supported"),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use bevy_render::{
+        render_resource::{
+            Extent3d, TextureDescriptor, TextureDimension, TextureFormat, TextureUsages,
+        },
+        renderer::{RenderDevice, RenderQueue},
+    };
+    use wgpu::util::TextureDataOrder;
+
+    use crate::ext::{headless_test_app, read_texture, RunGraphicsJobExt};
+
+    use super::{check_mipmap_format, GenerateMipmapsJob};
+
+    #[test]
+    fn color_formats_are_supported() {
+        assert!(check_mipmap_format(TextureFormat::Rgba8Unorm).is_ok());
+        assert!(check_mipmap_format(TextureFormat::Rgba8UnormSrgb).is_ok());
+        assert!(check_mipmap_format(TextureFormat::Rgba16Float).is_ok());
+        assert!(check_mipmap_format(TextureFormat::R32Float).is_ok());
+    }
+
+    #[test]
+    fn compressed_formats_fail() {
+        assert!(check_mipmap_format(TextureFormat::Bc1RgbaUnorm).is_err());
+        assert!(check_mipmap_format(TextureFormat::Etc2Rgb8Unorm).is_err());
+    }
+
+    #[test]
+    fn non_float_formats_fail() {
+        assert!(check_mipmap_format(TextureFormat::Rgba8Uint).is_err());
+        assert!(check_mipmap_format(TextureFormat::Depth32Float).is_err());
+    }
+
+    /// The red and green channels of a texel of the 8x8 test pattern. Red ramps up
+    /// along x on the first layer and down on the second, and green ramps up along y.
+    fn pattern(x: u32, y: u32, layer: u32) -> [u8; 2] {
+        let x = match layer {
+            0 => x,
+            _ => 7 - x,
+        };
+        [(x * 32) as u8, (y * 32) as u8]
+    }
+
+    // Like other tests running jobs on the real renderer, this needs a GPU adapter.
+    #[test]
+    fn mips_average_the_level_above() {
+        let mut app = headless_test_app();
+        app.finish();
+        app.cleanup();
+
+        // mip level 0 of each layer, followed by its three smaller levels, left empty
+        let data = (0..2)
+            .flat_map(|layer| {
+                let level = (0..64).flat_map(move |index| {
+                    let [red, green] = pattern(index % 8, index / 8, layer);
+                    [red, green, 0, 255]
+                });
+                level.chain([0; (16 + 4 + 1) * 4])
+            })
+            .collect::<Vec<_>>();
+        let texture = app
+            .world()
+            .resource::<RenderDevice>()
+            .create_texture_with_data(
+                app.world().resource::<RenderQueue>(),
+                &TextureDescriptor {
+                    label: None,
+                    size: Extent3d {
+                        width: 8,
+                        height: 8,
+                        depth_or_array_layers: 2,
+                    },
+                    mip_level_count: 4,
+                    sample_count: 1,
+                    dimension: TextureDimension::D2,
+                    format: TextureFormat::Rgba8Unorm,
+                    usage: TextureUsages::TEXTURE_BINDING
+                        | TextureUsages::RENDER_ATTACHMENT
+                        | TextureUsages::COPY_DST
+                        | TextureUsages::COPY_SRC,
+                    view_formats: &[],
+                },
+                TextureDataOrder::LayerMajor,
+                &data,
+            );
+
+        assert_eq!(
+            app.run_job_blocking(GenerateMipmapsJob::new(texture.clone())),
+            Ok(())
+        );
+
+        for layer in 0..2 {
+            for mip_level in 1..=3 {
+                let width = 8 >> mip_level;
+                let texels = read_texture(&app, &texture, mip_level, layer);
+                for (index, texel) in texels.chunks(4).enumerate() {
+                    let (x, y) = (index as u32 % width, index as u32 / width);
+
+                    // the average of the 2^mip x 2^mip block of level 0 this texel covers
+                    let block = 1 << mip_level;
+                    let mut sum = [0; 2];
+                    for source_y in y * block..(y + 1) * block {
+                        for source_x in x * block..(x + 1) * block {
+                            let [red, green] = pattern(source_x, source_y, layer);
+                            sum[0] += red as u32;
+                            sum[1] += green as u32;
+                        }
+                    }
+                    let expected = sum.map(|sum| sum / (block * block));
+
+                    for (channel, expected) in expected.into_iter().enumerate() {
+                        assert!(
+                            texel[channel].abs_diff(expected as u8) <= 1,
+                            "texel ({x}, {y}) of mip level {mip_level} of layer {layer} is {texel:?}, expected {expected:?}",
+                        );
+                    }
+                    assert_eq!(texel[2..], [0, 255]);
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,48 @@
+#ifdef ARRAY
+@group(0) @binding(0) var source: texture_2d_array<f32>;
+#else
+@group(0) @binding(0) var source: texture_2d<f32>;
+#endif
+
+struct VertexOutput {
+    @builtin(position) position: vec4<f32>,
+    // the array layer being downsampled
+    @location(0) @interpolate(flat) layer: u32,
+}
+
+// a single triangle covering the whole target
+@vertex
+fn vertex(
+    @builtin(vertex_index) index: u32,
+    @builtin(instance_index) layer: u32,
+) -> VertexOutput {
+    let uv = vec2<f32>(f32(index >> 1u), f32(index & 1u)) * 2.0;
+    return VertexOutput(vec4<f32>(uv * 2.0 - 1.0, 0.0, 1.0), layer);
+}
+
+fn load(coord: vec2<i32>, layer: u32) -> vec4<f32> {
+#ifdef ARRAY
+    return textureLoad(source, coord, layer, 0);
+#else
+    return textureLoad(source, coord, 0);
+#endif
+}
+
+@fragment
+fn fragment(in: VertexOutput) -> @location(0) vec4<f32> {
+    let source_size = vec2<i32>(textureDimensions(source));
+    let base = vec2<i32>(in.position.xy) * 2;
+
+    // odd source dimensions take a third tap, so that the last row
+    // or column of texels isn't dropped from the chain
+    let taps = select(vec2(2), vec2(3), (source_size & vec2(1)) == vec2(1));
+
+    var sum = vec4(0.0);
+    for (var y = 0; y < taps.y; y++) {
+        for (var x = 0; x < taps.x; x++) {
+            let coord = min(base + vec2(x, y), source_size - 1);
+            sum += load(coord, in.layer);
+        }
+    }
+    return sum / f32(taps.x * taps.y);
+}
@@ -0,0 +1,49 @@
+use std::borrow::Cow;
+
+use disqualified::ShortName;
+
+/// Builds the label of a GPU resource created for a job, so that it's easy to find
+/// in a GPU capture.
+///
+/// With the `labels` feature, the label is the job's name followed by the resource's
+/// purpose, like `MyJob/job_offscreen_target`. Otherwise it's only the purpose, so no
+/// string is allocated.
+#[cfg(feature = "labels")]
+pub(crate) fn job_resource_label(
+    job: Option<ShortName<'static>>,
+    purpose: &'static str,
+) -> Cow<'static, str> {
+    match job {
+        Some(job) => Cow::Owned(format!("{job}/{purpose}")),
+        None => Cow::Borrowed(purpose),
+    }
+}
+
+#[cfg(not(feature = "labels"))]
+pub(crate) fn job_resource_label(
+    _job: Option<ShortName<'static>>,
+    purpose: &'static str,
+) -> Cow<'static, str> {
+    Cow::Borrowed(purpose)
+}
+
+#[cfg(test)]
+mod test {
+    use disqualified::ShortName;
+
+    use super::job_resource_label;
+
+    struct LabeledJob;
+
+    #[test]
+    fn labels_are_prefixed_with_job_name() {
+        let label = job_resource_label(Some(ShortName::of::<LabeledJob>()), "scratch");
+        if cfg!(feature = "labels") {
+            assert_eq!(label, "LabeledJob/scratch");
+        } else {
+            assert_eq!(label, "scratch");
+        }
+
+        assert_eq!(job_resource_label(None, "scratch"), "scratch");
+    }
+}
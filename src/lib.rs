@@ -15,41 +15,145 @@
 //! 4. Call `init_graphics_job` on `App` to initialize your custom job
 //! 5. To run the job, simply spawn an entity with your job component!
 //!
+//! Common one-off tasks, like clearing a texture or generating its mipmaps, are provided as
+//! built-in jobs in the [`jobs`] module.
+//!
+//! For headless tools without a main loop, [`run_job_blocking`](RunGraphicsJobExt::run_job_blocking)
+//! runs a single job to completion, and [`flush_task`](RunGraphicsJobExt::flush_task) runs
+//! the jobs of a single [`JobTask`](meta::JobTask) to completion. Code outside of the ECS can
+//! also be notified of completed jobs over a channel with [`JobCompletionSender`], or with
+//! [`JobCompletionBatch`] to receive every job completed in a frame as a single message.
+//!
+//! Besides [`JobComplete`], jobs trigger [`OnJobReady`], [`OnJobDone`] and [`OnJobFailed`]
+//! on their entity as they transition, which can be observed when spawning them with
+//! [`ObserveJobExt`]. To enumerate the jobs of a type along with their [`JobStatus`],
+//! for example to report progress, use the [`Jobs`] system param, and for a timeline of
+//! recent transitions, read the [`JobTransitions`] resource. Jobs can also describe
+//! their result with [`JobRunContext::set_result`], to be read from [`JobResults`] once
+//! they complete, and the queue submission holding their commands can be read from
+//! [`JobSubmissions`] to wait on exactly their work. Rolling statistics of each job
+//! type, like how many completed and how long they took, are kept in [`JobStats`].
+//!
+//! Jobs that need others to finish first can wait on them with [`JobAfter`](meta::JobAfter),
+//! and whole chains of jobs can be spawned at once with [`SpawnJobChainExt::spawn_job`].
+//! Jobs tied to an entity, like a camera, can be cancelled once it's despawned with
+//! [`CancelOnTargetDespawn`](meta::CancelOnTargetDespawn).
+//!
+//! On systems with more than one GPU, jobs spawned with [`JobAdapter::Secondary`](meta::JobAdapter)
+//! run on a [`JobSecondaryDevice`] instead of bevy's own, which comes with substantial
+//! limitations, described in its docs.
+//!
+//! With the `diagnostics` feature enabled and bevy's `RenderDiagnosticsPlugin` added, each
+//! job's GPU time is recorded next to bevy's render diagnostics, labeled with the job's
+//! name. These timings also drive
+//! [`JobExecutionSettings::time_budget`], which adapts the number of jobs executed each
+//! frame to a target GPU time.
+//!
+//! With the `labels` feature enabled, the GPU resources created for jobs by the built-in
+//! inputs are labeled with the job's name, so they're easy to tell apart in GPU captures.
+//!
+//! To debug scheduling issues, a [`JobQueueSnapshot`] of the render world's jobs can be
+//! captured and replayed. With the `serialize` feature enabled, snapshots can be saved
+//! with `serde`.
+//!
 //! See the examples in the repo for more in-depth showcases!
 
 #![allow(clippy::type_complexity)]
 
+mod adapter;
+mod budget;
+mod chain;
+mod completion;
+mod context;
+mod device;
+#[cfg(feature = "diagnostics")]
+mod diagnostics;
+mod done;
 mod ext;
 pub mod input;
+mod invariant;
+pub mod jobs;
+mod label;
+mod memory;
 pub mod meta;
+mod registry;
+mod result;
 mod runner;
+mod shutdown;
+mod snapshot;
+mod stats;
+mod status;
+mod submission;
+mod task;
+mod transition;
+pub use adapter::JobSecondaryDevice;
+pub use budget::{JobAdaptiveBudget, JobTimeBudget};
+use chain::release_chained_jobs;
+pub use chain::{JobChain, SpawnJobChainExt};
+pub use completion::{JobCompletion, JobCompletionBatch, JobCompletionSender};
+pub use context::JobRunContext;
+pub use device::DeviceLostPolicy;
+use device::{recover_lost_device, watch_device_lost, JobDeviceLost};
 use disqualified::ShortName;
+use done::despawn_done_jobs;
+pub use done::JobDone;
 pub use ext::*;
 use input::{JobInput, JobInputItem};
-use meta::{extract_job_meta, JobMarker};
+pub use invariant::InvariantViolation;
+use jobs::{ClearBufferJob, ClearTextureJob, GenerateMipmapsJob};
+use label::job_resource_label;
+pub use memory::{JobMemoryBreakdown, JobMemoryCategory, JobMemoryUsage};
+use meta::{
+    defer_chained_extraction, defer_extraction, extract_despawned_job_targets, extract_job_meta,
+    release_deferred_jobs, sequence_jobs, JobComparator, JobMarker,
+};
+use registry::{register_job_type, warn_unregistered_jobs, RegisteredJobs};
+use result::clean_up_job_results;
+pub use result::JobResults;
 use runner::{
-    check_job_inputs, erase_jobs, increment_time_out_frames, run_jobs, setup_time_out_frames,
-    sync_completed_jobs, sync_completed_jobs_main_world, time_out_jobs, JobResultMainWorldReceiver,
-    JobResultMainWorldSender, JobResultReceiver, JobResultSender, JobSet,
+    cancel_orphaned_jobs, check_job_inputs, configure_job_sets, erase_jobs,
+    increment_interval_frames, increment_time_out_frames, run_jobs, setup_interval_frames,
+    setup_time_out_frames, sync_completed_jobs, sync_completed_jobs_main_world, time_out_jobs,
+    warn_stalled_jobs, JobResultMainWorldReceiver, JobResultMainWorldSender, JobResultReceiver,
+    JobResultSender,
+};
+pub use runner::{job_input_statuses, JobSet};
+use shutdown::handle_app_exit;
+pub use shutdown::JobShutdownPolicy;
+pub use snapshot::{JobQueueSnapshot, JobReplay, JobSnapshot};
+use stats::{
+    forget_spawned_job, record_job_completion_stats, record_job_ready_stats,
+    reset_job_stats_window, track_spawned_job,
+};
+pub use stats::{JobStats, JobStatsWindow, JobTimeStats, JobTypeStats};
+pub use status::{JobStatus, Jobs};
+use submission::clean_up_job_submissions;
+pub use submission::JobSubmissions;
+pub use task::{cancel_task, JobTaskStatus};
+use transition::{
+    forget_ready_transition, record_done_transition, record_ready_transition,
+    sync_ready_jobs_main_world, JobReadyMainWorldReceiver, JobReadyMainWorldSender,
+};
+pub use transition::{
+    JobTransition, JobTransitions, ObserveJobExt, OnJobDone, OnJobFailed, OnJobReady,
 };
 
-use core::marker::PhantomData;
+use core::{marker::PhantomData, time::Duration};
+use std::borrow::Cow;
 
-use bevy_app::{App, Plugin, Update};
+use bevy_app::{App, Last, Plugin, Update};
 use bevy_ecs::{
     component::Component,
     event::Event,
     query::Added,
-    schedule::{IntoSystemConfigs, IntoSystemSetConfigs},
+    schedule::IntoSystemConfigs,
     system::{Commands, Query, Resource},
     world::World,
 };
 use bevy_render::{
     extract_resource::{ExtractResource, ExtractResourcePlugin},
-    render_resource::CommandEncoder,
-    renderer::RenderDevice,
     sync_component::SyncComponentPlugin,
-    ExtractSchedule, Render, RenderApp, RenderSet,
+    ExtractSchedule, Render, RenderApp,
 };
 use bevy_render::{sync_world::RenderEntity, Extract};
 
@@ -61,27 +165,88 @@ use bevy_render::{sync_world::RenderEntity, Extract};
 /// will be submitted before the render graph is executed.
 ///
 /// You can also specify a priority for a running job by adding the [`JobPriority`]
-/// component when it is spawned.
+/// component when it is spawned. Changes to its priority are extracted each frame,
+/// so a waiting job can be reprioritized from the main world. Jobs that need the GPU
+/// to themselves can be spawned with [`JobExclusive`](meta::JobExclusive).
 ///
 /// Note: you must call [`init_graphics_job`](crate::ext::InitGraphicsJobExt::init_graphics_job)
 /// on [`App`] for the job to execute.
 pub trait GraphicsJob: Component + Clone {
     type In: JobInput<Self>;
 
+    /// Whether running the job more than once has the same effect as running it once,
+    /// which is true of most jobs, since they overwrite their outputs. Jobs with side
+    /// effects that shouldn't repeat, like appending to a buffer, should set this to
+    /// `false`, and are then never re-executed automatically: they don't run with the
+    /// placeholders of a [`JobFallback`](input::JobFallback), and jobs that yielded
+    /// partway through their work fail with [`JobError::DeviceLost`] when the device
+    /// is lost, instead of being requeued by [`DeviceLostPolicy::Requeue`]. To run
+    /// such a job again, spawn it again.
+    const IS_IDEMPOTENT: bool = true;
+
     fn label() -> ShortName<'static> {
         ShortName::of::<Self>()
     }
 
+    /// Builds a label for a GPU resource this job creates, to make it easy to find in
+    /// GPU captures. With the `labels` feature, the job's [`label`](GraphicsJob::label)
+    /// is prepended to the `purpose`. Otherwise only the `purpose` is used, so that
+    /// release builds don't allocate the label.
+    fn resource_label(purpose: &'static str) -> Cow<'static, str> {
+        job_resource_label(Some(Self::label()), purpose)
+    }
+
+    /// Records the job's commands into the [`JobRunContext`], which dereferences
+    /// to a [`CommandEncoder`](bevy_render::render_resource::CommandEncoder), and
+    /// also provides the [`RenderDevice`](bevy_render::renderer::RenderDevice).
     fn run(
         &self,
         world: &World,
-        render_device: &RenderDevice,
-        command_encoder: &mut CommandEncoder,
+        context: &mut JobRunContext,
         input: JobInputItem<Self, Self::In>,
     ) -> Result<(), JobError>;
+
+    /// Records a single chunk of a job's work. Override this instead of
+    /// [`run`](GraphicsJob::run) for very heavy jobs that should spread
+    /// their work across several command buffer submissions.
+    ///
+    /// Returning [`JobChunk::Yield`] submits the commands recorded so far and
+    /// re-invokes this method with the next [`chunk`](JobRunContext::chunk)
+    /// index, either later in the same frame or, once
+    /// [`JobExecutionSettings::max_submits_per_frame`] is reached, in the next
+    /// frame. Submitting smaller command buffers keeps
+    /// a single job from monopolizing the GPU for long enough that the OS
+    /// watchdog resets the device (a "TDR" on Windows).
+    ///
+    /// By default, this calls [`run`](GraphicsJob::run) and completes in a
+    /// single chunk.
+    fn run_chunk(
+        &self,
+        world: &World,
+        context: &mut JobRunContext,
+        input: JobInputItem<Self, Self::In>,
+    ) -> Result<JobChunk, JobError> {
+        self.run(world, context, input).map(|()| JobChunk::Done)
+    }
+}
+
+/// Signals whether a job has finished recording its work. See [`GraphicsJob::run_chunk`].
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum JobChunk {
+    /// Signals that the job has recorded all of its work.
+    Done,
+    /// Signals that more work remains, and that the job should be
+    /// invoked again after its commands so far are submitted.
+    Yield,
 }
 
 /// The main plugin for `gigs`. This plugin is needed for all functionality.
+///
+/// To detect the GPU device being lost, this plugin takes ownership of the render
+/// device's device lost callback when it finishes. `wgpu` only supports one such callback
+/// and doesn't hand back the previous one, so any callback set on the device before
+/// then is replaced, and callbacks set afterwards stop [`DeviceLostPolicy`] from
+/// applying.
 #[derive(Default)]
 pub struct GraphicsJobsPlugin {
     settings: JobExecutionSettings,
@@ -89,60 +254,105 @@ pub struct GraphicsJobsPlugin {
 
 impl Plugin for GraphicsJobsPlugin {
     fn build(&self, app: &mut App) {
-        app.insert_resource(self.settings);
+        let memory_usage = JobMemoryUsage::default();
+        app.insert_resource(self.settings)
+            .insert_resource(memory_usage.clone())
+            .init_resource::<JobAdaptiveBudget>()
+            .init_resource::<JobComparator>()
+            .add_observer(sequence_jobs)
+            .add_observer(defer_extraction)
+            .add_observer(defer_chained_extraction)
+            .add_observer(release_chained_jobs);
 
         app.add_plugins((
             SyncComponentPlugin::<JobMarker>::default(),
             ExtractResourcePlugin::<JobExecutionSettings>::default(),
+            ExtractResourcePlugin::<JobAdaptiveBudget>::default(),
+            ExtractResourcePlugin::<JobComparator>::default(),
+            ExtractResourcePlugin::<JobSecondaryDevice>::default(),
         ));
 
+        app.init_graphics_job::<ClearBufferJob>()
+            .init_graphics_job::<ClearTextureJob>()
+            .init_graphics_job::<GenerateMipmapsJob>();
+
+        #[cfg(feature = "diagnostics")]
+        app.add_plugins(diagnostics::JobDiagnosticsPlugin);
+
         let (main_sender, main_receiver) = crossbeam_channel::unbounded();
+        let (ready_sender, ready_receiver) = crossbeam_channel::unbounded();
 
         app.insert_resource(JobResultMainWorldReceiver(main_receiver))
-            .add_systems(Update, sync_completed_jobs_main_world);
+            .insert_resource(JobReadyMainWorldReceiver(ready_receiver))
+            .init_resource::<RegisteredJobs>()
+            .init_resource::<JobResults>()
+            .init_resource::<JobSubmissions>()
+            .init_resource::<JobTransitions>()
+            .init_resource::<JobStats>()
+            .add_observer(record_ready_transition)
+            .add_observer(record_done_transition)
+            .add_observer(forget_ready_transition)
+            .add_observer(track_spawned_job)
+            .add_observer(forget_spawned_job)
+            .add_observer(record_job_ready_stats)
+            .add_observer(record_job_completion_stats)
+            .add_systems(Update, (warn_unregistered_jobs, reset_job_stats_window))
+            .add_systems(
+                Update,
+                (
+                    sync_ready_jobs_main_world,
+                    clean_up_job_results,
+                    clean_up_job_submissions,
+                    sync_completed_jobs_main_world,
+                    despawn_done_jobs,
+                )
+                    .chain(),
+            )
+            .add_systems(Last, (release_deferred_jobs, handle_app_exit).chain());
 
         if let Some(render_app) = app.get_sub_app_mut(RenderApp) {
             let (sender, receiver) = crossbeam_channel::unbounded();
             render_app
+                .init_resource::<JobDeviceLost>()
+                .insert_resource(memory_usage)
                 .insert_resource(JobResultSender(sender))
                 .insert_resource(JobResultReceiver(receiver))
-                .insert_resource(JobResultMainWorldSender(main_sender));
+                .insert_resource(JobResultMainWorldSender(main_sender))
+                .insert_resource(JobReadyMainWorldSender(ready_sender));
 
-            render_app.add_systems(ExtractSchedule, extract_job_meta);
-
-            render_app.configure_sets(
-                Render,
-                (
-                    JobSet::Setup,
-                    JobSet::Check,
-                    JobSet::Execute,
-                    JobSet::Cleanup,
-                )
-                    .chain(),
+            render_app.add_systems(
+                ExtractSchedule,
+                (extract_job_meta, extract_despawned_job_targets),
             );
 
-            render_app.configure_sets(
-                Render,
-                (
-                    JobSet::Check.after(RenderSet::Prepare),
-                    JobSet::Execute.before(RenderSet::Render),
-                    JobSet::Cleanup.in_set(RenderSet::Cleanup),
-                ),
-            );
+            render_app.edit_schedule(Render, configure_job_sets);
 
             render_app.add_systems(
                 Render,
                 (
+                    recover_lost_device.in_set(JobSet::Setup),
                     setup_time_out_frames.in_set(JobSet::Setup),
+                    setup_interval_frames.in_set(JobSet::Setup),
+                    cancel_orphaned_jobs
+                        .in_set(JobSet::Check)
+                        .before(check_job_inputs),
                     check_job_inputs.in_set(JobSet::Check),
                     time_out_jobs.in_set(JobSet::Check),
+                    warn_stalled_jobs.in_set(JobSet::Check),
                     run_jobs.in_set(JobSet::Execute),
                     increment_time_out_frames.in_set(JobSet::Cleanup),
+                    increment_interval_frames.in_set(JobSet::Cleanup),
                     sync_completed_jobs.in_set(JobSet::Cleanup),
                 ),
             );
         }
     }
+
+    fn finish(&self, app: &mut App) {
+        if let Some(render_app) = app.get_sub_app_mut(RenderApp) {
+            watch_device_lost(render_app.world());
+        }
+    }
 }
 
 /// Settings for how jobs are scheduled each frame
@@ -150,11 +360,64 @@ impl Plugin for GraphicsJobsPlugin {
 pub struct JobExecutionSettings {
     /// The maximum number of jobs to execute each frame. This number
     /// may be exceeded in the case that a large number of jobs are
-    /// queued with [`Priority::Critical`].
+    /// queued with [`Priority::Critical`]. Only jobs whose inputs
+    /// are ready count towards this limit, so jobs that are still
+    /// waiting never keep ready jobs from running.
     pub max_jobs_per_frame: u32,
     /// The maximum number of frames a job should wait to execute
     /// before timing out.
     pub time_out_frames: u32,
+    /// The maximum number of extra submissions made each frame for jobs
+    /// that yield between chunks of work. Once exceeded, yielding jobs
+    /// are resumed on the next frame. See [`GraphicsJob::run_chunk`].
+    pub max_submits_per_frame: u32,
+    /// What to do with in-flight jobs when the GPU device is lost.
+    pub on_device_lost: DeviceLostPolicy,
+    /// The maximum total [`JobTransientMemory`](meta::JobTransientMemory) of the jobs executed each frame, or
+    /// `None` for no limit. Jobs that would exceed this are deferred to a later frame.
+    /// Like [`max_jobs_per_frame`](Self::max_jobs_per_frame), this may be exceeded by
+    /// [critical](meta::JobPriority::critical) jobs, or by a single job that's larger
+    /// than the budget.
+    pub max_transient_bytes: Option<u64>,
+    /// What to do with pending jobs when the app exits.
+    pub on_exit: JobShutdownPolicy,
+    /// Whether jobs that others depend on are prioritized. Off by default.
+    pub dependency_priority: meta::JobDependencyPriority,
+    /// How ready jobs are picked each frame. Defaults to admitting them by priority.
+    pub admission: meta::JobAdmission,
+    /// The number of frames a completed job's main world entity is kept for, with a
+    /// [`JobDone`] holding its result, before it's despawned. Jobs that pending jobs
+    /// still depend on are kept until those are done too. Defaults to 0, which
+    /// despawns completed jobs right away.
+    pub done_retention_frames: u32,
+    /// What to do when an internal invariant doesn't hold while a job runs. Defaults to
+    /// panicking, but production builds may prefer to fail the job and keep going.
+    pub on_invariant_violation: InvariantViolation,
+    /// Where the device is polled for readback buffers that finished mapping. Defaults
+    /// to polling on the render thread. See [`ReadbackPolling`](input::ReadbackPolling).
+    pub readback_polling: input::ReadbackPolling,
+    /// A target for the total GPU time of the jobs executed each frame, or `None` to
+    /// always execute up to [`max_jobs_per_frame`](Self::max_jobs_per_frame). When set,
+    /// the number of jobs executed each frame adapts to the measured GPU time of recent
+    /// jobs, starting from `max_jobs_per_frame`. See [`JobTimeBudget`].
+    pub time_budget: Option<JobTimeBudget>,
+    /// A limit on the CPU time spent recording jobs each frame, or `None` for no limit.
+    /// When set, the time each job's [`run`](GraphicsJob::run) takes is measured, and once
+    /// the frame's total exceeds the limit, the remaining ready jobs are deferred to the
+    /// next frame. This complements [`time_budget`](Self::time_budget), which limits GPU
+    /// time instead. Critical jobs are still executed, and at least one job is always
+    /// recorded, so that a single slow job isn't deferred forever.
+    pub max_recording_time: Option<Duration>,
+    /// The number of recent status transitions kept in [`JobTransitions`], or 0 to
+    /// record none. Defaults to 256.
+    pub transition_history: usize,
+    /// The number of frames a job waits on its inputs before a warning names the inputs
+    /// it's still waiting on, or `None` to never warn. Each job type is only warned
+    /// about once. Defaults to 8, half of the default
+    /// [`time_out_frames`](Self::time_out_frames), so that jobs stalled on an input that
+    /// never becomes ready, like a pipeline that fails to compile, are reported before
+    /// they time out.
+    pub stall_warning_frames: Option<u32>,
 }
 
 impl Default for JobExecutionSettings {
@@ -162,18 +425,64 @@ impl Default for JobExecutionSettings {
         Self {
             max_jobs_per_frame: 16,
             time_out_frames: 16,
+            max_submits_per_frame: 16,
+            on_device_lost: DeviceLostPolicy::Fail,
+            max_transient_bytes: None,
+            on_exit: JobShutdownPolicy::Drop,
+            dependency_priority: meta::JobDependencyPriority::Off,
+            admission: meta::JobAdmission::Ordered,
+            done_retention_frames: 0,
+            on_invariant_violation: InvariantViolation::Panic,
+            readback_polling: input::ReadbackPolling::RenderThread,
+            time_budget: None,
+            max_recording_time: None,
+            transition_history: 256,
+            stall_warning_frames: Some(8),
         }
     }
 }
 
 /// A plugin that sets up logic for a specific implementation of [`GraphicsJob`].
 /// It's recommended to call [`init_graphics_job`](crate::ext::InitGraphicsJobExt::init_graphics_job)
-/// on [`App`] rather than add this plugin manually.
-pub struct SpecializedGraphicsJobPlugin<J: GraphicsJob>(PhantomData<J>);
+/// on [`App`] rather than add this plugin manually, unless the job type has defaults
+/// to register with [`with_default`](Self::with_default).
+pub struct SpecializedGraphicsJobPlugin<J: GraphicsJob> {
+    defaults: Vec<Box<dyn Fn(&mut App) + Send + Sync>>,
+    _job: PhantomData<J>,
+}
 
 impl<J: GraphicsJob> Default for SpecializedGraphicsJobPlugin<J> {
     fn default() -> Self {
-        Self(PhantomData)
+        Self {
+            defaults: Vec::new(),
+            _job: PhantomData,
+        }
+    }
+}
+
+impl<J: GraphicsJob> SpecializedGraphicsJobPlugin<J> {
+    /// Inserts the component returned by `default` on every spawned job of this type
+    /// that doesn't already have one, as a [required component](Component#required-components)
+    /// of `J`. This keeps components that every job of a type needs, like its
+    /// [`JobPriority`](meta::JobPriority) or the key of its pipeline, in one place,
+    /// while still letting each job override them when it's spawned.
+    ///
+    /// ```ignore
+    /// app.add_plugins(
+    ///     SpecializedGraphicsJobPlugin::<BlurJob>::default()
+    ///         .with_default(|| JobPriority::non_critical::<4>())
+    ///         .with_default(|| JobRenderPipeline::<BlurPipeline>(BlurKey::default())),
+    /// );
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Building the plugin panics if `C` is registered twice for the same job type.
+    pub fn with_default<C: Component>(mut self, default: fn() -> C) -> Self {
+        self.defaults.push(Box::new(move |app| {
+            app.register_required_components_with::<J, C>(default);
+        }));
+        self
     }
 }
 
@@ -182,11 +491,16 @@ impl<J: GraphicsJob> Plugin for SpecializedGraphicsJobPlugin<J> {
         app.add_plugins(<J as GraphicsJob>::In::plugin());
 
         app.register_required_components::<J, JobMarker>();
+        for default in &self.defaults {
+            default(app);
+        }
+        register_job_type::<J>(app);
 
         if let Some(render_app) = app.get_sub_app_mut(RenderApp) {
             render_app
                 .add_systems(ExtractSchedule, extract_jobs::<J>)
-                .add_systems(Render, erase_jobs::<J>.in_set(JobSet::Setup));
+                .add_systems(Render, erase_jobs::<J>.in_set(JobSet::Setup))
+                .edit_schedule(Render, <J as GraphicsJob>::In::configure_prepare_sets);
         }
     }
 }
@@ -196,7 +510,7 @@ impl<J: GraphicsJob> Plugin for SpecializedGraphicsJobPlugin<J> {
 pub struct JobComplete(pub Result<(), JobError>);
 
 /// Describes how an incomplete job may have failed.
-#[derive(Copy, Clone, Debug)]
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
 pub enum JobError {
     /// Signals a job that failed due to timing out, either
     /// because its needed resources were not ready in time,
@@ -208,6 +522,15 @@ pub enum JobError {
     InputsFailed,
     /// Signals a job that failed during execution.
     ExecutionFailed,
+    /// Signals a job that was in flight when the GPU device was lost.
+    /// See [`JobExecutionSettings::on_device_lost`].
+    DeviceLost,
+    /// Signals a job that was failed because one of the crate's internal invariants
+    /// didn't hold while it ran. See [`JobExecutionSettings::on_invariant_violation`].
+    InvariantViolated,
+    /// Signals a job that was cancelled before it completed, along with the rest of
+    /// its task. See [`cancel_task`].
+    Cancelled,
 }
 
 fn extract_jobs<J: GraphicsJob>(
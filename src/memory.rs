@@ -0,0 +1,173 @@
+use std::sync::{
+    atomic::{AtomicU64, Ordering},
+    Arc,
+};
+
+use bevy_ecs::system::Resource;
+use bevy_render::render_resource::TextureDescriptor;
+
+/// A category of GPU memory owned by the job system, reported by [`JobMemoryUsage`].
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+pub enum JobMemoryCategory {
+    /// Textures created for a single job, like a [`JobOffscreenTarget`](crate::input::JobOffscreenTarget),
+    /// and the depth copies made for [`JobDepthCopy`](crate::input::JobDepthCopy).
+    ScratchTextures,
+    /// The buffers written each frame by [`JobFrameUniform`](crate::input::JobFrameUniform),
+    /// and the parameters uploaded by [`JobViewParams`](crate::input::JobViewParams).
+    UniformBuffers,
+    /// The staging buffers copied into by [`JobReadback`](crate::input::JobReadback).
+    ReadbackStaging,
+    /// Resources kept between jobs, like the arrays assembled by
+    /// [`JobImageArray`](crate::input::JobImageArray).
+    Persistent,
+}
+
+impl JobMemoryCategory {
+    const ALL: [Self; 4] = [
+        Self::ScratchTextures,
+        Self::UniformBuffers,
+        Self::ReadbackStaging,
+        Self::Persistent,
+    ];
+
+    fn index(self) -> usize {
+        self as usize
+    }
+}
+
+/// The GPU memory currently held by the job system's own resources, in bytes, by
+/// [`JobMemoryCategory`]. Resources created by jobs themselves aren't counted.
+///
+/// This is a resource in both the main world and the render world, sharing the same
+/// counters, which are updated as resources are created and dropped. Sizes are
+/// estimated from the resources' descriptors, so they don't include any padding or
+/// alignment added by the driver. This complements
+/// [`JobExecutionSettings::max_transient_bytes`](crate::JobExecutionSettings::max_transient_bytes),
+/// for tuning limits.
+#[derive(Clone, Default, Resource)]
+pub struct JobMemoryUsage(Arc<[AtomicU64; 4]>);
+
+impl JobMemoryUsage {
+    /// The bytes currently held in the given category.
+    pub fn bytes(&self, category: JobMemoryCategory) -> u64 {
+        self.0[category.index()].load(Ordering::Relaxed)
+    }
+
+    /// The bytes currently held in every category.
+    pub fn breakdown(&self) -> JobMemoryBreakdown {
+        JobMemoryBreakdown {
+            scratch_textures: self.bytes(JobMemoryCategory::ScratchTextures),
+            uniform_buffers: self.bytes(JobMemoryCategory::UniformBuffers),
+            readback_staging: self.bytes(JobMemoryCategory::ReadbackStaging),
+            persistent: self.bytes(JobMemoryCategory::Persistent),
+        }
+    }
+
+    /// The bytes currently held across all categories.
+    pub fn total(&self) -> u64 {
+        JobMemoryCategory::ALL
+            .into_iter()
+            .map(|category| self.bytes(category))
+            .sum()
+    }
+
+    /// Counts `bytes` towards `category` until the returned allocation is dropped,
+    /// which should be stored alongside the resource it describes.
+    pub(crate) fn allocate(&self, category: JobMemoryCategory, bytes: u64) -> JobMemoryAllocation {
+        self.0[category.index()].fetch_add(bytes, Ordering::Relaxed);
+        JobMemoryAllocation {
+            usage: self.clone(),
+            category,
+            bytes,
+        }
+    }
+}
+
+/// A snapshot of [`JobMemoryUsage`], in bytes.
+#[derive(Copy, Clone, Default, PartialEq, Eq, Debug)]
+pub struct JobMemoryBreakdown {
+    /// See [`JobMemoryCategory::ScratchTextures`].
+    pub scratch_textures: u64,
+    /// See [`JobMemoryCategory::UniformBuffers`].
+    pub uniform_buffers: u64,
+    /// See [`JobMemoryCategory::ReadbackStaging`].
+    pub readback_staging: u64,
+    /// See [`JobMemoryCategory::Persistent`].
+    pub persistent: u64,
+}
+
+impl JobMemoryBreakdown {
+    /// The bytes held across all categories.
+    pub fn total(&self) -> u64 {
+        self.scratch_textures + self.uniform_buffers + self.readback_staging + self.persistent
+    }
+}
+
+/// Memory counted towards a [`JobMemoryUsage`], until this is dropped.
+pub(crate) struct JobMemoryAllocation {
+    usage: JobMemoryUsage,
+    category: JobMemoryCategory,
+    bytes: u64,
+}
+
+impl Drop for JobMemoryAllocation {
+    fn drop(&mut self) {
+        self.usage.0[self.category.index()].fetch_sub(self.bytes, Ordering::Relaxed);
+    }
+}
+
+/// Estimates the memory used by a texture with the given descriptor, with every
+/// mip level and sample.
+pub(crate) fn texture_bytes(descriptor: &TextureDescriptor) -> u64 {
+    let (block_width, block_height) = descriptor.format.block_dimensions();
+    // combined depth-stencil formats have no single block size, so assume the largest
+    let block_size = descriptor.format.block_copy_size(None).unwrap_or(8) as u64;
+
+    let bytes = (0..descriptor.mip_level_count)
+        .map(|mip_level| {
+            let size = descriptor
+                .size
+                .mip_level_size(mip_level, descriptor.dimension);
+            size.width.div_ceil(block_width) as u64
+                * size.height.div_ceil(block_height) as u64
+                * size.depth_or_array_layers as u64
+                * block_size
+        })
+        .sum::<u64>();
+
+    bytes * descriptor.sample_count as u64
+}
+
+#[cfg(test)]
+mod test {
+    use super::{JobMemoryBreakdown, JobMemoryCategory, JobMemoryUsage};
+
+    #[test]
+    fn allocations_are_counted_until_dropped() {
+        let usage = JobMemoryUsage::default();
+        // the main world and render world share the same counters
+        let render_usage = usage.clone();
+
+        let target = render_usage.allocate(JobMemoryCategory::ScratchTextures, 1024);
+        let staging = render_usage.allocate(JobMemoryCategory::ReadbackStaging, 256);
+        let array = render_usage.allocate(JobMemoryCategory::Persistent, 4096);
+        assert_eq!(
+            usage.breakdown(),
+            JobMemoryBreakdown {
+                scratch_textures: 1024,
+                uniform_buffers: 0,
+                readback_staging: 256,
+                persistent: 4096,
+            }
+        );
+        assert_eq!(usage.total(), 1024 + 256 + 4096);
+
+        drop(target);
+        drop(staging);
+        assert_eq!(usage.bytes(JobMemoryCategory::ScratchTextures), 0);
+        assert_eq!(usage.total(), 4096);
+
+        drop(array);
+        assert_eq!(usage.breakdown(), JobMemoryBreakdown::default());
+    }
+}
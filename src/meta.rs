@@ -1,15 +1,29 @@
 use std::{
     cmp::Ordering,
+    hash::Hash,
     num::NonZero,
     ops::{Add, AddAssign},
+    sync::Arc,
 };
 
 use bevy_ecs::{
     component::Component,
-    query::Added,
-    system::{Commands, Query},
+    entity::{Entities, Entity},
+    observer::Trigger,
+    query::{Added, Changed, Has, Or, With, Without},
+    removal_detection::RemovedComponents,
+    system::{Commands, Local, Query, Resource, SystemParam},
+    world::{EntityRef, OnAdd, World},
 };
-use bevy_render::{sync_world::RenderEntity, Extract};
+use bevy_render::{
+    extract_resource::ExtractResource,
+    sync_world::{MainEntity, RenderEntity},
+    Extract,
+};
+use bevy_utils::{HashMap, HashSet};
+use disqualified::ShortName;
+
+use crate::input::{JobOutputLabel, JobSeed};
 
 /// The priority level of a graphics job.
 ///
@@ -23,6 +37,7 @@ use bevy_render::{sync_world::RenderEntity, Extract};
 /// Jobs propagate their priority to their dependencies additively, so jobs with many
 /// dependents are prioritized.
 #[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
 pub enum Priority {
     Critical,
     NonCritical(NonZero<u32>),
@@ -70,13 +85,16 @@ impl AddAssign for Priority {
     }
 }
 
-/// A generic marker for all graphics jobs.
+/// A generic marker for all graphics jobs. This is required by every job
+/// component, and in turn requires the job's metadata, so that a job spawned
+/// on its own still gets a default [`JobPriority`].
 #[derive(Component, Default)]
 #[require(JobPriority)]
 pub struct JobMarker;
 
 /// Sets the execution priority for a scheduled job.
 #[derive(Copy, Clone, Component, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
 pub struct JobPriority(pub Priority);
 
 impl JobPriority {
@@ -89,10 +107,7 @@ impl JobPriority {
     pub const fn non_critical<const WEIGHT: u32>() -> Self {
         const {
             assert!(WEIGHT > 0);
-            //SAFETY: WEIGHT is not zero
-            Self(Priority::NonCritical(unsafe {
-                NonZero::new_unchecked(WEIGHT)
-            }))
+            Self(Priority::NonCritical(NonZero::new(WEIGHT).unwrap()))
         }
     }
 
@@ -102,20 +117,538 @@ impl JobPriority {
     }
 }
 
+/// Bounds the priority a job is scheduled with, regardless of the [`JobPriority`]
+/// it's spawned with. This is useful when priorities are computed elsewhere, for
+/// example from the number of jobs waiting on this one, and could otherwise grow
+/// far enough to always preempt every other job.
+///
+/// If `min` is greater than `max`, jobs are scheduled with `max`.
+#[derive(Copy, Clone, Component, PartialEq, Eq, Hash, Debug)]
+pub struct JobPriorityClamp {
+    pub min: Priority,
+    pub max: Priority,
+}
+
+impl Default for JobPriorityClamp {
+    fn default() -> Self {
+        Self {
+            min: Priority::default(),
+            max: Priority::Critical,
+        }
+    }
+}
+
+impl JobPriorityClamp {
+    /// Clamps a job's priority to this range. Unlike [`Ord::clamp`], this doesn't
+    /// panic if the range is empty.
+    #[inline]
+    pub fn clamp(&self, priority: JobPriority) -> JobPriority {
+        JobPriority(priority.0.max(self.min).min(self.max))
+    }
+}
+
+/// Raises the priority of jobs that other jobs are waiting on through
+/// [`JobDependency`](crate::input::JobDependency), so that jobs at the bottom of
+/// deep dependency chains run first and unblock the rest. Set it with
+/// [`JobExecutionSettings::dependency_priority`](crate::JobExecutionSettings::dependency_priority).
+///
+/// A job is raised by one [`Priority::NonCritical`] weight for each job it unblocks.
+/// This is added on top of the job's [`JobPriority`], after any [`JobPriorityClamp`].
+#[derive(Copy, Clone, Default, PartialEq, Eq, Hash, Debug)]
+pub enum JobDependencyPriority {
+    /// Jobs are scheduled with their own priority only.
+    #[default]
+    Off,
+    /// Counts the jobs waiting directly on a job's [`JobOutput`](crate::input::JobOutput).
+    Dependents,
+    /// Counts every job waiting on a job's output, directly or through other jobs.
+    Subtree,
+}
+
+/// How the ready jobs admitted each frame are picked, up to
+/// [`JobExecutionSettings::max_jobs_per_frame`](crate::JobExecutionSettings::max_jobs_per_frame).
+/// Set it with [`JobExecutionSettings::admission`](crate::JobExecutionSettings::admission).
+/// A [`JobComparator`] takes precedence over either mode.
+#[derive(Copy, Clone, Default, PartialEq, Eq, Hash, Debug)]
+pub enum JobAdmission {
+    /// Jobs are admitted from highest to lowest priority.
+    #[default]
+    Ordered,
+    /// Jobs are admitted in a random order, in which each job is picked before the
+    /// others with probability proportional to its [`Priority::NonCritical`] weight, for
+    /// stress-testing the scheduler or avoiding lockstep patterns between jobs. Critical
+    /// jobs are still always admitted.
+    ///
+    /// The order is drawn from the seed and the frame count, so runs with the same seed
+    /// and the same jobs admit them the same way. Replays of a
+    /// [`JobQueueSnapshot`](crate::JobQueueSnapshot) count frames from the snapshot.
+    WeightedRandom(JobSeed),
+}
+
+/// The key a job is sorted by for [`JobAdmission::WeightedRandom`], highest first. Each
+/// job draws `u` uniformly from `(0, 1]`, and sorting by `u^(1 / weight)` picks jobs in
+/// proportion to their weights. The logarithm keeps the same order without underflowing.
+pub(crate) fn weighted_admission_key(priority: JobPriority, seed: JobSeed) -> f64 {
+    match priority.0 {
+        Priority::Critical => f64::INFINITY,
+        Priority::NonCritical(weight) => {
+            let u = ((seed.get() >> 11) + 1) as f64 / (1u64 << 53) as f64;
+            u.ln() / weight.get() as f64
+        }
+    }
+}
+
+/// The number of jobs waiting on each output label, for [`JobDependencyPriority`].
+/// Labels are [`JobOutputLabel`]s in the render world, but may be anything that
+/// identifies an output, for example when replaying a [`JobQueueSnapshot`](crate::JobQueueSnapshot).
+pub(crate) struct DependentCounts<L = JobOutputLabel>(HashMap<L, u32>);
+
+impl<L: Copy + Eq + Hash> DependentCounts<L> {
+    /// Counts the dependents of each label from every pending job's dependency, along
+    /// with the label of the job's own output, if it has one.
+    pub fn new(
+        strategy: JobDependencyPriority,
+        dependents: impl IntoIterator<Item = (L, Option<L>)>,
+    ) -> Self {
+        if strategy == JobDependencyPriority::Off {
+            return Self(HashMap::default());
+        }
+
+        let mut consumers = HashMap::<L, Vec<Option<L>>>::default();
+        for (dependency, output) in dependents {
+            consumers.entry(dependency).or_default().push(output);
+        }
+
+        let mut counts = HashMap::default();
+        for label in consumers.keys() {
+            match strategy {
+                JobDependencyPriority::Dependents => {
+                    counts.insert(*label, consumers[label].len() as u32);
+                }
+                _ => {
+                    subtree_size(*label, &consumers, &mut counts);
+                }
+            }
+        }
+        Self(counts)
+    }
+
+    /// The number of jobs waiting on the given output.
+    pub fn count(&self, output: Option<L>) -> u32 {
+        output
+            .and_then(|output| self.0.get(&output))
+            .copied()
+            .unwrap_or(0)
+    }
+
+    /// Raises a job's priority by the number of jobs waiting on its output.
+    pub fn raise(&self, priority: JobPriority, output: Option<L>) -> JobPriority {
+        match output
+            .and_then(|output| self.0.get(&output))
+            .and_then(|count| NonZero::new(*count))
+        {
+            Some(count) => JobPriority(priority.0 + Priority::NonCritical(count)),
+            None => priority,
+        }
+    }
+}
+
+/// Counts the jobs waiting on `label`, directly or transitively. Each label is only
+/// counted once, so the whole graph is counted in a single pass. Labels in a cycle
+/// don't count the jobs of the cycle twice.
+fn subtree_size<L: Copy + Eq + Hash>(
+    label: L,
+    consumers: &HashMap<L, Vec<Option<L>>>,
+    counts: &mut HashMap<L, u32>,
+) -> u32 {
+    if let Some(count) = counts.get(&label) {
+        return *count;
+    }
+    // guards against cycles while this label's consumers are counted
+    counts.insert(label, 0);
+
+    let count = consumers.get(&label).map_or(0, |outputs| {
+        outputs.iter().fold(0u32, |count, output| {
+            let below = output.map_or(0, |output| subtree_size(output, consumers, counts));
+            count.saturating_add(1).saturating_add(below)
+        })
+    });
+    counts.insert(label, count);
+    count
+}
+
+/// Declares how many bytes of transient GPU memory, like job-owned textures and
+/// buffers, a job allocates while it runs. The scheduler uses this to keep the total
+/// under [`JobExecutionSettings::max_transient_bytes`](crate::JobExecutionSettings::max_transient_bytes),
+/// deferring jobs that would exceed it. Jobs without this component are assumed
+/// not to allocate anything.
+#[derive(Copy, Clone, Component, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+pub struct JobTransientMemory(pub u64);
+
+/// Groups jobs into a task, like the jobs of a single asset bake, so that they can be
+/// waited on together with [`flush_task`](crate::RunGraphicsJobExt::flush_task), or
+/// cancelled together with [`cancel_task`](crate::cancel_task). Tasks only exist in the main world, and don't affect how jobs are scheduled.
+#[derive(Copy, Clone, Component, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+pub struct JobTask(pub u32);
+
+/// Marks a job that needs the GPU to itself, like a heavy bake or a job being profiled.
+/// When an exclusive job is admitted, it runs alone in its frame, and every other job,
+/// including critical ones, waits until the next frame. If several exclusive jobs are
+/// ready, they run one per frame, from highest to lowest priority.
+#[derive(Copy, Clone, Component, Default, PartialEq, Eq, Hash, Debug)]
+pub struct JobExclusive;
+
+/// Picks the GPU a job runs on, in setups with more than one. Jobs run on bevy's own
+/// device unless they're pinned to a [`JobSecondaryDevice`](crate::JobSecondaryDevice),
+/// see its docs for the limitations of running jobs there.
+#[derive(Copy, Clone, Component, Default, PartialEq, Eq, Hash, Debug)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub enum JobAdapter {
+    /// Runs on the device bevy renders with.
+    #[default]
+    Primary,
+    /// Runs on the [`JobSecondaryDevice`](crate::JobSecondaryDevice), or on the primary
+    /// device if there isn't one.
+    Secondary,
+}
+
+impl JobAdapter {
+    /// The adapter a job actually runs on, given whether a secondary device exists.
+    pub fn resolve(self, has_secondary: bool) -> Self {
+        match self {
+            JobAdapter::Secondary if has_secondary => JobAdapter::Secondary,
+            _ => JobAdapter::Primary,
+        }
+    }
+}
+
+/// Runs a job periodically rather than once, on every `every_n_frames`th frame
+/// counting from the frame it was spawned on, offset by `phase` frames. Between its
+/// frames the job stays [`Waiting`](crate::JobStatus::Waiting), without checking its
+/// inputs or taking up the frame's budget, which is cheaper than spawning a new job
+/// on a timer. Frames are counted from each job's spawn, so several interval jobs
+/// spawned together can be spread across frames by giving them different phases,
+/// rather than all running on the same frame.
+///
+/// After each successful run, the job is queued again instead of completing, and any
+/// result it set is discarded. A failed or timed out run completes the job as usual.
+/// Interval jobs time out after waiting [`time_out_frames`](crate::JobExecutionSettings::time_out_frames)
+/// of their own intervals.
+#[derive(Copy, Clone, Component, PartialEq, Eq, Hash, Debug)]
+pub struct JobInterval {
+    /// The number of frames between each run. Zero is treated as one.
+    pub every_n_frames: u32,
+    /// The frame of each interval the job runs on, wrapping around `every_n_frames`.
+    pub phase: u32,
+}
+
+impl JobInterval {
+    /// Runs the job every `every_n_frames` frames, starting on the frame it's spawned.
+    pub const fn new(every_n_frames: u32) -> Self {
+        Self {
+            every_n_frames,
+            phase: 0,
+        }
+    }
+
+    /// Offsets the frames the job runs on by `phase` frames.
+    pub const fn with_phase(mut self, phase: u32) -> Self {
+        self.phase = phase;
+        self
+    }
+
+    /// Whether the job runs on the given frame, counting from the frame it was spawned.
+    pub fn is_due(&self, frame: u32) -> bool {
+        let every_n_frames = self.every_n_frames.max(1);
+        frame % every_n_frames == self.phase % every_n_frames
+    }
+}
+
+/// Defers extracting a job until a main world condition holds, for jobs spawned ahead
+/// of time, for example to hold on to their entity, that shouldn't run until some
+/// gameplay condition is met. The predicate is checked at the end of every frame, and
+/// once it passes, the job is extracted and runs as if it was spawned that frame.
+///
+/// Unlike an input that waits in the render world, a deferred job isn't sent to the
+/// render world at all, and isn't pending in the meantime: it doesn't time out, isn't
+/// waited on by [`flush_task`](crate::RunGraphicsJobExt::flush_task) or on exit, and
+/// its [`JobSequence`] is assigned when it's released.
+#[derive(Clone, Component)]
+pub struct ExtractWhen(Arc<dyn Fn(&World) -> bool + Send + Sync>);
+
+impl ExtractWhen {
+    /// Defers the job until `predicate` returns `true`.
+    pub fn new(predicate: impl Fn(&World) -> bool + Send + Sync + 'static) -> Self {
+        Self(Arc::new(predicate))
+    }
+}
+
+/// Holds back a job with [`ExtractWhen`] by removing its [`JobMarker`], which every
+/// job's extraction is filtered on.
+pub(super) fn defer_extraction(trigger: Trigger<OnAdd, ExtractWhen>, mut commands: Commands) {
+    commands.entity(trigger.entity()).remove::<JobMarker>();
+}
+
+/// Defers extracting a job until each of the given jobs completes successfully, for
+/// jobs that consume the results of others without publishing a
+/// [`JobOutput`](crate::input::JobOutput). If any of them fails, the job fails with
+/// [`JobError::InputsFailed`](crate::JobError::InputsFailed) without running, which in
+/// turn fails the jobs waiting on it.
+///
+/// The jobs are main world entities, which must complete after this is added, so it
+/// should be added as the job is spawned. Like [`ExtractWhen`], a job waiting on others
+/// isn't pending in the meantime. To spawn a chain of jobs, see
+/// [`SpawnJobChainExt`](crate::SpawnJobChainExt).
+#[derive(Clone, Component, PartialEq, Eq, Debug)]
+pub struct JobAfter(pub Vec<Entity>);
+
+impl JobAfter {
+    /// Defers the job until `job` completes.
+    pub fn new(job: Entity) -> Self {
+        Self(vec![job])
+    }
+}
+
+/// Holds back a job with [`JobAfter`], like [`defer_extraction`].
+pub(super) fn defer_chained_extraction(trigger: Trigger<OnAdd, JobAfter>, mut commands: Commands) {
+    commands.entity(trigger.entity()).remove::<JobMarker>();
+}
+
+/// Releases the deferred jobs whose [`ExtractWhen`] passes. Their [`JobMarker`] is
+/// added back, so they match `Added<JobMarker>` when they're extracted right after.
+/// Jobs still waiting on a [`JobAfter`] are released once it's removed instead.
+pub(super) fn release_deferred_jobs(world: &mut World) {
+    let released = world
+        .query_filtered::<(Entity, &ExtractWhen), Without<JobAfter>>()
+        .iter(world)
+        .filter(|(_, when)| (when.0)(world))
+        .map(|(job, _)| job)
+        .collect::<Vec<_>>();
+
+    for job in released {
+        world
+            .entity_mut(job)
+            .remove::<ExtractWhen>()
+            .insert(JobMarker);
+    }
+}
+
+/// Cancels a job once its target, like the camera or mesh it renders for, is despawned,
+/// rather than leaving it waiting on inputs that reference a dead entity until it times
+/// out. The target is a main world entity, looked up each frame as the job is extracted,
+/// and the job is cancelled in [`JobSet::Check`](crate::JobSet::Check), before it's
+/// checked for readiness.
+///
+/// The job completes with [`JobError::Cancelled`](crate::JobError::Cancelled) like any
+/// other failed job, so jobs waiting on it with [`JobAfter`] fail in turn, and jobs
+/// waiting on its [`JobOutput`](crate::input::JobOutput) keep waiting for another job to
+/// publish it, and time out otherwise.
+#[derive(Copy, Clone, Component, PartialEq, Eq, Hash, Debug)]
+pub struct CancelOnTargetDespawn(pub MainEntity);
+
+impl CancelOnTargetDespawn {
+    /// Cancels the job once `target` is despawned.
+    pub fn new(target: Entity) -> Self {
+        Self(MainEntity::from(target))
+    }
+}
+
+/// Marks a job whose [`CancelOnTargetDespawn`] target was despawned, to be cancelled
+/// in the render world.
+#[derive(Copy, Clone, Component)]
+pub(crate) struct JobTargetDespawned;
+
+pub(super) fn extract_despawned_job_targets(
+    jobs: Extract<Query<(RenderEntity, &CancelOnTargetDespawn), With<JobMarker>>>,
+    entities: Extract<&Entities>,
+    mut commands: Commands,
+) {
+    for (render_entity, target) in &jobs {
+        if entities.contains(target.0.id()) {
+            continue;
+        }
+        if let Some(mut entity) = commands.get_entity(render_entity) {
+            entity.insert(JobTargetDespawned);
+        }
+    }
+}
+
+/// The order a job was spawned in, counting from zero. This is added to every job
+/// when it's spawned, and is extracted along with its other metadata.
+#[derive(Copy, Clone, Component, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+pub struct JobSequence(pub u64);
+
+pub(super) fn sequence_jobs(
+    trigger: Trigger<OnAdd, JobMarker>,
+    mut next: Local<u64>,
+    mut commands: Commands,
+) {
+    commands.entity(trigger.entity()).insert(JobSequence(*next));
+    *next += 1;
+}
+
+/// What a [`JobComparator`] knows about a ready job.
+pub struct JobMeta<'a> {
+    /// The job's render world entity, for reading the job's own component.
+    pub entity: EntityRef<'a>,
+    /// The job's [`label`](crate::GraphicsJob::label), which names its type.
+    pub label: ShortName<'static>,
+    /// The job's priority, after any [`JobPriorityClamp`].
+    pub priority: JobPriority,
+    /// The number of frames the job has waited, which it times out after.
+    pub waited_frames: u32,
+    /// The number of pending jobs waiting on the job's [`JobOutput`](crate::input::JobOutput),
+    /// directly or through other jobs.
+    pub dependents: u32,
+    /// The order the job was spawned in. See [`JobSequence`].
+    pub sequence: u64,
+}
+
+/// A custom order for admitting ready jobs each frame, for apps whose scheduling needs
+/// aren't captured by [`JobPriority`] and [`JobDependencyPriority`]. Jobs are sorted
+/// with the comparator, so that a job comparing [`Ordering::Less`] than another is
+/// admitted first, and then admitted up to the frame's limits as usual.
+///
+/// Critical jobs are still always admitted, and only the highest priority
+/// [`JobExclusive`] job is considered while one is ready. Replays of a
+/// [`JobQueueSnapshot`](crate::JobQueueSnapshot) use the built-in order.
+///
+/// The default has no comparator, which keeps the built-in order, from highest to
+/// lowest priority.
+#[derive(Clone, Default, Resource, ExtractResource)]
+pub struct JobComparator(Option<Arc<dyn Fn(&JobMeta, &JobMeta) -> Ordering + Send + Sync>>);
+
+impl JobComparator {
+    /// Sorts ready jobs with the given comparator.
+    pub fn new(compare: impl Fn(&JobMeta, &JobMeta) -> Ordering + Send + Sync + 'static) -> Self {
+        Self(Some(Arc::new(compare)))
+    }
+
+    pub(crate) fn get(&self) -> Option<&(dyn Fn(&JobMeta, &JobMeta) -> Ordering + Send + Sync)> {
+        self.0.as_deref()
+    }
+}
+
+/// Matches jobs that were just spawned, or whose metadata changed since the last
+/// extraction, for example when a job's priority is raised while it waits. Removed
+/// metadata isn't caught by change detection, and is found by [`extract_job_meta`]
+/// with [`RemovedComponents`] instead.
+type JobMetaChanged = Or<(
+    Added<JobMarker>,
+    Changed<JobPriority>,
+    Changed<JobPriorityClamp>,
+    Changed<JobTransientMemory>,
+    Added<JobExclusive>,
+    Changed<JobInterval>,
+    Changed<JobAdapter>,
+)>;
+
+/// The metadata of every job removed since the last extraction.
+#[derive(SystemParam)]
+pub(super) struct RemovedJobMeta<'w, 's> {
+    priorities: RemovedComponents<'w, 's, JobPriority>,
+    clamps: RemovedComponents<'w, 's, JobPriorityClamp>,
+    transient_memory: RemovedComponents<'w, 's, JobTransientMemory>,
+    exclusive: RemovedComponents<'w, 's, JobExclusive>,
+    intervals: RemovedComponents<'w, 's, JobInterval>,
+    adapters: RemovedComponents<'w, 's, JobAdapter>,
+}
+
+impl RemovedJobMeta<'_, '_> {
+    fn read(&mut self) -> impl Iterator<Item = Entity> + '_ {
+        self.priorities
+            .read()
+            .chain(self.clamps.read())
+            .chain(self.transient_memory.read())
+            .chain(self.exclusive.read())
+            .chain(self.intervals.read())
+            .chain(self.adapters.read())
+    }
+}
+
 pub(super) fn extract_job_meta(
-    jobs: Extract<Query<(RenderEntity, &JobPriority), Added<JobMarker>>>,
+    jobs: Extract<
+        Query<
+            (
+                RenderEntity,
+                Option<&JobPriority>,
+                Option<&JobPriorityClamp>,
+                Option<&JobTransientMemory>,
+                Has<JobExclusive>,
+                Option<&JobSequence>,
+                Option<&JobInterval>,
+                Option<&JobAdapter>,
+            ),
+            With<JobMarker>,
+        >,
+    >,
+    changed: Extract<Query<Entity, (JobMetaChanged, With<JobMarker>)>>,
+    mut removed: Extract<RemovedJobMeta>,
     mut commands: Commands,
 ) {
-    for (render_entity, priority) in &jobs {
-        commands.entity(render_entity).insert(*priority);
+    let extracted = changed.iter().chain(removed.read()).collect::<HashSet<_>>();
+
+    // `JobPriority` is required by `JobMarker`, but may have been removed manually.
+    // Fall back to the default so that the job still runs.
+    for (
+        render_entity,
+        priority,
+        clamp,
+        transient_memory,
+        exclusive,
+        sequence,
+        interval,
+        adapter,
+    ) in extracted.into_iter().filter_map(|job| jobs.get(job).ok())
+    {
+        // a job that completed in the render world may not have been despawned in
+        // the main world yet, so it can still change there
+        let Some(mut entity) = commands.get_entity(render_entity) else {
+            continue;
+        };
+        let priority = priority.copied().unwrap_or_default();
+        entity.insert(clamp.map_or(priority, |clamp| clamp.clamp(priority)));
+        match transient_memory {
+            Some(transient_memory) => entity.insert(*transient_memory),
+            None => entity.remove::<JobTransientMemory>(),
+        };
+        match exclusive {
+            true => entity.insert(JobExclusive),
+            false => entity.remove::<JobExclusive>(),
+        };
+        if let Some(sequence) = sequence {
+            entity.insert(*sequence);
+        }
+        match interval {
+            Some(interval) => entity.insert(*interval),
+            None => entity.remove::<JobInterval>(),
+        };
+        match adapter {
+            Some(adapter) => entity.insert(*adapter),
+            None => entity.remove::<JobAdapter>(),
+        };
     }
 }
 
 #[cfg(test)]
+#[allow(clippy::manual_repeat_n)]
 mod test {
     use std::{iter, num::NonZero};
 
-    use super::Priority;
+    use bevy_ecs::{
+        component::Component,
+        entity::Entity,
+        query::Added,
+        system::{Query, Resource},
+        world::{EntityWorldMut, World},
+    };
+    use bevy_render::{sync_world::RenderEntity, MainWorld};
+
+    use super::{
+        defer_extraction, extract_job_meta, release_deferred_jobs, sequence_jobs, ExtractWhen,
+        JobAdapter, JobMarker, JobMetaChanged, JobPriority, JobPriorityClamp, JobSequence,
+        JobTransientMemory, Priority,
+    };
 
     fn or_min(num: u32) -> NonZero<u32> {
         NonZero::new(num).unwrap_or(NonZero::<u32>::MIN)
@@ -163,4 +696,171 @@ mod test {
             sum_priorities(priorities.into_iter().chain(iter::once(Priority::Critical))).unwrap();
         assert_eq!(sum, Priority::Critical);
     }
+
+    #[derive(Component)]
+    struct BareJob;
+
+    #[test]
+    fn bare_jobs_get_default_meta() {
+        let mut world = World::new();
+        world.register_required_components::<BareJob, JobMarker>();
+
+        let job = world.spawn(BareJob).id();
+        assert!(world.get::<JobMarker>(job).is_some());
+        assert_eq!(world.get::<JobPriority>(job), Some(&JobPriority::default()));
+    }
+
+    #[test]
+    fn clamp_bounds_priority() {
+        let clamp = JobPriorityClamp {
+            min: Priority::NonCritical(or_min(2)),
+            max: Priority::NonCritical(or_min(8)),
+        };
+        assert_eq!(
+            clamp.clamp(JobPriority::default()),
+            JobPriority::non_critical::<2>()
+        );
+        assert_eq!(
+            clamp.clamp(JobPriority::non_critical::<5>()),
+            JobPriority::non_critical::<5>()
+        );
+        assert_eq!(
+            clamp.clamp(JobPriority::critical()),
+            JobPriority::non_critical::<8>()
+        );
+    }
+
+    #[test]
+    fn empty_clamp_uses_max() {
+        let clamp = JobPriorityClamp {
+            min: Priority::Critical,
+            max: Priority::NonCritical(or_min(4)),
+        };
+        assert_eq!(
+            clamp.clamp(JobPriority::default()),
+            JobPriority::non_critical::<4>()
+        );
+    }
+
+    #[test]
+    fn secondary_jobs_fall_back_to_the_primary_device() {
+        assert_eq!(JobAdapter::Secondary.resolve(true), JobAdapter::Secondary);
+        assert_eq!(JobAdapter::Secondary.resolve(false), JobAdapter::Primary);
+        assert_eq!(JobAdapter::Primary.resolve(true), JobAdapter::Primary);
+    }
+
+    #[test]
+    fn changed_priorities_are_extracted_again() {
+        let mut world = World::new();
+        let changed = world
+            .register_system(|jobs: Query<Entity, JobMetaChanged>| jobs.iter().collect::<Vec<_>>());
+
+        let job = world.spawn(JobMarker).id();
+        assert_eq!(world.run_system(changed).unwrap(), vec![job]);
+        assert_eq!(world.run_system(changed).unwrap(), vec![]);
+
+        *world.get_mut::<JobPriority>(job).unwrap() = JobPriority::non_critical::<4>();
+        assert_eq!(world.run_system(changed).unwrap(), vec![job]);
+        assert_eq!(world.run_system(changed).unwrap(), vec![]);
+    }
+
+    #[test]
+    fn removed_meta_is_extracted_again() {
+        let mut render_world = World::new();
+        render_world.init_resource::<MainWorld>();
+        let extract = render_world.register_system(extract_job_meta);
+        let render_job = render_world.spawn_empty().id();
+
+        let clamp = JobPriorityClamp {
+            min: Priority::Critical,
+            max: Priority::NonCritical(or_min(2)),
+        };
+        let job = render_world
+            .resource_mut::<MainWorld>()
+            .spawn((
+                JobMarker,
+                RenderEntity::from(render_job),
+                JobPriority::non_critical::<8>(),
+                clamp,
+                JobTransientMemory(64),
+            ))
+            .id();
+        let extract_after = |render_world: &mut World, change: fn(EntityWorldMut)| {
+            let mut main_world = render_world.resource_mut::<MainWorld>();
+            change(main_world.entity_mut(job));
+            main_world.increment_change_tick();
+            render_world.run_system(extract).unwrap();
+        };
+
+        extract_after(&mut render_world, |_| {});
+        let meta = |world: &World| {
+            (
+                world.get::<JobPriority>(render_job).copied(),
+                world.get::<JobTransientMemory>(render_job).copied(),
+            )
+        };
+        assert_eq!(
+            meta(&render_world),
+            (
+                Some(JobPriority::non_critical::<2>()),
+                Some(JobTransientMemory(64))
+            )
+        );
+
+        extract_after(&mut render_world, |mut job| {
+            job.insert(JobTransientMemory(128));
+        });
+        assert_eq!(meta(&render_world).1, Some(JobTransientMemory(128)));
+
+        extract_after(&mut render_world, |mut job| {
+            job.remove::<(JobPriorityClamp, JobTransientMemory)>();
+        });
+        assert_eq!(
+            meta(&render_world),
+            (Some(JobPriority::non_critical::<8>()), None)
+        );
+    }
+
+    #[test]
+    fn jobs_are_sequenced_in_spawn_order() {
+        let mut world = World::new();
+        world.add_observer(sequence_jobs);
+
+        let jobs = [(); 3].map(|()| world.spawn(JobMarker).id());
+        world.flush();
+        let sequences = jobs.map(|job| world.get::<JobSequence>(job).map(|sequence| sequence.0));
+        assert_eq!(sequences, [Some(0), Some(1), Some(2)]);
+    }
+
+    #[derive(Resource)]
+    struct Unlocked(bool);
+
+    #[test]
+    fn deferred_jobs_are_extracted_once_released() {
+        let mut world = World::new();
+        world.add_observer(sequence_jobs);
+        world.add_observer(defer_extraction);
+        world.insert_resource(Unlocked(false));
+        let added = world.register_system(|jobs: Query<Entity, Added<JobMarker>>| {
+            jobs.iter().collect::<Vec<_>>()
+        });
+
+        let job = world
+            .spawn((
+                JobMarker,
+                ExtractWhen::new(|world| world.resource::<Unlocked>().0),
+            ))
+            .id();
+        world.flush();
+        release_deferred_jobs(&mut world);
+        assert!(world.get::<JobMarker>(job).is_none());
+        assert_eq!(world.run_system(added).unwrap(), vec![]);
+
+        world.resource_mut::<Unlocked>().0 = true;
+        release_deferred_jobs(&mut world);
+        world.flush();
+        assert!(world.get::<ExtractWhen>(job).is_none());
+        assert_eq!(world.run_system(added).unwrap(), vec![job]);
+        assert_eq!(world.run_system(added).unwrap(), vec![]);
+    }
 }
@@ -0,0 +1,151 @@
+use core::any::TypeId;
+
+use bevy_app::App;
+use bevy_ecs::{
+    archetype::{Archetype, ArchetypeId},
+    component::{ComponentId, Components},
+    query::Added,
+    system::{Query, ResMut, Resource},
+};
+use bevy_utils::{tracing::warn, HashMap, HashSet};
+use disqualified::ShortName;
+
+use crate::{meta::JobMarker, GraphicsJob};
+
+/// The job component types registered with
+/// [`init_graphics_job`](crate::ext::InitGraphicsJobExt::init_graphics_job), used to
+/// diagnose jobs spawned with a type that was never registered, which would otherwise
+/// wait until they time out without ever running.
+#[derive(Resource, Default)]
+pub(crate) struct RegisteredJobs {
+    components: HashMap<ComponentId, JobType>,
+    /// The archetypes of unregistered jobs that were already warned about.
+    warned: HashSet<ArchetypeId>,
+}
+
+/// A registered job type, along with its [`GraphicsJob::label`].
+#[derive(Copy, Clone, Debug)]
+pub(crate) struct JobType {
+    pub type_id: TypeId,
+    pub label: ShortName<'static>,
+}
+
+impl RegisteredJobs {
+    /// The registered job type of a job with the given archetype, if it has one.
+    pub fn job_type(&self, archetype: &Archetype) -> Option<JobType> {
+        self.components
+            .iter()
+            .find(|(component, _)| archetype.contains(**component))
+            .map(|(_, job_type)| *job_type)
+    }
+
+    /// Iterates over every registered job type.
+    #[cfg_attr(not(feature = "diagnostics"), allow(dead_code))]
+    pub fn job_types(&self) -> impl Iterator<Item = JobType> + '_ {
+        self.components.values().copied()
+    }
+}
+
+pub(crate) fn register_job_type<J: GraphicsJob>(app: &mut App) {
+    let component = app.world_mut().register_component::<J>();
+    app.world_mut()
+        .get_resource_or_init::<RegisteredJobs>()
+        .components
+        .insert(
+            component,
+            JobType {
+                type_id: TypeId::of::<J>(),
+                label: J::label(),
+            },
+        );
+}
+
+/// Warns once for each set of components that was spawned as a job, but doesn't include
+/// a registered job type.
+pub(crate) fn warn_unregistered_jobs(
+    jobs: Query<&Archetype, Added<JobMarker>>,
+    mut registered: ResMut<RegisteredJobs>,
+    components: &Components,
+) {
+    for archetype in &jobs {
+        let is_registered = registered.job_type(archetype).is_some();
+        if is_registered || !registered.warned.insert(archetype.id()) {
+            continue;
+        }
+
+        let names = archetype
+            .components()
+            .filter_map(|component| components.get_name(component))
+            .collect::<Vec<_>>();
+        warn!(
+            "a job was spawned with components {names:?}, but none of them is a graphics job \
+            registered with `init_graphics_job`, so it will never run"
+        );
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use bevy_app::{App, Update};
+    use bevy_ecs::{component::Component, world::World};
+
+    use crate::{input::JobInputItem, meta::JobMarker, GraphicsJob, JobError, JobRunContext};
+
+    use super::{register_job_type, warn_unregistered_jobs, RegisteredJobs};
+
+    #[derive(Clone, Component)]
+    struct RegisteredJob;
+
+    #[derive(Clone, Component)]
+    struct UnregisteredJob;
+
+    impl GraphicsJob for RegisteredJob {
+        type In = ();
+
+        fn run(
+            &self,
+            _world: &World,
+            _context: &mut JobRunContext,
+            (): JobInputItem<Self, Self::In>,
+        ) -> Result<(), JobError> {
+            Ok(())
+        }
+    }
+
+    impl GraphicsJob for UnregisteredJob {
+        type In = ();
+
+        fn run(
+            &self,
+            _world: &World,
+            _context: &mut JobRunContext,
+            (): JobInputItem<Self, Self::In>,
+        ) -> Result<(), JobError> {
+            Ok(())
+        }
+    }
+
+    fn warned(app: &App) -> usize {
+        app.world().resource::<RegisteredJobs>().warned.len()
+    }
+
+    #[test]
+    fn warns_once_for_unregistered_jobs() {
+        let mut app = App::new();
+        register_job_type::<RegisteredJob>(&mut app);
+        app.add_systems(Update, warn_unregistered_jobs);
+
+        app.world_mut().spawn((RegisteredJob, JobMarker));
+        app.update();
+        assert_eq!(warned(&app), 0);
+
+        app.world_mut().spawn((UnregisteredJob, JobMarker));
+        app.update();
+        assert_eq!(warned(&app), 1);
+
+        // another job of the same type isn't warned about again
+        app.world_mut().spawn((UnregisteredJob, JobMarker));
+        app.update();
+        assert_eq!(warned(&app), 1);
+    }
+}
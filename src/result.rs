@@ -0,0 +1,77 @@
+use core::any::Any;
+
+use bevy_ecs::{
+    entity::{Entities, Entity},
+    system::{ResMut, Resource},
+};
+use bevy_utils::HashMap;
+
+/// A value set by a job with [`JobRunContext::set_result`](crate::JobRunContext::set_result).
+pub(crate) type JobResultValue = Box<dyn Any + Send + Sync>;
+
+/// The values jobs set with [`JobRunContext::set_result`](crate::JobRunContext::set_result),
+/// keyed by the job's main world entity, for reading structured results after the job
+/// completes, like the number of objects a job counted.
+///
+/// Values are stored as the job completes, before [`JobComplete`](crate::JobComplete) is
+/// triggered, and are kept for as long as the job's entity, which is
+/// [`JobExecutionSettings::done_retention_frames`](crate::JobExecutionSettings::done_retention_frames).
+/// Values of jobs that are despawned as they complete can still be read until the next
+/// frame's [`Update`](bevy_app::Update).
+#[derive(Resource, Default)]
+pub struct JobResults(HashMap<Entity, JobResultValue>);
+
+impl JobResults {
+    /// Returns the value set by the given job, if it set one of type `T`.
+    pub fn get<T: Any>(&self, job: Entity) -> Option<&T> {
+        self.0.get(&job)?.downcast_ref()
+    }
+
+    /// Returns whether the given job set a value.
+    pub fn contains(&self, job: Entity) -> bool {
+        self.0.contains_key(&job)
+    }
+
+    pub(crate) fn insert(&mut self, job: Entity, value: JobResultValue) {
+        self.0.insert(job, value);
+    }
+
+    /// Removes the value set by the given job, if it set one of type `T`.
+    pub(crate) fn take<T: Any>(&mut self, job: Entity) -> Option<T> {
+        self.get::<T>(job)?;
+        self.0.remove(&job)?.downcast().ok().map(|value| *value)
+    }
+}
+
+/// Removes the values of jobs whose entity was despawned.
+pub(crate) fn clean_up_job_results(mut results: ResMut<JobResults>, entities: &Entities) {
+    if !results.0.is_empty() {
+        results.0.retain(|job, _| entities.contains(*job));
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use bevy_ecs::{system::RunSystemOnce, world::World};
+
+    use super::{clean_up_job_results, JobResults};
+
+    #[test]
+    fn results_are_kept_with_their_job() {
+        let mut world = World::new();
+        let kept = world.spawn_empty().id();
+        let despawned = world.spawn_empty().id();
+
+        let mut results = JobResults::default();
+        results.insert(kept, Box::new(37u32));
+        results.insert(despawned, Box::new(1024u32));
+        world.insert_resource(results);
+        world.despawn(despawned);
+
+        world.run_system_once(clean_up_job_results).unwrap();
+        let results = world.resource::<JobResults>();
+        assert_eq!(results.get::<u32>(kept), Some(&37));
+        assert_eq!(results.get::<f32>(kept), None);
+        assert!(!results.contains(despawned));
+    }
+}
@@ -1,71 +1,116 @@
-use core::iter;
+use core::{any::type_name, cmp::Ordering, hash::Hash, iter, time::Duration};
 
+use bevy_core::FrameCount;
 use bevy_ecs::{
     component::Component,
     entity::Entity,
-    query::{With, Without},
-    schedule::SystemSet,
-    system::{Commands, Local, Query, Res, Resource},
+    query::{Has, With, Without},
+    schedule::{IntoSystemSetConfigs, Schedule, SystemSet},
+    system::{Commands, Local, NonSend, Query, Res, ResMut, Resource},
     world::{EntityRef, World},
 };
 use bevy_render::render_resource::CommandEncoder;
-use bevy_render::render_resource::CommandEncoderDescriptor;
 use bevy_render::renderer::RenderDevice;
 use bevy_render::renderer::RenderQueue;
 use bevy_render::sync_world::MainEntity;
+use bevy_render::RenderSet;
+use bevy_utils::{tracing::warn, HashMap, HashSet, Instant};
 use crossbeam_channel::Receiver;
 use crossbeam_channel::Sender;
 use disqualified::ShortName;
+use wgpu::SubmissionIndex;
 
 use crate::{
-    input::{JobInput, JobInputStatus},
-    meta::JobPriority,
-    JobComplete, JobMarker,
+    adapter::JobSecondaryDevice,
+    device::DeviceLostPolicy,
+    done::JobDone,
+    input::{JobDependency, JobFallbackUsed, JobInput, JobInputStatus, JobOutput},
+    meta::{
+        weighted_admission_key, DependentCounts, JobAdapter, JobAdmission, JobComparator,
+        JobDependencyPriority, JobExclusive, JobInterval, JobMeta, JobPriority, JobSequence,
+        JobTargetDespawned, JobTransientMemory,
+    },
+    result::{JobResultValue, JobResults},
+    submission::JobSubmissions,
+    transition::{trigger_job_result, JobReadyMainWorldSender},
+    JobAdaptiveBudget, JobChunk, JobComplete, JobMarker,
 };
 
 use super::JobExecutionSettings;
-use super::{GraphicsJob, JobError};
+use super::{
+    completion::{JobCompletion, JobCompletionBatch, JobCompletionBatches, JobCompletionSender},
+    GraphicsJob, JobError, JobRunContext,
+};
 
 #[derive(Copy, Clone, Component)]
 pub struct DynamicJob {
     label: ShortName<'static>,
+    is_idempotent: bool,
     status: fn(EntityRef, &World) -> JobInputStatus,
-    run: fn(EntityRef, &World, &RenderDevice, &mut CommandEncoder) -> Result<(), JobError>,
+    input_statuses: fn(EntityRef, &World) -> Vec<(&'static str, JobInputStatus)>,
+    run: fn(EntityRef, &World, &mut JobRunContext) -> Result<JobChunk, JobError>,
+    is_fallback: fn(EntityRef, &World) -> bool,
 }
 
 impl DynamicJob {
     pub fn new<J: GraphicsJob>() -> Self {
         let label = J::label();
         let status = erased_status::<J>;
+        let input_statuses = erased_input_statuses::<J>;
         let run = erased_run::<J>;
-        Self { label, status, run }
+        let is_fallback = erased_is_fallback::<J>;
+        Self {
+            label,
+            is_idempotent: J::IS_IDEMPOTENT,
+            status,
+            input_statuses,
+            run,
+            is_fallback,
+        }
     }
 
     pub fn label(&self) -> ShortName<'static> {
         self.label
     }
 
+    /// See [`GraphicsJob::IS_IDEMPOTENT`].
+    pub fn is_idempotent(&self) -> bool {
+        self.is_idempotent
+    }
+
     pub fn status(&self, entity: EntityRef, world: &World) -> JobInputStatus {
         (self.status)(entity, world)
     }
 
+    pub fn input_statuses(
+        &self,
+        entity: EntityRef,
+        world: &World,
+    ) -> Vec<(&'static str, JobInputStatus)> {
+        (self.input_statuses)(entity, world)
+    }
+
     pub fn run(
         &self,
         entity: EntityRef,
         world: &World,
-        render_device: &RenderDevice,
-        command_encoder: &mut CommandEncoder,
-    ) -> Result<(), JobError> {
-        (self.run)(entity, world, render_device, command_encoder)
+        context: &mut JobRunContext,
+    ) -> Result<JobChunk, JobError> {
+        (self.run)(entity, world, context)
+    }
+
+    /// Whether the job's inputs provide any placeholders, like with
+    /// [`JobFallback`](crate::input::JobFallback).
+    pub fn is_fallback(&self, entity: EntityRef, world: &World) -> bool {
+        (self.is_fallback)(entity, world)
     }
 }
 
 fn erased_run<J: GraphicsJob>(
     entity: EntityRef,
     world: &World,
-    render_device: &RenderDevice,
-    command_encoder: &mut CommandEncoder,
-) -> Result<(), JobError> {
+    context: &mut JobRunContext,
+) -> Result<JobChunk, JobError> {
     let Some((job, input_data)) = entity.get_components::<(&J, <J::In as JobInput<J>>::Data)>()
     else {
         return Err(JobError::InputsFailed);
@@ -73,7 +118,7 @@ fn erased_run<J: GraphicsJob>(
 
     let input = <J::In as JobInput<J>>::get(input_data, world);
 
-    job.run(world, render_device, command_encoder, input)
+    job.run_chunk(world, context, input)
 }
 
 fn erased_status<J: GraphicsJob>(entity: EntityRef, world: &World) -> JobInputStatus {
@@ -84,6 +129,42 @@ fn erased_status<J: GraphicsJob>(entity: EntityRef, world: &World) -> JobInputSt
     <J::In as JobInput<J>>::status(input_data, world)
 }
 
+fn erased_is_fallback<J: GraphicsJob>(entity: EntityRef, world: &World) -> bool {
+    entity
+        .get_components::<<J::In as JobInput<J>>::Data>()
+        .is_some_and(|input_data| <J::In as JobInput<J>>::is_fallback(input_data, world))
+}
+
+fn erased_input_statuses<J: GraphicsJob>(
+    entity: EntityRef,
+    world: &World,
+) -> Vec<(&'static str, JobInputStatus)> {
+    let Some(input_data) = entity.get_components::<<J::In as JobInput<J>>::Data>() else {
+        return vec![(type_name::<J::In>(), JobInputStatus::Fail)];
+    };
+
+    let mut statuses = Vec::new();
+    <J::In as JobInput<J>>::statuses(input_data, world, &mut statuses);
+    statuses
+}
+
+/// Re-evaluates the status of each input of a job in the render world, labeled
+/// by the input's type name. This is useful for diagnosing why a job never
+/// becomes ready.
+///
+/// Returns an empty list if the entity isn't a job, or if its job type hasn't
+/// been set up yet. For jobs with non-send inputs, like [`JobLocal`](crate::input::JobLocal),
+/// this must be called from the render thread.
+pub fn job_input_statuses(world: &World, job: Entity) -> Vec<(&'static str, JobInputStatus)> {
+    let Ok(entity) = world.get_entity(job) else {
+        return Vec::new();
+    };
+    let Some(dynamic_job) = entity.get::<DynamicJob>() else {
+        return Vec::new();
+    };
+    dynamic_job.input_statuses(entity, world)
+}
+
 pub fn erase_jobs<J: GraphicsJob>(
     query: Query<Entity, (With<J>, Without<DynamicJob>)>,
     mut commands: Commands,
@@ -97,12 +178,21 @@ pub fn erase_jobs<J: GraphicsJob>(
 }
 
 /// The render-world system sets for graphics jobs
+///
+/// Render systems can be ordered against these like any other set, for example to
+/// update a job's pipeline key after [`JobSet::QueuePipelines`] has read it, or to
+/// prepare a resource for jobs before [`JobSet::Check`].
 #[derive(Copy, Clone, PartialEq, Eq, Hash, Debug, SystemSet)]
 pub enum JobSet {
     /// Various graphics jobs components are setup in this set
     Setup,
+    /// The pipelines of jobs, like [`JobRenderPipeline`](crate::input::JobRenderPipeline)
+    /// and [`JobComputePipeline`](crate::input::JobComputePipeline), are queued for
+    /// compilation in this set, within [`RenderSet::Queue`]. Systems that change a job's
+    /// pipeline key should run before it, so that the pipeline is queued the same frame.
+    QueuePipelines,
     /// Graphics jobs are checked to see if they're ready for
-    /// execution in this set
+    /// execution in this set, after every input's [`JobPrepareSet`](crate::input::JobPrepareSet).
     Check,
     /// Graphics jobs are executed in this set.
     Execute,
@@ -111,8 +201,31 @@ pub enum JobSet {
     Cleanup,
 }
 
+/// Orders the [`JobSet`]s within the [`Render`](bevy_render::Render) schedule. Pipelines are queued before
+/// jobs are checked, so a job never runs with a pipeline queued later in the same frame.
+pub(super) fn configure_job_sets(schedule: &mut Schedule) {
+    schedule.configure_sets(
+        (
+            JobSet::Setup,
+            JobSet::Check,
+            JobSet::Execute,
+            JobSet::Cleanup,
+        )
+            .chain(),
+    );
+
+    schedule.configure_sets((
+        JobSet::QueuePipelines
+            .in_set(RenderSet::Queue)
+            .before(JobSet::Check),
+        JobSet::Check.after(RenderSet::Prepare),
+        JobSet::Execute.before(RenderSet::Render),
+        JobSet::Cleanup.in_set(RenderSet::Cleanup),
+    ));
+}
+
 #[derive(Component, Copy, Clone)]
-pub(super) struct TimeOutFrames(u32);
+pub(super) struct TimeOutFrames(pub(super) u32);
 
 pub(super) fn setup_time_out_frames(
     jobs: Query<Entity, (With<JobMarker>, Without<TimeOutFrames>)>,
@@ -126,54 +239,210 @@ pub(super) fn setup_time_out_frames(
 }
 
 pub(super) fn time_out_jobs(
-    jobs: Query<(Entity, Option<&MainEntity>, &TimeOutFrames)>,
+    jobs: Query<
+        (
+            Entity,
+            Option<&MainEntity>,
+            &TimeOutFrames,
+            Option<&JobInterval>,
+        ),
+        Without<JobChunkProgress>,
+    >,
     exec_settings: Res<JobExecutionSettings>,
     completed_jobs: Res<JobResultSender>,
     mut commands: Commands,
 ) {
     jobs.iter()
-        .filter(|(_, _, frames)| (frames.0 > exec_settings.time_out_frames))
-        .for_each(|(id, main_id, _)| {
-            completed_jobs
-                .0
-                .send(JobResult {
+        .filter(|(_, _, frames, interval)| {
+            // interval jobs wait out their interval between runs
+            let every_n_frames = interval.map_or(1, |interval| interval.every_n_frames.max(1));
+            frames.0 > exec_settings.time_out_frames.saturating_mul(every_n_frames)
+        })
+        .for_each(|(id, main_id, ..)| {
+            exec_settings.on_invariant_violation.send(
+                &completed_jobs.0,
+                JobResult {
                     entity: id,
                     main_entity: main_id.copied(),
                     result: Err(JobError::TimedOut),
-                })
-                .unwrap();
+                    value: None,
+                    submission: None,
+                },
+            );
             commands.entity(id).despawn();
         });
 }
 
+/// Cancels the jobs whose [`CancelOnTargetDespawn`](crate::meta::CancelOnTargetDespawn) target was despawned, including
+/// jobs that yielded partway through their work, since there's nothing left to do it for.
+pub(super) fn cancel_orphaned_jobs(
+    jobs: Query<(Entity, Option<&MainEntity>), With<JobTargetDespawned>>,
+    exec_settings: Res<JobExecutionSettings>,
+    completed_jobs: Res<JobResultSender>,
+    mut commands: Commands,
+) {
+    for (id, main_id) in &jobs {
+        exec_settings.on_invariant_violation.send(
+            &completed_jobs.0,
+            JobResult {
+                entity: id,
+                main_entity: main_id.copied(),
+                result: Err(JobError::Cancelled),
+                value: None,
+                submission: None,
+            },
+        );
+        commands.entity(id).despawn();
+    }
+}
+
+/// Warns once for each job type whose jobs wait on an input for at least
+/// [`JobExecutionSettings::stall_warning_frames`], naming the inputs still waiting,
+/// so that a job stalled on an input that never becomes ready doesn't fail silently.
+pub(super) fn warn_stalled_jobs(
+    jobs: Query<
+        (EntityRef, &DynamicJob, &TimeOutFrames, Option<&JobInterval>),
+        (Without<JobReady>, Without<JobChunkProgress>),
+    >,
+    world: &World,
+    exec_settings: Res<JobExecutionSettings>,
+    mut warned: Local<HashSet<&'static str>>,
+    _render_thread: Option<NonSend<RenderThreadMarker>>,
+) {
+    let Some(stall_frames) = exec_settings.stall_warning_frames else {
+        return;
+    };
+
+    for (entity, job, frames, interval) in &jobs {
+        // interval jobs wait out their interval between runs
+        let every_n_frames = interval.map_or(1, |interval| interval.every_n_frames.max(1));
+        if frames.0 < stall_frames.saturating_mul(every_n_frames) {
+            continue;
+        }
+        if let Some(warning) = stall_warning(job, entity, world, frames.0, &mut warned) {
+            warn!("{warning}");
+        }
+    }
+}
+
+/// Describes the inputs a stalled job is still waiting on, unless a job of the same
+/// type was already warned about.
+fn stall_warning(
+    job: &DynamicJob,
+    entity: EntityRef,
+    world: &World,
+    frames: u32,
+    warned: &mut HashSet<&'static str>,
+) -> Option<String> {
+    let label = job.label();
+    if warned.contains(label.original()) {
+        return None;
+    }
+
+    let waiting = job
+        .input_statuses(entity, world)
+        .into_iter()
+        .filter(|(_, status)| *status == JobInputStatus::Wait)
+        .map(|(input, _)| format!("`{}`", ShortName(input)))
+        .collect::<Vec<_>>();
+    if waiting.is_empty() {
+        return None;
+    }
+
+    warned.insert(label.original());
+    Some(format!(
+        "input {} of job `{label}` never became ready after waiting {frames} frames. \
+        Further stalls of this job type won't be reported",
+        waiting.join(", ")
+    ))
+}
+
 pub(super) fn increment_time_out_frames(mut jobs: Query<&mut TimeOutFrames>) {
     jobs.iter_mut().for_each(|mut frames| frames.0 += 1);
 }
 
+/// The number of frames since a [`JobInterval`] job was extracted, which decides the
+/// frames it runs on.
+#[derive(Component, Copy, Clone)]
+pub(super) struct JobIntervalFrames(pub(super) u32);
+
+impl JobIntervalFrames {
+    fn is_due(interval: Option<&JobInterval>, frames: Option<&Self>) -> bool {
+        interval.is_none_or(|interval| interval.is_due(frames.map_or(0, |frames| frames.0)))
+    }
+}
+
+pub(super) fn setup_interval_frames(
+    jobs: Query<Entity, (With<JobInterval>, Without<JobIntervalFrames>)>,
+    mut commands: Commands,
+) {
+    let to_insert = jobs
+        .iter()
+        .zip(iter::repeat(JobIntervalFrames(0)))
+        .collect::<Vec<_>>();
+    commands.insert_batch(to_insert);
+}
+
+pub(super) fn increment_interval_frames(mut jobs: Query<&mut JobIntervalFrames>) {
+    jobs.iter_mut().for_each(|mut frames| frames.0 += 1);
+}
+
 #[derive(Copy, Clone, Component)]
 pub struct JobReady;
 
+/// Never inserted, but taken as an optional non-send parameter by systems that may
+/// read non-send job data, like [`JobLocal`](crate::input::JobLocal), to keep them
+/// on the render thread.
+pub(super) struct RenderThreadMarker;
+
 pub(super) fn check_job_inputs(
-    jobs: Query<(EntityRef, Option<&MainEntity>, &DynamicJob), Without<JobReady>>,
+    jobs: Query<
+        (
+            EntityRef,
+            Option<&MainEntity>,
+            &DynamicJob,
+            Option<&JobInterval>,
+            Option<&JobIntervalFrames>,
+        ),
+        Without<JobReady>,
+    >,
     world: &World,
+    exec_settings: Res<JobExecutionSettings>,
     job_result_sender: Res<JobResultSender>,
+    job_ready_sender: Res<JobReadyMainWorldSender>,
+    _render_thread: Option<NonSend<RenderThreadMarker>>,
     mut commands: Commands,
 ) {
+    let policy = exec_settings.on_invariant_violation;
     let to_insert = jobs
         .iter()
+        // interval jobs keep waiting between their frames, without checking their inputs
+        .filter(|(.., interval, frames)| JobIntervalFrames::is_due(*interval, *frames))
         .filter_map(
-            |(entity, main_entity, job)| match job.status(entity, world) {
-                JobInputStatus::Ready => Some(entity.id()),
+            |(entity, main_entity, job, ..)| match job.status(entity, world) {
+                // jobs that can't safely run twice wait for their real inputs, rather
+                // than running with placeholders and again once their inputs are ready
+                JobInputStatus::Ready if !job.is_idempotent() && job.is_fallback(entity, world) => {
+                    None
+                }
+                JobInputStatus::Ready => {
+                    if let Some(main_entity) = main_entity {
+                        policy.send(&job_ready_sender.0, *main_entity);
+                    }
+                    Some(entity.id())
+                }
                 JobInputStatus::Wait => None,
                 JobInputStatus::Fail => {
-                    job_result_sender
-                        .0
-                        .send(JobResult {
+                    policy.send(
+                        &job_result_sender.0,
+                        JobResult {
                             entity: entity.id(),
                             main_entity: main_entity.copied(),
                             result: Err(JobError::InputsFailed),
-                        })
-                        .unwrap();
+                            value: None,
+                            submission: None,
+                        },
+                    );
                     None
                 }
             },
@@ -183,11 +452,59 @@ pub(super) fn check_job_inputs(
     commands.insert_batch(to_insert)
 }
 
-#[derive(Copy, Clone)]
+/// Fails or requeues every in-flight job after the device is lost. Jobs that aren't
+/// [idempotent](GraphicsJob::IS_IDEMPOTENT) are failed rather than requeued if they
+/// already yielded partway through their work, since requeueing would run it again.
+pub(crate) fn reset_jobs(world: &mut World, policy: DeviceLostPolicy) {
+    let jobs = world
+        .query_filtered::<(
+            Entity,
+            Option<&MainEntity>,
+            Option<&DynamicJob>,
+            Has<JobChunkProgress>,
+        ), With<JobMarker>>()
+        .iter(world)
+        .map(|(entity, main_entity, job, started)| {
+            let can_requeue = !started || job.is_none_or(DynamicJob::is_idempotent);
+            (entity, main_entity.copied(), can_requeue)
+        })
+        .collect::<Vec<_>>();
+
+    for (entity, main_entity, can_requeue) in jobs {
+        match policy {
+            DeviceLostPolicy::Requeue if can_requeue => {
+                world
+                    .entity_mut(entity)
+                    .remove::<(JobReady, JobChunkProgress)>()
+                    .insert(TimeOutFrames(0));
+            }
+            DeviceLostPolicy::Fail | DeviceLostPolicy::Requeue => {
+                world
+                    .resource::<JobExecutionSettings>()
+                    .on_invariant_violation
+                    .send(
+                        &world.resource::<JobResultSender>().0,
+                        JobResult {
+                            entity,
+                            main_entity,
+                            result: Err(JobError::DeviceLost),
+                            value: None,
+                            submission: None,
+                        },
+                    );
+                world.despawn(entity);
+            }
+        }
+    }
+}
+
 pub(super) struct JobResult {
     entity: Entity,
     main_entity: Option<MainEntity>,
     result: Result<(), JobError>,
+    value: Option<JobResultValue>,
+    /// The submission holding the last of the job's commands, if it ran successfully.
+    submission: Option<SubmissionIndex>,
 }
 
 #[derive(Resource)]
@@ -203,12 +520,16 @@ pub(super) struct JobResultMainWorldSender(pub Sender<JobResult>);
 pub(super) fn sync_completed_jobs(
     job_result_receiver: Res<JobResultReceiver>,
     main_job_result_sender: Res<JobResultMainWorldSender>,
+    exec_settings: Res<JobExecutionSettings>,
     mut commands: Commands,
 ) {
     while let Ok(job) = job_result_receiver.0.try_recv() {
-        main_job_result_sender.0.send(job).unwrap();
-        commands.trigger_targets(JobComplete(job.result), job.entity);
-        if let Some(mut entity) = commands.get_entity(job.entity) {
+        let (entity, result) = (job.entity, job.result);
+        exec_settings
+            .on_invariant_violation
+            .send(&main_job_result_sender.0, job);
+        commands.trigger_targets(JobComplete(result), entity);
+        if let Some(mut entity) = commands.get_entity(entity) {
             entity.despawn();
         }
     }
@@ -216,56 +537,1594 @@ pub(super) fn sync_completed_jobs(
 
 pub(super) fn sync_completed_jobs_main_world(
     job_result_receiver: Res<JobResultMainWorldReceiver>,
+    notifiers: Query<(Option<&JobCompletionSender>, Option<&JobCompletionBatch>), With<JobMarker>>,
+    exec_settings: Res<JobExecutionSettings>,
+    mut results: ResMut<JobResults>,
+    mut submissions: ResMut<JobSubmissions>,
     mut commands: Commands,
 ) {
+    let mut batches = JobCompletionBatches::default();
     while let Ok(job) = job_result_receiver.0.try_recv() {
         if let Some(main_entity) = job.main_entity {
-            commands.trigger_targets(JobComplete(job.result), main_entity.id());
-            if let Some(mut entity) = commands.get_entity(main_entity.id()) {
-                entity.despawn();
+            // jobs that are no longer pending, like those cancelled while they ran, have
+            // already completed, whether or not they're kept as `JobDone`
+            let Ok((sender, batch)) = notifiers.get(main_entity.id()) else {
+                continue;
+            };
+            if let Some(value) = job.value {
+                results.insert(main_entity.id(), value);
+            }
+            if let Some(submission) = job.submission {
+                submissions.insert(main_entity.id(), submission);
+            }
+
+            complete_main_world_job(
+                &mut commands,
+                &mut batches,
+                &exec_settings,
+                (main_entity.id(), job.result),
+                sender,
+                batch,
+            );
+        }
+    }
+    batches.send();
+}
+
+/// Notifies and triggers the completion of a job in the main world, then despawns it,
+/// or keeps it as [`JobDone`] for [`JobExecutionSettings::done_retention_frames`].
+pub(crate) fn complete_main_world_job(
+    commands: &mut Commands,
+    batches: &mut JobCompletionBatches,
+    exec_settings: &JobExecutionSettings,
+    completion: JobCompletion,
+    sender: Option<&JobCompletionSender>,
+    batch: Option<&JobCompletionBatch>,
+) {
+    let (entity, result) = completion;
+    if let Some(sender) = sender {
+        let _ = sender.0.send(completion);
+    }
+    if let Some(batch) = batch {
+        batches.push(batch, completion);
+    }
+
+    commands.trigger_targets(JobComplete(result), entity);
+    trigger_job_result(commands, entity, result);
+    if let Some(mut entity) = commands.get_entity(entity) {
+        if exec_settings.done_retention_frames == 0 {
+            entity.despawn();
+        } else {
+            // the job is no longer pending, which also despawns its render entity
+            entity.remove::<JobMarker>().insert(JobDone::new(result));
+        }
+    }
+}
+
+/// Tracks the next chunk to record for a job that yielded partway
+/// through its work. See [`GraphicsJob::run_chunk`].
+#[derive(Copy, Clone, Component)]
+pub(super) struct JobChunkProgress(pub(super) u32);
+
+/// The outcome of driving a job's chunks for a frame.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+enum ChunkOutcome {
+    /// The job finished, successfully or not.
+    Finished(Result<(), JobError>),
+    /// The frame's submission budget ran out, and the job should resume at the given chunk.
+    Suspended(u32),
+}
+
+/// Runs chunks of a job starting from `chunk`, flushing after each yield, until the
+/// job finishes or `submits_left` reaches zero.
+fn drive_chunks<C>(
+    context: &mut C,
+    mut chunk: u32,
+    submits_left: &mut u32,
+    mut run_chunk: impl FnMut(&mut C, u32) -> Result<JobChunk, JobError>,
+    mut flush: impl FnMut(&mut C) -> Result<(), JobError>,
+) -> ChunkOutcome {
+    loop {
+        match run_chunk(context, chunk) {
+            Ok(JobChunk::Done) => return ChunkOutcome::Finished(Ok(())),
+            Err(err) => return ChunkOutcome::Finished(Err(err)),
+            Ok(JobChunk::Yield) => {
+                chunk += 1;
+                if *submits_left == 0 {
+                    return ChunkOutcome::Suspended(chunk);
+                }
+                *submits_left -= 1;
+                if let Err(err) = flush(context) {
+                    return ChunkOutcome::Finished(Err(err));
+                }
             }
         }
     }
 }
 
+/// Tracks the CPU time spent recording jobs this frame, against
+/// [`JobExecutionSettings::max_recording_time`].
+struct RecordingBudget {
+    limit: Option<Duration>,
+    spent: Duration,
+}
+
+impl RecordingBudget {
+    fn new(limit: Option<Duration>) -> Self {
+        Self {
+            limit,
+            spent: Duration::ZERO,
+        }
+    }
+
+    /// Whether a job may still be recorded this frame. Critical jobs always are.
+    fn admits(&self, priority: JobPriority) -> bool {
+        priority.is_critical() || self.limit.is_none_or(|limit| self.spent < limit)
+    }
+
+    /// Records a job, measuring how long it takes if there's a limit.
+    fn measure<R>(&mut self, record: impl FnOnce() -> R) -> R {
+        if self.limit.is_none() {
+            return record();
+        }
+        let start = Instant::now();
+        let result = record();
+        self.spent += start.elapsed();
+        result
+    }
+}
+
+/// Picks the jobs to execute this frame from those that are ready, from highest to
+/// lowest priority, or in the given order, up to `max_jobs_per_frame` plus any
+/// critical jobs.
+///
+/// Jobs still waiting on their inputs are never considered, so they don't take up
+/// the frame's budget: while some jobs are blocked, other ready jobs run in their
+/// place rather than the frame going idle.
+///
+/// Non-critical jobs whose [`JobTransientMemory`] would take the frame's total over
+/// `max_transient_bytes` are deferred to a later frame, unless nothing else has been
+/// admitted yet, so that a single oversized job isn't deferred forever.
+fn admit_jobs<T>(
+    mut jobs: Vec<(T, JobPriority, u64)>,
+    order: Option<&dyn Fn(&T, &T) -> Ordering>,
+    exec_settings: &JobExecutionSettings,
+) -> impl Iterator<Item = T> {
+    match order {
+        Some(order) => jobs.sort_by(|(a, ..), (b, ..)| order(a, b)),
+        None => {
+            jobs.sort_by_key(|(_, priority, _)| *priority);
+            jobs.reverse();
+        }
+    }
+
+    let max_jobs_per_frame = exec_settings.max_jobs_per_frame;
+    let max_transient_bytes = exec_settings.max_transient_bytes.unwrap_or(u64::MAX);
+    let mut admitted = 0;
+    let mut transient_bytes = 0u64;
+    jobs.into_iter()
+        .filter(move |(_, priority, bytes)| {
+            let total_bytes = transient_bytes.saturating_add(*bytes);
+            let admit = priority.is_critical()
+                || (admitted < max_jobs_per_frame
+                    && (transient_bytes == 0 || total_bytes <= max_transient_bytes));
+            if admit {
+                admitted += 1;
+                transient_bytes = total_bytes;
+            }
+            admit
+        })
+        .map(|(job, _, _)| job)
+}
+
+/// Picks the jobs to execute this frame with [`admit_jobs`], unless a [`JobExclusive`]
+/// job is ready, in which case only the highest priority exclusive job is considered,
+/// and every other job is deferred.
+fn admit_ready_jobs<T>(
+    mut jobs: Vec<(T, JobPriority, u64)>,
+    is_exclusive: impl Fn(&T) -> bool,
+    order: Option<&dyn Fn(&T, &T) -> Ordering>,
+    exec_settings: &JobExecutionSettings,
+) -> impl Iterator<Item = T> {
+    let exclusive = jobs
+        .iter()
+        .enumerate()
+        .filter(|(_, (job, _, _))| is_exclusive(job))
+        .max_by_key(|(_, (_, priority, _))| *priority)
+        .map(|(index, _)| index);
+    if let Some(index) = exclusive {
+        jobs = vec![jobs.swap_remove(index)];
+    }
+    admit_jobs(jobs, order, exec_settings)
+}
+
+/// What the scheduler needs to know about a ready job, for [`schedule_jobs`].
+pub(crate) struct JobSchedule<L> {
+    pub priority: JobPriority,
+    pub transient_bytes: u64,
+    pub exclusive: bool,
+    pub output: Option<L>,
+}
+
+/// Picks the jobs to execute this frame from those that are ready, after raising their
+/// priority by the jobs waiting on their output, given as the dependency and output of
+/// every pending job. Jobs are considered in the given order, if any, or as set by
+/// [`JobExecutionSettings::admission`], where `frame` varies the random order each frame.
+/// This is shared by [`run_jobs`] and by replays of a
+/// [`JobQueueSnapshot`](crate::JobQueueSnapshot), so that both make the same decisions.
+pub(crate) fn schedule_jobs<T, L: Copy + Eq + Hash>(
+    ready: impl IntoIterator<Item = (T, JobSchedule<L>)>,
+    dependents: impl IntoIterator<Item = (L, Option<L>)>,
+    order: Option<&dyn Fn(&T, &T) -> Ordering>,
+    exec_settings: &JobExecutionSettings,
+    frame: u32,
+) -> impl Iterator<Item = T> {
+    let dependent_counts = DependentCounts::new(exec_settings.dependency_priority, dependents);
+    let seed = match exec_settings.admission {
+        JobAdmission::WeightedRandom(seed) if order.is_none() => Some(seed.derive(frame)),
+        _ => None,
+    };
+    let random_order = |(.., a): &(T, bool, f64), (.., b): &(T, bool, f64)| b.total_cmp(a);
+    let order =
+        order.map(|order| move |(a, ..): &(T, bool, f64), (b, ..): &(T, bool, f64)| order(a, b));
+
+    admit_ready_jobs(
+        ready
+            .into_iter()
+            .enumerate()
+            .map(|(index, (job, schedule))| {
+                let priority = dependent_counts.raise(schedule.priority, schedule.output);
+                let key = seed.map_or(0.0, |seed| {
+                    weighted_admission_key(priority, seed.derive(index))
+                });
+                (
+                    (job, schedule.exclusive, key),
+                    priority,
+                    schedule.transient_bytes,
+                )
+            })
+            .collect(),
+        |(_, exclusive, _)| *exclusive,
+        match (&order, seed) {
+            (Some(order), _) => {
+                Some(order as &dyn Fn(&(T, bool, f64), &(T, bool, f64)) -> Ordering)
+            }
+            (None, Some(_)) => Some(&random_order as &dyn Fn(&_, &_) -> Ordering),
+            (None, None) => None,
+        },
+        exec_settings,
+    )
+    .map(|(job, ..)| job)
+}
+
+#[allow(clippy::too_many_arguments)]
 pub(super) fn run_jobs(
-    jobs: Query<(EntityRef, Option<&MainEntity>, &DynamicJob, &JobPriority), With<JobReady>>,
+    jobs: Query<
+        (
+            EntityRef,
+            Option<&MainEntity>,
+            &DynamicJob,
+            &JobPriority,
+            Option<&JobChunkProgress>,
+            Option<&JobTransientMemory>,
+            Option<&JobOutput>,
+            Option<&TimeOutFrames>,
+            Option<&JobSequence>,
+            Option<&JobInterval>,
+            Option<&JobIntervalFrames>,
+        ),
+        With<JobReady>,
+    >,
+    dependents: Query<(&JobDependency, Option<&JobOutput>), With<JobMarker>>,
+    comparator: Option<Res<JobComparator>>,
     world: &World,
     render_device: Res<RenderDevice>,
     render_queue: Res<RenderQueue>,
+    secondary_device: Option<Res<JobSecondaryDevice>>,
     exec_settings: Res<JobExecutionSettings>,
+    adaptive_budget: Option<Res<JobAdaptiveBudget>>,
+    frame_count: Option<Res<FrameCount>>,
     job_result_sender: Res<JobResultSender>,
     mut command_encoders: Local<Vec<CommandEncoder>>,
+    mut suspended_results: Local<HashMap<Entity, JobResultValue>>,
+    _render_thread: Option<NonSend<RenderThreadMarker>>,
+    #[cfg(feature = "diagnostics")] job_spans: Option<Res<crate::diagnostics::JobSpans>>,
+    mut commands: Commands,
 ) {
-    let sorted_jobs = jobs
-        .iter()
-        .sort::<&JobPriority>()
-        .rev()
-        .enumerate()
-        .take_while(|(i, (_, _, _, priority))| {
-            priority.is_critical() || (*i as u32) < exec_settings.max_jobs_per_frame
-        })
-        .map(|(_, a)| a);
+    let exec_settings = match (exec_settings.time_budget, adaptive_budget) {
+        (Some(budget), Some(adaptive)) => JobExecutionSettings {
+            max_jobs_per_frame: adaptive.current(&budget, exec_settings.max_jobs_per_frame),
+            ..*exec_settings
+        },
+        _ => *exec_settings,
+    };
 
-    for (entity_ref, main_entity, job, _) in sorted_jobs {
-        let mut command_encoder = render_device.create_command_encoder(&CommandEncoderDescriptor {
-            label: Some(job.label().original()),
-        });
+    let dependent_labels = || {
+        dependents
+            .iter()
+            .map(|(dependency, output)| (dependency.0, output.map(|output| output.label)))
+    };
+    let comparator = comparator.as_deref().and_then(JobComparator::get);
+    // a comparator may order jobs by how many others wait on them, whatever the
+    // dependency priority strategy is
+    let depths = comparator
+        .map(|_| DependentCounts::new(JobDependencyPriority::Subtree, dependent_labels()));
+    let order = comparator
+        .map(|comparator| move |(_, a): &(_, JobMeta), (_, b): &(_, JobMeta)| comparator(a, b));
+
+    let sorted_jobs = schedule_jobs(
+        jobs.iter()
+            // interval jobs deferred on their frame wait for the next one, unless they
+            // yielded partway through
+            .filter(|job| job.4.is_some() || JobIntervalFrames::is_due(job.9, job.10))
+            .map(|job| {
+                let schedule = JobSchedule {
+                    priority: *job.3,
+                    transient_bytes: job.5.map_or(0, |memory| memory.0),
+                    exclusive: job.0.contains::<JobExclusive>(),
+                    output: job.6.map(|output| output.label),
+                };
+                let meta = JobMeta {
+                    entity: job.0,
+                    label: job.2.label(),
+                    priority: *job.3,
+                    waited_frames: job.7.map_or(0, |frames| frames.0),
+                    dependents: depths
+                        .as_ref()
+                        .map_or(0, |depths| depths.count(schedule.output)),
+                    sequence: job.8.map_or(0, |sequence| sequence.0),
+                };
+                ((job, meta), schedule)
+            }),
+        dependent_labels(),
+        order
+            .as_ref()
+            .map(|order| order as &dyn Fn(&_, &_) -> Ordering),
+        &exec_settings,
+        frame_count.map_or(0, |frame_count| frame_count.0),
+    );
+
+    let mut submits_left = exec_settings.max_submits_per_frame;
+    let mut recording_budget = RecordingBudget::new(exec_settings.max_recording_time);
+    // results set by jobs that yielded in an earlier frame, until they finish
+    suspended_results.retain(|entity, _| world.get_entity(*entity).is_ok());
+    // finished jobs are only sent once their commands are submitted, along with the index
+    // of the submission, and jobs whose commands are still waiting for one are tracked
+    let mut finished = Vec::new();
+    let mut unsubmitted = Vec::new();
+
+    for ((entity_ref, main_entity, job, priority, progress, .., interval, _), _) in sorted_jobs {
+        // jobs past the frame's recording budget stay ready for the next frame
+        if !recording_budget.admits(*priority) {
+            continue;
+        }
+
+        let start_chunk = progress.map_or(0, |progress| progress.0);
+        let mut value = suspended_results.remove(&entity_ref.id());
+        let adapter = entity_ref
+            .get::<JobAdapter>()
+            .copied()
+            .unwrap_or_default()
+            .resolve(secondary_device.is_some());
+        let secondary = secondary_device
+            .as_deref()
+            .filter(|_| adapter == JobAdapter::Secondary);
+
+        // each chunk records into a fresh encoder, so that the commands recorded
+        // so far can be submitted whenever the job yields. Jobs on the secondary device
+        // also record the transfers to make once their commands are submitted.
+        let mut recorded = (Vec::new(), Vec::new());
+        // inputs that were ready when the job was checked should still be ready now, so
+        // that reading them can't fail
+        let outcome = if job.status(entity_ref, world) != JobInputStatus::Ready {
+            ChunkOutcome::Finished(Err(exec_settings.on_invariant_violation.fail_job(
+                job.label().original(),
+                "its inputs were ready when it was checked, but not when it ran",
+            )))
+        } else {
+            recording_budget.measure(|| {
+                drive_chunks(
+                    &mut recorded,
+                    start_chunk,
+                    &mut submits_left,
+                    |(chunk_encoders, transfers), chunk| {
+                        let mut context = JobRunContext::new(
+                            secondary.map_or(&render_device, |secondary| &secondary.device),
+                            adapter,
+                            job.label().original(),
+                            entity_ref.id(),
+                            main_entity.map(MainEntity::id),
+                            chunk,
+                        );
+                        let result = job.run(entity_ref, world, &mut context);
+                        let (encoders, chunk_transfers, chunk_value) = context.into_parts();
+                        chunk_encoders.extend(encoders);
+                        transfers.extend(chunk_transfers);
+                        value = chunk_value.or(value.take());
+                        result
+                    },
+                    |(chunk_encoders, transfers)| match secondary {
+                        Some(secondary) => secondary.submit(
+                            chunk_encoders.drain(..),
+                            transfers.drain(..),
+                            &render_queue,
+                        ),
+                        None => {
+                            let submission = render_queue.submit(
+                                command_encoders
+                                    .drain(..)
+                                    .chain(chunk_encoders.drain(..))
+                                    .map(|cmd| cmd.finish()),
+                            );
+                            assign_submission(&mut finished, &mut unsubmitted, submission);
+                            Ok(())
+                        }
+                    },
+                )
+            })
+        };
+        let (mut chunk_encoders, mut transfers) = recorded;
+
+        let mut result = match outcome {
+            ChunkOutcome::Suspended(next_chunk) => {
+                // jobs on the secondary device don't share its queue with other jobs, so
+                // what they recorded so far is submitted right away
+                let submitted = match secondary {
+                    Some(secondary) => secondary.submit(
+                        chunk_encoders.drain(..),
+                        transfers.drain(..),
+                        &render_queue,
+                    ),
+                    None => {
+                        command_encoders.append(&mut chunk_encoders);
+                        Ok(())
+                    }
+                };
+                match submitted {
+                    Ok(()) => {
+                        if let Some(value) = value {
+                            suspended_results.insert(entity_ref.id(), value);
+                        }
+                        commands
+                            .entity(entity_ref.id())
+                            .insert(JobChunkProgress(next_chunk));
+                        continue;
+                    }
+                    // the job can't resume without the results of its earlier chunks
+                    Err(err) => Err(err),
+                }
+            }
+            ChunkOutcome::Finished(result) => result,
+        };
 
-        let result = job.run(entity_ref, world, &render_device, &mut command_encoder);
         if result.is_ok() {
-            command_encoders.push(command_encoder);
+            if let Some(secondary) = secondary {
+                result = secondary.submit(chunk_encoders.drain(..), transfers, &render_queue);
+            } else {
+                #[cfg(feature = "diagnostics")]
+                if let Some(job_spans) = &job_spans {
+                    job_spans.record(&render_device, job.label(), &mut chunk_encoders);
+                }
+                command_encoders.append(&mut chunk_encoders);
+            }
+        }
+
+        if result.is_ok() {
+            // jobs that ran with placeholders wait for their real inputs to run again
+            let is_fallback = job.is_fallback(entity_ref, world);
+            if interval.is_some() || is_fallback {
+                let mut entity = commands.entity(entity_ref.id());
+                entity
+                    .remove::<(JobReady, JobChunkProgress)>()
+                    .insert(TimeOutFrames(0));
+                if is_fallback {
+                    entity.insert(JobFallbackUsed);
+                }
+                continue;
+            }
         }
 
-        job_result_sender
-            .0
+        if result.is_ok() && secondary.is_none() {
+            unsubmitted.push(finished.len());
+        }
+        finished.push(JobResult {
+            entity: entity_ref.id(),
+            main_entity: main_entity.copied(),
+            result,
+            value,
+            submission: None,
+        });
+    }
+
+    #[cfg(feature = "diagnostics")]
+    if let Some(job_spans) = &job_spans {
+        command_encoders.extend(job_spans.resolve(&render_device));
+    }
+    let submission = render_queue.submit(command_encoders.drain(..).map(|cmd| cmd.finish()));
+    assign_submission(&mut finished, &mut unsubmitted, submission);
+    #[cfg(feature = "diagnostics")]
+    if let Some(job_spans) = &job_spans {
+        job_spans.finish_frame();
+    }
+    for result in finished {
+        exec_settings
+            .on_invariant_violation
+            .send(&job_result_sender.0, result);
+    }
+}
+
+/// Sets the submission of the finished jobs whose commands were just submitted.
+fn assign_submission(
+    finished: &mut [JobResult],
+    unsubmitted: &mut Vec<usize>,
+    submission: SubmissionIndex,
+) {
+    for job in unsubmitted.drain(..) {
+        finished[job].submission = Some(submission.clone());
+    }
+}
+
+#[cfg(test)]
+mod properties;
+
+#[cfg(test)]
+mod test {
+    use core::{cmp::Ordering, iter, num::NonZero, time::Duration};
+
+    use bevy_ecs::{
+        component::Component,
+        entity::Entity,
+        observer::Trigger,
+        query::{QueryItem, With},
+        schedule::IntoSystemConfigs,
+        system::{Query, ResMut, Resource, RunSystemOnce},
+        world::{Command, World},
+    };
+    use bevy_render::{Render, RenderSet};
+    use bevy_utils::HashSet;
+    use disqualified::ShortName;
+
+    use crate::{
+        cancel_task,
+        input::{JobInput, JobInputItem, JobInputStatus, JobOutputLabel, JobPrepareSet, JobSeed},
+        meta::{
+            DependentCounts, JobAdmission, JobComparator, JobDependencyPriority, JobInterval,
+            JobMeta, JobPriority, JobTargetDespawned, JobTask, Priority,
+        },
+        DeviceLostPolicy, GraphicsJob, JobChunk, JobComplete, JobCompletion, JobCompletionBatch,
+        JobCompletionSender, JobDone, JobError, JobExecutionSettings, JobMarker, JobResults,
+        JobRunContext, JobSubmissions, OnJobDone, OnJobFailed,
+    };
+
+    use super::{
+        admit_jobs, admit_ready_jobs, cancel_orphaned_jobs, check_job_inputs, configure_job_sets,
+        drive_chunks, increment_interval_frames, job_input_statuses, reset_jobs, schedule_jobs,
+        setup_interval_frames, stall_warning, sync_completed_jobs_main_world, ChunkOutcome,
+        DynamicJob, JobChunkProgress, JobReady, JobResult, JobResultMainWorldReceiver,
+        JobResultSender, JobSchedule, JobSet, RecordingBudget, TimeOutFrames,
+    };
+    use crate::transition::JobReadyMainWorldSender;
+
+    #[derive(Resource, Default)]
+    struct SystemOrder(Vec<&'static str>);
+
+    #[test]
+    fn pipelines_are_queued_before_jobs_are_checked() {
+        let mut world = World::new();
+        world.init_resource::<SystemOrder>();
+
+        let mut schedule = Render::base_schedule();
+        configure_job_sets(&mut schedule);
+        let record = |name| move |mut order: ResMut<SystemOrder>| order.0.push(name);
+        schedule.add_systems((
+            record("execute").in_set(JobSet::Execute),
+            record("check").in_set(JobSet::Check),
+            record("prepare").in_set(RenderSet::Prepare),
+            // a user system reading the pipelines queued this frame
+            record("user").after(JobSet::QueuePipelines),
+            record("queue").in_set(JobSet::QueuePipelines),
+            record("setup").in_set(JobSet::Setup),
+        ));
+        schedule.run(&mut world);
+
+        let order = &world.resource::<SystemOrder>().0;
+        let position = |name| order.iter().position(|system| *system == name).unwrap();
+        assert!(position("queue") < position("user"));
+        assert!(position("queue") < position("prepare"));
+        assert!(position("prepare") < position("check"));
+        assert!(position("setup") < position("check"));
+        assert!(position("check") < position("execute"));
+    }
+
+    /// An input whose bind groups bind buffers written in [`RenderSet::PrepareResources`].
+    struct BindGroupInput;
+
+    impl<J: GraphicsJob> JobInput<J> for BindGroupInput {
+        type Data = ();
+
+        type Item<'a> = ();
+
+        const PREPARE_AFTER: Option<RenderSet> = Some(RenderSet::PrepareResources);
+
+        fn status(_data: QueryItem<Self::Data>, _world: &World) -> JobInputStatus {
+            JobInputStatus::Ready
+        }
+
+        fn get<'a>(_data: QueryItem<'a, Self::Data>, _world: &'a World) -> Self::Item<'a> {}
+    }
+
+    #[test]
+    fn inputs_are_prepared_after_their_render_set() {
+        let mut world = World::new();
+        world.init_resource::<SystemOrder>();
+
+        let mut schedule = Render::base_schedule();
+        configure_job_sets(&mut schedule);
+        <(ReadyInput, BindGroupInput) as JobInput<StuckJob>>::configure_prepare_sets(&mut schedule);
+        let record = |name| move |mut order: ResMut<SystemOrder>| order.0.push(name);
+        schedule.add_systems((
+            record("check").in_set(JobSet::Check),
+            record("prepare_input").in_set(JobPrepareSet::of::<BindGroupInput>()),
+            record("prepare_resources").in_set(RenderSet::PrepareResources),
+        ));
+        schedule.run(&mut world);
+
+        let order = &world.resource::<SystemOrder>().0;
+        let position = |name| order.iter().position(|system| *system == name).unwrap();
+        assert!(position("prepare_resources") < position("prepare_input"));
+        assert!(position("prepare_input") < position("check"));
+    }
+
+    fn chunked_job(chunks: u32) -> impl FnMut(&mut u32, u32) -> Result<JobChunk, JobError> {
+        move |_, chunk| {
+            if chunk + 1 < chunks {
+                Ok(JobChunk::Yield)
+            } else {
+                Ok(JobChunk::Done)
+            }
+        }
+    }
+
+    #[test]
+    fn three_chunks_complete_in_one_frame() {
+        let mut submits_left = 16;
+        let mut flushes = 0;
+        let outcome = drive_chunks(
+            &mut flushes,
+            0,
+            &mut submits_left,
+            chunked_job(3),
+            |flushes| {
+                *flushes += 1;
+                Ok(())
+            },
+        );
+        assert_eq!(outcome, ChunkOutcome::Finished(Ok(())));
+        assert_eq!(flushes, 2);
+        assert_eq!(submits_left, 14);
+    }
+
+    #[test]
+    fn chunks_resume_after_budget() {
+        let mut submits_left = 1;
+        let outcome = drive_chunks(&mut 0, 0, &mut submits_left, chunked_job(3), |_| Ok(()));
+        assert_eq!(outcome, ChunkOutcome::Suspended(2));
+        assert_eq!(submits_left, 0);
+
+        let mut submits_left = 1;
+        let outcome = drive_chunks(&mut 0, 2, &mut submits_left, chunked_job(3), |_| Ok(()));
+        assert_eq!(outcome, ChunkOutcome::Finished(Ok(())));
+    }
+
+    #[test]
+    fn chunk_errors_finish_job() {
+        let mut submits_left = 16;
+        let outcome = drive_chunks(
+            &mut (),
+            0,
+            &mut submits_left,
+            |_, chunk| match chunk {
+                0 => Ok(JobChunk::Yield),
+                _ => Err(JobError::ExecutionFailed),
+            },
+            |_| Ok(()),
+        );
+        assert_eq!(
+            outcome,
+            ChunkOutcome::Finished(Err(JobError::ExecutionFailed))
+        );
+    }
+
+    #[test]
+    fn flush_errors_finish_job() {
+        let mut submits_left = 16;
+        let mut chunks_run = 0;
+        let outcome = drive_chunks(
+            &mut chunks_run,
+            0,
+            &mut submits_left,
+            |chunks_run, chunk| {
+                *chunks_run += 1;
+                chunked_job(3)(chunks_run, chunk)
+            },
+            |_| Err(JobError::ExecutionFailed),
+        );
+        assert_eq!(
+            outcome,
+            ChunkOutcome::Finished(Err(JobError::ExecutionFailed))
+        );
+        // the job's later chunks aren't run once its earlier ones failed to submit
+        assert_eq!(chunks_run, 1);
+    }
+
+    #[test]
+    fn recording_stops_once_over_budget_and_resumes_next_frame() {
+        let mut pending = vec![JobPriority::default(); 3];
+        pending.push(JobPriority::critical());
+        let record_slowly = || std::thread::sleep(Duration::from_millis(2));
+
+        let mut frames = Vec::new();
+        while !pending.is_empty() {
+            let mut budget = RecordingBudget::new(Some(Duration::from_micros(100)));
+            let mut recorded = 0;
+            pending.retain(|priority| {
+                if !budget.admits(*priority) {
+                    return true;
+                }
+                budget.measure(record_slowly);
+                recorded += 1;
+                false
+            });
+            frames.push(recorded);
+        }
+
+        // the first job fits in the budget, and the critical job runs regardless
+        assert_eq!(frames, vec![2, 1, 1]);
+
+        // without a limit, every job is recorded
+        let budget = RecordingBudget::new(None);
+        assert!(budget.admits(JobPriority::default()));
+    }
+
+    struct ReadyInput;
+
+    impl<J: GraphicsJob> JobInput<J> for ReadyInput {
+        type Data = ();
+
+        type Item<'a> = ();
+
+        fn status(_data: QueryItem<Self::Data>, _world: &World) -> JobInputStatus {
+            JobInputStatus::Ready
+        }
+
+        fn get<'a>(_data: QueryItem<'a, Self::Data>, _world: &'a World) -> Self::Item<'a> {}
+    }
+
+    struct WaitingInput;
+
+    impl<J: GraphicsJob> JobInput<J> for WaitingInput {
+        type Data = ();
+
+        type Item<'a> = ();
+
+        fn status(_data: QueryItem<Self::Data>, _world: &World) -> JobInputStatus {
+            JobInputStatus::Wait
+        }
+
+        fn get<'a>(_data: QueryItem<'a, Self::Data>, _world: &'a World) -> Self::Item<'a> {}
+    }
+
+    #[derive(Clone, Component)]
+    struct StuckJob;
+
+    impl GraphicsJob for StuckJob {
+        type In = (ReadyInput, (ReadyInput, WaitingInput));
+
+        fn run(
+            &self,
+            _world: &World,
+            _context: &mut JobRunContext,
+            _input: JobInputItem<Self, Self::In>,
+        ) -> Result<(), JobError> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn input_statuses_identify_waiting_input() {
+        let mut world = World::new();
+        let job = world.spawn((StuckJob, DynamicJob::new::<StuckJob>())).id();
+
+        let statuses = job_input_statuses(&world, job);
+        let waiting = statuses
+            .iter()
+            .filter(|(_, status)| *status == JobInputStatus::Wait)
+            .collect::<Vec<_>>();
+
+        assert_eq!(statuses.len(), 3);
+        assert_eq!(waiting.len(), 1);
+        assert!(waiting[0].0.ends_with("WaitingInput"));
+    }
+
+    #[test]
+    fn stalls_are_reported_once_per_job_type() {
+        let mut world = World::new();
+        let job = DynamicJob::new::<StuckJob>();
+        let first = world.spawn((StuckJob, job)).id();
+        let second = world.spawn((StuckJob, job)).id();
+
+        let mut warned = HashSet::new();
+        let warning = stall_warning(&job, world.entity(first), &world, 8, &mut warned).unwrap();
+        assert!(warning.starts_with("input `WaitingInput` of job `StuckJob`"));
+        assert!(stall_warning(&job, world.entity(second), &world, 8, &mut warned).is_none());
+    }
+
+    #[test]
+    fn input_statuses_ignore_non_jobs() {
+        let mut world = World::new();
+        let entity = world.spawn_empty().id();
+        assert!(job_input_statuses(&world, entity).is_empty());
+    }
+
+    #[test]
+    fn device_lost_requeues_jobs() {
+        let mut world = World::new();
+        let job = world
+            .spawn((JobMarker, JobReady, JobChunkProgress(2), TimeOutFrames(5)))
+            .id();
+
+        reset_jobs(&mut world, DeviceLostPolicy::Requeue);
+        assert!(world.get::<JobReady>(job).is_none());
+        assert!(world.get::<JobChunkProgress>(job).is_none());
+        assert_eq!(
+            world.get::<TimeOutFrames>(job).map(|frames| frames.0),
+            Some(0)
+        );
+    }
+
+    #[test]
+    fn device_lost_fails_jobs() {
+        let mut world = World::new();
+        let (sender, receiver) = crossbeam_channel::unbounded();
+        world.insert_resource(JobResultSender(sender));
+        world.init_resource::<JobExecutionSettings>();
+        let job = world.spawn((JobMarker, JobReady)).id();
+
+        reset_jobs(&mut world, DeviceLostPolicy::Fail);
+        assert!(world.get_entity(job).is_err());
+        assert_eq!(
+            receiver.try_recv().map(|result| result.result),
+            Ok(Err(JobError::DeviceLost))
+        );
+    }
+
+    #[test]
+    fn orphaned_jobs_are_cancelled() {
+        let mut world = World::new();
+        let (sender, receiver) = crossbeam_channel::unbounded();
+        world.insert_resource(JobResultSender(sender));
+        world.init_resource::<JobExecutionSettings>();
+        let orphaned = world
+            .spawn((JobMarker, JobTargetDespawned, JobChunkProgress(1)))
+            .id();
+        let waiting = world.spawn(JobMarker).id();
+
+        world.run_system_once(cancel_orphaned_jobs).unwrap();
+        assert!(world.get_entity(orphaned).is_err());
+        assert!(world.get_entity(waiting).is_ok());
+        let results = receiver
+            .try_iter()
+            .map(|result| (result.entity, result.result))
+            .collect::<Vec<_>>();
+        assert_eq!(results, vec![(orphaned, Err(JobError::Cancelled))]);
+    }
+
+    #[derive(Clone, Component)]
+    struct AppendJob;
+
+    impl GraphicsJob for AppendJob {
+        type In = ();
+
+        const IS_IDEMPOTENT: bool = false;
+
+        fn run(
+            &self,
+            _world: &World,
+            _context: &mut JobRunContext,
+            (): JobInputItem<Self, Self::In>,
+        ) -> Result<(), JobError> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn device_lost_fails_started_non_idempotent_jobs() {
+        let mut world = World::new();
+        let (sender, receiver) = crossbeam_channel::unbounded();
+        world.insert_resource(JobResultSender(sender));
+        world.init_resource::<JobExecutionSettings>();
+        let job = DynamicJob::new::<AppendJob>();
+        let started = world
+            .spawn((JobMarker, job, JobReady, JobChunkProgress(2)))
+            .id();
+        let waiting = world.spawn((JobMarker, job)).id();
+
+        reset_jobs(&mut world, DeviceLostPolicy::Requeue);
+        // running the started job again would repeat its first chunks
+        assert!(world.get_entity(started).is_err());
+        assert_eq!(
+            receiver
+                .try_recv()
+                .map(|result| (result.entity, result.result)),
+            Ok((started, Err(JobError::DeviceLost)))
+        );
+        assert!(receiver.try_recv().is_err());
+        assert_eq!(
+            world.get::<TimeOutFrames>(waiting).map(|frames| frames.0),
+            Some(0)
+        );
+    }
+
+    #[derive(Clone, Component)]
+    struct ReadyJob;
+
+    impl GraphicsJob for ReadyJob {
+        type In = ReadyInput;
+
+        fn run(
+            &self,
+            _world: &World,
+            _context: &mut JobRunContext,
+            _input: JobInputItem<Self, Self::In>,
+        ) -> Result<(), JobError> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn blocked_jobs_leave_budget_to_ready_jobs() {
+        let mut world = World::new();
+        let (sender, _receiver) = crossbeam_channel::unbounded();
+        world.insert_resource(JobResultSender(sender));
+        world.init_resource::<JobExecutionSettings>();
+        let (ready_sender, _ready_receiver) = crossbeam_channel::unbounded();
+        world.insert_resource(JobReadyMainWorldSender(ready_sender));
+
+        // higher priority jobs that are blocked on their inputs
+        for _ in 0..4 {
+            world.spawn((
+                StuckJob,
+                DynamicJob::new::<StuckJob>(),
+                JobPriority::non_critical::<8>(),
+            ));
+        }
+        let ready = (0..2)
+            .map(|_| {
+                world
+                    .spawn((
+                        ReadyJob,
+                        DynamicJob::new::<ReadyJob>(),
+                        JobPriority::default(),
+                    ))
+                    .id()
+            })
+            .collect::<Vec<_>>();
+
+        world.run_system_once(check_job_inputs).unwrap();
+
+        let admitted = world
+            .run_system_once(|jobs: Query<(Entity, &JobPriority), With<JobReady>>| {
+                let settings = JobExecutionSettings {
+                    max_jobs_per_frame: 2,
+                    ..Default::default()
+                };
+                admit_jobs(
+                    jobs.iter().map(|(id, p)| (id, *p, 0)).collect(),
+                    None,
+                    &settings,
+                )
+                .collect::<Vec<_>>()
+            })
+            .unwrap();
+
+        assert_eq!(admitted.len(), 2);
+        assert!(admitted.iter().all(|job| ready.contains(job)));
+    }
+
+    #[test]
+    fn interval_jobs_run_on_their_frames() {
+        let mut world = World::new();
+        let (sender, _receiver) = crossbeam_channel::unbounded();
+        world.insert_resource(JobResultSender(sender));
+        world.init_resource::<JobExecutionSettings>();
+        let (ready_sender, _ready_receiver) = crossbeam_channel::unbounded();
+        world.insert_resource(JobReadyMainWorldSender(ready_sender));
+
+        let intervals = [JobInterval::new(4), JobInterval::new(4).with_phase(1)];
+        let jobs = intervals.map(|interval| {
+            world
+                .spawn((ReadyJob, DynamicJob::new::<ReadyJob>(), interval))
+                .id()
+        });
+
+        let mut frames = [const { Vec::new() }; 2];
+        for frame in 0..10 {
+            world.run_system_once(setup_interval_frames).unwrap();
+            world.run_system_once(check_job_inputs).unwrap();
+            for (job, frames) in jobs.iter().zip(&mut frames) {
+                if world.get::<JobReady>(*job).is_some() {
+                    frames.push(frame);
+                    // the job is queued again once it runs
+                    world.entity_mut(*job).remove::<JobReady>();
+                }
+            }
+            world.run_system_once(increment_interval_frames).unwrap();
+        }
+
+        assert_eq!(frames[0], [0, 4, 8]);
+        // a phase spreads jobs with the same interval across frames
+        assert_eq!(frames[1], [1, 5, 9]);
+    }
+
+    #[test]
+    fn admission_respects_priority_and_budget() {
+        let jobs = vec![
+            (0, JobPriority::non_critical::<1>(), 0),
+            (1, JobPriority::non_critical::<4>(), 0),
+            (2, JobPriority::critical(), 0),
+            (3, JobPriority::critical(), 0),
+            (4, JobPriority::non_critical::<2>(), 0),
+        ];
+        let settings = JobExecutionSettings {
+            max_jobs_per_frame: 3,
+            ..Default::default()
+        };
+
+        let admitted = admit_jobs(jobs, None, &settings).collect::<Vec<_>>();
+        assert_eq!(admitted.len(), 3);
+        assert!(admitted.contains(&2) && admitted.contains(&3));
+        assert_eq!(admitted[2], 1);
+    }
+
+    #[derive(Component)]
+    struct Distance(u32);
+
+    #[test]
+    fn comparators_order_admission() {
+        let mut world = World::new();
+        let jobs = [(30, 1), (10, 2), (20, 3), (40, 4)]
+            .map(|(distance, weight)| (world.spawn(Distance(distance)).id(), weight));
+        let settings = JobExecutionSettings {
+            max_jobs_per_frame: 3,
+            ..Default::default()
+        };
+        let metas = || {
+            jobs.iter()
+                .enumerate()
+                .map(|(sequence, (entity, weight))| {
+                    let priority =
+                        JobPriority(Priority::NonCritical(NonZero::new(*weight).unwrap()));
+                    let meta = JobMeta {
+                        entity: world.entity(*entity),
+                        label: ShortName::of::<Distance>(),
+                        priority,
+                        waited_frames: 0,
+                        dependents: 0,
+                        sequence: sequence as u64,
+                    };
+                    (meta, priority, 0)
+                })
+                .collect::<Vec<_>>()
+        };
+        let admit = |comparator: JobComparator| {
+            let order = comparator
+                .get()
+                .map(|compare| move |a: &JobMeta, b: &JobMeta| compare(a, b));
+            admit_jobs(
+                metas(),
+                order
+                    .as_ref()
+                    .map(|order| order as &dyn Fn(&_, &_) -> Ordering),
+                &settings,
+            )
+            .map(|meta| meta.sequence)
+            .collect::<Vec<_>>()
+        };
+
+        // without a comparator, jobs are admitted by priority
+        assert_eq!(admit(JobComparator::default()), [3, 2, 1]);
+
+        // the nearest jobs are admitted first, whatever their priority
+        let nearest = JobComparator::new(|a, b| {
+            let distance = |meta: &JobMeta| meta.entity.get::<Distance>().map(|d| d.0);
+            distance(a).cmp(&distance(b))
+        });
+        assert_eq!(admit(nearest), [1, 2, 0]);
+    }
+
+    #[test]
+    fn exclusive_jobs_run_alone() {
+        let settings = JobExecutionSettings::default();
+        let exclusive = [1, 3];
+        let mut pending = vec![
+            (0, JobPriority::critical(), 0),
+            (1, JobPriority::non_critical::<1>(), 0),
+            (2, JobPriority::non_critical::<2>(), 0),
+            (3, JobPriority::non_critical::<4>(), 0),
+        ];
+
+        // exclusive jobs run one per frame by priority, while the other ready jobs wait
+        let mut frames = Vec::new();
+        while !pending.is_empty() {
+            let admitted = admit_ready_jobs(
+                pending.clone(),
+                |job| exclusive.contains(job),
+                None,
+                &settings,
+            )
+            .collect::<Vec<_>>();
+            pending.retain(|(job, _, _)| !admitted.contains(job));
+            frames.push(admitted);
+        }
+        assert_eq!(frames, vec![vec![3], vec![1], vec![0, 2]]);
+    }
+
+    #[test]
+    fn transient_budget_staggers_admission() {
+        let settings = JobExecutionSettings {
+            max_transient_bytes: Some(100),
+            ..Default::default()
+        };
+        let mut pending = (0..4)
+            .map(|i| (i, JobPriority::default(), 60))
+            .collect::<Vec<_>>();
+
+        // only one large job fits in the budget each frame
+        for _ in 0..4 {
+            let admitted = admit_jobs(pending.clone(), None, &settings).collect::<Vec<_>>();
+            assert_eq!(admitted.len(), 1);
+            pending.retain(|(i, _, _)| !admitted.contains(i));
+        }
+        assert!(pending.is_empty());
+
+        // smaller jobs fill in the rest of the budget
+        let jobs = vec![
+            (0, JobPriority::non_critical::<3>(), 60),
+            (1, JobPriority::non_critical::<2>(), 60),
+            (2, JobPriority::non_critical::<1>(), 40),
+        ];
+        assert_eq!(
+            admit_jobs(jobs, None, &settings).collect::<Vec<_>>(),
+            vec![0, 2]
+        );
+    }
+
+    #[test]
+    fn oversized_jobs_still_run() {
+        let settings = JobExecutionSettings {
+            max_transient_bytes: Some(100),
+            ..Default::default()
+        };
+        let jobs = vec![(0, JobPriority::default(), 1000)];
+        assert_eq!(
+            admit_jobs(jobs, None, &settings).collect::<Vec<_>>(),
+            vec![0]
+        );
+    }
+
+    /// A leaf job whose output fans out to three jobs, one of which starts a chain of
+    /// three more, competing with shallow jobs that nothing depends on.
+    fn wide_then_deep(strategy: JobDependencyPriority) -> Vec<(&'static str, JobPriority, u64)> {
+        let label = JobOutputLabel;
+        let dependents = [
+            (label("leaf"), None),
+            (label("leaf"), None),
+            (label("leaf"), Some(label("mid"))),
+            (label("mid"), Some(label("deep"))),
+            (label("deep"), Some(label("deeper"))),
+            (label("deeper"), None),
+        ];
+        let counts = DependentCounts::new(strategy, dependents);
+
+        // only the leaf and the shallow jobs are ready
+        let ready = [
+            ("shallow_a", None),
+            ("shallow_b", None),
+            ("leaf", Some(label("leaf"))),
+            ("shallow_c", None),
+        ];
+        ready
+            .into_iter()
+            .map(|(name, output)| (name, counts.raise(JobPriority::default(), output), 0))
+            .collect()
+    }
+
+    #[test]
+    fn dependency_priority_runs_bottleneck_first() {
+        let settings = JobExecutionSettings {
+            max_jobs_per_frame: 1,
+            ..Default::default()
+        };
+
+        for strategy in [
+            JobDependencyPriority::Dependents,
+            JobDependencyPriority::Subtree,
+        ] {
+            let admitted =
+                admit_jobs(wide_then_deep(strategy), None, &settings).collect::<Vec<_>>();
+            assert_eq!(admitted, vec!["leaf"]);
+        }
+
+        // the leaf unblocks three jobs directly, and six in total
+        let priority = |strategy| {
+            wide_then_deep(strategy)
+                .into_iter()
+                .find(|(name, ..)| *name == "leaf")
+                .unwrap()
+                .1
+        };
+        assert_eq!(
+            priority(JobDependencyPriority::Dependents),
+            JobPriority::non_critical::<4>()
+        );
+        assert_eq!(
+            priority(JobDependencyPriority::Subtree),
+            JobPriority::non_critical::<7>()
+        );
+        assert_eq!(priority(JobDependencyPriority::Off), JobPriority::default());
+    }
+
+    #[test]
+    fn weighted_random_admission_tracks_weights() {
+        let settings = JobExecutionSettings {
+            max_jobs_per_frame: 1,
+            admission: JobAdmission::WeightedRandom(JobSeed::new(7)),
+            ..Default::default()
+        };
+        let weights = [1, 2, 4, 1];
+        let admit = |frame| {
+            let ready = weights.iter().enumerate().map(|(job, weight)| {
+                let schedule = JobSchedule::<()> {
+                    priority: JobPriority(Priority::NonCritical(NonZero::new(*weight).unwrap())),
+                    transient_bytes: 0,
+                    exclusive: false,
+                    output: None,
+                };
+                (job, schedule)
+            });
+            schedule_jobs(ready, [], None, &settings, frame).collect::<Vec<_>>()
+        };
+
+        const FRAMES: u32 = 8000;
+        let mut counts = [0; 4];
+        for frame in 0..FRAMES {
+            let admitted = admit(frame);
+            assert_eq!(admitted.len(), 1);
+            counts[admitted[0]] += 1;
+        }
+
+        // each job is admitted in proportion to its weight, give or take
+        let total = weights.iter().sum::<u32>() as f32;
+        for (count, weight) in counts.iter().zip(weights) {
+            let expected = FRAMES as f32 * weight as f32 / total;
+            assert!((*count as f32 - expected).abs() < expected * 0.1);
+        }
+
+        // the same seed makes the same decisions
+        assert!((0..64).all(|frame| admit(frame) == admit(frame)));
+        assert_ne!((0..64).map(admit).collect::<Vec<_>>(), vec![admit(0); 64]);
+    }
+
+    /// Returns the frame a low priority job is first admitted on, while four high
+    /// priority jobs are ready every frame, or `None` if it isn't within 256 frames.
+    fn admit_under_load(admission: JobAdmission) -> Option<u32> {
+        let settings = JobExecutionSettings {
+            max_jobs_per_frame: 1,
+            admission,
+            ..Default::default()
+        };
+
+        // admitted high priority jobs are replaced by new ones the next frame
+        let mut new_high = 4..;
+        let mut high = (0..4).map(Some).collect::<Vec<_>>();
+        (0..256).find(|frame| {
+            let ready = iter::once((None, JobPriority::default()))
+                .chain(
+                    high.iter()
+                        .map(|job| (*job, JobPriority::non_critical::<8>())),
+                )
+                .map(|(job, priority)| {
+                    let schedule = JobSchedule::<()> {
+                        priority,
+                        transient_bytes: 0,
+                        exclusive: false,
+                        output: None,
+                    };
+                    (job, schedule)
+                });
+            let admitted = schedule_jobs(ready, [], None, &settings, *frame).collect::<Vec<_>>();
+            assert_eq!(admitted.len(), 1);
+
+            let Some(job) = admitted[0] else {
+                return true;
+            };
+            high.retain(|high| *high != Some(job));
+            high.push(new_high.next());
+            false
+        })
+    }
+
+    #[test]
+    fn weighted_random_admission_avoids_starvation() {
+        // ordered admission always prefers the high priority jobs
+        assert_eq!(admit_under_load(JobAdmission::Ordered), None);
+
+        // the low priority job has a 1 in 33 chance each frame, so it's admitted within
+        // 256 frames with any but the unluckiest seeds, and on the same frame each run
+        let admission = JobAdmission::WeightedRandom(JobSeed::new(7));
+        let frame = admit_under_load(admission);
+        assert!(frame.is_some());
+        assert_eq!(admit_under_load(admission), frame);
+    }
+
+    #[test]
+    fn dependency_cycles_terminate() {
+        let label = JobOutputLabel;
+        let counts = DependentCounts::new(
+            JobDependencyPriority::Subtree,
+            [
+                (label("a"), Some(label("b"))),
+                (label("b"), Some(label("a"))),
+            ],
+        );
+        let raised = counts.raise(JobPriority::default(), Some(label("a")));
+        assert!(raised > JobPriority::default());
+    }
+
+    /// Completes 500 jobs in one frame, half of them with each kind of notification.
+    #[test]
+    fn batched_completions_are_sent_once_per_frame() {
+        let mut world = World::new();
+        let (main_sender, main_receiver) = crossbeam_channel::unbounded();
+        world.insert_resource(JobResultMainWorldReceiver(main_receiver));
+        world.init_resource::<JobExecutionSettings>();
+        world.init_resource::<JobResults>();
+        world.init_resource::<JobSubmissions>();
+
+        let (sender, receiver) = crossbeam_channel::unbounded();
+        let (batch_sender, batch_receiver) = crossbeam_channel::unbounded();
+        for i in 0..500 {
+            let job = match i % 2 {
+                0 => world
+                    .spawn((JobMarker, JobCompletionSender(sender.clone())))
+                    .id(),
+                _ => world
+                    .spawn((JobMarker, JobCompletionBatch(batch_sender.clone())))
+                    .id(),
+            };
+            main_sender
+                .send(JobResult {
+                    entity: job,
+                    main_entity: Some(job.into()),
+                    result: Ok(()),
+                    value: None,
+                    submission: None,
+                })
+                .unwrap();
+        }
+
+        world
+            .run_system_once(sync_completed_jobs_main_world)
+            .unwrap();
+
+        // one send per job, against a single send for the whole batch
+        assert_eq!(receiver.try_iter().count(), 250);
+        let batches = batch_receiver.try_iter().collect::<Vec<_>>();
+        assert_eq!(batches.len(), 1);
+        assert_eq!(batches[0].len(), 250);
+        assert!(batches[0].iter().all(|(_, result)| result.is_ok()));
+    }
+
+    #[test]
+    fn completions_trigger_done_or_failed() {
+        let mut world = World::new();
+        let (main_sender, main_receiver) = crossbeam_channel::unbounded();
+        world.insert_resource(JobResultMainWorldReceiver(main_receiver));
+        world.init_resource::<JobExecutionSettings>();
+        world.init_resource::<JobResults>();
+        world.init_resource::<JobSubmissions>();
+        world.init_resource::<Transitions>();
+
+        let done = world.spawn(JobMarker).observe(on_done).id();
+        let failed = world.spawn(JobMarker).observe(on_failed).id();
+        for (job, result) in [(done, Ok(())), (failed, Err(JobError::ExecutionFailed))] {
+            main_sender
+                .send(JobResult {
+                    entity: job,
+                    main_entity: Some(job.into()),
+                    result,
+                    value: None,
+                    submission: None,
+                })
+                .unwrap();
+        }
+
+        world
+            .run_system_once(sync_completed_jobs_main_world)
+            .unwrap();
+
+        let transitions = world.resource::<Transitions>();
+        assert_eq!(transitions.done, vec![done]);
+        assert_eq!(
+            transitions.failed,
+            vec![(failed, JobError::ExecutionFailed)]
+        );
+    }
+
+    #[test]
+    fn completions_store_result_values() {
+        let mut world = World::new();
+        let (main_sender, main_receiver) = crossbeam_channel::unbounded();
+        world.insert_resource(JobResultMainWorldReceiver(main_receiver));
+        world.insert_resource(JobExecutionSettings {
+            done_retention_frames: 1,
+            ..Default::default()
+        });
+        world.init_resource::<JobResults>();
+        world.init_resource::<JobSubmissions>();
+
+        let job = world.spawn(JobMarker).id();
+        main_sender
+            .send(JobResult {
+                entity: job,
+                main_entity: Some(job.into()),
+                result: Ok(()),
+                value: Some(Box::new(37u32)),
+                submission: None,
+            })
+            .unwrap();
+
+        world
+            .run_system_once(sync_completed_jobs_main_world)
+            .unwrap();
+
+        assert_eq!(world.resource::<JobResults>().get::<u32>(job), Some(&37));
+        assert_eq!(world.get::<JobDone>(job).unwrap().result, Ok(()));
+    }
+
+    /// Cancels a task of three jobs after the first completes, while the second is
+    /// still running on the GPU.
+    #[test]
+    fn cancelling_a_task_keeps_completed_results() {
+        let mut world = World::new();
+        let (main_sender, main_receiver) = crossbeam_channel::unbounded();
+        world.insert_resource(JobResultMainWorldReceiver(main_receiver));
+        world.insert_resource(JobExecutionSettings {
+            done_retention_frames: 4,
+            ..Default::default()
+        });
+        world.init_resource::<JobResults>();
+        world.init_resource::<JobSubmissions>();
+        world.init_resource::<Transitions>();
+
+        let [baked, running, waiting] = [(); 3].map(|()| {
+            world
+                .spawn((JobTask(0), JobMarker))
+                .observe(on_done)
+                .observe(on_failed)
+                .id()
+        });
+        let other = world.spawn((JobTask(1), JobMarker)).id();
+        let complete = |job: Entity, value: u32| JobResult {
+            entity: job,
+            main_entity: Some(job.into()),
+            result: Ok(()),
+            value: Some(Box::new(value)),
+            submission: None,
+        };
+
+        main_sender.send(complete(baked, 1)).unwrap();
+        world
+            .run_system_once(sync_completed_jobs_main_world)
+            .unwrap();
+
+        cancel_task(JobTask(0)).apply(&mut world);
+        // the running job finishes after it was cancelled
+        main_sender.send(complete(running, 2)).unwrap();
+        world
+            .run_system_once(sync_completed_jobs_main_world)
+            .unwrap();
+
+        let transitions = world.resource::<Transitions>();
+        assert_eq!(transitions.done, vec![baked]);
+        let mut failed = transitions.failed.clone();
+        failed.sort_by_key(|(job, _)| *job);
+        assert_eq!(
+            failed,
+            vec![
+                (running, JobError::Cancelled),
+                (waiting, JobError::Cancelled)
+            ]
+        );
+
+        let results = world.resource::<JobResults>();
+        assert_eq!(results.get::<u32>(baked), Some(&1));
+        assert_eq!(results.get::<u32>(running), None);
+        let done = [baked, running, waiting].map(|job| world.get::<JobDone>(job).unwrap().result);
+        assert_eq!(
+            done,
+            [Ok(()), Err(JobError::Cancelled), Err(JobError::Cancelled)]
+        );
+        assert!(world.get::<JobMarker>(other).is_some());
+    }
+
+    /// Cancels a task whose jobs are despawned as soon as they complete, while one of
+    /// them is still running on the GPU.
+    #[test]
+    fn cancelled_jobs_complete_once_without_retention() {
+        let mut world = World::new();
+        let (main_sender, main_receiver) = crossbeam_channel::unbounded();
+        world.insert_resource(JobResultMainWorldReceiver(main_receiver));
+        world.insert_resource(JobExecutionSettings {
+            done_retention_frames: 0,
+            ..Default::default()
+        });
+        world.init_resource::<JobResults>();
+        world.init_resource::<JobSubmissions>();
+        world.init_resource::<Completions>();
+        world.add_observer(on_complete);
+
+        let [running, waiting] = [(); 2].map(|()| world.spawn((JobTask(0), JobMarker)).id());
+        cancel_task(JobTask(0)).apply(&mut world);
+        world.flush();
+
+        // the running job finishes after it was cancelled and despawned
+        main_sender
             .send(JobResult {
-                entity: entity_ref.id(),
-                main_entity: main_entity.copied(),
-                result,
+                entity: running,
+                main_entity: Some(running.into()),
+                result: Ok(()),
+                value: Some(Box::new(2u32)),
+                submission: None,
             })
             .unwrap();
+        world
+            .run_system_once(sync_completed_jobs_main_world)
+            .unwrap();
+
+        let mut completions = world.resource::<Completions>().0.clone();
+        completions.sort_by_key(|(job, _)| *job);
+        assert_eq!(
+            completions,
+            vec![
+                (running, Err(JobError::Cancelled)),
+                (waiting, Err(JobError::Cancelled))
+            ]
+        );
+        assert_eq!(world.resource::<JobResults>().get::<u32>(running), None);
+        assert!(!world.entities().contains(running));
+    }
+
+    #[derive(Resource, Default)]
+    struct Transitions {
+        done: Vec<Entity>,
+        failed: Vec<(Entity, JobError)>,
     }
 
-    render_queue.submit(command_encoders.drain(..).map(|cmd| cmd.finish()));
+    #[derive(Resource, Default)]
+    struct Completions(Vec<JobCompletion>);
+
+    fn on_complete(trigger: Trigger<JobComplete>, mut completions: ResMut<Completions>) {
+        completions.0.push((trigger.entity(), trigger.event().0));
+    }
+
+    fn on_done(trigger: Trigger<OnJobDone>, mut transitions: ResMut<Transitions>) {
+        transitions.done.push(trigger.entity());
+    }
+
+    fn on_failed(trigger: Trigger<OnJobFailed>, mut transitions: ResMut<Transitions>) {
+        transitions
+            .failed
+            .push((trigger.entity(), trigger.event().0));
+    }
 }
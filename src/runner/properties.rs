@@ -0,0 +1,726 @@
+//! Property tests for the scheduler. Each test generates many random job graphs from
+//! fixed seeds, and runs them frame by frame through the real render world systems that
+//! check, cancel, time out and complete jobs, and the real main world systems that
+//! complete them. Only executing the jobs admitted by [`schedule_jobs`] is simulated,
+//! since that needs a GPU. Failing cases print their seed and graph.
+
+use core::{any::type_name, num::NonZero};
+
+use bevy_ecs::{
+    component::Component,
+    entity::Entity,
+    observer::{Observer, Trigger},
+    query::{QueryItem, With, Without},
+    schedule::{IntoSystemConfigs, Schedule},
+    system::{lifetimeless::Read, Local, Query, Res, ResMut, Resource},
+    world::{Command, World},
+};
+use bevy_render::sync_world::MainEntity;
+use bevy_utils::HashSet;
+
+use crate::{
+    cancel_task,
+    done::despawn_done_jobs,
+    input::{JobDependency, JobInput, JobInputItem, JobInputStatus, JobOutputLabel},
+    meta::{
+        JobDependencyPriority, JobPriority, JobTargetDespawned, JobTask, JobTransientMemory,
+        Priority,
+    },
+    result::JobResults,
+    submission::JobSubmissions,
+    transition::JobReadyMainWorldSender,
+    GraphicsJob, JobComplete, JobCompletion, JobError, JobExecutionSettings, JobMarker,
+    JobQueueSnapshot, JobRunContext, JobSnapshot,
+};
+
+use super::{
+    cancel_orphaned_jobs, check_job_inputs, increment_time_out_frames, schedule_jobs,
+    setup_time_out_frames, sync_completed_jobs, sync_completed_jobs_main_world, time_out_jobs,
+    DynamicJob, JobReady, JobResult, JobResultMainWorldReceiver, JobResultMainWorldSender,
+    JobResultReceiver, JobResultSender, JobSchedule,
+};
+
+/// The number of seeds each property is checked with.
+const CASES: u64 = 256;
+
+const LABELS: [&str; 24] = [
+    "j0", "j1", "j2", "j3", "j4", "j5", "j6", "j7", "j8", "j9", "j10", "j11", "j12", "j13", "j14",
+    "j15", "j16", "j17", "j18", "j19", "j20", "j21", "j22", "j23",
+];
+
+/// A small xorshift generator, so that failing cases can be reproduced from their seed.
+struct Rng(u64);
+
+impl Rng {
+    fn new(seed: u64) -> Self {
+        Self(seed.wrapping_mul(0x9E37_79B9_7F4A_7C15) | 1)
+    }
+
+    fn next(&mut self) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0
+    }
+
+    fn below(&mut self, n: u64) -> u64 {
+        self.next() % n
+    }
+
+    fn chance(&mut self, one_in: u64) -> bool {
+        self.below(one_in) == 0
+    }
+}
+
+/// A job of a generated graph. Each job publishes an output when it runs successfully.
+#[derive(Clone, Debug)]
+struct JobSpec {
+    priority: JobPriority,
+    transient_bytes: u64,
+    /// The jobs whose outputs this job needs, every one of them.
+    all_of: Vec<usize>,
+    /// The jobs whose outputs this job can use instead of each other, if any.
+    any_of: Vec<usize>,
+    fails: bool,
+}
+
+fn label(job: usize) -> JobOutputLabel {
+    JobOutputLabel(LABELS[job])
+}
+
+/// Picks up to `max` distinct jobs to depend on. In acyclic graphs, jobs only depend
+/// on earlier jobs.
+fn random_dependencies(
+    rng: &mut Rng,
+    index: usize,
+    len: usize,
+    acyclic: bool,
+    max: u64,
+) -> Vec<usize> {
+    let candidates = match acyclic {
+        true => index,
+        false => len,
+    } as u64;
+    if candidates == 0 {
+        return Vec::new();
+    }
+
+    let mut dependencies = (0..rng.below(max + 1))
+        .map(|_| rng.below(candidates) as usize)
+        .collect::<Vec<_>>();
+    dependencies.sort_unstable();
+    dependencies.dedup();
+    dependencies
+}
+
+/// Generates a job graph, where jobs may depend on several outputs of other jobs, and
+/// on any one of a few others.
+fn random_graph(rng: &mut Rng, acyclic: bool, failures: bool) -> Vec<JobSpec> {
+    let len = 1 + rng.below(LABELS.len() as u64) as usize;
+    (0..len)
+        .map(|index| {
+            let all_of = random_dependencies(rng, index, len, acyclic, 2);
+            let any_of = match rng.chance(4) {
+                true => random_dependencies(rng, index, len, acyclic, 3),
+                false => Vec::new(),
+            };
+            let priority = match rng.chance(16) {
+                true => Priority::Critical,
+                false => Priority::NonCritical(NonZero::new(1 + rng.below(8) as u32).unwrap()),
+            };
+
+            JobSpec {
+                priority: JobPriority(priority),
+                transient_bytes: rng.below(4) * 256,
+                all_of,
+                any_of,
+                fails: failures && rng.chance(8),
+            }
+        })
+        .collect()
+}
+
+/// Generates a graph where each job depends on the output of at most one earlier job,
+/// which is what a [`JobQueueSnapshot`] can describe.
+fn random_chain_graph(rng: &mut Rng) -> Vec<JobSpec> {
+    random_graph(rng, true, false)
+        .into_iter()
+        .map(|mut job| {
+            job.all_of.truncate(1);
+            job.any_of.clear();
+            job
+        })
+        .collect()
+}
+
+/// Captures a graph from [`random_chain_graph`] as it would be queued in the render
+/// world before its first frame, where only the jobs without a dependency are ready.
+fn snapshot(jobs: &[JobSpec]) -> JobQueueSnapshot {
+    let jobs = jobs
+        .iter()
+        .enumerate()
+        .map(|(index, job)| JobSnapshot {
+            label: format!("SimJob{index}"),
+            priority: job.priority,
+            transient_bytes: job.transient_bytes,
+            exclusive: false,
+            ready: job.all_of.is_empty(),
+            inputs: job
+                .all_of
+                .iter()
+                .map(|_| {
+                    (
+                        type_name::<JobDependency>().to_string(),
+                        JobInputStatus::Wait,
+                    )
+                })
+                .collect(),
+            waited_frames: 0,
+            next_chunk: None,
+            output: Some(LABELS[index].to_string()),
+            dependency: job
+                .all_of
+                .first()
+                .map(|dependency| LABELS[*dependency].to_string()),
+        })
+        .collect();
+    JobQueueSnapshot { jobs }
+}
+
+fn random_settings(rng: &mut Rng) -> JobExecutionSettings {
+    JobExecutionSettings {
+        max_jobs_per_frame: 1 + rng.below(4) as u32,
+        max_transient_bytes: rng.chance(2).then(|| 256 + rng.below(3) * 256),
+        dependency_priority: match rng.below(3) {
+            0 => JobDependencyPriority::Off,
+            1 => JobDependencyPriority::Dependents,
+            _ => JobDependencyPriority::Subtree,
+        },
+        done_retention_frames: rng.below(3) as u32,
+        ..Default::default()
+    }
+}
+
+/// A job of the simulated graph in the render world, by its index.
+#[derive(Clone, Component)]
+struct SimJob(usize);
+
+impl GraphicsJob for SimJob {
+    type In = (AllOf, AnyOf);
+
+    fn run(
+        &self,
+        _world: &World,
+        _context: &mut JobRunContext,
+        _dependencies: JobInputItem<Self, Self::In>,
+    ) -> Result<(), JobError> {
+        unreachable!("simulated jobs are executed by `execute_jobs`")
+    }
+}
+
+/// The dependencies of a [`SimJob`], as given by its [`JobSpec`].
+#[derive(Component)]
+struct SimDependencies {
+    all_of: Vec<usize>,
+    any_of: Vec<usize>,
+}
+
+/// The jobs that ran successfully, and published their output.
+#[derive(Resource, Default)]
+struct Published(HashSet<usize>);
+
+/// An input waiting on every output in [`SimDependencies::all_of`].
+struct AllOf;
+
+impl JobInput<SimJob> for AllOf {
+    type Data = Read<SimDependencies>;
+
+    type Item<'a> = ();
+
+    fn status(dependencies: QueryItem<Self::Data>, world: &World) -> JobInputStatus {
+        let published = &world.resource::<Published>().0;
+        match dependencies
+            .all_of
+            .iter()
+            .all(|job| published.contains(job))
+        {
+            true => JobInputStatus::Ready,
+            false => JobInputStatus::Wait,
+        }
+    }
+
+    fn get<'a>(_dependencies: QueryItem<'a, Self::Data>, _world: &'a World) -> Self::Item<'a> {}
+}
+
+/// An input waiting on any one output in [`SimDependencies::any_of`], if there are any.
+struct AnyOf;
+
+impl JobInput<SimJob> for AnyOf {
+    type Data = Read<SimDependencies>;
+
+    type Item<'a> = ();
+
+    fn status(dependencies: QueryItem<Self::Data>, world: &World) -> JobInputStatus {
+        let published = &world.resource::<Published>().0;
+        let any_published = dependencies
+            .any_of
+            .iter()
+            .any(|job| published.contains(job));
+        match dependencies.any_of.is_empty() || any_published {
+            true => JobInputStatus::Ready,
+            false => JobInputStatus::Wait,
+        }
+    }
+
+    fn get<'a>(_dependencies: QueryItem<'a, Self::Data>, _world: &'a World) -> Self::Item<'a> {}
+}
+
+/// The graph being simulated, and the jobs admitted in each frame that admitted any.
+#[derive(Resource)]
+struct Graph {
+    jobs: Vec<JobSpec>,
+    admitted: Vec<Vec<usize>>,
+}
+
+/// Stands in for [`run_jobs`](super::run_jobs), picking the jobs to run with the same
+/// [`schedule_jobs`], and completing them without recording anything. Every invariant
+/// of admitted jobs is checked here.
+fn execute_jobs(
+    ready: Query<(Entity, &MainEntity, &SimJob), With<JobReady>>,
+    pending: Query<&SimJob, With<JobMarker>>,
+    exec_settings: Res<JobExecutionSettings>,
+    job_result_sender: Res<JobResultSender>,
+    mut published: ResMut<Published>,
+    mut graph: ResMut<Graph>,
+    mut frame: Local<u32>,
+) {
+    let jobs = &graph.jobs;
+    let mut ready = ready.iter().collect::<Vec<_>>();
+    // in the order they were spawned, like a replay of a snapshot
+    ready.sort_by_key(|(.., job)| job.0);
+
+    let dependents = pending.iter().flat_map(|job| {
+        let spec = &jobs[job.0];
+        spec.all_of
+            .iter()
+            .chain(&spec.any_of)
+            .map(move |dependency| (label(*dependency), Some(label(job.0))))
+    });
+    let admitted = schedule_jobs(
+        ready.into_iter().map(|(entity, main_entity, job)| {
+            let schedule = JobSchedule {
+                priority: jobs[job.0].priority,
+                transient_bytes: jobs[job.0].transient_bytes,
+                exclusive: false,
+                output: Some(label(job.0)),
+            };
+            ((entity, *main_entity, job.0), schedule)
+        }),
+        dependents,
+        None,
+        &exec_settings,
+        *frame,
+    )
+    .collect::<Vec<_>>();
+    *frame += 1;
+
+    // no job runs before the outputs it depends on are published
+    for (.., job) in &admitted {
+        let spec = &jobs[*job];
+        assert!(
+            spec.all_of.iter().all(|job| published.0.contains(job)),
+            "job {job} ran before all of its dependencies"
+        );
+        assert!(
+            spec.any_of.is_empty() || spec.any_of.iter().any(|job| published.0.contains(job)),
+            "job {job} ran before any of its dependencies"
+        );
+    }
+
+    // only critical jobs may exceed the frame's budgets
+    let non_critical = admitted
+        .iter()
+        .filter(|(.., job)| !jobs[*job].priority.is_critical())
+        .count();
+    assert!(non_critical <= exec_settings.max_jobs_per_frame as usize);
+    if let Some(max_transient_bytes) = exec_settings.max_transient_bytes {
+        let bytes = admitted
+            .iter()
+            .map(|(.., job)| jobs[*job].transient_bytes)
+            .sum::<u64>();
+        let critical = admitted.len() - non_critical;
+        // an oversized job may run on its own, alongside jobs that don't use memory
+        let using_memory = admitted
+            .iter()
+            .filter(|(.., job)| jobs[*job].transient_bytes > 0)
+            .count();
+        assert!(bytes <= max_transient_bytes || using_memory == 1 || critical > 0);
+    }
+
+    for (entity, main_entity, job) in &admitted {
+        let result = match jobs[*job].fails {
+            true => Err(JobError::ExecutionFailed),
+            false => Ok(()),
+        };
+        if result.is_ok() {
+            published.0.insert(*job);
+        }
+        exec_settings.on_invariant_violation.send(
+            &job_result_sender.0,
+            JobResult {
+                entity: *entity,
+                main_entity: Some(*main_entity),
+                result,
+                value: None,
+                submission: None,
+            },
+        );
+    }
+
+    if !admitted.is_empty() {
+        let admitted = admitted.into_iter().map(|(.., job)| job).collect();
+        graph.admitted.push(admitted);
+    }
+}
+
+/// How a simulation cancels jobs partway through.
+#[derive(Copy, Clone, Default)]
+struct Cancellation {
+    /// Cancels the jobs of [`JobTask`] `0` with [`cancel_task`] on the given frame.
+    task_on_frame: Option<u32>,
+    /// Cancels a job as if its [`CancelOnTargetDespawn`](crate::meta::CancelOnTargetDespawn)
+    /// target was despawned on the given frame.
+    orphan: Option<(usize, u32)>,
+}
+
+impl Cancellation {
+    fn random(rng: &mut Rng, jobs: &[JobSpec]) -> Self {
+        Self {
+            task_on_frame: rng.chance(2).then(|| rng.below(8) as u32),
+            orphan: rng
+                .chance(2)
+                .then(|| (rng.below(jobs.len() as u64) as usize, rng.below(8) as u32)),
+        }
+    }
+}
+
+/// The task of a job, so that [`Cancellation::task_on_frame`] cancels about half of them.
+fn task(job: usize) -> JobTask {
+    JobTask(job as u32 % 2)
+}
+
+struct Outcome {
+    results: Vec<Result<(), JobError>>,
+    /// The frame in which each job completed in the main world.
+    completed_on: Vec<u32>,
+    /// The jobs admitted in each frame that admitted any.
+    admitted: Vec<Vec<usize>>,
+    frames: u32,
+}
+
+/// Runs frames until every job has completed, checking that each completes exactly
+/// once, and that every entity of the graph is gone from both worlds once done jobs
+/// are no longer retained.
+fn simulate(
+    jobs: &[JobSpec],
+    settings: JobExecutionSettings,
+    cancellation: Cancellation,
+    max_frames: u32,
+) -> Outcome {
+    let (result_sender, result_receiver) = crossbeam_channel::unbounded();
+    let (main_sender, main_receiver) = crossbeam_channel::unbounded();
+    let (ready_sender, _ready_receiver) = crossbeam_channel::unbounded();
+
+    let mut main_world = World::new();
+    main_world.insert_resource(JobResultMainWorldReceiver(main_receiver));
+    main_world.insert_resource(settings);
+    main_world.init_resource::<JobResults>();
+    main_world.init_resource::<JobSubmissions>();
+    main_world.init_resource::<Completions>();
+    main_world.add_observer(record_completion);
+    let main_entities = (0..jobs.len())
+        .map(|job| main_world.spawn((JobMarker, task(job))).id())
+        .collect::<Vec<_>>();
+    let mut main_schedule = Schedule::default();
+    main_schedule.add_systems((sync_completed_jobs_main_world, despawn_done_jobs).chain());
+
+    let mut render_world = World::new();
+    render_world.insert_resource(settings);
+    render_world.insert_resource(JobResultSender(result_sender));
+    render_world.insert_resource(JobResultReceiver(result_receiver));
+    render_world.insert_resource(JobResultMainWorldSender(main_sender));
+    render_world.insert_resource(JobReadyMainWorldSender(ready_sender));
+    render_world.init_resource::<Published>();
+    render_world.insert_resource(Graph {
+        jobs: jobs.to_vec(),
+        admitted: Vec::new(),
+    });
+    // the render world systems of the plugin, in the order of their sets
+    let mut render_schedule = Schedule::default();
+    render_schedule.add_systems(
+        (
+            setup_time_out_frames,
+            cancel_orphaned_jobs,
+            check_job_inputs,
+            time_out_jobs,
+            execute_jobs,
+            increment_time_out_frames,
+            sync_completed_jobs,
+        )
+            .chain(),
+    );
+
+    let mut render_entities = vec![None; jobs.len()];
+    let mut results = vec![None; jobs.len()];
+    let mut completed_on = vec![0; jobs.len()];
+    let mut drained_on = None;
+
+    for frame in 0..max_frames {
+        if cancellation.task_on_frame == Some(frame) {
+            cancel_task(JobTask(0)).apply(&mut main_world);
+        }
+        main_schedule.run(&mut main_world);
+        record_completions(
+            &mut main_world,
+            &main_entities,
+            frame,
+            &mut results,
+            &mut completed_on,
+        );
+
+        if let Some(drained_on) = drained_on {
+            // done jobs are despawned once they've been retained, along with their render entities
+            if frame > drained_on + settings.done_retention_frames + 1 {
+                assert_eq!(
+                    main_world
+                        .query_filtered::<Entity, Without<Observer>>()
+                        .iter(&main_world)
+                        .count(),
+                    0,
+                    "main world entities leaked"
+                );
+                assert_eq!(
+                    render_world.iter_entities().count(),
+                    0,
+                    "render world entities leaked"
+                );
+                return Outcome {
+                    results: results.into_iter().map(Option::unwrap).collect(),
+                    completed_on,
+                    admitted: render_world.remove_resource::<Graph>().unwrap().admitted,
+                    frames: drained_on,
+                };
+            }
+        } else if results.iter().all(Option::is_some) {
+            drained_on = Some(frame);
+        }
+
+        sync_render_entities(
+            &main_world,
+            &mut render_world,
+            &main_entities,
+            &mut render_entities,
+            jobs,
+        );
+        if let Some((job, _)) = cancellation.orphan.filter(|(_, on)| *on == frame) {
+            // as extracted when the target is gone
+            if let Some(mut entity) =
+                render_entities[job].and_then(|entity| render_world.get_entity_mut(entity).ok())
+            {
+                entity.insert(JobTargetDespawned);
+            }
+        }
+        render_schedule.run(&mut render_world);
+    }
+
+    panic!("jobs didn't drain within {max_frames} frames");
+}
+
+/// Every [`JobComplete`] triggered in the main world, however the job completed.
+#[derive(Resource, Default)]
+struct Completions(Vec<JobCompletion>);
+
+fn record_completion(trigger: Trigger<JobComplete>, mut completions: ResMut<Completions>) {
+    completions.0.push((trigger.entity(), trigger.event().0));
+}
+
+/// Takes the completions of this frame, checking that no job completes twice.
+fn record_completions(
+    main_world: &mut World,
+    main_entities: &[Entity],
+    frame: u32,
+    results: &mut [Option<Result<(), JobError>>],
+    completed_on: &mut [u32],
+) {
+    for (entity, result) in main_world.resource_mut::<Completions>().0.drain(..) {
+        let job = main_entities
+            .iter()
+            .position(|main_entity| *main_entity == entity)
+            .unwrap();
+        assert!(results[job].is_none(), "job {job} completed twice");
+        results[job] = Some(result);
+        completed_on[job] = frame;
+    }
+}
+
+/// Mirrors pending jobs into the render world, as `SyncComponentPlugin<JobMarker>` and
+/// the plugin's extraction do: a render entity is spawned with its job when the job is
+/// spawned, and despawned once the job is no longer pending.
+fn sync_render_entities(
+    main_world: &World,
+    render_world: &mut World,
+    main_entities: &[Entity],
+    render_entities: &mut [Option<Entity>],
+    jobs: &[JobSpec],
+) {
+    for (job, main_entity) in main_entities.iter().enumerate() {
+        let pending = main_world
+            .get_entity(*main_entity)
+            .is_ok_and(|entity| entity.contains::<JobMarker>());
+        match render_entities[job] {
+            None if pending => {
+                let spec = &jobs[job];
+                let entity = render_world.spawn((
+                    JobMarker,
+                    MainEntity::from(*main_entity),
+                    SimJob(job),
+                    DynamicJob::new::<SimJob>(),
+                    spec.priority,
+                    JobTransientMemory(spec.transient_bytes),
+                    SimDependencies {
+                        all_of: spec.all_of.clone(),
+                        any_of: spec.any_of.clone(),
+                    },
+                ));
+                render_entities[job] = Some(entity.id());
+            }
+            Some(entity) if !pending => {
+                if let Ok(entity) = render_world.get_entity_mut(entity) {
+                    entity.despawn();
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Returns whether a job can never run, because the outputs it depends on are never
+/// published, since their jobs fail, or can't run themselves.
+fn blocked(jobs: &[JobSpec], job: usize) -> bool {
+    // the jobs that publish their output, found by publishing outputs until no more can be
+    let mut published = HashSet::<usize>::default();
+    loop {
+        let newly_published = (0..jobs.len())
+            .filter(|job| !published.contains(job) && !jobs[*job].fails)
+            .filter(|job| !blocked_by(&jobs[*job], &published))
+            .collect::<Vec<_>>();
+        if newly_published.is_empty() {
+            return blocked_by(&jobs[job], &published);
+        }
+        published.extend(newly_published);
+    }
+}
+
+fn blocked_by(job: &JobSpec, published: &HashSet<usize>) -> bool {
+    !job.all_of.iter().all(|job| published.contains(job))
+        || !(job.any_of.is_empty() || job.any_of.iter().any(|job| published.contains(job)))
+}
+
+#[test]
+fn acyclic_graphs_always_drain() {
+    for seed in 0..CASES {
+        let mut rng = Rng::new(seed);
+        let jobs = random_graph(&mut rng, true, false);
+        let settings = JobExecutionSettings {
+            time_out_frames: u32::MAX,
+            ..random_settings(&mut rng)
+        };
+
+        // at least one ready job runs each frame, and completes in the main world the next
+        let max_frames = jobs.len() as u32 + settings.done_retention_frames + 4;
+        let outcome = simulate(&jobs, settings, Cancellation::default(), max_frames);
+        assert!(
+            outcome.results.iter().all(Result::is_ok),
+            "seed {seed} didn't complete every job: {jobs:?}"
+        );
+        assert!(outcome.frames <= jobs.len() as u32 + 1);
+    }
+}
+
+#[test]
+fn blocked_graphs_drain_by_timing_out() {
+    for seed in 0..CASES {
+        let mut rng = Rng::new(seed);
+        let jobs = random_graph(&mut rng, false, true);
+        let settings = random_settings(&mut rng);
+
+        // every job has either run or timed out once it has waited for `time_out_frames`
+        let max_frames = settings.time_out_frames + settings.done_retention_frames + 6;
+        let outcome = simulate(&jobs, settings, Cancellation::default(), max_frames);
+        for (job, result) in outcome.results.iter().enumerate() {
+            if blocked(&jobs, job) {
+                assert_eq!(
+                    *result,
+                    Err(JobError::TimedOut),
+                    "seed {seed}: blocked job {job} didn't time out: {jobs:?}"
+                );
+            }
+        }
+    }
+}
+
+#[test]
+fn cancelled_graphs_drain() {
+    for seed in 0..CASES {
+        let mut rng = Rng::new(seed);
+        let acyclic = rng.chance(2);
+        let jobs = random_graph(&mut rng, acyclic, true);
+        let settings = random_settings(&mut rng);
+        let cancellation = Cancellation::random(&mut rng, &jobs);
+
+        let max_frames = settings.time_out_frames + settings.done_retention_frames + 6;
+        let outcome = simulate(&jobs, settings, cancellation, max_frames);
+        for (job, result) in outcome.results.iter().enumerate() {
+            let completed_on = outcome.completed_on[job];
+            // jobs still pending when their task is cancelled complete as cancelled, even
+            // if they ran in the meantime
+            let cancelled_with_task = task(job) == JobTask(0)
+                && cancellation
+                    .task_on_frame
+                    .is_some_and(|frame| completed_on >= frame);
+            // orphaned jobs are cancelled in the render world, and complete the frame after
+            let orphaned = cancellation
+                .orphan
+                .is_some_and(|(orphan, frame)| orphan == job && completed_on > frame);
+            if cancelled_with_task || orphaned {
+                assert_eq!(
+                    *result,
+                    Err(JobError::Cancelled),
+                    "seed {seed}: job {job} wasn't cancelled: {jobs:?}"
+                );
+            }
+        }
+    }
+}
+
+#[test]
+fn replays_match_simulation() {
+    for seed in 0..CASES {
+        let mut rng = Rng::new(seed);
+        let jobs = random_chain_graph(&mut rng);
+        let settings = JobExecutionSettings {
+            time_out_frames: u32::MAX,
+            ..random_settings(&mut rng)
+        };
+
+        let max_frames = jobs.len() as u32 + settings.done_retention_frames + 4;
+        let outcome = simulate(&jobs, settings, Cancellation::default(), max_frames);
+        let snapshot = snapshot(&jobs);
+        let replay = snapshot.replay(&settings);
+        assert_eq!(
+            replay.frames, outcome.admitted,
+            "seed {seed}: replay diverged from {snapshot:?}"
+        );
+        assert!(replay.stalled.is_empty());
+    }
+}
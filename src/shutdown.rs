@@ -0,0 +1,199 @@
+use bevy_app::AppExit;
+use bevy_ecs::{
+    entity::Entity,
+    event::Events,
+    query::With,
+    system::{Commands, Local, Query, Res, ResMut},
+};
+use bevy_utils::tracing::warn;
+
+use crate::{meta::JobMarker, JobExecutionSettings};
+
+/// The maximum number of frames [`JobShutdownPolicy::Flush`] delays exiting for.
+const MAX_FLUSH_FRAMES: u32 = 1024;
+
+/// Describes what happens to jobs that are still pending when an [`AppExit`] event
+/// is sent, including jobs spawned in the same frame, for example a final bake spawned
+/// by an exit handler.
+#[derive(Copy, Clone, Default, PartialEq, Eq, Debug)]
+pub enum JobShutdownPolicy {
+    /// Despawns pending jobs with a warning, and exits right away.
+    #[default]
+    Drop,
+    /// Delays exiting until every pending job has completed or failed, for at most
+    /// 1024 frames. Jobs still waiting on their inputs eventually fail by timing out,
+    /// so exiting is delayed by at most [`JobExecutionSettings::time_out_frames`]
+    /// frames after the last job starts waiting.
+    Flush,
+}
+
+/// An exit held back by [`JobShutdownPolicy::Flush`] until pending jobs are done.
+#[derive(Default)]
+pub(crate) struct DeferredAppExit {
+    exit: Option<AppExit>,
+    frames: u32,
+}
+
+/// Prefers the first error, like [`App::should_exit`](bevy_app::App::should_exit).
+fn merge_exits(first: AppExit, second: AppExit) -> AppExit {
+    if first.is_error() {
+        first
+    } else {
+        second
+    }
+}
+
+pub(crate) fn handle_app_exit(
+    mut exits: ResMut<Events<AppExit>>,
+    mut deferred: Local<DeferredAppExit>,
+    settings: Res<JobExecutionSettings>,
+    jobs: Query<Entity, With<JobMarker>>,
+    mut commands: Commands,
+) {
+    if deferred.exit.is_none() {
+        if exits.is_empty() || jobs.is_empty() {
+            return;
+        }
+
+        match settings.on_exit {
+            JobShutdownPolicy::Drop => {
+                warn!(
+                    "dropping {} graphics jobs that were pending on exit",
+                    jobs.iter().len()
+                );
+                for job in &jobs {
+                    commands.entity(job).despawn();
+                }
+                return;
+            }
+            JobShutdownPolicy::Flush => {}
+        }
+    }
+
+    // exits sent while flushing are merged into the held back one
+    let exit = exits
+        .drain()
+        .fold(deferred.exit.take(), |exit, next| match exit {
+            Some(exit) => Some(merge_exits(exit, next)),
+            None => Some(next),
+        })
+        .expect("an exit should be pending by this point");
+
+    if jobs.is_empty() {
+        exits.send(exit);
+        *deferred = DeferredAppExit::default();
+    } else if deferred.frames >= MAX_FLUSH_FRAMES {
+        warn!(
+            "dropping {} graphics jobs that didn't complete within {MAX_FLUSH_FRAMES} frames of exiting",
+            jobs.iter().len()
+        );
+        for job in &jobs {
+            commands.entity(job).despawn();
+        }
+        exits.send(exit);
+        *deferred = DeferredAppExit::default();
+    } else {
+        deferred.exit = Some(exit);
+        deferred.frames += 1;
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    };
+
+    use bevy_app::{App, AppExit, Last, Update};
+    use bevy_ecs::{
+        component::Component,
+        entity::Entity,
+        event::EventWriter,
+        observer::Trigger,
+        system::{Commands, Local, Query},
+    };
+
+    use crate::{meta::JobMarker, JobComplete, JobExecutionSettings};
+
+    use super::{handle_app_exit, JobShutdownPolicy};
+
+    /// Stands in for a job that completes after a few frames.
+    #[derive(Component)]
+    struct SlowJob(u32);
+
+    fn complete_slow_jobs(mut jobs: Query<(Entity, &mut SlowJob)>, mut commands: Commands) {
+        for (entity, mut job) in &mut jobs {
+            if job.0 == 0 {
+                commands.trigger_targets(JobComplete(Ok(())), entity);
+                commands.entity(entity).despawn();
+            } else {
+                job.0 -= 1;
+            }
+        }
+    }
+
+    fn app_with_final_bake(on_exit: JobShutdownPolicy, completed: Arc<AtomicBool>) -> App {
+        let mut app = App::new();
+        app.insert_resource(JobExecutionSettings {
+            on_exit,
+            ..Default::default()
+        })
+        .add_systems(Update, complete_slow_jobs)
+        .add_systems(Last, handle_app_exit);
+
+        // an exit handler that spawns a final bake as the app exits
+        app.add_systems(
+            Update,
+            move |mut exited: Local<bool>,
+                  mut exits: EventWriter<AppExit>,
+                  mut commands: Commands| {
+                if !*exited {
+                    *exited = true;
+                    exits.send(AppExit::Success);
+                    let completed = completed.clone();
+                    commands.spawn((SlowJob(3), JobMarker)).observe(
+                        move |_trigger: Trigger<JobComplete>| {
+                            completed.store(true, Ordering::Relaxed);
+                        },
+                    );
+                }
+            },
+        );
+        app
+    }
+
+    #[test]
+    fn flushes_jobs_spawned_on_exit() {
+        let completed = Arc::new(AtomicBool::new(false));
+        let mut app = app_with_final_bake(JobShutdownPolicy::Flush, completed.clone());
+
+        let mut frames = 0;
+        while app.should_exit().is_none() {
+            app.update();
+            frames += 1;
+            assert!(frames < 16);
+        }
+
+        assert!(completed.load(Ordering::Relaxed));
+        assert!(frames > 1);
+        assert_eq!(app.should_exit(), Some(AppExit::Success));
+    }
+
+    #[test]
+    fn drops_jobs_spawned_on_exit() {
+        let completed = Arc::new(AtomicBool::new(false));
+        let mut app = app_with_final_bake(JobShutdownPolicy::Drop, completed.clone());
+
+        app.update();
+        assert_eq!(app.should_exit(), Some(AppExit::Success));
+        assert!(!completed.load(Ordering::Relaxed));
+        assert_eq!(
+            app.world_mut()
+                .query::<&SlowJob>()
+                .iter(app.world())
+                .count(),
+            0
+        );
+    }
+}
@@ -0,0 +1,278 @@
+use core::any::type_name;
+
+use bevy_ecs::{
+    entity::Entity,
+    world::{EntityRef, World},
+};
+use bevy_utils::HashSet;
+
+use crate::{
+    input::{JobDependency, JobInputStatus, JobOutput},
+    meta::{JobExclusive, JobPriority, JobTransientMemory},
+    runner::{
+        job_input_statuses, schedule_jobs, DynamicJob, JobChunkProgress, JobReady, JobSchedule,
+        TimeOutFrames,
+    },
+    JobExecutionSettings,
+};
+
+/// A copy of the render world's job queue, for debugging scheduling issues that are
+/// hard to reproduce, like a job that intermittently never runs.
+///
+/// Capture it with [`capture`](Self::capture), and [`replay`](Self::replay) it in a
+/// test or a fresh app to step through the scheduler's decisions from the captured
+/// state. With the `serialize` feature, snapshots can be saved with `serde`, for
+/// example to keep a failing case as a regression test.
+#[derive(Clone, PartialEq, Eq, Default, Debug)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub struct JobQueueSnapshot {
+    /// Every job in the queue, ordered by entity. A job's index here is its stable id,
+    /// since entities vary between runs.
+    pub jobs: Vec<JobSnapshot>,
+}
+
+/// The scheduling state of a job in a [`JobQueueSnapshot`].
+#[derive(Clone, PartialEq, Eq, Debug)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub struct JobSnapshot {
+    /// The job's [`label`](crate::GraphicsJob::label).
+    pub label: String,
+    /// The job's priority, after any [`JobPriorityClamp`](crate::meta::JobPriorityClamp).
+    pub priority: JobPriority,
+    /// The job's [`JobTransientMemory`], or zero.
+    pub transient_bytes: u64,
+    /// Whether the job is [`JobExclusive`].
+    pub exclusive: bool,
+    /// Whether every input of the job was ready.
+    pub ready: bool,
+    /// The status of each of the job's inputs, labeled by the input's type name.
+    pub inputs: Vec<(String, JobInputStatus)>,
+    /// The number of frames the job has waited, which it times out after.
+    pub waited_frames: u32,
+    /// The chunk the job resumes at, if it yielded in an earlier frame.
+    pub next_chunk: Option<u32>,
+    /// The label of the job's [`JobOutput`], if it publishes one.
+    pub output: Option<String>,
+    /// The label of the job's [`JobDependency`], if it consumes one.
+    pub dependency: Option<String>,
+}
+
+impl JobSnapshot {
+    fn capture(world: &World, entity: EntityRef) -> Option<Self> {
+        let job = entity.get::<DynamicJob>()?;
+        Some(Self {
+            label: job.label().to_string(),
+            priority: entity.get::<JobPriority>().copied().unwrap_or_default(),
+            transient_bytes: entity
+                .get::<JobTransientMemory>()
+                .map_or(0, |memory| memory.0),
+            exclusive: entity.contains::<JobExclusive>(),
+            ready: entity.contains::<JobReady>(),
+            inputs: job_input_statuses(world, entity.id())
+                .into_iter()
+                .map(|(input, status)| (input.to_string(), status))
+                .collect(),
+            waited_frames: entity.get::<TimeOutFrames>().map_or(0, |frames| frames.0),
+            next_chunk: entity.get::<JobChunkProgress>().map(|progress| progress.0),
+            output: entity
+                .get::<JobOutput>()
+                .map(|output| output.label.0.to_string()),
+            dependency: entity
+                .get::<JobDependency>()
+                .map(|dependency| dependency.0 .0.to_string()),
+        })
+    }
+
+    /// Whether the job is ready in a replay, given the outputs published so far.
+    ///
+    /// A job that wasn't ready becomes ready once its dependency is published, if that
+    /// was the only input it was waiting on. Its other inputs are assumed to stay as
+    /// they were captured.
+    fn ready(&self, published: &HashSet<&str>) -> bool {
+        let dependency = type_name::<JobDependency>();
+        let published_dependency = self
+            .dependency
+            .as_deref()
+            .is_some_and(|label| published.contains(label));
+        self.ready
+            || (published_dependency
+                && self
+                    .inputs
+                    .iter()
+                    .all(|(input, status)| *status == JobInputStatus::Ready || input == dependency))
+    }
+}
+
+/// The scheduler's decisions when replaying a [`JobQueueSnapshot`].
+#[derive(Clone, PartialEq, Eq, Default, Debug)]
+pub struct JobReplay {
+    /// The jobs admitted in each frame, as indices into [`JobQueueSnapshot::jobs`],
+    /// in the order they run.
+    pub frames: Vec<Vec<usize>>,
+    /// The jobs that never ran, because they wait on inputs that never become ready.
+    pub stalled: Vec<usize>,
+}
+
+impl JobQueueSnapshot {
+    /// Captures the job queue of the render world.
+    ///
+    /// To capture the jobs that are about to run, capture between [`JobSet::Check`](crate::JobSet::Check)
+    /// and [`JobSet::Execute`](crate::JobSet::Execute). Like [`job_input_statuses`], this
+    /// must run on the render thread for jobs with non-send inputs.
+    pub fn capture(world: &mut World) -> Self {
+        let mut entities = world
+            .query::<(Entity, &DynamicJob)>()
+            .iter(world)
+            .map(|(entity, _)| entity)
+            .collect::<Vec<_>>();
+        entities.sort();
+
+        let world = &*world;
+        let jobs = entities
+            .into_iter()
+            .filter_map(|entity| JobSnapshot::capture(world, world.entity(entity)))
+            .collect();
+        Self { jobs }
+    }
+
+    /// Replays the scheduler frame by frame from the captured state, with the same
+    /// admission and priority logic as the render world, until no more jobs can run.
+    ///
+    /// Every admitted job is assumed to complete in the frame it runs and to publish its
+    /// output, if it has one. Jobs aren't timed out, so jobs that would eventually
+    /// time out are reported as [`stalled`](JobReplay::stalled) instead.
+    pub fn replay(&self, settings: &JobExecutionSettings) -> JobReplay {
+        let mut pending = (0..self.jobs.len()).collect::<Vec<_>>();
+        let mut published = HashSet::<&str>::default();
+        let mut frames = Vec::new();
+
+        loop {
+            let ready = pending
+                .iter()
+                .copied()
+                .filter(|job| self.jobs[*job].ready(&published))
+                .map(|job| {
+                    let snapshot = &self.jobs[job];
+                    let schedule = JobSchedule {
+                        priority: snapshot.priority,
+                        transient_bytes: snapshot.transient_bytes,
+                        exclusive: snapshot.exclusive,
+                        output: snapshot.output.as_deref(),
+                    };
+                    (job, schedule)
+                })
+                .collect::<Vec<_>>();
+            let dependents = pending.iter().filter_map(|job| {
+                let snapshot = &self.jobs[*job];
+                Some((snapshot.dependency.as_deref()?, snapshot.output.as_deref()))
+            });
+
+            let frame = frames.len() as u32;
+            let admitted =
+                schedule_jobs(ready, dependents, None, settings, frame).collect::<Vec<_>>();
+            if admitted.is_empty() {
+                break;
+            }
+
+            for job in &admitted {
+                if let Some(output) = &self.jobs[*job].output {
+                    published.insert(output);
+                }
+            }
+            pending.retain(|job| !admitted.contains(job));
+            frames.push(admitted);
+        }
+
+        JobReplay {
+            frames,
+            stalled: pending,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use bevy_ecs::{component::Component, world::World};
+
+    use crate::{
+        input::{
+            JobDependency, JobInputItem, JobInputStatus, JobOutput, JobOutputLifetime, JobOutputs,
+        },
+        meta::JobPriority,
+        runner::{DynamicJob, JobReady},
+        GraphicsJob, JobError, JobExecutionSettings, JobRunContext,
+    };
+
+    use super::JobQueueSnapshot;
+
+    #[derive(Clone, Component)]
+    struct ProduceJob;
+
+    impl GraphicsJob for ProduceJob {
+        type In = JobOutput;
+
+        fn run(
+            &self,
+            _world: &World,
+            _context: &mut JobRunContext,
+            _output: JobInputItem<Self, Self::In>,
+        ) -> Result<(), JobError> {
+            Ok(())
+        }
+    }
+
+    #[derive(Clone, Component)]
+    struct ConsumeJob;
+
+    impl GraphicsJob for ConsumeJob {
+        type In = JobDependency;
+
+        fn run(
+            &self,
+            _world: &World,
+            _context: &mut JobRunContext,
+            _dependency: JobInputItem<Self, Self::In>,
+        ) -> Result<(), JobError> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn replays_reproduce_stalls() {
+        let mut world = World::new();
+        world.init_resource::<JobOutputs>();
+        world.spawn((
+            ConsumeJob,
+            DynamicJob::new::<ConsumeJob>(),
+            JobPriority::non_critical::<4>(),
+            JobDependency::new("lightmap"),
+        ));
+        // nothing publishes the output this job waits on
+        world.spawn((
+            ConsumeJob,
+            DynamicJob::new::<ConsumeJob>(),
+            JobPriority::critical(),
+            JobDependency::new("irradiance"),
+        ));
+        world.spawn((
+            ProduceJob,
+            DynamicJob::new::<ProduceJob>(),
+            JobPriority::default(),
+            JobOutput::new("lightmap", JobOutputLifetime::UntilDependentsDone),
+            JobReady,
+        ));
+
+        let snapshot = JobQueueSnapshot::capture(&mut world);
+        assert_eq!(snapshot.jobs.len(), 3);
+        assert_eq!(snapshot.jobs[1].label, "ConsumeJob");
+        assert_eq!(
+            snapshot.jobs[1].inputs[0].1,
+            JobInputStatus::Wait,
+            "the dependency isn't published yet"
+        );
+
+        let replay = snapshot.replay(&JobExecutionSettings::default());
+        assert_eq!(replay.frames, vec![vec![2], vec![0]]);
+        assert_eq!(replay.stalled, vec![1]);
+    }
+}
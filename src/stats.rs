@@ -0,0 +1,368 @@
+use core::{any::TypeId, num::NonZero, time::Duration};
+
+use bevy_core::FrameCount;
+use bevy_ecs::{
+    archetype::Archetype,
+    entity::EntityHashMap,
+    observer::Trigger,
+    system::{Query, Res, ResMut, Resource},
+    world::{OnAdd, OnRemove},
+};
+use bevy_utils::HashMap;
+use disqualified::ShortName;
+
+use crate::{
+    meta::JobMarker,
+    registry::{JobType, RegisteredJobs},
+    GraphicsJob, JobComplete, OnJobReady,
+};
+
+/// How long [`JobStats`] aggregate over before they're reset.
+#[derive(Copy, Clone, Default, PartialEq, Eq, Debug)]
+pub enum JobStatsWindow {
+    /// Statistics are kept for the whole session, until [`JobStats::reset`] is called.
+    #[default]
+    Session,
+    /// Statistics are reset every given number of frames, for reporting recent
+    /// behavior, like the statistics of the last second on a dashboard.
+    Frames(NonZero<u32>),
+}
+
+/// Rolling statistics of every job type that ran, keyed by the job's [`TypeId`], for
+/// performance dashboards and regression alerts.
+///
+/// Averages are exponential moving averages, weighted by [`smoothing`](Self::smoothing),
+/// so no samples are stored. GPU times are only measured with the `diagnostics` feature
+/// and bevy's `RenderDiagnosticsPlugin`, see [`JobTypeStats::gpu_time`].
+#[derive(Resource, Debug)]
+pub struct JobStats {
+    /// How long statistics aggregate over before they're reset.
+    pub window: JobStatsWindow,
+    /// The weight of each new sample in the averages, from 0 to 1. Higher values follow
+    /// recent samples more closely, lower values are steadier.
+    pub smoothing: f64,
+    stats: HashMap<TypeId, JobTypeStats>,
+    /// The frame each pending job was spawned on, or released on if it was deferred.
+    spawned: EntityHashMap<u32>,
+    window_start: u32,
+}
+
+impl Default for JobStats {
+    fn default() -> Self {
+        Self::new(JobStatsWindow::default())
+    }
+}
+
+impl JobStats {
+    /// Creates empty statistics aggregating over the given window.
+    pub fn new(window: JobStatsWindow) -> Self {
+        Self {
+            window,
+            smoothing: 0.1,
+            stats: HashMap::default(),
+            spawned: EntityHashMap::default(),
+            window_start: 0,
+        }
+    }
+
+    /// Sets the weight of each new sample in the averages.
+    pub fn with_smoothing(mut self, smoothing: f64) -> Self {
+        self.smoothing = smoothing;
+        self
+    }
+
+    /// Returns the statistics of the given job type, if any job of that type ran in
+    /// the current window.
+    pub fn get<J: GraphicsJob>(&self) -> Option<&JobTypeStats> {
+        self.get_by_id(TypeId::of::<J>())
+    }
+
+    /// Returns the statistics of the job type with the given [`TypeId`].
+    pub fn get_by_id(&self, type_id: TypeId) -> Option<&JobTypeStats> {
+        self.stats.get(&type_id)
+    }
+
+    /// Iterates over the statistics of every job type.
+    pub fn iter(&self) -> impl Iterator<Item = (TypeId, &JobTypeStats)> {
+        self.stats.iter().map(|(type_id, stats)| (*type_id, stats))
+    }
+
+    /// Forgets the statistics of every job type.
+    pub fn reset(&mut self) {
+        self.stats.clear();
+    }
+
+    fn entry(&mut self, job_type: JobType) -> &mut JobTypeStats {
+        self.stats
+            .entry(job_type.type_id)
+            .or_insert_with(|| JobTypeStats::new(job_type.label))
+    }
+}
+
+/// The statistics of a single job type, see [`JobStats`].
+#[derive(Clone, Debug)]
+pub struct JobTypeStats {
+    /// The job type's [`GraphicsJob::label`].
+    pub label: ShortName<'static>,
+    /// The number of jobs of this type that completed successfully.
+    pub completed: u64,
+    /// The number of jobs of this type that failed, including those that were cancelled
+    /// or timed out.
+    pub failed: u64,
+    /// The GPU time of a single job of this type, or `None` if none was measured.
+    pub gpu_time: Option<JobTimeStats>,
+    /// The average number of frames jobs of this type waited for their inputs, from
+    /// being spawned until they were ready, or `None` if none was ready yet. Readiness
+    /// is mirrored from the render world a frame late, which is included.
+    pub frames_to_ready: Option<f64>,
+}
+
+impl JobTypeStats {
+    fn new(label: ShortName<'static>) -> Self {
+        Self {
+            label,
+            completed: 0,
+            failed: 0,
+            gpu_time: None,
+            frames_to_ready: None,
+        }
+    }
+}
+
+/// The minimum, maximum and average of a job type's GPU time.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct JobTimeStats {
+    pub min: Duration,
+    pub max: Duration,
+    pub average: Duration,
+}
+
+impl JobTimeStats {
+    #[cfg_attr(not(feature = "diagnostics"), allow(dead_code))]
+    fn add(stats: &mut Option<Self>, sample: Duration, smoothing: f64) {
+        *stats = Some(match *stats {
+            None => Self {
+                min: sample,
+                max: sample,
+                average: sample,
+            },
+            Some(stats) => Self {
+                min: stats.min.min(sample),
+                max: stats.max.max(sample),
+                average: Duration::from_secs_f64(moving_average(
+                    Some(stats.average.as_secs_f64()),
+                    sample.as_secs_f64(),
+                    smoothing,
+                )),
+            },
+        });
+    }
+}
+
+fn moving_average(average: Option<f64>, sample: f64, smoothing: f64) -> f64 {
+    match average {
+        Some(average) => average + (sample - average) * smoothing.clamp(0.0, 1.0),
+        None => sample,
+    }
+}
+
+fn frame(frame_count: Option<Res<FrameCount>>) -> u32 {
+    frame_count.map_or(0, |frame_count| frame_count.0)
+}
+
+pub(crate) fn track_spawned_job(
+    trigger: Trigger<OnAdd, JobMarker>,
+    frame_count: Option<Res<FrameCount>>,
+    mut stats: ResMut<JobStats>,
+) {
+    stats.spawned.insert(trigger.entity(), frame(frame_count));
+}
+
+pub(crate) fn forget_spawned_job(
+    trigger: Trigger<OnRemove, JobMarker>,
+    mut stats: ResMut<JobStats>,
+) {
+    stats.spawned.remove(&trigger.entity());
+}
+
+/// Records how long a job waited for its inputs, the first time it's ready.
+pub(crate) fn record_job_ready_stats(
+    trigger: Trigger<OnJobReady>,
+    jobs: Query<&Archetype>,
+    registered: Res<RegisteredJobs>,
+    frame_count: Option<Res<FrameCount>>,
+    mut stats: ResMut<JobStats>,
+) {
+    let job = trigger.entity();
+    let Some(spawned) = stats.spawned.remove(&job) else {
+        return;
+    };
+    let Some(job_type) = jobs
+        .get(job)
+        .ok()
+        .and_then(|archetype| registered.job_type(archetype))
+    else {
+        return;
+    };
+
+    let frames = frame(frame_count).saturating_sub(spawned) as f64;
+    let smoothing = stats.smoothing;
+    let job_stats = stats.entry(job_type);
+    job_stats.frames_to_ready = Some(moving_average(job_stats.frames_to_ready, frames, smoothing));
+}
+
+/// Counts completed and failed jobs.
+pub(crate) fn record_job_completion_stats(
+    trigger: Trigger<JobComplete>,
+    jobs: Query<&Archetype>,
+    registered: Res<RegisteredJobs>,
+    mut stats: ResMut<JobStats>,
+) {
+    let Some(job_type) = jobs
+        .get(trigger.entity())
+        .ok()
+        .and_then(|archetype| registered.job_type(archetype))
+    else {
+        return;
+    };
+
+    let job_stats = stats.entry(job_type);
+    match trigger.event().0 {
+        Ok(()) => job_stats.completed += 1,
+        Err(_) => job_stats.failed += 1,
+    }
+}
+
+/// Resets the statistics once their [`JobStatsWindow`] is over.
+pub(crate) fn reset_job_stats_window(
+    frame_count: Option<Res<FrameCount>>,
+    mut stats: ResMut<JobStats>,
+) {
+    let JobStatsWindow::Frames(frames) = stats.window else {
+        return;
+    };
+
+    let frame = frame(frame_count);
+    if frame.wrapping_sub(stats.window_start) >= frames.get() {
+        stats.window_start = frame;
+        stats.reset();
+    }
+}
+
+/// Adds a measured GPU time of a single job of the given type.
+#[cfg(feature = "diagnostics")]
+pub(crate) fn record_job_gpu_time(stats: &mut JobStats, job_type: JobType, gpu_time: Duration) {
+    let smoothing = stats.smoothing;
+    JobTimeStats::add(&mut stats.entry(job_type).gpu_time, gpu_time, smoothing);
+}
+
+#[cfg(test)]
+mod test {
+    use core::{num::NonZero, time::Duration};
+
+    use bevy_app::App;
+    use bevy_core::FrameCount;
+    use bevy_ecs::{component::Component, system::RunSystemOnce, world::World};
+
+    use crate::{
+        input::JobInputItem, meta::JobMarker, registry::register_job_type, GraphicsJob,
+        JobComplete, JobError, JobRunContext, OnJobReady,
+    };
+
+    use super::{
+        forget_spawned_job, moving_average, record_job_completion_stats, record_job_ready_stats,
+        reset_job_stats_window, track_spawned_job, JobStats, JobStatsWindow, JobTimeStats,
+    };
+
+    #[derive(Clone, Component)]
+    struct StatsJob;
+
+    impl GraphicsJob for StatsJob {
+        type In = ();
+
+        fn run(
+            &self,
+            _world: &World,
+            _context: &mut JobRunContext,
+            (): JobInputItem<Self, Self::In>,
+        ) -> Result<(), JobError> {
+            Ok(())
+        }
+    }
+
+    fn app() -> App {
+        let mut app = App::new();
+        register_job_type::<StatsJob>(&mut app);
+        app.init_resource::<JobStats>()
+            .insert_resource(FrameCount(10))
+            .add_observer(track_spawned_job)
+            .add_observer(forget_spawned_job)
+            .add_observer(record_job_ready_stats)
+            .add_observer(record_job_completion_stats);
+        app
+    }
+
+    #[test]
+    fn averages_move_toward_new_samples() {
+        assert_eq!(moving_average(None, 4.0, 0.5), 4.0);
+        assert_eq!(moving_average(Some(4.0), 8.0, 0.5), 6.0);
+        assert_eq!(moving_average(Some(4.0), 8.0, 0.0), 4.0);
+
+        let mut gpu_time = None;
+        for millis in [4, 2, 8] {
+            JobTimeStats::add(&mut gpu_time, Duration::from_millis(millis), 0.5);
+        }
+        let gpu_time = gpu_time.unwrap();
+        assert_eq!(
+            (gpu_time.min, gpu_time.max),
+            (Duration::from_millis(2), Duration::from_millis(8))
+        );
+        assert!((gpu_time.average.as_secs_f64() - 0.0055).abs() < 1e-9);
+    }
+
+    #[test]
+    fn completions_and_readiness_are_counted_per_type() {
+        let mut app = app();
+        let world = app.world_mut();
+
+        let done = world.spawn((StatsJob, JobMarker)).id();
+        let failed = world.spawn((StatsJob, JobMarker)).id();
+        world.insert_resource(FrameCount(14));
+        world.trigger_targets(OnJobReady, done);
+        // only the first time a job is ready counts
+        world.insert_resource(FrameCount(20));
+        world.trigger_targets(OnJobReady, done);
+        world.trigger_targets(JobComplete(Ok(())), done);
+        world.trigger_targets(JobComplete(Err(JobError::TimedOut)), failed);
+
+        let stats = world.resource::<JobStats>();
+        let job_stats = stats.get::<StatsJob>().unwrap();
+        assert_eq!(job_stats.label.to_string(), "StatsJob");
+        assert_eq!((job_stats.completed, job_stats.failed), (1, 1));
+        assert_eq!(job_stats.frames_to_ready, Some(4.0));
+        assert_eq!(job_stats.gpu_time, None);
+    }
+
+    #[test]
+    fn windowed_stats_are_reset() {
+        let mut app = app();
+        let world = app.world_mut();
+        world.resource_mut::<JobStats>().window = JobStatsWindow::Frames(NonZero::new(5).unwrap());
+
+        let job = world.spawn((StatsJob, JobMarker)).id();
+        world.trigger_targets(JobComplete(Ok(())), job);
+
+        world.run_system_once(reset_job_stats_window).unwrap();
+        assert!(world.resource::<JobStats>().get::<StatsJob>().is_none());
+
+        let job = world.spawn((StatsJob, JobMarker)).id();
+        world.trigger_targets(JobComplete(Ok(())), job);
+        world.insert_resource(FrameCount(14));
+        world.run_system_once(reset_job_stats_window).unwrap();
+        assert!(world.resource::<JobStats>().get::<StatsJob>().is_some());
+
+        world.insert_resource(FrameCount(15));
+        world.run_system_once(reset_job_stats_window).unwrap();
+        assert!(world.resource::<JobStats>().get::<StatsJob>().is_none());
+    }
+}
@@ -0,0 +1,166 @@
+use bevy_ecs::{
+    entity::Entity,
+    query::Has,
+    system::{Query, SystemParam},
+};
+use bevy_render::sync_world::MainEntity;
+
+use crate::{
+    done::JobDone,
+    runner::{JobChunkProgress, JobReady},
+    GraphicsJob, JobError,
+};
+
+/// The status of a job, as seen from the world it's queried in.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum JobStatus {
+    /// In the main world, the job hasn't completed yet. Its progress is only
+    /// tracked in the render world.
+    Pending,
+    /// In the render world, the job is waiting on its inputs.
+    Waiting,
+    /// In the render world, the job's inputs are ready, and it's queued to run.
+    Ready,
+    /// In the render world, the job yielded partway through its work, and will
+    /// be resumed. See [`GraphicsJob::run_chunk`].
+    Running,
+    /// In the main world, the job completed with the given result, and is kept for
+    /// [`JobExecutionSettings::done_retention_frames`](crate::JobExecutionSettings::done_retention_frames).
+    Done(Result<(), JobError>),
+}
+
+/// A [`SystemParam`] for enumerating the jobs of type `J`, along with their status,
+/// for example to report the progress of outstanding bake jobs.
+///
+/// This can be used in both the main world and the render world. Since it's backed
+/// by a query on `J`, only the archetypes containing `J` are visited, rather than
+/// every entity.
+#[derive(SystemParam)]
+pub struct Jobs<'w, 's, J: GraphicsJob> {
+    jobs: Query<
+        'w,
+        's,
+        (
+            Entity,
+            &'static J,
+            Option<&'static JobDone>,
+            Has<JobReady>,
+            Has<JobChunkProgress>,
+            Has<MainEntity>,
+        ),
+    >,
+}
+
+impl<J: GraphicsJob> Jobs<'_, '_, J> {
+    /// Iterates over every job of type `J`.
+    pub fn iter(&self) -> impl Iterator<Item = (Entity, &J, JobStatus)> {
+        self.jobs
+            .iter()
+            .map(|(entity, job, done, ready, running, render_world)| {
+                let status = job_status(done, ready, running, render_world);
+                (entity, job, status)
+            })
+    }
+
+    /// Returns the job of type `J` on the given entity, if there is one.
+    pub fn get(&self, entity: Entity) -> Option<(&J, JobStatus)> {
+        let (_, job, done, ready, running, render_world) = self.jobs.get(entity).ok()?;
+        Some((job, job_status(done, ready, running, render_world)))
+    }
+
+    /// Returns the number of jobs of type `J`.
+    pub fn count(&self) -> usize {
+        self.jobs.iter().len()
+    }
+}
+
+fn job_status(done: Option<&JobDone>, ready: bool, running: bool, render_world: bool) -> JobStatus {
+    match done {
+        Some(done) => JobStatus::Done(done.result),
+        None if running => JobStatus::Running,
+        None if ready => JobStatus::Ready,
+        None if render_world => JobStatus::Waiting,
+        None => JobStatus::Pending,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use bevy_ecs::{component::Component, system::RunSystemOnce, world::World};
+    use bevy_render::sync_world::MainEntity;
+
+    use crate::{
+        done::JobDone,
+        input::JobInputItem,
+        runner::{JobChunkProgress, JobReady},
+        GraphicsJob, JobError, JobRunContext,
+    };
+
+    use super::{JobStatus, Jobs};
+
+    #[derive(Clone, Component)]
+    struct BakeJob;
+
+    #[derive(Clone, Component)]
+    struct OtherJob;
+
+    impl GraphicsJob for BakeJob {
+        type In = ();
+
+        fn run(
+            &self,
+            _world: &World,
+            _context: &mut JobRunContext,
+            (): JobInputItem<Self, Self::In>,
+        ) -> Result<(), JobError> {
+            Ok(())
+        }
+    }
+
+    impl GraphicsJob for OtherJob {
+        type In = ();
+
+        fn run(
+            &self,
+            _world: &World,
+            _context: &mut JobRunContext,
+            (): JobInputItem<Self, Self::In>,
+        ) -> Result<(), JobError> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn enumerates_jobs_of_one_type() {
+        let mut world = World::new();
+        let main_entity = MainEntity::from(world.spawn_empty().id());
+        let pending = world.spawn(BakeJob).id();
+        let done = world.spawn((BakeJob, JobDone::new(Ok(())))).id();
+        let waiting = world.spawn((BakeJob, main_entity)).id();
+        let ready = world.spawn((BakeJob, main_entity, JobReady)).id();
+        let running = world
+            .spawn((BakeJob, main_entity, JobReady, JobChunkProgress(1)))
+            .id();
+        world.spawn(OtherJob);
+
+        let mut statuses = world
+            .run_system_once(|jobs: Jobs<BakeJob>| {
+                jobs.iter()
+                    .map(|(entity, _, status)| (entity, status))
+                    .collect::<Vec<_>>()
+            })
+            .unwrap();
+        statuses.sort_by_key(|(entity, _)| *entity);
+
+        assert_eq!(
+            statuses,
+            vec![
+                (pending, JobStatus::Pending),
+                (done, JobStatus::Done(Ok(()))),
+                (waiting, JobStatus::Waiting),
+                (ready, JobStatus::Ready),
+                (running, JobStatus::Running),
+            ]
+        );
+    }
+}
@@ -0,0 +1,44 @@
+use bevy_ecs::{
+    entity::{Entities, Entity},
+    system::{ResMut, Resource},
+};
+use bevy_utils::HashMap;
+use wgpu::SubmissionIndex;
+
+/// The queue submission each completed job's commands were submitted in, keyed by the
+/// job's main world entity, for waiting on exactly the work of a job, for example with
+/// `Maintain::WaitForSubmissionIndex`, instead of on everything submitted in a frame.
+///
+/// Jobs that ran in the same frame usually share a submission, unless a job yielding
+/// with [`JobChunk::Yield`](crate::JobChunk::Yield) submitted the commands recorded
+/// before it. For a job that yielded, this is the submission holding its last chunk,
+/// which was submitted after the rest of its commands.
+///
+/// Only jobs that completed successfully have a submission.
+///
+/// Like [`JobResults`](crate::JobResults), submissions are stored before
+/// [`JobComplete`](crate::JobComplete) is triggered, and are kept for as long as the
+/// job's entity.
+#[derive(Resource, Default)]
+pub struct JobSubmissions(HashMap<Entity, SubmissionIndex>);
+
+impl JobSubmissions {
+    /// Returns the submission holding the given job's commands.
+    pub fn get(&self, job: Entity) -> Option<&SubmissionIndex> {
+        self.0.get(&job)
+    }
+
+    pub(crate) fn insert(&mut self, job: Entity, submission: SubmissionIndex) {
+        self.0.insert(job, submission);
+    }
+}
+
+/// Removes the submissions of jobs whose entity was despawned.
+pub(crate) fn clean_up_job_submissions(
+    mut submissions: ResMut<JobSubmissions>,
+    entities: &Entities,
+) {
+    if !submissions.0.is_empty() {
+        submissions.0.retain(|job, _| entities.contains(*job));
+    }
+}
@@ -0,0 +1,127 @@
+use bevy_ecs::{
+    entity::Entity,
+    query::With,
+    system::{Commands, In, Query, Res, RunSystemOnce},
+    world::{Command, World},
+};
+
+use crate::{
+    completion::{JobCompletionBatch, JobCompletionBatches, JobCompletionSender},
+    meta::{JobMarker, JobTask},
+    runner::complete_main_world_job,
+    JobCompletion, JobError, JobExecutionSettings,
+};
+
+/// Cancels every pending job of a [`JobTask`], for example to unwind a whole bake
+/// that's no longer needed. Queue it with [`Commands::queue`].
+///
+/// Each pending job completes right away with [`JobError::Cancelled`], like any other
+/// failed job: its [`JobComplete`](crate::JobComplete) and [`OnJobFailed`](crate::OnJobFailed)
+/// are triggered, completion channels are notified, and it's kept as
+/// [`JobDone`](crate::JobDone) for [`JobExecutionSettings::done_retention_frames`]. Jobs
+/// of the task that already completed keep their results. A job the render world has
+/// already recorded may still finish on the GPU, but its result is dropped, since the
+/// job is no longer pending.
+///
+/// Jobs of the task that depend on the outputs of cancelled jobs are cancelled along
+/// with them. Dependents in other tasks are handled the same way as the dependents of
+/// any failed producer: they keep waiting for another job to publish the output, and
+/// time out otherwise.
+pub fn cancel_task(task: JobTask) -> impl Command {
+    move |world: &mut World| {
+        // without the plugin's settings, there are no jobs to cancel
+        let _ = world.run_system_once_with(task, cancel_task_jobs);
+    }
+}
+
+fn cancel_task_jobs(
+    In(task): In<JobTask>,
+    jobs: Query<
+        (
+            Entity,
+            &JobTask,
+            Option<&JobCompletionSender>,
+            Option<&JobCompletionBatch>,
+        ),
+        With<JobMarker>,
+    >,
+    settings: Res<JobExecutionSettings>,
+    mut commands: Commands,
+) {
+    let mut batches = JobCompletionBatches::default();
+    for (entity, _, sender, batch) in jobs.iter().filter(|(_, job_task, ..)| **job_task == task) {
+        complete_main_world_job(
+            &mut commands,
+            &mut batches,
+            &settings,
+            (entity, Err(JobError::Cancelled)),
+            sender,
+            batch,
+        );
+    }
+    batches.send();
+}
+
+/// The combined status of the jobs of a [`JobTask`], from their [`JobCompletion`]s,
+/// like those returned by [`flush_task`](crate::RunGraphicsJobExt::flush_task).
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum JobTaskStatus {
+    /// Every job completed successfully.
+    Done,
+    /// At least one job failed with the given error, the first in the completions.
+    Failed(JobError),
+    /// The task was cancelled with [`cancel_task`], even if some of its jobs had
+    /// already completed or failed.
+    Cancelled,
+}
+
+impl JobTaskStatus {
+    /// Combines the results of a task's jobs.
+    pub fn from_completions<'a>(completions: impl IntoIterator<Item = &'a JobCompletion>) -> Self {
+        let mut status = Self::Done;
+        for (_, result) in completions {
+            match (status, result) {
+                (_, Err(JobError::Cancelled)) => return Self::Cancelled,
+                (Self::Done, Err(err)) => status = Self::Failed(*err),
+                _ => {}
+            }
+        }
+        status
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use bevy_ecs::world::World;
+
+    use crate::JobError;
+
+    use super::JobTaskStatus;
+
+    #[test]
+    fn task_status_combines_results() {
+        let world = World::new();
+        let job = world.entities().reserve_entity();
+        assert_eq!(
+            JobTaskStatus::from_completions(&[(job, Ok(())), (job, Ok(()))]),
+            JobTaskStatus::Done
+        );
+        assert_eq!(
+            JobTaskStatus::from_completions(&[
+                (job, Ok(())),
+                (job, Err(JobError::ExecutionFailed)),
+                (job, Err(JobError::TimedOut)),
+            ]),
+            JobTaskStatus::Failed(JobError::ExecutionFailed)
+        );
+        // jobs that completed before the task was cancelled don't change its status
+        assert_eq!(
+            JobTaskStatus::from_completions(&[
+                (job, Ok(())),
+                (job, Err(JobError::ExecutionFailed)),
+                (job, Err(JobError::Cancelled)),
+            ]),
+            JobTaskStatus::Cancelled
+        );
+    }
+}
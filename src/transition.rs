@@ -0,0 +1,318 @@
+use std::collections::VecDeque;
+
+use bevy_core::FrameCount;
+use bevy_ecs::{
+    entity::{Entity, EntityHashSet},
+    event::Event,
+    observer::Trigger,
+    system::{Commands, EntityCommands, IntoObserverSystem, Res, ResMut, Resource},
+    world::OnRemove,
+};
+use bevy_render::sync_world::MainEntity;
+use crossbeam_channel::{Receiver, Sender};
+
+use crate::{meta::JobMarker, JobComplete, JobError, JobExecutionSettings, JobStatus};
+
+/// Triggered on a job's main world entity once all of its inputs are ready, just
+/// before it's first scheduled to run. Jobs requeued after the device is lost, and
+/// [`JobInterval`](crate::meta::JobInterval) jobs queued again after each run, trigger
+/// this again once they're ready.
+#[derive(Event, Copy, Clone, Debug)]
+pub struct OnJobReady;
+
+/// Triggered on a job's main world entity when it completes successfully,
+/// alongside [`JobComplete`].
+#[derive(Event, Copy, Clone, Debug)]
+pub struct OnJobDone;
+
+/// Triggered on a job's main world entity when it fails, alongside
+/// [`JobComplete`].
+#[derive(Event, Copy, Clone, Debug)]
+pub struct OnJobFailed(pub JobError);
+
+/// An extension trait for observing a job's transitions when spawning it. Each method
+/// adds an observer to the job's main world entity, so it can read and write gameplay
+/// state. Transitions are mirrored from the render world, so observers run in the
+/// frame after the transition happened.
+pub trait ObserveJobExt {
+    fn on_job_ready<M>(
+        &mut self,
+        observer: impl IntoObserverSystem<OnJobReady, (), M>,
+    ) -> &mut Self;
+
+    fn on_job_done<M>(&mut self, observer: impl IntoObserverSystem<OnJobDone, (), M>) -> &mut Self;
+
+    fn on_job_failed<M>(
+        &mut self,
+        observer: impl IntoObserverSystem<OnJobFailed, (), M>,
+    ) -> &mut Self;
+}
+
+impl ObserveJobExt for EntityCommands<'_> {
+    fn on_job_ready<M>(
+        &mut self,
+        observer: impl IntoObserverSystem<OnJobReady, (), M>,
+    ) -> &mut Self {
+        self.observe(observer)
+    }
+
+    fn on_job_done<M>(&mut self, observer: impl IntoObserverSystem<OnJobDone, (), M>) -> &mut Self {
+        self.observe(observer)
+    }
+
+    fn on_job_failed<M>(
+        &mut self,
+        observer: impl IntoObserverSystem<OnJobFailed, (), M>,
+    ) -> &mut Self {
+        self.observe(observer)
+    }
+}
+
+#[derive(Resource)]
+pub(crate) struct JobReadyMainWorldSender(pub Sender<MainEntity>);
+#[derive(Resource)]
+pub(crate) struct JobReadyMainWorldReceiver(pub Receiver<MainEntity>);
+
+pub(crate) fn sync_ready_jobs_main_world(
+    receiver: Res<JobReadyMainWorldReceiver>,
+    mut commands: Commands,
+) {
+    while let Ok(main_entity) = receiver.0.try_recv() {
+        commands.trigger_targets(OnJobReady, main_entity.id());
+    }
+}
+
+/// Triggers [`OnJobDone`] or [`OnJobFailed`] for a completed job.
+pub(crate) fn trigger_job_result(
+    commands: &mut Commands,
+    entity: Entity,
+    result: Result<(), JobError>,
+) {
+    match result {
+        Ok(()) => commands.trigger_targets(OnJobDone, entity),
+        Err(err) => commands.trigger_targets(OnJobFailed(err), entity),
+    }
+}
+
+/// A single change of a job's [`JobStatus`], recorded in [`JobTransitions`].
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub struct JobTransition {
+    /// The main world [`FrameCount`] the transition was mirrored in.
+    pub frame: u32,
+    /// The job's main world entity.
+    pub job: Entity,
+    /// The job's status before the transition.
+    pub from: JobStatus,
+    /// The job's status after the transition.
+    pub to: JobStatus,
+}
+
+/// The most recent [`JobTransition`]s of every job, oldest first, for building a
+/// timeline of scheduling behavior without enabling verbose logging. At most
+/// [`JobExecutionSettings::transition_history`] transitions are kept, and the oldest
+/// are overwritten once it's full.
+///
+/// Like the events in this module, transitions are mirrored from the render world,
+/// so only the ones the main world sees are recorded: a job goes from
+/// [`Pending`](JobStatus::Pending) to [`Ready`](JobStatus::Ready) when its inputs are
+/// ready, and from either to [`Done`](JobStatus::Done) when it completes. Jobs that
+/// are queued again, like [`JobInterval`](crate::meta::JobInterval) jobs, go from
+/// `Ready` to `Ready`.
+#[derive(Resource, Default)]
+pub struct JobTransitions {
+    transitions: VecDeque<JobTransition>,
+    ready: EntityHashSet,
+}
+
+impl JobTransitions {
+    /// Iterates over the recorded transitions, oldest first.
+    pub fn iter(&self) -> impl DoubleEndedIterator<Item = &JobTransition> + ExactSizeIterator {
+        self.transitions.iter()
+    }
+
+    /// Iterates over the recorded transitions of a single job, oldest first.
+    pub fn of_job(&self, job: Entity) -> impl Iterator<Item = &JobTransition> {
+        self.transitions
+            .iter()
+            .filter(move |transition| transition.job == job)
+    }
+
+    /// Returns the number of recorded transitions.
+    pub fn len(&self) -> usize {
+        self.transitions.len()
+    }
+
+    /// Returns `true` if no transitions are recorded.
+    pub fn is_empty(&self) -> bool {
+        self.transitions.is_empty()
+    }
+
+    /// Forgets every recorded transition.
+    pub fn clear(&mut self) {
+        self.transitions.clear();
+    }
+
+    fn record(&mut self, capacity: usize, transition: JobTransition) {
+        if capacity == 0 {
+            return;
+        }
+        while self.transitions.len() >= capacity {
+            self.transitions.pop_front();
+        }
+        self.transitions.push_back(transition);
+    }
+}
+
+pub(crate) fn record_ready_transition(
+    trigger: Trigger<OnJobReady>,
+    settings: Res<JobExecutionSettings>,
+    frame_count: Option<Res<FrameCount>>,
+    mut transitions: ResMut<JobTransitions>,
+) {
+    let job = trigger.entity();
+    let from = match transitions.ready.insert(job) {
+        true => JobStatus::Pending,
+        false => JobStatus::Ready,
+    };
+    transitions.record(
+        settings.transition_history,
+        JobTransition {
+            frame: frame_count.map_or(0, |frame_count| frame_count.0),
+            job,
+            from,
+            to: JobStatus::Ready,
+        },
+    );
+}
+
+pub(crate) fn record_done_transition(
+    trigger: Trigger<JobComplete>,
+    settings: Res<JobExecutionSettings>,
+    frame_count: Option<Res<FrameCount>>,
+    mut transitions: ResMut<JobTransitions>,
+) {
+    let job = trigger.entity();
+    let from = match transitions.ready.remove(&job) {
+        true => JobStatus::Ready,
+        false => JobStatus::Pending,
+    };
+    transitions.record(
+        settings.transition_history,
+        JobTransition {
+            frame: frame_count.map_or(0, |frame_count| frame_count.0),
+            job,
+            from,
+            to: JobStatus::Done(trigger.event().0),
+        },
+    );
+}
+
+/// Forgets that a job was ready once it's no longer pending, including when it's
+/// despawned before completing.
+pub(crate) fn forget_ready_transition(
+    trigger: Trigger<OnRemove, JobMarker>,
+    mut transitions: ResMut<JobTransitions>,
+) {
+    transitions.ready.remove(&trigger.entity());
+}
+
+#[cfg(test)]
+mod test {
+    use bevy_core::FrameCount;
+    use bevy_ecs::{
+        observer::Trigger,
+        system::{Commands, ResMut, Resource, RunSystemOnce},
+        world::World,
+    };
+    use bevy_render::sync_world::MainEntity;
+
+    use crate::{meta::JobMarker, JobComplete, JobError, JobExecutionSettings, JobStatus};
+
+    use super::{
+        forget_ready_transition, record_done_transition, record_ready_transition,
+        sync_ready_jobs_main_world, JobReadyMainWorldReceiver, JobTransition, JobTransitions,
+        ObserveJobExt, OnJobReady,
+    };
+
+    #[derive(Resource, Default)]
+    struct ReadyCount(u32);
+
+    #[test]
+    fn ready_jobs_trigger_observers() {
+        let mut world = World::new();
+        let (sender, receiver) = crossbeam_channel::unbounded();
+        world.insert_resource(JobReadyMainWorldReceiver(receiver));
+        world.init_resource::<ReadyCount>();
+
+        let job = world
+            .run_system_once(|mut commands: Commands| {
+                commands
+                    .spawn_empty()
+                    .on_job_ready(
+                        |_trigger: Trigger<OnJobReady>, mut count: ResMut<ReadyCount>| {
+                            count.0 += 1;
+                        },
+                    )
+                    .id()
+            })
+            .unwrap();
+        sender.send(MainEntity::from(job)).unwrap();
+
+        world.run_system_once(sync_ready_jobs_main_world).unwrap();
+        assert_eq!(world.resource::<ReadyCount>().0, 1);
+    }
+
+    #[test]
+    fn transitions_overwrite_the_oldest_when_full() {
+        let mut world = World::new();
+        world.insert_resource(JobExecutionSettings {
+            transition_history: 3,
+            ..Default::default()
+        });
+        world.init_resource::<JobTransitions>();
+        world.insert_resource(FrameCount(7));
+        world.add_observer(record_ready_transition);
+        world.add_observer(record_done_transition);
+        world.add_observer(forget_ready_transition);
+
+        let ready = world.spawn(JobMarker).id();
+        let cancelled = world.spawn(JobMarker).id();
+        world.trigger_targets(OnJobReady, ready);
+        world.trigger_targets(JobComplete(Err(JobError::Cancelled)), cancelled);
+        world.trigger_targets(OnJobReady, ready);
+        world.trigger_targets(JobComplete(Ok(())), ready);
+
+        let transitions = world.resource::<JobTransitions>();
+        let transition = |job, from, to| JobTransition {
+            frame: 7,
+            job,
+            from,
+            to,
+        };
+        assert_eq!(
+            transitions.iter().copied().collect::<Vec<_>>(),
+            vec![
+                transition(
+                    cancelled,
+                    JobStatus::Pending,
+                    JobStatus::Done(Err(JobError::Cancelled))
+                ),
+                // the job was queued again before it completed
+                transition(ready, JobStatus::Ready, JobStatus::Ready),
+                transition(ready, JobStatus::Ready, JobStatus::Done(Ok(()))),
+            ]
+        );
+        assert_eq!(transitions.of_job(ready).count(), 2);
+
+        // a job despawned while ready is forgotten, even if its result arrives later
+        let despawned = world.spawn(JobMarker).id();
+        world.trigger_targets(OnJobReady, despawned);
+        world.despawn(despawned);
+        world.trigger_targets(JobComplete(Ok(())), despawned);
+        let last = world.resource::<JobTransitions>().iter().last().copied();
+        assert_eq!(
+            last.map(|transition| transition.from),
+            Some(JobStatus::Pending)
+        );
+    }
+}